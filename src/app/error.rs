@@ -0,0 +1,89 @@
+//! Typed failures shown in the status bar.
+//!
+//! Most messages are plain text, but failures the user may want to act on
+//! (retry a spawn, re-run a sync) are recorded as an [`AppError`] next to
+//! the message so the UI can tell what went wrong without parsing it.
+
+use std::fmt;
+use std::path::PathBuf;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AppError {
+    /// The session's program (`claude` or the shell) failed to start.
+    SpawnFailed {
+        program: &'static str,
+        reason: String,
+    },
+    /// A session's working directory was removed before it could start.
+    MissingCwd(PathBuf),
+    /// Creating the worktree for a new session failed.
+    Worktree(String),
+    /// `Ctrl+S` failed in one or more worktrees, one message each.
+    GitSync(Vec<String>),
+    /// A database read or write failed while doing `action`.
+    Db {
+        action: &'static str,
+        reason: String,
+    },
+    /// The session backend failed while doing `action`.
+    Backend {
+        action: &'static str,
+        reason: String,
+    },
+}
+
+impl fmt::Display for AppError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::SpawnFailed { program, reason } => {
+                write!(f, "Failed to start {program}: {reason}")
+            }
+            Self::MissingCwd(path) => {
+                write!(f, "Working directory no longer exists: {}", path.display())
+            }
+            Self::Worktree(reason) => write!(f, "Failed to create worktree: {reason}"),
+            Self::GitSync(errors) => write!(f, "Sync failed: {}", errors.join(", ")),
+            // The database error itself is logged; it means little to the user.
+            Self::Db { action, .. } => write!(f, "Failed to {action}"),
+            Self::Backend { action, reason } => write!(f, "Failed to {action}: {reason}"),
+        }
+    }
+}
+
+impl std::error::Error for AppError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_matches_status_bar_text() {
+        let cases = [
+            (
+                AppError::SpawnFailed {
+                    program: "claude",
+                    reason: "no tmux".to_string(),
+                },
+                "Failed to start claude: no tmux",
+            ),
+            (
+                AppError::MissingCwd(PathBuf::from("/gone")),
+                "Working directory no longer exists: /gone",
+            ),
+            (
+                AppError::GitSync(vec!["a: conflict".to_string(), "b: offline".to_string()]),
+                "Sync failed: a: conflict, b: offline",
+            ),
+            (
+                AppError::Db {
+                    action: "undo delete",
+                    reason: "database is locked".to_string(),
+                },
+                "Failed to undo delete",
+            ),
+        ];
+        for (error, text) in cases {
+            assert_eq!(error.to_string(), text);
+        }
+    }
+}
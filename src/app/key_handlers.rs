@@ -7,10 +7,16 @@
 
 use std::path::PathBuf;
 
-use crate::session::SessionConfig;
+use crate::session::SessionKind;
+use crate::ui::session_mode_modal;
 
+use super::keymap::Action;
 use super::mcp_editor_modal::McpEditorField;
-use super::{AddProjectField, App, EditProjectField, InputFocus, RoleEditorView, TerminalView};
+use super::settings::{CtrlCBehavior, WorktreeOnClose};
+use super::{
+    AddProjectField, App, EditProjectField, InputFocus, RoleEditorView, RoleFileAction, SearchStep,
+    TerminalView,
+};
 use crate::claude::input;
 use crate::paths;
 use crossterm::event::{KeyCode, KeyModifiers};
@@ -21,7 +27,7 @@ impl App {
     ///
     /// Routes key events to the appropriate handler based on:
     /// 1. Modal state (highest priority)
-    /// 2. Global keybindings (Ctrl+Q, Ctrl+N, etc.)
+    /// 2. Global keybindings from the keymap (Ctrl+Q, Ctrl+N, etc.)
     /// 3. Focus-based handlers (ProjectList, SessionList, Terminal)
     pub(crate) fn handle_key(&mut self, code: KeyCode, mods: KeyModifiers) {
         // Dismiss help overlay with Esc
@@ -32,12 +38,140 @@ impl App {
             return;
         }
 
+        // Quit confirmation captures all input; the quit key confirms too
+        if self.show_quit_modal {
+            match code {
+                KeyCode::Char('y') | KeyCode::Char('Y') | KeyCode::Enter => self.should_quit = true,
+                KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
+                    self.show_quit_modal = false
+                }
+                _ if self.keymap.resolve(code, mods) == Some(Action::Quit) => {
+                    self.should_quit = true
+                }
+                _ => {}
+            }
+            return;
+        }
+
+        // Session limit confirmation captures all input
+        if self.session_limit_prompt.is_some() {
+            match code {
+                KeyCode::Char('y') | KeyCode::Char('Y') | KeyCode::Enter => {
+                    self.confirm_session_limit()
+                }
+                KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
+                    self.session_limit_prompt = None
+                }
+                _ => {}
+            }
+            return;
+        }
+
         // Restore sessions modal captures all input
         if self.show_restore_sessions_modal {
             self.handle_restore_sessions_key(code);
             return;
         }
 
+        // Fuzzy switcher captures all input
+        if self.show_switcher {
+            self.handle_switcher_key(code);
+            return;
+        }
+
+        // Dirty-worktree close confirmation captures all input
+        if self.show_close_session_modal {
+            match code {
+                KeyCode::Char('y') | KeyCode::Char('Y') => self.confirm_close_session(false),
+                KeyCode::Char('k') | KeyCode::Char('K') => self.confirm_close_session(true),
+                KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
+                    self.cancel_close_session()
+                }
+                _ => {}
+            }
+            return;
+        }
+
+        // Pre-sync preview captures all input
+        if self.show_sync_preview_modal {
+            match code {
+                KeyCode::Char('y') | KeyCode::Char('Y') | KeyCode::Enter => {
+                    self.confirm_sync_preview()
+                }
+                KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
+                    self.cancel_sync_preview()
+                }
+                _ => {}
+            }
+            return;
+        }
+
+        // Close-all-sessions confirmation captures all input
+        if self.show_close_all_modal {
+            let on_close = self.settings.close.worktrees;
+            match code {
+                KeyCode::Char('y') | KeyCode::Char('Y') | KeyCode::Enter => {
+                    self.confirm_close_all_sessions(on_close == WorktreeOnClose::Keep)
+                }
+                KeyCode::Char('k') | KeyCode::Char('K') if on_close == WorktreeOnClose::Ask => {
+                    self.confirm_close_all_sessions(true)
+                }
+                KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
+                    self.show_close_all_modal = false
+                }
+                _ => {}
+            }
+            return;
+        }
+
+        // Rename session modal captures all input
+        if self.show_rename_session_modal {
+            self.handle_rename_session_key(code);
+            return;
+        }
+
+        // Session label modal captures all input
+        if self.show_session_label_modal {
+            self.handle_session_label_key(code);
+            return;
+        }
+
+        // Move-session project picker captures all input
+        if self.show_move_session_modal {
+            self.handle_move_session_key(code);
+            return;
+        }
+
+        // Extra directories editor captures all input
+        if self.show_session_dirs_modal {
+            self.handle_session_dirs_key(code);
+            return;
+        }
+
+        // Commit modal captures all input
+        if self.show_commit_modal {
+            self.handle_commit_key(code, mods);
+            return;
+        }
+
+        // Send prompt modal captures all input
+        if self.show_send_prompt_modal {
+            self.handle_send_prompt_key(code, mods);
+            return;
+        }
+
+        // Scrollback export prompt captures all input
+        if self.scrollback_export.is_some() {
+            self.handle_export_key(code);
+            return;
+        }
+
+        // Role import/export prompt captures all input
+        if self.role_file_action.is_some() {
+            self.handle_role_file_key(code);
+            return;
+        }
+
         // Repo selector modal captures all input
         if self.show_repo_selector {
             self.handle_repo_selector_key(code);
@@ -50,6 +184,12 @@ impl App {
             return;
         }
 
+        // Resume-by-id modal captures all input
+        if self.show_resume_session_modal {
+            self.handle_resume_session_key(code);
+            return;
+        }
+
         // Branch selector modal captures all input
         if self.show_branch_selector {
             self.handle_branch_selector_key(code);
@@ -98,6 +238,12 @@ impl App {
             return;
         }
 
+        // Clone URL prompt over the add-project modal
+        if self.add_project_clone_dest.is_some() {
+            self.handle_clone_repo_key(code);
+            return;
+        }
+
         // Add-project modal captures all input
         if self.show_add_project_modal {
             self.handle_add_project_key(code);
@@ -116,107 +262,57 @@ impl App {
             return;
         }
 
-        // Global keybindings (always active)
-        if mods.contains(KeyModifiers::CONTROL) {
+        // Terminal search prompt captures all input
+        if self
+            .terminal_search
+            .as_ref()
+            .is_some_and(|search| search.editing)
+        {
+            self.handle_terminal_search_key(code);
+            return;
+        }
+
+        // Project filter prompt captures all input
+        if self
+            .project_filter
+            .as_ref()
+            .is_some_and(|filter| filter.editing)
+        {
+            self.handle_project_filter_key(code);
+            return;
+        }
+
+        // Alt+1..9 jumps to the Nth session of the active project
+        if mods.contains(KeyModifiers::ALT) {
+            if let KeyCode::Char(c @ '1'..='9') = code {
+                self.jump_to_project_session(c as usize - '1' as usize);
+                return;
+            }
+        }
+
+        // Ctrl+Shift+J/K reorders the active session within its project.
+        // Checked before the keymap, which ignores Shift on letters.
+        if self.focus == InputFocus::SessionList
+            && mods.contains(KeyModifiers::CONTROL | KeyModifiers::SHIFT)
+        {
             match code {
-                KeyCode::Char('q') => {
-                    self.should_quit = true;
-                    return;
-                }
-                KeyCode::Char('n') => {
-                    if self.focus == InputFocus::ProjectList {
-                        self.show_add_project_modal = true;
-                        self.add_project_field = AddProjectField::Name;
-                    } else {
-                        self.spawn_session();
-                    }
-                    return;
-                }
-                KeyCode::Char('c') => {
-                    self.close_active_session();
-                    return;
-                }
-                KeyCode::Char('d') => match self.focus {
-                    InputFocus::SessionList => {
-                        self.close_active_session();
-                        return;
-                    }
-                    InputFocus::ProjectList => {
-                        self.show_delete_project_modal();
-                        return;
-                    }
-                    InputFocus::Terminal => {} // forward to PTY
-                },
-                KeyCode::Char('e') => {
-                    self.open_edit_project_modal();
-                    return;
-                }
-                KeyCode::Char('r') => {
-                    self.restart_active_session();
-                    return;
-                }
-                KeyCode::Char('s') => {
-                    self.start_sync();
-                    return;
-                }
-                KeyCode::Char('t') => {
-                    self.toggle_shell_view();
-                    return;
-                }
-                KeyCode::Char('z') => {
-                    if self.pending_delete.is_some() {
-                        self.undo_delete();
-                    }
+                KeyCode::Char('j' | 'J') => {
+                    self.move_active_session(1);
                     return;
                 }
-                KeyCode::Char('u') => {
-                    self.open_restore_sessions_modal();
-                    return;
-                }
-                // Vim navigation: h=left, j=down, k=up, l=cycle-right
-                KeyCode::Char('h') => {
-                    self.focus = InputFocus::ProjectList;
-                    return;
-                }
-                KeyCode::Char('j') => {
-                    if self.focus == InputFocus::ProjectList {
-                        self.switch_project_forward();
-                    } else {
-                        self.switch_session_forward();
-                    }
-                    return;
-                }
-                KeyCode::Char('k') => {
-                    if self.focus == InputFocus::ProjectList {
-                        self.switch_project_backward();
-                    } else {
-                        self.switch_session_backward();
-                    }
-                    return;
-                }
-                KeyCode::Char('l') => {
-                    self.focus = match self.focus {
-                        InputFocus::ProjectList => InputFocus::SessionList,
-                        InputFocus::SessionList => InputFocus::Terminal,
-                        InputFocus::Terminal => InputFocus::ProjectList,
-                    };
+                KeyCode::Char('k' | 'K') => {
+                    self.move_active_session(-1);
                     return;
                 }
                 _ => {}
             }
         }
 
-        // Function keys (work reliably in all terminals)
-        match code {
-            KeyCode::F(1) => {
-                self.show_help = true;
-                return;
-            }
-            KeyCode::F(2) => {
-                self.show_info_panel = !self.show_info_panel;
+        // Global keybindings (resolved through the keymap)
+        if let Some(action) = self.keymap.resolve(code, mods) {
+            if self.run_action(action, code, mods) {
                 return;
             }
-            _ => {}
         }
 
         match self.focus {
@@ -226,6 +322,96 @@ impl App {
         }
     }
 
+    /// Run a global keymap action. Returns `false` when the key should fall
+    /// through to the focused panel instead (e.g. forwarded to the PTY).
+    fn run_action(&mut self, action: Action, code: KeyCode, mods: KeyModifiers) -> bool {
+        match action {
+            Action::Quit => self.request_quit(),
+            Action::New => {
+                if self.focus == InputFocus::ProjectList {
+                    self.show_add_project_modal = true;
+                    self.add_project_field = AddProjectField::Name;
+                } else {
+                    self.spawn_session();
+                }
+            }
+            Action::CloseSession => {
+                let is_ctrl_c = code == KeyCode::Char('c') && mods.contains(KeyModifiers::CONTROL);
+                if is_ctrl_c
+                    && self.focus == InputFocus::Terminal
+                    && self.settings.terminal.ctrl_c == CtrlCBehavior::Forward
+                {
+                    // Forward to PTY (interrupts Claude). Remember it so the
+                    // exit a double Ctrl+C causes is not auto-restarted.
+                    if let Some(session) = self.sessions.get(self.active_index) {
+                        self.interrupted_at
+                            .insert(session.info.id, std::time::Instant::now());
+                    }
+                    return false;
+                }
+                self.close_active_session();
+            }
+            Action::CloseSessionAnywhere => self.close_active_session(),
+            Action::Delete => match self.focus {
+                InputFocus::SessionList => self.close_active_session(),
+                InputFocus::ProjectList => self.show_delete_project_modal(),
+                InputFocus::Terminal => return false, // forward to PTY
+            },
+            Action::EditProject => self.open_edit_project_modal(),
+            Action::RestartSession => self.restart_active_session(),
+            Action::SyncWorktrees => self.start_sync_preview(),
+            Action::PushBranch => self.start_push(),
+            Action::CommitWorktree => self.open_commit_modal(),
+            Action::ExportScrollback => self.open_export_modal(),
+            Action::ToggleShell => self.toggle_shell_view(),
+            Action::UndoDelete => {
+                if self.pending_delete.is_some() {
+                    self.undo_delete();
+                }
+            }
+            Action::RestoreSessions => self.open_restore_sessions_modal(),
+            Action::Switcher => self.open_switcher(),
+            Action::RenameSession => self.open_rename_session_modal(),
+            Action::FocusProjectList => self.focus = InputFocus::ProjectList,
+            Action::Next => {
+                if self.focus == InputFocus::ProjectList {
+                    self.switch_project_forward();
+                } else {
+                    self.switch_session_forward();
+                }
+            }
+            Action::Previous => {
+                if self.focus == InputFocus::ProjectList {
+                    self.switch_project_backward();
+                } else {
+                    self.switch_session_backward();
+                }
+            }
+            Action::CycleFocus => {
+                self.focus = match self.focus {
+                    InputFocus::ProjectList => InputFocus::SessionList,
+                    InputFocus::SessionList => InputFocus::Terminal,
+                    InputFocus::Terminal => InputFocus::ProjectList,
+                };
+            }
+            Action::Help => self.show_help = true,
+            Action::ToggleInfoPanel => self.set_info_panel(!self.info_panel_user_pref),
+            Action::CycleInfoPanel => self.cycle_info_panel_mode(),
+            Action::ShowLastSync => self.show_last_sync(),
+            Action::ToggleOutputLog => self.toggle_output_log(),
+            Action::ToggleSessionTabs => self.toggle_session_tabs(),
+            Action::ToggleWrap => self.toggle_wrap(),
+            Action::FilterSessions => self.cycle_session_filter(),
+            Action::CopyPath => {
+                if self.focus == InputFocus::Terminal {
+                    return false; // forward to PTY
+                }
+                self.copy_active_session_path();
+            }
+        }
+        true
+    }
+
     fn handle_project_list_key(&mut self, code: KeyCode) {
         match code {
             KeyCode::Char('j') | KeyCode::Down => {
@@ -237,10 +423,43 @@ impl App {
             KeyCode::Enter => {
                 self.focus = InputFocus::SessionList;
             }
+            KeyCode::Char('x') => self.open_close_all_modal(),
+            KeyCode::Char('/') => self.open_project_filter(),
+            KeyCode::Esc => self.close_project_filter(),
             _ => {}
         }
     }
 
+    /// Keys while typing a project filter. Changing the query re-filters
+    /// the list as you type; Up/Down move through the matches.
+    fn handle_project_filter_key(&mut self, code: KeyCode) {
+        let Some(filter) = self.project_filter.as_mut() else {
+            return;
+        };
+        let before = filter.query.value().to_string();
+        match code {
+            KeyCode::Esc => self.close_project_filter(),
+            KeyCode::Enter => self.submit_project_filter(),
+            KeyCode::Down => self.switch_project_forward(),
+            KeyCode::Up => self.switch_project_backward(),
+            KeyCode::Backspace => filter.query.backspace(),
+            KeyCode::Delete => filter.query.delete(),
+            KeyCode::Left => filter.query.move_left(),
+            KeyCode::Right => filter.query.move_right(),
+            KeyCode::Home => filter.query.home(),
+            KeyCode::End => filter.query.end(),
+            KeyCode::Char(c) => filter.query.insert(c),
+            _ => {}
+        }
+        if self
+            .project_filter
+            .as_ref()
+            .is_some_and(|filter| filter.query.value() != before)
+        {
+            self.refilter_projects();
+        }
+    }
+
     fn handle_session_list_key(&mut self, code: KeyCode) {
         match code {
             KeyCode::Char('j') | KeyCode::Down => {
@@ -252,6 +471,15 @@ impl App {
             KeyCode::Enter => {
                 self.focus = InputFocus::Terminal;
             }
+            KeyCode::Char('r') => self.open_rename_session_modal(),
+            KeyCode::Char('p') => self.toggle_pin_active_session(),
+            KeyCode::Char('t') => self.open_session_label_modal(),
+            KeyCode::Char('m') => self.open_move_session_modal(),
+            KeyCode::Char('o') => self.toggle_session_sort_by_age(),
+            KeyCode::Char('R') => self.forget_spawn_role(),
+            KeyCode::Char('D') => self.duplicate_active_session(),
+            KeyCode::Char('a') => self.open_session_dirs_modal(),
+            KeyCode::Char('s') => self.open_send_prompt_modal(),
             _ => {}
         }
     }
@@ -261,11 +489,11 @@ impl App {
         if mods.contains(KeyModifiers::SHIFT) {
             match code {
                 KeyCode::Up => {
-                    self.scroll_terminal_up(1);
+                    self.scroll_terminal_up(self.settings.scroll.line_step);
                     return;
                 }
                 KeyCode::Down => {
-                    self.scroll_terminal_down(1);
+                    self.scroll_terminal_down(self.settings.scroll.line_step);
                     return;
                 }
                 KeyCode::PageUp => {
@@ -282,6 +510,54 @@ impl App {
             }
         }
 
+        // Copy mode captures all remaining keys until yank or cancel
+        if self.terminal_selection.is_some() {
+            self.handle_terminal_selection_key(code);
+            return;
+        }
+
+        // Scrollback search: `/` opens the prompt while scrolled back, n/N
+        // cycle matches and Esc closes. Any other key ends the search.
+        // `v` starts copy mode from history (at the search match, if any).
+        let plain = !mods.intersects(KeyModifiers::CONTROL | KeyModifiers::ALT);
+        if code == KeyCode::Char('v')
+            && plain
+            && (self.terminal_search.is_some() || self.active_scrollback() > 0)
+        {
+            self.start_terminal_selection();
+            return;
+        }
+        if self.terminal_search.is_some() {
+            match code {
+                KeyCode::Char('n') if plain => {
+                    self.jump_terminal_search(SearchStep::Older);
+                    return;
+                }
+                KeyCode::Char('N') if plain => {
+                    self.jump_terminal_search(SearchStep::Newer);
+                    return;
+                }
+                KeyCode::Char('/') if plain => {
+                    self.open_terminal_search();
+                    return;
+                }
+                KeyCode::Esc => {
+                    self.close_terminal_search();
+                    return;
+                }
+                _ => self.close_terminal_search(),
+            }
+        } else if code == KeyCode::Char('/') && plain && self.active_scrollback() > 0 {
+            self.open_terminal_search();
+            return;
+        }
+
+        // End while scrolled up only returns to the bottom
+        if code == KeyCode::End && plain && self.active_scrollback() > 0 {
+            self.with_active_parser(|parser| parser.screen_mut().set_scrollback(0));
+            return;
+        }
+
         // Snap to bottom on any non-scroll key when scrolled up
         self.with_active_parser(|parser| {
             if parser.screen().scrollback() > 0 {
@@ -305,6 +581,38 @@ impl App {
         }
     }
 
+    fn handle_terminal_selection_key(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Esc | KeyCode::Char('v') => self.terminal_selection = None,
+            KeyCode::Char('y') | KeyCode::Enter => self.yank_terminal_selection(),
+            KeyCode::Char('h') | KeyCode::Left => self.move_terminal_selection(0, -1, None),
+            KeyCode::Char('l') | KeyCode::Right => self.move_terminal_selection(0, 1, None),
+            KeyCode::Char('k') | KeyCode::Up => self.move_terminal_selection(-1, 0, None),
+            KeyCode::Char('j') | KeyCode::Down => self.move_terminal_selection(1, 0, None),
+            KeyCode::Char('0') | KeyCode::Home => self.move_terminal_selection(0, 0, Some(false)),
+            KeyCode::Char('$') | KeyCode::End => self.move_terminal_selection(0, 0, Some(true)),
+            _ => {}
+        }
+    }
+
+    fn handle_terminal_search_key(&mut self, code: KeyCode) {
+        let Some(search) = self.terminal_search.as_mut() else {
+            return;
+        };
+        match code {
+            KeyCode::Esc => self.close_terminal_search(),
+            KeyCode::Enter => self.submit_terminal_search(),
+            KeyCode::Backspace => search.query.backspace(),
+            KeyCode::Delete => search.query.delete(),
+            KeyCode::Left => search.query.move_left(),
+            KeyCode::Right => search.query.move_right(),
+            KeyCode::Home => search.query.home(),
+            KeyCode::End => search.query.end(),
+            KeyCode::Char(c) => search.query.insert(c),
+            _ => {}
+        }
+    }
+
     fn handle_add_project_key(&mut self, code: KeyCode) {
         match self.add_project_field {
             AddProjectField::Name => self.handle_add_project_name_key(code),
@@ -442,6 +750,28 @@ impl App {
         self.add_project_repos.clear();
         self.add_project_repo_index = 0;
         self.add_project_path_suggestion = None;
+        self.add_project_clone_dest = None;
+        self.add_project_clone_url.clear();
+        self.add_project_clone_skipped.clear();
+    }
+
+    fn handle_clone_repo_key(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Esc => {
+                self.add_project_clone_dest = None;
+                self.add_project_clone_url.clear();
+            }
+            KeyCode::Enter => self.start_clone(),
+            KeyCode::Tab => self.skip_clone(),
+            KeyCode::Backspace => self.add_project_clone_url.backspace(),
+            KeyCode::Delete => self.add_project_clone_url.delete(),
+            KeyCode::Left => self.add_project_clone_url.move_left(),
+            KeyCode::Right => self.add_project_clone_url.move_right(),
+            KeyCode::Home => self.add_project_clone_url.home(),
+            KeyCode::End => self.add_project_clone_url.end(),
+            KeyCode::Char(c) => self.add_project_clone_url.insert(c),
+            _ => {}
+        }
     }
 
     fn handle_edit_project_key(&mut self, code: KeyCode) {
@@ -451,6 +781,12 @@ impl App {
             EditProjectField::RepoList => self.handle_edit_project_repo_list_key(code),
             EditProjectField::Roles => self.handle_edit_project_roles_key(code),
             EditProjectField::McpServers => self.handle_edit_project_mcp_servers_key(code),
+            EditProjectField::SyncStrategy => self.handle_edit_project_sync_strategy_key(code),
+            EditProjectField::ActivityTimeout => {
+                self.handle_edit_project_activity_timeout_key(code)
+            }
+            EditProjectField::WorktreeRoot => self.handle_edit_project_worktree_root_key(code),
+            EditProjectField::InitCommand => self.handle_edit_project_init_command_key(code),
         }
     }
 
@@ -461,7 +797,7 @@ impl App {
                 self.edit_project_field = EditProjectField::Path;
             }
             KeyCode::BackTab => {
-                self.edit_project_field = EditProjectField::McpServers;
+                self.edit_project_field = EditProjectField::InitCommand;
             }
             KeyCode::Enter => self.submit_edit_project(),
             KeyCode::Backspace => self.edit_project_name.backspace(),
@@ -593,14 +929,15 @@ impl App {
                     self.show_role_editor = true;
                 }
             }
-            KeyCode::Char('d') => {
-                if !self.role_editor_roles.is_empty() {
-                    self.role_editor_roles.remove(self.role_editor_list_index);
-                    if self.role_editor_list_index >= self.role_editor_roles.len()
-                        && self.role_editor_list_index > 0
-                    {
-                        self.role_editor_list_index -= 1;
-                    }
+            KeyCode::Char('s') => self.toggle_default_role(),
+            KeyCode::Char('x') => self.open_role_file_modal(RoleFileAction::Export),
+            KeyCode::Char('i') => self.open_role_file_modal(RoleFileAction::Import),
+            KeyCode::Char('d') if !self.role_editor_roles.is_empty() => {
+                self.role_editor_roles.remove(self.role_editor_list_index);
+                if self.role_editor_list_index >= self.role_editor_roles.len()
+                    && self.role_editor_list_index > 0
+                {
+                    self.role_editor_list_index -= 1;
                 }
             }
             _ => {}
@@ -656,6 +993,38 @@ impl App {
         }
     }
 
+    fn handle_switcher_key(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Esc => self.close_switcher(),
+            KeyCode::Enter => self.submit_switcher(),
+            KeyCode::Down => {
+                if self.switcher_index + 1 < self.switcher_matches.len() {
+                    self.switcher_index += 1;
+                }
+            }
+            KeyCode::Up => {
+                self.switcher_index = self.switcher_index.saturating_sub(1);
+            }
+            KeyCode::Backspace => {
+                self.switcher_input.backspace();
+                self.update_switcher_matches();
+            }
+            KeyCode::Delete => {
+                self.switcher_input.delete();
+                self.update_switcher_matches();
+            }
+            KeyCode::Left => self.switcher_input.move_left(),
+            KeyCode::Right => self.switcher_input.move_right(),
+            KeyCode::Home => self.switcher_input.home(),
+            KeyCode::End => self.switcher_input.end(),
+            KeyCode::Char(c) => {
+                self.switcher_input.insert(c);
+                self.update_switcher_matches();
+            }
+            _ => {}
+        }
+    }
+
     fn handle_restore_sessions_key(&mut self, code: KeyCode) {
         match code {
             KeyCode::Esc => {
@@ -692,6 +1061,22 @@ impl App {
         }
     }
 
+    fn handle_move_session_key(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Esc => self.close_move_session_modal(),
+            KeyCode::Char('j') | KeyCode::Down => {
+                if self.move_session_index + 1 < self.move_session_targets.len() {
+                    self.move_session_index += 1;
+                }
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.move_session_index = self.move_session_index.saturating_sub(1);
+            }
+            KeyCode::Enter => self.submit_move_session(),
+            _ => {}
+        }
+    }
+
     fn handle_repo_selector_key(&mut self, code: KeyCode) {
         let Some(project) = self.active_project() else {
             return;
@@ -727,31 +1112,35 @@ impl App {
                 self.show_session_mode_modal = false;
                 self.pending_repo_path = None;
                 self.pending_all_repos = None;
+                self.pending_spawn_mcp_servers.clear();
             }
+            KeyCode::Char('m') => self.open_spawn_mcp_editor(),
             KeyCode::Char('j') | KeyCode::Down => {
-                if self.session_mode_index == 0 {
-                    self.session_mode_index = 1;
+                if self.session_mode_index + 1 < session_mode_modal::MODES.len() {
+                    self.session_mode_index += 1;
                 }
             }
             KeyCode::Char('k') | KeyCode::Up => {
-                self.session_mode_index = 0;
+                self.session_mode_index = self.session_mode_index.saturating_sub(1);
             }
             KeyCode::Enter => {
                 self.show_session_mode_modal = false;
-                if self.session_mode_index == 0 {
+                // Normal, Worktree, Shell, Shell in worktree, Resume by id
+                let (kind, worktree) = match self.session_mode_index {
+                    0 => (SessionKind::Claude, false),
+                    1 => (SessionKind::Claude, true),
+                    2 => (SessionKind::Shell, false),
+                    3 => (SessionKind::Shell, true),
+                    _ => {
+                        self.open_resume_session_modal();
+                        return;
+                    }
+                };
+                self.pending_session_kind = kind;
+                if !worktree {
                     // Normal mode
-                    if let Some(all_repos) = self.pending_all_repos.take() {
-                        // Multi-repo project: use first repo as CWD, rest as add-dir
-                        self.pending_repo_path = None;
-                        let config = SessionConfig {
-                            cwd: Some(all_repos[0].clone()),
-                            additional_dirs: all_repos[1..].to_vec(),
-                            ..SessionConfig::default()
-                        };
+                    if let Some(config) = self.take_pending_repo_config() {
                         self.spawn_session_with_config(&config);
-                    } else if let Some(path) = self.pending_repo_path.take() {
-                        // Single-repo project
-                        self.spawn_session_in_repo(path);
                     }
                 } else {
                     // Worktree mode
@@ -791,6 +1180,10 @@ impl App {
     }
 
     fn handle_worktree_name_key(&mut self, code: KeyCode) {
+        // Any edit or cancel clears a previous validation error
+        if code != KeyCode::Enter {
+            self.worktree_name_error = None;
+        }
         match code {
             KeyCode::Esc => {
                 self.show_worktree_name_modal = false;
@@ -798,11 +1191,12 @@ impl App {
                 self.pending_base_branch = None;
                 self.pending_repo_path = None;
                 self.pending_all_repos = None;
+                self.pending_spawn_role = None;
             }
             KeyCode::Enter => {
                 let new_branch = self.worktree_name_input.value().trim().to_string();
-                if new_branch.is_empty() {
-                    self.set_error("Branch name cannot be empty");
+                if let Err(reason) = crate::git::validate_branch_name(&new_branch) {
+                    self.worktree_name_error = Some(reason);
                     return;
                 }
                 self.show_worktree_name_modal = false;
@@ -831,6 +1225,152 @@ impl App {
         }
     }
 
+    fn handle_export_key(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Esc => self.close_export_modal(),
+            KeyCode::Enter => self.submit_export(),
+            KeyCode::Backspace => self.export_path_input.backspace(),
+            KeyCode::Delete => self.export_path_input.delete(),
+            KeyCode::Left => self.export_path_input.move_left(),
+            KeyCode::Right => self.export_path_input.move_right(),
+            KeyCode::Home => self.export_path_input.home(),
+            KeyCode::End => self.export_path_input.end(),
+            KeyCode::Char(c) => self.export_path_input.insert(c),
+            _ => {}
+        }
+    }
+
+    fn handle_resume_session_key(&mut self, code: KeyCode) {
+        // Any edit clears a previous validation error
+        if code != KeyCode::Enter {
+            self.resume_session_error = None;
+        }
+        match code {
+            KeyCode::Esc => self.close_resume_session_modal(),
+            KeyCode::Enter => self.submit_resume_session(),
+            KeyCode::Backspace => self.resume_session_input.backspace(),
+            KeyCode::Delete => self.resume_session_input.delete(),
+            KeyCode::Left => self.resume_session_input.move_left(),
+            KeyCode::Right => self.resume_session_input.move_right(),
+            KeyCode::Home => self.resume_session_input.home(),
+            KeyCode::End => self.resume_session_input.end(),
+            KeyCode::Char(c) => self.resume_session_input.insert(c),
+            _ => {}
+        }
+    }
+
+    fn handle_role_file_key(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Esc => self.close_role_file_modal(),
+            KeyCode::Enter => self.submit_role_file(),
+            KeyCode::Backspace => self.role_file_path.backspace(),
+            KeyCode::Delete => self.role_file_path.delete(),
+            KeyCode::Left => self.role_file_path.move_left(),
+            KeyCode::Right => self.role_file_path.move_right(),
+            KeyCode::Home => self.role_file_path.home(),
+            KeyCode::End => self.role_file_path.end(),
+            KeyCode::Char(c) => self.role_file_path.insert(c),
+            _ => {}
+        }
+    }
+
+    fn handle_rename_session_key(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Esc => self.close_rename_session_modal(),
+            KeyCode::Enter => self.submit_rename_session(),
+            KeyCode::Backspace => self.rename_session_input.backspace(),
+            KeyCode::Delete => self.rename_session_input.delete(),
+            KeyCode::Left => self.rename_session_input.move_left(),
+            KeyCode::Right => self.rename_session_input.move_right(),
+            KeyCode::Home => self.rename_session_input.home(),
+            KeyCode::End => self.rename_session_input.end(),
+            KeyCode::Char(c) => self.rename_session_input.insert(c),
+            _ => {}
+        }
+    }
+
+    fn handle_session_label_key(&mut self, code: KeyCode) {
+        let input = if self.session_label_color_focused {
+            &mut self.session_label_color_input
+        } else {
+            &mut self.session_label_input
+        };
+        match code {
+            KeyCode::Esc => self.close_session_label_modal(),
+            KeyCode::Enter => self.submit_session_label(),
+            KeyCode::Tab | KeyCode::BackTab => {
+                self.session_label_color_focused = !self.session_label_color_focused;
+            }
+            KeyCode::Backspace => input.backspace(),
+            KeyCode::Delete => input.delete(),
+            KeyCode::Left => input.move_left(),
+            KeyCode::Right => input.move_right(),
+            KeyCode::Home => input.home(),
+            KeyCode::End => input.end(),
+            KeyCode::Char(c) => input.insert(c),
+            _ => {}
+        }
+    }
+
+    fn handle_session_dirs_key(&mut self, code: KeyCode) {
+        use crate::ui::role_editor_modal::ToolListMode;
+
+        if self.session_dirs.mode == ToolListMode::Adding {
+            if let Err(e) = handle_tool_list_adding_key(&mut self.session_dirs, code) {
+                self.set_error(e);
+            }
+            return;
+        }
+        match code {
+            KeyCode::Esc => self.close_session_dirs_modal(),
+            KeyCode::Enter => self.submit_session_dirs(),
+            KeyCode::Char('a') => self.session_dirs.start_adding(),
+            KeyCode::Char('d') => self.session_dirs.delete_selected(),
+            KeyCode::Char('j') | KeyCode::Down => self.session_dirs.move_down(),
+            KeyCode::Char('k') | KeyCode::Up => self.session_dirs.move_up(),
+            KeyCode::Char('J') => self.session_dirs.move_selected_down(),
+            KeyCode::Char('K') => self.session_dirs.move_selected_up(),
+            _ => {}
+        }
+    }
+
+    fn handle_commit_key(&mut self, code: KeyCode, mods: KeyModifiers) {
+        match code {
+            KeyCode::Esc => self.close_commit_modal(),
+            // Alt+Enter works everywhere; Shift+Enter needs the kitty protocol
+            KeyCode::Enter if mods.intersects(KeyModifiers::ALT | KeyModifiers::SHIFT) => {
+                self.commit_message_input.insert('\n')
+            }
+            KeyCode::Enter => self.submit_commit(),
+            KeyCode::Backspace => self.commit_message_input.backspace(),
+            KeyCode::Delete => self.commit_message_input.delete(),
+            KeyCode::Left => self.commit_message_input.move_left(),
+            KeyCode::Right => self.commit_message_input.move_right(),
+            KeyCode::Home => self.commit_message_input.home(),
+            KeyCode::End => self.commit_message_input.end(),
+            KeyCode::Char(c) => self.commit_message_input.insert(c),
+            _ => {}
+        }
+    }
+
+    fn handle_send_prompt_key(&mut self, code: KeyCode, mods: KeyModifiers) {
+        match code {
+            KeyCode::Esc => self.close_send_prompt_modal(),
+            KeyCode::Enter if mods.intersects(KeyModifiers::ALT | KeyModifiers::SHIFT) => {
+                self.send_prompt_input.insert('\n')
+            }
+            KeyCode::Enter => self.submit_send_prompt(),
+            KeyCode::Backspace => self.send_prompt_input.backspace(),
+            KeyCode::Delete => self.send_prompt_input.delete(),
+            KeyCode::Left => self.send_prompt_input.move_left(),
+            KeyCode::Right => self.send_prompt_input.move_right(),
+            KeyCode::Home => self.send_prompt_input.home(),
+            KeyCode::End => self.send_prompt_input.end(),
+            KeyCode::Char(c) => self.send_prompt_input.insert(c),
+            _ => {}
+        }
+    }
+
     fn handle_role_selector_key(&mut self, code: KeyCode) {
         let role_count = self
             .active_project()
@@ -840,7 +1380,11 @@ impl App {
             KeyCode::Esc => {
                 self.show_role_selector = false;
                 self.pending_spawn_config = None;
-                self.pending_spawn_worktrees.clear();
+                // Worktrees created for this spawn would be left behind.
+                super::remove_created_worktrees(
+                    &std::mem::take(&mut self.pending_spawn_worktrees),
+                    &std::mem::take(&mut self.pending_spawn_reused_worktrees),
+                );
                 self.pending_spawn_name = None;
                 // Undo the counter increment from prepare_spawn()
                 self.session_counter = self.session_counter.saturating_sub(1);
@@ -853,9 +1397,13 @@ impl App {
             KeyCode::Char('k') | KeyCode::Up => {
                 self.role_selector_index = self.role_selector_index.saturating_sub(1);
             }
+            KeyCode::Char('r') => self.role_selector_remember = !self.role_selector_remember,
             KeyCode::Enter => {
                 self.show_role_selector = false;
                 let role_index = self.role_selector_index;
+                if self.role_selector_remember {
+                    self.remember_spawn_role(role_index);
+                }
                 if let (Some(mut config), Some(name)) = (
                     self.pending_spawn_config.take(),
                     self.pending_spawn_name.take(),
@@ -865,7 +1413,12 @@ impl App {
                             config.role = role.name.clone();
                             config.permissions = role.permissions.clone();
                             let worktrees = std::mem::take(&mut self.pending_spawn_worktrees);
-                            self.do_spawn_session(name, &config, worktrees, None);
+                            let reused = std::mem::take(&mut self.pending_spawn_reused_worktrees);
+                            let sessions_before = self.sessions.len();
+                            self.do_spawn_session(name, &config, worktrees.clone(), None);
+                            if self.sessions.len() == sessions_before {
+                                super::remove_created_worktrees(&worktrees, &reused);
+                            }
                         }
                     }
                 }
@@ -907,14 +1460,12 @@ impl App {
                     self.open_role_for_editing(idx);
                 }
             }
-            KeyCode::Char('d') => {
-                if !self.role_editor_roles.is_empty() {
-                    self.role_editor_roles.remove(self.role_editor_list_index);
-                    if self.role_editor_list_index >= self.role_editor_roles.len()
-                        && self.role_editor_list_index > 0
-                    {
-                        self.role_editor_list_index -= 1;
-                    }
+            KeyCode::Char('d') if !self.role_editor_roles.is_empty() => {
+                self.role_editor_roles.remove(self.role_editor_list_index);
+                if self.role_editor_list_index >= self.role_editor_roles.len()
+                    && self.role_editor_list_index > 0
+                {
+                    self.role_editor_list_index -= 1;
                 }
             }
             _ => {}
@@ -996,7 +1547,9 @@ impl App {
     }
 
     fn handle_tool_adding_key(&mut self, code: KeyCode) {
-        handle_tool_list_adding_key(self.active_tool_list_mut(), code);
+        if let Err(e) = handle_tool_list_adding_key(self.active_tool_list_mut(), code) {
+            self.set_error(e);
+        }
     }
 
     fn next_editor_field(
@@ -1113,7 +1666,7 @@ impl App {
         match code {
             KeyCode::Esc => self.submit_edit_project(),
             KeyCode::Tab => {
-                self.edit_project_field = EditProjectField::Name;
+                self.edit_project_field = EditProjectField::SyncStrategy;
             }
             KeyCode::BackTab => {
                 self.edit_project_field = EditProjectField::Roles;
@@ -1140,17 +1693,106 @@ impl App {
                     self.show_mcp_editor = true;
                 }
             }
-            KeyCode::Char('d') => {
-                if !self.edit_project_mcp_servers.is_empty() {
-                    self.edit_project_mcp_servers
-                        .remove(self.edit_project_mcp_server_index);
-                    if self.edit_project_mcp_server_index >= self.edit_project_mcp_servers.len()
-                        && self.edit_project_mcp_server_index > 0
-                    {
-                        self.edit_project_mcp_server_index -= 1;
-                    }
+            KeyCode::Char(' ') => {
+                if let Some(server) = self
+                    .edit_project_mcp_servers
+                    .get_mut(self.edit_project_mcp_server_index)
+                {
+                    server.enabled = !server.enabled;
                 }
             }
+            KeyCode::Char('d') if !self.edit_project_mcp_servers.is_empty() => {
+                self.edit_project_mcp_servers
+                    .remove(self.edit_project_mcp_server_index);
+                if self.edit_project_mcp_server_index >= self.edit_project_mcp_servers.len()
+                    && self.edit_project_mcp_server_index > 0
+                {
+                    self.edit_project_mcp_server_index -= 1;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_edit_project_sync_strategy_key(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Esc => self.close_edit_project_modal(),
+            KeyCode::Tab => {
+                self.edit_project_field = EditProjectField::ActivityTimeout;
+            }
+            KeyCode::BackTab => {
+                self.edit_project_field = EditProjectField::McpServers;
+            }
+            KeyCode::Right | KeyCode::Char('l') | KeyCode::Char(' ') => {
+                self.edit_project_sync_strategy = self.edit_project_sync_strategy.next();
+            }
+            KeyCode::Left | KeyCode::Char('h') => {
+                self.edit_project_sync_strategy = self.edit_project_sync_strategy.prev();
+            }
+            KeyCode::Enter => self.submit_edit_project(),
+            _ => {}
+        }
+    }
+
+    fn handle_edit_project_activity_timeout_key(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Esc => self.close_edit_project_modal(),
+            KeyCode::Tab => {
+                self.edit_project_field = EditProjectField::WorktreeRoot;
+            }
+            KeyCode::BackTab => {
+                self.edit_project_field = EditProjectField::SyncStrategy;
+            }
+            KeyCode::Enter => self.submit_edit_project(),
+            KeyCode::Backspace => self.edit_project_activity_timeout.backspace(),
+            KeyCode::Delete => self.edit_project_activity_timeout.delete(),
+            KeyCode::Left => self.edit_project_activity_timeout.move_left(),
+            KeyCode::Right => self.edit_project_activity_timeout.move_right(),
+            KeyCode::Home => self.edit_project_activity_timeout.home(),
+            KeyCode::End => self.edit_project_activity_timeout.end(),
+            KeyCode::Char(c) if c.is_ascii_digit() => self.edit_project_activity_timeout.insert(c),
+            _ => {}
+        }
+    }
+
+    fn handle_edit_project_worktree_root_key(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Esc => self.close_edit_project_modal(),
+            KeyCode::Tab => {
+                self.edit_project_field = EditProjectField::InitCommand;
+            }
+            KeyCode::BackTab => {
+                self.edit_project_field = EditProjectField::ActivityTimeout;
+            }
+            KeyCode::Enter => self.submit_edit_project(),
+            KeyCode::Backspace => self.edit_project_worktree_root.backspace(),
+            KeyCode::Delete => self.edit_project_worktree_root.delete(),
+            KeyCode::Left => self.edit_project_worktree_root.move_left(),
+            KeyCode::Right => self.edit_project_worktree_root.move_right(),
+            KeyCode::Home => self.edit_project_worktree_root.home(),
+            KeyCode::End => self.edit_project_worktree_root.end(),
+            KeyCode::Char(c) => self.edit_project_worktree_root.insert(c),
+            _ => {}
+        }
+    }
+
+    fn handle_edit_project_init_command_key(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Esc => self.close_edit_project_modal(),
+            KeyCode::Tab => {
+                self.edit_project_field = EditProjectField::Name;
+            }
+            KeyCode::BackTab => {
+                self.edit_project_field = EditProjectField::WorktreeRoot;
+            }
+            KeyCode::Enter => self.submit_edit_project(),
+            KeyCode::Backspace => self.edit_project_init_command.backspace(),
+            KeyCode::Delete => self.edit_project_init_command.delete(),
+            KeyCode::Left => self.edit_project_init_command.move_left(),
+            KeyCode::Right => self.edit_project_init_command.move_right(),
+            KeyCode::Home => self.edit_project_init_command.home(),
+            KeyCode::End => self.edit_project_init_command.end(),
+            KeyCode::Char(c) => self.edit_project_init_command.insert(c),
             _ => {}
         }
     }
@@ -1191,7 +1833,10 @@ impl App {
             _ => {
                 let input = match self.mcp_editor_field {
                     McpEditorField::Name => &mut self.mcp_editor_name,
-                    McpEditorField::Command => &mut self.mcp_editor_command,
+                    McpEditorField::Command => {
+                        self.mcp_editor_command_warning = None;
+                        &mut self.mcp_editor_command
+                    }
                     _ => return,
                 };
                 match code {
@@ -1231,7 +1876,9 @@ impl App {
     }
 
     fn handle_mcp_tool_adding_key(&mut self, code: KeyCode) {
-        handle_tool_list_adding_key(self.active_mcp_tool_list_mut(), code);
+        if let Err(e) = handle_tool_list_adding_key(self.active_mcp_tool_list_mut(), code) {
+            self.set_error(e);
+        }
     }
 
     fn active_mcp_tool_list_mut(&mut self) -> &mut super::ToolListState {
@@ -1270,7 +1917,12 @@ impl App {
             }
             Ok(mut branches) => {
                 // Move the default branch to front so it's pre-selected.
-                if let Some(default) = crate::git::default_branch(repo_path, &branches) {
+                let remote = self
+                    .active_project()
+                    .map_or(crate::project::DEFAULT_REMOTE, |p| {
+                        p.config.effective_remote()
+                    });
+                if let Some(default) = crate::git::default_branch(repo_path, remote, &branches) {
                     if let Some(pos) = branches.iter().position(|b| b == &default) {
                         let branch = branches.remove(pos);
                         branches.insert(0, branch);
@@ -1291,11 +1943,16 @@ impl App {
 
 /// Handle key input when a [`ToolListState`] is in Adding mode.
 ///
-/// Shared between role editor and MCP editor tool list fields.
-fn handle_tool_list_adding_key(list: &mut super::ToolListState, code: KeyCode) {
+/// Shared between the role editor, MCP editor and extra directories editor
+/// list fields. Returns the list's validation error when Enter confirms a
+/// rejected entry.
+fn handle_tool_list_adding_key(
+    list: &mut super::ToolListState,
+    code: KeyCode,
+) -> Result<(), String> {
     match code {
         KeyCode::Esc => list.cancel_add(),
-        KeyCode::Enter => list.confirm_add(),
+        KeyCode::Enter => return list.confirm_add(),
         _ => {
             let input = &mut list.input;
             match code {
@@ -1310,4 +1967,5 @@ fn handle_tool_list_adding_key(list: &mut super::ToolListState, code: KeyCode) {
             }
         }
     }
+    Ok(())
 }
@@ -0,0 +1,492 @@
+//! Global keybinding table.
+//!
+//! Maps key chords to named [`Action`]s so users can rebind shortcuts that
+//! clash with programs running inside the PTY. Defaults mirror the built-in
+//! bindings; overrides come from the `[keybindings]` table in `config.toml`:
+//!
+//! ```toml
+//! [keybindings]
+//! close_session = "ctrl+w"
+//! sync_worktrees = "ctrl+g"
+//! ```
+//!
+//! Keys not bound to an action fall through to the focused panel (and from
+//! the terminal panel, to the PTY).
+
+use std::collections::HashMap;
+use std::fmt;
+
+use crossterm::event::{KeyCode, KeyModifiers};
+
+/// A global command that can be bound to a key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Action {
+    Quit,
+    New,
+    CloseSession,
+    CloseSessionAnywhere,
+    Delete,
+    EditProject,
+    RestartSession,
+    SyncWorktrees,
+    PushBranch,
+    CommitWorktree,
+    ExportScrollback,
+    ToggleShell,
+    UndoDelete,
+    RestoreSessions,
+    RenameSession,
+    FocusProjectList,
+    Next,
+    Previous,
+    CycleFocus,
+    Help,
+    ToggleInfoPanel,
+    CycleInfoPanel,
+    ShowLastSync,
+    ToggleOutputLog,
+    ToggleSessionTabs,
+    ToggleWrap,
+    FilterSessions,
+    Switcher,
+    CopyPath,
+}
+
+impl Action {
+    pub const ALL: [Action; 29] = [
+        Action::Quit,
+        Action::New,
+        Action::CloseSession,
+        Action::CloseSessionAnywhere,
+        Action::Delete,
+        Action::EditProject,
+        Action::RestartSession,
+        Action::SyncWorktrees,
+        Action::PushBranch,
+        Action::CommitWorktree,
+        Action::ExportScrollback,
+        Action::ToggleShell,
+        Action::UndoDelete,
+        Action::RestoreSessions,
+        Action::RenameSession,
+        Action::FocusProjectList,
+        Action::Next,
+        Action::Previous,
+        Action::CycleFocus,
+        Action::Help,
+        Action::ToggleInfoPanel,
+        Action::CycleInfoPanel,
+        Action::ShowLastSync,
+        Action::ToggleOutputLog,
+        Action::ToggleSessionTabs,
+        Action::ToggleWrap,
+        Action::FilterSessions,
+        Action::Switcher,
+        Action::CopyPath,
+    ];
+
+    /// Name used in the `[keybindings]` config table.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Quit => "quit",
+            Self::New => "new",
+            Self::CloseSession => "close_session",
+            Self::CloseSessionAnywhere => "close_session_anywhere",
+            Self::Delete => "delete",
+            Self::EditProject => "edit_project",
+            Self::RestartSession => "restart_session",
+            Self::SyncWorktrees => "sync_worktrees",
+            Self::PushBranch => "push_branch",
+            Self::CommitWorktree => "commit_worktree",
+            Self::ExportScrollback => "export_scrollback",
+            Self::ToggleShell => "toggle_shell",
+            Self::UndoDelete => "undo_delete",
+            Self::RestoreSessions => "restore_sessions",
+            Self::RenameSession => "rename_session",
+            Self::FocusProjectList => "focus_project_list",
+            Self::Next => "next",
+            Self::Previous => "previous",
+            Self::CycleFocus => "cycle_focus",
+            Self::Help => "help",
+            Self::ToggleInfoPanel => "toggle_info_panel",
+            Self::CycleInfoPanel => "cycle_info_panel",
+            Self::ShowLastSync => "show_last_sync",
+            Self::ToggleOutputLog => "toggle_output_log",
+            Self::ToggleSessionTabs => "toggle_session_tabs",
+            Self::ToggleWrap => "toggle_wrap",
+            Self::FilterSessions => "filter_sessions",
+            Self::Switcher => "switcher",
+            Self::CopyPath => "copy_path",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|a| a.name() == name)
+    }
+
+    fn default_key(self) -> (KeyCode, KeyModifiers) {
+        let ctrl = |c| (KeyCode::Char(c), KeyModifiers::CONTROL);
+        match self {
+            Self::Quit => ctrl('q'),
+            Self::New => ctrl('n'),
+            Self::CloseSession => ctrl('c'),
+            Self::CloseSessionAnywhere => ctrl('x'),
+            Self::Delete => ctrl('d'),
+            Self::EditProject => ctrl('e'),
+            Self::RestartSession => ctrl('r'),
+            Self::SyncWorktrees => ctrl('s'),
+            Self::PushBranch => (KeyCode::F(5), KeyModifiers::NONE),
+            Self::CommitWorktree => (KeyCode::F(6), KeyModifiers::NONE),
+            Self::ExportScrollback => (KeyCode::F(7), KeyModifiers::NONE),
+            Self::ToggleShell => ctrl('t'),
+            Self::UndoDelete => ctrl('z'),
+            Self::RestoreSessions => ctrl('u'),
+            Self::RenameSession => ctrl(','),
+            Self::FocusProjectList => ctrl('h'),
+            Self::Next => ctrl('j'),
+            Self::Previous => ctrl('k'),
+            Self::CycleFocus => ctrl('l'),
+            Self::Help => (KeyCode::F(1), KeyModifiers::NONE),
+            Self::ToggleInfoPanel => (KeyCode::F(2), KeyModifiers::NONE),
+            Self::CycleInfoPanel => (KeyCode::F(9), KeyModifiers::NONE),
+            Self::ShowLastSync => (KeyCode::F(10), KeyModifiers::NONE),
+            Self::ToggleOutputLog => (KeyCode::F(3), KeyModifiers::NONE),
+            Self::ToggleSessionTabs => (KeyCode::F(4), KeyModifiers::NONE),
+            Self::ToggleWrap => (KeyCode::F(8), KeyModifiers::NONE),
+            Self::FilterSessions => ctrl('f'),
+            Self::Switcher => ctrl('p'),
+            Self::CopyPath => ctrl('y'),
+        }
+    }
+}
+
+/// Error produced while building a [`KeyMap`] from user overrides.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KeyMapError {
+    UnknownAction(String),
+    InvalidKey { action: String, key: String },
+    Duplicate { key: String, actions: Vec<String> },
+}
+
+impl fmt::Display for KeyMapError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownAction(name) => write!(f, "Unknown keybinding action '{name}'"),
+            Self::InvalidKey { action, key } => {
+                write!(f, "Invalid key '{key}' for keybinding '{action}'")
+            }
+            Self::Duplicate { key, actions } => {
+                write!(
+                    f,
+                    "Key '{key}' bound to multiple actions: {}",
+                    actions.join(", ")
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for KeyMapError {}
+
+/// Resolved keybindings: one key chord per action.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyMap {
+    bindings: HashMap<Action, (KeyCode, KeyModifiers)>,
+}
+
+impl Default for KeyMap {
+    fn default() -> Self {
+        Self {
+            bindings: Action::ALL
+                .into_iter()
+                .map(|a| (a, a.default_key()))
+                .collect(),
+        }
+    }
+}
+
+impl KeyMap {
+    /// Build a keymap from the defaults plus `action name → key` overrides.
+    ///
+    /// Rejects unknown actions, unparseable keys, and any key bound to more
+    /// than one action after overrides are applied.
+    pub fn from_overrides(overrides: &HashMap<String, String>) -> Result<Self, KeyMapError> {
+        let mut map = Self::default();
+
+        // Sort for deterministic error reporting
+        let mut entries: Vec<_> = overrides.iter().collect();
+        entries.sort();
+
+        for (name, key) in entries {
+            let action =
+                Action::from_name(name).ok_or_else(|| KeyMapError::UnknownAction(name.clone()))?;
+            let chord = parse_key(key).ok_or_else(|| KeyMapError::InvalidKey {
+                action: name.clone(),
+                key: key.clone(),
+            })?;
+            map.bindings.insert(action, chord);
+        }
+
+        let mut by_key: HashMap<(KeyCode, KeyModifiers), Vec<Action>> = HashMap::new();
+        for action in Action::ALL {
+            by_key
+                .entry(map.bindings[&action])
+                .or_default()
+                .push(action);
+        }
+        let mut duplicates: Vec<_> = by_key
+            .into_iter()
+            .filter(|(_, actions)| actions.len() > 1)
+            .collect();
+        duplicates.sort_by_key(|(chord, _)| format_key(*chord));
+        if let Some((chord, actions)) = duplicates.into_iter().next() {
+            return Err(KeyMapError::Duplicate {
+                key: format_key(chord),
+                actions: actions.iter().map(|a| a.name().to_string()).collect(),
+            });
+        }
+
+        Ok(map)
+    }
+
+    /// Look up the action bound to a key event, if any.
+    pub fn resolve(&self, code: KeyCode, mods: KeyModifiers) -> Option<Action> {
+        let chord = normalize(code, mods);
+        Action::ALL
+            .into_iter()
+            .find(|a| self.bindings.get(a) == Some(&chord))
+    }
+
+    /// The key chord bound to `action`.
+    pub fn key_for(&self, action: Action) -> (KeyCode, KeyModifiers) {
+        self.bindings[&action]
+    }
+
+    /// Human-readable label for the key bound to `action` (e.g. `Ctrl+N`).
+    pub fn label(&self, action: Action) -> String {
+        display_key(self.key_for(action))
+    }
+}
+
+/// Drop SHIFT from character keys: the case of the char already encodes it,
+/// and terminals disagree on whether to report the modifier.
+fn normalize(code: KeyCode, mods: KeyModifiers) -> (KeyCode, KeyModifiers) {
+    match code {
+        KeyCode::Char(c) => (
+            KeyCode::Char(c.to_ascii_lowercase()),
+            mods - KeyModifiers::SHIFT,
+        ),
+        _ => (code, mods),
+    }
+}
+
+/// Parse a key chord like `ctrl+n`, `alt+shift+x`, or `f5`.
+pub fn parse_key(s: &str) -> Option<(KeyCode, KeyModifiers)> {
+    let lower = s.trim().to_ascii_lowercase();
+    let mut parts: Vec<&str> = lower.split('+').collect();
+    let key = parts.pop()?;
+
+    let mut mods = KeyModifiers::NONE;
+    for part in parts {
+        mods |= match part {
+            "ctrl" | "control" => KeyModifiers::CONTROL,
+            "alt" | "meta" => KeyModifiers::ALT,
+            "shift" => KeyModifiers::SHIFT,
+            _ => return None,
+        };
+    }
+
+    let code = match key {
+        "enter" => KeyCode::Enter,
+        "esc" | "escape" => KeyCode::Esc,
+        "tab" => KeyCode::Tab,
+        "backspace" => KeyCode::Backspace,
+        "delete" | "del" => KeyCode::Delete,
+        "insert" => KeyCode::Insert,
+        "home" => KeyCode::Home,
+        "end" => KeyCode::End,
+        "pageup" => KeyCode::PageUp,
+        "pagedown" => KeyCode::PageDown,
+        "up" => KeyCode::Up,
+        "down" => KeyCode::Down,
+        "left" => KeyCode::Left,
+        "right" => KeyCode::Right,
+        "space" => KeyCode::Char(' '),
+        k if k.len() > 1 && k.starts_with('f') => KeyCode::F(k[1..].parse().ok()?),
+        k => {
+            let mut chars = k.chars();
+            let c = chars.next()?;
+            if chars.next().is_some() {
+                return None;
+            }
+            KeyCode::Char(c)
+        }
+    };
+
+    Some(normalize(code, mods))
+}
+
+/// Format a chord in config syntax (`ctrl+n`).
+fn format_key((code, mods): (KeyCode, KeyModifiers)) -> String {
+    display_key((code, mods)).to_ascii_lowercase()
+}
+
+/// Format a chord for display (`Ctrl+N`, `F1`).
+fn display_key((code, mods): (KeyCode, KeyModifiers)) -> String {
+    let mut out = String::new();
+    if mods.contains(KeyModifiers::CONTROL) {
+        out.push_str("Ctrl+");
+    }
+    if mods.contains(KeyModifiers::ALT) {
+        out.push_str("Alt+");
+    }
+    if mods.contains(KeyModifiers::SHIFT) {
+        out.push_str("Shift+");
+    }
+    match code {
+        KeyCode::Char(' ') => out.push_str("Space"),
+        KeyCode::Char(c) => out.push(c.to_ascii_uppercase()),
+        KeyCode::F(n) => out.push_str(&format!("F{n}")),
+        other => out.push_str(&format!("{other:?}")),
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn overrides(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn default_bindings_match_builtin_keys() {
+        let map = KeyMap::default();
+        assert_eq!(
+            map.resolve(KeyCode::Char('q'), KeyModifiers::CONTROL),
+            Some(Action::Quit)
+        );
+        assert_eq!(
+            map.resolve(KeyCode::Char('s'), KeyModifiers::CONTROL),
+            Some(Action::SyncWorktrees)
+        );
+        assert_eq!(
+            map.resolve(KeyCode::F(2), KeyModifiers::NONE),
+            Some(Action::ToggleInfoPanel)
+        );
+    }
+
+    #[test]
+    fn default_bindings_have_no_duplicates() {
+        assert_eq!(
+            KeyMap::from_overrides(&HashMap::new()).unwrap(),
+            KeyMap::default()
+        );
+    }
+
+    #[test]
+    fn unbound_key_resolves_to_none() {
+        let map = KeyMap::default();
+        assert_eq!(map.resolve(KeyCode::Char('a'), KeyModifiers::NONE), None);
+        assert_eq!(map.resolve(KeyCode::Char('b'), KeyModifiers::CONTROL), None);
+    }
+
+    #[test]
+    fn shift_on_char_keys_is_ignored() {
+        let map = KeyMap::default();
+        assert_eq!(
+            map.resolve(
+                KeyCode::Char('Q'),
+                KeyModifiers::CONTROL | KeyModifiers::SHIFT
+            ),
+            Some(Action::Quit)
+        );
+    }
+
+    #[test]
+    fn override_rebinds_action() {
+        let map = KeyMap::from_overrides(&overrides(&[("close_session", "ctrl+w")])).unwrap();
+        assert_eq!(
+            map.resolve(KeyCode::Char('w'), KeyModifiers::CONTROL),
+            Some(Action::CloseSession)
+        );
+        assert_eq!(map.resolve(KeyCode::Char('c'), KeyModifiers::CONTROL), None);
+    }
+
+    #[test]
+    fn duplicate_binding_is_rejected() {
+        let err = KeyMap::from_overrides(&overrides(&[("sync_worktrees", "ctrl+q")])).unwrap_err();
+        assert_eq!(
+            err,
+            KeyMapError::Duplicate {
+                key: "ctrl+q".to_string(),
+                actions: vec!["quit".to_string(), "sync_worktrees".to_string()],
+            }
+        );
+    }
+
+    #[test]
+    fn swapping_two_bindings_is_allowed() {
+        let map = KeyMap::from_overrides(&overrides(&[("next", "ctrl+k"), ("previous", "ctrl+j")]))
+            .unwrap();
+        assert_eq!(
+            map.resolve(KeyCode::Char('k'), KeyModifiers::CONTROL),
+            Some(Action::Next)
+        );
+    }
+
+    #[test]
+    fn unknown_action_is_rejected() {
+        let err = KeyMap::from_overrides(&overrides(&[("fly", "ctrl+y")])).unwrap_err();
+        assert_eq!(err, KeyMapError::UnknownAction("fly".to_string()));
+    }
+
+    #[test]
+    fn invalid_key_is_rejected() {
+        let err = KeyMap::from_overrides(&overrides(&[("quit", "hyper+q")])).unwrap_err();
+        assert!(matches!(err, KeyMapError::InvalidKey { .. }));
+    }
+
+    #[test]
+    fn parse_key_variants() {
+        assert_eq!(
+            parse_key("Ctrl+N"),
+            Some((KeyCode::Char('n'), KeyModifiers::CONTROL))
+        );
+        assert_eq!(
+            parse_key("alt+x"),
+            Some((KeyCode::Char('x'), KeyModifiers::ALT))
+        );
+        assert_eq!(parse_key("f5"), Some((KeyCode::F(5), KeyModifiers::NONE)));
+        assert_eq!(
+            parse_key("f"),
+            Some((KeyCode::Char('f'), KeyModifiers::NONE))
+        );
+        assert_eq!(
+            parse_key("ctrl+pageup"),
+            Some((KeyCode::PageUp, KeyModifiers::CONTROL))
+        );
+        assert_eq!(parse_key(""), None);
+        assert_eq!(parse_key("ctrl+"), None);
+        assert_eq!(parse_key("ctrl+ab"), None);
+        assert_eq!(parse_key("fx"), None);
+    }
+
+    #[test]
+    fn label_formats_for_display() {
+        let map = KeyMap::default();
+        assert_eq!(map.label(Action::New), "Ctrl+N");
+        assert_eq!(map.label(Action::Help), "F1");
+    }
+
+    #[test]
+    fn action_names_roundtrip() {
+        for action in Action::ALL {
+            assert_eq!(Action::from_name(action.name()), Some(action));
+        }
+    }
+}
@@ -15,7 +15,40 @@ pub enum McpEditorField {
     Env,
 }
 
+/// Where the MCP editor saves the server it edits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum McpEditorTarget {
+    /// The edit-project modal's server list.
+    Project,
+    /// Extra servers for the session being spawned (session mode modal).
+    Spawn,
+}
+
 impl App {
+    /// Open the MCP editor from the session mode modal to add a server for
+    /// the session about to be spawned only.
+    pub(crate) fn open_spawn_mcp_editor(&mut self) {
+        self.prepare_new_mcp_editor();
+        self.mcp_editor_target = McpEditorTarget::Spawn;
+        self.show_session_mode_modal = false;
+        self.show_mcp_editor = true;
+    }
+
+    /// After the editor closes, go back to the modal that opened it.
+    pub(crate) fn return_from_mcp_editor(&mut self) {
+        if self.mcp_editor_target == McpEditorTarget::Spawn {
+            self.mcp_editor_target = McpEditorTarget::Project;
+            self.show_session_mode_modal = true;
+        }
+    }
+
+    fn mcp_editor_servers(&self) -> &[McpServerConfig] {
+        match self.mcp_editor_target {
+            McpEditorTarget::Project => &self.edit_project_mcp_servers,
+            McpEditorTarget::Spawn => &self.pending_spawn_mcp_servers,
+        }
+    }
+
     /// Reset MCP editor fields to prepare for adding a new server.
     pub(crate) fn prepare_new_mcp_editor(&mut self) {
         self.mcp_editor_editing_index = None;
@@ -24,6 +57,7 @@ impl App {
         self.mcp_editor_args.reset();
         self.mcp_editor_env.reset();
         self.mcp_editor_field = McpEditorField::Name;
+        self.mcp_editor_command_warning = None;
         self.mcp_editor_snapshot = Some(self.capture_mcp_editor_snapshot());
     }
 
@@ -42,6 +76,7 @@ impl App {
         self.mcp_editor_env.load(&env_strings);
 
         self.mcp_editor_field = McpEditorField::Name;
+        self.mcp_editor_command_warning = None;
         self.mcp_editor_snapshot = Some(self.capture_mcp_editor_snapshot());
     }
 
@@ -56,7 +91,7 @@ impl App {
 
         // Check duplicate names (excluding the server being edited)
         let is_duplicate = self
-            .edit_project_mcp_servers
+            .mcp_editor_servers()
             .iter()
             .enumerate()
             .any(|(i, s)| s.name == name && Some(i) != self.mcp_editor_editing_index);
@@ -64,6 +99,15 @@ impl App {
             return;
         }
 
+        // Warn once if the command can't be resolved; a second submit with
+        // the same command confirms the save.
+        if self.mcp_editor_command_warning.as_deref() != Some(command.as_str())
+            && crate::mcp::command::resolve_command(&command).is_none()
+        {
+            self.mcp_editor_command_warning = Some(command);
+            return;
+        }
+
         // Parse env entries from "KEY=VALUE" strings
         let env: HashMap<String, String> = self
             .mcp_editor_env
@@ -75,23 +119,34 @@ impl App {
             })
             .collect();
 
+        // Editing keeps the server's enabled state; new servers start enabled.
+        let enabled = self
+            .mcp_editor_editing_index
+            .and_then(|idx| self.mcp_editor_servers().get(idx))
+            .map_or(true, |s| s.enabled);
+
         let server = McpServerConfig {
             name,
             command,
             args: self.mcp_editor_args.items.clone(),
             env,
+            enabled,
         };
 
-        if let Some(idx) = self.mcp_editor_editing_index {
-            self.edit_project_mcp_servers[idx] = server;
-        } else {
-            self.edit_project_mcp_servers.push(server);
-            self.edit_project_mcp_server_index =
-                self.edit_project_mcp_servers.len().saturating_sub(1);
+        match (self.mcp_editor_target, self.mcp_editor_editing_index) {
+            (McpEditorTarget::Spawn, _) => self.pending_spawn_mcp_servers.push(server),
+            (McpEditorTarget::Project, Some(idx)) => self.edit_project_mcp_servers[idx] = server,
+            (McpEditorTarget::Project, None) => {
+                self.edit_project_mcp_servers.push(server);
+                self.edit_project_mcp_server_index =
+                    self.edit_project_mcp_servers.len().saturating_sub(1);
+            }
         }
 
         self.show_mcp_editor = false;
         self.mcp_editor_snapshot = None;
+        self.mcp_editor_command_warning = None;
         self.mcp_editor_field = McpEditorField::Name;
+        self.return_from_mcp_editor();
     }
 }
@@ -1,16 +1,26 @@
+pub mod error;
 mod key_handlers;
+pub mod keymap;
 pub(crate) mod mcp_editor_modal;
 mod modals;
+mod notify;
+pub mod settings;
 mod state;
+mod status_server;
+mod switcher;
+mod worker_pool;
+
+use error::AppError;
+use switcher::{SwitcherEntry, SwitcherTarget};
 
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::{mpsc, Arc};
 
 use crate::ui::theme::Theme;
 use crossterm::event::{KeyCode, KeyModifiers};
 use ratatui::{
-    layout::{Constraint, Direction, Layout, Position, Rect},
+    layout::{Position, Rect},
     style::Style,
     text::{Line, Span},
     widgets::{Block, Borders, Clear, Paragraph},
@@ -18,36 +28,87 @@ use ratatui::{
 };
 use tracing::error;
 
-use crate::claude::{Session, SessionBackend};
+use crate::claude::backend::{DiscoveredSession, SCROLLBACK_LINES};
+use crate::claude::{mcp_config, output_log, Session, SessionBackend};
 use crate::git;
-use crate::project::{ProjectConfig, ProjectId, ProjectInfo};
+use crate::project::{ProjectConfig, ProjectId, ProjectInfo, SyncStrategy};
 use crate::session::{
-    RoleConfig, RolePermissions, SessionCommand, SessionConfig, SessionId, SessionInfo,
-    SessionStatus, WorktreeInfo, DEFAULT_ROLE_NAME,
+    merge_mcp_servers, validate_tool_pattern, RoleConfig, RolePermissions, SessionCommand,
+    SessionConfig, SessionId, SessionInfo, SessionKind, SessionStatus, WorktreeInfo,
+    DEFAULT_ROLE_NAME,
 };
-use crate::storage::Database;
 use crate::storage::DeletedSessionInfo;
+use crate::storage::{Database, RoleFileFormat};
 use crate::sync::{self, SharedWorktree, StateDelta, SyncState};
+use crate::ui::centered_rect;
 use crate::ui::{
-    add_project_modal, branch_selector_modal, delete_project_modal, edit_project_modal, info_panel,
-    layout, project_list, repo_selector_modal, restore_sessions_modal, role_editor_modal,
-    role_selector_modal, session_mode_modal, status_bar, terminal_view, worktree_name_modal,
+    add_project_modal, branch_selector_modal, clone_repo_modal, close_all_modal,
+    close_session_modal, commit_modal, delete_project_modal, edit_project_modal, export_modal,
+    info_panel, layout, move_session_modal, project_list, quit_modal, rename_session_modal,
+    repo_selector_modal, restore_sessions_modal, resume_session_modal, role_editor_modal,
+    role_file_modal, role_selector_modal, search, selection, send_prompt_modal, session_dirs_modal,
+    session_label_modal, session_limit_modal, session_mode_modal, session_tabs, status_bar,
+    status_icons, switcher_modal, sync_preview_modal, terminal_view, worktree_name_modal,
 };
+use search::SearchMatch;
+use selection::CellPos;
 
-const MOUSE_SCROLL_LINES: usize = 3;
+/// Name of the admin pseudo-project; its ID is derived from it.
+const ADMIN_PROJECT_NAME: &str = "Admin";
 
 /// How long the user has to press Ctrl+Z to undo a session delete.
 const UNDO_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
 
 /// If no output for this many milliseconds, consider session "Waiting".
+/// Projects can override this with `activity_timeout_ms`.
 const ACTIVITY_TIMEOUT_MS: u64 = 1000;
 
-/// Prompt sent to Claude sessions when a worktree rebase has conflicts.
-const SYNC_CONFLICT_PROMPT: &str = "Please sync this worktree with main. Run: git fetch origin && git rebase origin/main -- if there are conflicts, resolve them and continue the rebase with git rebase --continue.";
+/// Delay before the first automatic restart; doubles with each attempt.
+const AUTO_RESTART_BASE_DELAY: std::time::Duration = std::time::Duration::from_secs(1);
+
+/// Upper bound on the automatic restart backoff.
+const AUTO_RESTART_MAX_DELAY: std::time::Duration = std::time::Duration::from_secs(30);
+
+/// A restarted session that stays up this long has its attempt count reset.
+const AUTO_RESTART_STABLE_AFTER: std::time::Duration = std::time::Duration::from_secs(60);
+
+/// How long a worktree's git status is cached before the info panel
+/// refreshes it.
+const WORKTREE_STATUS_REFRESH: std::time::Duration = std::time::Duration::from_secs(3);
+
+/// How long the info panel's activity summary is cached before it is
+/// re-read from the session's status timeline.
+const ACTIVITY_SUMMARY_REFRESH: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// An exit this soon after the user forwarded Ctrl+C counts as intentional.
+const INTERRUPT_GRACE: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// Default prompt sent to Claude sessions when a worktree rebase has
+/// conflicts, rendered with [`render_prompt_template`]: `{remote}` and
+/// `{base}` are the remote and branch the sync rebased onto, `{branch}` the
+/// worktree's branch.
+const SYNC_CONFLICT_PROMPT: &str = "Please sync this worktree with {base}. Run: git fetch {remote} && git rebase {remote}/{base} -- if there are conflicts, resolve them and continue the rebase with git rebase --continue.";
+
+/// Conflict prompt for projects using [`SyncStrategy::Merge`].
+const SYNC_MERGE_CONFLICT_PROMPT: &str = "Please sync this worktree with {base}. Run: git fetch {remote} && git merge {remote}/{base} -- if there are conflicts, resolve them and commit the merge. Do not rebase; local commits may already be pushed.";
 
-/// Tick delay before sending Enter after pasting text into a session.
-/// At ~10ms per tick, 10 ticks ≈ 100ms — enough for the app to process the pasted text.
-const DEFERRED_INPUT_DELAY_TICKS: u64 = 10;
+/// A session that printed output within this many milliseconds counts as
+/// busy when timing the Enter that follows a paste.
+const DEFERRED_INPUT_BUSY_MS: u64 = 1_000;
+
+/// Upper bound on how much a busy session stretches the paste-to-Enter delay.
+const DEFERRED_INPUT_MAX_SCALE: u64 = 3;
+
+/// Approximate duration of one event-loop tick, for converting
+/// millisecond settings into tick counts.
+const TICK_MS: u64 = 10;
+
+/// Ticks between redraws while nothing changed (~100ms), which keeps the
+/// sync spinner moving and picks up state that does not request a redraw.
+const REDRAW_HEARTBEAT_TICKS: u64 = 10;
+
+/// Ticks a terminal bell stays highlighted in the session list (~1.5s).
+const BELL_HIGHLIGHT_TICKS: u16 = 150;
 
 /// MCP tool names auto-allowed in the admin session so Claude can manage
 /// Thurbox without repeated permission prompts.
@@ -79,6 +140,13 @@ pub enum RoleEditorView {
     Editor,
 }
 
+/// Direction of the role file prompt opened from the Roles list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RoleFileAction {
+    Export,
+    Import,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum AddProjectField {
     Name,
@@ -93,14 +161,23 @@ pub enum EditProjectField {
     RepoList,
     Roles,
     McpServers,
+    SyncStrategy,
+    ActivityTimeout,
+    WorktreeRoot,
+    InitCommand,
 }
 
+/// Validation hook for [`ToolListState`] entries.
+type EntryValidator = fn(&str) -> Result<(), String>;
+
 /// State for an editable list of tool names (allowed or disallowed).
 pub(crate) struct ToolListState {
     pub(crate) items: Vec<String>,
     pub(crate) selected: usize,
     pub(crate) mode: role_editor_modal::ToolListMode,
     pub(crate) input: TextInput,
+    /// Checks each new entry before it is added; `None` accepts anything.
+    validate: Option<EntryValidator>,
 }
 
 impl ToolListState {
@@ -110,6 +187,15 @@ impl ToolListState {
             selected: 0,
             mode: role_editor_modal::ToolListMode::Browse,
             input: TextInput::new(),
+            validate: None,
+        }
+    }
+
+    /// A list whose entries must pass `validate`.
+    fn with_validator(validate: EntryValidator) -> Self {
+        Self {
+            validate: Some(validate),
+            ..Self::new()
         }
     }
 
@@ -132,13 +218,19 @@ impl ToolListState {
         self.input.clear();
     }
 
-    fn confirm_add(&mut self) {
+    /// Add the typed entry. An entry rejected by the validator is not
+    /// added and the list stays in Adding mode so it can be corrected.
+    fn confirm_add(&mut self) -> Result<(), String> {
         let val = self.input.value().trim().to_string();
         if !val.is_empty() {
+            if let Some(validate) = self.validate {
+                validate(&val)?;
+            }
             self.items.push(val);
             self.selected = self.items.len() - 1;
         }
         self.mode = role_editor_modal::ToolListMode::Browse;
+        Ok(())
     }
 
     fn cancel_add(&mut self) {
@@ -163,6 +255,31 @@ impl ToolListState {
     fn move_up(&mut self) {
         self.selected = self.selected.saturating_sub(1);
     }
+
+    /// Swap the selected entry with the one below it, keeping it selected.
+    fn move_selected_down(&mut self) {
+        if self.selected + 1 < self.items.len() {
+            self.items.swap(self.selected, self.selected + 1);
+            self.selected += 1;
+        }
+    }
+
+    /// Swap the selected entry with the one above it, keeping it selected.
+    fn move_selected_up(&mut self) {
+        if self.selected > 0 && self.selected < self.items.len() {
+            self.items.swap(self.selected, self.selected - 1);
+            self.selected -= 1;
+        }
+    }
+}
+
+/// Entries of the extra directories editor must name an existing directory.
+fn validate_additional_dir(path: &str) -> Result<(), String> {
+    if crate::paths::dir_exists(Path::new(path)) {
+        Ok(())
+    } else {
+        Err(format!("Not a directory: {path}"))
+    }
 }
 
 pub(crate) struct TextInput {
@@ -270,7 +387,48 @@ pub enum StatusLevel {
 pub struct StatusMessage {
     pub text: String,
     pub level: StatusLevel,
-    pub created_at: std::time::Instant,
+    /// `tick_count` when the message was set; see [`App::expire_status_message`].
+    pub created_tick: u64,
+    /// The failure behind an error message, when it has a type.
+    pub error: Option<AppError>,
+}
+
+/// Outcome of a worktree sync (`Ctrl+S`), kept so `F10` can show it again
+/// once its status message is gone.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SyncSummary {
+    pub synced: usize,
+    /// Conflicts sent to Claude to resolve.
+    pub conflicts: usize,
+    /// Fast-forward-only worktrees that could not fast-forward.
+    pub diverged: usize,
+    pub errors: Vec<String>,
+}
+
+impl SyncSummary {
+    /// Errors first, then anything needing attention, else success.
+    pub fn level(&self) -> StatusLevel {
+        if !self.errors.is_empty() {
+            StatusLevel::Error
+        } else if self.conflicts > 0 || self.diverged > 0 {
+            StatusLevel::Info
+        } else {
+            StatusLevel::Success
+        }
+    }
+
+    /// Compact counts for the footer badge, e.g. `3✓ 1⚠ 1✗`.
+    pub fn badge(&self) -> String {
+        let mut text = format!("{}✓", self.synced);
+        let attention = self.conflicts + self.diverged;
+        if attention > 0 {
+            text.push_str(&format!(" {attention}⚠"));
+        }
+        if !self.errors.is_empty() {
+            text.push_str(&format!(" {}✗", self.errors.len()));
+        }
+        text
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -287,12 +445,365 @@ pub(crate) enum TerminalView {
     Shell,
 }
 
+/// Which sessions the session list shows, cycled with `Ctrl+F`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) enum SessionFilter {
+    #[default]
+    All,
+    /// Sessions waiting on the user (`Waiting` or `NeedsInput`).
+    Waiting,
+    Busy,
+}
+
+impl SessionFilter {
+    fn next(self) -> Self {
+        match self {
+            Self::All => Self::Waiting,
+            Self::Waiting => Self::Busy,
+            Self::Busy => Self::All,
+        }
+    }
+
+    fn matches(self, status: SessionStatus) -> bool {
+        match self {
+            Self::All => true,
+            Self::Waiting => matches!(status, SessionStatus::Waiting | SessionStatus::NeedsInput),
+            Self::Busy => status == SessionStatus::Busy,
+        }
+    }
+
+    /// Name stored in per-project UI preferences.
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::All => "all",
+            Self::Waiting => "waiting",
+            Self::Busy => "busy",
+        }
+    }
+
+    /// Inverse of [`Self::as_str`]; unknown names show everything.
+    fn from_name(name: &str) -> Self {
+        match name {
+            "waiting" => Self::Waiting,
+            "busy" => Self::Busy,
+            _ => Self::All,
+        }
+    }
+
+    /// Footer label, or `None` when nothing is filtered out.
+    fn label(self) -> Option<&'static str> {
+        match self {
+            Self::All => None,
+            Self::Waiting => Some("Waiting"),
+            Self::Busy => Some("Busy"),
+        }
+    }
+}
+
+/// Name filter over the project list (`/` in the project list).
+pub(crate) struct ProjectFilter {
+    pub query: TextInput,
+    /// The query is being typed; keys go to the filter instead of the list.
+    pub editing: bool,
+}
+
+/// Whether a project named `name` passes the project filter `query`: a
+/// case-insensitive substring match. An empty query matches everything.
+pub(crate) fn project_matches_filter(name: &str, query: &str) -> bool {
+    name.to_lowercase().contains(&query.to_lowercase())
+}
+
+/// Scrollback search over the active terminal (`/` while scrolled back).
+pub(crate) struct TerminalSearch {
+    pub query: TextInput,
+    /// The query is being typed; keys go to the prompt instead of the PTY.
+    pub editing: bool,
+    /// Match the view is centred on. Re-located after every rescan.
+    pub current: Option<SearchMatch>,
+    /// 1-based index of `current` and the total match count.
+    pub position: Option<(usize, usize)>,
+}
+
+/// Copy-mode selection over the active terminal (`v` while scrolled back).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct TerminalSelection {
+    /// Where the selection started; stays fixed while the cursor moves.
+    pub anchor: CellPos,
+    pub cursor: CellPos,
+}
+
+/// Direction to move through scrollback search matches.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum SearchStep {
+    /// Closest match at or above the bottom of the view.
+    Nearest,
+    /// Previous match, towards the top of the scrollback (`n`).
+    Older,
+    /// Next match, towards the live screen (`N`).
+    Newer,
+}
+
+/// Automatic restart bookkeeping for a session whose process exited on its
+/// own. Only tracked while `[auto_restart]` is enabled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum AutoRestart {
+    /// Exited; restart number `attempts + 1` fires at `due`.
+    Scheduled {
+        attempts: u32,
+        due: std::time::Instant,
+    },
+    /// Restarted `attempts` times, most recently at `since`.
+    Running {
+        attempts: u32,
+        since: std::time::Instant,
+    },
+    /// Exited after a user interrupt, or auto-restart gave up. Left alone
+    /// until the session runs again.
+    Stopped,
+}
+
+/// Base branch for a new worktree in `repo_path`. In a multi-repo project
+/// the chosen base is listed from the first repo; a repo without it falls
+/// back to its own default branch.
+fn repo_base_branch(repo_path: &Path, remote: &str, base_branch: &str) -> String {
+    if git::branch_exists(repo_path, base_branch) {
+        return base_branch.to_string();
+    }
+    git::list_branches(repo_path)
+        .ok()
+        .and_then(|branches| git::default_branch(repo_path, remote, &branches))
+        .unwrap_or_else(|| base_branch.to_string())
+}
+
+/// Whether two paths name the same directory, resolving symlinks when both
+/// exist.
+fn same_path(a: &Path, b: &Path) -> bool {
+    match (a.canonicalize(), b.canonicalize()) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => a == b,
+    }
+}
+
+/// Remove every worktree in `worktrees`, logging failures.
+fn remove_worktrees(worktrees: &[WorktreeInfo]) {
+    for wt in worktrees {
+        if let Err(e) = git::remove_worktree(&wt.repo_path, &wt.worktree_path) {
+            error!("Failed to remove worktree: {e}");
+        }
+    }
+}
+
+/// Roll back a spawn's worktrees, leaving the `reused` ones that existed
+/// before the spawn (and may hold the user's work) on disk.
+fn remove_created_worktrees(worktrees: &[WorktreeInfo], reused: &[PathBuf]) {
+    let created: Vec<WorktreeInfo> = worktrees
+        .iter()
+        .filter(|wt| !reused.contains(&wt.worktree_path))
+        .cloned()
+        .collect();
+    remove_worktrees(&created);
+}
+
+/// Branches of `worktrees` with uncommitted changes. A worktree whose
+/// status cannot be read is listed too, since closing would force-remove it.
+fn dirty_worktree_branches(worktrees: &[WorktreeInfo]) -> Vec<String> {
+    worktrees
+        .iter()
+        .filter(|wt| {
+            git::worktree_is_dirty(&wt.worktree_path).unwrap_or_else(|e| {
+                error!(
+                    "Failed to check {} for changes: {e}",
+                    wt.worktree_path.display()
+                );
+                true
+            })
+        })
+        .map(|wt| wt.branch.clone())
+        .collect()
+}
+
+/// Branches of `session`'s worktrees with commits that were never pushed,
+/// skipping branches another session still has checked out. A branch that
+/// cannot be checked is listed too, so a git failure still prompts.
+fn unpushed_worktree_branches(session: &SessionInfo, sessions: &[Session]) -> Vec<String> {
+    session
+        .worktrees
+        .iter()
+        .filter(|wt| {
+            !sessions.iter().any(|other| {
+                other.info.id != session.id
+                    && other
+                        .info
+                        .worktrees
+                        .iter()
+                        .any(|o| o.repo_path == wt.repo_path && o.branch == wt.branch)
+            })
+        })
+        .filter(|wt| {
+            git::branch_has_unpushed_commits(&wt.worktree_path, &wt.base_branch).unwrap_or_else(
+                |e| {
+                    error!(
+                        "Failed to check {} for unpushed commits: {e}",
+                        wt.worktree_path.display()
+                    );
+                    true
+                },
+            )
+        })
+        .map(|wt| wt.branch.clone())
+        .collect()
+}
+
+/// Fill `{name}` placeholders in `template` from `vars`. Placeholders with
+/// no matching variable are kept as written, and substituted values are not
+/// scanned again.
+fn render_prompt_template(template: &str, vars: &[(&str, &str)]) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(open) = rest.find('{') {
+        out.push_str(&rest[..open]);
+        let after = &rest[open + 1..];
+        let value = after.find('}').and_then(|close| {
+            let name = &after[..close];
+            vars.iter()
+                .find(|(var, _)| *var == name)
+                .map(|(_, value)| (*value, close))
+        });
+        match value {
+            Some((value, close)) => {
+                out.push_str(value);
+                rest = &after[close + 1..];
+            }
+            None => {
+                out.push('{');
+                rest = after;
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Ticks to wait between pasting into a session and sending Enter.
+///
+/// A session quiet for [`DEFERRED_INPUT_BUSY_MS`] gets `base_ms`; one that
+/// printed output more recently is still busy rendering and ingests the
+/// paste more slowly, so the delay grows linearly up to
+/// [`DEFERRED_INPUT_MAX_SCALE`] times `base_ms` for output printed just now.
+fn deferred_input_delay_ticks(base_ms: u64, millis_since_output: u64) -> u64 {
+    let busy_ms = DEFERRED_INPUT_BUSY_MS.saturating_sub(millis_since_output);
+    let extra_ms =
+        base_ms.saturating_mul((DEFERRED_INPUT_MAX_SCALE - 1) * busy_ms) / DEFERRED_INPUT_BUSY_MS;
+    base_ms.saturating_add(extra_ms).div_ceil(TICK_MS).max(1)
+}
+
+/// Directory `Ctrl+Y` copies: the session's first worktree, otherwise its
+/// working directory.
+fn copy_path(info: &SessionInfo) -> Option<&Path> {
+    info.worktrees
+        .first()
+        .map(|wt| wt.worktree_path.as_path())
+        .or(info.cwd.as_deref())
+}
+
+/// Output-silence timeout for `session_id`: its project's
+/// `activity_timeout_ms`, or [`ACTIVITY_TIMEOUT_MS`] when unset.
+fn activity_timeout_for(projects: &[ProjectInfo], session_id: SessionId) -> u64 {
+    projects
+        .iter()
+        .find(|p| p.session_ids.contains(&session_id))
+        .and_then(|p| p.config.activity_timeout_ms)
+        .unwrap_or(ACTIVITY_TIMEOUT_MS)
+}
+
+/// `cwd` when it is set but no longer exists as a directory.
+fn missing_cwd(cwd: Option<&Path>) -> Option<&Path> {
+    cwd.filter(|dir| !crate::paths::dir_exists(dir))
+}
+
+/// Name for session number `counter` under `settings`. A branch-derived
+/// name already `taken` by another session gets the counter appended, so
+/// two worktrees whose branches share a slug still get distinct names.
+fn session_name(
+    settings: &settings::SessionNameSettings,
+    counter: usize,
+    branch: Option<&str>,
+    taken: impl Fn(&str) -> bool,
+) -> String {
+    match (settings.scheme, branch) {
+        (settings::SessionNameScheme::Branch, Some(branch)) => {
+            let slug = branch_slug(branch);
+            if slug.is_empty() {
+                counter.to_string()
+            } else if taken(&slug) {
+                format!("{slug}-{counter}")
+            } else {
+                slug
+            }
+        }
+        (settings::SessionNameScheme::Prefix, _) => format!("{}{counter}", settings.prefix),
+        _ => counter.to_string(),
+    }
+}
+
+/// `feat/Login fix` → `feat-Login-fix`: runs of characters other than
+/// letters, digits, `.` and `_` become a single `-`.
+fn branch_slug(branch: &str) -> String {
+    let mut slug = String::new();
+    for c in branch.chars() {
+        if c.is_alphanumeric() || c == '.' || c == '_' {
+            slug.push(c);
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    slug.trim_end_matches('-').to_string()
+}
+
+/// Backoff before restart number `attempts + 1`: 1s, 2s, 4s, … capped at
+/// [`AUTO_RESTART_MAX_DELAY`].
+fn auto_restart_delay(attempts: u32) -> std::time::Duration {
+    AUTO_RESTART_BASE_DELAY
+        .saturating_mul(1u32 << attempts.min(16))
+        .min(AUTO_RESTART_MAX_DELAY)
+}
+
+/// Ahead/behind counts of one worktree, gathered before a sync (Ctrl+S).
+struct SyncPreviewItem {
+    session_id: SessionId,
+    branch: String,
+    base_branch: String,
+    counts: Result<(u32, u32), String>,
+}
+
+/// Worktree queued for a sync or its preview: `(session, worktree path,
+/// branch, remote, recorded or configured base branch, strategy)`.
+type RepoWorktree = (
+    SessionId,
+    PathBuf,
+    String,
+    String,
+    Option<String>,
+    SyncStrategy,
+);
+
+/// Spawn held back by the session limit confirmation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum LimitedSpawn {
+    /// New session in the active project (Ctrl+N).
+    New,
+    /// Copy of the active session (`D`).
+    Duplicate,
+}
+
 /// Holds a recently deleted session for undo (Ctrl+Z) support.
 struct PendingDelete {
     session: Session,
     session_id: SessionId,
     project_id: ProjectId,
     created_at: std::time::Instant,
+    /// Leave the session's worktrees on disk when the delete is finalized.
+    keep_worktrees: bool,
 }
 
 pub struct App {
@@ -304,11 +815,28 @@ pub struct App {
     pub(crate) db: Database,
     pub(crate) focus: InputFocus,
     pub(crate) should_quit: bool,
+    /// Quit confirmation (Ctrl+Q with user sessions running).
+    pub(crate) show_quit_modal: bool,
+    /// Spawn awaiting confirmation because `[limits] max_sessions` user
+    /// sessions are already running.
+    pub(crate) session_limit_prompt: Option<LimitedSpawn>,
     pub(crate) status_message: Option<StatusMessage>,
     terminal_rows: u16,
     pub(crate) terminal_cols: u16,
     session_counter: usize,
+    /// Whether the info panel is drawn: the user's choice, suppressed while
+    /// the terminal is narrower than [`layout::INFO_PANEL_MIN_WIDTH`].
     pub(crate) show_info_panel: bool,
+    /// Whether the user wants the info panel (F2/F9), kept across narrow
+    /// resizes so widening the terminal brings the panel back.
+    pub(crate) info_panel_user_pref: bool,
+    /// What the info panel shows (F9).
+    pub(crate) info_panel_mode: info_panel::InfoPanelMode,
+    /// Session tab bar above the terminal instead of the left panel (F4).
+    pub(crate) show_session_tabs: bool,
+    /// Rewrap terminal output to the new width on resize instead of
+    /// truncating it (F8).
+    pub(crate) wrap_lines: bool,
     pub(crate) show_help: bool,
     pub(crate) show_add_project_modal: bool,
     pub(crate) add_project_name: TextInput,
@@ -317,6 +845,13 @@ pub struct App {
     pub(crate) add_project_repos: Vec<PathBuf>,
     pub(crate) add_project_repo_index: usize,
     pub(crate) add_project_path_suggestion: Option<String>,
+    /// Repo path from the add-project modal awaiting a clone URL.
+    pub(crate) add_project_clone_dest: Option<PathBuf>,
+    pub(crate) add_project_clone_url: TextInput,
+    /// Missing repos the user chose to add without cloning (Tab).
+    pub(crate) add_project_clone_skipped: Vec<PathBuf>,
+    /// Clone started from the add-project modal, with its destination.
+    repo_clone_rx: Option<mpsc::Receiver<(PathBuf, Result<(), String>)>>,
     pub(crate) show_edit_project_modal: bool,
     pub(crate) edit_project_name: TextInput,
     pub(crate) edit_project_path: TextInput,
@@ -333,6 +868,12 @@ pub struct App {
     pub(crate) repo_selector_index: usize,
     pub(crate) show_session_mode_modal: bool,
     pub(crate) session_mode_index: usize,
+    /// Kind picked in the session mode modal, applied by the next
+    /// `prepare_spawn`.
+    pub(crate) pending_session_kind: SessionKind,
+    /// Role inherited by the next `prepare_spawn` when duplicating a
+    /// session; skips the role selector.
+    pub(crate) pending_spawn_role: Option<String>,
     pub(crate) show_branch_selector: bool,
     pub(crate) branch_selector_index: usize,
     pub(crate) available_branches: Vec<String>,
@@ -341,10 +882,28 @@ pub struct App {
     pub(crate) show_worktree_name_modal: bool,
     pub(crate) worktree_name_input: TextInput,
     pub(crate) pending_base_branch: Option<String>,
+    /// Why the typed branch name was rejected, shown in the modal.
+    pub(crate) worktree_name_error: Option<String>,
+    /// "Resume by id" prompt for the Claude conversation a new session
+    /// resumes.
+    pub(crate) show_resume_session_modal: bool,
+    pub(crate) resume_session_input: TextInput,
+    /// Why the typed id was rejected, shown in the modal.
+    pub(crate) resume_session_error: Option<String>,
     pub(crate) show_role_selector: bool,
     pub(crate) role_selector_index: usize,
+    /// Remember the role picked in the selector for the project (`r`).
+    pub(crate) role_selector_remember: bool,
+    /// Roles remembered from the selector for the rest of this run, by
+    /// project. New sessions in the project skip the selector until the
+    /// choice is forgotten (`R` in the session list). Unlike
+    /// `default_role`, never persisted.
+    pub(crate) project_default_spawn_role: HashMap<ProjectId, String>,
     pub(crate) pending_spawn_config: Option<SessionConfig>,
     pub(crate) pending_spawn_worktrees: Vec<WorktreeInfo>,
+    /// Paths in `pending_spawn_worktrees` that were reused rather than
+    /// created, so cancelling the spawn must not remove them.
+    pub(crate) pending_spawn_reused_worktrees: Vec<PathBuf>,
     pub(crate) pending_spawn_name: Option<String>,
     pub(crate) show_role_editor: bool,
     pub(crate) role_editor_view: RoleEditorView,
@@ -358,8 +917,16 @@ pub struct App {
     pub(crate) role_editor_system_prompt: TextInput,
     pub(crate) role_editor_env: ToolListState,
     pub(crate) role_editor_editing_index: Option<usize>,
+    /// Role import/export path prompt (`x`/`i` in the Roles list).
+    pub(crate) role_file_action: Option<RoleFileAction>,
+    pub(crate) role_file_path: TextInput,
     pub(crate) edit_project_mcp_servers: Vec<crate::session::McpServerConfig>,
     pub(crate) edit_project_mcp_server_index: usize,
+    pub(crate) edit_project_sync_strategy: SyncStrategy,
+    pub(crate) edit_project_activity_timeout: TextInput,
+    pub(crate) edit_project_worktree_root: TextInput,
+    pub(crate) edit_project_init_command: TextInput,
+    pub(crate) edit_project_default_role: Option<String>,
     pub(crate) show_mcp_editor: bool,
     pub(crate) mcp_editor_field: mcp_editor_modal::McpEditorField,
     pub(crate) mcp_editor_name: TextInput,
@@ -367,6 +934,14 @@ pub struct App {
     pub(crate) mcp_editor_args: ToolListState,
     pub(crate) mcp_editor_env: ToolListState,
     pub(crate) mcp_editor_editing_index: Option<usize>,
+    /// Command that failed PATH lookup on the last submit. A second submit
+    /// with the same command saves anyway.
+    pub(crate) mcp_editor_command_warning: Option<String>,
+    /// Whether the MCP editor saves into the project or the pending spawn.
+    pub(crate) mcp_editor_target: mcp_editor_modal::McpEditorTarget,
+    /// Session-only MCP servers added in the session mode modal (`m`),
+    /// handed to the next spawn.
+    pub(crate) pending_spawn_mcp_servers: Vec<crate::session::McpServerConfig>,
     /// Snapshot of role editor fields at open time for dirty detection.
     pub(crate) role_editor_snapshot: Option<EditorSnapshot>,
     /// Snapshot of MCP editor fields at open time for dirty detection.
@@ -380,18 +955,141 @@ pub struct App {
     worktree_sync_rx: Option<mpsc::Receiver<(SessionId, git::SyncResult)>>,
     worktree_sync_pending: usize,
     worktree_sync_completed: Vec<(SessionId, git::SyncResult)>,
+    /// Pre-sync preview: counts arrive from background fetches, then the
+    /// modal asks for confirmation before [`Self::start_sync`] runs.
+    sync_preview_rx: Option<mpsc::Receiver<SyncPreviewItem>>,
+    sync_preview_pending: usize,
+    sync_preview: Vec<SyncPreviewItem>,
+    pub(crate) show_sync_preview_modal: bool,
+    /// Push of the active session's worktree branch (F5), with the branch
+    /// name, the remote and one result per worktree.
+    branch_push_rx: Option<mpsc::Receiver<(String, String, Vec<git::PushResult>)>>,
+    /// Outcome of the last finished sync, shown as a footer badge.
+    pub(crate) last_sync_summary: Option<SyncSummary>,
     tick_count: u64,
+    /// Whether the screen is stale; see [`Self::needs_redraw`].
+    needs_redraw: bool,
+    /// Latest session output seen by [`Self::tick`] (Unix ms).
+    seen_output_at: u64,
     /// Deferred inputs: `(session_id, data, tick_at_which_to_send)`.
     /// Used to introduce a small delay between pasting text and pressing Enter.
     deferred_inputs: Vec<(SessionId, Vec<u8>, u64)>,
     /// Per-session terminal view state (Claude vs Shell). Defaults to Claude.
     session_terminal_views: HashMap<SessionId, TerminalView>,
+    /// Scrollback offset each session was left at, restored when it becomes
+    /// active again.
+    pub(crate) scrollback_offsets: HashMap<SessionId, usize>,
+    /// Status filter applied to the active project's session list.
+    pub(crate) session_filter: SessionFilter,
+    /// List the active project's sessions oldest first instead of in the
+    /// project's order (`o` in the session list).
+    pub(crate) sort_sessions_by_age: bool,
+    /// Name filter narrowing the project list, if one is set.
+    pub(crate) project_filter: Option<ProjectFilter>,
+    /// Persisted state of sessions restored lazily ([restore] lazy) and not
+    /// yet focused. Used to adopt or resume them on first focus.
+    dormant_sessions: HashMap<SessionId, sync::SharedSession>,
     /// Recently deleted session awaiting finalization or undo (Ctrl+Z).
     pending_delete: Option<PendingDelete>,
+    /// Automatic restart state per session (`[auto_restart]`).
+    pub(crate) auto_restarts: HashMap<SessionId, AutoRestart>,
+    /// When Ctrl+C was last forwarded to each session, so an exit it caused
+    /// is not treated as a crash.
+    pub(crate) interrupted_at: HashMap<SessionId, std::time::Instant>,
     /// Restore deleted sessions modal (Ctrl+U).
     pub(crate) show_restore_sessions_modal: bool,
     pub(crate) restore_sessions_list: Vec<DeletedSessionInfo>,
     pub(crate) restore_sessions_index: usize,
+    /// Confirmation shown when closing a session with dirty worktrees or
+    /// unpushed branches.
+    pub(crate) show_close_session_modal: bool,
+    pub(crate) close_session_id: Option<SessionId>,
+    /// Branches of the session's worktrees that have uncommitted changes.
+    pub(crate) close_session_dirty: Vec<String>,
+    /// Branches only this session has checked out that have unpushed commits.
+    pub(crate) close_session_unpushed: Vec<String>,
+    /// Confirmation for closing every session of the active project (`x`).
+    pub(crate) show_close_all_modal: bool,
+    /// Sessions among them with uncommitted worktree changes.
+    pub(crate) close_all_dirty: usize,
+    /// Fuzzy project/session switcher (Ctrl+P).
+    pub(crate) show_switcher: bool,
+    pub(crate) switcher_input: TextInput,
+    pub(crate) switcher_entries: Vec<SwitcherEntry>,
+    /// Indices into `switcher_entries` matching the filter, best first.
+    pub(crate) switcher_matches: Vec<usize>,
+    pub(crate) switcher_index: usize,
+    /// Rename session modal (Ctrl+,).
+    pub(crate) show_rename_session_modal: bool,
+    pub(crate) rename_session_input: TextInput,
+    pub(crate) rename_session_id: Option<SessionId>,
+    /// Label and label color editor (`t` in the session list).
+    pub(crate) show_session_label_modal: bool,
+    pub(crate) session_label_input: TextInput,
+    pub(crate) session_label_color_input: TextInput,
+    pub(crate) session_label_color_focused: bool,
+    pub(crate) session_label_id: Option<SessionId>,
+    /// Project picker for moving a session (`m` in the session list):
+    /// indices into `projects` of the possible targets.
+    pub(crate) show_move_session_modal: bool,
+    pub(crate) move_session_targets: Vec<usize>,
+    pub(crate) move_session_index: usize,
+    pub(crate) move_session_id: Option<SessionId>,
+    /// Extra `--add-dir` directories editor for the active session (`a` in
+    /// the session list). Applied on the session's next restart.
+    pub(crate) show_session_dirs_modal: bool,
+    pub(crate) session_dirs: ToolListState,
+    pub(crate) session_dirs_id: Option<SessionId>,
+    /// Commit modal for the active worktree session (F6).
+    pub(crate) show_commit_modal: bool,
+    pub(crate) commit_message_input: TextInput,
+    pub(crate) commit_session_id: Option<SessionId>,
+    /// Prompt pasted into the active session in one go (`s` in the session
+    /// list).
+    pub(crate) show_send_prompt_modal: bool,
+    pub(crate) send_prompt_input: TextInput,
+    pub(crate) send_prompt_session_id: Option<SessionId>,
+    /// Scrollback snapshot awaiting a destination path (F7), present while
+    /// the export prompt is open.
+    pub(crate) scrollback_export: Option<Vec<String>>,
+    pub(crate) export_path_input: TextInput,
+    /// Terminal scrollback search, present while a query is open or active.
+    pub(crate) terminal_search: Option<TerminalSearch>,
+    /// Terminal copy-mode selection, present while selecting.
+    pub(crate) terminal_selection: Option<TerminalSelection>,
+    /// System clipboard handle, opened on first yank. Kept alive because on
+    /// X11 the copied text is only served while the handle exists.
+    clipboard: Option<arboard::Clipboard>,
+    /// Git status per worktree path for the info panel, with the time it
+    /// was read. `None` if `git status` failed.
+    pub(crate) worktree_statuses:
+        HashMap<PathBuf, (std::time::Instant, Option<crate::session::WorktreeStatus>)>,
+    /// Busy share and transition count of the info panel's session, with
+    /// the time it was read. `None` until a status change is recorded.
+    activity_summary: Option<(
+        SessionId,
+        std::time::Instant,
+        Option<crate::storage::ActivitySummary>,
+    )>,
+    /// Where the left panel's lists were last drawn, for mouse clicks.
+    /// Written by `view`, which only borrows `self`.
+    left_panel_regions: std::cell::RefCell<Vec<project_list::ListRegion>>,
+    /// User preferences from `config.toml`.
+    pub(crate) settings: settings::Settings,
+    /// Read-only JSON endpoint, running when `[status_server]` is enabled.
+    status_server: Option<status_server::StatusServer>,
+    /// Projects, sessions or statuses changed since the endpoint's snapshot
+    /// was last published.
+    status_snapshot_stale: bool,
+    /// Global keybindings, built from defaults plus `[keybindings]` overrides.
+    pub(crate) keymap: keymap::KeyMap,
+    /// Colors resolved from the `[theme]` config table.
+    pub(crate) theme: Theme,
+    /// Status glyphs and labels resolved from the `[status]` config table.
+    pub(crate) status_icons: status_icons::StatusIcons,
+    /// Panel widths validated from the `[layout]` config table. Shared by
+    /// rendering and PTY sizing via [`App::compute_layout`].
+    pub(crate) panel_ratios: layout::PanelRatios,
 }
 
 /// Snapshot of editor field values for dirty detection.
@@ -407,6 +1105,13 @@ fn shared_project_to_info(sp: sync::SharedProject) -> ProjectInfo {
         repos: sp.repos,
         roles: sp.roles,
         mcp_servers: sp.mcp_servers,
+        sync_base_branch: sp.sync_base_branch,
+        remote: sp.remote,
+        sync_strategy: sp.sync_strategy,
+        activity_timeout_ms: sp.activity_timeout_ms,
+        worktree_root: sp.worktree_root,
+        default_role: sp.default_role,
+        session_init_command: sp.session_init_command,
         id: Some(sp.id.to_string()),
     };
     let mut info = ProjectInfo::new(config);
@@ -414,6 +1119,35 @@ fn shared_project_to_info(sp: sync::SharedProject) -> ProjectInfo {
     info
 }
 
+/// Fold `incoming`, another instance's copy of the project with the same
+/// deterministic id, into `local`: repos are unioned by path and roles by
+/// name. On a role name conflict the incoming definition wins, so both
+/// instances converge on the same set once the merge is saved. Returns
+/// whether `local` changed.
+fn merge_duplicate_project(local: &mut ProjectConfig, incoming: &sync::SharedProject) -> bool {
+    let mut changed = false;
+    for repo in &incoming.repos {
+        if !local.repos.contains(repo) {
+            local.repos.push(repo.clone());
+            changed = true;
+        }
+    }
+    for role in &incoming.roles {
+        match local.roles.iter_mut().find(|r| r.name == role.name) {
+            Some(existing) if existing == role => {}
+            Some(existing) => {
+                *existing = role.clone();
+                changed = true;
+            }
+            None => {
+                local.roles.push(role.clone());
+                changed = true;
+            }
+        }
+    }
+    changed
+}
+
 /// One-time migration: import roles from config.toml into the database.
 ///
 /// If config.toml exists and has projects with roles, and the DB has no roles yet,
@@ -489,6 +1223,13 @@ fn migrate_config_toml_roles(db: &Database) {
                 repos: lp.repos.clone(),
                 roles: Vec::new(),
                 mcp_servers: Vec::new(),
+                sync_base_branch: None,
+                remote: None,
+                sync_strategy: SyncStrategy::default(),
+                activity_timeout_ms: None,
+                worktree_root: None,
+                default_role: None,
+                session_init_command: None,
                 id: None,
             };
             c.deterministic_id()
@@ -545,6 +1286,14 @@ impl App {
 
         let projects = load_projects_from_db(&db);
 
+        // Land on the project that was active when state was last saved
+        let active_project_index = db
+            .get_last_active()
+            .ok()
+            .and_then(|(project, _)| project)
+            .and_then(|id| projects.iter().position(|p| p.id == id))
+            .unwrap_or(0);
+
         // Load session counter from DB
         let session_counter = db.get_session_counter().unwrap_or(0);
 
@@ -558,18 +1307,24 @@ impl App {
 
         Self {
             projects,
-            active_project_index: 0,
+            active_project_index,
             sessions: Vec::new(),
             active_index: 0,
             backend,
             db,
             focus: InputFocus::ProjectList,
             should_quit: false,
+            show_quit_modal: false,
+            session_limit_prompt: None,
             status_message: None,
             terminal_rows: rows,
             terminal_cols: cols,
             session_counter,
             show_info_panel: false,
+            info_panel_user_pref: false,
+            info_panel_mode: info_panel::InfoPanelMode::default(),
+            show_session_tabs: false,
+            wrap_lines: false,
             show_help: false,
             show_add_project_modal: false,
             add_project_name: TextInput::new(),
@@ -578,6 +1333,10 @@ impl App {
             add_project_repos: Vec::new(),
             add_project_repo_index: 0,
             add_project_path_suggestion: None,
+            add_project_clone_dest: None,
+            add_project_clone_url: TextInput::new(),
+            add_project_clone_skipped: Vec::new(),
+            repo_clone_rx: None,
             show_edit_project_modal: false,
             edit_project_name: TextInput::new(),
             edit_project_path: TextInput::new(),
@@ -594,6 +1353,8 @@ impl App {
             repo_selector_index: 0,
             show_session_mode_modal: false,
             session_mode_index: 0,
+            pending_session_kind: SessionKind::Claude,
+            pending_spawn_role: None,
             show_branch_selector: false,
             branch_selector_index: 0,
             available_branches: Vec::new(),
@@ -602,10 +1363,17 @@ impl App {
             show_worktree_name_modal: false,
             worktree_name_input: TextInput::new(),
             pending_base_branch: None,
+            worktree_name_error: None,
+            show_resume_session_modal: false,
+            resume_session_input: TextInput::new(),
+            resume_session_error: None,
             show_role_selector: false,
             role_selector_index: 0,
+            role_selector_remember: false,
+            project_default_spawn_role: HashMap::new(),
             pending_spawn_config: None,
             pending_spawn_worktrees: Vec::new(),
+            pending_spawn_reused_worktrees: Vec::new(),
             pending_spawn_name: None,
             show_role_editor: false,
             role_editor_view: RoleEditorView::List,
@@ -614,13 +1382,20 @@ impl App {
             role_editor_field: role_editor_modal::RoleEditorField::Name,
             role_editor_name: TextInput::new(),
             role_editor_description: TextInput::new(),
-            role_editor_allowed_tools: ToolListState::new(),
-            role_editor_disallowed_tools: ToolListState::new(),
+            role_editor_allowed_tools: ToolListState::with_validator(validate_tool_pattern),
+            role_editor_disallowed_tools: ToolListState::with_validator(validate_tool_pattern),
             role_editor_system_prompt: TextInput::new(),
             role_editor_env: ToolListState::new(),
             role_editor_editing_index: None,
+            role_file_action: None,
+            role_file_path: TextInput::new(),
             edit_project_mcp_servers: Vec::new(),
             edit_project_mcp_server_index: 0,
+            edit_project_sync_strategy: SyncStrategy::default(),
+            edit_project_activity_timeout: TextInput::new(),
+            edit_project_worktree_root: TextInput::new(),
+            edit_project_init_command: TextInput::new(),
+            edit_project_default_role: None,
             show_mcp_editor: false,
             mcp_editor_field: mcp_editor_modal::McpEditorField::Name,
             mcp_editor_name: TextInput::new(),
@@ -628,22 +1403,159 @@ impl App {
             mcp_editor_args: ToolListState::new(),
             mcp_editor_env: ToolListState::new(),
             mcp_editor_editing_index: None,
+            mcp_editor_command_warning: None,
+            mcp_editor_target: mcp_editor_modal::McpEditorTarget::Project,
+            pending_spawn_mcp_servers: Vec::new(),
             role_editor_snapshot: None,
             mcp_editor_snapshot: None,
             show_discard_confirmation: false,
             sync_state,
             worktree_sync_in_progress: false,
             worktree_sync_rx: None,
+            sync_preview_rx: None,
+            sync_preview_pending: 0,
+            sync_preview: Vec::new(),
+            show_sync_preview_modal: false,
             worktree_sync_pending: 0,
             worktree_sync_completed: Vec::new(),
+            branch_push_rx: None,
+            last_sync_summary: None,
             tick_count: 0,
+            needs_redraw: true,
+            seen_output_at: 0,
             deferred_inputs: Vec::new(),
             session_terminal_views: HashMap::new(),
+            scrollback_offsets: HashMap::new(),
+            session_filter: SessionFilter::default(),
+            sort_sessions_by_age: false,
+            project_filter: None,
+            dormant_sessions: HashMap::new(),
             pending_delete: None,
+            auto_restarts: HashMap::new(),
+            interrupted_at: HashMap::new(),
             show_restore_sessions_modal: false,
             restore_sessions_list: Vec::new(),
             restore_sessions_index: 0,
+            show_close_session_modal: false,
+            close_session_id: None,
+            close_session_dirty: Vec::new(),
+            close_session_unpushed: Vec::new(),
+            show_close_all_modal: false,
+            close_all_dirty: 0,
+            show_switcher: false,
+            switcher_input: TextInput::new(),
+            switcher_entries: Vec::new(),
+            switcher_matches: Vec::new(),
+            switcher_index: 0,
+            show_rename_session_modal: false,
+            rename_session_input: TextInput::new(),
+            rename_session_id: None,
+            show_session_label_modal: false,
+            session_label_input: TextInput::new(),
+            session_label_color_input: TextInput::new(),
+            session_label_color_focused: false,
+            session_label_id: None,
+            show_move_session_modal: false,
+            move_session_targets: Vec::new(),
+            move_session_index: 0,
+            move_session_id: None,
+            show_session_dirs_modal: false,
+            session_dirs: ToolListState::with_validator(validate_additional_dir),
+            session_dirs_id: None,
+            show_commit_modal: false,
+            commit_message_input: TextInput::new(),
+            commit_session_id: None,
+            show_send_prompt_modal: false,
+            send_prompt_input: TextInput::new(),
+            send_prompt_session_id: None,
+            scrollback_export: None,
+            export_path_input: TextInput::new(),
+            terminal_search: None,
+            terminal_selection: None,
+            clipboard: None,
+            worktree_statuses: HashMap::new(),
+            activity_summary: None,
+            left_panel_regions: std::cell::RefCell::new(Vec::new()),
+            settings: settings::Settings::default(),
+            status_server: None,
+            status_snapshot_stale: true,
+            keymap: keymap::KeyMap::default(),
+            theme: Theme::default(),
+            status_icons: status_icons::StatusIcons::default(),
+            panel_ratios: layout::PanelRatios::default(),
+        }
+    }
+
+    /// Apply user preferences loaded from the config file.
+    ///
+    /// Invalid keybinding overrides are reported as a status error and the
+    /// default keymap is kept; an invalid `[theme]`, `[status]` icon set,
+    /// `[layout]` or `[scroll]` likewise falls back to the defaults.
+    /// Read `config.toml` and apply it. An invalid file is reported in the
+    /// status bar and the defaults are used.
+    pub fn load_settings(&mut self) {
+        match settings::Settings::load() {
+            Ok(settings) => self.set_settings(settings),
+            Err(e) => {
+                self.set_settings(settings::Settings::default());
+                self.set_error(format!("{e}; using default settings"));
+            }
+        }
+    }
+
+    pub fn set_settings(&mut self, settings: settings::Settings) {
+        match keymap::KeyMap::from_overrides(&settings.keybindings) {
+            Ok(keymap) => self.keymap = keymap,
+            Err(e) => {
+                self.keymap = keymap::KeyMap::default();
+                self.set_error(format!("{e}; using default keybindings"));
+            }
+        }
+        match Theme::from_config(settings.theme.name.as_deref(), &settings.theme.colors) {
+            Ok(theme) => self.theme = theme,
+            Err(e) => {
+                self.theme = Theme::default();
+                self.set_error(format!("{e}; using default theme"));
+            }
         }
+        match status_icons::StatusIcons::from_config(
+            settings.status.preset.as_deref(),
+            &settings.status.icons,
+            &settings.status.labels,
+        ) {
+            Ok(icons) => self.status_icons = icons,
+            Err(e) => {
+                self.status_icons = status_icons::StatusIcons::default();
+                self.set_error(format!("{e}; using default status icons"));
+            }
+        }
+        match layout::PanelRatios::new(settings.layout.list_percent, settings.layout.info_percent) {
+            Ok(ratios) => self.panel_ratios = ratios,
+            Err(e) => {
+                self.panel_ratios = layout::PanelRatios::default();
+                self.set_error(format!("{e}; using default layout"));
+            }
+        }
+        let mut settings = settings;
+        if let Err(e) = settings.scroll.validate() {
+            settings.scroll = settings::ScrollSettings::default();
+            self.set_error(format!("{e}; using default scroll steps"));
+        }
+        self.sync_state
+            .set_interval(std::time::Duration::from_millis(
+                settings.sync.poll_interval_ms,
+            ));
+        if settings.status_server.enabled && self.status_server.is_none() {
+            let port = settings.status_server.port;
+            match status_server::StatusServer::start(port) {
+                Ok(server) => {
+                    tracing::info!("Status endpoint listening on http://{}", server.addr());
+                    self.status_server = Some(server);
+                }
+                Err(e) => self.set_error(format!("Status endpoint on port {port}: {e}")),
+            }
+        }
+        self.settings = settings;
     }
 
     /// Ensure the global admin session and project exist.
@@ -652,8 +1564,14 @@ impl App {
     /// `thurbox-mcp` binary, an "Admin" pseudo-project pinned at index 0,
     /// and spawns an admin session if one doesn't already exist.
     /// The `.mcp.json` is rewritten on every startup to pick up binary path
-    /// changes after upgrades.
+    /// changes after upgrades. With `[admin] enabled = false`, nothing is
+    /// created and an Admin project left by earlier runs is hidden instead.
     pub fn ensure_admin_session(&mut self) {
+        if !self.settings.admin.enabled {
+            self.hide_admin_project();
+            return;
+        }
+
         let Some(admin_dir) = crate::paths::admin_directory() else {
             tracing::warn!("Could not resolve admin directory path");
             return;
@@ -691,13 +1609,54 @@ impl App {
         }
     }
 
+    /// Drop an Admin project loaded from the DB, and its sessions, from the
+    /// lists. The sessions are detached rather than killed and the rows are
+    /// kept, so re-enabling the admin brings them back.
+    fn hide_admin_project(&mut self) {
+        let admin_id = ProjectId::from_name(ADMIN_PROJECT_NAME);
+        let Some(pos) = self.projects.iter().position(|p| p.id == admin_id) else {
+            return;
+        };
+        let project = self.projects.remove(pos);
+        if self.active_project_index > pos {
+            self.active_project_index -= 1;
+        }
+        self.active_project_index = self
+            .active_project_index
+            .min(self.projects.len().saturating_sub(1));
+
+        let active_id = self.sessions.get(self.active_index).map(|s| s.info.id);
+        let (admin_sessions, sessions) = std::mem::take(&mut self.sessions)
+            .into_iter()
+            .partition(|s| project.session_ids.contains(&s.info.id));
+        self.sessions = sessions;
+        for session in admin_sessions {
+            session.detach();
+        }
+        self.active_index = active_id
+            .and_then(|id| self.sessions.iter().position(|s| s.info.id == id))
+            .unwrap_or(0);
+    }
+
+    /// Whether `project_id` is the Admin project while `[admin]` is disabled.
+    fn is_hidden_admin_project(&self, project_id: ProjectId) -> bool {
+        !self.settings.admin.enabled && project_id == ProjectId::from_name(ADMIN_PROJECT_NAME)
+    }
+
     /// Ensure the Admin project exists at index 0.
     fn ensure_admin_project(&mut self, admin_dir: &std::path::Path) {
         let admin_config = ProjectConfig {
-            name: "Admin".to_string(),
+            name: ADMIN_PROJECT_NAME.to_string(),
             repos: vec![admin_dir.to_path_buf()],
             roles: Vec::new(),
             mcp_servers: Vec::new(),
+            sync_base_branch: None,
+            remote: None,
+            sync_strategy: SyncStrategy::default(),
+            activity_timeout_ms: None,
+            worktree_root: None,
+            default_role: None,
+            session_init_command: None,
             id: None,
         };
         let admin_id = admin_config.effective_id();
@@ -727,12 +1686,19 @@ impl App {
 
     /// Spawn a single admin session in the admin directory.
     fn spawn_admin_session(&mut self, admin_dir: PathBuf) {
-        let config = SessionConfig {
+        let config = self.admin_session_config(admin_dir);
+        self.do_spawn_session("admin".to_string(), &config, Vec::new(), Some(0));
+    }
+
+    /// Config for the admin session: the `thurbox` MCP tools pre-allowed and
+    /// the `[admin]` command, if one is configured, in place of `claude`.
+    fn admin_session_config(&self, admin_dir: PathBuf) -> SessionConfig {
+        SessionConfig {
             cwd: Some(admin_dir),
             permissions: admin_mcp_permissions(),
+            command: self.settings.admin.spawn_command(),
             ..SessionConfig::default()
-        };
-        self.do_spawn_session("admin".to_string(), &config, Vec::new(), Some(0));
+        }
     }
 
     /// Count sessions belonging to non-admin projects.
@@ -744,7 +1710,57 @@ impl App {
             .sum()
     }
 
+    /// Quit (Ctrl+Q), asking first if user sessions would be detached and
+    /// `[quit] confirm` is on.
+    pub(crate) fn request_quit(&mut self) {
+        if self.settings.quit.confirm && self.user_session_count() > 0 {
+            self.show_quit_modal = true;
+        } else {
+            self.should_quit = true;
+        }
+    }
+
+    /// Whether another user session would go past `[limits] max_sessions`.
+    fn at_session_limit(&self) -> bool {
+        let max = self.settings.limits.max_sessions;
+        max > 0 && self.user_session_count() >= max
+    }
+
+    /// Run `spawn` now, or ask first when the session limit is reached.
+    /// The admin project is never limited.
+    fn spawn_within_limit(&mut self, spawn: LimitedSpawn) {
+        if self.active_project().is_some_and(|p| !p.is_admin) && self.at_session_limit() {
+            self.session_limit_prompt = Some(spawn);
+            return;
+        }
+        self.run_limited_spawn(spawn);
+    }
+
+    fn run_limited_spawn(&mut self, spawn: LimitedSpawn) {
+        match spawn {
+            LimitedSpawn::New => self.start_new_session(),
+            LimitedSpawn::Duplicate => self.start_duplicate_session(),
+        }
+    }
+
+    /// Spawn past the session limit after confirmation.
+    pub(crate) fn confirm_session_limit(&mut self) {
+        if let Some(spawn) = self.session_limit_prompt.take() {
+            self.run_limited_spawn(spawn);
+        }
+    }
+
+    /// Start a new session in the active project (Ctrl+N), asking first if
+    /// the session limit is reached.
     pub fn spawn_session(&mut self) {
+        self.spawn_within_limit(LimitedSpawn::New);
+    }
+
+    fn start_new_session(&mut self) {
+        // Extras left over from a cancelled spawn must not leak into this one.
+        self.pending_spawn_mcp_servers.clear();
+        self.pending_session_kind = SessionKind::Claude;
+        self.pending_spawn_role = None;
         let Some(project) = self.active_project() else {
             return;
         };
@@ -778,17 +1794,121 @@ impl App {
         }
     }
 
-    pub(crate) fn spawn_session_in_repo(&mut self, repo_path: PathBuf) {
+    /// Config for a plain (non-worktree) session in the repos picked before
+    /// the session mode modal: a multi-repo project runs in its first repo
+    /// with the rest as additional directories.
+    pub(crate) fn take_pending_repo_config(&mut self) -> Option<SessionConfig> {
+        if let Some(all_repos) = self.pending_all_repos.take() {
+            self.pending_repo_path = None;
+            let (first, rest) = all_repos.split_first()?;
+            Some(SessionConfig {
+                cwd: Some(first.clone()),
+                additional_dirs: rest.to_vec(),
+                ..SessionConfig::default()
+            })
+        } else {
+            let path = self.pending_repo_path.take()?;
+            Some(SessionConfig {
+                cwd: Some(path),
+                ..SessionConfig::default()
+            })
+        }
+    }
+
+    pub(crate) fn open_resume_session_modal(&mut self) {
+        self.resume_session_input.clear();
+        self.resume_session_error = None;
+        self.show_resume_session_modal = true;
+    }
+
+    /// Spawn a Claude session in the pending repos that resumes the typed
+    /// conversation id. An invalid id keeps the modal open with the reason.
+    pub(crate) fn submit_resume_session(&mut self) {
+        let id = self.resume_session_input.value().trim().to_string();
+        if let Err(reason) = crate::session::validate_claude_session_id(&id) {
+            self.resume_session_error = Some(reason);
+            return;
+        }
+        self.show_resume_session_modal = false;
+        self.resume_session_input.clear();
+        let Some(mut config) = self.take_pending_repo_config() else {
+            return;
+        };
+        config.resume_session_id = Some(id.clone());
+        config.claude_session_id = Some(id);
+        self.spawn_session_with_config(&config);
+    }
+
+    pub(crate) fn close_resume_session_modal(&mut self) {
+        self.show_resume_session_modal = false;
+        self.resume_session_input.clear();
+        self.resume_session_error = None;
+        self.pending_repo_path = None;
+        self.pending_all_repos = None;
+        self.pending_spawn_mcp_servers.clear();
+    }
+
+    /// Spawn another session like the active one: same role, session-only
+    /// MCP servers and directories. For a worktree session, prompts for a
+    /// new branch created from the session's branch in the same repos.
+    pub(crate) fn duplicate_active_session(&mut self) {
+        if self.active_project().map_or(true, |p| p.is_admin) {
+            return;
+        }
+        self.spawn_within_limit(LimitedSpawn::Duplicate);
+    }
+
+    fn start_duplicate_session(&mut self) {
+        let Some(session) = self.sessions.get(self.active_index) else {
+            return;
+        };
+        let info = &session.info;
+        let kind = if info.claude_session_id.is_none() && info.role == "shell" {
+            SessionKind::Shell
+        } else {
+            SessionKind::Claude
+        };
+        let role = info.role.clone();
+        let extra_mcp_servers = info.extra_mcp_servers.clone();
+        let worktrees = info.worktrees.clone();
         let config = SessionConfig {
-            cwd: Some(repo_path),
+            cwd: info.cwd.clone(),
+            additional_dirs: info.additional_dirs.clone(),
             ..SessionConfig::default()
         };
-        self.spawn_session_with_config(&config);
+
+        self.pending_session_kind = kind;
+        self.pending_spawn_role = Some(role);
+        self.pending_spawn_mcp_servers = extra_mcp_servers;
+
+        let Some(first) = worktrees.first() else {
+            self.prepare_spawn(config, Vec::new());
+            return;
+        };
+        let repos: Vec<PathBuf> = worktrees.iter().map(|w| w.repo_path.clone()).collect();
+        self.worktree_name_input.set(&format!("{}-2", first.branch));
+        self.pending_base_branch = Some(first.branch.clone());
+        if repos.len() > 1 {
+            self.pending_repo_path = Some(repos[0].clone());
+            self.pending_all_repos = Some(repos);
+        } else {
+            self.pending_repo_path = repos.into_iter().next();
+            self.pending_all_repos = None;
+        }
+        self.show_worktree_name_modal = true;
     }
 
-    fn next_session_name(&mut self) -> String {
+    /// Advance the counter and name the next session after the configured
+    /// scheme. `worktrees` are the ones the session will run in.
+    fn next_session_name(&mut self, worktrees: &[WorktreeInfo]) -> String {
         self.session_counter += 1;
-        self.session_counter.to_string()
+        let branch = worktrees.first().map(|wt| wt.branch.as_str());
+        session_name(
+            &self.settings.session_names,
+            self.session_counter,
+            branch,
+            |name| self.sessions.iter().any(|s| s.info.name == name),
+        )
     }
 
     pub(crate) fn spawn_session_with_config(&mut self, config: &SessionConfig) {
@@ -799,17 +1919,55 @@ impl App {
     ///
     /// Assigns a session name, then spawns immediately if no roles or exactly
     /// one role is configured, or shows the role selector modal for 2+ roles.
+    /// Shell sessions skip roles entirely.
     pub(crate) fn prepare_spawn(
         &mut self,
         mut config: SessionConfig,
         worktrees: Vec<WorktreeInfo>,
     ) {
-        let name = self.next_session_name();
+        let name = self.next_session_name(&worktrees);
+        config.kind = std::mem::take(&mut self.pending_session_kind);
+        if config.kind == SessionKind::Shell {
+            self.pending_spawn_mcp_servers.clear();
+            config.role = "shell".to_string();
+            self.do_spawn_session(name, &config, worktrees, None);
+            return;
+        }
+        config
+            .extra_mcp_servers
+            .append(&mut self.pending_spawn_mcp_servers);
+        if let Some(role) = self.pending_spawn_role.take() {
+            config.permissions = self.resolve_role_permissions(&role);
+            config.role = role;
+            self.do_spawn_session(name, &config, worktrees, None);
+            return;
+        }
         let Some(project) = self.active_project() else {
             return;
         };
         let roles = &project.config.roles;
 
+        // A role remembered from the selector this run, or else a configured
+        // default role, skips the selector. One that no longer names a role
+        // is ignored.
+        if let Some(role) = self
+            .project_default_spawn_role
+            .get(&project.id)
+            .filter(|name| roles.iter().any(|r| &r.name == *name))
+            .or_else(|| {
+                project
+                    .config
+                    .default_role
+                    .as_ref()
+                    .filter(|name| roles.iter().any(|r| &r.name == *name))
+            })
+        {
+            config.role = role.clone();
+            config.permissions = self.resolve_role_permissions(&config.role);
+            self.do_spawn_session(name, &config, worktrees, None);
+            return;
+        }
+
         match roles.len() {
             0 => {
                 // No roles configured — spawn with default (empty) permissions.
@@ -827,6 +1985,7 @@ impl App {
                 self.pending_spawn_config = Some(config);
                 self.pending_spawn_worktrees = worktrees;
                 self.role_selector_index = 0;
+                self.role_selector_remember = false;
                 self.show_role_selector = true;
             }
         }
@@ -836,34 +1995,221 @@ impl App {
         let Some(session) = self.sessions.get(self.active_index) else {
             return;
         };
-        let Some(claude_session_id) = session.info.claude_session_id.clone() else {
+        let session_id = session.info.id;
+        let Some(config) = self.restart_config(self.active_index) else {
             return;
         };
+        if let Some(cwd) = missing_cwd(config.cwd.as_deref()) {
+            self.set_app_error(AppError::MissingCwd(cwd.to_path_buf()));
+            return;
+        }
 
-        let role = session.info.role.clone();
-        let cwd = session.info.cwd.clone();
-        let additional_dirs = session.info.additional_dirs.clone();
+        let (rows, cols) = self.content_area_size();
+        let session = &mut self.sessions[self.active_index];
+        // A manual restart is the user intervening, so it clears any crash loop.
+        session.info.reset_crash_loop();
+        session.info.cwd_missing = false;
+        session.info.record_restart(sync::current_time_millis());
+        self.auto_restarts.remove(&session_id);
+        match session.restart(&config, rows, cols) {
+            Ok(()) => {
+                self.save_state();
+                self.set_status(StatusLevel::Info, "Session restarted");
+            }
+            Err(e) => {
+                error!("Failed to restart session: {e}");
+                self.set_app_error(AppError::Backend {
+                    action: "restart session",
+                    reason: format!("{e:#}"),
+                });
+            }
+        }
+    }
 
-        let permissions = self.resolve_role_permissions(&role);
-        let config = SessionConfig {
+    /// Config that resumes the session at `index` in place, using the role
+    /// permissions and MCP servers of the project that owns it. `None` if the
+    /// session has no Claude session ID to resume.
+    fn restart_config(&self, index: usize) -> Option<SessionConfig> {
+        let info = &self.sessions.get(index)?.info;
+        let claude_session_id = info.claude_session_id.clone()?;
+
+        // Find the project that owns this session (may not be the active project)
+        let project_index = self
+            .projects
+            .iter()
+            .position(|p| p.session_ids.contains(&info.id))
+            .unwrap_or(self.active_project_index);
+        let permissions = self.resolve_role_permissions_for_project(&info.role, project_index);
+
+        let mut config = SessionConfig {
+            kind: SessionKind::Claude,
             resume_session_id: Some(claude_session_id.clone()),
             claude_session_id: Some(claude_session_id),
-            cwd,
-            additional_dirs,
-            role,
+            cwd: info.cwd.clone(),
+            additional_dirs: info.additional_dirs.clone(),
+            role: info.role.clone(),
             permissions,
+            log_output: info.log_output,
+            extra_mcp_servers: info.extra_mcp_servers.clone(),
+            mcp_config: None,
+            command: self
+                .projects
+                .get(project_index)
+                .filter(|p| p.is_admin)
+                .and_then(|_| self.settings.admin.spawn_command()),
+        };
+        Self::attach_mcp_config(&mut config, self.projects.get(project_index));
+        Some(config)
+    }
+
+    /// Merge the project's MCP servers with the session's extras and, when
+    /// there are any, write them to the session's config file so the spawn
+    /// passes it as `--mcp-config`.
+    fn attach_mcp_config(config: &mut SessionConfig, project: Option<&ProjectInfo>) {
+        config.mcp_config = None;
+        let project_servers = project.map_or(&[][..], |p| p.config.mcp_servers.as_slice());
+        let servers = merge_mcp_servers(project_servers, &config.extra_mcp_servers);
+        if !servers.iter().any(|s| s.enabled) {
+            return;
+        }
+        let Some(path) = config
+            .claude_session_id
+            .as_deref()
+            .and_then(mcp_config::session_mcp_config_path)
+        else {
+            return;
+        };
+        match mcp_config::write_mcp_config(&path, &servers) {
+            Ok(()) => config.mcp_config = Some(path),
+            Err(e) => tracing::warn!("Failed to write MCP config {}: {e}", path.display()),
+        }
+    }
+
+    /// Schedule, fire or abandon automatic restarts for sessions whose
+    /// process exited without the user asking. Each consecutive restart
+    /// waits twice as long as the last; after `max_attempts` the session is
+    /// flagged as crash looping until the user restarts it with Ctrl+R.
+    pub(crate) fn process_auto_restarts(&mut self, now: std::time::Instant) {
+        if !self.settings.auto_restart.enabled {
+            return;
+        }
+        let max_attempts = self.settings.auto_restart.max_attempts;
+        let mut due = Vec::new();
+        let mut gave_up = Vec::new();
+
+        for session in &mut self.sessions {
+            let id = session.info.id;
+            let state = self.auto_restarts.get(&id).copied();
+
+            if !session.has_exited() {
+                let settled = match state {
+                    Some(AutoRestart::Running { since, .. }) => {
+                        now.duration_since(since) >= AUTO_RESTART_STABLE_AFTER
+                    }
+                    Some(_) => true,
+                    None => false,
+                };
+                if settled {
+                    self.auto_restarts.remove(&id);
+                }
+                continue;
+            }
+
+            let attempts = match state {
+                Some(AutoRestart::Stopped) => continue,
+                Some(AutoRestart::Scheduled { due: at, .. }) => {
+                    if at <= now {
+                        due.push(id);
+                    }
+                    continue;
+                }
+                Some(AutoRestart::Running { attempts, .. }) => attempts,
+                None => 0,
+            };
+
+            let interrupted = self
+                .interrupted_at
+                .remove(&id)
+                .is_some_and(|at| now.duration_since(at) <= INTERRUPT_GRACE);
+            let next = if interrupted
+                || session.info.crash_looping
+                || session.info.claude_session_id.is_none()
+            {
+                AutoRestart::Stopped
+            } else if attempts >= max_attempts {
+                session.info.crash_looping = true;
+                session.info.status = SessionStatus::Error;
+                gave_up.push(session.info.name.clone());
+                AutoRestart::Stopped
+            } else {
+                AutoRestart::Scheduled {
+                    attempts,
+                    due: now + auto_restart_delay(attempts),
+                }
+            };
+            self.auto_restarts.insert(id, next);
+        }
+
+        for name in gave_up {
+            self.set_error(format!(
+                "'{name}' keeps exiting; gave up restarting. Ctrl+R to retry"
+            ));
+        }
+        for id in due {
+            self.auto_restart_session(id, now);
+        }
+    }
+
+    /// Fire a scheduled automatic restart for session `id`.
+    fn auto_restart_session(&mut self, id: SessionId, now: std::time::Instant) {
+        let Some(index) = self.sessions.iter().position(|s| s.info.id == id) else {
+            self.auto_restarts.remove(&id);
+            return;
+        };
+        let Some(AutoRestart::Scheduled { attempts, .. }) = self.auto_restarts.get(&id).copied()
+        else {
+            return;
+        };
+        let Some(config) = self.restart_config(index) else {
+            self.auto_restarts.insert(id, AutoRestart::Stopped);
+            return;
         };
 
         let (rows, cols) = self.content_area_size();
-        let session = &mut self.sessions[self.active_index];
+        let session = &mut self.sessions[index];
+        let name = session.info.name.clone();
+        if session.info.record_restart(sync::current_time_millis()) {
+            session.info.status = SessionStatus::Error;
+            self.auto_restarts.insert(id, AutoRestart::Stopped);
+            self.set_error(format!(
+                "'{name}' is crash looping. Ctrl+R to reset and restart"
+            ));
+            return;
+        }
+
+        // A failed restart still counts as an attempt; the session stays
+        // exited, so the next tick schedules the following one.
+        self.auto_restarts.insert(
+            id,
+            AutoRestart::Running {
+                attempts: attempts + 1,
+                since: now,
+            },
+        );
         match session.restart(&config, rows, cols) {
             Ok(()) => {
                 self.save_state();
-                self.set_status(StatusLevel::Info, "Session restarted");
+                self.set_status(
+                    StatusLevel::Info,
+                    format!(
+                        "Restarted '{name}' after it exited (attempt {}/{})",
+                        attempts + 1,
+                        self.settings.auto_restart.max_attempts
+                    ),
+                );
             }
             Err(e) => {
-                error!("Failed to restart session: {e}");
-                self.set_error(format!("Failed to restart session: {e:#}"));
+                error!("Failed to auto-restart session {id}: {e}");
             }
         }
     }
@@ -891,6 +2237,129 @@ impl App {
             }
         }
 
+        let info = &self.sessions[self.active_index].info;
+        let on_close = self.settings.close.worktrees;
+        if info.worktrees.is_empty() || on_close == settings::WorktreeOnClose::Keep {
+            self.delete_active_session(on_close == settings::WorktreeOnClose::Keep);
+            return;
+        }
+
+        // Worktrees are force-removed once the undo window passes, so ask
+        // before throwing away uncommitted work, or the last checkout of a
+        // branch whose commits only exist locally.
+        let dirty_branches = dirty_worktree_branches(&info.worktrees);
+        let unpushed_branches = unpushed_worktree_branches(info, &self.sessions);
+        if !dirty_branches.is_empty()
+            || !unpushed_branches.is_empty()
+            || on_close == settings::WorktreeOnClose::Ask
+        {
+            self.close_session_id = Some(session_id);
+            self.close_session_dirty = dirty_branches;
+            self.close_session_unpushed = unpushed_branches;
+            self.show_close_session_modal = true;
+            return;
+        }
+
+        self.delete_active_session(false);
+    }
+
+    /// Close the session held by the close confirmation: `y` removes its
+    /// worktrees, `k` keeps them on disk.
+    pub(crate) fn confirm_close_session(&mut self, keep_worktrees: bool) {
+        let id = self.close_session_id;
+        self.dismiss_close_session_modal();
+        let Some(index) = id.and_then(|id| self.sessions.iter().position(|s| s.info.id == id))
+        else {
+            return;
+        };
+        self.active_index = index;
+        self.delete_active_session(keep_worktrees);
+    }
+
+    /// Keep the session held by the dirty-worktree confirmation (`n`/Esc).
+    pub(crate) fn cancel_close_session(&mut self) {
+        self.dismiss_close_session_modal();
+        self.set_status(StatusLevel::Info, "Session kept");
+    }
+
+    pub(crate) fn dismiss_close_session_modal(&mut self) {
+        self.show_close_session_modal = false;
+        self.close_session_id = None;
+        self.close_session_dirty.clear();
+        self.close_session_unpushed.clear();
+    }
+
+    /// Ask before closing every session of the active project (`x` in the
+    /// project list). The project itself is kept.
+    pub(crate) fn open_close_all_modal(&mut self) {
+        let Some(project) = self.active_project() else {
+            return;
+        };
+        if project.is_admin {
+            self.set_error("Cannot close admin sessions");
+            return;
+        }
+        let sessions: Vec<&Session> = project
+            .session_ids
+            .iter()
+            .filter_map(|id| self.sessions.iter().find(|s| s.info.id == *id))
+            .collect();
+        if sessions.is_empty() {
+            self.set_status(StatusLevel::Info, "No sessions to close");
+            return;
+        }
+        let dirty = sessions
+            .iter()
+            .filter(|s| !dirty_worktree_branches(&s.info.worktrees).is_empty())
+            .count();
+        self.close_all_dirty = dirty;
+        self.show_close_all_modal = true;
+    }
+
+    /// Close every session of the active project, as if each were closed
+    /// with `Ctrl+X`; worktrees are removed unless `keep_worktrees`. The
+    /// deletes are finalized right away, so they are restored with
+    /// `Ctrl+U` rather than undone with `Ctrl+Z`.
+    pub(crate) fn confirm_close_all_sessions(&mut self, keep_worktrees: bool) {
+        self.show_close_all_modal = false;
+        self.close_all_dirty = 0;
+        let Some(project) = self.active_project() else {
+            return;
+        };
+        if project.is_admin {
+            return;
+        }
+        let project_name = project.config.name.clone();
+        let ids = project.session_ids.clone();
+
+        let mut closed = 0;
+        for id in ids {
+            // Indices shift as sessions are removed, so look each one up.
+            let Some(index) = self.sessions.iter().position(|s| s.info.id == id) else {
+                continue;
+            };
+            self.active_index = index;
+            self.delete_active_session(keep_worktrees);
+            closed += 1;
+        }
+        self.finalize_pending_delete();
+
+        let noun = if closed == 1 { "session" } else { "sessions" };
+        self.set_status(
+            StatusLevel::Info,
+            format!("Closed {closed} {noun} in '{project_name}'"),
+        );
+    }
+
+    /// Soft-delete the active session and hold it for undo (Ctrl+Z). The
+    /// backend is killed and, unless `keep_worktrees`, worktrees removed
+    /// when the delete is finalized.
+    fn delete_active_session(&mut self, keep_worktrees: bool) {
+        let Some(session) = self.sessions.get(self.active_index) else {
+            return;
+        };
+        let session_id = session.info.id;
+
         // Find the project this session belongs to
         let project_id = self
             .projects
@@ -910,6 +2379,10 @@ impl App {
 
         // Clean up terminal view state
         self.session_terminal_views.remove(&session_id);
+        self.scrollback_offsets.remove(&session_id);
+        self.dormant_sessions.remove(&session_id);
+        self.auto_restarts.remove(&session_id);
+        self.interrupted_at.remove(&session_id);
 
         // Remove session from its project
         for project in &mut self.projects {
@@ -930,31 +2403,50 @@ impl App {
             session_id,
             project_id,
             created_at: std::time::Instant::now(),
+            keep_worktrees,
         });
 
+        let kept = if keep_worktrees {
+            " (worktree kept)"
+        } else {
+            ""
+        };
         self.set_status(
             StatusLevel::Info,
-            format!("Deleted '{session_name}'. Ctrl+Z to undo"),
+            format!("Deleted '{session_name}'{kept}. Ctrl+Z to undo"),
         );
 
         // Sync to shared state for other instances
         self.save_state();
     }
 
+    /// Configured worktree root of the project with `project_id`, if any.
+    fn project_worktree_root(&self, project_id: ProjectId) -> Option<PathBuf> {
+        self.projects
+            .iter()
+            .find(|p| p.id == project_id)
+            .and_then(|p| p.config.worktree_root.clone())
+    }
+
     /// Recreate git worktrees from shared worktree metadata.
     ///
     /// For each worktree whose branch still exists, runs `git worktree add`
-    /// to restore it. Returns the successfully recreated worktrees.
-    fn recreate_worktrees(worktrees: &[SharedWorktree]) -> Vec<WorktreeInfo> {
+    /// under `worktree_root` to restore it. Returns the successfully
+    /// recreated worktrees.
+    fn recreate_worktrees(
+        worktrees: &[SharedWorktree],
+        worktree_root: Option<&Path>,
+    ) -> Vec<WorktreeInfo> {
         let mut infos = Vec::new();
         for wt in worktrees {
             if git::branch_exists(&wt.repo_path, &wt.branch) {
-                match git::add_existing_worktree(&wt.repo_path, &wt.branch) {
+                match git::add_existing_worktree(&wt.repo_path, &wt.branch, worktree_root) {
                     Ok(wt_path) => {
                         infos.push(WorktreeInfo {
                             repo_path: wt.repo_path.clone(),
                             worktree_path: wt_path,
                             branch: wt.branch.clone(),
+                            base_branch: wt.base_branch.clone(),
                         });
                     }
                     Err(e) => {
@@ -966,13 +2458,12 @@ impl App {
         infos
     }
 
-    /// Finalize a pending delete — kill backend and remove worktrees.
+    /// Finalize a pending delete — kill backend and remove worktrees
+    /// unless they are being kept.
     fn finalize_pending_delete(&mut self) {
         if let Some(pending) = self.pending_delete.take() {
-            for wt in &pending.session.info.worktrees {
-                if let Err(e) = git::remove_worktree(&wt.repo_path, &wt.worktree_path) {
-                    error!("Failed to remove worktree: {e}");
-                }
+            if !pending.keep_worktrees {
+                remove_worktrees(&pending.session.info.worktrees);
             }
             pending.session.kill();
         }
@@ -986,7 +2477,10 @@ impl App {
 
         if let Err(e) = self.db.restore_session(pending.session_id) {
             error!("Failed to restore session in DB: {e}");
-            self.set_error("Failed to undo delete");
+            self.set_app_error(AppError::Db {
+                action: "undo delete",
+                reason: e.to_string(),
+            });
             return;
         }
 
@@ -1013,119 +2507,612 @@ impl App {
             }
             Err(e) => {
                 error!("Failed to list deleted sessions: {e}");
-                self.set_error("Failed to list deleted sessions");
+                self.set_app_error(AppError::Db {
+                    action: "list deleted sessions",
+                    reason: e.to_string(),
+                });
             }
         }
     }
 
-    /// Restore a soft-deleted session: un-delete in DB, recreate worktrees, and spawn.
-    fn restore_deleted_session(&mut self, deleted: DeletedSessionInfo) {
-        if let Err(e) = self.db.restore_session(deleted.id) {
-            error!("Failed to restore session in DB: {e}");
-            self.set_error("Failed to restore session");
+    /// Open the rename modal for the active session (Ctrl+,).
+    pub(crate) fn open_rename_session_modal(&mut self) {
+        let Some(session) = self.sessions.get(self.active_index) else {
             return;
-        }
+        };
+        self.rename_session_input.set(&session.info.name);
+        self.rename_session_id = Some(session.info.id);
+        self.show_rename_session_modal = true;
+    }
 
-        let worktree_infos = Self::recreate_worktrees(&deleted.worktrees);
-        let cwd = worktree_infos
-            .first()
-            .map(|wt| wt.worktree_path.clone())
-            .or(deleted.cwd.clone());
+    /// Apply the rename and persist it. Only the display name changes; the
+    /// backend ID (tmux window) is left untouched.
+    pub(crate) fn submit_rename_session(&mut self) {
+        let name = self.rename_session_input.value().trim().to_string();
+        if name.is_empty() {
+            self.set_error("Session name cannot be empty");
+            return;
+        }
 
-        let permissions = self.resolve_role_permissions(&deleted.role);
-        let config = SessionConfig {
-            resume_session_id: deleted.claude_session_id.clone(),
-            claude_session_id: deleted.claude_session_id,
-            cwd,
-            additional_dirs: Vec::new(),
-            role: deleted.role,
-            permissions,
+        let Some(id) = self.rename_session_id else {
+            self.close_rename_session_modal();
+            return;
+        };
+        let Some(session) = self.sessions.iter_mut().find(|s| s.info.id == id) else {
+            self.close_rename_session_modal();
+            self.set_error("Session no longer exists");
+            return;
         };
 
-        let session_name = deleted.name.clone();
-        let (rows, cols) = self.content_area_size();
-
-        match Session::spawn(session_name.clone(), rows, cols, &config, &self.backend) {
-            Ok(mut session) => {
-                session.info.id = deleted.id;
-                session.info.worktrees = worktree_infos;
-                let session_id = session.info.id;
-                self.sessions.push(session);
-                self.active_index = self.sessions.len() - 1;
-                self.focus = InputFocus::Terminal;
+        session.info.name = name;
+        self.save_state();
+        self.close_rename_session_modal();
+        self.set_status(StatusLevel::Info, "Session renamed");
+    }
 
-                self.associate_session_with_project(session_id, deleted.project_id);
-                self.save_state();
+    pub(crate) fn close_rename_session_modal(&mut self) {
+        self.show_rename_session_modal = false;
+        self.rename_session_input.clear();
+        self.rename_session_id = None;
+    }
 
-                self.set_status(StatusLevel::Success, format!("Restored '{session_name}'"));
-            }
-            Err(e) => {
-                error!("Failed to spawn restored session: {e}");
-                self.set_error(format!("Failed to restore session: {e:#}"));
+    /// Open the label editor for the active session (`t` in the session list).
+    pub(crate) fn open_session_label_modal(&mut self) {
+        let Some(session) = self.sessions.get(self.active_index) else {
+            return;
+        };
+        self.session_label_input
+            .set(session.info.label.as_deref().unwrap_or_default());
+        self.session_label_color_input
+            .set(session.info.label_color.as_deref().unwrap_or_default());
+        self.session_label_color_focused = false;
+        self.session_label_id = Some(session.info.id);
+        self.show_session_label_modal = true;
+    }
+
+    /// Apply the label and color and persist them. Empty fields clear them;
+    /// an unrecognised color keeps the modal open.
+    pub(crate) fn submit_session_label(&mut self) {
+        let label =
+            Some(self.session_label_input.value().trim().to_string()).filter(|l| !l.is_empty());
+        let color = Some(self.session_label_color_input.value().trim().to_string())
+            .filter(|c| !c.is_empty());
+        if let Some(color) = color.as_deref() {
+            if crate::ui::parse_color(color).is_none() {
+                self.set_error(format!("Unknown color '{color}'"));
+                return;
             }
         }
-    }
 
-    /// Get sessions belonging to the active project.
-    pub(crate) fn active_project_sessions(&self) -> Vec<usize> {
-        match self.active_project() {
-            Some(project) => self
-                .sessions
-                .iter()
-                .enumerate()
-                .filter(|(_, s)| project.session_ids.contains(&s.info.id))
-                .map(|(i, _)| i)
-                .collect(),
-            None => Vec::new(),
-        }
+        let Some(id) = self.session_label_id else {
+            self.close_session_label_modal();
+            return;
+        };
+        let Some(session) = self.sessions.iter_mut().find(|s| s.info.id == id) else {
+            self.close_session_label_modal();
+            self.set_error("Session no longer exists");
+            return;
+        };
+
+        let message = if label.is_some() || color.is_some() {
+            format!("Labeled '{}'", session.info.name)
+        } else {
+            format!("Cleared label of '{}'", session.info.name)
+        };
+        session.info.label = label;
+        session.info.label_color = color;
+        self.save_state();
+        self.close_session_label_modal();
+        self.set_status(StatusLevel::Info, message);
     }
 
-    /// Get the active session's index within the active project's session list.
-    pub(crate) fn active_session_in_project(&self) -> usize {
-        let project_sessions = self.active_project_sessions();
-        project_sessions
-            .iter()
-            .position(|&i| i == self.active_index)
-            .unwrap_or(0)
+    pub(crate) fn close_session_label_modal(&mut self) {
+        self.show_session_label_modal = false;
+        self.session_label_input.clear();
+        self.session_label_color_input.clear();
+        self.session_label_color_focused = false;
+        self.session_label_id = None;
     }
 
-    /// Ensure a session is associated with a project.
-    /// Tries to add to the session's original project, falling back to the first project.
-    fn associate_session_with_project(&mut self, session_id: SessionId, project_id: ProjectId) {
-        let mut found_project = false;
-        if let Some(project) = self.projects.iter_mut().find(|p| p.id == project_id) {
-            if !project.session_ids.contains(&session_id) {
-                project.session_ids.push(session_id);
-            }
-            found_project = true;
+    /// Open the project picker to move the active session to another
+    /// project (`m` in the session list). Admin sessions stay put.
+    pub(crate) fn open_move_session_modal(&mut self) {
+        let Some(session_id) = self.sessions.get(self.active_index).map(|s| s.info.id) else {
+            return;
+        };
+        let current = self
+            .projects
+            .iter()
+            .position(|p| p.session_ids.contains(&session_id));
+        if current.is_some_and(|i| self.projects[i].is_admin) {
+            self.set_error("Cannot move admin session");
+            return;
         }
+        let targets: Vec<usize> = (0..self.projects.len())
+            .filter(|&i| Some(i) != current && !self.projects[i].is_admin)
+            .collect();
+        if targets.is_empty() {
+            self.set_error("No other project to move the session to");
+            return;
+        }
+        self.move_session_targets = targets;
+        self.move_session_index = 0;
+        self.move_session_id = Some(session_id);
+        self.show_move_session_modal = true;
+    }
+
+    /// Move the session to the project selected in the picker.
+    pub(crate) fn submit_move_session(&mut self) {
+        let target = self
+            .move_session_targets
+            .get(self.move_session_index)
+            .copied();
+        let id = self.move_session_id;
+        self.close_move_session_modal();
+        let (Some(target), Some(id)) = (target, id) else {
+            return;
+        };
+        let Some(name) = self
+            .sessions
+            .iter()
+            .find(|s| s.info.id == id)
+            .map(|s| s.info.name.clone())
+        else {
+            self.set_error("Session no longer exists");
+            return;
+        };
 
-        // If session's project doesn't exist in this instance, add to the first project
-        if !found_project {
-            if let Some(project) = self.projects.first_mut() {
-                if !project.session_ids.contains(&session_id) {
-                    project.session_ids.push(session_id);
-                }
-            }
+        self.move_session_to_project(id, target);
+        self.save_state();
+        if let Err(e) = self
+            .db
+            .set_session_order(&self.projects[target].session_ids)
+        {
+            error!("Failed to save session order: {e}");
         }
+        // The moved session left the active project's list.
+        self.sync_active_session_to_project();
+        let project = self.projects[target].config.name.clone();
+        self.set_status(StatusLevel::Info, format!("Moved '{name}' to '{project}'"));
     }
 
-    /// Apply shared session metadata to a local session info.
-    /// Used when updating or adopting sessions from shared state.
-    fn apply_shared_session_metadata(session: &mut Session, shared: &sync::SharedSession) {
-        session.info.name = shared.name.clone();
-        session.info.role = shared.role.clone();
-        session.info.cwd = shared.cwd.clone();
-        session.info.additional_dirs = shared.additional_dirs.clone();
-        session.info.claude_session_id = shared.claude_session_id.clone();
-        session.info.worktrees = shared.worktrees.iter().cloned().map(Into::into).collect();
+    pub(crate) fn close_move_session_modal(&mut self) {
+        self.show_move_session_modal = false;
+        self.move_session_targets.clear();
+        self.move_session_index = 0;
+        self.move_session_id = None;
     }
 
-    pub fn update(&mut self, msg: AppMessage) {
-        match msg {
-            AppMessage::KeyPress(code, mods) => self.handle_key(code, mods),
-            AppMessage::MouseScrollUp => self.scroll_terminal_up(MOUSE_SCROLL_LINES),
-            AppMessage::MouseScrollDown => self.scroll_terminal_down(MOUSE_SCROLL_LINES),
+    /// Make project `target` the only one listing `session_id`, appending it
+    /// to the end of that project's sessions.
+    fn move_session_to_project(&mut self, session_id: SessionId, target: usize) {
+        for project in &mut self.projects {
+            project.session_ids.retain(|id| *id != session_id);
+        }
+        if let Some(project) = self.projects.get_mut(target) {
+            project.session_ids.push(session_id);
+        }
+    }
+
+    /// Open the extra directories editor for the active session.
+    pub(crate) fn open_session_dirs_modal(&mut self) {
+        let Some(session) = self.sessions.get(self.active_index) else {
+            return;
+        };
+        let dirs: Vec<String> = session
+            .info
+            .additional_dirs
+            .iter()
+            .map(|d| d.display().to_string())
+            .collect();
+        self.session_dirs.load(&dirs);
+        self.session_dirs_id = Some(session.info.id);
+        self.show_session_dirs_modal = true;
+    }
+
+    /// Store the edited directories on the session and persist them. The
+    /// running process keeps its `--add-dir` flags until it is restarted.
+    pub(crate) fn submit_session_dirs(&mut self) {
+        let Some(id) = self.session_dirs_id else {
+            self.close_session_dirs_modal();
+            return;
+        };
+        let dirs: Vec<PathBuf> = self.session_dirs.items.iter().map(PathBuf::from).collect();
+        let Some(session) = self.sessions.iter_mut().find(|s| s.info.id == id) else {
+            self.close_session_dirs_modal();
+            self.set_error("Session no longer exists");
+            return;
+        };
+
+        let changed = session.info.additional_dirs != dirs;
+        session.info.additional_dirs = dirs;
+        self.close_session_dirs_modal();
+        if changed {
+            self.save_state();
+            self.set_status(
+                StatusLevel::Info,
+                "Extra directories saved; restart the session (Ctrl+R) to apply",
+            );
+        }
+    }
+
+    pub(crate) fn close_session_dirs_modal(&mut self) {
+        self.show_session_dirs_modal = false;
+        self.session_dirs.reset();
+        self.session_dirs_id = None;
+    }
+
+    /// Open the commit modal (F6) for the active session's worktrees.
+    pub(crate) fn open_commit_modal(&mut self) {
+        let Some(session) = self.sessions.get(self.active_index) else {
+            return;
+        };
+        if session.info.worktrees.is_empty() {
+            self.set_status(StatusLevel::Info, "Not a worktree session");
+            return;
+        }
+        self.commit_session_id = Some(session.info.id);
+        self.commit_message_input.clear();
+        self.show_commit_modal = true;
+    }
+
+    /// Stage and commit everything in each of the session's worktrees with
+    /// the typed message.
+    pub(crate) fn submit_commit(&mut self) {
+        let message = self.commit_message_input.value().trim().to_string();
+        if message.is_empty() {
+            self.set_error("Commit message cannot be empty");
+            return;
+        }
+
+        let Some(id) = self.commit_session_id else {
+            self.close_commit_modal();
+            return;
+        };
+        let Some(session) = self.sessions.iter().find(|s| s.info.id == id) else {
+            self.close_commit_modal();
+            self.set_error("Session no longer exists");
+            return;
+        };
+        let worktree_paths: Vec<PathBuf> = session
+            .info
+            .worktrees
+            .iter()
+            .map(|wt| wt.worktree_path.clone())
+            .collect();
+        self.close_commit_modal();
+
+        let mut hashes = Vec::new();
+        for path in &worktree_paths {
+            // Force a fresh info-panel status after the commit
+            self.worktree_statuses.remove(path);
+            match git::commit_all(path, &message) {
+                Ok(git::CommitResult::Committed(hash)) => hashes.push(hash),
+                Ok(git::CommitResult::NothingToCommit) => {}
+                Err(e) => {
+                    self.set_error(format!("Commit failed: {e:#}"));
+                    return;
+                }
+            }
+        }
+
+        if hashes.is_empty() {
+            self.set_status(StatusLevel::Info, "Nothing to commit");
+        } else {
+            self.set_status(
+                StatusLevel::Success,
+                format!("Committed {}", hashes.join(", ")),
+            );
+        }
+    }
+
+    pub(crate) fn close_commit_modal(&mut self) {
+        self.show_commit_modal = false;
+        self.commit_message_input.clear();
+        self.commit_session_id = None;
+    }
+
+    /// Open the send prompt modal (`s` in the session list) for the active
+    /// session.
+    pub(crate) fn open_send_prompt_modal(&mut self) {
+        let Some(session) = self.sessions.get(self.active_index) else {
+            return;
+        };
+        self.send_prompt_session_id = Some(session.info.id);
+        self.send_prompt_input.clear();
+        self.show_send_prompt_modal = true;
+    }
+
+    /// Paste the typed prompt into its session and submit it.
+    pub(crate) fn submit_send_prompt(&mut self) {
+        let prompt = self.send_prompt_input.value().trim().to_string();
+        if prompt.is_empty() {
+            self.set_error("Prompt cannot be empty");
+            return;
+        }
+        let Some(id) = self.send_prompt_session_id else {
+            self.close_send_prompt_modal();
+            return;
+        };
+        self.close_send_prompt_modal();
+        match self.send_text_to_session(id, &prompt) {
+            Ok(()) => self.set_status(StatusLevel::Success, "Prompt sent"),
+            Err(e) => self.set_error(format!("Failed to send prompt: {e}")),
+        }
+    }
+
+    pub(crate) fn close_send_prompt_modal(&mut self) {
+        self.show_send_prompt_modal = false;
+        self.send_prompt_input.clear();
+        self.send_prompt_session_id = None;
+    }
+
+    /// Snapshot the active terminal's whole scrollback and prompt for a
+    /// file to write it to (F7).
+    pub(crate) fn open_export_modal(&mut self) {
+        let Some(session) = self.sessions.get(self.active_index) else {
+            return;
+        };
+        let default_path = output_log::scrollback_export_path(&session.info.name, session.info.id);
+        let mut lines = Vec::new();
+        self.with_active_parser(|parser| lines = selection::buffer_lines(parser));
+
+        self.export_path_input.clear();
+        if let Some(path) = default_path {
+            self.export_path_input.set(&path.to_string_lossy());
+        }
+        self.scrollback_export = Some(lines);
+    }
+
+    /// Write the snapshot to the typed path, creating parent directories.
+    pub(crate) fn submit_export(&mut self) {
+        let path_text = self.export_path_input.value().trim().to_string();
+        if path_text.is_empty() {
+            self.set_error("Export path cannot be empty");
+            return;
+        }
+        let Some(lines) = self.scrollback_export.as_ref() else {
+            return;
+        };
+        let path = PathBuf::from(&path_text);
+        let mut contents = lines.join("\n");
+        if !contents.is_empty() {
+            contents.push('\n');
+        }
+        let count = lines.len();
+
+        let result = path
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+            .map_or(Ok(()), std::fs::create_dir_all)
+            .and_then(|()| std::fs::write(&path, contents));
+        match result {
+            Ok(()) => {
+                self.close_export_modal();
+                let noun = if count == 1 { "line" } else { "lines" };
+                self.set_status(
+                    StatusLevel::Success,
+                    format!("Exported {count} {noun} to {path_text}"),
+                );
+            }
+            Err(e) => self.set_error(format!("Failed to export scrollback: {e}")),
+        }
+    }
+
+    pub(crate) fn close_export_modal(&mut self) {
+        self.scrollback_export = None;
+        self.export_path_input.clear();
+    }
+
+    /// Open the fuzzy switcher (Ctrl+P) listing every project and session.
+    pub(crate) fn open_switcher(&mut self) {
+        let mut entries = Vec::new();
+        for (project_index, project) in self.projects.iter().enumerate() {
+            entries.push(SwitcherEntry {
+                label: project.config.name.clone(),
+                target: SwitcherTarget::Project(project_index),
+            });
+            for session_id in &project.session_ids {
+                let Some(session_index) =
+                    self.sessions.iter().position(|s| s.info.id == *session_id)
+                else {
+                    continue;
+                };
+                entries.push(SwitcherEntry {
+                    label: format!(
+                        "{} / {}",
+                        project.config.name, self.sessions[session_index].info.name
+                    ),
+                    target: SwitcherTarget::Session {
+                        project: project_index,
+                        session: session_index,
+                    },
+                });
+            }
+        }
+        self.switcher_entries = entries;
+        self.switcher_input.clear();
+        self.update_switcher_matches();
+        self.show_switcher = true;
+    }
+
+    /// Re-rank the switcher entries against the filter and select the best.
+    pub(crate) fn update_switcher_matches(&mut self) {
+        self.switcher_matches =
+            switcher::rank_entries(self.switcher_input.value(), &self.switcher_entries);
+        self.switcher_index = 0;
+    }
+
+    /// Jump to the selected switcher entry. A project is focused in the
+    /// project list; a session is made active and its terminal focused.
+    pub(crate) fn submit_switcher(&mut self) {
+        let target = self
+            .switcher_matches
+            .get(self.switcher_index)
+            .and_then(|&i| self.switcher_entries.get(i))
+            .map(|entry| entry.target);
+        self.close_switcher();
+
+        match target {
+            Some(SwitcherTarget::Project(project)) if project < self.projects.len() => {
+                self.active_project_index = project;
+                self.sync_active_session_to_project();
+                self.focus = InputFocus::ProjectList;
+            }
+            Some(SwitcherTarget::Session { project, session })
+                if project < self.projects.len() && session < self.sessions.len() =>
+            {
+                self.active_project_index = project;
+                self.activate_session(session);
+                self.focus = InputFocus::Terminal;
+            }
+            _ => {}
+        }
+    }
+
+    pub(crate) fn close_switcher(&mut self) {
+        self.show_switcher = false;
+        self.switcher_input.clear();
+        self.switcher_entries.clear();
+        self.switcher_matches.clear();
+        self.switcher_index = 0;
+    }
+
+    /// Restore a soft-deleted session: un-delete in DB, recreate worktrees, and spawn.
+    fn restore_deleted_session(&mut self, deleted: DeletedSessionInfo) {
+        if let Err(e) = self.db.restore_session(deleted.id) {
+            error!("Failed to restore session in DB: {e}");
+            self.set_error("Failed to restore session");
+            return;
+        }
+
+        let worktree_root = self.project_worktree_root(deleted.project_id);
+        let worktree_infos = Self::recreate_worktrees(&deleted.worktrees, worktree_root.as_deref());
+        let cwd = worktree_infos
+            .first()
+            .map(|wt| wt.worktree_path.clone())
+            .or(deleted.cwd.clone());
+
+        let permissions = self.resolve_role_permissions(&deleted.role);
+        let mut config = SessionConfig {
+            kind: SessionKind::Claude,
+            resume_session_id: deleted.claude_session_id.clone(),
+            claude_session_id: deleted.claude_session_id,
+            cwd,
+            additional_dirs: Vec::new(),
+            role: deleted.role,
+            permissions,
+            log_output: self.settings.session_logs.enabled,
+            extra_mcp_servers: Vec::new(),
+            mcp_config: None,
+            command: None,
+        };
+        let project = self.projects.iter().find(|p| p.id == deleted.project_id);
+        Self::attach_mcp_config(&mut config, project);
+
+        let session_name = deleted.name.clone();
+        let (rows, cols) = self.content_area_size();
+
+        match Session::spawn(session_name.clone(), rows, cols, &config, &self.backend) {
+            Ok(mut session) => {
+                session.info.id = deleted.id;
+                session.info.worktrees = worktree_infos;
+                let session_id = session.info.id;
+                self.sessions.push(session);
+                self.active_index = self.sessions.len() - 1;
+                self.focus = InputFocus::Terminal;
+
+                self.associate_session_with_project(session_id, deleted.project_id);
+                self.save_state();
+
+                self.set_status(StatusLevel::Success, format!("Restored '{session_name}'"));
+            }
+            Err(e) => {
+                error!("Failed to spawn restored session: {e}");
+                self.set_error(format!("Failed to restore session: {e:#}"));
+            }
+        }
+    }
+
+    /// Get sessions belonging to the active project in display order:
+    /// pinned sessions first, otherwise in the project's order (or oldest
+    /// first with [`Self::sort_sessions_by_age`]). Sessions
+    /// not matching [`Self::session_filter`] are left out, except the active
+    /// one so the selection never disappears from under the user.
+    pub(crate) fn active_project_sessions(&self) -> Vec<usize> {
+        let Some(project) = self.active_project() else {
+            return Vec::new();
+        };
+        let mut indices: Vec<usize> = project
+            .session_ids
+            .iter()
+            .filter_map(|id| self.sessions.iter().position(|s| s.info.id == *id))
+            .filter(|&i| {
+                i == self.active_index || self.session_filter.matches(self.sessions[i].info.status)
+            })
+            .collect();
+        if self.sort_sessions_by_age {
+            // Sessions without a recorded creation time sort last.
+            indices.sort_by_key(|&i| self.sessions[i].info.created_at.unwrap_or(u64::MAX));
+        }
+        indices.sort_by_key(|&i| !self.sessions[i].info.is_pinned);
+        indices
+    }
+
+    /// Get the active session's index within the active project's session list.
+    pub(crate) fn active_session_in_project(&self) -> usize {
+        let project_sessions = self.active_project_sessions();
+        project_sessions
+            .iter()
+            .position(|&i| i == self.active_index)
+            .unwrap_or(0)
+    }
+
+    /// Ensure a session is associated with a project.
+    /// Tries to add to the session's original project, falling back to the first project.
+    fn associate_session_with_project(&mut self, session_id: SessionId, project_id: ProjectId) {
+        let mut found_project = false;
+        if let Some(project) = self.projects.iter_mut().find(|p| p.id == project_id) {
+            if !project.session_ids.contains(&session_id) {
+                project.session_ids.push(session_id);
+            }
+            found_project = true;
+        }
+
+        // If session's project doesn't exist in this instance, add to the first project
+        if !found_project {
+            if let Some(project) = self.projects.first_mut() {
+                if !project.session_ids.contains(&session_id) {
+                    project.session_ids.push(session_id);
+                }
+            }
+        }
+    }
+
+    /// Apply shared session metadata to a local session info.
+    /// Used when updating or adopting sessions from shared state.
+    fn apply_shared_session_metadata(session: &mut Session, shared: &sync::SharedSession) {
+        session.info.name = shared.name.clone();
+        session.info.role = shared.role.clone();
+        session.info.cwd = shared.cwd.clone();
+        session.info.additional_dirs = shared.additional_dirs.clone();
+        session.info.claude_session_id = shared.claude_session_id.clone();
+        session.info.worktrees = shared.worktrees.iter().cloned().map(Into::into).collect();
+        session.info.is_pinned = shared.is_pinned;
+        session.info.label = shared.label.clone();
+        session.info.label_color = shared.label_color.clone();
+        session.info.created_at = shared.created_at;
+    }
+
+    pub fn update(&mut self, msg: AppMessage) {
+        self.needs_redraw = true;
+        self.status_snapshot_stale = true;
+        match msg {
+            AppMessage::KeyPress(code, mods) => self.handle_key(code, mods),
+            AppMessage::MouseScrollUp => self.scroll_terminal_up(self.settings.scroll.mouse_lines),
+            AppMessage::MouseScrollDown => {
+                self.scroll_terminal_down(self.settings.scroll.mouse_lines)
+            }
             AppMessage::MouseClick { x, y, modifiers } => self.handle_mouse_click(x, y, modifiers),
             AppMessage::Resize(cols, rows) => self.handle_resize(cols, rows),
             AppMessage::ExternalStateChange(delta) => self.handle_external_state_change(delta),
@@ -1209,46 +3196,290 @@ impl App {
         });
     }
 
+    /// Lines scrolled by `Shift+PageUp/PageDown`: `[scroll] page_fraction`
+    /// of the terminal height, at least one.
     pub(crate) fn page_scroll_amount(&self) -> usize {
         let (rows, _) = self.content_area_size();
-        (rows as usize) / 2
+        ((rows as f64 * self.settings.scroll.page_fraction) as usize).max(1)
     }
 
-    fn handle_mouse_click(&self, x: u16, y: u16, modifiers: KeyModifiers) {
-        use crate::ui::links;
+    /// Scrollback offset of the active terminal (0 = following live output).
+    pub(crate) fn active_scrollback(&self) -> usize {
+        let mut offset = 0;
+        self.with_active_parser(|parser| offset = parser.screen().scrollback());
+        offset
+    }
 
-        if !modifiers.contains(KeyModifiers::CONTROL) {
+    /// Open the search prompt, keeping the previous query for editing.
+    pub(crate) fn open_terminal_search(&mut self) {
+        self.terminal_search
+            .get_or_insert_with(|| TerminalSearch {
+                query: TextInput::new(),
+                editing: false,
+                current: None,
+                position: None,
+            })
+            .editing = true;
+    }
+
+    /// Run the typed query and jump to the nearest match. An empty query
+    /// closes the search.
+    pub(crate) fn submit_terminal_search(&mut self) {
+        let Some(search) = self.terminal_search.as_mut() else {
+            return;
+        };
+        if search.query.value().is_empty() {
+            self.close_terminal_search();
             return;
         }
+        search.editing = false;
+        search.current = None;
+        self.jump_terminal_search(SearchStep::Nearest);
+    }
 
-        let area = Rect::new(0, 0, self.terminal_cols, self.terminal_rows);
-        let term_area = layout::compute_layout(area, self.show_info_panel).terminal;
-        let inner = Block::default().borders(Borders::ALL).inner(term_area);
+    pub(crate) fn close_terminal_search(&mut self) {
+        self.terminal_search = None;
+    }
 
-        if !inner.contains(Position::new(x, y)) {
-            return;
-        }
+    /// Enter copy mode. The cursor starts on the current search match, or on
+    /// the first column of the middle visible row.
+    pub(crate) fn start_terminal_selection(&mut self) {
+        let search_match = self.terminal_search.as_ref().and_then(|s| s.current);
+        self.close_terminal_search();
 
-        let screen_col = (x - inner.x) as usize;
-        let screen_row = (y - inner.y) as usize;
+        let mut start = None;
+        self.with_active_parser(|parser| {
+            let total = search::scrollback_len(parser);
+            let rows = parser.screen().size().0 as usize;
+            let top = total - parser.screen().scrollback();
+            start = Some(match search_match {
+                Some(m) => CellPos {
+                    line: m.line,
+                    col: m.start_col,
+                },
+                None => CellPos {
+                    line: top + rows / 2,
+                    col: 0,
+                },
+            });
+        });
+        self.terminal_selection = start.map(|pos| TerminalSelection {
+            anchor: pos,
+            cursor: pos,
+        });
+    }
 
+    /// Move the copy-mode cursor by `lines`/`cols`, clamped to the buffer,
+    /// scrolling the view so the cursor stays visible. `line_edge` jumps to
+    /// the first (`Some(false)`) or last (`Some(true)`) column instead.
+    pub(crate) fn move_terminal_selection(
+        &mut self,
+        lines: isize,
+        cols: isize,
+        line_edge: Option<bool>,
+    ) {
+        let Some(mut sel) = self.terminal_selection else {
+            return;
+        };
         self.with_active_parser(|parser| {
-            let rows = links::extract_screen_rows(parser.screen());
-            let detected = links::detect_urls(&rows);
-            if let Some(url) = links::url_at_position(&detected, screen_row, screen_col) {
-                open_url(url);
+            let total = search::scrollback_len(parser);
+            let (rows, width) = parser.screen().size();
+            let rows = rows as usize;
+            let last_line = total + rows - 1;
+
+            sel.cursor.line = sel.cursor.line.saturating_add_signed(lines).min(last_line);
+            sel.cursor.col = match line_edge {
+                Some(false) => 0,
+                Some(true) => width.saturating_sub(1),
+                None => (sel.cursor.col as isize + cols).clamp(0, width as isize - 1) as u16,
+            };
+
+            let top = total - parser.screen().scrollback();
+            if sel.cursor.line < top {
+                parser.screen_mut().set_scrollback(total - sel.cursor.line);
+            } else if sel.cursor.line >= top + rows {
+                parser
+                    .screen_mut()
+                    .set_scrollback((total + rows - 1).saturating_sub(sel.cursor.line));
             }
         });
+        self.terminal_selection = Some(sel);
     }
 
-    pub(crate) fn submit_role_editor(&mut self) {
-        let name = self.role_editor_name.value().trim().to_string();
-        if name.is_empty() {
-            self.set_error("Role name cannot be empty");
+    /// Text covered by the copy-mode selection in the active terminal.
+    pub(crate) fn selected_terminal_text(&self) -> Option<String> {
+        let sel = self.terminal_selection?;
+        let mut text = None;
+        self.with_active_parser(|parser| {
+            text = Some(selection::selected_text(parser, sel.anchor, sel.cursor));
+        });
+        text
+    }
+
+    /// Copy the selection to the system clipboard and leave copy mode.
+    pub(crate) fn yank_terminal_selection(&mut self) {
+        let Some(text) = self.selected_terminal_text() else {
             return;
+        };
+        self.terminal_selection = None;
+
+        match self.copy_to_clipboard(&text) {
+            Ok(()) => {
+                let lines = text.lines().count().max(1);
+                let noun = if lines == 1 { "line" } else { "lines" };
+                self.set_status(
+                    StatusLevel::Success,
+                    format!("Copied {lines} {noun} to clipboard"),
+                );
+            }
+            Err(e) => {
+                error!("Failed to copy to clipboard: {e}");
+                self.set_error(format!("Failed to copy to clipboard: {e}"));
+            }
         }
+    }
 
-        // Check uniqueness (exclude the role being edited)
+    /// Put `text` on the system clipboard, opening it on first use.
+    fn copy_to_clipboard(&mut self, text: &str) -> Result<(), arboard::Error> {
+        match self.clipboard.as_mut() {
+            Some(clipboard) => clipboard.set_text(text),
+            None => arboard::Clipboard::new().and_then(|mut clipboard| {
+                clipboard.set_text(text)?;
+                self.clipboard = Some(clipboard);
+                Ok(())
+            }),
+        }
+    }
+
+    /// Copy the active session's directory to the clipboard (`Ctrl+Y`), so
+    /// it can be pasted into `cd` in another terminal.
+    pub(crate) fn copy_active_session_path(&mut self) {
+        let Some(path) = self
+            .sessions
+            .get(self.active_index)
+            .and_then(|s| copy_path(&s.info))
+        else {
+            self.set_status(StatusLevel::Info, "Session has no working directory");
+            return;
+        };
+        let path = path.display().to_string();
+        match self.copy_to_clipboard(&path) {
+            Ok(()) => self.set_status(StatusLevel::Success, format!("Copied {path}")),
+            Err(e) => {
+                error!("Failed to copy to clipboard: {e}");
+                self.set_error(format!("Failed to copy to clipboard: {e}"));
+            }
+        }
+    }
+
+    /// Rescan the active parser for the query and move the scrollback so the
+    /// chosen match sits in the middle of the view.
+    pub(crate) fn jump_terminal_search(&mut self, step: SearchStep) {
+        let Some(search) = self.terminal_search.as_ref() else {
+            return;
+        };
+        let query = search.query.value().to_string();
+        let current = search.current;
+
+        let mut found = None;
+        self.with_active_parser(|parser| {
+            let matches = search::find_matches(parser, &query);
+            if matches.is_empty() {
+                return;
+            }
+            let total = search::scrollback_len(parser);
+            let rows = parser.screen().size().0 as usize;
+            let bottom = (total - parser.screen().scrollback() + rows).saturating_sub(1);
+
+            let current_index = current.and_then(|c| matches.iter().position(|m| *m == c));
+            let index = match (step, current_index) {
+                (SearchStep::Older, Some(i)) => i.checked_sub(1).unwrap_or(matches.len() - 1),
+                (SearchStep::Newer, Some(i)) => (i + 1) % matches.len(),
+                _ => search::nearest_at_or_above(&matches, bottom).unwrap_or(0),
+            };
+            let target = matches[index];
+            parser
+                .screen_mut()
+                .set_scrollback(search::scrollback_for_line(target.line, total, rows));
+            found = Some((target, index + 1, matches.len()));
+        });
+
+        let Some(search) = self.terminal_search.as_mut() else {
+            return;
+        };
+        match found {
+            Some((target, index, count)) => {
+                search.current = Some(target);
+                search.position = Some((index, count));
+            }
+            None => {
+                search.current = None;
+                search.position = None;
+                self.set_error(format!("Pattern not found: {query}"));
+            }
+        }
+    }
+
+    fn handle_mouse_click(&mut self, x: u16, y: u16, modifiers: KeyModifiers) {
+        use crate::ui::links;
+
+        let item = project_list::item_at(&self.left_panel_regions.borrow(), x, y);
+        if let Some(item) = item {
+            self.select_panel_item(item);
+            return;
+        }
+
+        if !modifiers.contains(KeyModifiers::CONTROL) {
+            return;
+        }
+
+        let area = Rect::new(0, 0, self.terminal_cols, self.terminal_rows);
+        let term_area = self.compute_layout(area).terminal;
+        let inner = Block::default().borders(Borders::ALL).inner(term_area);
+
+        if !inner.contains(Position::new(x, y)) {
+            return;
+        }
+
+        let screen_col = (x - inner.x) as usize;
+        let screen_row = (y - inner.y) as usize;
+
+        self.with_active_parser(|parser| {
+            let rows = links::extract_screen_rows(parser.screen());
+            let detected = links::detect_urls(&rows);
+            if let Some(url) = links::url_at_position(&detected, screen_row, screen_col) {
+                open_url(url);
+            }
+        });
+    }
+
+    /// Select a clicked project (focusing the project list) or session
+    /// (focusing the session list).
+    fn select_panel_item(&mut self, item: project_list::PanelItem) {
+        match item {
+            project_list::PanelItem::Project(index) if index < self.projects.len() => {
+                self.active_project_index = index;
+                self.sync_active_session_to_project();
+                self.focus = InputFocus::ProjectList;
+            }
+            project_list::PanelItem::Session(index) => {
+                if let Some(&session) = self.active_project_sessions().get(index) {
+                    self.activate_session(session);
+                    self.focus = InputFocus::SessionList;
+                }
+            }
+            project_list::PanelItem::Project(_) => {}
+        }
+    }
+
+    pub(crate) fn submit_role_editor(&mut self) {
+        let name = self.role_editor_name.value().trim().to_string();
+        if name.is_empty() {
+            self.set_error("Role name cannot be empty");
+            return;
+        }
+
+        // Check uniqueness (exclude the role being edited)
         let duplicate = self
             .role_editor_roles
             .iter()
@@ -1305,6 +3536,12 @@ impl App {
 
         match self.role_editor_editing_index {
             Some(idx) => {
+                // A renamed default role stays the default
+                if self.edit_project_default_role.as_deref()
+                    == Some(self.role_editor_roles[idx].name.as_str())
+                {
+                    self.edit_project_default_role = Some(role.name.clone());
+                }
                 self.role_editor_roles[idx] = role;
             }
             None => {
@@ -1326,29 +3563,50 @@ impl App {
         new_branch: &str,
         base_branch: &str,
     ) {
+        let (worktree_root, remote) = self.active_project().map_or((None, None), |p| {
+            (
+                p.config.worktree_root.clone(),
+                Some(p.config.effective_remote().to_string()),
+            )
+        });
+        let worktree_root = worktree_root.as_deref();
+        let remote = remote.as_deref().unwrap_or(crate::project::DEFAULT_REMOTE);
         let mut worktree_infos = Vec::new();
         let mut worktree_paths = Vec::new();
+        let mut reused = false;
+        // Worktrees checked out before this spawn; never rolled back.
+        let mut reused_paths = Vec::new();
 
         for repo_path in repo_paths {
-            match git::create_worktree(repo_path, new_branch, base_branch) {
+            // An existing branch is resumed in a worktree instead of failing
+            // on `-b`; the base branch does not apply to it.
+            let base = repo_base_branch(repo_path, remote, base_branch);
+            let created = if git::branch_exists(repo_path, new_branch) {
+                reused = true;
+                git::add_existing_worktree(repo_path, new_branch, worktree_root).or_else(|e| {
+                    let path = self.reuse_checked_out_worktree(repo_path, new_branch, e)?;
+                    reused_paths.push(path.clone());
+                    Ok(path)
+                })
+            } else {
+                git::create_worktree(repo_path, new_branch, &base, worktree_root)
+            };
+            match created {
                 Ok(worktree_path) => {
                     worktree_infos.push(WorktreeInfo {
                         repo_path: repo_path.clone(),
                         worktree_path: worktree_path.clone(),
                         branch: new_branch.to_string(),
+                        base_branch: base,
                     });
                     worktree_paths.push(worktree_path);
                 }
                 Err(e) => {
                     // Roll back already-created worktrees
-                    for info in &worktree_infos {
-                        if let Err(re) = git::remove_worktree(&info.repo_path, &info.worktree_path)
-                        {
-                            error!("Failed to roll back worktree: {re}");
-                        }
-                    }
+                    remove_created_worktrees(&worktree_infos, &reused_paths);
                     error!("Failed to create worktree in {}: {e}", repo_path.display());
-                    self.set_error(format!("Failed to create worktree: {e:#}"));
+                    self.pending_spawn_role = None;
+                    self.set_app_error(AppError::Worktree(format!("{e:#}")));
                     return;
                 }
             }
@@ -1359,7 +3617,60 @@ impl App {
             additional_dirs: worktree_paths[1..].to_vec(),
             ..SessionConfig::default()
         };
-        self.prepare_spawn(config, worktree_infos);
+        let sessions_before = self.sessions.len();
+        self.prepare_spawn(config, worktree_infos.clone());
+        let spawned = self.sessions.len() > sessions_before;
+        if !spawned && !self.show_role_selector {
+            // The session failed to start; nothing owns the new worktrees.
+            remove_created_worktrees(&worktree_infos, &reused_paths);
+        } else if reused {
+            self.set_status(
+                StatusLevel::Info,
+                format!("Reused existing branch '{new_branch}'"),
+            );
+        }
+        if self.show_role_selector {
+            self.pending_spawn_reused_worktrees = reused_paths;
+        }
+    }
+
+    /// Recover from git refusing `branch` because another worktree has it
+    /// checked out: a linked worktree no session uses is reused, otherwise
+    /// the error says where the branch is and what to do about it.
+    fn reuse_checked_out_worktree(
+        &self,
+        repo_path: &Path,
+        branch: &str,
+        err: anyhow::Error,
+    ) -> anyhow::Result<PathBuf> {
+        if !git::is_checked_out_elsewhere(&err) {
+            return Err(err);
+        }
+        let Some(path) = git::existing_worktree_for_branch(repo_path, branch) else {
+            return Err(err);
+        };
+        if same_path(&path, repo_path) {
+            anyhow::bail!(
+                "Branch '{branch}' is checked out in {} itself; \
+                 switch that checkout to another branch or pick a different branch",
+                path.display()
+            );
+        }
+        if let Some(session) = self.sessions.iter().find(|s| {
+            s.info
+                .worktrees
+                .iter()
+                .any(|wt| same_path(&wt.worktree_path, &path))
+        }) {
+            anyhow::bail!(
+                "Branch '{branch}' is already open in session '{}' ({}); \
+                 switch to that session or pick a different branch",
+                session.info.name,
+                path.display()
+            );
+        }
+        tracing::info!("Reusing worktree {} for branch '{branch}'", path.display());
+        Ok(path)
     }
 
     pub(crate) fn do_spawn_session(
@@ -1369,16 +3680,31 @@ impl App {
         worktrees: Vec<WorktreeInfo>,
         target_project_index: Option<usize>,
     ) {
+        if let Some(cwd) = missing_cwd(config.cwd.as_deref()) {
+            self.set_app_error(AppError::MissingCwd(cwd.to_path_buf()));
+            return;
+        }
+
         let (rows, cols) = self.content_area_size();
 
         let mut config = config.clone();
-        if config.claude_session_id.is_none() {
+        // Shell sessions get no Claude session ID, so they are never resumed
+        // as Claude by restart or restore.
+        if config.claude_session_id.is_none() && config.kind == SessionKind::Claude {
             config.claude_session_id = Some(uuid::Uuid::new_v4().to_string());
         }
+        if self.settings.session_logs.enabled {
+            config.log_output = true;
+        }
+        let project_index = target_project_index.unwrap_or(self.active_project_index);
+        if config.kind == SessionKind::Claude {
+            Self::attach_mcp_config(&mut config, self.projects.get(project_index));
+        }
 
         match Session::spawn(name, rows, cols, &config, &self.backend) {
             Ok(mut session) => {
                 session.info.worktrees = worktrees;
+                session.info.created_at = Some(sync::current_time_millis());
                 let session_id = session.info.id;
                 self.sessions.push(session);
                 self.active_index = self.sessions.len() - 1;
@@ -1386,19 +3712,34 @@ impl App {
                 self.status_message = None;
 
                 // Only add to project if not already there
-                let project_index = target_project_index.unwrap_or(self.active_project_index);
                 if let Some(project) = self.projects.get_mut(project_index) {
                     if !project.session_ids.contains(&session_id) {
                         project.session_ids.push(session_id);
                     }
                 }
 
+                let init_command = self
+                    .projects
+                    .get(project_index)
+                    .filter(|p| !p.is_admin)
+                    .and_then(|p| p.config.session_init_command.clone());
+                if let Some(command) = init_command {
+                    self.send_init_command(session_id, &command);
+                }
+
                 // Sync to shared state for other instances
                 self.save_state();
             }
             Err(e) => {
                 error!("Failed to spawn session: {e}");
-                self.set_error(format!("Failed to start claude: {e:#}"));
+                let program = match config.kind {
+                    SessionKind::Claude => "claude",
+                    SessionKind::Shell => "shell",
+                };
+                self.set_app_error(AppError::SpawnFailed {
+                    program,
+                    reason: format!("{e:#}"),
+                });
             }
         }
     }
@@ -1410,6 +3751,7 @@ impl App {
         let pending_path = self.add_project_path.value().trim().to_string();
         if !pending_path.is_empty() {
             self.add_project_repos.push(PathBuf::from(pending_path));
+            self.add_project_path.clear();
         }
 
         if name.is_empty() || self.add_project_repos.is_empty() {
@@ -1417,11 +3759,34 @@ impl App {
             return;
         }
 
+        if self.repo_clone_rx.is_some() {
+            self.set_status(StatusLevel::Info, "Waiting for clone to finish...");
+            return;
+        }
+        // Offer to clone repos that are missing or empty; populated non-git
+        // directories, and repos the user skipped, are left alone.
+        if let Some(dest) = self
+            .add_project_repos
+            .iter()
+            .find(|repo| !self.add_project_clone_skipped.contains(repo) && git::needs_clone(repo))
+        {
+            self.add_project_clone_dest = Some(dest.clone());
+            self.add_project_clone_url.clear();
+            return;
+        }
+
         let config = ProjectConfig {
             name,
             repos: self.add_project_repos.clone(),
             roles: Vec::new(),
             mcp_servers: Vec::new(),
+            sync_base_branch: None,
+            remote: None,
+            sync_strategy: SyncStrategy::default(),
+            activity_timeout_ms: None,
+            worktree_root: None,
+            default_role: None,
+            session_init_command: None,
             id: None,
         };
         let info = ProjectInfo::new(config);
@@ -1448,7 +3813,25 @@ impl App {
         let name = project.config.name.clone();
         let repos = project.config.repos.clone();
         let roles = project.config.roles.clone();
+        let default_role = project.config.default_role.clone();
         let mcp_servers = project.config.mcp_servers.clone();
+        let sync_strategy = project.config.sync_strategy;
+        let activity_timeout = project
+            .config
+            .activity_timeout_ms
+            .map(|ms| ms.to_string())
+            .unwrap_or_default();
+        let worktree_root = project
+            .config
+            .worktree_root
+            .as_ref()
+            .map(|p| p.display().to_string())
+            .unwrap_or_default();
+        let init_command = project
+            .config
+            .session_init_command
+            .clone()
+            .unwrap_or_default();
         let id = project.id;
 
         self.edit_project_name.set(&name);
@@ -1460,8 +3843,13 @@ impl App {
         self.edit_project_original_id = Some(id);
         self.role_editor_roles = roles;
         self.role_editor_list_index = 0;
+        self.edit_project_default_role = default_role;
         self.edit_project_mcp_servers = mcp_servers;
         self.edit_project_mcp_server_index = 0;
+        self.edit_project_sync_strategy = sync_strategy;
+        self.edit_project_activity_timeout.set(&activity_timeout);
+        self.edit_project_worktree_root.set(&worktree_root);
+        self.edit_project_init_command.set(&init_command);
         self.show_edit_project_modal = true;
     }
 
@@ -1479,6 +3867,24 @@ impl App {
             return;
         }
 
+        let timeout_text = self.edit_project_activity_timeout.value().trim();
+        let activity_timeout_ms = if timeout_text.is_empty() {
+            None
+        } else {
+            match timeout_text.parse::<u64>() {
+                Ok(ms) if ms > 0 => Some(ms),
+                _ => {
+                    self.set_error("Activity timeout must be a positive number of milliseconds");
+                    return;
+                }
+            }
+        };
+
+        let root_text = self.edit_project_worktree_root.value().trim();
+        let worktree_root = (!root_text.is_empty()).then(|| PathBuf::from(root_text));
+        let init_text = self.edit_project_init_command.value().trim();
+        let session_init_command = (!init_text.is_empty()).then(|| init_text.to_string());
+
         let Some(original_id) = self.edit_project_original_id else {
             return;
         };
@@ -1494,6 +3900,15 @@ impl App {
         project.config.repos = self.edit_project_repos.clone();
         project.config.roles = self.role_editor_roles.clone();
         project.config.mcp_servers = self.edit_project_mcp_servers.clone();
+        project.config.sync_strategy = self.edit_project_sync_strategy;
+        project.config.activity_timeout_ms = activity_timeout_ms;
+        project.config.worktree_root = worktree_root;
+        project.config.session_init_command = session_init_command;
+        // Drop a default whose role was deleted
+        project.config.default_role = self
+            .edit_project_default_role
+            .clone()
+            .filter(|name| self.role_editor_roles.iter().any(|r| &r.name == name));
 
         // Persist project to DB at point of change
         let project_clone = project.clone();
@@ -1519,6 +3934,89 @@ impl App {
         self.role_editor_list_index = 0;
         self.edit_project_mcp_servers.clear();
         self.edit_project_mcp_server_index = 0;
+        self.edit_project_sync_strategy = SyncStrategy::default();
+        self.edit_project_activity_timeout.clear();
+        self.edit_project_worktree_root.clear();
+        self.edit_project_init_command.clear();
+        self.edit_project_default_role = None;
+        self.close_role_file_modal();
+    }
+
+    /// Mark the selected role as the project default (`s` in the Roles
+    /// list), or clear it when it already is. Saved with the project.
+    pub(crate) fn toggle_default_role(&mut self) {
+        let Some(role) = self.role_editor_roles.get(self.role_editor_list_index) else {
+            return;
+        };
+        if self.edit_project_default_role.as_deref() == Some(role.name.as_str()) {
+            self.edit_project_default_role = None;
+        } else {
+            self.edit_project_default_role = Some(role.name.clone());
+        }
+    }
+
+    /// Open the role file path prompt over the edit-project modal.
+    pub(crate) fn open_role_file_modal(&mut self, action: RoleFileAction) {
+        self.role_file_action = Some(action);
+        self.role_file_path.clear();
+    }
+
+    /// Export or import the edit-project modal's roles. Imports merge into
+    /// the list by name and, like other edits, are saved with the project.
+    pub(crate) fn submit_role_file(&mut self) {
+        let Some(action) = self.role_file_action else {
+            return;
+        };
+        let path_text = self.role_file_path.value().trim().to_string();
+        if path_text.is_empty() {
+            self.set_error("Role file path cannot be empty");
+            return;
+        }
+        let path = PathBuf::from(&path_text);
+        let format = RoleFileFormat::from_path(&path);
+
+        match action {
+            RoleFileAction::Export => {
+                let result = crate::storage::serialize_roles(&self.role_editor_roles, format)
+                    .and_then(|contents| Ok(std::fs::write(&path, contents)?));
+                match result {
+                    Ok(()) => {
+                        let count = self.role_editor_roles.len();
+                        self.close_role_file_modal();
+                        self.set_status(
+                            StatusLevel::Info,
+                            format!("Exported {count} role(s) to {path_text}"),
+                        );
+                    }
+                    Err(e) => self.set_error(format!("Failed to export roles: {e:#}")),
+                }
+            }
+            RoleFileAction::Import => {
+                let result = std::fs::read_to_string(&path)
+                    .map_err(anyhow::Error::from)
+                    .and_then(|contents| crate::storage::parse_roles(&contents, format));
+                match result {
+                    Ok(imported) => {
+                        let count = imported.len();
+                        crate::storage::merge_roles(&mut self.role_editor_roles, imported);
+                        self.role_editor_list_index = self
+                            .role_editor_list_index
+                            .min(self.role_editor_roles.len().saturating_sub(1));
+                        self.close_role_file_modal();
+                        self.set_status(
+                            StatusLevel::Info,
+                            format!("Imported {count} role(s) from {path_text}"),
+                        );
+                    }
+                    Err(e) => self.set_error(format!("Failed to import roles: {e:#}")),
+                }
+            }
+        }
+    }
+
+    pub(crate) fn close_role_file_modal(&mut self) {
+        self.role_file_action = None;
+        self.role_file_path.clear();
     }
 
     pub(crate) fn show_delete_project_modal(&mut self) {
@@ -1602,34 +4100,191 @@ impl App {
     pub(crate) fn sync_active_session_to_project(&mut self) {
         let project_sessions = self.active_project_sessions();
         if let Some(&first) = project_sessions.first() {
-            self.active_index = first;
+            self.activate_session(first);
         }
     }
 
-    /// Switch to the next project (wraps around to first).
-    pub(crate) fn switch_project_forward(&mut self) {
-        if self.projects.is_empty() {
+    /// Make `index` the active session, remembering the scrollback offset of
+    /// the session being left and restoring the one `index` was left at.
+    pub(crate) fn activate_session(&mut self, index: usize) {
+        if index == self.active_index {
             return;
         }
-        if self.active_project_index + 1 < self.projects.len() {
-            self.active_project_index += 1;
-        } else {
-            self.active_project_index = 0;
+        if let Some(id) = self.sessions.get(self.active_index).map(|s| s.info.id) {
+            let offset = self.active_scrollback();
+            self.scrollback_offsets.insert(id, offset);
+        }
+        self.active_index = index;
+        self.wake_session(index);
+        self.detect_dead_session(index);
+        let saved = self
+            .sessions
+            .get(index)
+            .and_then(|s| self.scrollback_offsets.get(&s.info.id))
+            .copied();
+        if let Some(offset) = saved {
+            self.with_active_parser(|parser| parser.screen_mut().set_scrollback(offset));
         }
-        self.sync_active_session_to_project();
     }
 
-    /// Switch to the previous project (wraps around to last).
-    pub(crate) fn switch_project_backward(&mut self) {
-        if self.projects.is_empty() {
+    /// Flag session `index` as errored when its backend pane died while it
+    /// was out of view, so the terminal shows a restart hint instead of
+    /// frozen output. Sessions that already exited keep their status.
+    fn detect_dead_session(&mut self, index: usize) {
+        let Some(session) = self.sessions.get_mut(index) else {
+            return;
+        };
+        if session.is_dormant() || session.has_exited() || session.info.backend_dead {
             return;
         }
-        if self.active_project_index > 0 {
-            self.active_project_index -= 1;
-        } else {
-            self.active_project_index = self.projects.len() - 1;
+        match self.backend.is_dead(session.backend_id()) {
+            Ok(true) => {
+                session.info.backend_dead = true;
+                session.info.status = SessionStatus::Error;
+                let name = session.info.name.clone();
+                self.set_error(format!("'{name}' died. Ctrl+R to restart"));
+            }
+            Ok(false) => {}
+            Err(e) => tracing::debug!("Failed to check session liveness: {e}"),
+        }
+    }
+
+    /// Switch to the next shown project (wraps around to first).
+    pub(crate) fn switch_project_forward(&mut self) {
+        self.switch_project_by_offset(1);
+    }
+
+    /// Switch to the previous shown project (wraps around to last).
+    pub(crate) fn switch_project_backward(&mut self) {
+        self.switch_project_by_offset(-1);
+    }
+
+    /// Move the active project by `offset` positions within
+    /// [`Self::visible_projects`], wrapping at either end.
+    fn switch_project_by_offset(&mut self, offset: isize) {
+        let visible = self.visible_projects();
+        if visible.is_empty() {
+            return;
         }
+        let current = visible
+            .iter()
+            .position(|&i| i == self.active_project_index)
+            .unwrap_or(0);
+        let next = (current as isize + offset).rem_euclid(visible.len() as isize);
+        self.active_project_index = visible[next as usize];
         self.sync_active_session_to_project();
+        self.apply_project_ui_prefs();
+    }
+
+    /// Store the active project's info panel, layout and session filter so
+    /// switching back to it restores them.
+    fn save_project_ui_prefs(&self) {
+        let Some(project) = self.active_project() else {
+            return;
+        };
+        let prefs = crate::storage::ProjectUiPrefs {
+            info_panel: self.info_panel_user_pref,
+            session_tabs: self.show_session_tabs,
+            session_filter: self.session_filter.as_str().to_string(),
+        };
+        if let Err(e) = self.db.set_project_ui_prefs(project.id, &prefs) {
+            error!("Failed to save UI preferences: {e}");
+        }
+    }
+
+    /// Apply the UI preferences stored for the active project. A project
+    /// with none stored keeps the current ones.
+    fn apply_project_ui_prefs(&mut self) {
+        let Some(id) = self.active_project().map(|p| p.id) else {
+            return;
+        };
+        let prefs = match self.db.get_project_ui_prefs(id) {
+            Ok(Some(prefs)) => prefs,
+            Ok(None) => return,
+            Err(e) => {
+                error!("Failed to load UI preferences: {e}");
+                return;
+            }
+        };
+        self.info_panel_user_pref = prefs.info_panel;
+        self.show_info_panel =
+            prefs.info_panel && self.terminal_cols >= layout::INFO_PANEL_MIN_WIDTH;
+        self.session_filter = SessionFilter::from_name(&prefs.session_filter);
+        if prefs.session_tabs != self.show_session_tabs {
+            self.show_session_tabs = prefs.session_tabs;
+            if self.show_session_tabs {
+                self.focus = InputFocus::Terminal;
+            }
+        }
+        let (rows, cols) = self.content_area_size();
+        for session in &self.sessions {
+            session.resize(rows, cols, self.wrap_lines);
+        }
+    }
+
+    /// Indices of the projects shown in the project list, in order. Projects
+    /// not matching [`Self::project_filter`] are left out, except the admin
+    /// project and the active one so the selection never disappears.
+    pub(crate) fn visible_projects(&self) -> Vec<usize> {
+        let query = self
+            .project_filter
+            .as_ref()
+            .map_or("", |filter| filter.query.value());
+        (0..self.projects.len())
+            .filter(|&i| {
+                let project = &self.projects[i];
+                i == self.active_project_index
+                    || project.is_admin
+                    || project_matches_filter(&project.config.name, query)
+            })
+            .collect()
+    }
+
+    /// Start typing a project filter (`/` in the project list), keeping any
+    /// query already set.
+    pub(crate) fn open_project_filter(&mut self) {
+        self.project_filter
+            .get_or_insert_with(|| ProjectFilter {
+                query: TextInput::new(),
+                editing: false,
+            })
+            .editing = true;
+    }
+
+    /// Stop typing and keep the filter applied. An empty query clears it.
+    pub(crate) fn submit_project_filter(&mut self) {
+        let Some(filter) = self.project_filter.as_mut() else {
+            return;
+        };
+        if filter.query.value().is_empty() {
+            self.close_project_filter();
+            return;
+        }
+        filter.editing = false;
+    }
+
+    /// Clear the project filter, showing every project again.
+    pub(crate) fn close_project_filter(&mut self) {
+        self.project_filter = None;
+    }
+
+    /// After the filter query changed: if the active project no longer
+    /// matches, activate the first project that does. With no matches the
+    /// active project is kept.
+    fn refilter_projects(&mut self) {
+        let Some(filter) = &self.project_filter else {
+            return;
+        };
+        let query = filter.query.value();
+        let matches =
+            |p: &ProjectInfo| !p.is_admin && project_matches_filter(&p.config.name, query);
+        if self.active_project().is_some_and(matches) {
+            return;
+        }
+        if let Some(first) = self.projects.iter().position(matches) {
+            self.active_project_index = first;
+            self.sync_active_session_to_project();
+        }
     }
 
     /// Switch to the next session within the active project.
@@ -1651,46 +4306,243 @@ impl App {
             .unwrap_or(0);
         let new_pos = current_pos as isize + offset;
         if new_pos >= 0 && (new_pos as usize) < project_sessions.len() {
-            self.active_index = project_sessions[new_pos as usize];
+            self.activate_session(project_sessions[new_pos as usize]);
         }
     }
 
-    fn handle_resize(&mut self, cols: u16, rows: u16) {
-        self.terminal_cols = cols;
-        self.terminal_rows = rows;
-
-        // Collapse info panel if terminal gets too narrow
-        if cols < 120 {
-            self.show_info_panel = false;
+    /// Move the active session one place down (`1`) or up (`-1`) in the
+    /// active project's list and persist the new order. Sessions only move
+    /// among others with the same pin state; no-op at either end.
+    pub(crate) fn move_active_session(&mut self, offset: isize) {
+        if self.sort_sessions_by_age {
+            self.set_status(
+                StatusLevel::Info,
+                "Sessions are sorted by age; press o to reorder them",
+            );
+            return;
         }
-
-        let (r, c) = self.content_area_size();
-        for session in &self.sessions {
-            session.resize(r, c);
+        let displayed = self.active_project_sessions();
+        let Some(pos) = displayed.iter().position(|&i| i == self.active_index) else {
+            return;
+        };
+        let Some(&neighbor) = pos
+            .checked_add_signed(offset)
+            .and_then(|p| displayed.get(p))
+        else {
+            return;
+        };
+        let active = &self.sessions[self.active_index].info;
+        let other = &self.sessions[neighbor].info;
+        if active.is_pinned != other.is_pinned {
+            return;
         }
-    }
+        let (active_id, other_id) = (active.id, other.id);
 
-    pub fn tick(&mut self) {
-        self.tick_count = self.tick_count.wrapping_add(1);
+        let Some(project) = self.projects.get_mut(self.active_project_index) else {
+            return;
+        };
+        let find = |id| project.session_ids.iter().position(|s| *s == id);
+        let (Some(a), Some(b)) = (find(active_id), find(other_id)) else {
+            return;
+        };
+        project.session_ids.swap(a, b);
 
-        for session in &mut self.sessions {
-            session.info.status = if session.has_exited() {
-                SessionStatus::Idle
-            } else if session.millis_since_last_output() > ACTIVITY_TIMEOUT_MS {
-                SessionStatus::Waiting
-            } else {
-                SessionStatus::Busy
-            };
+        if let Err(e) = self.db.set_session_order(&project.session_ids) {
+            error!("Failed to save session order: {e}");
         }
+    }
 
-        // Poll for sync results from background worktree sync threads
-        self.poll_sync_results();
+    /// Cycle the session list filter: all → waiting → busy → all.
+    pub(crate) fn cycle_session_filter(&mut self) {
+        self.session_filter = self.session_filter.next();
+        self.save_project_ui_prefs();
+        let message = match self.session_filter.label() {
+            Some(label) => format!("Showing {} sessions", label.to_lowercase()),
+            None => "Showing all sessions".to_string(),
+        };
+        self.set_status(StatusLevel::Info, message);
+    }
 
-        // Send deferred inputs whose delay has elapsed
-        self.drain_deferred_inputs();
+    /// Remember the active project's role at `role_index` for later spawns
+    /// in this run.
+    pub(crate) fn remember_spawn_role(&mut self, role_index: usize) {
+        let Some(project) = self.active_project() else {
+            return;
+        };
+        if let Some(role) = project.config.roles.get(role_index) {
+            let (id, role) = (project.id, role.name.clone());
+            self.project_default_spawn_role.insert(id, role);
+        }
+    }
 
-        // Finalize pending delete after undo timeout
-        if let Some(ref pending) = self.pending_delete {
+    /// Forget the role remembered from the selector for the active project,
+    /// so the next spawn asks again.
+    pub(crate) fn forget_spawn_role(&mut self) {
+        let Some(project) = self.active_project() else {
+            return;
+        };
+        let (id, name) = (project.id, project.config.name.clone());
+        match self.project_default_spawn_role.remove(&id) {
+            Some(role) => self.set_status(
+                StatusLevel::Info,
+                format!("Forgot role '{role}' for '{name}'"),
+            ),
+            None => self.set_status(
+                StatusLevel::Info,
+                format!("No remembered role for '{name}'"),
+            ),
+        }
+    }
+
+    /// Switch the session list between the project's order and oldest first.
+    pub(crate) fn toggle_session_sort_by_age(&mut self) {
+        self.sort_sessions_by_age = !self.sort_sessions_by_age;
+        let message = if self.sort_sessions_by_age {
+            "Sorting sessions by age"
+        } else {
+            "Sorting sessions in project order"
+        };
+        self.set_status(StatusLevel::Info, message);
+    }
+
+    /// Pin or unpin the active session. Pinned sessions are listed first.
+    pub(crate) fn toggle_pin_active_session(&mut self) {
+        let Some(session) = self.sessions.get_mut(self.active_index) else {
+            return;
+        };
+        session.info.is_pinned = !session.info.is_pinned;
+        let message = if session.info.is_pinned {
+            format!("Pinned '{}'", session.info.name)
+        } else {
+            format!("Unpinned '{}'", session.info.name)
+        };
+        self.save_state();
+        self.set_status(StatusLevel::Info, message);
+    }
+
+    /// Make the session at `position` (0-based) in the active project's
+    /// session list active and focus the terminal. Out-of-range positions
+    /// are ignored.
+    pub(crate) fn jump_to_project_session(&mut self, position: usize) {
+        if let Some(&index) = self.active_project_sessions().get(position) {
+            self.activate_session(index);
+            self.focus = InputFocus::Terminal;
+        }
+    }
+
+    fn handle_resize(&mut self, cols: u16, rows: u16) {
+        self.terminal_cols = cols;
+        self.terminal_rows = rows;
+
+        // Collapse the info panel while the terminal is too narrow and
+        // bring it back once it is wide enough again.
+        self.show_info_panel = self.info_panel_user_pref && cols >= layout::INFO_PANEL_MIN_WIDTH;
+
+        let (r, c) = self.content_area_size();
+        for session in &self.sessions {
+            session.resize(r, c, self.wrap_lines);
+        }
+    }
+
+    /// Give the active session the full scrollback and cap the rest at
+    /// `[scroll] background_lines`. Only sessions whose cap changes are
+    /// rebuilt, so this is cheap to run every tick.
+    fn sync_scrollback_caps(&self) {
+        let background = self.settings.scroll.background_lines.min(SCROLLBACK_LINES);
+        for (i, session) in self.sessions.iter().enumerate() {
+            let cap = if i == self.active_index {
+                SCROLLBACK_LINES
+            } else {
+                background
+            };
+            session.set_scrollback_cap(cap);
+        }
+    }
+
+    pub fn tick(&mut self) {
+        self.tick_count = self.tick_count.wrapping_add(1);
+        if self.tick_count % REDRAW_HEARTBEAT_TICKS == 0 {
+            self.needs_redraw = true;
+        }
+        self.expire_status_message();
+        let sessions_before = self.sessions.len();
+
+        // Attach a lazily restored session once it is focused
+        self.wake_session(self.active_index);
+        self.sync_scrollback_caps();
+
+        let previous: Vec<SessionStatus> = self.sessions.iter().map(|s| s.info.status).collect();
+        for session in &mut self.sessions {
+            let timeout_ms = activity_timeout_for(&self.projects, session.info.id);
+            session.info.status = if session.info.cwd_missing {
+                SessionStatus::Error
+            } else if session.is_dormant() {
+                SessionStatus::Dormant
+            } else if session.info.crash_looping || session.info.backend_dead {
+                SessionStatus::Error
+            } else if session.has_exited() {
+                SessionStatus::Idle
+            } else if session.millis_since_last_output() > timeout_ms {
+                if session.is_awaiting_input() {
+                    SessionStatus::NeedsInput
+                } else {
+                    SessionStatus::Waiting
+                }
+            } else {
+                SessionStatus::Busy
+            };
+        }
+
+        self.record_status_changes(&previous);
+
+        let output_at = self
+            .sessions
+            .iter()
+            .map(Session::last_output_at)
+            .max()
+            .unwrap_or(0);
+        if output_at > self.seen_output_at {
+            self.seen_output_at = output_at;
+            self.needs_redraw = true;
+        }
+        if self
+            .sessions
+            .iter()
+            .map(|s| s.info.status)
+            .ne(previous.iter().copied())
+        {
+            self.needs_redraw = true;
+            self.status_snapshot_stale = true;
+        }
+
+        // Tell the user about sessions that just finished ([notifications])
+        if self.settings.notifications.enabled {
+            self.notify_finished_sessions(&previous, notify::session_waiting);
+        }
+        self.process_bells(notify::session_bell);
+        self.poll_titles();
+
+        // Restart sessions that exited on their own ([auto_restart])
+        self.process_auto_restarts(std::time::Instant::now());
+
+        // Keep the info panel's git status fresh
+        self.refresh_worktree_statuses(std::time::Instant::now());
+        self.refresh_activity_summary(std::time::Instant::now());
+
+        // Open/close output logs to match each session's log_output flag
+        self.sync_output_logs();
+
+        // Poll for sync results from background worktree sync threads
+        self.poll_sync_preview();
+        self.poll_sync_results();
+        self.poll_push_result();
+        self.poll_clone_result();
+
+        // Send deferred inputs whose delay has elapsed
+        self.drain_deferred_inputs();
+
+        // Finalize pending delete after undo timeout
+        if let Some(ref pending) = self.pending_delete {
             if pending.created_at.elapsed() >= UNDO_TIMEOUT {
                 self.finalize_pending_delete();
             }
@@ -1699,10 +4551,316 @@ impl App {
         // Poll for external state changes from other thurbox instances (DB-based)
         if let Ok(Some(delta)) = sync::poll_for_changes(&mut self.sync_state, &mut self.db) {
             self.handle_external_state_change(delta);
+            self.needs_redraw = true;
+            self.status_snapshot_stale = true;
         }
 
         // Process queued session commands from MCP
         self.process_session_commands();
+
+        if self.sessions.len() != sessions_before {
+            self.needs_redraw = true;
+            self.status_snapshot_stale = true;
+        }
+
+        self.publish_status_snapshot();
+    }
+
+    /// Refresh what the status endpoint serves, only when something it
+    /// shows changed since the last publish.
+    fn publish_status_snapshot(&mut self) {
+        if !self.status_snapshot_stale {
+            return;
+        }
+        if let Some(server) = &self.status_server {
+            let infos: Vec<&SessionInfo> = self.sessions.iter().map(|s| &s.info).collect();
+            server.publish(&status_server::snapshot(&self.projects, &infos));
+            self.status_snapshot_stale = false;
+        }
+    }
+
+    /// Whether something changed since the last [`Self::mark_drawn`]: any
+    /// [`Self::update`], new session output, a status change, a status
+    /// message, or the periodic heartbeat.
+    pub fn needs_redraw(&self) -> bool {
+        self.needs_redraw
+    }
+
+    /// Record that the current state has been drawn.
+    pub fn mark_drawn(&mut self) {
+        self.needs_redraw = false;
+    }
+
+    /// Call `notify(project, session)` for every session that went from
+    /// `Busy` to `Waiting` or `NeedsInput` since `previous` (statuses in
+    /// session order).
+    pub(crate) fn notify_finished_sessions(
+        &self,
+        previous: &[SessionStatus],
+        mut notify: impl FnMut(&str, &str),
+    ) {
+        for (session, &before) in self.sessions.iter().zip(previous) {
+            if !notify::finished_work(before, session.info.status) {
+                continue;
+            }
+            let project = self
+                .projects
+                .iter()
+                .find(|p| p.session_ids.contains(&session.info.id))
+                .map_or("", |p| p.config.name.as_str());
+            notify(project, &session.info.name);
+        }
+    }
+
+    /// Highlight sessions whose terminal bell rang since the last tick and
+    /// fade older highlights. With `[notifications] bell`, each newly rung
+    /// session is also passed to `notify(project, session)`.
+    pub(crate) fn process_bells(&mut self, mut notify: impl FnMut(&str, &str)) {
+        for session in &mut self.sessions {
+            if !session.take_bell() {
+                session.info.bell_ticks = session.info.bell_ticks.saturating_sub(1);
+                continue;
+            }
+            session.info.bell_ticks = BELL_HIGHLIGHT_TICKS;
+            if self.settings.notifications.bell {
+                let project = self
+                    .projects
+                    .iter()
+                    .find(|p| p.session_ids.contains(&session.info.id))
+                    .map_or("", |p| p.config.name.as_str());
+                notify(project, &session.info.name);
+            }
+        }
+    }
+
+    /// Copy each session's latest window title (OSC 0/2) into its info for
+    /// the session list.
+    pub(crate) fn poll_titles(&mut self) {
+        for session in &mut self.sessions {
+            session.info.title = session.title();
+        }
+    }
+
+    /// Re-read `git status` for the active session's worktrees once their
+    /// cached entry is older than [`WORKTREE_STATUS_REFRESH`]. Only runs while
+    /// the info panel is visible, since nothing else shows it.
+    pub(crate) fn refresh_worktree_statuses(&mut self, now: std::time::Instant) {
+        if !self.show_info_panel {
+            return;
+        }
+        let Some(session) = self.sessions.get(self.active_index) else {
+            return;
+        };
+        for wt in &session.info.worktrees {
+            let fresh = self
+                .worktree_statuses
+                .get(&wt.worktree_path)
+                .is_some_and(|(at, _)| now.duration_since(*at) < WORKTREE_STATUS_REFRESH);
+            if fresh {
+                continue;
+            }
+            let status = match git::worktree_status(&wt.worktree_path) {
+                Ok(status) => Some(status),
+                Err(e) => {
+                    error!(
+                        "Failed to read git status of {}: {e}",
+                        wt.worktree_path.display()
+                    );
+                    None
+                }
+            };
+            self.worktree_statuses
+                .insert(wt.worktree_path.clone(), (now, status));
+        }
+    }
+
+    /// Append a timeline event for every session whose status differs from
+    /// `previous` (statuses before this tick, in session order).
+    fn record_status_changes(&mut self, previous: &[SessionStatus]) {
+        let now = sync::current_time_millis();
+        for (session, &before) in self.sessions.iter().zip(previous) {
+            let after = session.info.status;
+            if before == after {
+                continue;
+            }
+            if let Err(e) = self
+                .db
+                .record_status_change(session.info.id, before, after, now)
+            {
+                error!("Failed to record status change: {e}");
+            }
+            // Show the new transition instead of a stale summary
+            if self
+                .activity_summary
+                .as_ref()
+                .is_some_and(|(id, _, _)| *id == session.info.id)
+            {
+                self.activity_summary = None;
+            }
+        }
+    }
+
+    /// Re-read the active session's activity summary once the cached one is
+    /// older than [`ACTIVITY_SUMMARY_REFRESH`] or belongs to another session.
+    /// Only runs while the info panel is visible.
+    pub(crate) fn refresh_activity_summary(&mut self, now: std::time::Instant) {
+        if !self.show_info_panel {
+            return;
+        }
+        let Some(session) = self.sessions.get(self.active_index) else {
+            return;
+        };
+        let id = session.info.id;
+        let fresh = self
+            .activity_summary
+            .as_ref()
+            .is_some_and(|(cached, at, _)| {
+                *cached == id && now.duration_since(*at) < ACTIVITY_SUMMARY_REFRESH
+            });
+        if fresh {
+            return;
+        }
+        let summary = match self.db.session_activity(id) {
+            Ok(spans) => crate::storage::ActivitySummary::from_spans(&spans),
+            Err(e) => {
+                error!("Failed to read session activity: {e}");
+                None
+            }
+        };
+        self.activity_summary = Some((id, now, summary));
+    }
+
+    /// Cached git status for each of `worktrees`, in order.
+    fn cached_worktree_statuses(
+        &self,
+        worktrees: &[WorktreeInfo],
+    ) -> Vec<Option<crate::session::WorktreeStatus>> {
+        worktrees
+            .iter()
+            .map(|wt| {
+                self.worktree_statuses
+                    .get(&wt.worktree_path)
+                    .and_then(|(_, status)| status.clone())
+            })
+            .collect()
+    }
+
+    /// Open or close session output logs to match each session's
+    /// `log_output` flag. Runs every tick, so sessions spawned, restored or
+    /// adopted anywhere pick the flag up once their ID is final.
+    pub(crate) fn sync_output_logs(&mut self) {
+        let max_bytes = self.settings.session_logs.max_bytes;
+        let mut errors = Vec::new();
+
+        for session in &mut self.sessions {
+            let open = session.is_logging_output();
+            if session.info.log_output == open {
+                continue;
+            }
+            if !session.info.log_output {
+                session.stop_output_log();
+                continue;
+            }
+            if session.info.log_path.is_some() {
+                // The reader loop closed the log after a write error.
+                session.stop_output_log();
+                session.info.log_output = false;
+                errors.push(format!(
+                    "Output log for '{}' stopped after a write error",
+                    session.info.name
+                ));
+                continue;
+            }
+
+            let Some(path) = output_log::session_log_path(&session.info.name, session.info.id)
+            else {
+                session.info.log_output = false;
+                errors.push("Cannot resolve log directory".to_string());
+                continue;
+            };
+            if let Err(e) = session.start_output_log(&path, max_bytes) {
+                error!("Failed to open output log {}: {e}", path.display());
+                session.info.log_output = false;
+                errors.push(format!("Failed to open output log: {e}"));
+            }
+        }
+
+        if let Some(message) = errors.pop() {
+            self.set_error(message);
+        }
+    }
+
+    /// Switch between the left panel and the session tab bar (F4). The
+    /// terminal changes size, so sessions are resized to match.
+    pub(crate) fn toggle_session_tabs(&mut self) {
+        self.show_session_tabs = !self.show_session_tabs;
+        self.save_project_ui_prefs();
+        if self.show_session_tabs {
+            // The lists are hidden, so keep focus somewhere visible
+            self.focus = InputFocus::Terminal;
+        }
+        let (rows, cols) = self.content_area_size();
+        for session in &self.sessions {
+            session.resize(rows, cols, self.wrap_lines);
+        }
+    }
+
+    /// Show the next info panel view (F9). A hidden panel is shown first,
+    /// in the view it was left in.
+    pub(crate) fn cycle_info_panel_mode(&mut self) {
+        if self.show_info_panel {
+            self.info_panel_mode = self.info_panel_mode.next();
+        } else {
+            self.set_info_panel(true);
+        }
+    }
+
+    /// Record whether the user wants the info panel (F2) and show it if the
+    /// terminal is wide enough.
+    pub(crate) fn set_info_panel(&mut self, visible: bool) {
+        self.info_panel_user_pref = visible;
+        self.save_project_ui_prefs();
+        self.show_info_panel = visible && self.terminal_cols >= layout::INFO_PANEL_MIN_WIDTH;
+        if visible && !self.show_info_panel {
+            self.set_status(
+                StatusLevel::Info,
+                format!(
+                    "Info panel shows once the terminal is {} columns wide",
+                    layout::INFO_PANEL_MIN_WIDTH
+                ),
+            );
+        }
+    }
+
+    /// Toggle rewrapping terminal output on resize (F8).
+    pub(crate) fn toggle_wrap(&mut self) {
+        self.wrap_lines = !self.wrap_lines;
+        let message = if self.wrap_lines {
+            "Line wrap on: output rewraps when the terminal resizes"
+        } else {
+            "Line wrap off: output is truncated when the terminal narrows"
+        };
+        self.set_status(StatusLevel::Info, message);
+    }
+
+    /// Toggle raw output logging for the active session (F3).
+    pub(crate) fn toggle_output_log(&mut self) {
+        let Some(session) = self.sessions.get_mut(self.active_index) else {
+            return;
+        };
+        session.info.log_output = !session.info.log_output;
+        let enabled = session.info.log_output;
+        self.sync_output_logs();
+
+        let Some(session) = self.sessions.get(self.active_index) else {
+            return;
+        };
+        if let (true, Some(path)) = (enabled, &session.info.log_path) {
+            let message = format!("Logging output to {}", path.display());
+            self.set_status(StatusLevel::Info, message);
+        } else if !enabled {
+            self.set_status(StatusLevel::Info, "Output logging stopped");
+        }
     }
 
     /// Send deferred inputs whose scheduled tick has arrived.
@@ -1742,99 +4900,305 @@ impl App {
     /// Finalize sync: compose status message and send conflict prompts.
     fn finish_sync(&mut self) {
         let results = std::mem::take(&mut self.worktree_sync_completed);
-        let mut synced = 0usize;
-        let mut conflicts = 0usize;
-        let mut errors = Vec::new();
+        let mut summary = SyncSummary::default();
 
         for (session_id, result) in results {
             match result {
-                git::SyncResult::Synced => synced += 1,
-                git::SyncResult::Conflict(_) => {
-                    conflicts += 1;
-                    self.send_conflict_prompt(session_id);
+                git::SyncResult::Synced => summary.synced += 1,
+                // Fast-forward-only projects opted out of history changes,
+                // so there is nothing for Claude to resolve.
+                git::SyncResult::Conflict {
+                    strategy: SyncStrategy::FastForwardOnly,
+                    ..
+                } => summary.diverged += 1,
+                git::SyncResult::Conflict {
+                    remote,
+                    base_branch,
+                    strategy,
+                    ..
+                } => {
+                    summary.conflicts += 1;
+                    self.send_conflict_prompt(session_id, &remote, &base_branch, strategy);
                 }
-                git::SyncResult::Error(msg) => errors.push(msg),
+                git::SyncResult::Error(msg) => summary.errors.push(msg),
             }
         }
 
-        if !errors.is_empty() {
-            self.set_error(format!("Sync failed: {}", errors.join(", ")));
-        } else if conflicts > 0 {
-            self.set_status(
-                StatusLevel::Info,
-                format!("{synced} synced, {conflicts} conflict(s) (sent to Claude)"),
-            );
-        } else {
-            self.set_status(StatusLevel::Success, format!("{synced} worktree(s) synced"));
+        self.show_sync_summary(&summary);
+        self.last_sync_summary = Some(summary);
+    }
+
+    /// Report a sync outcome in the status bar.
+    fn show_sync_summary(&mut self, summary: &SyncSummary) {
+        let SyncSummary {
+            synced,
+            conflicts,
+            diverged,
+            errors,
+        } = summary;
+        match summary.level() {
+            StatusLevel::Error => self.set_app_error(AppError::GitSync(errors.clone())),
+            StatusLevel::Info => {
+                let mut text = format!("{synced} synced");
+                if *conflicts > 0 {
+                    text.push_str(&format!(", {conflicts} conflict(s) (sent to Claude)"));
+                }
+                if *diverged > 0 {
+                    text.push_str(&format!(", {diverged} cannot fast-forward"));
+                }
+                self.set_status(StatusLevel::Info, text);
+            }
+            StatusLevel::Success => {
+                self.set_status(StatusLevel::Success, format!("{synced} worktree(s) synced"))
+            }
         }
     }
 
-    /// Send a conflict resolution prompt to a session via bracketed paste,
-    /// with a deferred Enter so the app processes the text first.
-    fn send_conflict_prompt(&mut self, session_id: SessionId) {
-        if let Some(session) = self.sessions.iter().find(|s| s.info.id == session_id) {
-            let mut paste = b"\x1b[200~".to_vec();
-            paste.extend_from_slice(SYNC_CONFLICT_PROMPT.as_bytes());
-            paste.extend_from_slice(b"\x1b[201~");
-            if let Err(e) = session.send_input(paste) {
-                error!("Failed to send sync prompt to session: {e}");
-            } else {
-                self.deferred_inputs.push((
-                    session_id,
-                    b"\r".to_vec(),
-                    self.tick_count + DEFERRED_INPUT_DELAY_TICKS,
-                ));
-            }
+    /// Show the last sync's outcome again (F10).
+    pub(crate) fn show_last_sync(&mut self) {
+        match self.last_sync_summary.clone() {
+            Some(summary) => self.show_sync_summary(&summary),
+            None => self.set_status(StatusLevel::Info, "No sync has finished yet"),
         }
     }
 
-    /// Start syncing all worktree sessions with origin/main.
-    ///
-    /// Worktrees sharing the same parent repo are synced sequentially (to avoid
-    /// concurrent `index.lock` contention), while different repos sync in parallel.
-    pub(crate) fn start_sync(&mut self) {
-        if self.worktree_sync_in_progress {
+    /// Send a conflict resolution prompt to a session with
+    /// [`Self::send_text_to_session`].
+    fn send_conflict_prompt(
+        &mut self,
+        session_id: SessionId,
+        remote: &str,
+        base_branch: &str,
+        strategy: SyncStrategy,
+    ) {
+        let Some(session) = self.sessions.iter().find(|s| s.info.id == session_id) else {
             return;
+        };
+        let configured = &self.settings.worktree_sync;
+        let template = match strategy {
+            SyncStrategy::Merge => configured
+                .merge_conflict_prompt
+                .as_deref()
+                .unwrap_or(SYNC_MERGE_CONFLICT_PROMPT),
+            SyncStrategy::Rebase | SyncStrategy::FastForwardOnly => configured
+                .conflict_prompt
+                .as_deref()
+                .unwrap_or(SYNC_CONFLICT_PROMPT),
+        };
+        let branch = session
+            .info
+            .worktrees
+            .first()
+            .map_or("", |wt| wt.branch.as_str());
+        let prompt = render_prompt_template(
+            template,
+            &[
+                ("branch", branch),
+                ("base", base_branch),
+                ("remote", remote),
+            ],
+        );
+        if let Err(e) = self.send_text_to_session(session_id, &prompt) {
+            error!("Failed to send sync prompt to session: {e}");
         }
+    }
 
-        let worktree_sessions: Vec<_> = self
+    /// Paste `text` into a session as one bracketed paste, so newlines do
+    /// not submit it early, then press Enter after the paste delay so the
+    /// app has taken in the text first.
+    pub(crate) fn send_text_to_session(
+        &mut self,
+        session_id: SessionId,
+        text: &str,
+    ) -> anyhow::Result<()> {
+        let session = self
             .sessions
             .iter()
-            .flat_map(|s| {
-                s.info
-                    .worktrees
-                    .iter()
-                    .map(move |wt| (s.info.id, wt.worktree_path.clone(), wt.repo_path.clone()))
-            })
-            .collect();
+            .find(|s| s.info.id == session_id)
+            .ok_or_else(|| anyhow::anyhow!("Session no longer exists"))?;
+        let mut paste = b"\x1b[200~".to_vec();
+        paste.extend_from_slice(text.as_bytes());
+        paste.extend_from_slice(b"\x1b[201~");
+        session.send_input(paste)?;
+        self.defer_enter(session_id);
+        Ok(())
+    }
+
+    /// Queue an Enter for `session_id` after the paste delay, so the session
+    /// has processed the text sent just before it.
+    fn defer_enter(&mut self, session_id: SessionId) {
+        let Some(session) = self.sessions.iter().find(|s| s.info.id == session_id) else {
+            return;
+        };
+        let paste = &self.settings.paste;
+        let since_output = if paste.adaptive {
+            session.millis_since_last_output()
+        } else {
+            u64::MAX
+        };
+        let delay = deferred_input_delay_ticks(paste.enter_delay_ms, since_output);
+        self.deferred_inputs
+            .push((session_id, b"\r".to_vec(), self.tick_count + delay));
+    }
 
-        if worktree_sessions.is_empty() {
-            self.set_status(StatusLevel::Info, "No worktrees to sync");
+    /// Type the project's startup command into a newly spawned session.
+    fn send_init_command(&mut self, session_id: SessionId, command: &str) {
+        let Some(session) = self.sessions.iter().find(|s| s.info.id == session_id) else {
+            return;
+        };
+        if let Err(e) = session.send_input(command.as_bytes().to_vec()) {
+            error!("Failed to send startup command to session: {e}");
             return;
         }
+        self.defer_enter(session_id);
+    }
 
-        let count = worktree_sessions.len();
-        let (tx, rx) = mpsc::channel();
+    /// Worktrees of every session, grouped by parent repo so those sharing a
+    /// repo can be processed sequentially.
+    fn worktrees_by_repo(&self) -> HashMap<PathBuf, Vec<RepoWorktree>> {
+        let mut by_repo = HashMap::<PathBuf, Vec<RepoWorktree>>::new();
+        for session in &self.sessions {
+            let (remote, base_branch, strategy) = self.sync_settings_for(session.info.id);
+            for wt in &session.info.worktrees {
+                // The branch a worktree was created from wins over the
+                // project-wide setting; older worktrees have none recorded.
+                let base_branch = Some(wt.base_branch.clone())
+                    .filter(|b| !b.is_empty())
+                    .or_else(|| base_branch.clone());
+                by_repo.entry(wt.repo_path.clone()).or_default().push((
+                    session.info.id,
+                    wt.worktree_path.clone(),
+                    wt.branch.clone(),
+                    remote.clone(),
+                    base_branch,
+                    strategy,
+                ));
+            }
+        }
+        by_repo
+    }
 
-        // Group worktrees by repo so those sharing a repo sync sequentially.
-        let mut by_repo = std::collections::HashMap::<PathBuf, Vec<(SessionId, PathBuf)>>::new();
-        for (session_id, worktree_path, repo_path) in worktree_sessions {
-            by_repo
-                .entry(repo_path)
-                .or_default()
-                .push((session_id, worktree_path));
-        }
-
-        for worktrees in by_repo.into_values() {
-            let tx = tx.clone();
-            std::thread::spawn(move || {
-                for (session_id, worktree_path) in worktrees {
-                    let result = git::sync_worktree(&worktree_path);
-                    let _ = tx.send((session_id, result));
-                }
-            });
+    /// Fetch every worktree and count how far it is ahead of and behind its
+    /// base branch on background threads, then show the counts for
+    /// confirmation (see [`Self::poll_sync_preview`]) before syncing.
+    pub(crate) fn start_sync_preview(&mut self) {
+        if self.worktree_sync_in_progress || self.sync_preview_rx.is_some() {
+            return;
         }
 
+        let by_repo = self.worktrees_by_repo();
+        let count: usize = by_repo.values().map(Vec::len).sum();
+        if count == 0 {
+            self.set_status(StatusLevel::Info, "No worktrees to sync");
+            return;
+        }
+
+        let (tx, rx) = mpsc::channel();
+        let jobs = by_repo
+            .into_iter()
+            .map(|(repo_path, worktrees)| {
+                let tx = tx.clone();
+                Box::new(move || {
+                    for (session_id, worktree_path, branch, remote, configured, _) in worktrees {
+                        let base_branch =
+                            git::sync_base_branch(&repo_path, &remote, configured.as_deref());
+                        let counts = git::sync_preview(&worktree_path, &remote, &base_branch)
+                            .map_err(|e| format!("{e:#}"));
+                        let _ = tx.send(SyncPreviewItem {
+                            session_id,
+                            branch,
+                            base_branch,
+                            counts,
+                        });
+                    }
+                }) as worker_pool::Job
+            })
+            .collect();
+        worker_pool::spawn_bounded(jobs, self.settings.worktree_sync.max_concurrent);
+
+        self.sync_preview_rx = Some(rx);
+        self.sync_preview_pending = count;
+        self.sync_preview.clear();
+        self.set_status(
+            StatusLevel::Info,
+            format!("Fetching {count} worktree(s)..."),
+        );
+    }
+
+    /// Collect preview counts and open the confirmation modal once every
+    /// worktree has reported.
+    fn poll_sync_preview(&mut self) {
+        if let Some(rx) = &self.sync_preview_rx {
+            while let Ok(item) = rx.try_recv() {
+                self.sync_preview.push(item);
+            }
+
+            if self.sync_preview.len() >= self.sync_preview_pending {
+                self.sync_preview_rx = None;
+                // Threads finish in any order; list worktrees in session order.
+                let order: HashMap<SessionId, usize> = self
+                    .sessions
+                    .iter()
+                    .enumerate()
+                    .map(|(i, s)| (s.info.id, i))
+                    .collect();
+                self.sync_preview
+                    .sort_by_key(|item| order.get(&item.session_id).copied());
+                self.status_message = None;
+                self.show_sync_preview_modal = true;
+            }
+        }
+    }
+
+    /// Run the previewed sync.
+    pub(crate) fn confirm_sync_preview(&mut self) {
+        self.show_sync_preview_modal = false;
+        self.sync_preview.clear();
+        self.start_sync();
+    }
+
+    /// Close the preview without syncing.
+    pub(crate) fn cancel_sync_preview(&mut self) {
+        self.show_sync_preview_modal = false;
+        self.sync_preview.clear();
+        self.set_status(StatusLevel::Info, "Sync cancelled");
+    }
+
+    /// Start syncing all worktree sessions with their project's base branch.
+    ///
+    /// Worktrees sharing the same parent repo are synced sequentially (to avoid
+    /// concurrent `index.lock` contention), while different repos sync in
+    /// parallel, at most `[worktree_sync] max_concurrent` at a time.
+    pub(crate) fn start_sync(&mut self) {
+        if self.worktree_sync_in_progress {
+            return;
+        }
+
+        let by_repo = self.worktrees_by_repo();
+        let count: usize = by_repo.values().map(Vec::len).sum();
+        if count == 0 {
+            self.set_status(StatusLevel::Info, "No worktrees to sync");
+            return;
+        }
+
+        let (tx, rx) = mpsc::channel();
+        let jobs = by_repo
+            .into_iter()
+            .map(|(repo_path, worktrees)| {
+                let tx = tx.clone();
+                Box::new(move || {
+                    for (session_id, worktree_path, _, remote, configured, strategy) in worktrees {
+                        let base_branch =
+                            git::sync_base_branch(&repo_path, &remote, configured.as_deref());
+                        let result =
+                            git::sync_worktree(&worktree_path, &remote, &base_branch, strategy);
+                        let _ = tx.send((session_id, result));
+                    }
+                }) as worker_pool::Job
+            })
+            .collect();
+        worker_pool::spawn_bounded(jobs, self.settings.worktree_sync.max_concurrent);
+
         self.worktree_sync_in_progress = true;
         self.worktree_sync_rx = Some(rx);
         self.worktree_sync_pending = count;
@@ -1842,6 +5206,185 @@ impl App {
         self.set_status(StatusLevel::Info, format!("Syncing {count} worktree(s)..."));
     }
 
+    /// Push the active worktree session's branch to its project's sync
+    /// remote on a background thread. The outcome is reported by
+    /// [`Self::poll_push_result`].
+    pub(crate) fn start_push(&mut self) {
+        if self.branch_push_rx.is_some() {
+            return;
+        }
+        let Some(session) = self.sessions.get(self.active_index) else {
+            return;
+        };
+        let Some(branch) = session.info.worktrees.first().map(|wt| wt.branch.clone()) else {
+            self.set_status(StatusLevel::Info, "Not a worktree session");
+            return;
+        };
+        let worktree_paths: Vec<PathBuf> = session
+            .info
+            .worktrees
+            .iter()
+            .map(|wt| wt.worktree_path.clone())
+            .collect();
+        let (remote, _, _) = self.sync_settings_for(session.info.id);
+
+        let (tx, rx) = mpsc::channel();
+        let thread_branch = branch.clone();
+        let thread_remote = remote.clone();
+        std::thread::spawn(move || {
+            let results = worktree_paths
+                .iter()
+                .map(|path| git::push_branch(path, &thread_remote, &thread_branch, true))
+                .collect();
+            let _ = tx.send((thread_branch, thread_remote, results));
+        });
+
+        self.branch_push_rx = Some(rx);
+        self.set_status(
+            StatusLevel::Info,
+            format!("Pushing '{branch}' to {remote}..."),
+        );
+    }
+
+    /// Clone the prompted URL into the pending add-project repo on a
+    /// background thread. The outcome is reported by
+    /// [`Self::poll_clone_result`].
+    pub(crate) fn start_clone(&mut self) {
+        let url = self.add_project_clone_url.value().trim().to_string();
+        if url.is_empty() {
+            self.set_error("Clone URL cannot be empty");
+            return;
+        }
+        let Some(dest) = self.add_project_clone_dest.take() else {
+            return;
+        };
+        self.add_project_clone_url.clear();
+
+        let (tx, rx) = mpsc::channel();
+        let thread_dest = dest.clone();
+        let thread_url = url.clone();
+        std::thread::spawn(move || {
+            let result = git::clone(&thread_url, &thread_dest).map_err(|e| e.to_string());
+            let _ = tx.send((thread_dest, result));
+        });
+
+        self.repo_clone_rx = Some(rx);
+        self.set_status(
+            StatusLevel::Info,
+            format!("Cloning {url} into {}...", dest.display()),
+        );
+    }
+
+    /// Add the prompted repo as-is, without cloning, and carry on creating
+    /// the project (e.g. a new non-git project directory).
+    pub(crate) fn skip_clone(&mut self) {
+        let Some(dest) = self.add_project_clone_dest.take() else {
+            return;
+        };
+        self.add_project_clone_url.clear();
+        self.add_project_clone_skipped.push(dest);
+        self.submit_add_project();
+    }
+
+    /// Report a finished clone and resume creating the project if the
+    /// add-project modal is still open.
+    fn poll_clone_result(&mut self) {
+        let Some(rx) = &self.repo_clone_rx else {
+            return;
+        };
+        let (dest, result) = match rx.try_recv() {
+            Ok(done) => done,
+            Err(mpsc::TryRecvError::Empty) => return,
+            Err(mpsc::TryRecvError::Disconnected) => {
+                self.repo_clone_rx = None;
+                return;
+            }
+        };
+        self.repo_clone_rx = None;
+        match result {
+            Ok(()) => {
+                self.set_status(
+                    StatusLevel::Success,
+                    format!("Cloned into {}", dest.display()),
+                );
+                if self.show_add_project_modal {
+                    self.submit_add_project();
+                }
+            }
+            Err(e) => self.set_error(e),
+        }
+    }
+
+    /// Report a finished branch push in the status line.
+    fn poll_push_result(&mut self) {
+        let Some(rx) = &self.branch_push_rx else {
+            return;
+        };
+        let (branch, remote, results) = match rx.try_recv() {
+            Ok(done) => done,
+            Err(mpsc::TryRecvError::Empty) => return,
+            Err(mpsc::TryRecvError::Disconnected) => {
+                self.branch_push_rx = None;
+                return;
+            }
+        };
+        self.branch_push_rx = None;
+        self.finish_push(&branch, &remote, results);
+    }
+
+    /// Compose the status message for a branch push. Failures take
+    /// precedence; a push where every worktree was current is reported as
+    /// nothing to push.
+    fn finish_push(&mut self, branch: &str, remote: &str, results: Vec<git::PushResult>) {
+        let mut pushed = 0usize;
+        let mut errors = Vec::new();
+        for result in results {
+            match result {
+                git::PushResult::Pushed => pushed += 1,
+                git::PushResult::UpToDate => {}
+                git::PushResult::NoUpstream(msg) => errors.push(format!("no upstream ({msg})")),
+                git::PushResult::Rejected(msg) => errors.push(format!("rejected ({msg})")),
+                git::PushResult::Error(msg) => errors.push(msg),
+            }
+        }
+
+        if !errors.is_empty() {
+            self.set_error(format!("Push of '{branch}' failed: {}", errors.join(", ")));
+        } else if pushed == 0 {
+            self.set_status(
+                StatusLevel::Info,
+                format!("Nothing to push: '{branch}' is up to date"),
+            );
+        } else {
+            self.set_status(
+                StatusLevel::Success,
+                format!("Pushed '{branch}' to {remote}"),
+            );
+        }
+    }
+
+    /// The sync remote, configured base branch and strategy of the project
+    /// owning `session_id`.
+    fn sync_settings_for(&self, session_id: SessionId) -> (String, Option<String>, SyncStrategy) {
+        self.projects
+            .iter()
+            .find(|p| p.session_ids.contains(&session_id))
+            .map(|p| {
+                (
+                    p.config.effective_remote().to_string(),
+                    p.config.sync_base_branch.clone(),
+                    p.config.sync_strategy,
+                )
+            })
+            .unwrap_or_else(|| {
+                (
+                    crate::project::DEFAULT_REMOTE.to_string(),
+                    None,
+                    SyncStrategy::default(),
+                )
+            })
+    }
+
     /// Handle external state changes detected from other instances.
     fn handle_external_state_change(&mut self, delta: StateDelta) {
         // Update session counter to avoid conflicts
@@ -1865,8 +5408,15 @@ impl App {
 
         // Handle added projects from other instances
         for shared_project in delta.added_projects {
-            // Skip if we already have this project
-            if self.projects.iter().any(|p| p.id == shared_project.id) {
+            if self.is_hidden_admin_project(shared_project.id) {
+                continue;
+            }
+            // Same name, hence same id, created here too: merge the copies.
+            if let Some(index) = self.projects.iter().position(|p| p.id == shared_project.id) {
+                if merge_duplicate_project(&mut self.projects[index].config, &shared_project) {
+                    self.save_project_to_db(&self.projects[index]);
+                    tracing::info!("Merged duplicate project {}", shared_project.name);
+                }
                 continue;
             }
 
@@ -1886,6 +5436,13 @@ impl App {
                 project.config.repos = shared_project.repos;
                 project.config.roles = shared_project.roles;
                 project.config.mcp_servers = shared_project.mcp_servers;
+                project.config.sync_base_branch = shared_project.sync_base_branch;
+                project.config.remote = shared_project.remote;
+                project.config.sync_strategy = shared_project.sync_strategy;
+                project.config.activity_timeout_ms = shared_project.activity_timeout_ms;
+                project.config.worktree_root = shared_project.worktree_root;
+                project.config.default_role = shared_project.default_role;
+                project.config.session_init_command = shared_project.session_init_command;
                 tracing::debug!("Updated project {} from external state", project_name);
             }
         }
@@ -1917,13 +5474,29 @@ impl App {
             {
                 Self::apply_shared_session_metadata(session, &shared_session);
             }
+            // Moved to another project by another instance.
+            let target = self
+                .projects
+                .iter()
+                .position(|p| p.id == shared_session.project_id);
+            if let Some(target) = target {
+                if !self.projects[target]
+                    .session_ids
+                    .contains(&shared_session.id)
+                    && self.sessions.iter().any(|s| s.info.id == shared_session.id)
+                {
+                    self.move_session_to_project(shared_session.id, target);
+                }
+            }
         }
 
         // Handle added sessions from other instances
         // Try to adopt them from the backend using their backend_id
         for shared_session in delta.added_sessions {
             // Skip if we already have this session
-            if self.sessions.iter().any(|s| s.info.id == shared_session.id) {
+            if self.sessions.iter().any(|s| s.info.id == shared_session.id)
+                || self.is_hidden_admin_project(shared_session.project_id)
+            {
                 continue;
             }
 
@@ -1945,6 +5518,7 @@ impl App {
 
                     // Update with metadata from shared state
                     Self::apply_shared_session_metadata(&mut adopted_session, &shared_session);
+                    adopted_session.info.log_output = self.settings.session_logs.enabled;
 
                     // Add to sessions
                     let session_id = adopted_session.info.id;
@@ -1968,21 +5542,35 @@ impl App {
                     // If adopt failed but session has a claude_session_id,
                     // try spawning with --resume (e.g. restored via MCP).
                     if let Some(ref claude_sid) = shared_session.claude_session_id {
-                        let worktree_infos = Self::recreate_worktrees(&shared_session.worktrees);
+                        let worktree_root = self.project_worktree_root(shared_session.project_id);
+                        let worktree_infos = Self::recreate_worktrees(
+                            &shared_session.worktrees,
+                            worktree_root.as_deref(),
+                        );
                         let cwd = worktree_infos
                             .first()
                             .map(|wt| wt.worktree_path.clone())
                             .or(shared_session.cwd.clone());
 
                         let permissions = self.resolve_role_permissions(&shared_session.role);
-                        let config = SessionConfig {
+                        let mut config = SessionConfig {
+                            kind: SessionKind::Claude,
                             resume_session_id: Some(claude_sid.clone()),
                             claude_session_id: Some(claude_sid.clone()),
                             cwd,
                             additional_dirs: shared_session.additional_dirs.clone(),
                             role: shared_session.role.clone(),
                             permissions,
+                            log_output: self.settings.session_logs.enabled,
+                            extra_mcp_servers: Vec::new(),
+                            mcp_config: None,
+                            command: None,
                         };
+                        let project = self
+                            .projects
+                            .iter()
+                            .find(|p| p.id == shared_session.project_id);
+                        Self::attach_mcp_config(&mut config, project);
 
                         let (rows, cols) = self.content_area_size();
                         if let Ok(mut spawned) = Session::spawn(
@@ -2013,26 +5601,31 @@ impl App {
     }
 
     pub fn view(&self, frame: &mut Frame) {
-        let areas = layout::compute_layout(frame.area(), self.show_info_panel);
+        let theme = &self.theme;
+        let areas = self.compute_layout(frame.area());
 
-        status_bar::render_header(frame, areas.header);
+        status_bar::render_header(frame, theme, areas.header);
 
         // Left panel (projects + sessions)
+        let mut panel_regions = Vec::new();
         if let Some(left_area) = areas.left_panel {
             let project_entries: Vec<project_list::ProjectEntry<'_>> = self
-                .projects
-                .iter()
-                .map(|p| {
+                .visible_projects()
+                .into_iter()
+                .map(|index| {
+                    let p = &self.projects[index];
                     let mut busy_count = 0usize;
+                    let mut needs_input_count = 0usize;
                     let mut waiting_count = 0usize;
                     let mut error_count = 0usize;
                     for sid in &p.session_ids {
                         if let Some(s) = self.sessions.iter().find(|s| s.info.id == *sid) {
                             match s.info.status {
                                 SessionStatus::Busy => busy_count += 1,
+                                SessionStatus::NeedsInput => needs_input_count += 1,
                                 SessionStatus::Waiting => waiting_count += 1,
                                 SessionStatus::Error => error_count += 1,
-                                SessionStatus::Idle => {}
+                                SessionStatus::Idle | SessionStatus::Dormant => {}
                             }
                         }
                     }
@@ -2045,12 +5638,14 @@ impl App {
                     };
 
                     project_list::ProjectEntry {
+                        index,
                         name: &p.config.name,
                         is_admin: p.is_admin,
                         repo_count,
                         repo_short,
                         role_count: p.config.roles.len(),
                         busy_count,
+                        needs_input_count,
                         waiting_count,
                         error_count,
                     }
@@ -2082,11 +5677,20 @@ impl App {
                 InputFocus::Terminal => (FocusLevel::Inactive, FocusLevel::Active),
             };
 
-            project_list::render_left_panel(
+            panel_regions = project_list::render_left_panel(
                 frame,
+                theme,
                 left_area,
                 &project_list::LeftPanelState {
                     projects: &project_entries,
+                    project_filter: self
+                        .project_filter
+                        .as_ref()
+                        .map(|filter| filter.query.value()),
+                    project_filter_editing: self
+                        .project_filter
+                        .as_ref()
+                        .is_some_and(|filter| filter.editing),
                     active_project: self.active_project_index,
                     sessions: &project_sessions,
                     active_session: self.active_session_in_project(),
@@ -2095,15 +5699,56 @@ impl App {
                     panel_focused: self.focus != InputFocus::Terminal,
                     project_focus,
                     session_focus,
+                    status_icons: &self.status_icons,
                 },
             );
         }
+        *self.left_panel_regions.borrow_mut() = panel_regions;
+
+        // Session tab bar (tab layout mode)
+        if let Some(tabs_area) = areas.session_tabs {
+            let project_sessions: Vec<&SessionInfo> = self
+                .active_project_sessions()
+                .into_iter()
+                .map(|i| &self.sessions[i].info)
+                .collect();
+            session_tabs::render_session_tabs(
+                frame,
+                theme,
+                &self.status_icons,
+                tabs_area,
+                &project_sessions,
+                self.active_session_in_project(),
+            );
+        }
 
         // Info panel
         if let Some(info_area) = areas.info_panel {
             let active_project = self.projects.get(self.active_project_index);
             if let Some(session) = self.sessions.get(self.active_index) {
-                info_panel::render_info_panel(frame, info_area, &session.info, active_project);
+                let permissions = (self.info_panel_mode == info_panel::InfoPanelMode::Role)
+                    .then(|| self.resolve_role_permissions(&session.info.role));
+                info_panel::render_info_panel(
+                    frame,
+                    theme,
+                    info_area,
+                    &info_panel::InfoPanelState {
+                        mode: self.info_panel_mode,
+                        info: &session.info,
+                        project: active_project,
+                        log_toggle_key: &self.keymap.label(keymap::Action::ToggleOutputLog),
+                        worktree_statuses: &self.cached_worktree_statuses(&session.info.worktrees),
+                        idle_ms: session.millis_since_last_output(),
+                        created: session.info.created_at.map(format_time_ago),
+                        activity: self
+                            .activity_summary
+                            .as_ref()
+                            .filter(|(id, _, _)| *id == session.info.id)
+                            .and_then(|(_, _, summary)| *summary),
+                        permissions: permissions.as_ref(),
+                        status_icons: &self.status_icons,
+                    },
+                );
             }
         }
 
@@ -2129,6 +5774,8 @@ impl App {
                 if let Ok(mut parser) = parser_arc.lock() {
                     terminal_view::render_terminal(
                         frame,
+                        theme,
+                        &self.status_icons,
                         areas.terminal,
                         &mut parser,
                         &session.info,
@@ -2136,9 +5783,43 @@ impl App {
                         is_admin_project,
                         is_shell_view,
                     );
+                    if session.info.backend_dead && !is_shell_view {
+                        terminal_view::render_dead_session_bar(frame, theme, areas.terminal);
+                    }
+                    if let Some(sel) = self.terminal_selection {
+                        terminal_view::highlight_selection(
+                            frame,
+                            areas.terminal,
+                            &mut parser,
+                            sel.anchor,
+                            sel.cursor,
+                        );
+                        terminal_view::render_selection_bar(frame, theme, areas.terminal);
+                    }
+                    if let Some(search) = &self.terminal_search {
+                        terminal_view::highlight_search_matches(
+                            frame,
+                            theme,
+                            areas.terminal,
+                            &mut parser,
+                            search.query.value(),
+                            search.current,
+                        );
+                        terminal_view::render_search_bar(
+                            frame,
+                            theme,
+                            areas.terminal,
+                            &terminal_view::SearchBarState {
+                                query: search.query.value(),
+                                cursor: search.query.cursor_pos(),
+                                editing: search.editing,
+                                position: search.position,
+                            },
+                        );
+                    }
                 }
             }
-            None => terminal_view::render_empty_terminal(frame, areas.terminal),
+            None => terminal_view::render_empty_terminal(frame, theme, areas.terminal),
         }
 
         let focus_label = match self.focus {
@@ -2149,26 +5830,36 @@ impl App {
         };
         status_bar::render_footer(
             frame,
+            theme,
             areas.footer,
             &status_bar::FooterState {
                 session_count: self.sessions.len(),
                 project_count: self.projects.len(),
                 status: self.status_message.as_ref(),
                 focus_label,
+                session_filter: self.session_filter.label(),
                 sync_in_progress: self.worktree_sync_in_progress,
+                last_sync: self.last_sync_summary.as_ref(),
                 tick_count: self.tick_count,
+                instance_count: self.sync_state.live_instances().len(),
             },
         );
 
         // Help overlay (rendered last, on top of everything)
         if self.show_help {
-            render_help_overlay(frame);
+            let remote = self
+                .active_project()
+                .map_or(crate::project::DEFAULT_REMOTE, |p| {
+                    p.config.effective_remote()
+                });
+            render_help_overlay(frame, theme, remote);
         }
 
         // Add-project modal (on top of everything including help)
         if self.show_add_project_modal {
             add_project_modal::render_add_project_modal(
                 frame,
+                theme,
                 &add_project_modal::AddProjectModalState {
                     name: self.add_project_name.value(),
                     name_cursor: self.add_project_name.cursor_pos(),
@@ -2180,12 +5871,24 @@ impl App {
                     focused_field: self.add_project_field,
                 },
             );
+            if let Some(dest) = &self.add_project_clone_dest {
+                clone_repo_modal::render_clone_repo_modal(
+                    frame,
+                    theme,
+                    &clone_repo_modal::CloneRepoState {
+                        dest: &dest.to_string_lossy(),
+                        url: self.add_project_clone_url.value(),
+                        cursor: self.add_project_clone_url.cursor_pos(),
+                    },
+                );
+            }
         }
 
         // Edit-project modal
         if self.show_edit_project_modal {
             edit_project_modal::render_edit_project_modal(
                 frame,
+                theme,
                 &edit_project_modal::EditProjectModalState {
                     name: self.edit_project_name.value(),
                     name_cursor: self.edit_project_name.cursor_pos(),
@@ -2196,8 +5899,16 @@ impl App {
                     repo_index: self.edit_project_repo_index,
                     roles: &self.role_editor_roles,
                     role_index: self.role_editor_list_index,
+                    default_role: self.edit_project_default_role.as_deref(),
                     mcp_servers: &self.edit_project_mcp_servers,
                     mcp_server_index: self.edit_project_mcp_server_index,
+                    sync_strategy: self.edit_project_sync_strategy,
+                    activity_timeout: self.edit_project_activity_timeout.value(),
+                    activity_timeout_cursor: self.edit_project_activity_timeout.cursor_pos(),
+                    worktree_root: self.edit_project_worktree_root.value(),
+                    worktree_root_cursor: self.edit_project_worktree_root.cursor_pos(),
+                    init_command: self.edit_project_init_command.value(),
+                    init_command_cursor: self.edit_project_init_command.cursor_pos(),
                     focused_field: self.edit_project_field,
                 },
             );
@@ -2207,6 +5918,7 @@ impl App {
         if self.show_delete_project_modal_flag {
             delete_project_modal::render_delete_project_modal(
                 frame,
+                theme,
                 &delete_project_modal::DeleteProjectModalState {
                     project_name: &self.delete_project_name,
                     confirmation: self.delete_project_confirmation.value(),
@@ -2216,11 +5928,35 @@ impl App {
             );
         }
 
+        // Move-session project picker
+        if self.show_move_session_modal {
+            let session_name = self
+                .move_session_id
+                .and_then(|id| self.sessions.iter().find(|s| s.info.id == id))
+                .map_or("", |s| s.info.name.as_str());
+            let projects: Vec<&str> = self
+                .move_session_targets
+                .iter()
+                .filter_map(|&i| self.projects.get(i))
+                .map(|p| p.config.name.as_str())
+                .collect();
+            move_session_modal::render_move_session_modal(
+                frame,
+                theme,
+                &move_session_modal::MoveSessionState {
+                    session_name,
+                    projects: &projects,
+                    selected_index: self.move_session_index,
+                },
+            );
+        }
+
         // Repo selector modal
         if self.show_repo_selector {
             if let Some(active_project) = self.active_project() {
                 repo_selector_modal::render_repo_selector_modal(
                     frame,
+                    theme,
                     &repo_selector_modal::RepoSelectorState {
                         repos: &active_project.config.repos,
                         selected_index: self.repo_selector_index,
@@ -2231,10 +5967,17 @@ impl App {
 
         // Session mode modal
         if self.show_session_mode_modal {
+            let extra_mcp_names: Vec<&str> = self
+                .pending_spawn_mcp_servers
+                .iter()
+                .map(|s| s.name.as_str())
+                .collect();
             session_mode_modal::render_session_mode_modal(
                 frame,
+                theme,
                 &session_mode_modal::SessionModeState {
                     selected_index: self.session_mode_index,
+                    extra_mcp_servers: &extra_mcp_names,
                 },
             );
         }
@@ -2244,10 +5987,25 @@ impl App {
             let base = self.pending_base_branch.as_deref().unwrap_or("");
             worktree_name_modal::render_worktree_name_modal(
                 frame,
+                theme,
                 &worktree_name_modal::WorktreeNameState {
                     name: self.worktree_name_input.value(),
                     cursor: self.worktree_name_input.cursor_pos(),
                     base_branch: base,
+                    error: self.worktree_name_error.as_deref(),
+                },
+            );
+        }
+
+        // Resume-by-id modal
+        if self.show_resume_session_modal {
+            resume_session_modal::render_resume_session_modal(
+                frame,
+                theme,
+                &resume_session_modal::ResumeSessionState {
+                    session_id: self.resume_session_input.value(),
+                    cursor: self.resume_session_input.cursor_pos(),
+                    error: self.resume_session_error.as_deref(),
                 },
             );
         }
@@ -2256,6 +6014,7 @@ impl App {
         if self.show_branch_selector {
             branch_selector_modal::render_branch_selector_modal(
                 frame,
+                theme,
                 &branch_selector_modal::BranchSelectorState {
                     branches: &self.available_branches,
                     selected_index: self.branch_selector_index,
@@ -2268,9 +6027,11 @@ impl App {
             if let Some(project) = self.active_project() {
                 role_selector_modal::render_role_selector_modal(
                     frame,
+                    theme,
                     &role_selector_modal::RoleSelectorState {
                         roles: &project.config.roles,
                         selected_index: self.role_selector_index,
+                        remember: self.role_selector_remember,
                     },
                 );
             }
@@ -2280,6 +6041,7 @@ impl App {
         if self.show_role_editor {
             role_editor_modal::render_role_editor_modal(
                 frame,
+                theme,
                 &role_editor_modal::RoleEditorState {
                     project_name: self.edit_project_name.value(),
                     name: self.role_editor_name.value(),
@@ -2313,10 +6075,19 @@ impl App {
 
         // MCP editor modal (detail form, overlays edit-project modal)
         if self.show_mcp_editor {
+            let parent = match self.mcp_editor_target {
+                mcp_editor_modal::McpEditorTarget::Project => {
+                    format!("Edit \"{}\" > MCP Servers", self.edit_project_name.value())
+                }
+                mcp_editor_modal::McpEditorTarget::Spawn => {
+                    "New Session > Extra MCP Servers".to_string()
+                }
+            };
             crate::ui::mcp_editor_modal::render_mcp_editor_modal(
                 frame,
+                theme,
                 &crate::ui::mcp_editor_modal::McpEditorState {
-                    project_name: self.edit_project_name.value(),
+                    parent: &parent,
                     name: self.mcp_editor_name.value(),
                     name_cursor: self.mcp_editor_name.cursor_pos(),
                     command: self.mcp_editor_command.value(),
@@ -2332,6 +6103,7 @@ impl App {
                     env_input: self.mcp_editor_env.input.value(),
                     env_input_cursor: self.mcp_editor_env.input.cursor_pos(),
                     focused_field: self.mcp_editor_field,
+                    command_warning: self.mcp_editor_command_warning.as_deref(),
                 },
             );
         }
@@ -2350,6 +6122,7 @@ impl App {
                 .collect();
             restore_sessions_modal::render_restore_sessions_modal(
                 frame,
+                theme,
                 &restore_sessions_modal::RestoreSessionsModalState {
                     entries: &entries,
                     selected_index: self.restore_sessions_index,
@@ -2357,6 +6130,216 @@ impl App {
             );
         }
 
+        // Fuzzy switcher
+        if self.show_switcher {
+            let items: Vec<&str> = self
+                .switcher_matches
+                .iter()
+                .map(|&i| self.switcher_entries[i].label.as_str())
+                .collect();
+            switcher_modal::render_switcher_modal(
+                frame,
+                theme,
+                &switcher_modal::SwitcherModalState {
+                    query: self.switcher_input.value(),
+                    query_cursor: self.switcher_input.cursor_pos(),
+                    items: &items,
+                    selected_index: self.switcher_index,
+                },
+            );
+        }
+
+        // Role import/export prompt (overlays edit-project modal)
+        if let Some(action) = self.role_file_action {
+            role_file_modal::render_role_file_modal(
+                frame,
+                theme,
+                &role_file_modal::RoleFileState {
+                    action,
+                    path: self.role_file_path.value(),
+                    cursor: self.role_file_path.cursor_pos(),
+                },
+            );
+        }
+
+        // Rename session modal
+        if self.show_rename_session_modal {
+            rename_session_modal::render_rename_session_modal(
+                frame,
+                theme,
+                &rename_session_modal::RenameSessionState {
+                    name: self.rename_session_input.value(),
+                    cursor: self.rename_session_input.cursor_pos(),
+                },
+            );
+        }
+
+        // Session label modal
+        if self.show_session_label_modal {
+            session_label_modal::render_session_label_modal(
+                frame,
+                theme,
+                &session_label_modal::SessionLabelState {
+                    label: self.session_label_input.value(),
+                    label_cursor: self.session_label_input.cursor_pos(),
+                    color: self.session_label_color_input.value(),
+                    color_cursor: self.session_label_color_input.cursor_pos(),
+                    color_focused: self.session_label_color_focused,
+                },
+            );
+        }
+
+        // Extra directories editor
+        if self.show_session_dirs_modal {
+            let session_name = self
+                .session_dirs_id
+                .and_then(|id| self.sessions.iter().find(|s| s.info.id == id))
+                .map(|s| s.info.name.as_str())
+                .unwrap_or_default();
+            session_dirs_modal::render_session_dirs_modal(
+                frame,
+                theme,
+                &session_dirs_modal::SessionDirsState {
+                    session_name,
+                    dirs: &self.session_dirs.items,
+                    selected_index: self.session_dirs.selected,
+                    mode: self.session_dirs.mode,
+                    input: self.session_dirs.input.value(),
+                    input_cursor: self.session_dirs.input.cursor_pos(),
+                },
+            );
+        }
+
+        // Commit modal
+        if self.show_commit_modal {
+            let branch = self
+                .commit_session_id
+                .and_then(|id| self.sessions.iter().find(|s| s.info.id == id))
+                .and_then(|s| s.info.worktrees.first())
+                .map(|wt| wt.branch.as_str())
+                .unwrap_or_default();
+            commit_modal::render_commit_modal(
+                frame,
+                theme,
+                &commit_modal::CommitState {
+                    branch,
+                    message: self.commit_message_input.value(),
+                    cursor: self.commit_message_input.cursor_pos(),
+                },
+            );
+        }
+
+        // Send prompt modal
+        if self.show_send_prompt_modal {
+            let session = self
+                .send_prompt_session_id
+                .and_then(|id| self.sessions.iter().find(|s| s.info.id == id))
+                .map(|s| s.info.name.as_str())
+                .unwrap_or_default();
+            send_prompt_modal::render_send_prompt_modal(
+                frame,
+                theme,
+                &send_prompt_modal::SendPromptState {
+                    session,
+                    prompt: self.send_prompt_input.value(),
+                    cursor: self.send_prompt_input.cursor_pos(),
+                },
+            );
+        }
+
+        // Scrollback export prompt
+        if let Some(lines) = &self.scrollback_export {
+            export_modal::render_export_modal(
+                frame,
+                theme,
+                &export_modal::ExportState {
+                    line_count: lines.len(),
+                    path: self.export_path_input.value(),
+                    cursor: self.export_path_input.cursor_pos(),
+                },
+            );
+        }
+
+        // Close confirmation (dirty worktrees, or `[close] worktrees = "ask"`)
+        if self.show_close_session_modal {
+            let session = self
+                .close_session_id
+                .and_then(|id| self.sessions.iter().find(|s| s.info.id == id));
+            let session_name = session.map_or("", |s| s.info.name.as_str());
+            let branches: Vec<String> = session
+                .map(|s| s.info.worktrees.iter().map(|w| w.branch.clone()).collect())
+                .unwrap_or_default();
+            close_session_modal::render_close_session_modal(
+                frame,
+                theme,
+                &close_session_modal::CloseSessionState {
+                    session_name,
+                    branches: &branches,
+                    dirty_branches: &self.close_session_dirty,
+                    unpushed_branches: &self.close_session_unpushed,
+                },
+            );
+        }
+
+        // Pre-sync preview
+        if self.show_sync_preview_modal {
+            let entries: Vec<sync_preview_modal::SyncPreviewEntry> = self
+                .sync_preview
+                .iter()
+                .map(|item| sync_preview_modal::SyncPreviewEntry {
+                    session_name: self
+                        .sessions
+                        .iter()
+                        .find(|s| s.info.id == item.session_id)
+                        .map_or_else(|| "?".to_string(), |s| s.info.name.clone()),
+                    branch: item.branch.clone(),
+                    base_branch: item.base_branch.clone(),
+                    counts: item.counts.clone(),
+                })
+                .collect();
+            sync_preview_modal::render_sync_preview_modal(
+                frame,
+                theme,
+                &sync_preview_modal::SyncPreviewState { entries: &entries },
+            );
+        }
+
+        // Close-all-sessions confirmation
+        if self.show_close_all_modal {
+            if let Some(project) = self.active_project() {
+                close_all_modal::render_close_all_modal(
+                    frame,
+                    theme,
+                    &close_all_modal::CloseAllState {
+                        project_name: &project.config.name,
+                        session_count: project.session_ids.len(),
+                        dirty_count: self.close_all_dirty,
+                        offer_keep: self.settings.close.worktrees == settings::WorktreeOnClose::Ask,
+                    },
+                );
+            }
+        }
+
+        // Session limit confirmation
+        if self.session_limit_prompt.is_some() {
+            session_limit_modal::render_session_limit_modal(
+                frame,
+                theme,
+                self.user_session_count(),
+                self.settings.limits.max_sessions,
+            );
+        }
+
+        // Quit confirmation
+        if self.show_quit_modal {
+            quit_modal::render_quit_modal(
+                frame,
+                theme,
+                self.user_session_count(),
+                self.backend.keeps_sessions_on_detach(),
+            );
+        }
+
         // Discard confirmation overlay
         if self.show_discard_confirmation {
             let confirm_area = crate::ui::centered_fixed_height_rect(40, 5, frame.area());
@@ -2364,17 +6347,17 @@ impl App {
             let block = Block::default()
                 .title(" Unsaved Changes ")
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Theme::STATUS_ERROR));
+                .border_style(Style::default().fg(theme.status_error));
             let inner = block.inner(confirm_area);
             frame.render_widget(block, confirm_area);
             let text = Line::from(vec![
                 Span::styled(
                     " Discard changes? ",
-                    Style::default().fg(Theme::TEXT_PRIMARY),
+                    Style::default().fg(theme.text_primary),
                 ),
-                Span::styled("y", Theme::keybind()),
-                Span::styled("/", Style::default().fg(Theme::TEXT_MUTED)),
-                Span::styled("n", Theme::keybind()),
+                Span::styled("y", theme.keybind()),
+                Span::styled("/", Style::default().fg(theme.text_muted)),
+                Span::styled("n", theme.keybind()),
             ]);
             frame.render_widget(
                 Paragraph::new(text),
@@ -2394,6 +6377,9 @@ impl App {
         // Finalize any pending delete before shutting down
         self.finalize_pending_delete();
         self.save_state();
+        if let Err(e) = self.db.remove_heartbeat() {
+            tracing::warn!("Failed to remove instance heartbeat: {e}");
+        }
         // Do NOT remove worktrees — they persist for resume.
         // Detach from backend sessions without killing them — they persist in tmux.
         for session in self.sessions {
@@ -2403,17 +6389,47 @@ impl App {
 
     /// Set status bar message with the given severity level.
     fn set_status(&mut self, level: StatusLevel, text: impl Into<String>) {
+        self.needs_redraw = true;
         self.status_message = Some(StatusMessage {
             text: text.into(),
             level,
-            created_at: std::time::Instant::now(),
+            created_tick: self.tick_count,
+            error: None,
         });
     }
 
+    /// Clear the status message once it has outlived its `[status]` TTL.
+    /// Errors use the longer `error_ttl_ms`; a TTL of 0 never expires.
+    fn expire_status_message(&mut self) {
+        let Some(msg) = &self.status_message else {
+            return;
+        };
+        let ttl_ms = match msg.level {
+            StatusLevel::Error => self.settings.status.error_ttl_ms,
+            StatusLevel::Info | StatusLevel::Success => self.settings.status.ttl_ms,
+        };
+        if ttl_ms == 0 {
+            return;
+        }
+        let age_ticks = self.tick_count.wrapping_sub(msg.created_tick);
+        if age_ticks >= ttl_ms.div_ceil(TICK_MS) {
+            self.status_message = None;
+            self.needs_redraw = true;
+        }
+    }
+
     fn set_error(&mut self, text: impl Into<String>) {
         self.set_status(StatusLevel::Error, text.into());
     }
 
+    /// Show `error` as an error message, keeping its type for the UI.
+    fn set_app_error(&mut self, error: AppError) {
+        self.set_status(StatusLevel::Error, error.to_string());
+        if let Some(message) = &mut self.status_message {
+            message.error = Some(error);
+        }
+    }
+
     /// Capture current role editor field values as a snapshot for dirty detection.
     fn capture_role_editor_snapshot(&self) -> EditorSnapshot {
         EditorSnapshot {
@@ -2468,8 +6484,10 @@ impl App {
     fn close_mcp_editor(&mut self) {
         self.show_mcp_editor = false;
         self.mcp_editor_snapshot = None;
+        self.mcp_editor_command_warning = None;
         self.show_discard_confirmation = false;
         self.mcp_editor_field = crate::app::mcp_editor_modal::McpEditorField::Name;
+        self.return_from_mcp_editor();
     }
 
     /// Persist session state to the SQLite database.
@@ -2490,6 +6508,18 @@ impl App {
                 error!("Failed to upsert session to DB: {e}");
             }
         }
+
+        // Remember the selection for the next startup. The active session
+        // only counts if it is shown under the active project.
+        let project = self.active_project();
+        let session = self
+            .sessions
+            .get(self.active_index)
+            .map(|s| s.info.id)
+            .filter(|id| project.is_some_and(|p| p.session_ids.contains(id)));
+        if let Err(e) = self.db.set_last_active(project.map(|p| p.id), session) {
+            error!("Failed to save last active session to DB: {e}");
+        }
     }
 
     /// Persist a single project to the DB (insert or update).
@@ -2523,7 +6553,42 @@ impl App {
         if let Err(e) = self.db.replace_mcp_servers(id, &project.config.mcp_servers) {
             error!("Failed to save project MCP servers to DB: {e}");
         }
-    }
+
+        if let Err(e) = self
+            .db
+            .set_project_sync_strategy(id, project.config.sync_strategy)
+        {
+            error!("Failed to save project sync strategy to DB: {e}");
+        }
+
+        if let Err(e) = self
+            .db
+            .set_project_activity_timeout(id, project.config.activity_timeout_ms)
+        {
+            error!("Failed to save project activity timeout to DB: {e}");
+        }
+
+        if let Err(e) = self
+            .db
+            .set_project_worktree_root(id, project.config.worktree_root.as_deref())
+        {
+            error!("Failed to save project worktree root to DB: {e}");
+        }
+
+        if let Err(e) = self
+            .db
+            .set_project_default_role(id, project.config.default_role.as_deref())
+        {
+            error!("Failed to save project default role to DB: {e}");
+        }
+
+        if let Err(e) = self
+            .db
+            .set_project_session_init_command(id, project.config.session_init_command.as_deref())
+        {
+            error!("Failed to save project startup command to DB: {e}");
+        }
+    }
 
     /// Build a SharedSession from a local Session.
     fn session_to_shared(&self, session: &Session) -> sync::SharedSession {
@@ -2550,6 +6615,10 @@ impl App {
                 .map(Into::into)
                 .collect(),
             shell_backend_id: session.info.shell_backend_id.clone(),
+            is_pinned: session.info.is_pinned,
+            label: session.info.label.clone(),
+            label_color: session.info.label_color.clone(),
+            created_at: session.info.created_at,
             tombstone: false,
             tombstone_at: None,
         }
@@ -2582,141 +6651,337 @@ impl App {
     /// Restore sessions from the database on startup.
     ///
     /// Tries to adopt existing backend sessions (tmux windows) or spawns new
-    /// sessions with `--resume` to reconnect to the Claude session.
+    /// sessions with `--resume` to reconnect to the Claude session. With
+    /// `[restore] lazy`, sessions become dormant placeholders instead and are
+    /// only adopted or resumed when first focused.
+    ///
+    /// The session that was active at the last save is selected again, even
+    /// if it was respawned under a new id. If it is gone, the last restored
+    /// session stays selected unless it belongs to another project than the
+    /// active one, in which case that project's first session is used.
     pub fn restore_sessions(&mut self, sessions: Vec<sync::SharedSession>, session_counter: usize) {
         self.session_counter = session_counter;
 
+        let last_session = self.db.get_last_active().ok().and_then(|(_, s)| s);
+        let lazy = self.settings.restore.lazy;
         // Discover existing sessions from the backend.
-        let discovered = self.backend.discover().unwrap_or_default();
+        let discovered = if lazy {
+            Vec::new()
+        } else {
+            self.backend.discover().unwrap_or_default()
+        };
 
+        let mut remembered = None;
         for shared in sessions {
-            let name = shared.name;
-            let session_id = shared.id;
-
-            let role = if shared.role.is_empty() {
-                DEFAULT_ROLE_NAME.to_string()
+            let id = shared.id;
+            let restored_before = self.sessions.len();
+            if lazy {
+                self.restore_dormant_session(shared);
             } else {
-                shared.role
-            };
+                self.restore_session(shared, &discovered);
+            }
+            if Some(id) == last_session && self.sessions.len() > restored_before {
+                remembered = Some(restored_before);
+            }
+        }
+
+        match remembered {
+            Some(index) => {
+                self.active_index = index;
+                let id = self.sessions[index].info.id;
+                if let Some(project) = self
+                    .projects
+                    .iter()
+                    .position(|p| p.session_ids.contains(&id))
+                {
+                    self.active_project_index = project;
+                }
+            }
+            None => {
+                // Keep the active session under the active project
+                let shown = self.active_project_sessions();
+                if !shown.contains(&self.active_index) {
+                    if let Some(&first) = shown.first() {
+                        self.active_index = first;
+                    }
+                }
+            }
+        }
 
-            let worktrees: Vec<WorktreeInfo> =
-                shared.worktrees.into_iter().map(Into::into).collect();
+        // Claim ownership of restored sessions in the shared state
+        self.save_state();
+    }
 
-            let claude_session_id = match shared.claude_session_id {
-                Some(id) => id,
-                None => continue, // Skip sessions without a claude session ID
-            };
+    /// Adopt the backend session matching `shared`, or respawn it with
+    /// `--resume` when none is alive.
+    fn restore_session(&mut self, shared: sync::SharedSession, discovered: &[DiscoveredSession]) {
+        let name = shared.name;
+        let session_id = shared.id;
 
-            // Try to match a discovered backend session by backend_id.
-            let matching_discovered = if !shared.backend_id.is_empty() {
-                discovered
-                    .iter()
-                    .find(|d| d.backend_id == shared.backend_id && d.is_alive)
-            } else {
-                // Fall back to matching by window name (tb-<name>).
-                let expected_name = format!("tb-{name}");
+        let role = if shared.role.is_empty() {
+            DEFAULT_ROLE_NAME.to_string()
+        } else {
+            shared.role
+        };
+
+        let worktrees: Vec<WorktreeInfo> = shared.worktrees.into_iter().map(Into::into).collect();
+
+        let claude_session_id = match shared.claude_session_id {
+            Some(id) => id,
+            None => return, // Skip sessions without a claude session ID
+        };
+
+        // Match a discovered backend session by the persisted backend_id,
+        // which survives renames, then fall back to the window name.
+        let window_name = format!("tb-{name}");
+        let matching_discovered = discovered
+            .iter()
+            .find(|d| {
+                d.is_alive && !shared.backend_id.is_empty() && d.backend_id == shared.backend_id
+            })
+            .or_else(|| {
                 discovered
                     .iter()
-                    .find(|d| d.name == expected_name && d.is_alive)
-            };
+                    .find(|d| d.is_alive && d.name == window_name)
+            });
 
-            // Try to adopt the existing backend session.
-            let env = self.resolve_role_permissions(&role).env;
-            let adopted = matching_discovered.and_then(|disc| {
-                let (rows, cols) = self.content_area_size();
-                match Session::adopt(
-                    name.clone(),
-                    rows,
-                    cols,
-                    &disc.backend_id,
-                    &self.backend,
-                    env.clone(),
-                ) {
-                    Ok(session) => Some(session),
-                    Err(e) => {
-                        error!("Failed to adopt session '{name}': {e}");
-                        None
+        // Try to adopt the existing backend session.
+        let env = self.resolve_role_permissions(&role).env;
+        let adopted = matching_discovered.and_then(|disc| {
+            let (rows, cols) = self.content_area_size();
+            match Session::adopt(
+                name.clone(),
+                rows,
+                cols,
+                &disc.backend_id,
+                &self.backend,
+                env.clone(),
+            ) {
+                Ok(session) => {
+                    // Keep the window name in step with a session renamed
+                    // since it was spawned, so name matching keeps working.
+                    if disc.name != window_name {
+                        if let Err(e) = self.backend.rename(&disc.backend_id, &window_name) {
+                            tracing::warn!("Failed to rename window for '{name}': {e}");
+                        }
                     }
+                    Some(session)
                 }
-            });
+                Err(e) => {
+                    error!("Failed to adopt session '{name}': {e}");
+                    None
+                }
+            }
+        });
 
-            if let Some(mut session) = adopted {
-                session.info.id = session_id;
-                session.info.claude_session_id = Some(claude_session_id.clone());
-                session.info.cwd = shared.cwd.clone();
-                session.info.additional_dirs = shared.additional_dirs.clone();
-                session.info.role = role;
-                session.info.worktrees = worktrees.clone();
-
-                // Re-adopt shell pane if one was persisted
-                if let Some(shell_bid) = &shared.shell_backend_id {
-                    if discovered
-                        .iter()
-                        .any(|d| d.backend_id == *shell_bid && d.is_alive)
-                    {
-                        let (rows, cols) = self.content_area_size();
-                        if let Err(e) = session.adopt_shell_pane(shell_bid, rows, cols) {
-                            tracing::warn!("Failed to re-adopt shell pane: {e}");
-                        }
+        if let Some(mut session) = adopted {
+            session.info.id = session_id;
+            session.info.claude_session_id = Some(claude_session_id.clone());
+            session.info.cwd = shared.cwd.clone();
+            session.info.additional_dirs = shared.additional_dirs.clone();
+            session.info.role = role;
+            session.info.worktrees = worktrees.clone();
+            session.info.is_pinned = shared.is_pinned;
+            session.info.label = shared.label.clone();
+            session.info.label_color = shared.label_color.clone();
+            session.info.created_at = shared.created_at;
+            session.info.log_output = self.settings.session_logs.enabled;
+
+            // Re-adopt shell pane if one was persisted
+            if let Some(shell_bid) = &shared.shell_backend_id {
+                if discovered
+                    .iter()
+                    .any(|d| d.backend_id == *shell_bid && d.is_alive)
+                {
+                    let (rows, cols) = self.content_area_size();
+                    if let Err(e) = session.adopt_shell_pane(shell_bid, rows, cols) {
+                        tracing::warn!("Failed to re-adopt shell pane: {e}");
                     }
                 }
+            }
 
-                let sid = session.info.id;
-                self.sessions.push(session);
-                self.active_index = self.sessions.len() - 1;
-                self.focus = InputFocus::Terminal;
+            let sid = session.info.id;
+            self.sessions.push(session);
+            self.active_index = self.sessions.len() - 1;
+            self.focus = InputFocus::Terminal;
 
-                // Associate with the original project
-                let target_project_index =
-                    self.find_project_index_for_session(sid, &shared.project_id);
+            // Associate with the original project
+            let target_project_index = self.find_project_index_for_session(sid, &shared.project_id);
 
-                if let Some(project) = self.projects.get_mut(target_project_index) {
-                    if !project.session_ids.contains(&sid) {
-                        project.session_ids.push(sid);
-                    }
+            if let Some(project) = self.projects.get_mut(target_project_index) {
+                if !project.session_ids.contains(&sid) {
+                    project.session_ids.push(sid);
                 }
-            } else {
-                // No matching backend session or adopt failed — spawn new with --resume.
-                // Soft-delete the stale session entry to prevent duplication on next restart.
-                if let Err(e) = self.db.soft_delete_session(session_id) {
-                    error!("Failed to soft-delete stale session {session_id}: {e}");
+            }
+        } else if let Some(cwd) = missing_cwd(shared.cwd.as_deref()).map(Path::to_path_buf) {
+            // Resuming would fail inside claude with a confusing error. Keep
+            // the session as an unspawned placeholder the user can restart
+            // once the directory is back, or close.
+            self.set_error(format!(
+                "Working directory of '{name}' no longer exists: {}",
+                cwd.display()
+            ));
+            let mut info = SessionInfo::new(name);
+            info.id = session_id;
+            info.status = SessionStatus::Error;
+            info.cwd_missing = true;
+            info.role = role;
+            info.worktrees = worktrees;
+            info.claude_session_id = Some(claude_session_id);
+            info.cwd = shared.cwd;
+            info.additional_dirs = shared.additional_dirs;
+            info.is_pinned = shared.is_pinned;
+            info.label = shared.label.clone();
+            info.label_color = shared.label_color.clone();
+            info.created_at = shared.created_at;
+
+            let session = Session::dormant(info, String::new(), &self.backend);
+            self.sessions.push(session);
+            self.active_index = self.sessions.len() - 1;
+
+            let target_project_index =
+                self.find_project_index_for_session(session_id, &shared.project_id);
+            if let Some(project) = self.projects.get_mut(target_project_index) {
+                if !project.session_ids.contains(&session_id) {
+                    project.session_ids.push(session_id);
                 }
+            }
+        } else {
+            // No matching backend session or adopt failed — spawn new with --resume.
+            // Soft-delete the stale session entry to prevent duplication on next restart.
+            if let Err(e) = self.db.soft_delete_session(session_id) {
+                error!("Failed to soft-delete stale session {session_id}: {e}");
+            }
 
-                // Look up the original project so we respawn into the correct one.
-                let target_project_index =
-                    self.find_project_index_for_session(session_id, &shared.project_id);
+            // Look up the original project so we respawn into the correct one.
+            let target_project_index =
+                self.find_project_index_for_session(session_id, &shared.project_id);
 
-                let is_admin = self
-                    .projects
-                    .get(target_project_index)
-                    .is_some_and(|p| p.config.name == "Admin");
+            let is_admin = self
+                .projects
+                .get(target_project_index)
+                .is_some_and(|p| p.config.name == "Admin");
 
-                let permissions =
-                    self.resolve_role_permissions_for_project(&role, target_project_index);
+            let permissions =
+                self.resolve_role_permissions_for_project(&role, target_project_index);
 
-                // Admin sessions start fresh — --resume would fail because the
-                // old Claude conversation no longer exists after a tmux restart.
-                let config = SessionConfig {
-                    resume_session_id: if is_admin {
-                        None
-                    } else {
-                        Some(claude_session_id.clone())
-                    },
-                    claude_session_id: if is_admin {
-                        None
-                    } else {
-                        Some(claude_session_id)
-                    },
-                    cwd: shared.cwd,
-                    additional_dirs: shared.additional_dirs,
-                    role,
-                    permissions,
-                };
-                self.do_spawn_session(name, &config, worktrees, Some(target_project_index));
+            // Admin sessions start fresh — --resume would fail because the
+            // old Claude conversation no longer exists after a tmux restart.
+            let config = SessionConfig {
+                kind: SessionKind::Claude,
+                resume_session_id: if is_admin {
+                    None
+                } else {
+                    Some(claude_session_id.clone())
+                },
+                claude_session_id: if is_admin {
+                    None
+                } else {
+                    Some(claude_session_id)
+                },
+                cwd: shared.cwd,
+                additional_dirs: shared.additional_dirs,
+                role,
+                permissions,
+                log_output: self.settings.session_logs.enabled,
+                extra_mcp_servers: Vec::new(),
+                mcp_config: None,
+                command: is_admin
+                    .then(|| self.settings.admin.spawn_command())
+                    .flatten(),
+            };
+            let spawned_before = self.sessions.len();
+            self.do_spawn_session(name, &config, worktrees, Some(target_project_index));
+            if self.sessions.len() > spawned_before {
+                if let Some(session) = self.sessions.last_mut() {
+                    session.info.is_pinned = shared.is_pinned;
+                    session.info.label = shared.label.clone();
+                    session.info.label_color = shared.label_color.clone();
+                    session.info.created_at = shared.created_at;
+                }
             }
         }
+    }
 
-        // Claim ownership of restored sessions in the shared state
+    /// Add a placeholder for `shared` without touching the backend.
+    fn restore_dormant_session(&mut self, shared: sync::SharedSession) {
+        if shared.claude_session_id.is_none() {
+            return;
+        }
+
+        let mut info = SessionInfo::new(shared.name.clone());
+        info.id = shared.id;
+        info.status = SessionStatus::Dormant;
+        info.role = if shared.role.is_empty() {
+            DEFAULT_ROLE_NAME.to_string()
+        } else {
+            shared.role.clone()
+        };
+        info.worktrees = shared.worktrees.iter().cloned().map(Into::into).collect();
+        info.claude_session_id = shared.claude_session_id.clone();
+        info.cwd = shared.cwd.clone();
+        info.additional_dirs = shared.additional_dirs.clone();
+        info.backend_id = Some(shared.backend_id.clone()).filter(|id| !id.is_empty());
+        info.shell_backend_id = shared.shell_backend_id.clone();
+        info.is_pinned = shared.is_pinned;
+        info.label = shared.label.clone();
+        info.label_color = shared.label_color.clone();
+        info.created_at = shared.created_at;
+
+        let sid = info.id;
+        let session = Session::dormant(info, shared.backend_id.clone(), &self.backend);
+        self.sessions.push(session);
+        self.active_index = self.sessions.len() - 1;
+        self.focus = InputFocus::Terminal;
+
+        let target_project_index = self.find_project_index_for_session(sid, &shared.project_id);
+        if let Some(project) = self.projects.get_mut(target_project_index) {
+            if !project.session_ids.contains(&sid) {
+                project.session_ids.push(sid);
+            }
+        }
+
+        self.dormant_sessions.insert(sid, shared);
+    }
+
+    /// Adopt or resume the dormant session at `index`, keeping its place in
+    /// the session and project lists. Does nothing for live sessions.
+    fn wake_session(&mut self, index: usize) {
+        let Some(id) = self.sessions.get(index).map(|s| s.info.id) else {
+            return;
+        };
+        let Some(shared) = self.dormant_sessions.remove(&id) else {
+            return;
+        };
+
+        let slot = self.projects.iter().enumerate().find_map(|(pi, p)| {
+            p.session_ids
+                .iter()
+                .position(|sid| *sid == id)
+                .map(|pos| (pi, pos))
+        });
+        self.sessions.remove(index);
+        if let Some((pi, pos)) = slot {
+            self.projects[pi].session_ids.remove(pos);
+        }
+
+        let focus = self.focus;
+        let discovered = self.backend.discover().unwrap_or_default();
+        let restored_before = self.sessions.len();
+        self.restore_session(shared, &discovered);
+        self.focus = focus;
+
+        if self.sessions.len() > restored_before {
+            let session = self.sessions.remove(restored_before);
+            let new_id = session.info.id;
+            self.sessions.insert(index, session);
+            if let Some((pi, pos)) = slot {
+                let ids = &mut self.projects[pi].session_ids;
+                ids.retain(|sid| *sid != new_id);
+                ids.insert(pos.min(ids.len()), new_id);
+            }
+        }
+        self.active_index = index.min(self.sessions.len().saturating_sub(1));
         self.save_state();
     }
 
@@ -2803,7 +7068,14 @@ impl App {
         };
 
         let session = &self.sessions[session_idx];
-        let Some(claude_session_id) = session.info.claude_session_id.clone() else {
+        if session.info.crash_looping {
+            error!(
+                "Ignoring restart command for crash-looping session {}",
+                cmd.session_id
+            );
+            return;
+        }
+        let Some(config) = self.restart_config(session_idx) else {
             error!(
                 "Cannot restart session {} without claude_session_id",
                 cmd.session_id
@@ -2811,29 +7083,15 @@ impl App {
             return;
         };
 
-        let role = session.info.role.clone();
-        let cwd = session.info.cwd.clone();
-        let additional_dirs = session.info.additional_dirs.clone();
-
-        // Find the project that owns this session (may not be the active project)
-        let project_index = self
-            .projects
-            .iter()
-            .position(|p| p.session_ids.contains(&cmd.session_id))
-            .unwrap_or(self.active_project_index);
-        let permissions = self.resolve_role_permissions_for_project(&role, project_index);
-
-        let config = SessionConfig {
-            resume_session_id: Some(claude_session_id.clone()),
-            claude_session_id: Some(claude_session_id),
-            cwd,
-            additional_dirs,
-            role,
-            permissions,
-        };
-
         let (rows, cols) = self.content_area_size();
         let session = &mut self.sessions[session_idx];
+        if session.info.record_restart(sync::current_time_millis()) {
+            let name = session.info.name.clone();
+            self.set_error(format!(
+                "'{name}' is crash looping. Ctrl+R to reset and restart"
+            ));
+            return;
+        }
         match session.restart(&config, rows, cols) {
             Ok(()) => {
                 self.save_state();
@@ -2847,15 +7105,25 @@ impl App {
         }
     }
 
+    /// Panel areas for the current info panel and layout mode.
+    pub(crate) fn compute_layout(&self, area: Rect) -> layout::PanelAreas {
+        let mode = if self.show_session_tabs {
+            layout::LayoutMode::Tabs
+        } else {
+            layout::LayoutMode::Panels
+        };
+        layout::compute_layout(area, self.show_info_panel, mode, self.panel_ratios)
+    }
+
     pub(crate) fn content_area_size(&self) -> (u16, u16) {
         let area = Rect::new(0, 0, self.terminal_cols, self.terminal_rows);
-        let terminal = layout::compute_layout(area, self.show_info_panel).terminal;
+        let terminal = self.compute_layout(area).terminal;
         let inner = Block::default().borders(Borders::ALL).inner(terminal);
         (inner.height, inner.width)
     }
 }
 
-fn render_help_overlay(frame: &mut Frame) {
+fn render_help_overlay(frame: &mut Frame, theme: &Theme, remote: &str) {
     let area = centered_rect(60, 70, frame.area());
 
     frame.render_widget(Clear, area);
@@ -2863,55 +7131,94 @@ fn render_help_overlay(frame: &mut Frame) {
     let block = Block::default()
         .title(" Keybindings ")
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Theme::ACCENT));
+        .border_style(Style::default().fg(theme.accent));
 
+    let push_desc = format!("Push worktree branch to {remote}");
     let help_lines = vec![
-        help_section("Navigation (Vim: h/j/k/l)"),
-        help_line("Ctrl+H", "Focus project list (h = left)"),
-        help_line("Ctrl+J", "Next project (project focus) / session"),
-        help_line("Ctrl+K", "Previous project (project focus) / session"),
-        help_line("Ctrl+L", "Cycle focus (l = right/forward)"),
+        help_section("Navigation (Vim: h/j/k/l)", theme),
+        help_line("Ctrl+H", "Focus project list (h = left)", theme),
+        help_line("Ctrl+J", "Next project (project focus) / session", theme),
+        help_line(
+            "Ctrl+K",
+            "Previous project (project focus) / session",
+            theme,
+        ),
+        help_line("Ctrl+L", "Cycle focus (l = right/forward)", theme),
+        help_line("Alt+1..9", "Jump to Nth session of active project", theme),
+        help_line("Ctrl+P", "Fuzzy-find a project or session", theme),
         Line::from(""),
-        help_section("Session Management"),
-        help_line("Ctrl+N", "New project (project focus) / session"),
-        help_line("Ctrl+C", "Close active session"),
-        help_line("Ctrl+R", "Restart active session"),
-        help_line("Ctrl+S", "Sync all worktrees with main"),
-        help_line("Ctrl+T", "Toggle shell pane"),
-        help_line("Ctrl+Z", "Undo session delete"),
-        help_line("Ctrl+U", "Restore deleted session"),
+        help_section("Session Management", theme),
+        help_line("Ctrl+N", "New project (project focus) / session", theme),
+        help_line(
+            "Ctrl+C",
+            "Close active session (interrupts in terminal)",
+            theme,
+        ),
+        help_line("Ctrl+X", "Close active session (any focus)", theme),
+        help_line("Ctrl+R", "Restart active session", theme),
+        help_line("Ctrl+S", "Sync all worktrees with base branch", theme),
+        help_line("F5", &push_desc, theme),
+        help_line("F6", "Commit all worktree changes", theme),
+        help_line("F7", "Export scrollback to a file", theme),
+        help_line("Ctrl+T", "Toggle shell pane", theme),
+        help_line("Ctrl+Z", "Undo session delete", theme),
+        help_line("Ctrl+U", "Restore deleted session", theme),
+        help_line("Ctrl+,", "Rename active session", theme),
+        help_line("Ctrl+F", "Filter sessions: all / waiting / busy", theme),
+        help_line("Ctrl+Y", "Copy session directory to clipboard", theme),
         Line::from(""),
-        help_section("Project Management"),
+        help_section("Project Management", theme),
         help_line(
             "Ctrl+D",
             "Delete session (session list) / project (project list)",
+            theme,
         ),
-        help_line("Ctrl+E", "Edit active project (name, repos, roles)"),
+        help_line("Ctrl+E", "Edit active project (name, repos, roles)", theme),
         Line::from(""),
-        help_section("UI"),
-        help_line("Ctrl+Q", "Quit Thurbox"),
-        help_line("F1", "Show this help"),
-        help_line("F2", "Toggle info panel"),
+        help_section("UI", theme),
+        help_line("Ctrl+Q", "Quit Thurbox", theme),
+        help_line("F1", "Show this help", theme),
+        help_line("F2", "Toggle info panel", theme),
+        help_line("F9", "Cycle info panel: session / git / role", theme),
+        help_line("F10", "Show the last sync result again", theme),
+        help_line("F3", "Toggle output log", theme),
+        help_line("F4", "Toggle session tab bar", theme),
+        help_line("F8", "Toggle line wrap (rewrap on resize)", theme),
         Line::from(""),
-        help_section("Project List (when focused)"),
-        help_line("j / Down", "Next project"),
-        help_line("k / Up", "Previous project"),
-        help_line("Enter", "Focus session list"),
+        help_section("Project List (when focused)", theme),
+        help_line("j / Down", "Next project", theme),
+        help_line("k / Up", "Previous project", theme),
+        help_line("Enter", "Focus session list", theme),
+        help_line("x", "Close all sessions of the project", theme),
+        help_line("/", "Filter projects by name (Esc clears)", theme),
         Line::from(""),
-        help_section("Session List (when focused)"),
-        help_line("j / Down", "Next session"),
-        help_line("k / Up", "Previous session"),
-        help_line("Enter", "Focus terminal"),
+        help_section("Session List (when focused)", theme),
+        help_line("j / Down", "Next session", theme),
+        help_line("k / Up", "Previous session", theme),
+        help_line("Enter", "Focus terminal", theme),
+        help_line("r", "Rename session", theme),
+        help_line("Ctrl+Shift+J/K", "Move session down/up", theme),
+        help_line("p", "Pin / unpin session", theme),
+        help_line("a", "Edit extra directories (--add-dir)", theme),
+        help_line("D", "Duplicate session (same role and directory)", theme),
+        help_line("s", "Send a prompt to the session", theme),
+        help_line("Click", "Select project/session in left panel", theme),
         Line::from(""),
-        help_section("Terminal (when focused)"),
-        help_line("Shift+\u{2191}/\u{2193}", "Scroll up/down 1 line"),
-        help_line("Shift+PgUp/PgDn", "Scroll up/down half page"),
-        help_line("Mouse wheel", "Scroll up/down 3 lines"),
-        help_line("*", "All other keys forwarded to session"),
+        help_section("Terminal (when focused)", theme),
+        help_line("Shift+\u{2191}/\u{2193}", "Scroll up/down 1 line", theme),
+        help_line("Shift+PgUp/PgDn", "Scroll up/down half page", theme),
+        help_line("Mouse wheel", "Scroll up/down 3 lines", theme),
+        help_line("End", "Back to bottom (while scrolled up)", theme),
+        help_line("/", "Search scrollback (while scrolled up)", theme),
+        help_line("n / N", "Older / newer search match", theme),
+        help_line("v", "Copy mode (while scrolled up)", theme),
+        help_line("h/j/k/l, 0/$", "Copy mode: move selection end", theme),
+        help_line("y", "Copy mode: yank to clipboard", theme),
+        help_line("*", "All other keys forwarded to session", theme),
         Line::from(""),
         Line::from(Span::styled(
             "Press Esc to close",
-            Style::default().fg(Theme::TEXT_MUTED),
+            Style::default().fg(theme.text_muted),
         )),
     ];
 
@@ -2919,38 +7226,17 @@ fn render_help_overlay(frame: &mut Frame) {
     frame.render_widget(paragraph, area);
 }
 
-fn help_section(title: &str) -> Line<'_> {
-    Line::from(Span::styled(title, Theme::section_header()))
+fn help_section<'a>(title: &'a str, theme: &Theme) -> Line<'a> {
+    Line::from(Span::styled(title, theme.section_header()))
 }
 
-fn help_line<'a>(key: &'a str, desc: &'a str) -> Line<'a> {
+fn help_line<'a>(key: &'a str, desc: &'a str, theme: &Theme) -> Line<'a> {
     Line::from(vec![
-        Span::styled(format!("  {key:<16}"), Theme::keybind()),
-        Span::styled(desc, Style::default().fg(Theme::TEXT_PRIMARY)),
+        Span::styled(format!("  {key:<16}"), theme.keybind()),
+        Span::styled(desc, Style::default().fg(theme.text_primary)),
     ])
 }
 
-/// Create a centered rectangle within the given area.
-fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
-    let vertical = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Percentage((100 - percent_y) / 2),
-            Constraint::Percentage(percent_y),
-            Constraint::Percentage((100 - percent_y) / 2),
-        ])
-        .split(area);
-
-    Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([
-            Constraint::Percentage((100 - percent_x) / 2),
-            Constraint::Percentage(percent_x),
-            Constraint::Percentage((100 - percent_x) / 2),
-        ])
-        .split(vertical[1])[1]
-}
-
 /// Format a millisecond timestamp as a human-readable "time ago" string.
 fn format_time_ago(millis: u64) -> String {
     let now = crate::sync::current_time_millis();
@@ -3199,6 +7485,9 @@ mod tests {
         fn detach(&self, _: &str) -> anyhow::Result<()> {
             Ok(())
         }
+        fn rename(&self, _: &str, _: &str) -> anyhow::Result<()> {
+            Ok(())
+        }
     }
 
     fn stub_backend() -> Arc<dyn SessionBackend> {
@@ -3216,6 +7505,13 @@ mod tests {
             repos: vec![PathBuf::from("/test")],
             roles: Vec::new(),
             mcp_servers: Vec::new(),
+            sync_base_branch: None,
+            remote: None,
+            sync_strategy: SyncStrategy::default(),
+            activity_timeout_ms: None,
+            worktree_root: None,
+            default_role: None,
+            session_init_command: None,
             id: None,
         }
     }
@@ -3228,6 +7524,13 @@ mod tests {
         if !config.roles.is_empty() {
             db.replace_roles(id, &config.roles).unwrap();
         }
+        if let Some(role) = &config.default_role {
+            db.set_project_default_role(id, Some(role)).unwrap();
+        }
+        if let Some(command) = &config.session_init_command {
+            db.set_project_session_init_command(id, Some(command))
+                .unwrap();
+        }
         db
     }
 
@@ -3303,104 +7606,578 @@ mod tests {
         assert_eq!(app.active_index, 0);
     }
 
-    // --- Scroll tests ---
+    // --- Session reordering tests ---
 
-    fn parser_with_scrollback() -> vt100::Parser {
-        let mut parser = vt100::Parser::new(24, 80, 100);
-        // Fill screen and scrollback by writing many lines
-        for i in 0..50 {
-            parser.process(format!("line {i}\r\n").as_bytes());
-        }
-        parser
-    }
+    const CTRL_SHIFT: KeyModifiers = KeyModifiers::CONTROL.union(KeyModifiers::SHIFT);
 
-    #[test]
-    fn scrollback_starts_at_zero() {
-        let parser = parser_with_scrollback();
-        assert_eq!(parser.screen().scrollback(), 0);
+    fn session_ids(app: &App) -> Vec<SessionId> {
+        app.sessions.iter().map(|s| s.info.id).collect()
     }
 
     #[test]
-    fn scrollback_increments() {
-        let mut parser = parser_with_scrollback();
-        parser.screen_mut().set_scrollback(5);
-        assert_eq!(parser.screen().scrollback(), 5);
-    }
+    fn ctrl_shift_j_moves_session_down() {
+        let mut app = app_with_sessions(3);
+        app.save_state();
+        let ids = session_ids(&app);
+        app.focus = InputFocus::SessionList;
+        app.active_index = 0;
 
-    #[test]
-    fn scrollback_clamps_to_max() {
-        let mut parser = parser_with_scrollback();
-        parser.screen_mut().set_scrollback(usize::MAX);
-        let max = parser.screen().scrollback();
-        // Should be clamped to the actual scrollback content, not usize::MAX
-        assert!(max < usize::MAX);
-        assert!(max > 0);
+        app.handle_key(KeyCode::Char('J'), CTRL_SHIFT);
+
+        assert_eq!(app.projects[0].session_ids, vec![ids[1], ids[0], ids[2]]);
+        assert_eq!(app.active_index, 0);
+        assert_eq!(app.active_session_in_project(), 1);
+        assert_eq!(app.active_project_sessions(), vec![1, 0, 2]);
+        let stored: Vec<_> = app
+            .db
+            .list_active_sessions()
+            .unwrap()
+            .into_iter()
+            .map(|s| s.id)
+            .collect();
+        assert_eq!(stored, vec![ids[1], ids[0], ids[2]]);
     }
 
     #[test]
-    fn scrollback_restores_after_probe() {
-        let mut parser = parser_with_scrollback();
-        parser.screen_mut().set_scrollback(3);
+    fn ctrl_shift_k_moves_session_up() {
+        let mut app = app_with_sessions(3);
+        let ids = session_ids(&app);
+        app.focus = InputFocus::SessionList;
+        app.active_index = 2;
 
-        // Probe total scrollback (same technique as render_terminal)
-        let saved = parser.screen().scrollback();
-        parser.screen_mut().set_scrollback(usize::MAX);
-        let _total = parser.screen().scrollback();
-        parser.screen_mut().set_scrollback(saved);
+        app.handle_key(KeyCode::Char('K'), CTRL_SHIFT);
 
-        assert_eq!(parser.screen().scrollback(), 3);
+        assert_eq!(app.projects[0].session_ids, vec![ids[0], ids[2], ids[1]]);
+        assert_eq!(app.active_session_in_project(), 1);
     }
 
     #[test]
-    fn scrollback_zero_stays_at_bottom() {
-        let mut parser = parser_with_scrollback();
-        assert_eq!(parser.screen().scrollback(), 0);
+    fn move_session_up_at_top_is_noop() {
+        let mut app = app_with_sessions(3);
+        let ids = session_ids(&app);
+        app.focus = InputFocus::SessionList;
+        app.active_index = 0;
 
-        // New output while at bottom keeps offset at 0
-        parser.process(b"new line\r\n");
-        assert_eq!(parser.screen().scrollback(), 0);
-    }
+        app.handle_key(KeyCode::Char('K'), CTRL_SHIFT);
 
-    #[test]
-    fn page_scroll_amount_is_half_content_height() {
-        let app = App::new(50, 100, stub_backend(), test_db());
-        // rows = 50 - 4 = 46, half = 23
-        assert_eq!(app.page_scroll_amount(), 23);
+        assert_eq!(app.projects[0].session_ids, ids);
+        assert_eq!(app.active_index, 0);
     }
 
     #[test]
-    fn page_scroll_amount_small_terminal() {
-        let app = App::new(6, 80, stub_backend(), test_db());
-        // rows = 6 - 4 = 2, half = 1
-        assert_eq!(app.page_scroll_amount(), 1);
+    fn move_session_down_at_bottom_is_noop() {
+        let mut app = app_with_sessions(3);
+        let ids = session_ids(&app);
+        app.focus = InputFocus::SessionList;
+        app.active_index = 2;
+
+        app.handle_key(KeyCode::Char('J'), CTRL_SHIFT);
+
+        assert_eq!(app.projects[0].session_ids, ids);
+        assert_eq!(app.active_index, 2);
     }
 
     #[test]
-    fn mouse_scroll_lines_constant() {
-        assert_eq!(MOUSE_SCROLL_LINES, 3);
-    }
+    fn ctrl_shift_j_outside_session_list_does_not_reorder() {
+        let mut app = app_with_sessions(3);
+        let ids = session_ids(&app);
+        app.focus = InputFocus::Terminal;
+        app.active_index = 0;
 
-    // --- Session naming tests ---
+        app.handle_key(KeyCode::Char('J'), CTRL_SHIFT);
 
-    #[test]
-    fn next_session_name_starts_at_one() {
-        let mut app = App::new(24, 80, stub_backend(), test_db());
-        assert_eq!(app.next_session_name(), "1");
+        assert_eq!(app.projects[0].session_ids, ids);
     }
 
     #[test]
-    fn next_session_name_increments() {
-        let mut app = App::new(24, 80, stub_backend(), test_db());
-        assert_eq!(app.next_session_name(), "1");
-        assert_eq!(app.next_session_name(), "2");
-        assert_eq!(app.next_session_name(), "3");
+    fn pinning_session_moves_it_to_front_of_list() {
+        let mut app = app_with_sessions(3);
+        let ids = session_ids(&app);
+        app.focus = InputFocus::SessionList;
+        app.active_index = 2;
+
+        app.handle_key(KeyCode::Char('p'), KeyModifiers::NONE);
+
+        assert!(app.sessions[2].info.is_pinned);
+        assert_eq!(app.active_project_sessions(), vec![2, 0, 1]);
+        // Only the display order changes.
+        assert_eq!(app.active_index, 2);
+        assert_eq!(app.active_session_in_project(), 0);
+        assert_eq!(app.projects[0].session_ids, ids);
+        assert!(app.db.list_active_sessions().unwrap()[2].is_pinned);
     }
 
-    #[test]
-    fn next_session_name_continues_from_restored_counter() {
+    /// Two projects, each saved to the DB, with `count` sessions in the first.
+    fn app_with_sessions_and_second_project(count: usize) -> App {
+        let mut app = app_with_projects(2);
+        let second = app.projects[1].clone();
+        app.save_project_to_db(&second);
+        for i in 0..count {
+            let session = Session::stub(&format!("s{i}"), &app.backend);
+            app.projects[0].session_ids.push(session.info.id);
+            app.sessions.push(session);
+        }
+        app.save_state();
+        app
+    }
+
+    #[test]
+    fn moving_session_updates_both_projects() {
+        let mut app = app_with_sessions_and_second_project(2);
+        let moved = app.sessions[1].info.id;
+        let kept = app.sessions[0].info.id;
+        let target = app.projects[1].id;
+        app.focus = InputFocus::SessionList;
+        app.active_index = 1;
+
+        app.handle_key(KeyCode::Char('m'), KeyModifiers::NONE);
+        assert!(app.show_move_session_modal);
+        assert_eq!(app.move_session_targets, vec![1]);
+        app.handle_key(KeyCode::Enter, KeyModifiers::NONE);
+
+        assert!(!app.show_move_session_modal);
+        assert_eq!(app.projects[0].session_ids, vec![kept]);
+        assert_eq!(app.projects[1].session_ids, vec![moved]);
+        // The backend session is kept, and the active project still shows one.
+        assert_eq!(app.sessions.len(), 2);
+        assert_eq!(app.sessions[app.active_index].info.id, kept);
+
+        let stored = app.db.list_active_sessions().unwrap();
+        let stored = stored.iter().find(|s| s.id == moved).unwrap();
+        assert_eq!(stored.project_id, target);
+        assert_eq!(app.session_to_shared(&app.sessions[1]).project_id, target);
+    }
+
+    #[test]
+    fn admin_session_cannot_be_moved() {
+        let mut app = app_with_sessions_and_second_project(1);
+        app.projects[0].is_admin = true;
+        app.focus = InputFocus::SessionList;
+
+        app.handle_key(KeyCode::Char('m'), KeyModifiers::NONE);
+
+        assert!(!app.show_move_session_modal);
+        assert_eq!(
+            app.status_message.as_ref().map(|m| m.level),
+            Some(StatusLevel::Error)
+        );
+    }
+
+    #[test]
+    fn move_session_esc_keeps_project() {
+        let mut app = app_with_sessions_and_second_project(1);
+        let id = app.sessions[0].info.id;
+        app.focus = InputFocus::SessionList;
+
+        app.handle_key(KeyCode::Char('m'), KeyModifiers::NONE);
+        app.handle_key(KeyCode::Esc, KeyModifiers::NONE);
+
+        assert!(!app.show_move_session_modal);
+        assert_eq!(app.projects[0].session_ids, vec![id]);
+        assert!(app.projects[1].session_ids.is_empty());
+    }
+
+    #[test]
+    fn unpinning_restores_project_order() {
+        let mut app = app_with_sessions(3);
+        app.focus = InputFocus::SessionList;
+        app.active_index = 2;
+
+        app.handle_key(KeyCode::Char('p'), KeyModifiers::NONE);
+        app.handle_key(KeyCode::Char('p'), KeyModifiers::NONE);
+
+        assert!(!app.sessions[2].info.is_pinned);
+        assert_eq!(app.active_project_sessions(), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn label_modal_sets_and_persists_label() {
+        let mut app = app_with_sessions(1);
+        let id = app.sessions[0].info.id;
+        app.focus = InputFocus::SessionList;
+
+        app.handle_key(KeyCode::Char('t'), KeyModifiers::NONE);
+        assert!(app.show_session_label_modal);
+        for c in " frontend ".chars() {
+            app.handle_key(KeyCode::Char(c), KeyModifiers::NONE);
+        }
+        app.handle_key(KeyCode::Tab, KeyModifiers::NONE);
+        for c in "green".chars() {
+            app.handle_key(KeyCode::Char(c), KeyModifiers::NONE);
+        }
+        app.handle_key(KeyCode::Enter, KeyModifiers::NONE);
+
+        assert!(!app.show_session_label_modal);
+        assert_eq!(app.sessions[0].info.label.as_deref(), Some("frontend"));
+        assert_eq!(app.sessions[0].info.label_color.as_deref(), Some("green"));
+        let stored = app.db.list_active_sessions().unwrap();
+        let stored = stored.iter().find(|s| s.id == id).unwrap();
+        assert_eq!(stored.label.as_deref(), Some("frontend"));
+        assert_eq!(stored.label_color.as_deref(), Some("green"));
+
+        // Reopening pre-fills the fields; clearing both removes the label.
+        app.handle_key(KeyCode::Char('t'), KeyModifiers::NONE);
+        assert_eq!(app.session_label_input.value(), "frontend");
+        assert_eq!(app.session_label_color_input.value(), "green");
+        app.session_label_input.clear();
+        app.session_label_color_input.clear();
+        app.handle_key(KeyCode::Enter, KeyModifiers::NONE);
+        assert_eq!(app.sessions[0].info.label, None);
+        assert_eq!(app.sessions[0].info.label_color, None);
+    }
+
+    #[test]
+    fn label_modal_rejects_unknown_color() {
+        let mut app = app_with_sessions(1);
+        app.focus = InputFocus::SessionList;
+        app.handle_key(KeyCode::Char('t'), KeyModifiers::NONE);
+        app.session_label_color_input.set("blurple");
+        app.handle_key(KeyCode::Enter, KeyModifiers::NONE);
+
+        assert!(app.show_session_label_modal);
+        assert_eq!(app.sessions[0].info.label_color, None);
+        assert_eq!(
+            app.status_message.as_ref().map(|m| m.level),
+            Some(StatusLevel::Error)
+        );
+    }
+
+    #[test]
+    fn pinned_sessions_keep_relative_order() {
+        let mut app = app_with_sessions(4);
+        app.sessions[3].info.is_pinned = true;
+        app.sessions[1].info.is_pinned = true;
+
+        assert_eq!(app.active_project_sessions(), vec![1, 3, 0, 2]);
+    }
+
+    #[test]
+    fn sort_by_age_lists_oldest_first_after_pinned() {
+        let mut app = app_with_sessions(4);
+        app.sessions[0].info.created_at = Some(300);
+        app.sessions[1].info.created_at = Some(100);
+        app.sessions[2].info.created_at = None;
+        app.sessions[3].info.created_at = Some(200);
+        app.sessions[0].info.is_pinned = true;
+        app.focus = InputFocus::SessionList;
+
+        app.handle_key(KeyCode::Char('o'), KeyModifiers::NONE);
+        assert!(app.sort_sessions_by_age);
+        // Unknown creation times sort last.
+        assert_eq!(app.active_project_sessions(), vec![0, 1, 3, 2]);
+
+        app.handle_key(KeyCode::Char('o'), KeyModifiers::NONE);
+        assert_eq!(app.active_project_sessions(), vec![0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn restore_keeps_created_at() {
+        let mut app = app_with_sessions(1);
+        app.sessions[0].info.created_at = Some(1_700_000_000_000);
+        let mut shared = app.session_to_shared(&app.sessions[0]);
+        assert_eq!(shared.created_at, Some(1_700_000_000_000));
+        // Only sessions with a Claude conversation are restored lazily.
+        shared.claude_session_id = Some("claude-abc".to_string());
+
+        let mut restored = app_with_sessions(0);
+        restored.settings.restore.lazy = true;
+        restored.restore_sessions(vec![shared], 1);
+        assert_eq!(
+            restored.sessions[0].info.created_at,
+            Some(1_700_000_000_000)
+        );
+    }
+
+    fn history_len(session: &Session) -> usize {
+        crate::ui::search::scrollback_len(&mut session.parser.lock().unwrap())
+    }
+
+    fn screen_rows(session: &Session) -> Vec<String> {
+        let parser = session.parser.lock().unwrap();
+        parser.screen().rows(0, 80).collect()
+    }
+
+    #[test]
+    fn focus_grows_scrollback_and_background_shrinks_it() {
+        let mut app = app_with_sessions(2);
+        app.settings.scroll.background_lines = 5;
+        app.active_index = 0;
+        app.tick();
+        for session in &app.sessions {
+            let mut parser = session.parser.lock().unwrap();
+            for i in 0..60 {
+                parser.process(format!("{} line {i}\r\n", session.info.name).as_bytes());
+            }
+        }
+        assert_eq!(app.sessions[0].scrollback_cap(), SCROLLBACK_LINES);
+        assert_eq!(app.sessions[1].scrollback_cap(), 5);
+        assert_eq!(history_len(&app.sessions[0]), 60 + 1 - 24);
+        assert_eq!(history_len(&app.sessions[1]), 5);
+
+        let first_screen = screen_rows(&app.sessions[0]);
+        let second_screen = screen_rows(&app.sessions[1]);
+        app.active_index = 1;
+        app.tick();
+
+        assert_eq!(app.sessions[0].scrollback_cap(), 5);
+        assert_eq!(app.sessions[1].scrollback_cap(), SCROLLBACK_LINES);
+        assert_eq!(history_len(&app.sessions[0]), 5);
+        assert_eq!(screen_rows(&app.sessions[0]), first_screen);
+        assert_eq!(screen_rows(&app.sessions[1]), second_screen);
+
+        // The newly active session has room for its full history again.
+        app.sessions[1]
+            .parser
+            .lock()
+            .unwrap()
+            .process("more\r\n".repeat(20).as_bytes());
+        assert_eq!(history_len(&app.sessions[1]), 25);
+    }
+
+    #[test]
+    fn move_session_does_not_cross_pinned_boundary() {
+        let mut app = app_with_sessions(3);
+        let ids = session_ids(&app);
+        app.sessions[0].info.is_pinned = true;
+        app.focus = InputFocus::SessionList;
+        app.active_index = 1;
+
+        app.handle_key(KeyCode::Char('K'), CTRL_SHIFT);
+
+        assert_eq!(app.projects[0].session_ids, ids);
+    }
+
+    #[test]
+    fn reordered_sessions_drive_navigation() {
+        let mut app = app_with_sessions(3);
+        app.focus = InputFocus::SessionList;
+        app.active_index = 0;
+        app.move_active_session(1);
+
+        // Project order is now [1, 0, 2]; "next" from session 0 is session 2.
+        app.switch_session_forward();
+        assert_eq!(app.active_index, 2);
+    }
+
+    // --- Scroll tests ---
+
+    fn parser_with_scrollback() -> vt100::Parser {
+        let mut parser = vt100::Parser::new(24, 80, 100);
+        // Fill screen and scrollback by writing many lines
+        for i in 0..50 {
+            parser.process(format!("line {i}\r\n").as_bytes());
+        }
+        parser
+    }
+
+    #[test]
+    fn scrollback_starts_at_zero() {
+        let parser = parser_with_scrollback();
+        assert_eq!(parser.screen().scrollback(), 0);
+    }
+
+    #[test]
+    fn scrollback_increments() {
+        let mut parser = parser_with_scrollback();
+        parser.screen_mut().set_scrollback(5);
+        assert_eq!(parser.screen().scrollback(), 5);
+    }
+
+    #[test]
+    fn scrollback_clamps_to_max() {
+        let mut parser = parser_with_scrollback();
+        parser.screen_mut().set_scrollback(usize::MAX);
+        let max = parser.screen().scrollback();
+        // Should be clamped to the actual scrollback content, not usize::MAX
+        assert!(max < usize::MAX);
+        assert!(max > 0);
+    }
+
+    #[test]
+    fn scrollback_restores_after_probe() {
+        let mut parser = parser_with_scrollback();
+        parser.screen_mut().set_scrollback(3);
+
+        // Probe total scrollback (same technique as render_terminal)
+        let saved = parser.screen().scrollback();
+        parser.screen_mut().set_scrollback(usize::MAX);
+        let _total = parser.screen().scrollback();
+        parser.screen_mut().set_scrollback(saved);
+
+        assert_eq!(parser.screen().scrollback(), 3);
+    }
+
+    #[test]
+    fn scrollback_zero_stays_at_bottom() {
+        let mut parser = parser_with_scrollback();
+        assert_eq!(parser.screen().scrollback(), 0);
+
+        // New output while at bottom keeps offset at 0
+        parser.process(b"new line\r\n");
+        assert_eq!(parser.screen().scrollback(), 0);
+    }
+
+    #[test]
+    fn page_scroll_amount_is_half_content_height() {
+        let app = App::new(50, 100, stub_backend(), test_db());
+        // rows = 50 - 4 = 46, half = 23
+        assert_eq!(app.page_scroll_amount(), 23);
+    }
+
+    #[test]
+    fn page_scroll_amount_small_terminal() {
+        let app = App::new(6, 80, stub_backend(), test_db());
+        // rows = 6 - 4 = 2, half = 1
+        assert_eq!(app.page_scroll_amount(), 1);
+    }
+
+    #[test]
+    fn page_scroll_amount_uses_configured_fraction() {
+        let mut app = App::new(50, 100, stub_backend(), test_db());
+        app.set_settings(settings::Settings::parse("[scroll]\npage_fraction = 0.25\n").unwrap());
+        assert!(app.status_message.is_none());
+        // rows = 46, a quarter = 11
+        assert_eq!(app.page_scroll_amount(), 11);
+    }
+
+    #[test]
+    fn configured_scroll_steps_move_scrollback() {
+        let mut app = app_with_sessions(1);
+        app.set_settings(
+            settings::Settings::parse("[scroll]\nmouse_lines = 7\nline_step = 4\n").unwrap(),
+        );
+        let mut parser = vt100::Parser::new(5, 20, 100);
+        let text: Vec<String> = (0..40).map(|i| format!("line {i}")).collect();
+        parser.process(text.join("\r\n").as_bytes());
+        *app.sessions[0].parser.lock().unwrap() = parser;
+
+        app.update(AppMessage::MouseScrollUp);
+        assert_eq!(app.active_scrollback(), 7);
+        app.focus = InputFocus::Terminal;
+        app.handle_key(KeyCode::Up, KeyModifiers::SHIFT);
+        assert_eq!(app.active_scrollback(), 11);
+        app.handle_key(KeyCode::Down, KeyModifiers::SHIFT);
+        app.update(AppMessage::MouseScrollDown);
+        assert_eq!(app.active_scrollback(), 0);
+    }
+
+    #[test]
+    fn invalid_scroll_settings_fall_back_to_defaults() {
+        let mut app = app_with_sessions(0);
+        app.set_settings(settings::Settings::parse("[scroll]\nline_step = 0\n").unwrap());
+        assert_eq!(app.settings.scroll, settings::ScrollSettings::default());
+        assert_eq!(
+            app.status_message.as_ref().unwrap().text,
+            "scroll.line_step must be at least 1; using default scroll steps"
+        );
+    }
+
+    #[test]
+    fn invalid_config_file_is_reported_in_status_bar() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let _guard = crate::paths::TestPathGuard::new(tmp.path());
+        std::fs::write(
+            tmp.path().join("config.toml"),
+            "[terminal]\nctrl_c = \"clsoe\"\n",
+        )
+        .unwrap();
+        let mut app = app_with_sessions(0);
+
+        app.load_settings();
+
+        assert_eq!(app.settings, settings::Settings::default());
+        let msg = app.status_message.as_ref().unwrap();
+        assert_eq!(msg.level, StatusLevel::Error);
+        assert!(msg.text.starts_with("Invalid config.toml (line 2): "));
+        assert!(msg.text.ends_with("; using default settings"));
+    }
+
+    // --- Session naming tests ---
+
+    #[test]
+    fn next_session_name_starts_at_one() {
+        let mut app = App::new(24, 80, stub_backend(), test_db());
+        assert_eq!(app.next_session_name(&[]), "1");
+    }
+
+    #[test]
+    fn next_session_name_increments() {
+        let mut app = App::new(24, 80, stub_backend(), test_db());
+        assert_eq!(app.next_session_name(&[]), "1");
+        assert_eq!(app.next_session_name(&[]), "2");
+        assert_eq!(app.next_session_name(&[]), "3");
+    }
+
+    #[test]
+    fn next_session_name_continues_from_restored_counter() {
         let mut app = App::new(24, 80, stub_backend(), test_db());
         app.session_counter = 5;
-        assert_eq!(app.next_session_name(), "6");
+        assert_eq!(app.next_session_name(&[]), "6");
+    }
+
+    fn worktree_on(branch: &str) -> WorktreeInfo {
+        WorktreeInfo {
+            repo_path: PathBuf::from("/repo"),
+            worktree_path: PathBuf::from("/repo/.git/thurbox-worktrees").join(branch),
+            branch: branch.to_string(),
+            base_branch: "main".to_string(),
+        }
+    }
+
+    #[test]
+    fn branch_slug_collapses_separators() {
+        assert_eq!(branch_slug("feat/login"), "feat-login");
+        assert_eq!(branch_slug("fix//Auth bug/"), "fix-Auth-bug");
+        assert_eq!(branch_slug("v1.2_rc"), "v1.2_rc");
+        assert_eq!(branch_slug("///"), "");
+    }
+
+    #[test]
+    fn branch_scheme_names_worktree_sessions_after_the_branch() {
+        let mut app = App::new(24, 80, stub_backend(), test_db());
+        app.settings.session_names.scheme = settings::SessionNameScheme::Branch;
+        assert_eq!(
+            app.next_session_name(&[worktree_on("feat/login")]),
+            "feat-login"
+        );
+        // Plain sessions have no branch; the counter kept advancing.
+        assert_eq!(app.next_session_name(&[]), "2");
+    }
+
+    #[test]
+    fn branch_scheme_appends_counter_on_slug_collision() {
+        let mut app = app_with_sessions(1);
+        app.sessions[0].info.name = "feat-x".to_string();
+        app.session_counter = 1;
+        app.settings.session_names.scheme = settings::SessionNameScheme::Branch;
+        assert_eq!(app.next_session_name(&[worktree_on("feat/x")]), "feat-x-2");
+        assert_eq!(app.next_session_name(&[worktree_on("feat-y")]), "feat-y");
+    }
+
+    #[test]
+    fn prefix_scheme_prepends_prefix_to_counter() {
+        let mut app = App::new(24, 80, stub_backend(), test_db());
+        app.settings.session_names.scheme = settings::SessionNameScheme::Prefix;
+        app.settings.session_names.prefix = "wt-".to_string();
+        assert_eq!(app.next_session_name(&[]), "wt-1");
+        assert_eq!(app.next_session_name(&[worktree_on("feat/x")]), "wt-2");
+    }
+
+    #[test]
+    fn prepare_spawn_names_session_after_worktree_branch() {
+        use crate::session::{RoleConfig, RolePermissions};
+        let role = |name: &str| RoleConfig {
+            name: name.to_string(),
+            description: String::new(),
+            permissions: RolePermissions::default(),
+        };
+        let mut app = app_with_roles(vec![role("dev"), role("review")]);
+        app.settings.session_names.scheme = settings::SessionNameScheme::Branch;
+
+        app.prepare_spawn(SessionConfig::default(), vec![worktree_on("feat/login")]);
+
+        assert!(app.show_role_selector);
+        assert_eq!(app.pending_spawn_name.as_deref(), Some("feat-login"));
     }
 
     // --- Role editor tests ---
@@ -3432,6 +8209,13 @@ mod tests {
                 permissions: RolePermissions::default(),
             }],
             mcp_servers: vec![],
+            sync_base_branch: None,
+            remote: None,
+            sync_strategy: SyncStrategy::default(),
+            activity_timeout_ms: None,
+            worktree_root: None,
+            default_role: None,
+            session_init_command: None,
             id: None,
         };
         let mut app = App::new(24, 120, stub_backend(), test_db_with_project(&config));
@@ -3505,6 +8289,13 @@ mod tests {
                 },
             ],
             mcp_servers: vec![],
+            sync_base_branch: None,
+            remote: None,
+            sync_strategy: SyncStrategy::default(),
+            activity_timeout_ms: None,
+            worktree_root: None,
+            default_role: None,
+            session_init_command: None,
             id: None,
         };
         let mut app = App::new(24, 120, stub_backend(), test_db_with_project(&config));
@@ -3520,6 +8311,13 @@ mod tests {
             repos: vec![],
             roles: vec![],
             mcp_servers: vec![],
+            sync_base_branch: None,
+            remote: None,
+            sync_strategy: SyncStrategy::default(),
+            activity_timeout_ms: None,
+            worktree_root: None,
+            default_role: None,
+            session_init_command: None,
             id: None,
         };
         let app = App::new(24, 120, stub_backend(), test_db_with_project(&config));
@@ -3604,6 +8402,13 @@ mod tests {
                 },
             }],
             mcp_servers: vec![],
+            sync_base_branch: None,
+            remote: None,
+            sync_strategy: SyncStrategy::default(),
+            activity_timeout_ms: None,
+            worktree_root: None,
+            default_role: None,
+            session_init_command: None,
             id: None,
         };
         let mut app = App::new(24, 120, stub_backend(), test_db_with_project(&config));
@@ -3658,6 +8463,13 @@ mod tests {
                 },
             }],
             mcp_servers: vec![],
+            sync_base_branch: None,
+            remote: None,
+            sync_strategy: SyncStrategy::default(),
+            activity_timeout_ms: None,
+            worktree_root: None,
+            default_role: None,
+            session_init_command: None,
             id: None,
         };
         let mut app = App::new(24, 120, stub_backend(), test_db_with_project(&config));
@@ -3754,6 +8566,13 @@ mod tests {
                 },
             ],
             mcp_servers: vec![],
+            sync_base_branch: None,
+            remote: None,
+            sync_strategy: SyncStrategy::default(),
+            activity_timeout_ms: None,
+            worktree_root: None,
+            default_role: None,
+            session_init_command: None,
             id: None,
         };
         let mut app = App::new(24, 120, stub_backend(), test_db_with_project(&config));
@@ -3797,7 +8616,7 @@ mod tests {
         for c in "Bash(git:*)".chars() {
             tls.input.insert(c);
         }
-        tls.confirm_add();
+        tls.confirm_add().unwrap();
 
         assert_eq!(tls.items, vec!["Bash(git:*)".to_string()]);
         assert_eq!(tls.selected, 0);
@@ -3821,7 +8640,7 @@ mod tests {
     fn tool_list_state_confirm_empty_input_is_no_op() {
         let mut tls = ToolListState::new();
         tls.start_adding();
-        tls.confirm_add();
+        tls.confirm_add().unwrap();
         assert!(tls.items.is_empty());
     }
 
@@ -3831,7 +8650,7 @@ mod tests {
         tls.start_adding();
         tls.input.insert(' ');
         tls.input.insert(' ');
-        tls.confirm_add();
+        tls.confirm_add().unwrap();
         assert!(tls.items.is_empty());
     }
 
@@ -3922,16 +8741,80 @@ mod tests {
     }
 
     #[test]
-    fn tool_browse_delete_via_key_handler() {
+    fn tool_adding_rejects_invalid_pattern() {
         use role_editor_modal::RoleEditorField;
         let mut app = App::new(24, 120, stub_backend(), test_db());
         app.open_role_editor();
         app.handle_role_editor_list_key(KeyCode::Char('a'));
-        app.role_editor_field = RoleEditorField::AllowedTools;
-        app.role_editor_allowed_tools.items = vec!["Read".into(), "Write".into()];
-        app.role_editor_allowed_tools.selected = 0;
+        app.role_editor_field = RoleEditorField::DisallowedTools;
 
-        app.handle_role_editor_editor_key(KeyCode::Char('d'));
+        app.handle_role_editor_editor_key(KeyCode::Char('a'));
+        for c in "Bash(git:*".chars() {
+            app.handle_role_editor_editor_key(KeyCode::Char(c));
+        }
+        app.handle_role_editor_editor_key(KeyCode::Enter);
+
+        assert!(app.role_editor_disallowed_tools.items.is_empty());
+        // Still adding, with the typo kept for correction
+        assert_eq!(
+            app.role_editor_disallowed_tools.mode,
+            role_editor_modal::ToolListMode::Adding
+        );
+        assert_eq!(app.role_editor_disallowed_tools.input.value(), "Bash(git:*");
+        let msg = app.status_message.as_ref().unwrap();
+        assert_eq!(msg.level, StatusLevel::Error);
+        assert!(msg.text.contains("Unbalanced parentheses"));
+
+        // Fix it and confirm
+        app.handle_role_editor_editor_key(KeyCode::Char(')'));
+        app.handle_role_editor_editor_key(KeyCode::Enter);
+        assert_eq!(
+            app.role_editor_disallowed_tools.items,
+            vec!["Bash(git:*)".to_string()]
+        );
+    }
+
+    #[test]
+    fn env_list_is_not_tool_validated() {
+        use role_editor_modal::RoleEditorField;
+        let mut app = App::new(24, 120, stub_backend(), test_db());
+        app.open_role_editor();
+        app.handle_role_editor_list_key(KeyCode::Char('a'));
+        app.role_editor_field = RoleEditorField::Env;
+
+        app.handle_role_editor_editor_key(KeyCode::Char('a'));
+        for c in "FOO=bar baz".chars() {
+            app.handle_role_editor_editor_key(KeyCode::Char(c));
+        }
+        app.handle_role_editor_editor_key(KeyCode::Enter);
+        assert_eq!(app.role_editor_env.items, vec!["FOO=bar baz".to_string()]);
+    }
+
+    #[test]
+    fn tool_list_with_validator_rejects_and_stays_adding() {
+        let mut tls = ToolListState::with_validator(validate_tool_pattern);
+        tls.start_adding();
+        tls.input.set("read");
+        assert!(tls.confirm_add().is_err());
+        assert!(tls.items.is_empty());
+        assert_eq!(tls.mode, role_editor_modal::ToolListMode::Adding);
+
+        tls.input.set("Read");
+        tls.confirm_add().unwrap();
+        assert_eq!(tls.items, vec!["Read".to_string()]);
+    }
+
+    #[test]
+    fn tool_browse_delete_via_key_handler() {
+        use role_editor_modal::RoleEditorField;
+        let mut app = App::new(24, 120, stub_backend(), test_db());
+        app.open_role_editor();
+        app.handle_role_editor_list_key(KeyCode::Char('a'));
+        app.role_editor_field = RoleEditorField::AllowedTools;
+        app.role_editor_allowed_tools.items = vec!["Read".into(), "Write".into()];
+        app.role_editor_allowed_tools.selected = 0;
+
+        app.handle_role_editor_editor_key(KeyCode::Char('d'));
         assert_eq!(
             app.role_editor_allowed_tools.items,
             vec!["Write".to_string()]
@@ -3974,6 +8857,13 @@ mod tests {
                 },
             }],
             mcp_servers: vec![],
+            sync_base_branch: None,
+            remote: None,
+            sync_strategy: SyncStrategy::default(),
+            activity_timeout_ms: None,
+            worktree_root: None,
+            default_role: None,
+            session_init_command: None,
             id: None,
         };
         let mut app = App::new(24, 120, stub_backend(), test_db_with_project(&config));
@@ -4023,6 +8913,13 @@ mod tests {
                 },
             }],
             mcp_servers: vec![],
+            sync_base_branch: None,
+            remote: None,
+            sync_strategy: SyncStrategy::default(),
+            activity_timeout_ms: None,
+            worktree_root: None,
+            default_role: None,
+            session_init_command: None,
             id: None,
         };
         let app = App::new(24, 120, stub_backend(), test_db_with_project(&config));
@@ -4040,6 +8937,13 @@ mod tests {
             repos: vec![PathBuf::from("/path/to/repo")],
             roles: Vec::new(),
             mcp_servers: Vec::new(),
+            sync_base_branch: None,
+            remote: None,
+            sync_strategy: SyncStrategy::default(),
+            activity_timeout_ms: None,
+            worktree_root: None,
+            default_role: None,
+            session_init_command: None,
             id: None,
         };
         let proj_id = proj_config.deterministic_id();
@@ -4050,6 +8954,13 @@ mod tests {
             repos: vec![PathBuf::from("/path/to/repo")],
             roles: Vec::new(),
             mcp_servers: Vec::new(),
+            sync_base_branch: None,
+            remote: None,
+            sync_strategy: SyncStrategy::default(),
+            activity_timeout_ms: None,
+            worktree_root: None,
+            default_role: None,
+            session_init_command: None,
         };
 
         let info = shared_project_to_info(shared_proj.clone());
@@ -4060,6 +8971,100 @@ mod tests {
         assert!(info.config.roles.is_empty());
     }
 
+    fn role_named(name: &str, description: &str) -> crate::session::RoleConfig {
+        crate::session::RoleConfig {
+            name: name.to_string(),
+            description: description.to_string(),
+            permissions: Default::default(),
+        }
+    }
+
+    fn shared_project_copy(
+        project: &ProjectInfo,
+        repos: &[&str],
+        roles: Vec<crate::session::RoleConfig>,
+    ) -> sync::SharedProject {
+        sync::SharedProject {
+            id: project.id,
+            name: project.config.name.clone(),
+            repos: repos.iter().map(PathBuf::from).collect(),
+            roles,
+            mcp_servers: Vec::new(),
+            sync_base_branch: None,
+            remote: None,
+            sync_strategy: SyncStrategy::default(),
+            activity_timeout_ms: None,
+            worktree_root: None,
+            default_role: None,
+            session_init_command: None,
+        }
+    }
+
+    #[test]
+    fn merge_duplicate_project_unions_repos_and_roles() {
+        let mut local = test_project_config();
+        local.repos = vec![PathBuf::from("/a"), PathBuf::from("/b")];
+        local.roles = vec![role_named("dev", "local dev"), role_named("ops", "ops")];
+        let project = ProjectInfo::new(local.clone());
+        let incoming = shared_project_copy(
+            &project,
+            &["/b", "/c"],
+            vec![
+                role_named("dev", "shared dev"),
+                role_named("review", "review"),
+            ],
+        );
+
+        assert!(merge_duplicate_project(&mut local, &incoming));
+
+        assert_eq!(local.repos, ["/a", "/b", "/c"].map(PathBuf::from).to_vec());
+        let roles: Vec<_> = local
+            .roles
+            .iter()
+            .map(|r| (r.name.as_str(), r.description.as_str()))
+            .collect();
+        // The incoming definition wins a name conflict.
+        assert_eq!(
+            roles,
+            [("dev", "shared dev"), ("ops", "ops"), ("review", "review")]
+        );
+
+        // Merging the same copy again changes nothing.
+        assert!(!merge_duplicate_project(&mut local, &incoming));
+    }
+
+    #[test]
+    fn duplicate_project_from_other_instance_is_merged_and_saved() {
+        let mut app = app_with_project("test", vec![PathBuf::from("/repo")]);
+        let incoming = shared_project_copy(
+            &app.projects[0],
+            &["/other-repo"],
+            vec![role_named("dev", "")],
+        );
+        let id = incoming.id;
+
+        app.handle_external_state_change(StateDelta {
+            added_projects: vec![incoming],
+            ..StateDelta::default()
+        });
+
+        let project = app.projects.iter().find(|p| p.id == id).unwrap();
+        assert_eq!(
+            project.config.repos,
+            ["/repo", "/other-repo"].map(PathBuf::from).to_vec()
+        );
+        assert_eq!(project.config.roles.len(), 1);
+        let stored = app.db.list_active_projects().unwrap();
+        let stored = stored.iter().find(|p| p.id == id).unwrap();
+        let mut stored_repos = stored.repos.clone();
+        stored_repos.sort();
+        assert_eq!(
+            stored_repos,
+            ["/other-repo", "/repo"].map(PathBuf::from).to_vec()
+        );
+        assert_eq!(stored.roles, project.config.roles);
+    }
+
     #[test]
     fn shared_project_to_info_multiple_repos() {
         let proj_config = ProjectConfig {
@@ -4071,6 +9076,13 @@ mod tests {
             ],
             roles: Vec::new(),
             mcp_servers: Vec::new(),
+            sync_base_branch: None,
+            remote: None,
+            sync_strategy: SyncStrategy::default(),
+            activity_timeout_ms: None,
+            worktree_root: None,
+            default_role: None,
+            session_init_command: None,
             id: None,
         };
         let proj_id = proj_config.deterministic_id();
@@ -4085,6 +9097,13 @@ mod tests {
             ],
             roles: Vec::new(),
             mcp_servers: Vec::new(),
+            sync_base_branch: None,
+            remote: None,
+            sync_strategy: SyncStrategy::default(),
+            activity_timeout_ms: None,
+            worktree_root: None,
+            default_role: None,
+            session_init_command: None,
         };
 
         let info = shared_project_to_info(shared_proj.clone());
@@ -4103,6 +9122,13 @@ mod tests {
             repos: vec![PathBuf::from("/db/repo")],
             roles: Vec::new(),
             mcp_servers: Vec::new(),
+            sync_base_branch: None,
+            remote: None,
+            sync_strategy: SyncStrategy::default(),
+            activity_timeout_ms: None,
+            worktree_root: None,
+            default_role: None,
+            session_init_command: None,
             id: None,
         };
         let proj_id = proj_config.deterministic_id();
@@ -4145,6 +9171,13 @@ mod tests {
             repos: vec![PathBuf::from("/repo")],
             roles: Vec::new(),
             mcp_servers: Vec::new(),
+            sync_base_branch: None,
+            remote: None,
+            sync_strategy: SyncStrategy::default(),
+            activity_timeout_ms: None,
+            worktree_root: None,
+            default_role: None,
+            session_init_command: None,
             id: None,
         };
         let proj_id = proj_config.deterministic_id();
@@ -4174,6 +9207,13 @@ mod tests {
             repos: vec![PathBuf::from("/a")],
             roles: Vec::new(),
             mcp_servers: Vec::new(),
+            sync_base_branch: None,
+            remote: None,
+            sync_strategy: SyncStrategy::default(),
+            activity_timeout_ms: None,
+            worktree_root: None,
+            default_role: None,
+            session_init_command: None,
             id: None,
         };
         let config_b = ProjectConfig {
@@ -4181,6 +9221,13 @@ mod tests {
             repos: vec![PathBuf::from("/b")],
             roles: Vec::new(),
             mcp_servers: Vec::new(),
+            sync_base_branch: None,
+            remote: None,
+            sync_strategy: SyncStrategy::default(),
+            activity_timeout_ms: None,
+            worktree_root: None,
+            default_role: None,
+            session_init_command: None,
             id: None,
         };
         db.insert_project(
@@ -4212,6 +9259,13 @@ mod tests {
             repos: vec![PathBuf::from("/repo")],
             roles: Vec::new(),
             mcp_servers: Vec::new(),
+            sync_base_branch: None,
+            remote: None,
+            sync_strategy: SyncStrategy::default(),
+            activity_timeout_ms: None,
+            worktree_root: None,
+            default_role: None,
+            session_init_command: None,
             id: None,
         };
         let id = config.deterministic_id();
@@ -4263,6 +9317,44 @@ mod tests {
         assert!(app.sessions.len() < initial_count);
     }
 
+    #[test]
+    fn ctrl_c_forwards_to_pty_from_terminal_by_default() {
+        let mut app = app_with_sessions(2);
+        app.focus = InputFocus::Terminal;
+        app.handle_key(KeyCode::Char('c'), KeyModifiers::CONTROL);
+        assert_eq!(app.sessions.len(), 2);
+    }
+
+    #[test]
+    fn ctrl_c_closes_from_terminal_when_configured() {
+        let mut app = app_with_sessions(2);
+        app.set_settings(settings::Settings {
+            terminal: settings::TerminalSettings {
+                ctrl_c: settings::CtrlCBehavior::Close,
+            },
+            ..Default::default()
+        });
+        app.focus = InputFocus::Terminal;
+        app.handle_key(KeyCode::Char('c'), KeyModifiers::CONTROL);
+        assert_eq!(app.sessions.len(), 1);
+    }
+
+    #[test]
+    fn ctrl_c_closes_from_session_list() {
+        let mut app = app_with_sessions(2);
+        app.focus = InputFocus::SessionList;
+        app.handle_key(KeyCode::Char('c'), KeyModifiers::CONTROL);
+        assert_eq!(app.sessions.len(), 1);
+    }
+
+    #[test]
+    fn ctrl_x_closes_from_terminal() {
+        let mut app = app_with_sessions(2);
+        app.focus = InputFocus::Terminal;
+        app.handle_key(KeyCode::Char('x'), KeyModifiers::CONTROL);
+        assert_eq!(app.sessions.len(), 1);
+    }
+
     #[test]
     fn ctrl_d_deletes_session_from_session_list() {
         let mut app = app_with_sessions(2);
@@ -4283,6 +9375,13 @@ mod tests {
                 repos: vec![],
                 roles: vec![],
                 mcp_servers: vec![],
+                sync_base_branch: None,
+                remote: None,
+                sync_strategy: SyncStrategy::default(),
+                activity_timeout_ms: None,
+                worktree_root: None,
+                default_role: None,
+                session_init_command: None,
                 id: None,
             },
             session_ids: vec![],
@@ -4336,6 +9435,156 @@ mod tests {
         assert!(!app.show_help);
     }
 
+    #[test]
+    fn f4_toggles_session_tabs_and_widens_terminal() {
+        let mut app = app_with_sessions(1);
+        app.focus = InputFocus::SessionList;
+        let (rows, cols) = app.content_area_size();
+
+        app.handle_key(KeyCode::F(4), KeyModifiers::NONE);
+        assert!(app.show_session_tabs);
+        assert_eq!(app.focus, InputFocus::Terminal);
+        assert_eq!(app.content_area_size(), (rows - 1, app.terminal_cols - 2));
+        assert!(app.content_area_size().1 > cols);
+
+        app.handle_key(KeyCode::F(4), KeyModifiers::NONE);
+        assert!(!app.show_session_tabs);
+        assert_eq!(app.content_area_size(), (rows, cols));
+    }
+
+    #[test]
+    fn layout_ratios_drive_render_and_pty_size() {
+        let mut app = app_with_sessions(1);
+        let area = Rect::new(0, 0, app.terminal_cols, app.terminal_rows);
+        let default_terminal = app.compute_layout(area).terminal;
+
+        app.set_settings(settings::Settings::parse("[layout]\nlist_percent = 40\n").unwrap());
+        assert!(app.status_message.is_none());
+        let areas = app.compute_layout(area);
+        assert_eq!(
+            areas.left_panel.unwrap().width,
+            app.terminal_cols * 40 / 100
+        );
+        assert!(areas.terminal.width < default_terminal.width);
+        assert_eq!(
+            app.content_area_size(),
+            (areas.terminal.height - 2, areas.terminal.width - 2)
+        );
+    }
+
+    #[test]
+    fn invalid_layout_ratios_fall_back_to_defaults() {
+        let mut app = app_with_sessions(0);
+        app.set_settings(
+            settings::Settings::parse("[layout]\nlist_percent = 70\ninfo_percent = 30\n").unwrap(),
+        );
+        assert_eq!(app.panel_ratios, layout::PanelRatios::default());
+        assert_eq!(
+            app.status_message.as_ref().unwrap().text,
+            "layout.list_percent (70) + layout.info_percent (30) must be under 100; \
+             using default layout"
+        );
+    }
+
+    #[test]
+    fn f8_wrap_rewraps_long_line_when_narrowed() {
+        let mut app = app_with_sessions(1);
+        let (rows, cols) = app.content_area_size();
+        {
+            let mut parser = app.sessions[0].parser.lock().unwrap();
+            *parser = vt100::Parser::new(rows, cols, 100);
+            parser.process("x".repeat(usize::from(cols)).as_bytes());
+        }
+
+        app.handle_key(KeyCode::F(8), KeyModifiers::NONE);
+        assert!(app.wrap_lines);
+        app.handle_resize(app.terminal_cols - 20, app.terminal_rows);
+
+        let (_, narrow) = app.content_area_size();
+        assert!(narrow < cols);
+        let parser = app.sessions[0].parser.lock().unwrap();
+        let screen = parser.screen();
+        assert_eq!(screen.size().1, narrow);
+        let lines: Vec<String> = screen.rows(0, narrow).take(2).collect();
+        assert_eq!(lines[0], "x".repeat(usize::from(narrow)));
+        assert_eq!(lines[1], "x".repeat(usize::from(cols - narrow)));
+        assert!(screen.row_wrapped(0));
+    }
+
+    #[test]
+    fn resize_without_wrap_truncates() {
+        let mut app = app_with_sessions(1);
+        let (rows, cols) = app.content_area_size();
+        {
+            let mut parser = app.sessions[0].parser.lock().unwrap();
+            *parser = vt100::Parser::new(rows, cols, 100);
+            parser.process("x".repeat(usize::from(cols)).as_bytes());
+        }
+
+        app.handle_resize(app.terminal_cols - 20, app.terminal_rows);
+
+        let (_, narrow) = app.content_area_size();
+        let parser = app.sessions[0].parser.lock().unwrap();
+        let lines: Vec<String> = parser.screen().rows(0, narrow).take(2).collect();
+        assert_eq!(lines[0], "x".repeat(usize::from(narrow)));
+        assert_eq!(lines[1], "");
+    }
+
+    #[test]
+    fn f9_cycles_info_panel_mode() {
+        let mut app = app_with_sessions(0);
+        assert!(!app.show_info_panel);
+
+        // A hidden panel is shown first, keeping its view
+        app.handle_key(KeyCode::F(9), KeyModifiers::NONE);
+        assert!(app.show_info_panel);
+        assert_eq!(app.info_panel_mode, info_panel::InfoPanelMode::Session);
+
+        app.handle_key(KeyCode::F(9), KeyModifiers::NONE);
+        assert_eq!(app.info_panel_mode, info_panel::InfoPanelMode::Git);
+        app.handle_key(KeyCode::F(9), KeyModifiers::NONE);
+        assert_eq!(app.info_panel_mode, info_panel::InfoPanelMode::Role);
+        app.handle_key(KeyCode::F(9), KeyModifiers::NONE);
+        assert_eq!(app.info_panel_mode, info_panel::InfoPanelMode::Session);
+
+        // Hiding and showing again keeps the view
+        app.info_panel_mode = info_panel::InfoPanelMode::Git;
+        app.handle_key(KeyCode::F(2), KeyModifiers::NONE);
+        app.handle_key(KeyCode::F(2), KeyModifiers::NONE);
+        assert_eq!(app.info_panel_mode, info_panel::InfoPanelMode::Git);
+    }
+
+    #[test]
+    fn info_panel_returns_after_narrow_resize() {
+        let mut app = app_with_sessions(0);
+        app.handle_resize(140, 40);
+        app.handle_key(KeyCode::F(2), KeyModifiers::NONE);
+        assert!(app.show_info_panel);
+
+        app.handle_resize(100, 40);
+        assert!(!app.show_info_panel);
+        assert!(app.info_panel_user_pref);
+
+        app.handle_resize(140, 40);
+        assert!(app.show_info_panel);
+    }
+
+    #[test]
+    fn info_panel_stays_hidden_after_resize_when_toggled_off() {
+        let mut app = app_with_sessions(0);
+        app.handle_resize(140, 40);
+        app.handle_resize(100, 40);
+        app.handle_resize(140, 40);
+        assert!(!app.show_info_panel);
+
+        // Turned on while narrow: shown once the terminal widens.
+        app.handle_resize(100, 40);
+        app.handle_key(KeyCode::F(2), KeyModifiers::NONE);
+        assert!(!app.show_info_panel);
+        app.handle_resize(140, 40);
+        assert!(app.show_info_panel);
+    }
+
     #[test]
     fn f2_toggles_info_panel() {
         let mut app = app_with_sessions(0);
@@ -4346,6 +9595,34 @@ mod tests {
         assert!(!app.show_info_panel);
     }
 
+    #[test]
+    fn project_switch_applies_stored_ui_prefs() {
+        let mut app = app_with_projects(2);
+        app.handle_resize(140, 40);
+
+        // Project 1: panel on, busy filter
+        app.handle_key(KeyCode::F(2), KeyModifiers::NONE);
+        app.cycle_session_filter();
+        app.cycle_session_filter();
+        assert_eq!(app.session_filter, SessionFilter::Busy);
+
+        // Project 2 has nothing stored yet and keeps the current state
+        app.switch_project_forward();
+        assert!(app.show_info_panel);
+        app.handle_key(KeyCode::F(2), KeyModifiers::NONE);
+        app.cycle_session_filter();
+        assert!(!app.show_info_panel);
+
+        app.switch_project_backward();
+        assert!(app.show_info_panel);
+        assert_eq!(app.session_filter, SessionFilter::Busy);
+
+        app.switch_project_forward();
+        assert!(!app.show_info_panel);
+        assert!(!app.info_panel_user_pref);
+        assert_eq!(app.session_filter, SessionFilter::All);
+    }
+
     #[test]
     fn ctrl_l_cycles_focus() {
         let mut app = app_with_sessions(1);
@@ -4358,1221 +9635,5491 @@ mod tests {
         assert_eq!(app.focus, InputFocus::ProjectList);
     }
 
-    // --- Context-sensitive Ctrl+J/K tests ---
+    // --- Rename session tests ---
 
-    fn app_with_projects(count: usize) -> App {
-        let mut app = app_with_sessions(0);
-        // app already has one default project at index 0
-        for i in 1..count {
-            app.projects.push(ProjectInfo {
-                id: ProjectId::default(),
-                config: ProjectConfig {
-                    name: format!("Project {}", i + 1),
-                    repos: vec![],
-                    roles: vec![],
-                    mcp_servers: vec![],
-                    id: None,
-                },
-                session_ids: vec![],
-                is_admin: false,
-            });
-        }
-        app
+    #[test]
+    fn ctrl_comma_opens_rename_modal_prefilled() {
+        let mut app = app_with_sessions(2);
+        app.active_index = 1;
+        app.handle_key(KeyCode::Char(','), KeyModifiers::CONTROL);
+        assert!(app.show_rename_session_modal);
+        assert_eq!(app.rename_session_input.value(), "Session 2");
+        assert_eq!(app.rename_session_id, Some(app.sessions[1].info.id));
     }
 
     #[test]
-    fn ctrl_j_moves_project_forward_when_project_list_focused() {
-        let mut app = app_with_projects(3);
-        app.focus = InputFocus::ProjectList;
-        app.active_project_index = 0;
-        app.handle_key(KeyCode::Char('j'), KeyModifiers::CONTROL);
-        assert_eq!(app.active_project_index, 1);
+    fn r_in_session_list_opens_rename_modal() {
+        let mut app = app_with_sessions(1);
+        app.focus = InputFocus::SessionList;
+        app.handle_key(KeyCode::Char('r'), KeyModifiers::NONE);
+        assert!(app.show_rename_session_modal);
     }
 
     #[test]
-    fn ctrl_k_moves_project_backward_when_project_list_focused() {
-        let mut app = app_with_projects(3);
-        app.focus = InputFocus::ProjectList;
-        app.active_project_index = 2;
-        app.handle_key(KeyCode::Char('k'), KeyModifiers::CONTROL);
-        assert_eq!(app.active_project_index, 1);
+    fn rename_modal_noop_without_sessions() {
+        let mut app = app_with_sessions(0);
+        app.handle_key(KeyCode::Char(','), KeyModifiers::CONTROL);
+        assert!(!app.show_rename_session_modal);
     }
 
     #[test]
-    fn ctrl_j_switches_session_when_session_list_focused() {
-        let mut app = app_with_sessions(3);
-        app.focus = InputFocus::SessionList;
-        app.active_index = 0;
-        app.handle_key(KeyCode::Char('j'), KeyModifiers::CONTROL);
-        assert_eq!(app.active_index, 1);
+    fn rename_session_persists_to_db() {
+        let mut app = app_with_sessions(1);
+        let id = app.sessions[0].info.id;
+        let backend_id = app.sessions[0].backend_id().to_string();
+
+        app.handle_key(KeyCode::Char(','), KeyModifiers::CONTROL);
+        app.rename_session_input.clear();
+        for c in "  Backend API ".chars() {
+            app.handle_key(KeyCode::Char(c), KeyModifiers::NONE);
+        }
+        app.handle_key(KeyCode::Enter, KeyModifiers::NONE);
+
+        assert!(!app.show_rename_session_modal);
+        assert_eq!(app.sessions[0].info.name, "Backend API");
+        assert_eq!(app.sessions[0].backend_id(), backend_id);
+
+        let stored = app.db.list_active_sessions().unwrap();
+        let stored = stored.iter().find(|s| s.id == id).unwrap();
+        assert_eq!(stored.name, "Backend API");
     }
 
     #[test]
-    fn ctrl_j_switches_session_when_terminal_focused() {
-        let mut app = app_with_sessions(3);
-        app.focus = InputFocus::Terminal;
-        app.active_index = 0;
-        app.handle_key(KeyCode::Char('j'), KeyModifiers::CONTROL);
-        assert_eq!(app.active_index, 1);
+    fn rename_session_rejects_empty_name() {
+        let mut app = app_with_sessions(1);
+        app.handle_key(KeyCode::Char(','), KeyModifiers::CONTROL);
+        app.rename_session_input.set("   ");
+        app.handle_key(KeyCode::Enter, KeyModifiers::NONE);
+
+        assert!(app.show_rename_session_modal);
+        assert_eq!(app.sessions[0].info.name, "Session 1");
+        assert_eq!(
+            app.status_message.as_ref().map(|m| m.level),
+            Some(StatusLevel::Error)
+        );
     }
 
     #[test]
-    fn ctrl_j_at_last_project_wraps_to_first() {
-        let mut app = app_with_projects(3);
-        app.focus = InputFocus::ProjectList;
-        app.active_project_index = 2;
-        app.handle_key(KeyCode::Char('j'), KeyModifiers::CONTROL);
-        assert_eq!(app.active_project_index, 0);
+    fn rename_session_esc_cancels() {
+        let mut app = app_with_sessions(1);
+        app.handle_key(KeyCode::Char(','), KeyModifiers::CONTROL);
+        app.handle_key(KeyCode::Char('x'), KeyModifiers::NONE);
+        app.handle_key(KeyCode::Esc, KeyModifiers::NONE);
+
+        assert!(!app.show_rename_session_modal);
+        assert_eq!(app.sessions[0].info.name, "Session 1");
     }
 
-    #[test]
-    fn ctrl_k_at_first_project_wraps_to_last() {
-        let mut app = app_with_projects(3);
-        app.focus = InputFocus::ProjectList;
-        app.active_project_index = 0;
-        app.handle_key(KeyCode::Char('k'), KeyModifiers::CONTROL);
-        assert_eq!(app.active_project_index, 2);
-    }
-
-    // --- DB persistence tests ---
+    // --- Extra directories editor tests ---
 
     #[test]
-    fn load_persisted_state_empty_db_returns_none() {
-        let app = App::new(24, 80, stub_backend(), test_db());
-        assert!(app.load_persisted_state_from_db().is_none());
+    fn a_in_session_list_opens_dirs_editor_prefilled() {
+        let mut app = app_with_sessions(1);
+        app.sessions[0].info.additional_dirs = vec![PathBuf::from("/repo2")];
+        app.focus = InputFocus::SessionList;
+        app.handle_key(KeyCode::Char('a'), KeyModifiers::NONE);
+        assert!(app.show_session_dirs_modal);
+        assert_eq!(app.session_dirs.items, vec!["/repo2".to_string()]);
+        assert_eq!(app.session_dirs_id, Some(app.sessions[0].info.id));
     }
 
     #[test]
-    fn load_persisted_state_sessions_without_claude_id_returns_none() {
-        let db = test_db();
-        let proj_config = ProjectConfig {
-            name: "test".to_string(),
-            repos: vec![],
-            roles: Vec::new(),
-            mcp_servers: Vec::new(),
-            id: None,
-        };
-        let pid = proj_config.deterministic_id();
-        db.insert_project(pid, "test", &[]).unwrap();
+    fn editing_additional_dirs_updates_info_and_shared() {
+        let extra = tempfile::TempDir::new().unwrap();
+        let mut app = app_with_sessions(1);
+        app.sessions[0].info.additional_dirs = vec![PathBuf::from("/repo2")];
+        app.focus = InputFocus::SessionList;
+        app.handle_key(KeyCode::Char('a'), KeyModifiers::NONE);
 
-        // Session without claude_session_id — not resumable
-        let session = sync::SharedSession {
-            id: SessionId::default(),
-            name: "1".to_string(),
-            project_id: pid,
-            role: "developer".to_string(),
-            backend_id: "thurbox:@0".to_string(),
-            backend_type: "tmux".to_string(),
-            claude_session_id: None,
-            cwd: None,
-            additional_dirs: Vec::new(),
-            worktrees: Vec::new(),
-            shell_backend_id: None,
-            tombstone: false,
-            tombstone_at: None,
-        };
-        db.upsert_session(&session).unwrap();
+        // Add the temp dir, move it first, then drop /repo2
+        app.handle_key(KeyCode::Char('a'), KeyModifiers::NONE);
+        for c in extra.path().display().to_string().chars() {
+            app.handle_key(KeyCode::Char(c), KeyModifiers::NONE);
+        }
+        app.handle_key(KeyCode::Enter, KeyModifiers::NONE);
+        app.handle_key(KeyCode::Char('K'), KeyModifiers::NONE);
+        app.handle_key(KeyCode::Char('j'), KeyModifiers::NONE);
+        app.handle_key(KeyCode::Char('d'), KeyModifiers::NONE);
+        app.handle_key(KeyCode::Enter, KeyModifiers::NONE);
 
-        let app = App::new(24, 80, stub_backend(), db);
-        assert!(app.load_persisted_state_from_db().is_none());
+        assert!(!app.show_session_dirs_modal);
+        assert_eq!(
+            app.sessions[0].info.additional_dirs,
+            vec![extra.path().to_path_buf()]
+        );
+        let shared = app.session_to_shared(&app.sessions[0]);
+        assert_eq!(shared.additional_dirs, vec![extra.path().to_path_buf()]);
+        let stored = app.db.list_active_sessions().unwrap();
+        assert_eq!(stored[0].additional_dirs, vec![extra.path().to_path_buf()]);
     }
 
     #[test]
-    fn load_persisted_state_filters_to_resumable_only() {
-        let db = test_db();
-        let proj_config = ProjectConfig {
-            name: "test".to_string(),
-            repos: vec![],
-            roles: Vec::new(),
-            mcp_servers: Vec::new(),
-            id: None,
-        };
-        let pid = proj_config.deterministic_id();
-        db.insert_project(pid, "test", &[]).unwrap();
-
-        // Non-resumable session
-        let s1 = sync::SharedSession {
-            id: SessionId::default(),
-            name: "1".to_string(),
-            project_id: pid,
-            role: "developer".to_string(),
-            backend_id: "thurbox:@0".to_string(),
-            backend_type: "tmux".to_string(),
-            claude_session_id: None,
-            cwd: None,
-            additional_dirs: Vec::new(),
-            worktrees: Vec::new(),
-            shell_backend_id: None,
-            tombstone: false,
-            tombstone_at: None,
-        };
-        db.upsert_session(&s1).unwrap();
-
-        // Resumable session
-        let s2 = sync::SharedSession {
-            id: SessionId::default(),
-            name: "2".to_string(),
-            project_id: pid,
-            role: "developer".to_string(),
-            backend_id: "thurbox:@1".to_string(),
-            backend_type: "tmux".to_string(),
-            claude_session_id: Some("claude-abc".to_string()),
-            cwd: None,
-            additional_dirs: Vec::new(),
-            worktrees: Vec::new(),
-            shell_backend_id: None,
-            tombstone: false,
-            tombstone_at: None,
-        };
-        db.upsert_session(&s2).unwrap();
-        db.set_session_counter(7).unwrap();
+    fn dirs_editor_rejects_missing_directory() {
+        let mut app = app_with_sessions(1);
+        app.focus = InputFocus::SessionList;
+        app.handle_key(KeyCode::Char('a'), KeyModifiers::NONE);
+        app.handle_key(KeyCode::Char('a'), KeyModifiers::NONE);
+        app.session_dirs.input.set("/definitely/not/here");
+        app.handle_key(KeyCode::Enter, KeyModifiers::NONE);
 
-        let app = App::new(24, 80, stub_backend(), db);
-        let (sessions, counter) = app.load_persisted_state_from_db().unwrap();
-        assert_eq!(sessions.len(), 1);
-        assert_eq!(sessions[0].name, "2");
-        assert_eq!(counter, 7);
+        assert!(app.session_dirs.items.is_empty());
+        assert_eq!(
+            app.status_message.as_ref().map(|m| m.level),
+            Some(StatusLevel::Error)
+        );
     }
 
     #[test]
-    fn save_state_roundtrips_sessions() {
-        let backend = stub_backend();
-        let mut app = App::new(
-            24,
-            120,
-            backend.clone(),
-            test_db_with_project(&test_project_config()),
+    fn dirs_editor_esc_discards_changes() {
+        let mut app = app_with_sessions(1);
+        app.sessions[0].info.additional_dirs = vec![PathBuf::from("/repo2")];
+        app.focus = InputFocus::SessionList;
+        app.handle_key(KeyCode::Char('a'), KeyModifiers::NONE);
+        app.handle_key(KeyCode::Char('d'), KeyModifiers::NONE);
+        app.handle_key(KeyCode::Esc, KeyModifiers::NONE);
+
+        assert!(!app.show_session_dirs_modal);
+        assert_eq!(
+            app.sessions[0].info.additional_dirs,
+            vec![PathBuf::from("/repo2")]
         );
+    }
 
-        // Add a session
-        let session = Session::stub("Session 1", &backend);
-        let sid = session.info.id;
-        app.sessions.push(session);
-        app.projects[0].session_ids.push(sid);
+    // --- Terminal search tests ---
 
-        // Save to DB (only persists sessions + counter, not projects)
-        app.save_state();
+    /// One session in terminal focus whose parser holds `line 0..line 39`
+    /// on a 5-row screen, scrolled back by one line.
+    fn app_with_searchable_terminal() -> App {
+        let mut app = app_with_sessions(1);
+        app.focus = InputFocus::Terminal;
+        let mut parser = vt100::Parser::new(5, 20, 100);
+        let text: Vec<String> = (0..40).map(|i| format!("line {i}")).collect();
+        parser.process(text.join("\r\n").as_bytes());
+        parser.screen_mut().set_scrollback(1);
+        *app.sessions[0].parser.lock().unwrap() = parser;
+        app
+    }
 
-        // Verify session in DB
-        let sessions = app.db.list_active_sessions().unwrap();
-        assert_eq!(sessions.len(), 1);
-        assert_eq!(sessions[0].name, "Session 1");
+    fn type_search(app: &mut App, query: &str) {
+        app.handle_key(KeyCode::Char('/'), KeyModifiers::NONE);
+        for c in query.chars() {
+            app.handle_key(KeyCode::Char(c), KeyModifiers::NONE);
+        }
+        app.handle_key(KeyCode::Enter, KeyModifiers::NONE);
+    }
+
+    fn current_match_line(app: &App) -> usize {
+        app.terminal_search
+            .as_ref()
+            .and_then(|s| s.current)
+            .expect("current match")
+            .line
     }
 
     #[test]
-    fn save_state_persists_session_counter() {
-        let backend = stub_backend();
-        let mut app = App::new(24, 120, backend.clone(), test_db());
-        app.session_counter = 42;
+    fn end_while_scrolled_back_returns_to_bottom() {
+        let mut app = app_with_searchable_terminal();
+        app.scroll_terminal_up(10);
+        assert_eq!(app.active_scrollback(), 11);
+        app.handle_key(KeyCode::End, KeyModifiers::NONE);
+        assert_eq!(app.active_scrollback(), 0);
+    }
 
-        app.save_state();
+    #[test]
+    fn slash_at_live_view_is_forwarded() {
+        let mut app = app_with_searchable_terminal();
+        app.scroll_terminal_down(1);
+        app.handle_key(KeyCode::Char('/'), KeyModifiers::NONE);
+        assert!(app.terminal_search.is_none());
+    }
 
-        let counter = app.db.get_session_counter().unwrap();
-        assert_eq!(counter, 42);
+    #[test]
+    fn slash_while_scrolled_back_opens_search_prompt() {
+        let mut app = app_with_searchable_terminal();
+        app.handle_key(KeyCode::Char('/'), KeyModifiers::NONE);
+        let search = app.terminal_search.as_ref().unwrap();
+        assert!(search.editing);
+        assert_eq!(search.query.value(), "");
     }
 
     #[test]
-    fn session_to_shared_converts_correctly() {
-        let backend = stub_backend();
-        let mut app = App::new(
-            24,
-            120,
-            backend.clone(),
-            test_db_with_project(&test_project_config()),
-        );
+    fn search_prompt_captures_global_shortcut_letters() {
+        let mut app = app_with_searchable_terminal();
+        app.handle_key(KeyCode::Char('/'), KeyModifiers::NONE);
+        app.handle_key(KeyCode::Char('q'), KeyModifiers::NONE);
+        app.handle_key(KeyCode::Char('n'), KeyModifiers::NONE);
+        assert_eq!(app.terminal_search.as_ref().unwrap().query.value(), "qn");
+        assert!(!app.should_quit);
+    }
 
-        let mut session = Session::stub("TestSession", &backend);
-        session.info.role = "reviewer".to_string();
-        session.info.cwd = Some(PathBuf::from("/home/user"));
-        session.info.claude_session_id = Some("claude-xyz".to_string());
+    #[test]
+    fn search_jumps_to_nearest_match_above_view() {
+        let mut app = app_with_searchable_terminal();
+        app.scroll_terminal_up(20);
+        type_search(&mut app, "line 1");
+        // View bottom is line 18; nearest "line 1*" at or above it is line 18
+        assert_eq!(current_match_line(&app), 18);
+        let search = app.terminal_search.as_ref().unwrap();
+        assert!(!search.editing);
+        assert_eq!(search.position, Some((10, 11)));
+    }
 
-        let sid = session.info.id;
-        app.sessions.push(session);
-        app.projects[0].session_ids.push(sid);
+    #[test]
+    fn search_centres_match_in_view() {
+        let mut app = app_with_searchable_terminal();
+        type_search(&mut app, "line 3");
+        let line = current_match_line(&app);
+        let mut top = 0;
+        app.with_active_parser(|parser| {
+            let total = search::scrollback_len(parser);
+            top = total - parser.screen().scrollback();
+        });
+        assert!((top..top + 5).contains(&line));
+    }
 
-        let shared = app.session_to_shared(&app.sessions[0]);
-        assert_eq!(shared.id, sid);
-        assert_eq!(shared.name, "TestSession");
-        assert_eq!(shared.role, "reviewer");
-        assert_eq!(shared.cwd, Some(PathBuf::from("/home/user")));
-        assert_eq!(shared.claude_session_id, Some("claude-xyz".to_string()));
-        assert!(!shared.tombstone);
-        assert!(shared.tombstone_at.is_none());
+    #[test]
+    fn n_moves_to_older_match_and_wraps() {
+        let mut app = app_with_searchable_terminal();
+        type_search(&mut app, "line 2");
+        // "line 2" and "line 20".."line 29": nearest is line 29
+        assert_eq!(current_match_line(&app), 29);
+        app.handle_key(KeyCode::Char('n'), KeyModifiers::NONE);
+        assert_eq!(current_match_line(&app), 28);
+        app.handle_key(KeyCode::Char('N'), KeyModifiers::SHIFT);
+        assert_eq!(current_match_line(&app), 29);
+        app.handle_key(KeyCode::Char('N'), KeyModifiers::SHIFT);
+        // Wraps from the newest match back to the oldest
+        assert_eq!(current_match_line(&app), 2);
+        app.handle_key(KeyCode::Char('n'), KeyModifiers::NONE);
+        assert_eq!(current_match_line(&app), 29);
     }
 
-    // --- Edit-project modal tests ---
+    #[test]
+    fn search_without_matches_reports_error() {
+        let mut app = app_with_searchable_terminal();
+        type_search(&mut app, "nothing here");
+        let search = app.terminal_search.as_ref().unwrap();
+        assert!(search.current.is_none());
+        assert!(search.position.is_none());
+        let msg = app.status_message.as_ref().unwrap();
+        assert_eq!(msg.level, StatusLevel::Error);
+        assert!(msg.text.contains("nothing here"));
+    }
 
-    /// Create an App with a single project for edit-project tests.
-    fn app_with_project(name: &str, repos: Vec<PathBuf>) -> App {
-        let config = ProjectConfig {
-            name: name.to_string(),
-            repos,
-            roles: Vec::new(),
-            mcp_servers: Vec::new(),
-            id: None,
-        };
-        App::new(24, 120, stub_backend(), test_db_with_project(&config))
+    #[test]
+    fn empty_search_closes_prompt() {
+        let mut app = app_with_searchable_terminal();
+        type_search(&mut app, "");
+        assert!(app.terminal_search.is_none());
     }
 
     #[test]
-    fn open_edit_project_populates_fields() {
-        let mut app = app_with_project("my-proj", vec![PathBuf::from("/repo/a")]);
-        app.open_edit_project_modal();
-        assert!(app.show_edit_project_modal);
-        assert_eq!(app.edit_project_name.value(), "my-proj");
-        assert_eq!(app.edit_project_repos, vec![PathBuf::from("/repo/a")]);
-        assert_eq!(app.edit_project_field, EditProjectField::Name);
-        assert!(app.edit_project_original_id.is_some());
+    fn esc_closes_search_without_snapping() {
+        let mut app = app_with_searchable_terminal();
+        type_search(&mut app, "line 2");
+        let offset = app.active_scrollback();
+        app.handle_key(KeyCode::Esc, KeyModifiers::NONE);
+        assert!(app.terminal_search.is_none());
+        assert_eq!(app.active_scrollback(), offset);
     }
 
     #[test]
-    fn submit_edit_project_updates_name_and_repos() {
-        let mut app = app_with_project("old-name", vec![PathBuf::from("/repo/a")]);
-        let original_id = app.projects[0].id;
+    fn other_key_ends_search_and_snaps_to_bottom() {
+        let mut app = app_with_searchable_terminal();
+        type_search(&mut app, "line 2");
+        app.handle_key(KeyCode::Char('x'), KeyModifiers::NONE);
+        assert!(app.terminal_search.is_none());
+        assert_eq!(app.active_scrollback(), 0);
+    }
 
-        app.open_edit_project_modal();
-        app.edit_project_name.clear();
-        app.edit_project_name.set("new-name");
-        app.edit_project_repos = vec![PathBuf::from("/repo/b"), PathBuf::from("/repo/c")];
-        app.submit_edit_project();
+    #[test]
+    fn slash_reopens_prompt_with_previous_query() {
+        let mut app = app_with_searchable_terminal();
+        type_search(&mut app, "line 2");
+        app.handle_key(KeyCode::Char('/'), KeyModifiers::NONE);
+        let search = app.terminal_search.as_ref().unwrap();
+        assert!(search.editing);
+        assert_eq!(search.query.value(), "line 2");
+    }
 
-        assert!(!app.show_edit_project_modal);
-        assert_eq!(app.projects[0].config.name, "new-name");
-        assert_eq!(app.projects[0].config.repos.len(), 2);
-        // ID must stay stable (no UUID regeneration)
-        assert_eq!(app.projects[0].id, original_id);
+    // --- Terminal copy mode tests ---
+
+    #[test]
+    fn v_at_live_view_is_forwarded() {
+        let mut app = app_with_searchable_terminal();
+        app.scroll_terminal_down(1);
+        app.handle_key(KeyCode::Char('v'), KeyModifiers::NONE);
+        assert!(app.terminal_selection.is_none());
     }
 
     #[test]
-    fn submit_edit_project_rejects_empty_name() {
-        let mut app = app_with_project("test", vec![PathBuf::from("/repo")]);
-        app.open_edit_project_modal();
-        app.edit_project_name.clear();
-        app.submit_edit_project();
+    fn v_while_scrolled_back_starts_on_middle_row() {
+        let mut app = app_with_searchable_terminal();
+        // 35 lines of scrollback, offset 1: view shows lines 34..39
+        app.handle_key(KeyCode::Char('v'), KeyModifiers::NONE);
+        let sel = app.terminal_selection.unwrap();
+        assert_eq!(sel.anchor, CellPos { line: 36, col: 0 });
+        assert_eq!(sel.cursor, sel.anchor);
+    }
 
-        // Modal should still be open
-        assert!(app.show_edit_project_modal);
-        assert!(app.status_message.is_some());
+    #[test]
+    fn v_during_search_starts_on_current_match() {
+        let mut app = app_with_searchable_terminal();
+        type_search(&mut app, "line 12");
+        app.handle_key(KeyCode::Char('v'), KeyModifiers::NONE);
+        assert!(app.terminal_search.is_none());
+        let sel = app.terminal_selection.unwrap();
+        assert_eq!(sel.anchor, CellPos { line: 12, col: 0 });
     }
 
     #[test]
-    fn submit_edit_project_rejects_empty_repos() {
-        let mut app = app_with_project("test", vec![PathBuf::from("/repo")]);
-        app.open_edit_project_modal();
-        app.edit_project_repos.clear();
-        app.submit_edit_project();
+    fn copy_mode_movement_extends_selection() {
+        let mut app = app_with_searchable_terminal();
+        type_search(&mut app, "line 12");
+        app.handle_key(KeyCode::Char('v'), KeyModifiers::NONE);
+        app.handle_key(KeyCode::Char('j'), KeyModifiers::NONE);
+        app.handle_key(KeyCode::Char('$'), KeyModifiers::NONE);
+        app.handle_key(KeyCode::Char('h'), KeyModifiers::NONE);
+        let sel = app.terminal_selection.unwrap();
+        assert_eq!(sel.cursor, CellPos { line: 13, col: 18 });
+        assert_eq!(
+            app.selected_terminal_text().as_deref(),
+            Some("line 12\nline 13")
+        );
+    }
 
-        assert!(app.show_edit_project_modal);
-        assert!(app.status_message.is_some());
+    #[test]
+    fn copy_mode_captures_keys() {
+        let mut app = app_with_searchable_terminal();
+        app.handle_key(KeyCode::Char('v'), KeyModifiers::NONE);
+        let offset = app.active_scrollback();
+        app.handle_key(KeyCode::Char('x'), KeyModifiers::NONE);
+        assert!(app.terminal_selection.is_some());
+        assert_eq!(app.active_scrollback(), offset);
     }
 
     #[test]
-    fn submit_edit_project_auto_adds_pending_path() {
-        let mut app = app_with_project("test", vec![PathBuf::from("/repo/a")]);
-        app.open_edit_project_modal();
-        app.edit_project_path.set("/repo/b");
-        app.submit_edit_project();
+    fn copy_mode_cursor_scrolls_view() {
+        let mut app = app_with_searchable_terminal();
+        app.handle_key(KeyCode::Char('v'), KeyModifiers::NONE);
+        for _ in 0..10 {
+            app.handle_key(KeyCode::Char('k'), KeyModifiers::NONE);
+        }
+        let line = app.terminal_selection.unwrap().cursor.line;
+        assert_eq!(line, 26);
+        // Cursor line is now the top row of the view
+        assert_eq!(app.active_scrollback(), 35 - 26);
+    }
 
-        assert!(!app.show_edit_project_modal);
-        assert_eq!(app.projects[0].config.repos.len(), 2);
-        assert_eq!(app.projects[0].config.repos[1], PathBuf::from("/repo/b"));
+    #[test]
+    fn copy_mode_cursor_clamps_to_buffer() {
+        let mut app = app_with_searchable_terminal();
+        app.handle_key(KeyCode::Char('v'), KeyModifiers::NONE);
+        for _ in 0..10 {
+            app.handle_key(KeyCode::Char('j'), KeyModifiers::NONE);
+            app.handle_key(KeyCode::Char('l'), KeyModifiers::NONE);
+        }
+        let cursor = app.terminal_selection.unwrap().cursor;
+        assert_eq!(cursor, CellPos { line: 39, col: 10 });
+        assert_eq!(app.active_scrollback(), 0);
     }
 
     #[test]
-    fn close_edit_project_clears_all_state() {
-        let mut app = app_with_project("test", vec![PathBuf::from("/repo")]);
-        app.open_edit_project_modal();
-        assert!(app.show_edit_project_modal);
+    fn esc_cancels_copy_mode() {
+        let mut app = app_with_searchable_terminal();
+        app.handle_key(KeyCode::Char('v'), KeyModifiers::NONE);
+        app.handle_key(KeyCode::Esc, KeyModifiers::NONE);
+        assert!(app.terminal_selection.is_none());
+        assert!(app.status_message.is_none());
+    }
 
-        app.close_edit_project_modal();
-        assert!(!app.show_edit_project_modal);
-        assert_eq!(app.edit_project_name.value(), "");
-        assert!(app.edit_project_repos.is_empty());
-        assert!(app.edit_project_original_id.is_none());
+    // --- Output log tests ---
+
+    #[test]
+    fn toggle_output_log_opens_log_under_session_logs() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let _guard = crate::paths::TestPathGuard::new(tmp.path());
+        let mut app = app_with_sessions(1);
+
+        app.handle_key(KeyCode::F(3), KeyModifiers::NONE);
+
+        let session = &app.sessions[0];
+        assert!(session.info.log_output);
+        assert!(session.is_logging_output());
+        let path = session.info.log_path.clone().unwrap();
+        assert_eq!(path.parent().unwrap(), tmp.path().join("session-logs"));
+        let file_name = path.file_name().unwrap().to_string_lossy().to_string();
+        assert_eq!(file_name, format!("Session_1-{}.log", session.info.id));
+        assert!(path.exists());
+        let msg = app.status_message.as_ref().unwrap();
+        assert!(msg.text.contains("Logging output to"));
     }
 
     #[test]
-    fn edit_project_tab_cycles_through_all_fields() {
-        let mut app = app_with_project("test", vec![PathBuf::from("/repo")]);
-        app.open_edit_project_modal();
+    fn toggle_output_log_twice_stops_logging() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let _guard = crate::paths::TestPathGuard::new(tmp.path());
+        let mut app = app_with_sessions(1);
 
-        // Name -> Path
-        assert_eq!(app.edit_project_field, EditProjectField::Name);
-        app.handle_key(KeyCode::Tab, KeyModifiers::NONE);
-        assert_eq!(app.edit_project_field, EditProjectField::Path);
+        app.toggle_output_log();
+        app.toggle_output_log();
 
-        // Path -> RepoList (repos not empty, no suggestion)
-        app.handle_key(KeyCode::Tab, KeyModifiers::NONE);
-        assert_eq!(app.edit_project_field, EditProjectField::RepoList);
+        let session = &app.sessions[0];
+        assert!(!session.info.log_output);
+        assert!(!session.is_logging_output());
+        assert!(session.info.log_path.is_none());
+        assert_eq!(
+            app.status_message.as_ref().unwrap().text,
+            "Output logging stopped"
+        );
+    }
 
-        // RepoList -> Roles
-        app.handle_key(KeyCode::Tab, KeyModifiers::NONE);
-        assert_eq!(app.edit_project_field, EditProjectField::Roles);
+    #[test]
+    fn toggle_output_log_noop_without_sessions() {
+        let mut app = app_with_sessions(0);
+        app.toggle_output_log();
+        assert!(app.status_message.is_none());
+    }
 
-        // Roles -> McpServers
-        app.handle_key(KeyCode::Tab, KeyModifiers::NONE);
-        assert_eq!(app.edit_project_field, EditProjectField::McpServers);
+    #[test]
+    fn tick_opens_log_for_flagged_session() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let _guard = crate::paths::TestPathGuard::new(tmp.path());
+        let mut app = app_with_sessions(2);
+        app.sessions[1].info.log_output = true;
 
-        // McpServers -> Name
-        app.handle_key(KeyCode::Tab, KeyModifiers::NONE);
-        assert_eq!(app.edit_project_field, EditProjectField::Name);
+        app.tick();
+
+        assert!(!app.sessions[0].is_logging_output());
+        assert!(app.sessions[1].is_logging_output());
     }
 
     #[test]
-    fn edit_project_tab_skips_repo_list_when_empty() {
-        let mut app = app_with_project("test", vec![PathBuf::from("/repo")]);
-        app.open_edit_project_modal();
-        app.edit_project_repos.clear();
+    fn output_log_open_failure_clears_flag() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        // A file where the session-logs directory should be
+        std::fs::write(tmp.path().join("session-logs"), b"").unwrap();
+        let _guard = crate::paths::TestPathGuard::new(tmp.path());
+        let mut app = app_with_sessions(1);
 
-        // Name -> Path
-        app.handle_key(KeyCode::Tab, KeyModifiers::NONE);
-        assert_eq!(app.edit_project_field, EditProjectField::Path);
+        app.toggle_output_log();
 
-        // Path -> Roles (skip empty RepoList)
-        app.handle_key(KeyCode::Tab, KeyModifiers::NONE);
-        assert_eq!(app.edit_project_field, EditProjectField::Roles);
+        assert!(!app.sessions[0].info.log_output);
+        assert!(!app.sessions[0].is_logging_output());
+        let msg = app.status_message.as_ref().unwrap();
+        assert_eq!(msg.level, StatusLevel::Error);
+        assert!(msg.text.contains("Failed to open output log"));
     }
 
     #[test]
-    fn edit_project_esc_closes_modal() {
-        let mut app = app_with_project("test", vec![PathBuf::from("/repo")]);
-        app.open_edit_project_modal();
-        app.handle_key(KeyCode::Esc, KeyModifiers::NONE);
-        assert!(!app.show_edit_project_modal);
+    fn output_log_closed_by_reader_is_reported() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let _guard = crate::paths::TestPathGuard::new(tmp.path());
+        let mut app = app_with_sessions(1);
+        app.toggle_output_log();
+        // Simulate the reader loop dropping the log after a write error
+        let path = app.sessions[0].info.log_path.clone().unwrap();
+        app.sessions[0].stop_output_log();
+        app.sessions[0].info.log_path = Some(path);
+
+        app.sync_output_logs();
+
+        assert!(!app.sessions[0].info.log_output);
+        assert!(app.sessions[0].info.log_path.is_none());
+        assert!(app
+            .status_message
+            .as_ref()
+            .unwrap()
+            .text
+            .contains("stopped after a write error"));
     }
 
     #[test]
-    fn edit_project_repo_list_delete() {
-        let mut app = app_with_project(
-            "test",
-            vec![PathBuf::from("/repo/a"), PathBuf::from("/repo/b")],
+    fn busy_to_waiting_edge_notifies_once() {
+        let mut app = app_with_sessions(2);
+        app.sessions[0].info.status = SessionStatus::Busy;
+        app.sessions[1].info.status = SessionStatus::Busy;
+        let mut calls = Vec::new();
+
+        let previous: Vec<SessionStatus> = app.sessions.iter().map(|s| s.info.status).collect();
+        app.sessions[0].info.status = SessionStatus::Waiting;
+        app.sessions[1].info.status = SessionStatus::NeedsInput;
+        app.notify_finished_sessions(&previous, |project, session| {
+            calls.push(format!("{project}/{session}"));
+        });
+
+        // Still waiting on the next tick: no repeat
+        let previous: Vec<SessionStatus> = app.sessions.iter().map(|s| s.info.status).collect();
+        app.notify_finished_sessions(&previous, |project, session| {
+            calls.push(format!("{project}/{session}"));
+        });
+
+        let project = app.projects[0].config.name.clone();
+        assert_eq!(
+            calls,
+            vec![
+                format!("{project}/Session 1"),
+                format!("{project}/Session 2")
+            ]
         );
-        app.open_edit_project_modal();
-        app.edit_project_field = EditProjectField::RepoList;
-        app.edit_project_repo_index = 0;
+    }
 
-        // Delete first repo
-        app.handle_key(KeyCode::Char('d'), KeyModifiers::NONE);
-        assert_eq!(app.edit_project_repos.len(), 1);
-        assert_eq!(app.edit_project_repos[0], PathBuf::from("/repo/b"));
+    #[test]
+    fn status_snapshot_is_published_only_after_changes() {
+        let mut app = app_with_sessions(1);
+        app.status_server = Some(status_server::StatusServer::start(0).unwrap());
+
+        app.tick();
+        assert!(!app.status_snapshot_stale);
+        let first = app.status_server.as_ref().unwrap().published();
+        assert!(first.contains("Session 1"));
+
+        // Nothing changed: the snapshot is left alone
+        app.tick();
+        assert!(!app.status_snapshot_stale);
+
+        app.update(AppMessage::KeyPress(KeyCode::Null, KeyModifiers::NONE));
+        assert!(app.status_snapshot_stale);
+        app.tick();
+        assert!(!app.status_snapshot_stale);
     }
 
     #[test]
-    fn edit_project_repo_list_empty_after_delete_switches_to_path() {
-        let mut app = app_with_project("test", vec![PathBuf::from("/repo/a")]);
-        app.open_edit_project_modal();
-        app.edit_project_field = EditProjectField::RepoList;
+    fn poll_titles_copies_latest_title_into_info() {
+        let mut app = app_with_sessions(2);
+        app.sessions[1].set_title(Some("Running tests"));
+        app.poll_titles();
+        assert_eq!(app.sessions[0].info.title, None);
+        assert_eq!(app.sessions[1].info.title.as_deref(), Some("Running tests"));
 
-        app.handle_key(KeyCode::Char('d'), KeyModifiers::NONE);
-        assert!(app.edit_project_repos.is_empty());
-        assert_eq!(app.edit_project_field, EditProjectField::Path);
+        app.sessions[1].set_title(None);
+        app.poll_titles();
+        assert_eq!(app.sessions[1].info.title, None);
     }
 
     #[test]
-    fn edit_project_id_stable_on_rename() {
-        let mut app = app_with_project("alpha", vec![PathBuf::from("/repo")]);
-        let id_before = app.projects[0].id;
+    fn bell_highlights_session_until_it_fades() {
+        let mut app = app_with_sessions(2);
+        app.sessions[1].ring_bell();
 
-        app.open_edit_project_modal();
-        app.edit_project_name.clear();
-        app.edit_project_name.set("beta");
-        app.submit_edit_project();
+        app.process_bells(|_, _| panic!("bell notifications are off"));
+        assert_eq!(app.sessions[0].info.bell_ticks, 0);
+        assert_eq!(app.sessions[1].info.bell_ticks, BELL_HIGHLIGHT_TICKS);
 
-        assert_eq!(app.projects[0].config.name, "beta");
-        assert_eq!(app.projects[0].id, id_before);
+        for _ in 0..BELL_HIGHLIGHT_TICKS {
+            app.process_bells(|_, _| panic!("no new bell"));
+        }
+        assert_eq!(app.sessions[1].info.bell_ticks, 0);
     }
 
     #[test]
-    fn renamed_project_loads_with_roles_from_db() {
-        // DB has a project that was renamed, with roles stored in project_roles table.
-        let db = test_db();
-        let old_config = ProjectConfig {
-            name: "old-name".to_string(),
-            repos: vec![PathBuf::from("/repo")],
-            roles: Vec::new(),
-            mcp_servers: Vec::new(),
-            id: None,
-        };
-        let det_id = old_config.deterministic_id();
+    fn bell_notifies_once_per_ring_when_enabled() {
+        let mut app = app_with_sessions(2);
+        app.settings.notifications.bell = true;
+        let mut calls = Vec::new();
 
-        // Insert project with old name's ID but renamed
-        db.insert_project(det_id, "renamed-proj", &[PathBuf::from("/repo")])
-            .unwrap();
+        app.sessions[0].ring_bell();
+        app.sessions[0].ring_bell();
+        app.process_bells(|project, session| calls.push(format!("{project}/{session}")));
+        app.process_bells(|project, session| calls.push(format!("{project}/{session}")));
 
-        // Store roles in DB
-        use crate::session::{RoleConfig, RolePermissions};
-        db.replace_roles(
-            det_id,
-            &[RoleConfig {
-                name: "dev".to_string(),
-                description: String::new(),
-                permissions: RolePermissions::default(),
-            }],
-        )
-        .unwrap();
+        let project = app.projects[0].config.name.clone();
+        assert_eq!(calls, vec![format!("{project}/Session 1")]);
+    }
 
-        let projects = load_projects_from_db(&db);
-        let proj = projects.iter().find(|p| p.id == det_id).unwrap();
-        assert_eq!(proj.config.name, "renamed-proj");
-        assert_eq!(proj.config.roles.len(), 1);
-        assert_eq!(proj.config.roles[0].name, "dev");
+    // --- Keymap tests ---
+
+    fn settings_with_keybindings(contents: &str) -> settings::Settings {
+        settings::Settings::parse(contents).unwrap()
     }
 
     #[test]
-    fn rename_project_full_lifecycle() {
-        // Full lifecycle test: create app, rename project, shutdown, create new app (restart).
-        // Verifies no duplicate projects and sessions stay associated.
-        let db = test_db();
-        let backend = stub_backend();
+    fn custom_keymap_roundtrip_fires_bound_action() {
+        let mut app = app_with_sessions(2);
+        app.set_settings(settings_with_keybindings(
+            "[keybindings]\nclose_session = \"ctrl+w\"\nhelp = \"f11\"\n",
+        ));
+        app.focus = InputFocus::SessionList;
 
-        // Step 1: Start app with project "TestA"
-        let config = ProjectConfig {
-            name: "TestA".to_string(),
-            repos: vec![PathBuf::from("/repo")],
-            roles: Vec::new(),
-            mcp_servers: Vec::new(),
-            id: None,
-        };
-        let original_id = config.deterministic_id();
-        let id = config.effective_id();
-        db.insert_project(id, &config.name, &config.repos).unwrap();
-        let mut app = App::new(24, 120, backend.clone(), db);
+        app.handle_key(KeyCode::Char('w'), KeyModifiers::CONTROL);
+        assert_eq!(app.sessions.len(), 1);
 
-        // Verify initial state: 1 project named "TestA"
-        assert_eq!(app.projects.len(), 1);
-        assert_eq!(app.projects[0].config.name, "TestA");
-        assert_eq!(app.projects[0].id, original_id);
+        app.handle_key(KeyCode::F(11), KeyModifiers::NONE);
+        assert!(app.show_help);
+    }
 
-        // Step 2: Create a session for TestA
-        app.active_project_index = app
-            .projects
-            .iter()
-            .position(|p| p.config.name == "TestA")
-            .unwrap();
-        let session = Session::stub("Session1", &backend);
-        let session_id = session.info.id;
-        app.sessions.push(session);
-        app.projects[app.active_project_index]
-            .session_ids
-            .push(session_id);
+    #[test]
+    fn rebound_action_frees_old_key() {
+        let mut app = app_with_sessions(2);
+        app.set_settings(settings_with_keybindings(
+            "[keybindings]\nclose_session = \"ctrl+w\"\n",
+        ));
+        app.focus = InputFocus::SessionList;
+        app.handle_key(KeyCode::Char('c'), KeyModifiers::CONTROL);
+        assert_eq!(app.sessions.len(), 2);
+    }
 
-        // Step 3: Rename "TestA" → "TestB" via edit modal
-        app.open_edit_project_modal();
-        assert!(app.show_edit_project_modal);
-        app.edit_project_name.set("TestB");
-        // Repos stay the same (pre-populated from open_edit_project_modal)
-        app.submit_edit_project();
-        assert!(
-            !app.show_edit_project_modal,
-            "Modal should close on success"
-        );
+    #[test]
+    fn rebound_close_session_in_terminal_closes() {
+        let mut app = app_with_sessions(2);
+        app.set_settings(settings_with_keybindings(
+            "[keybindings]\nclose_session = \"ctrl+w\"\n",
+        ));
+        app.focus = InputFocus::Terminal;
+        app.handle_key(KeyCode::Char('w'), KeyModifiers::CONTROL);
+        assert_eq!(app.sessions.len(), 1);
+    }
 
-        // Verify: project renamed, ID stable
-        let renamed_project = app.projects.iter().find(|p| p.config.name == "TestB");
-        assert!(renamed_project.is_some(), "Should have project TestB");
+    #[test]
+    fn duplicate_keybinding_surfaces_status_error() {
+        let mut app = app_with_sessions(1);
+        app.set_settings(settings_with_keybindings(
+            "[keybindings]\nsync_worktrees = \"ctrl+q\"\n",
+        ));
         assert_eq!(
-            renamed_project.unwrap().id,
-            original_id,
-            "ID should be stable"
-        );
-        assert!(
-            app.projects.iter().all(|p| p.config.name != "TestA"),
-            "TestA should no longer exist"
+            app.status_message.as_ref().map(|m| m.level),
+            Some(StatusLevel::Error)
         );
+        assert_eq!(app.keymap, keymap::KeyMap::default());
 
-        // Step 4: Save state (simulates shutdown)
-        app.save_state();
-
-        // Step 5: Simulate restart with the same DB (project already persisted from edit)
-        let app2 = App::new(24, 120, backend.clone(), app.db);
+        // Defaults still apply
+        app.handle_key(KeyCode::Char('q'), KeyModifiers::CONTROL);
+        assert!(app.show_quit_modal);
+    }
 
-        // Verify: only 1 project, named "TestB"
+    #[test]
+    fn unknown_keybinding_action_surfaces_status_error() {
+        let mut app = app_with_sessions(1);
+        app.set_settings(settings_with_keybindings(
+            "[keybindings]\nteleport = \"ctrl+y\"\n",
+        ));
         assert_eq!(
-            app2.projects.len(),
-            1,
-            "Expected 1 project, got {}: {:?}",
-            app2.projects.len(),
-            app2.projects
-                .iter()
-                .map(|p| &p.config.name)
-                .collect::<Vec<_>>()
+            app.status_message.as_ref().map(|m| m.level),
+            Some(StatusLevel::Error)
         );
-        assert_eq!(app2.projects[0].config.name, "TestB");
-        assert_eq!(app2.projects[0].id, original_id);
+    }
 
-        // Step 6: Restore sessions
-        if let Some((sessions, _counter)) = app2.load_persisted_state_from_db() {
-            // Verify session has correct project_id
-            assert_eq!(sessions.len(), 1);
-            assert_eq!(
-                sessions[0].project_id, original_id,
-                "Session should reference original project ID"
-            );
-        }
-    }
+    // --- Alt+N quick-jump tests ---
 
     #[test]
-    fn rename_project_survives_restart_db_only() {
-        // After a rename, the DB is the single source of truth.
-        // On restart, load_projects_from_db returns the renamed project with stable ID.
-        let db = test_db();
-
-        let original_config = ProjectConfig {
-            name: "TestA".to_string(),
-            repos: vec![PathBuf::from("/repo")],
-            roles: Vec::new(),
-            mcp_servers: Vec::new(),
-            id: None,
-        };
-        let original_id = original_config.deterministic_id();
-        db.insert_project(original_id, "TestA", &[PathBuf::from("/repo")])
-            .unwrap();
+    fn alt_digit_jumps_to_nth_project_session() {
+        let mut app = app_with_sessions(3);
+        app.focus = InputFocus::SessionList;
+        app.handle_key(KeyCode::Char('2'), KeyModifiers::ALT);
+        assert_eq!(app.active_index, app.active_project_sessions()[1]);
+        assert_eq!(app.focus, InputFocus::Terminal);
+    }
 
-        // Rename in DB (as submit_edit_project does)
-        db.update_project(original_id, "TestB", &[PathBuf::from("/repo")])
-            .unwrap();
+    #[test]
+    fn alt_digit_out_of_range_is_noop() {
+        let mut app = app_with_sessions(3);
+        app.focus = InputFocus::SessionList;
+        app.handle_key(KeyCode::Char('9'), KeyModifiers::ALT);
+        assert_eq!(app.active_index, 0);
+        assert_eq!(app.focus, InputFocus::SessionList);
+    }
 
-        // Simulate restart: load from DB only
-        let projects = load_projects_from_db(&db);
+    #[test]
+    fn alt_digit_skips_sessions_of_other_projects() {
+        let mut app = app_with_sessions(3);
+        // Move the first session out of the active project
+        let other = app.projects[0].session_ids.remove(0);
+        let mut second = app.projects[0].clone();
+        second.session_ids = vec![other];
+        app.projects.push(second);
 
-        assert_eq!(projects.len(), 1);
-        assert_eq!(projects[0].config.name, "TestB");
-        assert_eq!(projects[0].id, original_id);
+        app.handle_key(KeyCode::Char('1'), KeyModifiers::ALT);
+        assert_eq!(app.active_index, 1);
     }
 
     #[test]
-    fn rename_project_sessions_survive_restart() {
-        // Simulate: project "TestA" has a session, renamed to "TestB", then restart.
-        // The session should remain associated with the renamed project via stable ID.
-        let db = test_db();
+    fn plain_digit_in_terminal_is_not_a_jump() {
+        let mut app = app_with_sessions(3);
+        app.focus = InputFocus::Terminal;
+        app.handle_key(KeyCode::Char('2'), KeyModifiers::NONE);
+        assert_eq!(app.active_index, 0);
+    }
 
-        // Step 1: Insert original project "TestA" into DB
-        let original_config = ProjectConfig {
-            name: "TestA".to_string(),
-            repos: vec![PathBuf::from("/repo")],
-            roles: Vec::new(),
-            mcp_servers: Vec::new(),
-            id: None,
-        };
-        let original_id = original_config.deterministic_id();
-        db.insert_project(original_id, "TestA", &[PathBuf::from("/repo")])
-            .unwrap();
+    // --- Scrollback position tests ---
 
-        // Step 2: Create a session associated with "TestA"
-        let session_id = SessionId::default();
-        let shared_session = sync::SharedSession {
-            id: session_id,
-            name: "Session 1".to_string(),
-            project_id: original_id,
-            role: "developer".to_string(),
-            backend_id: "thurbox:@0".to_string(),
-            backend_type: "tmux".to_string(),
-            claude_session_id: Some("claude-abc".to_string()),
-            cwd: None,
-            additional_dirs: Vec::new(),
-            worktrees: Vec::new(),
-            shell_backend_id: None,
-            tombstone: false,
-            tombstone_at: None,
-        };
-        db.upsert_session(&shared_session).unwrap();
+    fn app_with_scrollable_sessions(count: usize) -> App {
+        let app = app_with_sessions(count);
+        for session in &app.sessions {
+            let mut parser = vt100::Parser::new(5, 20, 100);
+            let text: Vec<String> = (0..40).map(|i| format!("line {i}")).collect();
+            parser.process(text.join("\r\n").as_bytes());
+            *session.parser.lock().unwrap() = parser;
+        }
+        app
+    }
 
-        // Step 3: Rename in DB (as submit_edit_project does)
-        db.update_project(original_id, "TestB", &[PathBuf::from("/repo")])
-            .unwrap();
+    #[test]
+    fn scrollback_offsets_survive_switch_cycle() {
+        let mut app = app_with_scrollable_sessions(2);
+        app.scroll_terminal_up(3);
+        app.switch_session_forward();
+        assert_eq!(app.active_index, 1);
+        assert_eq!(app.active_scrollback(), 0);
+        app.scroll_terminal_up(5);
 
-        // Step 4: Simulate restart — load from DB only
-        let projects = load_projects_from_db(&db);
-        assert_eq!(projects.len(), 1);
-        assert_eq!(projects[0].id, original_id);
-        assert_eq!(projects[0].config.name, "TestB");
+        // Something resets the inactive parser while it is in the background
+        app.sessions[0]
+            .parser
+            .lock()
+            .unwrap()
+            .screen_mut()
+            .set_scrollback(0);
 
-        // Check session still references the correct project
-        let sessions = db.list_active_sessions().unwrap();
-        assert_eq!(sessions.len(), 1);
-        assert_eq!(sessions[0].project_id, original_id);
+        app.switch_session_backward();
+        assert_eq!(app.active_scrollback(), 3);
+        app.switch_session_forward();
+        assert_eq!(app.active_scrollback(), 5);
     }
 
     #[test]
-    fn session_to_shared_maps_worktree() {
-        let backend = stub_backend();
-        let mut app = App::new(
-            24,
-            120,
-            backend.clone(),
-            test_db_with_project(&test_project_config()),
-        );
+    fn scrollback_offset_restored_across_project_switch() {
+        let mut app = app_with_scrollable_sessions(2);
+        // Move the second session into its own project
+        let moved = app.projects[0].session_ids.remove(1);
+        let mut other = app.projects[0].clone();
+        other.id = ProjectId::default();
+        other.session_ids = vec![moved];
+        app.projects.push(other);
 
-        let mut session = Session::stub("WTSession", &backend);
-        session.info.worktrees = vec![WorktreeInfo {
-            repo_path: PathBuf::from("/repo"),
-            worktree_path: PathBuf::from("/repo/.git/wt/feat"),
-            branch: "feat".to_string(),
-        }];
+        app.scroll_terminal_up(4);
+        app.switch_project_forward();
+        assert_eq!(app.active_index, 1);
+        app.sessions[0]
+            .parser
+            .lock()
+            .unwrap()
+            .screen_mut()
+            .set_scrollback(0);
 
-        let sid = session.info.id;
-        app.sessions.push(session);
-        app.projects[0].session_ids.push(sid);
+        app.switch_project_backward();
+        assert_eq!(app.active_index, 0);
+        assert_eq!(app.active_scrollback(), 4);
+    }
 
-        let shared = app.session_to_shared(&app.sessions[0]);
-        assert_eq!(shared.worktrees.len(), 1);
-        let wt = &shared.worktrees[0];
-        assert_eq!(wt.branch, "feat");
-        assert_eq!(wt.repo_path, PathBuf::from("/repo"));
+    #[test]
+    fn closing_session_forgets_scrollback_offset() {
+        let mut app = app_with_scrollable_sessions(2);
+        app.scroll_terminal_up(2);
+        app.switch_session_forward();
+        let id = app.sessions[0].info.id;
+        assert_eq!(app.scrollback_offsets.get(&id), Some(&2));
+
+        app.switch_session_backward();
+        app.close_active_session();
+        assert!(!app.scrollback_offsets.contains_key(&id));
     }
 
-    // --- Edit-project inline roles tests ---
+    // --- Fuzzy switcher tests ---
 
-    fn app_with_roles(roles: Vec<crate::session::RoleConfig>) -> App {
-        let config = ProjectConfig {
-            name: "test".to_string(),
-            repos: vec![PathBuf::from("/repo")],
-            roles,
-            mcp_servers: vec![],
-            id: None,
-        };
-        App::new(24, 120, stub_backend(), test_db_with_project(&config))
+    /// Projects "Test" (sessions "Session 1", "Session 2") and "Project 2"
+    /// (session "deploy").
+    fn app_for_switcher() -> App {
+        let mut app = app_with_projects(2);
+        let backend = stub_backend();
+        for (name, project) in [("Session 1", 0), ("Session 2", 0), ("deploy", 1)] {
+            let session = Session::stub(name, &backend);
+            app.projects[project].session_ids.push(session.info.id);
+            app.sessions.push(session);
+        }
+        app.active_index = 0;
+        app
     }
 
-    #[test]
-    fn open_edit_project_loads_roles() {
-        use crate::session::{RoleConfig, RolePermissions};
-        let mut app = app_with_roles(vec![RoleConfig {
-            name: "dev".to_string(),
-            description: "Developer".to_string(),
-            permissions: RolePermissions::default(),
-        }]);
-        app.open_edit_project_modal();
-        assert_eq!(app.role_editor_roles.len(), 1);
-        assert_eq!(app.role_editor_roles[0].name, "dev");
-        assert_eq!(app.role_editor_list_index, 0);
+    fn type_text(app: &mut App, text: &str) {
+        for c in text.chars() {
+            app.handle_key(KeyCode::Char(c), KeyModifiers::NONE);
+        }
     }
 
     #[test]
-    fn submit_edit_project_saves_roles() {
-        use crate::session::{RoleConfig, RolePermissions};
-        let mut app = app_with_roles(vec![]);
-        app.open_edit_project_modal();
-        // Add a role to the editor state
-        app.role_editor_roles.push(RoleConfig {
-            name: "new-role".to_string(),
-            description: String::new(),
-            permissions: RolePermissions::default(),
-        });
-        app.submit_edit_project();
-        // Verify the project now has the role
-        let project = app
-            .projects
+    fn ctrl_p_opens_switcher_with_all_projects_and_sessions() {
+        let mut app = app_for_switcher();
+        app.handle_key(KeyCode::Char('p'), KeyModifiers::CONTROL);
+        assert!(app.show_switcher);
+        let labels: Vec<&str> = app
+            .switcher_matches
             .iter()
-            .find(|p| p.config.name == "test")
-            .unwrap();
-        assert_eq!(project.config.roles.len(), 1);
-        assert_eq!(project.config.roles[0].name, "new-role");
+            .map(|&i| app.switcher_entries[i].label.as_str())
+            .collect();
+        assert_eq!(
+            labels,
+            vec![
+                "Test",
+                "Test / Session 1",
+                "Test / Session 2",
+                "Project 2",
+                "Project 2 / deploy",
+            ]
+        );
     }
 
     #[test]
-    fn close_edit_project_clears_role_editor() {
-        use crate::session::{RoleConfig, RolePermissions};
-        let mut app = app_with_roles(vec![RoleConfig {
-            name: "dev".to_string(),
-            description: String::new(),
-            permissions: RolePermissions::default(),
-        }]);
-        app.open_edit_project_modal();
-        app.show_role_editor = true; // Simulate role editor being open
-        app.close_edit_project_modal();
-        assert!(!app.show_edit_project_modal);
-        assert!(!app.show_role_editor);
-        assert!(app.role_editor_roles.is_empty());
-        assert_eq!(app.role_editor_list_index, 0);
+    fn switcher_enter_jumps_to_session_in_other_project() {
+        let mut app = app_for_switcher();
+        app.focus = InputFocus::SessionList;
+        app.open_switcher();
+        type_text(&mut app, "dep");
+        assert_eq!(app.switcher_matches.len(), 1);
+
+        app.handle_key(KeyCode::Enter, KeyModifiers::NONE);
+        assert!(!app.show_switcher);
+        assert_eq!(app.active_project_index, 1);
+        assert_eq!(app.active_index, 2);
+        assert_eq!(app.focus, InputFocus::Terminal);
     }
 
     #[test]
-    fn edit_project_roles_navigate_and_delete() {
-        use crate::session::{RoleConfig, RolePermissions};
-        let mut app = app_with_roles(vec![
-            RoleConfig {
-                name: "a".to_string(),
-                description: String::new(),
-                permissions: RolePermissions::default(),
-            },
-            RoleConfig {
-                name: "b".to_string(),
-                description: String::new(),
-                permissions: RolePermissions::default(),
-            },
-        ]);
-        app.open_edit_project_modal();
-        app.edit_project_field = EditProjectField::Roles;
-        assert_eq!(app.role_editor_list_index, 0);
+    fn switcher_enter_on_project_selects_its_first_session() {
+        let mut app = app_for_switcher();
+        app.open_switcher();
+        type_text(&mut app, "project 2");
+        assert_eq!(
+            app.switcher_entries[app.switcher_matches[0]].target,
+            SwitcherTarget::Project(1)
+        );
 
-        // Navigate down
-        app.handle_key(KeyCode::Char('j'), KeyModifiers::NONE);
-        assert_eq!(app.role_editor_list_index, 1);
+        app.handle_key(KeyCode::Enter, KeyModifiers::NONE);
+        assert_eq!(app.active_project_index, 1);
+        assert_eq!(app.active_index, 2);
+        assert_eq!(app.focus, InputFocus::ProjectList);
+    }
 
-        // Navigate up
-        app.handle_key(KeyCode::Char('k'), KeyModifiers::NONE);
-        assert_eq!(app.role_editor_list_index, 0);
+    #[test]
+    fn switcher_arrows_move_selection_and_typing_resets_it() {
+        let mut app = app_for_switcher();
+        app.open_switcher();
+        type_text(&mut app, "sess");
+        assert_eq!(app.switcher_matches.len(), 2);
 
-        // Delete first role
-        app.handle_key(KeyCode::Char('d'), KeyModifiers::NONE);
-        assert_eq!(app.role_editor_roles.len(), 1);
-        assert_eq!(app.role_editor_roles[0].name, "b");
+        app.handle_key(KeyCode::Down, KeyModifiers::NONE);
+        app.handle_key(KeyCode::Down, KeyModifiers::NONE);
+        assert_eq!(app.switcher_index, 1);
+        app.handle_key(KeyCode::Char('i'), KeyModifiers::NONE);
+        assert_eq!(app.switcher_index, 0);
+
+        app.handle_key(KeyCode::Down, KeyModifiers::NONE);
+        app.handle_key(KeyCode::Enter, KeyModifiers::NONE);
+        assert_eq!(app.active_index, 1);
     }
 
     #[test]
-    fn edit_project_roles_add_opens_role_editor() {
-        let mut app = app_with_roles(vec![]);
-        app.open_edit_project_modal();
-        app.edit_project_field = EditProjectField::Roles;
-        app.handle_key(KeyCode::Char('a'), KeyModifiers::NONE);
-        assert!(app.show_role_editor);
-        assert_eq!(app.role_editor_view, RoleEditorView::Editor);
-        assert!(app.role_editor_editing_index.is_none());
+    fn switcher_esc_closes_without_switching() {
+        let mut app = app_for_switcher();
+        app.open_switcher();
+        type_text(&mut app, "dep");
+        app.handle_key(KeyCode::Esc, KeyModifiers::NONE);
+        assert!(!app.show_switcher);
+        assert_eq!(app.active_project_index, 0);
+        assert_eq!(app.active_index, 0);
+        assert!(app.switcher_entries.is_empty());
     }
 
     #[test]
-    fn edit_project_roles_edit_opens_role_editor() {
-        use crate::session::{RoleConfig, RolePermissions};
-        let mut app = app_with_roles(vec![RoleConfig {
-            name: "dev".to_string(),
-            description: "Developer".to_string(),
-            permissions: RolePermissions::default(),
-        }]);
-        app.open_edit_project_modal();
-        app.edit_project_field = EditProjectField::Roles;
-        app.handle_key(KeyCode::Char('e'), KeyModifiers::NONE);
-        assert!(app.show_role_editor);
-        assert_eq!(app.role_editor_view, RoleEditorView::Editor);
-        assert_eq!(app.role_editor_editing_index, Some(0));
-        assert_eq!(app.role_editor_name.value(), "dev");
+    fn switcher_enter_with_no_matches_just_closes() {
+        let mut app = app_for_switcher();
+        app.open_switcher();
+        type_text(&mut app, "zzz");
+        assert!(app.switcher_matches.is_empty());
+        app.handle_key(KeyCode::Enter, KeyModifiers::NONE);
+        assert!(!app.show_switcher);
+        assert_eq!(app.active_index, 0);
+    }
+
+    // --- Context-sensitive Ctrl+J/K tests ---
+
+    fn app_with_projects(count: usize) -> App {
+        let mut app = app_with_sessions(0);
+        // app already has one default project at index 0
+        for i in 1..count {
+            app.projects.push(ProjectInfo {
+                id: ProjectId::default(),
+                config: ProjectConfig {
+                    name: format!("Project {}", i + 1),
+                    repos: vec![],
+                    roles: vec![],
+                    mcp_servers: vec![],
+                    sync_base_branch: None,
+                    remote: None,
+                    sync_strategy: SyncStrategy::default(),
+                    activity_timeout_ms: None,
+                    worktree_root: None,
+                    default_role: None,
+                    session_init_command: None,
+                    id: None,
+                },
+                session_ids: vec![],
+                is_admin: false,
+            });
+        }
+        app
+    }
+
+    fn click(app: &mut App, x: u16, y: u16) {
+        app.update(AppMessage::MouseClick {
+            x,
+            y,
+            modifiers: KeyModifiers::NONE,
+        });
     }
 
     #[test]
-    fn edit_project_roles_esc_saves_and_closes() {
-        use crate::session::{RoleConfig, RolePermissions};
-        let mut app = app_with_roles(vec![]);
-        app.open_edit_project_modal();
-        app.edit_project_field = EditProjectField::Roles;
-        // Add a role directly to the editor state
-        app.role_editor_roles.push(RoleConfig {
-            name: "added".to_string(),
-            description: String::new(),
-            permissions: RolePermissions::default(),
+    fn clicking_left_panel_selects_project_or_session() {
+        let mut app = app_with_sessions(3);
+        app.projects.push(ProjectInfo {
+            id: ProjectId::default(),
+            config: ProjectConfig {
+                name: "Other".to_string(),
+                id: None,
+                ..app.projects[0].config.clone()
+            },
+            session_ids: vec![],
+            is_admin: false,
         });
-        // Esc from Roles field triggers submit_edit_project (saves)
-        app.handle_key(KeyCode::Esc, KeyModifiers::NONE);
-        assert!(!app.show_edit_project_modal);
-        let project = app
+        app.focus = InputFocus::Terminal;
+        *app.left_panel_regions.borrow_mut() = vec![
+            project_list::ListRegion {
+                inner: Rect::new(1, 2, 20, 4),
+                item_height: 2,
+                offset: 0,
+                items: vec![
+                    project_list::PanelItem::Project(0),
+                    project_list::PanelItem::Project(1),
+                ],
+            },
+            project_list::ListRegion {
+                inner: Rect::new(1, 8, 20, 6),
+                item_height: 2,
+                offset: 0,
+                items: (0..3).map(project_list::PanelItem::Session).collect(),
+            },
+        ];
+
+        click(&mut app, 5, 11);
+        assert_eq!(app.active_index, 1);
+        assert_eq!(app.focus, InputFocus::SessionList);
+
+        click(&mut app, 5, 4);
+        assert_eq!(app.active_project_index, 1);
+        assert_eq!(app.focus, InputFocus::ProjectList);
+
+        // Border rows are no-ops
+        click(&mut app, 5, 7);
+        assert_eq!(app.active_project_index, 1);
+        assert_eq!(app.focus, InputFocus::ProjectList);
+    }
+
+    #[test]
+    fn ctrl_j_moves_project_forward_when_project_list_focused() {
+        let mut app = app_with_projects(3);
+        app.focus = InputFocus::ProjectList;
+        app.active_project_index = 0;
+        app.handle_key(KeyCode::Char('j'), KeyModifiers::CONTROL);
+        assert_eq!(app.active_project_index, 1);
+    }
+
+    #[test]
+    fn ctrl_k_moves_project_backward_when_project_list_focused() {
+        let mut app = app_with_projects(3);
+        app.focus = InputFocus::ProjectList;
+        app.active_project_index = 2;
+        app.handle_key(KeyCode::Char('k'), KeyModifiers::CONTROL);
+        assert_eq!(app.active_project_index, 1);
+    }
+
+    #[test]
+    fn ctrl_j_switches_session_when_session_list_focused() {
+        let mut app = app_with_sessions(3);
+        app.focus = InputFocus::SessionList;
+        app.active_index = 0;
+        app.handle_key(KeyCode::Char('j'), KeyModifiers::CONTROL);
+        assert_eq!(app.active_index, 1);
+    }
+
+    #[test]
+    fn ctrl_j_switches_session_when_terminal_focused() {
+        let mut app = app_with_sessions(3);
+        app.focus = InputFocus::Terminal;
+        app.active_index = 0;
+        app.handle_key(KeyCode::Char('j'), KeyModifiers::CONTROL);
+        assert_eq!(app.active_index, 1);
+    }
+
+    #[test]
+    fn ctrl_j_at_last_project_wraps_to_first() {
+        let mut app = app_with_projects(3);
+        app.focus = InputFocus::ProjectList;
+        app.active_project_index = 2;
+        app.handle_key(KeyCode::Char('j'), KeyModifiers::CONTROL);
+        assert_eq!(app.active_project_index, 0);
+    }
+
+    #[test]
+    fn ctrl_k_at_first_project_wraps_to_last() {
+        let mut app = app_with_projects(3);
+        app.focus = InputFocus::ProjectList;
+        app.active_project_index = 0;
+        app.handle_key(KeyCode::Char('k'), KeyModifiers::CONTROL);
+        assert_eq!(app.active_project_index, 2);
+    }
+
+    // --- Project filter tests ---
+
+    fn app_with_named_projects(names: &[&str]) -> App {
+        let mut app = app_with_projects(names.len());
+        for (project, name) in app.projects.iter_mut().zip(names) {
+            project.config.name = name.to_string();
+        }
+        app.focus = InputFocus::ProjectList;
+        app
+    }
+
+    fn type_keys(app: &mut App, text: &str) {
+        for c in text.chars() {
+            app.handle_key(KeyCode::Char(c), KeyModifiers::NONE);
+        }
+    }
+
+    #[test]
+    fn project_filter_matches_name_substring_ignoring_case() {
+        assert!(project_matches_filter("api-gateway", "GATE"));
+        assert!(project_matches_filter("My API", "api"));
+        assert!(project_matches_filter("web", ""));
+        assert!(!project_matches_filter("web", "api"));
+    }
+
+    #[test]
+    fn project_filter_narrows_navigation_without_reordering() {
+        let mut app = app_with_named_projects(&["api", "web", "api-gateway", "docs"]);
+        app.active_project_index = 1;
+
+        app.handle_key(KeyCode::Char('/'), KeyModifiers::NONE);
+        type_keys(&mut app, "API");
+        // "web" no longer matches, so the first match becomes active
+        assert_eq!(app.active_project_index, 0);
+        assert_eq!(app.visible_projects(), vec![0, 2]);
+
+        app.handle_key(KeyCode::Down, KeyModifiers::NONE);
+        assert_eq!(app.active_project_index, 2);
+        app.handle_key(KeyCode::Down, KeyModifiers::NONE);
+        assert_eq!(app.active_project_index, 0);
+
+        // Enter keeps the filter; j/k navigate the filtered list
+        app.handle_key(KeyCode::Enter, KeyModifiers::NONE);
+        assert!(!app.project_filter.as_ref().unwrap().editing);
+        app.handle_key(KeyCode::Char('k'), KeyModifiers::NONE);
+        assert_eq!(app.active_project_index, 2);
+        app.handle_key(KeyCode::Char('j'), KeyModifiers::CONTROL);
+        assert_eq!(app.active_project_index, 0);
+
+        let names: Vec<&str> = app
             .projects
             .iter()
-            .find(|p| p.config.name == "test")
-            .unwrap();
-        assert_eq!(project.config.roles.len(), 1);
-        assert_eq!(project.config.roles[0].name, "added");
+            .map(|p| p.config.name.as_str())
+            .collect();
+        assert_eq!(names, ["api", "web", "api-gateway", "docs"]);
+
+        app.handle_key(KeyCode::Esc, KeyModifiers::NONE);
+        assert!(app.project_filter.is_none());
+        assert_eq!(app.visible_projects(), vec![0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn project_filter_without_matches_keeps_active_project() {
+        let mut app = app_with_named_projects(&["api", "web", "docs"]);
+        app.active_project_index = 1;
+
+        app.handle_key(KeyCode::Char('/'), KeyModifiers::NONE);
+        type_keys(&mut app, "zzz");
+        assert_eq!(app.active_project_index, 1);
+        assert_eq!(app.visible_projects(), vec![1]);
+
+        // Typing a query that matches again moves to the first match
+        for _ in 0..3 {
+            app.handle_key(KeyCode::Backspace, KeyModifiers::NONE);
+        }
+        type_keys(&mut app, "do");
+        assert_eq!(app.active_project_index, 2);
+
+        // An empty query clears the filter on Enter
+        app.handle_key(KeyCode::Backspace, KeyModifiers::NONE);
+        app.handle_key(KeyCode::Backspace, KeyModifiers::NONE);
+        app.handle_key(KeyCode::Enter, KeyModifiers::NONE);
+        assert!(app.project_filter.is_none());
+    }
+
+    // --- Session filter tests ---
+
+    fn app_with_statuses(statuses: &[SessionStatus]) -> App {
+        let mut app = app_with_sessions(statuses.len());
+        for (session, &status) in app.sessions.iter_mut().zip(statuses) {
+            session.info.status = status;
+        }
+        app.focus = InputFocus::SessionList;
+        app.active_index = 0;
+        app
+    }
+
+    #[test]
+    fn ctrl_f_cycles_session_filter() {
+        let mut app = app_with_sessions(1);
+        assert_eq!(app.session_filter, SessionFilter::All);
+        app.handle_key(KeyCode::Char('f'), KeyModifiers::CONTROL);
+        assert_eq!(app.session_filter, SessionFilter::Waiting);
+        app.handle_key(KeyCode::Char('f'), KeyModifiers::CONTROL);
+        assert_eq!(app.session_filter, SessionFilter::Busy);
+        app.handle_key(KeyCode::Char('f'), KeyModifiers::CONTROL);
+        assert_eq!(app.session_filter, SessionFilter::All);
+    }
+
+    #[test]
+    fn waiting_filter_skips_busy_sessions_when_navigating() {
+        use SessionStatus::*;
+        let mut app = app_with_statuses(&[Waiting, Busy, NeedsInput, Busy]);
+        app.session_filter = SessionFilter::Waiting;
+
+        assert_eq!(app.active_project_sessions(), vec![0, 2]);
+        app.handle_key(KeyCode::Char('j'), KeyModifiers::CONTROL);
+        assert_eq!(app.active_index, 2);
+        app.handle_key(KeyCode::Char('j'), KeyModifiers::CONTROL);
+        assert_eq!(app.active_index, 2);
+        app.handle_key(KeyCode::Char('k'), KeyModifiers::CONTROL);
+        assert_eq!(app.active_index, 0);
+        assert_eq!(app.sessions.len(), 4);
+    }
+
+    #[test]
+    fn filter_keeps_active_session_listed() {
+        use SessionStatus::*;
+        let mut app = app_with_statuses(&[Busy, Waiting, Busy]);
+        app.session_filter = SessionFilter::Waiting;
+
+        assert_eq!(app.active_project_sessions(), vec![0, 1]);
+        app.handle_key(KeyCode::Char('j'), KeyModifiers::CONTROL);
+        assert_eq!(app.active_index, 1);
+        assert_eq!(app.active_project_sessions(), vec![1]);
+    }
+
+    // --- DB persistence tests ---
+
+    #[test]
+    fn load_persisted_state_empty_db_returns_none() {
+        let app = App::new(24, 80, stub_backend(), test_db());
+        assert!(app.load_persisted_state_from_db().is_none());
+    }
+
+    #[test]
+    fn load_persisted_state_sessions_without_claude_id_returns_none() {
+        let db = test_db();
+        let proj_config = ProjectConfig {
+            name: "test".to_string(),
+            repos: vec![],
+            roles: Vec::new(),
+            mcp_servers: Vec::new(),
+            sync_base_branch: None,
+            remote: None,
+            sync_strategy: SyncStrategy::default(),
+            activity_timeout_ms: None,
+            worktree_root: None,
+            default_role: None,
+            session_init_command: None,
+            id: None,
+        };
+        let pid = proj_config.deterministic_id();
+        db.insert_project(pid, "test", &[]).unwrap();
+
+        // Session without claude_session_id — not resumable
+        let session = sync::SharedSession {
+            id: SessionId::default(),
+            name: "1".to_string(),
+            project_id: pid,
+            role: "developer".to_string(),
+            backend_id: "thurbox:@0".to_string(),
+            backend_type: "tmux".to_string(),
+            claude_session_id: None,
+            cwd: None,
+            additional_dirs: Vec::new(),
+            worktrees: Vec::new(),
+            shell_backend_id: None,
+            is_pinned: false,
+            label: None,
+            label_color: None,
+            created_at: None,
+            tombstone: false,
+            tombstone_at: None,
+        };
+        db.upsert_session(&session).unwrap();
+
+        let app = App::new(24, 80, stub_backend(), db);
+        assert!(app.load_persisted_state_from_db().is_none());
     }
 
-    #[test]
-    fn ctrl_r_no_op_without_claude_session_id() {
-        let mut app = app_with_sessions(1);
-        // Session exists but has no claude_session_id
-        app.sessions[0].info.claude_session_id = None;
-        app.focus = InputFocus::Terminal;
-        app.handle_key(KeyCode::Char('r'), KeyModifiers::CONTROL);
-        // Should be a no-op (no error, no crash)
-        assert!(app.status_message.is_none());
+    #[test]
+    fn load_persisted_state_filters_to_resumable_only() {
+        let db = test_db();
+        let proj_config = ProjectConfig {
+            name: "test".to_string(),
+            repos: vec![],
+            roles: Vec::new(),
+            mcp_servers: Vec::new(),
+            sync_base_branch: None,
+            remote: None,
+            sync_strategy: SyncStrategy::default(),
+            activity_timeout_ms: None,
+            worktree_root: None,
+            default_role: None,
+            session_init_command: None,
+            id: None,
+        };
+        let pid = proj_config.deterministic_id();
+        db.insert_project(pid, "test", &[]).unwrap();
+
+        // Non-resumable session
+        let s1 = sync::SharedSession {
+            id: SessionId::default(),
+            name: "1".to_string(),
+            project_id: pid,
+            role: "developer".to_string(),
+            backend_id: "thurbox:@0".to_string(),
+            backend_type: "tmux".to_string(),
+            claude_session_id: None,
+            cwd: None,
+            additional_dirs: Vec::new(),
+            worktrees: Vec::new(),
+            shell_backend_id: None,
+            is_pinned: false,
+            label: None,
+            label_color: None,
+            created_at: None,
+            tombstone: false,
+            tombstone_at: None,
+        };
+        db.upsert_session(&s1).unwrap();
+
+        // Resumable session
+        let s2 = sync::SharedSession {
+            id: SessionId::default(),
+            name: "2".to_string(),
+            project_id: pid,
+            role: "developer".to_string(),
+            backend_id: "thurbox:@1".to_string(),
+            backend_type: "tmux".to_string(),
+            claude_session_id: Some("claude-abc".to_string()),
+            cwd: None,
+            additional_dirs: Vec::new(),
+            worktrees: Vec::new(),
+            shell_backend_id: None,
+            is_pinned: false,
+            label: None,
+            label_color: None,
+            created_at: None,
+            tombstone: false,
+            tombstone_at: None,
+        };
+        db.upsert_session(&s2).unwrap();
+        db.set_session_counter(7).unwrap();
+
+        let app = App::new(24, 80, stub_backend(), db);
+        let (sessions, counter) = app.load_persisted_state_from_db().unwrap();
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(sessions[0].name, "2");
+        assert_eq!(counter, 7);
+    }
+
+    #[test]
+    fn save_state_roundtrips_sessions() {
+        let backend = stub_backend();
+        let mut app = App::new(
+            24,
+            120,
+            backend.clone(),
+            test_db_with_project(&test_project_config()),
+        );
+
+        // Add a session
+        let session = Session::stub("Session 1", &backend);
+        let sid = session.info.id;
+        app.sessions.push(session);
+        app.projects[0].session_ids.push(sid);
+
+        // Save to DB (only persists sessions + counter, not projects)
+        app.save_state();
+
+        // Verify session in DB
+        let sessions = app.db.list_active_sessions().unwrap();
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(sessions[0].name, "Session 1");
+    }
+
+    #[test]
+    fn save_state_persists_session_counter() {
+        let backend = stub_backend();
+        let mut app = App::new(24, 120, backend.clone(), test_db());
+        app.session_counter = 42;
+
+        app.save_state();
+
+        let counter = app.db.get_session_counter().unwrap();
+        assert_eq!(counter, 42);
+    }
+
+    #[test]
+    fn session_to_shared_converts_correctly() {
+        let backend = stub_backend();
+        let mut app = App::new(
+            24,
+            120,
+            backend.clone(),
+            test_db_with_project(&test_project_config()),
+        );
+
+        let mut session = Session::stub("TestSession", &backend);
+        session.info.role = "reviewer".to_string();
+        session.info.cwd = Some(PathBuf::from("/home/user"));
+        session.info.claude_session_id = Some("claude-xyz".to_string());
+
+        let sid = session.info.id;
+        app.sessions.push(session);
+        app.projects[0].session_ids.push(sid);
+
+        let shared = app.session_to_shared(&app.sessions[0]);
+        assert_eq!(shared.id, sid);
+        assert_eq!(shared.name, "TestSession");
+        assert_eq!(shared.role, "reviewer");
+        assert_eq!(shared.cwd, Some(PathBuf::from("/home/user")));
+        assert_eq!(shared.claude_session_id, Some("claude-xyz".to_string()));
+        assert!(!shared.tombstone);
+        assert!(shared.tombstone_at.is_none());
+    }
+
+    #[test]
+    fn render_prompt_template_substitutes_placeholders() {
+        let vars = [("branch", "feat"), ("base", "main"), ("remote", "origin")];
+        assert_eq!(
+            render_prompt_template("Rebase {branch} onto {remote}/{base}", &vars),
+            "Rebase feat onto origin/main"
+        );
+        assert_eq!(render_prompt_template("{base}{base}", &vars), "mainmain");
+    }
+
+    #[test]
+    fn render_prompt_template_passes_unknown_placeholders_through() {
+        let vars = [("base", "{branch}")];
+        assert_eq!(
+            render_prompt_template("{unknown} on {base} {", &vars),
+            "{unknown} on {branch} {"
+        );
+        assert_eq!(render_prompt_template("fn() {}", &vars), "fn() {}");
+        assert_eq!(render_prompt_template("{{base}}", &vars), "{{branch}}");
+    }
+
+    #[test]
+    fn deferred_input_delay_adapts_to_recent_output() {
+        // Quiet sessions get the configured delay.
+        assert_eq!(deferred_input_delay_ticks(100, 5_000), 10);
+        assert_eq!(deferred_input_delay_ticks(100, DEFERRED_INPUT_BUSY_MS), 10);
+        // Busy ones wait longer the more recently they printed.
+        assert_eq!(deferred_input_delay_ticks(100, 500), 20);
+        assert_eq!(deferred_input_delay_ticks(100, 0), 30);
+        assert!(deferred_input_delay_ticks(100, 100) > deferred_input_delay_ticks(100, 900));
+        // Never zero, and no overflow on huge settings.
+        assert_eq!(deferred_input_delay_ticks(0, 0), 1);
+        assert!(deferred_input_delay_ticks(u64::MAX, 0) > 0);
+    }
+
+    #[test]
+    fn copy_path_prefers_worktree_over_cwd() {
+        let mut info = SessionInfo::new("s".to_string());
+        assert_eq!(copy_path(&info), None);
+
+        info.cwd = Some(PathBuf::from("/repo"));
+        assert_eq!(copy_path(&info), Some(Path::new("/repo")));
+
+        info.worktrees = vec![WorktreeInfo {
+            repo_path: PathBuf::from("/repo"),
+            worktree_path: PathBuf::from("/repo/.git/wt/feat"),
+            branch: "feat".to_string(),
+            base_branch: "main".to_string(),
+        }];
+        assert_eq!(copy_path(&info), Some(Path::new("/repo/.git/wt/feat")));
+    }
+
+    #[test]
+    fn ctrl_y_without_directory_reports_status() {
+        let mut app = app_with_sessions(1);
+        app.focus = InputFocus::SessionList;
+
+        app.handle_key(KeyCode::Char('y'), KeyModifiers::CONTROL);
+
+        assert_eq!(
+            app.status_message.as_ref().unwrap().text,
+            "Session has no working directory"
+        );
+    }
+
+    #[test]
+    fn worktree_base_branch_survives_persist_and_restore() {
+        let backend = stub_backend();
+        let mut app = App::new(
+            24,
+            120,
+            backend.clone(),
+            test_db_with_project(&test_project_config()),
+        );
+
+        let mut session = Session::stub("TestSession", &backend);
+        session.info.worktrees = vec![WorktreeInfo {
+            repo_path: PathBuf::from("/repo"),
+            worktree_path: PathBuf::from("/repo/.git/wt/feat"),
+            branch: "feat".to_string(),
+            base_branch: "release/2.0".to_string(),
+        }];
+        let sid = session.info.id;
+        app.sessions.push(session);
+        app.projects[0].session_ids.push(sid);
+
+        let shared = app.session_to_shared(&app.sessions[0]);
+        assert_eq!(shared.worktrees[0].base_branch, "release/2.0");
+
+        app.db.upsert_session(&shared).unwrap();
+        let loaded = app.db.list_active_sessions().unwrap();
+        let stored = loaded.iter().find(|s| s.id == sid).unwrap();
+        let restored: WorktreeInfo = stored.worktrees[0].clone().into();
+        assert_eq!(restored.base_branch, "release/2.0");
+    }
+
+    // --- Edit-project modal tests ---
+
+    /// Create an App with a single project for edit-project tests.
+    fn app_with_project(name: &str, repos: Vec<PathBuf>) -> App {
+        let config = ProjectConfig {
+            name: name.to_string(),
+            repos,
+            roles: Vec::new(),
+            mcp_servers: Vec::new(),
+            sync_base_branch: None,
+            remote: None,
+            sync_strategy: SyncStrategy::default(),
+            activity_timeout_ms: None,
+            worktree_root: None,
+            default_role: None,
+            session_init_command: None,
+            id: None,
+        };
+        App::new(24, 120, stub_backend(), test_db_with_project(&config))
+    }
+
+    /// Project with one repo and `running` user sessions, limited to
+    /// `max_sessions`.
+    fn app_near_session_limit(running: usize, max_sessions: usize) -> App {
+        let mut app = app_with_project("test", vec![PathBuf::from("/repo")]);
+        app.settings.limits.max_sessions = max_sessions;
+        app.focus = InputFocus::SessionList;
+        for i in 0..running {
+            let session = Session::stub(&format!("s{i}"), &app.backend);
+            app.projects[0].session_ids.push(session.info.id);
+            app.sessions.push(session);
+        }
+        app
+    }
+
+    #[test]
+    fn ctrl_n_below_session_limit_spawns_directly() {
+        let mut app = app_near_session_limit(1, 2);
+        app.handle_key(KeyCode::Char('n'), KeyModifiers::CONTROL);
+        assert!(app.session_limit_prompt.is_none());
+        assert!(app.show_session_mode_modal);
+    }
+
+    #[test]
+    fn ctrl_n_at_session_limit_asks_first() {
+        let mut app = app_near_session_limit(2, 2);
+        app.handle_key(KeyCode::Char('n'), KeyModifiers::CONTROL);
+        assert_eq!(app.session_limit_prompt, Some(LimitedSpawn::New));
+        assert!(!app.show_session_mode_modal);
+
+        app.handle_key(KeyCode::Enter, KeyModifiers::NONE);
+        assert!(app.session_limit_prompt.is_none());
+        assert!(app.show_session_mode_modal);
+    }
+
+    #[test]
+    fn session_limit_esc_cancels_spawn() {
+        let mut app = app_near_session_limit(3, 2);
+        app.handle_key(KeyCode::Char('n'), KeyModifiers::CONTROL);
+        assert!(app.session_limit_prompt.is_some());
+
+        app.handle_key(KeyCode::Esc, KeyModifiers::NONE);
+        assert!(app.session_limit_prompt.is_none());
+        assert!(!app.show_session_mode_modal);
+    }
+
+    #[test]
+    fn zero_session_limit_never_asks() {
+        let mut app = app_near_session_limit(5, 0);
+        app.handle_key(KeyCode::Char('n'), KeyModifiers::CONTROL);
+        assert!(app.session_limit_prompt.is_none());
+        assert!(app.show_session_mode_modal);
+    }
+
+    #[test]
+    fn open_edit_project_populates_fields() {
+        let mut app = app_with_project("my-proj", vec![PathBuf::from("/repo/a")]);
+        app.open_edit_project_modal();
+        assert!(app.show_edit_project_modal);
+        assert_eq!(app.edit_project_name.value(), "my-proj");
+        assert_eq!(app.edit_project_repos, vec![PathBuf::from("/repo/a")]);
+        assert_eq!(app.edit_project_field, EditProjectField::Name);
+        assert!(app.edit_project_original_id.is_some());
+    }
+
+    #[test]
+    fn submit_edit_project_updates_name_and_repos() {
+        let mut app = app_with_project("old-name", vec![PathBuf::from("/repo/a")]);
+        let original_id = app.projects[0].id;
+
+        app.open_edit_project_modal();
+        app.edit_project_name.clear();
+        app.edit_project_name.set("new-name");
+        app.edit_project_repos = vec![PathBuf::from("/repo/b"), PathBuf::from("/repo/c")];
+        app.submit_edit_project();
+
+        assert!(!app.show_edit_project_modal);
+        assert_eq!(app.projects[0].config.name, "new-name");
+        assert_eq!(app.projects[0].config.repos.len(), 2);
+        // ID must stay stable (no UUID regeneration)
+        assert_eq!(app.projects[0].id, original_id);
+    }
+
+    #[test]
+    fn submit_edit_project_rejects_empty_name() {
+        let mut app = app_with_project("test", vec![PathBuf::from("/repo")]);
+        app.open_edit_project_modal();
+        app.edit_project_name.clear();
+        app.submit_edit_project();
+
+        // Modal should still be open
+        assert!(app.show_edit_project_modal);
+        assert!(app.status_message.is_some());
+    }
+
+    #[test]
+    fn submit_edit_project_rejects_empty_repos() {
+        let mut app = app_with_project("test", vec![PathBuf::from("/repo")]);
+        app.open_edit_project_modal();
+        app.edit_project_repos.clear();
+        app.submit_edit_project();
+
+        assert!(app.show_edit_project_modal);
+        assert!(app.status_message.is_some());
+    }
+
+    #[test]
+    fn submit_edit_project_auto_adds_pending_path() {
+        let mut app = app_with_project("test", vec![PathBuf::from("/repo/a")]);
+        app.open_edit_project_modal();
+        app.edit_project_path.set("/repo/b");
+        app.submit_edit_project();
+
+        assert!(!app.show_edit_project_modal);
+        assert_eq!(app.projects[0].config.repos.len(), 2);
+        assert_eq!(app.projects[0].config.repos[1], PathBuf::from("/repo/b"));
+    }
+
+    #[test]
+    fn close_edit_project_clears_all_state() {
+        let mut app = app_with_project("test", vec![PathBuf::from("/repo")]);
+        app.open_edit_project_modal();
+        assert!(app.show_edit_project_modal);
+
+        app.close_edit_project_modal();
+        assert!(!app.show_edit_project_modal);
+        assert_eq!(app.edit_project_name.value(), "");
+        assert!(app.edit_project_repos.is_empty());
+        assert!(app.edit_project_original_id.is_none());
+    }
+
+    #[test]
+    fn edit_project_tab_cycles_through_all_fields() {
+        let mut app = app_with_project("test", vec![PathBuf::from("/repo")]);
+        app.open_edit_project_modal();
+
+        // Name -> Path
+        assert_eq!(app.edit_project_field, EditProjectField::Name);
+        app.handle_key(KeyCode::Tab, KeyModifiers::NONE);
+        assert_eq!(app.edit_project_field, EditProjectField::Path);
+
+        // Path -> RepoList (repos not empty, no suggestion)
+        app.handle_key(KeyCode::Tab, KeyModifiers::NONE);
+        assert_eq!(app.edit_project_field, EditProjectField::RepoList);
+
+        // RepoList -> Roles
+        app.handle_key(KeyCode::Tab, KeyModifiers::NONE);
+        assert_eq!(app.edit_project_field, EditProjectField::Roles);
+
+        // Roles -> McpServers
+        app.handle_key(KeyCode::Tab, KeyModifiers::NONE);
+        assert_eq!(app.edit_project_field, EditProjectField::McpServers);
+
+        // McpServers -> SyncStrategy
+        app.handle_key(KeyCode::Tab, KeyModifiers::NONE);
+        assert_eq!(app.edit_project_field, EditProjectField::SyncStrategy);
+
+        // SyncStrategy -> ActivityTimeout
+        app.handle_key(KeyCode::Tab, KeyModifiers::NONE);
+        assert_eq!(app.edit_project_field, EditProjectField::ActivityTimeout);
+
+        // ActivityTimeout -> WorktreeRoot
+        app.handle_key(KeyCode::Tab, KeyModifiers::NONE);
+        assert_eq!(app.edit_project_field, EditProjectField::WorktreeRoot);
+
+        // WorktreeRoot -> InitCommand
+        app.handle_key(KeyCode::Tab, KeyModifiers::NONE);
+        assert_eq!(app.edit_project_field, EditProjectField::InitCommand);
+
+        // InitCommand -> Name
+        app.handle_key(KeyCode::Tab, KeyModifiers::NONE);
+        assert_eq!(app.edit_project_field, EditProjectField::Name);
+    }
+
+    #[test]
+    fn edit_project_tab_skips_repo_list_when_empty() {
+        let mut app = app_with_project("test", vec![PathBuf::from("/repo")]);
+        app.open_edit_project_modal();
+        app.edit_project_repos.clear();
+
+        // Name -> Path
+        app.handle_key(KeyCode::Tab, KeyModifiers::NONE);
+        assert_eq!(app.edit_project_field, EditProjectField::Path);
+
+        // Path -> Roles (skip empty RepoList)
+        app.handle_key(KeyCode::Tab, KeyModifiers::NONE);
+        assert_eq!(app.edit_project_field, EditProjectField::Roles);
+    }
+
+    #[test]
+    fn edit_project_sync_strategy_cycles_and_saves() {
+        let mut app = app_with_project("test", vec![PathBuf::from("/repo")]);
+        app.open_edit_project_modal();
+        assert_eq!(app.edit_project_sync_strategy, SyncStrategy::Rebase);
+
+        app.edit_project_field = EditProjectField::SyncStrategy;
+        app.handle_key(KeyCode::Right, KeyModifiers::NONE);
+        assert_eq!(app.edit_project_sync_strategy, SyncStrategy::Merge);
+        app.handle_key(KeyCode::Right, KeyModifiers::NONE);
+        assert_eq!(
+            app.edit_project_sync_strategy,
+            SyncStrategy::FastForwardOnly
+        );
+        app.handle_key(KeyCode::Right, KeyModifiers::NONE);
+        assert_eq!(app.edit_project_sync_strategy, SyncStrategy::Rebase);
+        app.handle_key(KeyCode::Left, KeyModifiers::NONE);
+        assert_eq!(
+            app.edit_project_sync_strategy,
+            SyncStrategy::FastForwardOnly
+        );
+
+        app.handle_key(KeyCode::Enter, KeyModifiers::NONE);
+        assert!(!app.show_edit_project_modal);
+        let project = app.active_project().unwrap();
+        assert_eq!(project.config.sync_strategy, SyncStrategy::FastForwardOnly);
+
+        let stored = app.db.list_active_projects().unwrap();
+        let stored = stored.iter().find(|p| p.id == project.id).unwrap();
+        assert_eq!(stored.sync_strategy, SyncStrategy::FastForwardOnly);
+    }
+
+    #[test]
+    fn edit_project_sync_strategy_esc_discards() {
+        let mut app = app_with_project("test", vec![PathBuf::from("/repo")]);
+        app.open_edit_project_modal();
+        app.edit_project_field = EditProjectField::SyncStrategy;
+        app.handle_key(KeyCode::Right, KeyModifiers::NONE);
+        app.handle_key(KeyCode::Esc, KeyModifiers::NONE);
+
+        assert!(!app.show_edit_project_modal);
+        let project = app.active_project().unwrap();
+        assert_eq!(project.config.sync_strategy, SyncStrategy::Rebase);
+    }
+
+    #[test]
+    fn edit_project_activity_timeout_saves() {
+        let mut app = app_with_project("test", vec![PathBuf::from("/repo")]);
+        app.open_edit_project_modal();
+        assert_eq!(app.edit_project_activity_timeout.value(), "");
+
+        app.edit_project_field = EditProjectField::ActivityTimeout;
+        for c in "5x00".chars() {
+            app.handle_key(KeyCode::Char(c), KeyModifiers::NONE);
+        }
+        // Non-digits are ignored
+        assert_eq!(app.edit_project_activity_timeout.value(), "500");
+        app.handle_key(KeyCode::Enter, KeyModifiers::NONE);
+
+        assert!(!app.show_edit_project_modal);
+        let project = app.active_project().unwrap();
+        assert_eq!(project.config.activity_timeout_ms, Some(500));
+        let stored = app.db.list_active_projects().unwrap();
+        let stored = stored.iter().find(|p| p.id == project.id).unwrap();
+        assert_eq!(stored.activity_timeout_ms, Some(500));
+
+        // Reopening shows the saved value; clearing it restores the default
+        app.open_edit_project_modal();
+        assert_eq!(app.edit_project_activity_timeout.value(), "500");
+        app.edit_project_activity_timeout.clear();
+        app.submit_edit_project();
+        assert_eq!(
+            app.active_project().unwrap().config.activity_timeout_ms,
+            None
+        );
+    }
+
+    #[test]
+    fn edit_project_worktree_root_saves() {
+        let mut app = app_with_project("test", vec![PathBuf::from("/repo")]);
+        app.open_edit_project_modal();
+        assert_eq!(app.edit_project_worktree_root.value(), "");
+
+        app.edit_project_field = EditProjectField::WorktreeRoot;
+        for c in "../wt".chars() {
+            app.handle_key(KeyCode::Char(c), KeyModifiers::NONE);
+        }
+        app.handle_key(KeyCode::Enter, KeyModifiers::NONE);
+
+        assert!(!app.show_edit_project_modal);
+        let project = app.active_project().unwrap();
+        assert_eq!(project.config.worktree_root, Some(PathBuf::from("../wt")));
+        let stored = app.db.list_active_projects().unwrap();
+        let stored = stored.iter().find(|p| p.id == project.id).unwrap();
+        assert_eq!(stored.worktree_root, Some(PathBuf::from("../wt")));
+
+        // Clearing the field falls back to the `.git` default
+        app.open_edit_project_modal();
+        assert_eq!(app.edit_project_worktree_root.value(), "../wt");
+        app.edit_project_worktree_root.clear();
+        app.submit_edit_project();
+        assert_eq!(app.active_project().unwrap().config.worktree_root, None);
+    }
+
+    #[test]
+    fn edit_project_rejects_zero_activity_timeout() {
+        let mut app = app_with_project("test", vec![PathBuf::from("/repo")]);
+        app.open_edit_project_modal();
+        app.edit_project_activity_timeout.set("0");
+        app.submit_edit_project();
+
+        assert!(app.show_edit_project_modal);
+        assert!(app
+            .status_message
+            .as_ref()
+            .is_some_and(|m| m.text.contains("Activity timeout")));
+        assert_eq!(
+            app.active_project().unwrap().config.activity_timeout_ms,
+            None
+        );
+    }
+
+    #[test]
+    fn edit_project_esc_closes_modal() {
+        let mut app = app_with_project("test", vec![PathBuf::from("/repo")]);
+        app.open_edit_project_modal();
+        app.handle_key(KeyCode::Esc, KeyModifiers::NONE);
+        assert!(!app.show_edit_project_modal);
+    }
+
+    #[test]
+    fn edit_project_repo_list_delete() {
+        let mut app = app_with_project(
+            "test",
+            vec![PathBuf::from("/repo/a"), PathBuf::from("/repo/b")],
+        );
+        app.open_edit_project_modal();
+        app.edit_project_field = EditProjectField::RepoList;
+        app.edit_project_repo_index = 0;
+
+        // Delete first repo
+        app.handle_key(KeyCode::Char('d'), KeyModifiers::NONE);
+        assert_eq!(app.edit_project_repos.len(), 1);
+        assert_eq!(app.edit_project_repos[0], PathBuf::from("/repo/b"));
+    }
+
+    #[test]
+    fn edit_project_repo_list_empty_after_delete_switches_to_path() {
+        let mut app = app_with_project("test", vec![PathBuf::from("/repo/a")]);
+        app.open_edit_project_modal();
+        app.edit_project_field = EditProjectField::RepoList;
+
+        app.handle_key(KeyCode::Char('d'), KeyModifiers::NONE);
+        assert!(app.edit_project_repos.is_empty());
+        assert_eq!(app.edit_project_field, EditProjectField::Path);
+    }
+
+    #[test]
+    fn edit_project_id_stable_on_rename() {
+        let mut app = app_with_project("alpha", vec![PathBuf::from("/repo")]);
+        let id_before = app.projects[0].id;
+
+        app.open_edit_project_modal();
+        app.edit_project_name.clear();
+        app.edit_project_name.set("beta");
+        app.submit_edit_project();
+
+        assert_eq!(app.projects[0].config.name, "beta");
+        assert_eq!(app.projects[0].id, id_before);
+    }
+
+    #[test]
+    fn renamed_project_loads_with_roles_from_db() {
+        // DB has a project that was renamed, with roles stored in project_roles table.
+        let db = test_db();
+        let old_config = ProjectConfig {
+            name: "old-name".to_string(),
+            repos: vec![PathBuf::from("/repo")],
+            roles: Vec::new(),
+            mcp_servers: Vec::new(),
+            sync_base_branch: None,
+            remote: None,
+            sync_strategy: SyncStrategy::default(),
+            activity_timeout_ms: None,
+            worktree_root: None,
+            default_role: None,
+            session_init_command: None,
+            id: None,
+        };
+        let det_id = old_config.deterministic_id();
+
+        // Insert project with old name's ID but renamed
+        db.insert_project(det_id, "renamed-proj", &[PathBuf::from("/repo")])
+            .unwrap();
+
+        // Store roles in DB
+        use crate::session::{RoleConfig, RolePermissions};
+        db.replace_roles(
+            det_id,
+            &[RoleConfig {
+                name: "dev".to_string(),
+                description: String::new(),
+                permissions: RolePermissions::default(),
+            }],
+        )
+        .unwrap();
+
+        let projects = load_projects_from_db(&db);
+        let proj = projects.iter().find(|p| p.id == det_id).unwrap();
+        assert_eq!(proj.config.name, "renamed-proj");
+        assert_eq!(proj.config.roles.len(), 1);
+        assert_eq!(proj.config.roles[0].name, "dev");
+    }
+
+    #[test]
+    fn rename_project_full_lifecycle() {
+        // Full lifecycle test: create app, rename project, shutdown, create new app (restart).
+        // Verifies no duplicate projects and sessions stay associated.
+        let db = test_db();
+        let backend = stub_backend();
+
+        // Step 1: Start app with project "TestA"
+        let config = ProjectConfig {
+            name: "TestA".to_string(),
+            repos: vec![PathBuf::from("/repo")],
+            roles: Vec::new(),
+            mcp_servers: Vec::new(),
+            sync_base_branch: None,
+            remote: None,
+            sync_strategy: SyncStrategy::default(),
+            activity_timeout_ms: None,
+            worktree_root: None,
+            default_role: None,
+            session_init_command: None,
+            id: None,
+        };
+        let original_id = config.deterministic_id();
+        let id = config.effective_id();
+        db.insert_project(id, &config.name, &config.repos).unwrap();
+        let mut app = App::new(24, 120, backend.clone(), db);
+
+        // Verify initial state: 1 project named "TestA"
+        assert_eq!(app.projects.len(), 1);
+        assert_eq!(app.projects[0].config.name, "TestA");
+        assert_eq!(app.projects[0].id, original_id);
+
+        // Step 2: Create a session for TestA
+        app.active_project_index = app
+            .projects
+            .iter()
+            .position(|p| p.config.name == "TestA")
+            .unwrap();
+        let session = Session::stub("Session1", &backend);
+        let session_id = session.info.id;
+        app.sessions.push(session);
+        app.projects[app.active_project_index]
+            .session_ids
+            .push(session_id);
+
+        // Step 3: Rename "TestA" → "TestB" via edit modal
+        app.open_edit_project_modal();
+        assert!(app.show_edit_project_modal);
+        app.edit_project_name.set("TestB");
+        // Repos stay the same (pre-populated from open_edit_project_modal)
+        app.submit_edit_project();
+        assert!(
+            !app.show_edit_project_modal,
+            "Modal should close on success"
+        );
+
+        // Verify: project renamed, ID stable
+        let renamed_project = app.projects.iter().find(|p| p.config.name == "TestB");
+        assert!(renamed_project.is_some(), "Should have project TestB");
+        assert_eq!(
+            renamed_project.unwrap().id,
+            original_id,
+            "ID should be stable"
+        );
+        assert!(
+            app.projects.iter().all(|p| p.config.name != "TestA"),
+            "TestA should no longer exist"
+        );
+
+        // Step 4: Save state (simulates shutdown)
+        app.save_state();
+
+        // Step 5: Simulate restart with the same DB (project already persisted from edit)
+        let app2 = App::new(24, 120, backend.clone(), app.db);
+
+        // Verify: only 1 project, named "TestB"
+        assert_eq!(
+            app2.projects.len(),
+            1,
+            "Expected 1 project, got {}: {:?}",
+            app2.projects.len(),
+            app2.projects
+                .iter()
+                .map(|p| &p.config.name)
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(app2.projects[0].config.name, "TestB");
+        assert_eq!(app2.projects[0].id, original_id);
+
+        // Step 6: Restore sessions
+        if let Some((sessions, _counter)) = app2.load_persisted_state_from_db() {
+            // Verify session has correct project_id
+            assert_eq!(sessions.len(), 1);
+            assert_eq!(
+                sessions[0].project_id, original_id,
+                "Session should reference original project ID"
+            );
+        }
+    }
+
+    #[test]
+    fn rename_project_survives_restart_db_only() {
+        // After a rename, the DB is the single source of truth.
+        // On restart, load_projects_from_db returns the renamed project with stable ID.
+        let db = test_db();
+
+        let original_config = ProjectConfig {
+            name: "TestA".to_string(),
+            repos: vec![PathBuf::from("/repo")],
+            roles: Vec::new(),
+            mcp_servers: Vec::new(),
+            sync_base_branch: None,
+            remote: None,
+            sync_strategy: SyncStrategy::default(),
+            activity_timeout_ms: None,
+            worktree_root: None,
+            default_role: None,
+            session_init_command: None,
+            id: None,
+        };
+        let original_id = original_config.deterministic_id();
+        db.insert_project(original_id, "TestA", &[PathBuf::from("/repo")])
+            .unwrap();
+
+        // Rename in DB (as submit_edit_project does)
+        db.update_project(original_id, "TestB", &[PathBuf::from("/repo")])
+            .unwrap();
+
+        // Simulate restart: load from DB only
+        let projects = load_projects_from_db(&db);
+
+        assert_eq!(projects.len(), 1);
+        assert_eq!(projects[0].config.name, "TestB");
+        assert_eq!(projects[0].id, original_id);
+    }
+
+    #[test]
+    fn rename_project_sessions_survive_restart() {
+        // Simulate: project "TestA" has a session, renamed to "TestB", then restart.
+        // The session should remain associated with the renamed project via stable ID.
+        let db = test_db();
+
+        // Step 1: Insert original project "TestA" into DB
+        let original_config = ProjectConfig {
+            name: "TestA".to_string(),
+            repos: vec![PathBuf::from("/repo")],
+            roles: Vec::new(),
+            mcp_servers: Vec::new(),
+            sync_base_branch: None,
+            remote: None,
+            sync_strategy: SyncStrategy::default(),
+            activity_timeout_ms: None,
+            worktree_root: None,
+            default_role: None,
+            session_init_command: None,
+            id: None,
+        };
+        let original_id = original_config.deterministic_id();
+        db.insert_project(original_id, "TestA", &[PathBuf::from("/repo")])
+            .unwrap();
+
+        // Step 2: Create a session associated with "TestA"
+        let session_id = SessionId::default();
+        let shared_session = sync::SharedSession {
+            id: session_id,
+            name: "Session 1".to_string(),
+            project_id: original_id,
+            role: "developer".to_string(),
+            backend_id: "thurbox:@0".to_string(),
+            backend_type: "tmux".to_string(),
+            claude_session_id: Some("claude-abc".to_string()),
+            cwd: None,
+            additional_dirs: Vec::new(),
+            worktrees: Vec::new(),
+            shell_backend_id: None,
+            is_pinned: false,
+            label: None,
+            label_color: None,
+            created_at: None,
+            tombstone: false,
+            tombstone_at: None,
+        };
+        db.upsert_session(&shared_session).unwrap();
+
+        // Step 3: Rename in DB (as submit_edit_project does)
+        db.update_project(original_id, "TestB", &[PathBuf::from("/repo")])
+            .unwrap();
+
+        // Step 4: Simulate restart — load from DB only
+        let projects = load_projects_from_db(&db);
+        assert_eq!(projects.len(), 1);
+        assert_eq!(projects[0].id, original_id);
+        assert_eq!(projects[0].config.name, "TestB");
+
+        // Check session still references the correct project
+        let sessions = db.list_active_sessions().unwrap();
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(sessions[0].project_id, original_id);
+    }
+
+    #[test]
+    fn session_to_shared_maps_worktree() {
+        let backend = stub_backend();
+        let mut app = App::new(
+            24,
+            120,
+            backend.clone(),
+            test_db_with_project(&test_project_config()),
+        );
+
+        let mut session = Session::stub("WTSession", &backend);
+        session.info.worktrees = vec![WorktreeInfo {
+            repo_path: PathBuf::from("/repo"),
+            worktree_path: PathBuf::from("/repo/.git/wt/feat"),
+            branch: "feat".to_string(),
+            base_branch: "main".to_string(),
+        }];
+
+        let sid = session.info.id;
+        app.sessions.push(session);
+        app.projects[0].session_ids.push(sid);
+
+        let shared = app.session_to_shared(&app.sessions[0]);
+        assert_eq!(shared.worktrees.len(), 1);
+        let wt = &shared.worktrees[0];
+        assert_eq!(wt.branch, "feat");
+        assert_eq!(wt.repo_path, PathBuf::from("/repo"));
+    }
+
+    // --- Edit-project inline roles tests ---
+
+    fn app_with_roles(roles: Vec<crate::session::RoleConfig>) -> App {
+        let config = ProjectConfig {
+            name: "test".to_string(),
+            repos: vec![PathBuf::from("/repo")],
+            roles,
+            mcp_servers: vec![],
+            sync_base_branch: None,
+            remote: None,
+            sync_strategy: SyncStrategy::default(),
+            activity_timeout_ms: None,
+            worktree_root: None,
+            default_role: None,
+            session_init_command: None,
+            id: None,
+        };
+        App::new(24, 120, stub_backend(), test_db_with_project(&config))
+    }
+
+    #[test]
+    fn open_edit_project_loads_roles() {
+        use crate::session::{RoleConfig, RolePermissions};
+        let mut app = app_with_roles(vec![RoleConfig {
+            name: "dev".to_string(),
+            description: "Developer".to_string(),
+            permissions: RolePermissions::default(),
+        }]);
+        app.open_edit_project_modal();
+        assert_eq!(app.role_editor_roles.len(), 1);
+        assert_eq!(app.role_editor_roles[0].name, "dev");
+        assert_eq!(app.role_editor_list_index, 0);
+    }
+
+    #[test]
+    fn submit_edit_project_saves_roles() {
+        use crate::session::{RoleConfig, RolePermissions};
+        let mut app = app_with_roles(vec![]);
+        app.open_edit_project_modal();
+        // Add a role to the editor state
+        app.role_editor_roles.push(RoleConfig {
+            name: "new-role".to_string(),
+            description: String::new(),
+            permissions: RolePermissions::default(),
+        });
+        app.submit_edit_project();
+        // Verify the project now has the role
+        let project = app
+            .projects
+            .iter()
+            .find(|p| p.config.name == "test")
+            .unwrap();
+        assert_eq!(project.config.roles.len(), 1);
+        assert_eq!(project.config.roles[0].name, "new-role");
+    }
+
+    #[test]
+    fn close_edit_project_clears_role_editor() {
+        use crate::session::{RoleConfig, RolePermissions};
+        let mut app = app_with_roles(vec![RoleConfig {
+            name: "dev".to_string(),
+            description: String::new(),
+            permissions: RolePermissions::default(),
+        }]);
+        app.open_edit_project_modal();
+        app.show_role_editor = true; // Simulate role editor being open
+        app.close_edit_project_modal();
+        assert!(!app.show_edit_project_modal);
+        assert!(!app.show_role_editor);
+        assert!(app.role_editor_roles.is_empty());
+        assert_eq!(app.role_editor_list_index, 0);
+    }
+
+    #[test]
+    fn edit_project_roles_navigate_and_delete() {
+        use crate::session::{RoleConfig, RolePermissions};
+        let mut app = app_with_roles(vec![
+            RoleConfig {
+                name: "a".to_string(),
+                description: String::new(),
+                permissions: RolePermissions::default(),
+            },
+            RoleConfig {
+                name: "b".to_string(),
+                description: String::new(),
+                permissions: RolePermissions::default(),
+            },
+        ]);
+        app.open_edit_project_modal();
+        app.edit_project_field = EditProjectField::Roles;
+        assert_eq!(app.role_editor_list_index, 0);
+
+        // Navigate down
+        app.handle_key(KeyCode::Char('j'), KeyModifiers::NONE);
+        assert_eq!(app.role_editor_list_index, 1);
+
+        // Navigate up
+        app.handle_key(KeyCode::Char('k'), KeyModifiers::NONE);
+        assert_eq!(app.role_editor_list_index, 0);
+
+        // Delete first role
+        app.handle_key(KeyCode::Char('d'), KeyModifiers::NONE);
+        assert_eq!(app.role_editor_roles.len(), 1);
+        assert_eq!(app.role_editor_roles[0].name, "b");
+    }
+
+    #[test]
+    fn edit_project_roles_add_opens_role_editor() {
+        let mut app = app_with_roles(vec![]);
+        app.open_edit_project_modal();
+        app.edit_project_field = EditProjectField::Roles;
+        app.handle_key(KeyCode::Char('a'), KeyModifiers::NONE);
+        assert!(app.show_role_editor);
+        assert_eq!(app.role_editor_view, RoleEditorView::Editor);
+        assert!(app.role_editor_editing_index.is_none());
+    }
+
+    #[test]
+    fn edit_project_roles_edit_opens_role_editor() {
+        use crate::session::{RoleConfig, RolePermissions};
+        let mut app = app_with_roles(vec![RoleConfig {
+            name: "dev".to_string(),
+            description: "Developer".to_string(),
+            permissions: RolePermissions::default(),
+        }]);
+        app.open_edit_project_modal();
+        app.edit_project_field = EditProjectField::Roles;
+        app.handle_key(KeyCode::Char('e'), KeyModifiers::NONE);
+        assert!(app.show_role_editor);
+        assert_eq!(app.role_editor_view, RoleEditorView::Editor);
+        assert_eq!(app.role_editor_editing_index, Some(0));
+        assert_eq!(app.role_editor_name.value(), "dev");
+    }
+
+    #[test]
+    fn edit_project_roles_export_then_import_roundtrip() {
+        use crate::session::{RoleConfig, RolePermissions};
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join("roles.toml");
+        let roles = vec![
+            RoleConfig {
+                name: "dev".to_string(),
+                description: "Developer".to_string(),
+                permissions: RolePermissions {
+                    allowed_tools: vec!["Read".to_string()],
+                    ..RolePermissions::default()
+                },
+            },
+            RoleConfig {
+                name: "reviewer".to_string(),
+                description: String::new(),
+                permissions: RolePermissions {
+                    permission_mode: Some("plan".to_string()),
+                    ..RolePermissions::default()
+                },
+            },
+        ];
+        let mut app = app_with_roles(roles.clone());
+        app.open_edit_project_modal();
+        app.edit_project_field = EditProjectField::Roles;
+
+        app.handle_key(KeyCode::Char('x'), KeyModifiers::NONE);
+        assert_eq!(app.role_file_action, Some(RoleFileAction::Export));
+        type_text(&mut app, path.to_str().unwrap());
+        app.handle_key(KeyCode::Enter, KeyModifiers::NONE);
+        assert!(app.role_file_action.is_none());
+        assert!(path.exists());
+
+        // Import into a project with one overlapping role
+        let mut other = app_with_roles(vec![RoleConfig {
+            name: "dev".to_string(),
+            description: "stale".to_string(),
+            permissions: RolePermissions::default(),
+        }]);
+        other.open_edit_project_modal();
+        other.edit_project_field = EditProjectField::Roles;
+        other.handle_key(KeyCode::Char('i'), KeyModifiers::NONE);
+        assert_eq!(other.role_file_action, Some(RoleFileAction::Import));
+        type_text(&mut other, path.to_str().unwrap());
+        other.handle_key(KeyCode::Enter, KeyModifiers::NONE);
+        assert!(other.role_file_action.is_none());
+        assert_eq!(other.role_editor_roles, roles);
+        assert!(other.show_edit_project_modal);
+    }
+
+    #[test]
+    fn edit_project_roles_import_json() {
+        use crate::session::{RoleConfig, RolePermissions};
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join("roles.json");
+        std::fs::write(
+            &path,
+            r#"{"roles": [{"name": "ops", "allowed_tools": ["Read"]}]}"#,
+        )
+        .unwrap();
+        let mut app = app_with_roles(vec![]);
+        app.open_edit_project_modal();
+        app.edit_project_field = EditProjectField::Roles;
+        app.open_role_file_modal(RoleFileAction::Import);
+        app.role_file_path.set(path.to_str().unwrap());
+        app.submit_role_file();
+        assert_eq!(
+            app.role_editor_roles,
+            vec![RoleConfig {
+                name: "ops".to_string(),
+                description: String::new(),
+                permissions: RolePermissions {
+                    allowed_tools: vec!["Read".to_string()],
+                    ..RolePermissions::default()
+                },
+            }]
+        );
+    }
+
+    #[test]
+    fn edit_project_roles_import_malformed_reports_error() {
+        use crate::session::{RoleConfig, RolePermissions};
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join("roles.toml");
+        std::fs::write(&path, "[[roles]\nname = ").unwrap();
+        let existing = vec![RoleConfig {
+            name: "dev".to_string(),
+            description: String::new(),
+            permissions: RolePermissions::default(),
+        }];
+        let mut app = app_with_roles(existing.clone());
+        app.open_edit_project_modal();
+        app.edit_project_field = EditProjectField::Roles;
+        app.open_role_file_modal(RoleFileAction::Import);
+        app.role_file_path.set(path.to_str().unwrap());
+        app.submit_role_file();
+
+        let msg = app.status_message.as_ref().unwrap();
+        assert_eq!(msg.level, StatusLevel::Error);
+        assert!(msg.text.starts_with("Failed to import roles"));
+        // Prompt stays open for correction; roles untouched
+        assert_eq!(app.role_file_action, Some(RoleFileAction::Import));
+        assert_eq!(app.role_editor_roles, existing);
+    }
+
+    #[test]
+    fn edit_project_roles_import_missing_file_reports_error() {
+        let mut app = app_with_roles(vec![]);
+        app.open_edit_project_modal();
+        app.open_role_file_modal(RoleFileAction::Import);
+        app.role_file_path.set("/nonexistent/roles.toml");
+        app.submit_role_file();
+        assert_eq!(
+            app.status_message.as_ref().map(|m| m.level),
+            Some(StatusLevel::Error)
+        );
+    }
+
+    #[test]
+    fn role_file_prompt_esc_cancels() {
+        let mut app = app_with_roles(vec![]);
+        app.open_edit_project_modal();
+        app.edit_project_field = EditProjectField::Roles;
+        app.handle_key(KeyCode::Char('i'), KeyModifiers::NONE);
+        type_text(&mut app, "x.toml");
+        app.handle_key(KeyCode::Esc, KeyModifiers::NONE);
+        assert!(app.role_file_action.is_none());
+        assert_eq!(app.role_file_path.value(), "");
+        // Edit-project modal is still open
+        assert!(app.show_edit_project_modal);
+    }
+
+    #[test]
+    fn edit_project_roles_esc_saves_and_closes() {
+        use crate::session::{RoleConfig, RolePermissions};
+        let mut app = app_with_roles(vec![]);
+        app.open_edit_project_modal();
+        app.edit_project_field = EditProjectField::Roles;
+        // Add a role directly to the editor state
+        app.role_editor_roles.push(RoleConfig {
+            name: "added".to_string(),
+            description: String::new(),
+            permissions: RolePermissions::default(),
+        });
+        // Esc from Roles field triggers submit_edit_project (saves)
+        app.handle_key(KeyCode::Esc, KeyModifiers::NONE);
+        assert!(!app.show_edit_project_modal);
+        let project = app
+            .projects
+            .iter()
+            .find(|p| p.config.name == "test")
+            .unwrap();
+        assert_eq!(project.config.roles.len(), 1);
+        assert_eq!(project.config.roles[0].name, "added");
+    }
+
+    #[test]
+    fn ctrl_r_no_op_without_claude_session_id() {
+        let mut app = app_with_sessions(1);
+        // Session exists but has no claude_session_id
+        app.sessions[0].info.claude_session_id = None;
+        app.focus = InputFocus::Terminal;
+        app.handle_key(KeyCode::Char('r'), KeyModifiers::CONTROL);
+        // Should be a no-op (no error, no crash)
+        assert!(app.status_message.is_none());
+    }
+
+    // --- Crash-loop protection tests ---
+
+    fn restart_command(app: &App, idx: usize) -> SessionCommand {
+        SessionCommand {
+            id: 0,
+            session_id: app.sessions[idx].info.id,
+            command: "restart".to_string(),
+            created_at: 0,
+        }
+    }
+
+    #[test]
+    fn repeated_restart_commands_flag_crash_loop() {
+        let mut app = app_with_sessions(1);
+        app.sessions[0].info.claude_session_id = Some("abc".to_string());
+
+        for _ in 0..crate::session::CRASH_LOOP_MAX_RESTARTS {
+            let cmd = restart_command(&app, 0);
+            app.handle_restart_command(&cmd);
+        }
+        assert!(!app.sessions[0].info.crash_looping);
+
+        let cmd = restart_command(&app, 0);
+        app.handle_restart_command(&cmd);
+        assert!(app.sessions[0].info.crash_looping);
+        let msg = app.status_message.as_ref().unwrap();
+        assert_eq!(msg.level, StatusLevel::Error);
+        assert!(msg.text.contains("crash looping"));
+    }
+
+    #[test]
+    fn restart_command_ignored_while_crash_looping() {
+        let mut app = app_with_sessions(1);
+        app.sessions[0].info.claude_session_id = Some("abc".to_string());
+        app.sessions[0].info.crash_looping = true;
+
+        let cmd = restart_command(&app, 0);
+        app.handle_restart_command(&cmd);
+        assert_eq!(app.sessions[0].info.restart_count, 0);
+    }
+
+    // --- Auto-restart tests ---
+
+    fn app_with_exited_session() -> App {
+        let mut app = app_with_sessions(1);
+        app.settings.auto_restart.enabled = true;
+        app.sessions[0].info.claude_session_id = Some("abc".to_string());
+        app.sessions[0].mark_exited();
+        app
+    }
+
+    fn auto_restart_state(app: &App) -> Option<AutoRestart> {
+        app.auto_restarts.get(&app.sessions[0].info.id).copied()
+    }
+
+    #[test]
+    fn auto_restart_delay_doubles_and_caps() {
+        let secs: Vec<u64> = (0..8).map(|n| auto_restart_delay(n).as_secs()).collect();
+        assert_eq!(secs, vec![1, 2, 4, 8, 16, 30, 30, 30]);
+        assert_eq!(auto_restart_delay(u32::MAX), AUTO_RESTART_MAX_DELAY);
+    }
+
+    #[test]
+    fn auto_restart_disabled_by_default() {
+        let mut app = app_with_exited_session();
+        app.settings.auto_restart.enabled = false;
+        app.process_auto_restarts(std::time::Instant::now());
+        assert!(app.auto_restarts.is_empty());
+    }
+
+    #[test]
+    fn auto_restart_backs_off_then_gives_up() {
+        let mut app = app_with_exited_session();
+        let max = app.settings.auto_restart.max_attempts;
+        let start = std::time::Instant::now();
+        let mut now = start;
+
+        // The stub backend cannot spawn, so every restart leaves the session
+        // exited again, just like a process that crashes on startup.
+        for attempt in 0..max {
+            app.process_auto_restarts(now);
+            let delay = auto_restart_delay(attempt);
+            assert_eq!(
+                auto_restart_state(&app),
+                Some(AutoRestart::Scheduled {
+                    attempts: attempt,
+                    due: now + delay,
+                })
+            );
+
+            // Nothing fires before the backoff elapses
+            app.process_auto_restarts(now + delay / 2);
+            assert!(matches!(
+                auto_restart_state(&app),
+                Some(AutoRestart::Scheduled { .. })
+            ));
+
+            now += delay;
+            app.process_auto_restarts(now);
+            assert_eq!(
+                auto_restart_state(&app),
+                Some(AutoRestart::Running {
+                    attempts: attempt + 1,
+                    since: now,
+                })
+            );
+        }
+        assert_eq!(now - start, std::time::Duration::from_secs(1 + 2 + 4));
+        assert_eq!(app.sessions[0].info.restart_count, max);
+
+        // One more exit: out of attempts
+        app.process_auto_restarts(now);
+        assert_eq!(auto_restart_state(&app), Some(AutoRestart::Stopped));
+        assert!(app.sessions[0].info.crash_looping);
+        assert_eq!(app.sessions[0].info.status, SessionStatus::Error);
+        let msg = app.status_message.as_ref().unwrap();
+        assert_eq!(msg.level, StatusLevel::Error);
+        assert!(msg.text.contains("gave up"));
+
+        // Stays given up
+        app.process_auto_restarts(now + AUTO_RESTART_MAX_DELAY);
+        assert_eq!(app.sessions[0].info.restart_count, max);
+    }
+
+    #[test]
+    fn auto_restart_skips_session_without_claude_id() {
+        let mut app = app_with_exited_session();
+        app.sessions[0].info.claude_session_id = None;
+        app.process_auto_restarts(std::time::Instant::now());
+        assert_eq!(auto_restart_state(&app), Some(AutoRestart::Stopped));
+    }
+
+    #[test]
+    fn auto_restart_skips_exit_after_ctrl_c() {
+        let mut app = app_with_exited_session();
+        app.focus = InputFocus::Terminal;
+        app.handle_key(KeyCode::Char('c'), KeyModifiers::CONTROL);
+        assert!(app.interrupted_at.contains_key(&app.sessions[0].info.id));
+
+        app.process_auto_restarts(std::time::Instant::now());
+        assert_eq!(auto_restart_state(&app), Some(AutoRestart::Stopped));
+        // Still left alone once the grace period is over
+        app.process_auto_restarts(std::time::Instant::now() + INTERRUPT_GRACE * 2);
+        assert_eq!(auto_restart_state(&app), Some(AutoRestart::Stopped));
+    }
+
+    #[test]
+    fn auto_restart_ignores_stale_interrupt() {
+        let mut app = app_with_exited_session();
+        let now = std::time::Instant::now();
+        let id = app.sessions[0].info.id;
+        app.interrupted_at.insert(id, now);
+        app.process_auto_restarts(now + INTERRUPT_GRACE * 2);
+        assert!(matches!(
+            auto_restart_state(&app),
+            Some(AutoRestart::Scheduled { .. })
+        ));
+    }
+
+    #[test]
+    fn auto_restart_state_cleared_once_session_is_stable() {
+        let mut app = app_with_sessions(1);
+        app.settings.auto_restart.enabled = true;
+        let id = app.sessions[0].info.id;
+        let now = std::time::Instant::now();
+        app.auto_restarts.insert(
+            id,
+            AutoRestart::Running {
+                attempts: 2,
+                since: now,
+            },
+        );
+
+        app.process_auto_restarts(now + AUTO_RESTART_STABLE_AFTER / 2);
+        assert!(app.auto_restarts.contains_key(&id));
+        app.process_auto_restarts(now + AUTO_RESTART_STABLE_AFTER);
+        assert!(!app.auto_restarts.contains_key(&id));
+    }
+
+    #[test]
+    fn ctrl_r_clears_auto_restart_state() {
+        let mut app = app_with_exited_session();
+        app.process_auto_restarts(std::time::Instant::now());
+        assert!(auto_restart_state(&app).is_some());
+
+        app.focus = InputFocus::SessionList;
+        app.handle_key(KeyCode::Char('r'), KeyModifiers::CONTROL);
+        assert_eq!(auto_restart_state(&app), None);
+    }
+
+    #[test]
+    fn tick_marks_quiet_session_with_prompt_as_needs_input() {
+        let mut app = app_with_sessions(2);
+        for session in &app.sessions {
+            session.backdate_output(ACTIVITY_TIMEOUT_MS + 500);
+        }
+        app.sessions[0]
+            .parser
+            .lock()
+            .unwrap()
+            .process(b"Do you want to proceed?\r\n\xe2\x9d\xaf 1. Yes\r\n");
+        app.tick();
+        assert_eq!(app.sessions[0].info.status, SessionStatus::NeedsInput);
+        // No prompt markers: falls back to the timeout heuristic
+        assert_eq!(app.sessions[1].info.status, SessionStatus::Waiting);
+    }
+
+    #[test]
+    fn tick_uses_project_activity_timeout() {
+        let mut app = app_with_sessions(1);
+        app.sessions[0].backdate_output(ACTIVITY_TIMEOUT_MS + 500);
+        app.tick();
+        assert_eq!(app.sessions[0].info.status, SessionStatus::Waiting);
+
+        app.projects[0].config.activity_timeout_ms = Some(60_000);
+        app.tick();
+        assert_eq!(app.sessions[0].info.status, SessionStatus::Busy);
+    }
+
+    #[test]
+    fn tick_keeps_recent_output_busy_despite_prompt() {
+        let mut app = app_with_sessions(1);
+        app.sessions[0]
+            .parser
+            .lock()
+            .unwrap()
+            .process(b"Do you want to proceed?\r\n");
+        app.tick();
+        assert_eq!(app.sessions[0].info.status, SessionStatus::Busy);
+    }
+
+    #[test]
+    fn tick_marks_crash_looping_session_as_error() {
+        let mut app = app_with_sessions(1);
+        app.sessions[0].info.crash_looping = true;
+        app.tick();
+        assert_eq!(app.sessions[0].info.status, SessionStatus::Error);
+    }
+
+    #[test]
+    fn tick_records_status_changes() {
+        let mut app = app_with_sessions(1);
+        let id = app.sessions[0].info.id;
+        app.sessions[0].info.crash_looping = true;
+        app.tick();
+        app.tick();
+
+        let spans = app.db.session_activity(id).unwrap();
+        assert_eq!(spans.len(), 1);
+        assert_eq!(spans[0].status, SessionStatus::Error);
+    }
+
+    #[test]
+    fn activity_summary_refreshes_for_info_panel() {
+        let mut app = app_with_sessions(1);
+        let id = app.sessions[0].info.id;
+        app.show_info_panel = true;
+        app.db
+            .record_status_change(id, SessionStatus::Idle, SessionStatus::Busy, 0)
+            .unwrap();
+
+        let now = std::time::Instant::now();
+        app.refresh_activity_summary(now);
+        let (cached, _, summary) = app.activity_summary.unwrap();
+        assert_eq!(cached, id);
+        assert_eq!(summary.unwrap().transitions, 1);
+
+        app.db
+            .record_status_change(id, SessionStatus::Busy, SessionStatus::Waiting, 1)
+            .unwrap();
+        app.refresh_activity_summary(now + ACTIVITY_SUMMARY_REFRESH / 2);
+        assert_eq!(app.activity_summary.unwrap().2.unwrap().transitions, 1);
+        app.refresh_activity_summary(now + ACTIVITY_SUMMARY_REFRESH);
+        assert_eq!(app.activity_summary.unwrap().2.unwrap().transitions, 2);
+    }
+
+    #[test]
+    fn ctrl_r_resets_crash_loop() {
+        let mut app = app_with_sessions(1);
+        app.sessions[0].info.claude_session_id = Some("abc".to_string());
+        app.sessions[0].info.crash_looping = true;
+        app.focus = InputFocus::Terminal;
+
+        app.handle_key(KeyCode::Char('r'), KeyModifiers::CONTROL);
+        assert!(!app.sessions[0].info.crash_looping);
+        assert_eq!(app.sessions[0].info.restart_count, 1);
+    }
+
+    #[test]
+    fn session_to_shared_maps_additional_dirs() {
+        let backend = stub_backend();
+        let mut app = App::new(
+            24,
+            120,
+            backend.clone(),
+            test_db_with_project(&test_project_config()),
+        );
+
+        let mut session = Session::stub("MultiDir", &backend);
+        session.info.additional_dirs = vec![PathBuf::from("/repo2"), PathBuf::from("/repo3")];
+
+        let sid = session.info.id;
+        app.sessions.push(session);
+        app.projects[0].session_ids.push(sid);
+
+        let shared = app.session_to_shared(&app.sessions[0]);
+        assert_eq!(shared.additional_dirs.len(), 2);
+        assert_eq!(shared.additional_dirs[0], PathBuf::from("/repo2"));
+        assert_eq!(shared.additional_dirs[1], PathBuf::from("/repo3"));
+    }
+
+    #[test]
+    fn disabled_admin_creates_no_admin_project() {
+        let mut app = app_with_sessions(1);
+        app.settings.admin.enabled = false;
+        app.ensure_admin_session();
+        assert_eq!(app.projects.len(), 1);
+        assert!(app.projects.iter().all(|p| !p.is_admin));
+        assert_eq!(app.sessions.len(), 1);
+        assert_eq!(app.user_session_count(), 1);
+    }
+
+    #[test]
+    fn disabled_admin_hides_admin_project_from_earlier_runs() {
+        let mut app = app_with_sessions(2);
+        let backend = stub_backend();
+        // As loaded from the DB: the Admin project and its session
+        let mut admin = ProjectInfo::new(ProjectConfig {
+            name: ADMIN_PROJECT_NAME.to_string(),
+            ..test_project_config()
+        });
+        let admin_session = Session::stub("admin", &backend);
+        admin.session_ids.push(admin_session.info.id);
+        app.sessions.insert(0, admin_session);
+        app.projects.insert(0, admin);
+        app.active_project_index = 1;
+        app.active_index = 2;
+        let active_id = app.sessions[2].info.id;
+
+        app.settings.admin.enabled = false;
+        app.ensure_admin_session();
+
+        assert_eq!(app.projects.len(), 1);
+        assert_eq!(app.projects[0].config.name, "Test");
+        assert_eq!(app.active_project_index, 0);
+        assert_eq!(app.sessions.len(), 2);
+        assert_eq!(app.sessions[app.active_index].info.id, active_id);
+        assert_eq!(app.user_session_count(), 2);
+    }
+
+    #[test]
+    fn user_session_count_excludes_admin_project() {
+        let backend = stub_backend();
+        let config = ProjectConfig {
+            name: "UserProj".to_string(),
+            repos: vec![PathBuf::from("/repo")],
+            roles: Vec::new(),
+            mcp_servers: Vec::new(),
+            sync_base_branch: None,
+            remote: None,
+            sync_strategy: SyncStrategy::default(),
+            activity_timeout_ms: None,
+            worktree_root: None,
+            default_role: None,
+            session_init_command: None,
+            id: None,
+        };
+        let mut app = App::new(24, 120, backend.clone(), test_db_with_project(&config));
+
+        // User project has no sessions
+        assert_eq!(app.user_session_count(), 0);
+
+        // Add a session to the user project
+        let session = Session::stub("user-1", &backend);
+        let sid = session.info.id;
+        app.sessions.push(session);
+        app.projects[0].session_ids.push(sid);
+        assert_eq!(app.user_session_count(), 1);
+
+        // Add an admin project with a session
+        let admin_config = ProjectConfig {
+            name: "Admin".to_string(),
+            repos: vec![],
+            roles: Vec::new(),
+            mcp_servers: Vec::new(),
+            sync_base_branch: None,
+            remote: None,
+            sync_strategy: SyncStrategy::default(),
+            activity_timeout_ms: None,
+            worktree_root: None,
+            default_role: None,
+            session_init_command: None,
+            id: None,
+        };
+        let mut admin_project = ProjectInfo::new_admin(admin_config);
+        let admin_session = Session::stub("admin", &backend);
+        let admin_sid = admin_session.info.id;
+        app.sessions.push(admin_session);
+        admin_project.session_ids.push(admin_sid);
+        app.projects.insert(0, admin_project);
+
+        // Admin sessions should not count
+        assert_eq!(app.user_session_count(), 1);
+    }
+
+    #[test]
+    fn cannot_edit_admin_project() {
+        let backend = stub_backend();
+        let mut app = App::new(24, 120, backend, test_db());
+
+        // Add an admin project and select it
+        let admin_project = ProjectInfo::new_admin(ProjectConfig {
+            name: "Admin".to_string(),
+            repos: vec![],
+            roles: Vec::new(),
+            mcp_servers: Vec::new(),
+            sync_base_branch: None,
+            remote: None,
+            sync_strategy: SyncStrategy::default(),
+            activity_timeout_ms: None,
+            worktree_root: None,
+            default_role: None,
+            session_init_command: None,
+            id: None,
+        });
+        app.projects.push(admin_project);
+        app.active_project_index = app.projects.len() - 1;
+
+        app.open_edit_project_modal();
+        assert!(!app.show_edit_project_modal);
+        assert_eq!(
+            app.status_message.as_ref().map(|m| m.text.as_str()),
+            Some("Cannot edit admin project")
+        );
+    }
+
+    #[test]
+    fn cannot_delete_admin_project() {
+        let backend = stub_backend();
+        let mut app = App::new(24, 120, backend, test_db());
+
+        // Add an admin project and select it
+        let admin_project = ProjectInfo::new_admin(ProjectConfig {
+            name: "Admin".to_string(),
+            repos: vec![],
+            roles: Vec::new(),
+            mcp_servers: Vec::new(),
+            sync_base_branch: None,
+            remote: None,
+            sync_strategy: SyncStrategy::default(),
+            activity_timeout_ms: None,
+            worktree_root: None,
+            default_role: None,
+            session_init_command: None,
+            id: None,
+        });
+        app.projects.push(admin_project);
+        app.active_project_index = app.projects.len() - 1;
+
+        app.show_delete_project_modal();
+        assert!(!app.show_delete_project_modal_flag);
+        assert_eq!(
+            app.status_message.as_ref().map(|m| m.text.as_str()),
+            Some("Cannot delete admin project")
+        );
+    }
+
+    #[test]
+    fn cannot_close_admin_session() {
+        let backend = stub_backend();
+        let mut app = App::new(24, 120, backend.clone(), test_db());
+
+        // Add an admin project with a session and select it
+        let mut admin_project = ProjectInfo::new_admin(ProjectConfig {
+            name: "Admin".to_string(),
+            repos: vec![],
+            roles: Vec::new(),
+            mcp_servers: Vec::new(),
+            sync_base_branch: None,
+            remote: None,
+            sync_strategy: SyncStrategy::default(),
+            activity_timeout_ms: None,
+            worktree_root: None,
+            default_role: None,
+            session_init_command: None,
+            id: None,
+        });
+        let session = Session::stub("admin", &backend);
+        let sid = session.info.id;
+        app.sessions.push(session);
+        admin_project.session_ids.push(sid);
+        app.projects.push(admin_project);
+        app.active_project_index = app.projects.len() - 1;
+        app.active_index = 0;
+
+        app.handle_key(KeyCode::Char('c'), KeyModifiers::CONTROL);
+        assert_eq!(app.sessions.len(), 1); // Session not closed
+        assert_eq!(
+            app.status_message.as_ref().map(|m| m.text.as_str()),
+            Some("Cannot close admin session")
+        );
+    }
+
+    // --- Dirty worktree close tests ---
+
+    /// Give session 0 a worktree backed by a fresh git repo. Returns the
+    /// temp dir (keep it alive) and the worktree path.
+    fn attach_git_worktree(app: &mut App) -> (tempfile::TempDir, PathBuf) {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let dir = tmp.path().to_path_buf();
+        init_git_repo(&dir, &[]);
+        app.sessions[0].info.worktrees = vec![WorktreeInfo {
+            repo_path: dir.clone(),
+            worktree_path: dir.clone(),
+            branch: "feature".to_string(),
+            base_branch: "main".to_string(),
+        }];
+        (tmp, dir)
+    }
+
+    /// Run `git` in `dir` with a throwaway identity, returning trimmed stdout.
+    fn git(dir: &Path, args: &[&str]) -> String {
+        let output = std::process::Command::new("git")
+            .args(["-c", "user.name=t", "-c", "user.email=t@t"])
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .unwrap();
+        assert!(
+            output.status.success(),
+            "git {args:?} failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        String::from_utf8_lossy(&output.stdout).trim().to_string()
+    }
+
+    /// `git init` a repo on `main` with one empty commit, plus `branches`.
+    fn init_git_repo(dir: &Path, branches: &[&str]) {
+        std::fs::create_dir_all(dir).unwrap();
+        git(dir, &["init", "-q", "-b", "main"]);
+        git(dir, &["commit", "-q", "--allow-empty", "-m", "init"]);
+        for branch in branches {
+            git(dir, &["branch", branch]);
+        }
+    }
+
+    /// Project with a `frontend` (which has `develop`) and a `backend` repo.
+    fn app_with_two_repos(roles: usize) -> (tempfile::TempDir, App, PathBuf, PathBuf) {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let frontend = tmp.path().join("frontend");
+        let backend = tmp.path().join("backend");
+        init_git_repo(&frontend, &["develop"]);
+        init_git_repo(&backend, &[]);
+        let mut app = app_with_project("fullstack", vec![frontend.clone(), backend.clone()]);
+        app.projects[0].config.roles = (0..roles)
+            .map(|i| crate::session::RoleConfig {
+                name: format!("role{i}"),
+                description: String::new(),
+                permissions: Default::default(),
+            })
+            .collect();
+        (tmp, app, frontend, backend)
+    }
+
+    #[test]
+    fn multi_repo_worktree_session_creates_one_worktree_per_repo() {
+        let (_tmp, mut app, frontend, backend) = app_with_two_repos(2);
+        app.spawn_worktree_session(&[frontend.clone(), backend.clone()], "feat", "develop");
+
+        // Two roles park the spawn in the role selector.
+        assert!(app.show_role_selector);
+        let worktrees = app.pending_spawn_worktrees.clone();
+        assert_eq!(worktrees.len(), 2);
+        assert_eq!(worktrees[0].repo_path, frontend);
+        assert_eq!(worktrees[1].repo_path, backend);
+        assert!(worktrees.iter().all(|wt| wt.branch == "feat"));
+        assert!(worktrees.iter().all(|wt| wt.worktree_path.is_dir()));
+        // The backend has no `develop`, so it branches from its default.
+        assert_eq!(worktrees[0].base_branch, "develop");
+        assert_eq!(worktrees[1].base_branch, "main");
+
+        let config = app.pending_spawn_config.as_ref().unwrap();
+        assert_eq!(config.cwd.as_ref(), Some(&worktrees[0].worktree_path));
+        assert_eq!(
+            config.additional_dirs,
+            vec![worktrees[1].worktree_path.clone()]
+        );
+
+        // Cancelling removes every worktree created for the spawn.
+        app.handle_key(KeyCode::Esc, KeyModifiers::NONE);
+        assert!(app.pending_spawn_worktrees.is_empty());
+        assert!(worktrees.iter().all(|wt| !wt.worktree_path.exists()));
+    }
+
+    #[test]
+    fn multi_repo_worktrees_are_removed_when_spawn_fails() {
+        let (_tmp, mut app, frontend, backend) = app_with_two_repos(0);
+        // The stub backend refuses to spawn.
+        app.spawn_worktree_session(&[frontend.clone(), backend.clone()], "feat", "main");
+
+        assert!(app.sessions.is_empty());
+        for repo in [&frontend, &backend] {
+            let git_dir = repo.join(".git").join("thurbox-worktrees").join("feat");
+            assert!(!git_dir.exists(), "{}", git_dir.display());
+        }
+    }
+
+    #[test]
+    fn closing_multi_repo_session_removes_all_worktrees() {
+        let (_tmp, mut app, frontend, backend) = app_with_two_repos(0);
+        let worktrees: Vec<WorktreeInfo> = [&frontend, &backend]
+            .into_iter()
+            .map(|repo| {
+                let path = git::create_worktree(repo, "feat", "main", None).unwrap();
+                WorktreeInfo {
+                    repo_path: repo.clone(),
+                    worktree_path: path,
+                    branch: "feat".to_string(),
+                    base_branch: "main".to_string(),
+                }
+            })
+            .collect();
+        let mut session = Session::stub("multi", &app.backend);
+        session.info.worktrees = worktrees.clone();
+        app.projects[0].session_ids.push(session.info.id);
+        app.sessions.push(session);
+        app.active_index = 0;
+
+        app.focus = InputFocus::SessionList;
+        app.handle_key(KeyCode::Char('c'), KeyModifiers::CONTROL);
+        assert!(app.pending_delete.is_some());
+        app.finalize_pending_delete();
+
+        assert!(worktrees.iter().all(|wt| !wt.worktree_path.exists()));
+    }
+
+    #[test]
+    fn info_panel_git_status_is_cached() {
+        let mut app = app_with_sessions(1);
+        app.show_info_panel = true;
+        let (_tmp, dir) = attach_git_worktree(&mut app);
+        let now = std::time::Instant::now();
+
+        app.refresh_worktree_statuses(now);
+        let statuses = app.cached_worktree_statuses(&app.sessions[0].info.worktrees);
+        assert_eq!(statuses.len(), 1);
+        let status = statuses[0].as_ref().unwrap();
+        assert_eq!(status.branch.as_deref(), Some("main"));
+        assert_eq!(status.dirty_files, 0);
+
+        // A change inside the refresh interval is not picked up yet
+        std::fs::write(dir.join("wip.txt"), "x\n").unwrap();
+        app.refresh_worktree_statuses(now + WORKTREE_STATUS_REFRESH / 2);
+        assert_eq!(
+            app.worktree_statuses[&dir].1.as_ref().unwrap().dirty_files,
+            0
+        );
+
+        app.refresh_worktree_statuses(now + WORKTREE_STATUS_REFRESH);
+        assert_eq!(
+            app.worktree_statuses[&dir].1.as_ref().unwrap().dirty_files,
+            1
+        );
+    }
+
+    #[test]
+    fn git_status_not_read_while_info_panel_hidden() {
+        let mut app = app_with_sessions(1);
+        app.show_info_panel = false;
+        let (_tmp, _dir) = attach_git_worktree(&mut app);
+        app.refresh_worktree_statuses(std::time::Instant::now());
+        assert!(app.worktree_statuses.is_empty());
+    }
+
+    #[test]
+    fn quit_with_sessions_asks_first() {
+        let mut app = app_with_sessions(2);
+        app.handle_key(KeyCode::Char('q'), KeyModifiers::CONTROL);
+        assert!(app.show_quit_modal);
+        assert!(!app.should_quit);
+
+        // Other keys do nothing while the modal is open
+        app.handle_key(KeyCode::Char('x'), KeyModifiers::NONE);
+        assert!(app.show_quit_modal);
+        assert!(!app.should_quit);
+    }
+
+    #[test]
+    fn quit_confirm_sets_should_quit() {
+        let mut app = app_with_sessions(1);
+        app.handle_key(KeyCode::Char('q'), KeyModifiers::CONTROL);
+        app.handle_key(KeyCode::Char('y'), KeyModifiers::NONE);
+        assert!(app.should_quit);
+
+        let mut app = app_with_sessions(1);
+        app.handle_key(KeyCode::Char('q'), KeyModifiers::CONTROL);
+        app.handle_key(KeyCode::Char('q'), KeyModifiers::CONTROL);
+        assert!(app.should_quit);
+    }
+
+    #[test]
+    fn quit_esc_cancels() {
+        let mut app = app_with_sessions(1);
+        app.handle_key(KeyCode::Char('q'), KeyModifiers::CONTROL);
+        app.handle_key(KeyCode::Esc, KeyModifiers::NONE);
+        assert!(!app.show_quit_modal);
+        assert!(!app.should_quit);
+    }
+
+    #[test]
+    fn quit_without_user_sessions_is_immediate() {
+        let mut app = app_with_sessions(0);
+        app.handle_key(KeyCode::Char('q'), KeyModifiers::CONTROL);
+        assert!(!app.show_quit_modal);
+        assert!(app.should_quit);
+    }
+
+    #[test]
+    fn quit_confirmation_can_be_disabled() {
+        let mut app = app_with_sessions(1);
+        app.set_settings(settings::Settings::parse("[quit]\nconfirm = false\n").unwrap());
+        app.handle_key(KeyCode::Char('q'), KeyModifiers::CONTROL);
+        assert!(!app.show_quit_modal);
+        assert!(app.should_quit);
+    }
+
+    #[test]
+    fn close_with_clean_worktree_closes_immediately() {
+        let mut app = app_with_sessions(2);
+        app.active_index = 0;
+        let (_tmp, _dir) = attach_git_worktree(&mut app);
+        app.focus = InputFocus::SessionList;
+        app.handle_key(KeyCode::Char('c'), KeyModifiers::CONTROL);
+        assert!(!app.show_close_session_modal);
+        assert_eq!(app.sessions.len(), 1);
+    }
+
+    #[test]
+    fn close_with_dirty_worktree_is_blocked() {
+        let mut app = app_with_sessions(2);
+        app.active_index = 0;
+        let (_tmp, dir) = attach_git_worktree(&mut app);
+        std::fs::write(dir.join("wip.txt"), "unsaved work\n").unwrap();
+        let id = app.sessions[0].info.id;
+
+        app.focus = InputFocus::SessionList;
+        app.handle_key(KeyCode::Char('c'), KeyModifiers::CONTROL);
+        assert!(app.show_close_session_modal);
+        assert_eq!(app.close_session_id, Some(id));
+        assert_eq!(app.close_session_dirty, vec!["feature".to_string()]);
+        assert_eq!(app.sessions.len(), 2);
+        assert!(app.pending_delete.is_none());
+
+        // Other keys do nothing while the modal is open
+        app.handle_key(KeyCode::Char('x'), KeyModifiers::NONE);
+        assert!(app.show_close_session_modal);
+        assert_eq!(app.sessions.len(), 2);
+    }
+
+    /// Commit on top of a `base` branch so the worktree has unpushed work.
+    fn add_unpushed_commit(app: &mut App, dir: &Path) {
+        git(dir, &["branch", "base"]);
+        git(dir, &["commit", "-q", "--allow-empty", "-m", "work"]);
+        app.sessions[0].info.worktrees[0].base_branch = "base".to_string();
+    }
+
+    #[test]
+    fn close_last_session_on_unpushed_branch_asks_first() {
+        let mut app = app_with_sessions(2);
+        app.active_index = 0;
+        let (_tmp, dir) = attach_git_worktree(&mut app);
+        add_unpushed_commit(&mut app, &dir);
+
+        app.focus = InputFocus::SessionList;
+        app.handle_key(KeyCode::Char('c'), KeyModifiers::CONTROL);
+        assert!(app.show_close_session_modal);
+        assert!(app.close_session_dirty.is_empty());
+        assert_eq!(app.close_session_unpushed, vec!["feature".to_string()]);
+        assert_eq!(app.sessions.len(), 2);
+
+        app.handle_key(KeyCode::Char('y'), KeyModifiers::NONE);
+        assert!(app.close_session_unpushed.is_empty());
+        assert_eq!(app.sessions.len(), 1);
+    }
+
+    #[test]
+    fn close_asks_when_unpushed_check_fails() {
+        let mut app = app_with_sessions(2);
+        app.active_index = 0;
+        let (_tmp, _dir) = attach_git_worktree(&mut app);
+        // No upstream and no such base branch: the check itself fails
+        app.sessions[0].info.worktrees[0].base_branch = "missing".to_string();
+
+        app.focus = InputFocus::SessionList;
+        app.handle_key(KeyCode::Char('c'), KeyModifiers::CONTROL);
+        assert!(app.show_close_session_modal);
+        assert_eq!(app.close_session_unpushed, vec!["feature".to_string()]);
+        assert_eq!(app.sessions.len(), 2);
+    }
+
+    #[test]
+    fn close_unpushed_branch_shared_with_another_session_closes_immediately() {
+        let mut app = app_with_sessions(2);
+        app.active_index = 0;
+        let (_tmp, dir) = attach_git_worktree(&mut app);
+        add_unpushed_commit(&mut app, &dir);
+        app.sessions[1].info.worktrees = app.sessions[0].info.worktrees.clone();
+
+        app.focus = InputFocus::SessionList;
+        app.handle_key(KeyCode::Char('c'), KeyModifiers::CONTROL);
+        assert!(!app.show_close_session_modal);
+        assert_eq!(app.sessions.len(), 1);
+    }
+
+    #[test]
+    fn close_dirty_worktree_cancel_keeps_session() {
+        let mut app = app_with_sessions(2);
+        app.active_index = 0;
+        let (_tmp, dir) = attach_git_worktree(&mut app);
+        std::fs::write(dir.join("wip.txt"), "unsaved work\n").unwrap();
+
+        app.focus = InputFocus::SessionList;
+        app.handle_key(KeyCode::Char('c'), KeyModifiers::CONTROL);
+        app.handle_key(KeyCode::Esc, KeyModifiers::NONE);
+        assert!(!app.show_close_session_modal);
+        assert!(app.close_session_id.is_none());
+        assert_eq!(app.sessions.len(), 2);
+        assert!(dir.join("wip.txt").exists());
+    }
+
+    #[test]
+    fn close_dirty_worktree_confirm_closes_session() {
+        let mut app = app_with_sessions(2);
+        app.active_index = 0;
+        let (_tmp, dir) = attach_git_worktree(&mut app);
+        std::fs::write(dir.join("wip.txt"), "unsaved work\n").unwrap();
+        let id = app.sessions[0].info.id;
+
+        app.focus = InputFocus::SessionList;
+        app.handle_key(KeyCode::Char('c'), KeyModifiers::CONTROL);
+        // Selection moved meanwhile: the held session is still the one closed
+        app.active_index = 1;
+        app.handle_key(KeyCode::Char('y'), KeyModifiers::NONE);
+        assert!(!app.show_close_session_modal);
+        assert_eq!(app.sessions.len(), 1);
+        assert!(app.sessions.iter().all(|s| s.info.id != id));
+        assert_eq!(app.pending_delete.as_ref().map(|p| p.session_id), Some(id));
+    }
+
+    /// Give session 0 a clean linked worktree on `feature`, returning the
+    /// worktree directory.
+    fn attach_linked_worktree(app: &mut App) -> (tempfile::TempDir, PathBuf) {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let repo = tmp.path().join("repo");
+        let worktree = tmp.path().join("feature");
+        init_git_repo(&repo, &[]);
+        git(
+            &repo,
+            &[
+                "worktree",
+                "add",
+                "-q",
+                "-b",
+                "feature",
+                worktree.to_str().unwrap(),
+            ],
+        );
+        app.sessions[0].info.worktrees = vec![WorktreeInfo {
+            repo_path: repo,
+            worktree_path: worktree.clone(),
+            branch: "feature".to_string(),
+            base_branch: "main".to_string(),
+        }];
+        (tmp, worktree)
+    }
+
+    #[test]
+    fn close_clean_worktree_removes_it_by_default() {
+        let mut app = app_with_sessions(2);
+        app.active_index = 0;
+        let (_tmp, worktree) = attach_linked_worktree(&mut app);
+
+        app.focus = InputFocus::SessionList;
+        app.handle_key(KeyCode::Char('c'), KeyModifiers::CONTROL);
+        assert!(!app.show_close_session_modal);
+        assert_eq!(app.sessions.len(), 1);
+        app.finalize_pending_delete();
+        assert!(!worktree.exists());
+    }
+
+    #[test]
+    fn close_keeps_worktree_when_configured() {
+        let mut app = app_with_sessions(2);
+        app.active_index = 0;
+        app.settings.close.worktrees = settings::WorktreeOnClose::Keep;
+        let (_tmp, worktree) = attach_linked_worktree(&mut app);
+
+        app.focus = InputFocus::SessionList;
+        app.handle_key(KeyCode::Char('c'), KeyModifiers::CONTROL);
+        assert!(!app.show_close_session_modal);
+        assert_eq!(app.sessions.len(), 1);
+        app.finalize_pending_delete();
+        assert!(worktree.exists());
+    }
+
+    #[test]
+    fn close_ask_offers_keeping_or_removing_worktree() {
+        for (key, kept) in [('k', true), ('y', false)] {
+            let mut app = app_with_sessions(2);
+            app.active_index = 0;
+            app.settings.close.worktrees = settings::WorktreeOnClose::Ask;
+            let (_tmp, worktree) = attach_linked_worktree(&mut app);
+
+            app.focus = InputFocus::SessionList;
+            app.handle_key(KeyCode::Char('c'), KeyModifiers::CONTROL);
+            assert!(app.show_close_session_modal);
+            assert!(app.close_session_dirty.is_empty());
+            assert!(app.close_session_unpushed.is_empty());
+
+            app.handle_key(KeyCode::Char(key), KeyModifiers::NONE);
+            assert!(!app.show_close_session_modal);
+            assert_eq!(app.sessions.len(), 1);
+            app.finalize_pending_delete();
+            assert_eq!(worktree.exists(), kept, "key {key}");
+        }
+    }
+
+    #[test]
+    fn close_all_sessions_empties_project() {
+        let mut app = app_with_sessions(3);
+        // A session of another project is left alone
+        let mut other = ProjectInfo::new(ProjectConfig {
+            name: "Other".to_string(),
+            ..test_project_config()
+        });
+        let outsider = Session::stub("Outsider", &stub_backend());
+        other.session_ids.push(outsider.info.id);
+        let outsider_id = outsider.info.id;
+        app.sessions.push(outsider);
+        app.projects.push(other);
+        app.active_project_index = 0;
+        app.active_index = 1;
+
+        app.focus = InputFocus::ProjectList;
+        app.handle_key(KeyCode::Char('x'), KeyModifiers::NONE);
+        assert!(app.show_close_all_modal);
+        assert_eq!(app.sessions.len(), 4);
+
+        app.handle_key(KeyCode::Char('y'), KeyModifiers::NONE);
+        assert!(!app.show_close_all_modal);
+        assert!(app.projects[0].session_ids.is_empty());
+        assert_eq!(app.projects.len(), 2);
+        assert_eq!(app.sessions.len(), 1);
+        assert_eq!(app.sessions[0].info.id, outsider_id);
+        assert_eq!(app.active_index, 0);
+        assert!(app.pending_delete.is_none());
+    }
+
+    #[test]
+    fn close_all_sessions_ask_offers_keeping_worktrees() {
+        for (key, kept) in [('k', true), ('y', false)] {
+            let mut app = app_with_sessions(2);
+            app.active_index = 0;
+            app.settings.close.worktrees = settings::WorktreeOnClose::Ask;
+            let (_tmp, worktree) = attach_linked_worktree(&mut app);
+
+            app.focus = InputFocus::ProjectList;
+            app.handle_key(KeyCode::Char('x'), KeyModifiers::NONE);
+            app.handle_key(KeyCode::Char(key), KeyModifiers::NONE);
+
+            assert!(!app.show_close_all_modal);
+            assert!(app.sessions.is_empty());
+            assert_eq!(worktree.exists(), kept, "key {key}");
+        }
+    }
+
+    #[test]
+    fn close_all_sessions_k_only_applies_with_ask() {
+        let mut app = app_with_sessions(2);
+        app.active_index = 0;
+        let (_tmp, worktree) = attach_linked_worktree(&mut app);
+
+        app.focus = InputFocus::ProjectList;
+        app.handle_key(KeyCode::Char('x'), KeyModifiers::NONE);
+        app.handle_key(KeyCode::Char('k'), KeyModifiers::NONE);
+        assert!(app.show_close_all_modal);
+        assert_eq!(app.sessions.len(), 2);
+
+        app.handle_key(KeyCode::Enter, KeyModifiers::NONE);
+        assert!(app.sessions.is_empty());
+        assert!(!worktree.exists());
+    }
+
+    #[test]
+    fn close_all_sessions_esc_keeps_them() {
+        let mut app = app_with_sessions(2);
+        app.focus = InputFocus::ProjectList;
+        app.handle_key(KeyCode::Char('x'), KeyModifiers::NONE);
+        app.handle_key(KeyCode::Esc, KeyModifiers::NONE);
+        assert!(!app.show_close_all_modal);
+        assert_eq!(app.sessions.len(), 2);
+        assert_eq!(app.projects[0].session_ids.len(), 2);
+    }
+
+    // --- MCP editor command lookup tests ---
+
+    fn app_with_mcp_editor(command: &str) -> App {
+        let mut app = app_with_project("test", vec![PathBuf::from("/repo")]);
+        app.open_edit_project_modal();
+        app.prepare_new_mcp_editor();
+        app.show_mcp_editor = true;
+        app.mcp_editor_name.set("server");
+        app.mcp_editor_command.set(command);
+        app
+    }
+
+    #[test]
+    fn submit_mcp_editor_saves_resolvable_command() {
+        let exe = std::env::current_exe().unwrap();
+        let mut app = app_with_mcp_editor(&exe.display().to_string());
+        app.submit_mcp_editor();
+
+        assert!(!app.show_mcp_editor);
+        assert!(app.mcp_editor_command_warning.is_none());
+        assert_eq!(app.edit_project_mcp_servers.len(), 1);
+    }
+
+    #[test]
+    fn submit_mcp_editor_warns_on_missing_command() {
+        let mut app = app_with_mcp_editor("/nonexistent/thurbox-test-server");
+        app.submit_mcp_editor();
+
+        assert!(app.show_mcp_editor);
+        assert_eq!(
+            app.mcp_editor_command_warning.as_deref(),
+            Some("/nonexistent/thurbox-test-server")
+        );
+        assert!(app.edit_project_mcp_servers.is_empty());
+    }
+
+    #[test]
+    fn submit_mcp_editor_second_submit_saves_anyway() {
+        let mut app = app_with_mcp_editor("/nonexistent/thurbox-test-server");
+        app.submit_mcp_editor();
+        app.submit_mcp_editor();
+
+        assert!(!app.show_mcp_editor);
+        assert!(app.mcp_editor_command_warning.is_none());
+        assert_eq!(app.edit_project_mcp_servers.len(), 1);
+        assert_eq!(
+            app.edit_project_mcp_servers[0].command,
+            "/nonexistent/thurbox-test-server"
+        );
+    }
+
+    #[test]
+    fn editing_command_clears_mcp_warning() {
+        let mut app = app_with_mcp_editor("/nonexistent/thurbox-test-server");
+        app.submit_mcp_editor();
+        assert!(app.mcp_editor_command_warning.is_some());
+
+        app.mcp_editor_field = mcp_editor_modal::McpEditorField::Command;
+        app.handle_mcp_editor_key(KeyCode::Char('x'));
+        assert!(app.mcp_editor_command_warning.is_none());
+
+        // Changed command is checked again rather than saved blindly
+        app.submit_mcp_editor();
+        assert!(app.show_mcp_editor);
+        assert!(app.edit_project_mcp_servers.is_empty());
+    }
+
+    // --- Per-session MCP server tests ---
+
+    fn mcp_server(name: &str, command: &str) -> crate::session::McpServerConfig {
+        crate::session::McpServerConfig {
+            name: name.to_string(),
+            command: command.to_string(),
+            args: Vec::new(),
+            env: std::collections::HashMap::new(),
+            enabled: true,
+        }
+    }
+
+    #[test]
+    fn session_mode_m_adds_extra_mcp_server() {
+        let exe = std::env::current_exe().unwrap().display().to_string();
+        let mut app = app_with_project("test", vec![PathBuf::from("/repo")]);
+        app.spawn_session();
+        assert!(app.show_session_mode_modal);
+
+        app.handle_key(KeyCode::Char('m'), KeyModifiers::NONE);
+        assert!(app.show_mcp_editor);
+        assert!(!app.show_session_mode_modal);
+        assert_eq!(
+            app.mcp_editor_target,
+            mcp_editor_modal::McpEditorTarget::Spawn
+        );
+
+        app.mcp_editor_name.set("extra");
+        app.mcp_editor_command.set(&exe);
+        app.submit_mcp_editor();
+
+        assert!(!app.show_mcp_editor);
+        assert!(app.show_session_mode_modal);
+        assert_eq!(
+            app.mcp_editor_target,
+            mcp_editor_modal::McpEditorTarget::Project
+        );
+        assert_eq!(app.pending_spawn_mcp_servers.len(), 1);
+        assert_eq!(app.pending_spawn_mcp_servers[0].name, "extra");
+        assert!(app.projects[0].config.mcp_servers.is_empty());
+    }
+
+    #[test]
+    fn closing_spawn_mcp_editor_returns_to_session_mode() {
+        let mut app = app_with_project("test", vec![PathBuf::from("/repo")]);
+        app.spawn_session();
+        app.handle_key(KeyCode::Char('m'), KeyModifiers::NONE);
+
+        app.close_mcp_editor();
+        assert!(!app.show_mcp_editor);
+        assert!(app.show_session_mode_modal);
+        assert!(app.pending_spawn_mcp_servers.is_empty());
+    }
+
+    #[test]
+    fn cancelling_session_mode_drops_extra_mcp_servers() {
+        let mut app = app_with_project("test", vec![PathBuf::from("/repo")]);
+        app.spawn_session();
+        app.pending_spawn_mcp_servers
+            .push(mcp_server("extra", "extra-cmd"));
+
+        app.handle_key(KeyCode::Esc, KeyModifiers::NONE);
+        assert!(!app.show_session_mode_modal);
+        assert!(app.pending_spawn_mcp_servers.is_empty());
+    }
+
+    #[test]
+    fn prepare_spawn_moves_extra_mcp_servers_into_config() {
+        use crate::session::{RoleConfig, RolePermissions};
+        let role = |name: &str| RoleConfig {
+            name: name.to_string(),
+            description: String::new(),
+            permissions: RolePermissions::default(),
+        };
+        let mut app = app_with_roles(vec![role("dev"), role("review")]);
+        app.pending_spawn_mcp_servers
+            .push(mcp_server("extra", "extra-cmd"));
+
+        app.prepare_spawn(SessionConfig::default(), Vec::new());
+
+        assert!(app.show_role_selector);
+        assert!(app.pending_spawn_mcp_servers.is_empty());
+        let config = app.pending_spawn_config.as_ref().unwrap();
+        assert_eq!(config.extra_mcp_servers.len(), 1);
+        assert_eq!(config.extra_mcp_servers[0].name, "extra");
+    }
+
+    #[test]
+    fn session_mode_shell_options_set_pending_kind() {
+        let mut app = app_with_project("test", vec![std::env::temp_dir()]);
+        app.spawn_session();
+        for _ in 0..6 {
+            app.handle_key(KeyCode::Char('j'), KeyModifiers::NONE);
+        }
+        assert_eq!(app.session_mode_index, session_mode_modal::MODES.len() - 1);
+        app.session_mode_index = 3;
+        app.handle_key(KeyCode::Char('k'), KeyModifiers::NONE);
+        assert_eq!(app.session_mode_index, 2);
+
+        app.handle_key(KeyCode::Enter, KeyModifiers::NONE);
+        // The stub backend refuses to spawn; the kind was still consumed
+        assert_eq!(app.pending_session_kind, SessionKind::Claude);
+        assert!(app
+            .status_message
+            .as_ref()
+            .unwrap()
+            .text
+            .contains("Failed to start shell"));
+        assert!(matches!(
+            app.status_message.as_ref().unwrap().error,
+            Some(AppError::SpawnFailed {
+                program: "shell",
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn resume_by_id_sets_resume_session_id() {
+        use crate::session::{RoleConfig, RolePermissions};
+        let role = |name: &str| RoleConfig {
+            name: name.to_string(),
+            description: String::new(),
+            permissions: RolePermissions::default(),
+        };
+        // Two roles park the config in the role selector instead of spawning.
+        let mut app = app_with_roles(vec![role("dev"), role("review")]);
+        app.spawn_session();
+        app.session_mode_index = session_mode_modal::MODES.len() - 1;
+        app.handle_key(KeyCode::Enter, KeyModifiers::NONE);
+        assert!(!app.show_session_mode_modal);
+        assert!(app.show_resume_session_modal);
+
+        let id = "0b5e3a8c-1f2d-4e6a-9c7b-8d9e0f1a2b3c";
+        for c in format!(" {id} ").chars() {
+            app.handle_key(KeyCode::Char(c), KeyModifiers::NONE);
+        }
+        app.handle_key(KeyCode::Enter, KeyModifiers::NONE);
+
+        assert!(!app.show_resume_session_modal);
+        assert!(app.show_role_selector);
+        let config = app.pending_spawn_config.as_ref().unwrap();
+        assert_eq!(config.resume_session_id.as_deref(), Some(id));
+        assert_eq!(config.claude_session_id.as_deref(), Some(id));
+        assert_eq!(config.cwd.as_deref(), Some(Path::new("/repo")));
+    }
+
+    #[test]
+    fn resume_by_id_rejects_invalid_id() {
+        let mut app = app_with_project("test", vec![PathBuf::from("/repo")]);
+        app.spawn_session();
+        app.session_mode_index = session_mode_modal::MODES.len() - 1;
+        app.handle_key(KeyCode::Enter, KeyModifiers::NONE);
+        app.resume_session_input.set("not-an-id");
+        app.handle_key(KeyCode::Enter, KeyModifiers::NONE);
+
+        assert!(app.show_resume_session_modal);
+        assert!(app.resume_session_error.is_some());
+        assert!(app.pending_repo_path.is_some());
+
+        // Editing clears the error; Esc drops the pending spawn.
+        app.handle_key(KeyCode::Backspace, KeyModifiers::NONE);
+        assert!(app.resume_session_error.is_none());
+        app.handle_key(KeyCode::Esc, KeyModifiers::NONE);
+        assert!(!app.show_resume_session_modal);
+        assert!(app.pending_repo_path.is_none());
+    }
+
+    #[test]
+    fn prepare_spawn_shell_skips_role_selection() {
+        use crate::session::{RoleConfig, RolePermissions};
+        let role = |name: &str| RoleConfig {
+            name: name.to_string(),
+            description: String::new(),
+            permissions: RolePermissions::default(),
+        };
+        let mut app = app_with_roles(vec![role("dev"), role("review")]);
+        app.pending_session_kind = SessionKind::Shell;
+
+        app.prepare_spawn(SessionConfig::default(), Vec::new());
+
+        assert!(!app.show_role_selector);
+        assert!(app.pending_spawn_config.is_none());
+        assert_eq!(app.pending_session_kind, SessionKind::Claude);
+    }
+
+    #[test]
+    fn attach_mcp_config_writes_merged_servers() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let _guard = crate::paths::TestPathGuard::new(tmp.path());
+        let mut app = app_with_project("test", vec![PathBuf::from("/repo")]);
+        app.projects[0].config.mcp_servers =
+            vec![mcp_server("shared", "a"), mcp_server("project-only", "b")];
+        let mut config = SessionConfig {
+            claude_session_id: Some("abc".to_string()),
+            extra_mcp_servers: vec![mcp_server("shared", "override")],
+            ..SessionConfig::default()
+        };
+
+        App::attach_mcp_config(&mut config, app.projects.first());
+
+        let path = config.mcp_config.unwrap();
+        assert_eq!(path, tmp.path().join("mcp-configs").join("abc.json"));
+        let json: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap();
+        assert_eq!(json["mcpServers"]["shared"]["command"], "override");
+        assert_eq!(json["mcpServers"]["project-only"]["command"], "b");
+    }
+
+    #[test]
+    fn attach_mcp_config_skips_file_when_all_disabled() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let _guard = crate::paths::TestPathGuard::new(tmp.path());
+        let mut app = app_with_project("test", vec![PathBuf::from("/repo")]);
+        app.projects[0].config.mcp_servers = vec![crate::session::McpServerConfig {
+            enabled: false,
+            ..mcp_server("off", "a")
+        }];
+        let mut config = SessionConfig {
+            claude_session_id: Some("abc".to_string()),
+            ..SessionConfig::default()
+        };
+
+        App::attach_mcp_config(&mut config, app.projects.first());
+
+        assert!(config.mcp_config.is_none());
+    }
+
+    #[test]
+    fn space_toggles_mcp_server_enabled() {
+        let mut app = app_with_project("test", vec![PathBuf::from("/repo")]);
+        app.open_edit_project_modal();
+        app.edit_project_mcp_servers = vec![mcp_server("a", "a"), mcp_server("b", "b")];
+        app.edit_project_field = EditProjectField::McpServers;
+        app.edit_project_mcp_server_index = 1;
+
+        app.handle_key(KeyCode::Char(' '), KeyModifiers::NONE);
+        assert!(app.edit_project_mcp_servers[0].enabled);
+        assert!(!app.edit_project_mcp_servers[1].enabled);
+
+        app.handle_key(KeyCode::Char(' '), KeyModifiers::NONE);
+        assert!(app.edit_project_mcp_servers[1].enabled);
+    }
+
+    #[test]
+    fn editing_disabled_mcp_server_keeps_it_disabled() {
+        let exe = std::env::current_exe().unwrap().display().to_string();
+        let mut app = app_with_project("test", vec![PathBuf::from("/repo")]);
+        app.open_edit_project_modal();
+        app.edit_project_mcp_servers = vec![crate::session::McpServerConfig {
+            enabled: false,
+            ..mcp_server("off", &exe)
+        }];
+        app.open_mcp_server_for_editing(0);
+        app.show_mcp_editor = true;
+        app.mcp_editor_name.set("renamed");
+
+        app.submit_mcp_editor();
+
+        assert_eq!(app.edit_project_mcp_servers[0].name, "renamed");
+        assert!(!app.edit_project_mcp_servers[0].enabled);
+    }
+
+    #[test]
+    fn attach_mcp_config_skips_file_without_servers() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let _guard = crate::paths::TestPathGuard::new(tmp.path());
+        let app = app_with_project("test", vec![PathBuf::from("/repo")]);
+        let mut config = SessionConfig {
+            claude_session_id: Some("abc".to_string()),
+            ..SessionConfig::default()
+        };
+
+        App::attach_mcp_config(&mut config, app.projects.first());
+
+        assert!(config.mcp_config.is_none());
+        assert!(!tmp.path().join("mcp-configs").exists());
+    }
+
+    // --- StatusMessage / set_error / set_status tests ---
+
+    #[test]
+    fn set_error_creates_error_status() {
+        let mut app = App::new(24, 80, stub_backend(), test_db());
+        app.set_error("something failed");
+        let msg = app.status_message.as_ref().unwrap();
+        assert_eq!(msg.level, StatusLevel::Error);
+        assert_eq!(msg.text, "something failed");
+    }
+
+    #[test]
+    fn set_status_creates_typed_status() {
+        let mut app = App::new(24, 80, stub_backend(), test_db());
+        app.set_status(StatusLevel::Success, "all good");
+        let msg = app.status_message.as_ref().unwrap();
+        assert_eq!(msg.level, StatusLevel::Success);
+        assert_eq!(msg.text, "all good");
+    }
+
+    #[test]
+    fn set_status_replaces_previous() {
+        let mut app = App::new(24, 80, stub_backend(), test_db());
+        app.set_error("old error");
+        app.set_status(StatusLevel::Info, "new info");
+        let msg = app.status_message.as_ref().unwrap();
+        assert_eq!(msg.level, StatusLevel::Info);
+        assert_eq!(msg.text, "new info");
+    }
+
+    #[test]
+    fn status_message_expires_after_ttl() {
+        let mut app = App::new(24, 80, stub_backend(), test_db());
+        let ttl_ticks = app.settings.status.ttl_ms / TICK_MS;
+
+        app.set_status(StatusLevel::Info, "synced");
+        app.tick_count += ttl_ticks - 1;
+        app.expire_status_message();
+        assert!(app.status_message.is_some());
+
+        app.tick_count += 1;
+        app.expire_status_message();
+        assert!(app.status_message.is_none());
+
+        // A message set just now survives the same check
+        app.set_status(StatusLevel::Info, "fresh");
+        app.expire_status_message();
+        assert_eq!(app.status_message.as_ref().unwrap().text, "fresh");
+    }
+
+    #[test]
+    fn error_status_outlives_info_ttl() {
+        let mut app = App::new(24, 80, stub_backend(), test_db());
+        app.set_error("push failed");
+        app.tick_count += app.settings.status.ttl_ms / TICK_MS;
+        app.expire_status_message();
+        assert!(app.status_message.is_some());
+
+        app.tick_count += app.settings.status.error_ttl_ms / TICK_MS;
+        app.expire_status_message();
+        assert!(app.status_message.is_none());
+    }
+
+    #[test]
+    fn zero_ttl_keeps_status_message() {
+        let mut app = App::new(24, 80, stub_backend(), test_db());
+        app.settings.status.ttl_ms = 0;
+        app.set_status(StatusLevel::Info, "sticky");
+        app.tick_count += 1_000_000;
+        app.expire_status_message();
+        assert!(app.status_message.is_some());
+    }
+
+    // --- Worktree sync tests ---
+
+    #[test]
+    fn start_sync_with_no_worktrees_shows_info() {
+        let mut app = App::new(24, 80, stub_backend(), test_db());
+        app.start_sync();
+        assert!(!app.worktree_sync_in_progress);
+        let msg = app.status_message.as_ref().unwrap();
+        assert_eq!(msg.level, StatusLevel::Info);
+        assert_eq!(msg.text, "No worktrees to sync");
+    }
+
+    #[test]
+    fn start_sync_ignores_if_already_in_progress() {
+        let mut app = App::new(24, 80, stub_backend(), test_db());
+        app.worktree_sync_in_progress = true;
+        app.status_message = None;
+        app.start_sync();
+        // Should not set any new status message
+        assert!(app.status_message.is_none());
+    }
+
+    #[test]
+    fn ctrl_s_triggers_start_sync() {
+        let mut app = App::new(24, 80, stub_backend(), test_db());
+        app.handle_key(KeyCode::Char('s'), KeyModifiers::CONTROL);
+        // No worktrees → info message
+        let msg = app.status_message.as_ref().unwrap();
+        assert_eq!(msg.text, "No worktrees to sync");
+    }
+
+    #[test]
+    fn start_sync_with_worktree_sessions_sets_in_progress() {
+        let backend = stub_backend();
+        let config = test_project_config();
+        let mut app = App::new(24, 120, backend.clone(), test_db_with_project(&config));
+        let mut session = Session::stub("wt-session", &backend);
+        session.info.worktrees = vec![WorktreeInfo {
+            repo_path: PathBuf::from("/tmp/nonexistent-repo"),
+            worktree_path: PathBuf::from("/tmp/nonexistent-wt"),
+            branch: "test-branch".to_string(),
+            base_branch: "main".to_string(),
+        }];
+        let session_id = session.info.id;
+        app.sessions.push(session);
+        app.projects[0].session_ids.push(session_id);
+
+        app.start_sync();
+        assert!(app.worktree_sync_in_progress);
+        assert_eq!(app.worktree_sync_pending, 1);
+        let msg = app.status_message.as_ref().unwrap();
+        assert_eq!(msg.level, StatusLevel::Info);
+        assert!(msg.text.contains("Syncing 1 worktree"));
+    }
+
+    #[test]
+    fn tick_increments_tick_count() {
+        let mut app = App::new(24, 80, stub_backend(), test_db());
+        assert_eq!(app.tick_count, 0);
+        app.tick();
+        assert_eq!(app.tick_count, 1);
+        app.tick();
+        assert_eq!(app.tick_count, 2);
+    }
+
+    #[test]
+    fn idle_tick_skips_redraw_until_output_arrives() {
+        let mut app = app_with_sessions(1);
+        app.sessions[0].backdate_output(60_000);
+        app.tick();
+        app.mark_drawn();
+
+        app.tick();
+        assert!(!app.needs_redraw());
+
+        app.sessions[0].backdate_output(0);
+        app.tick();
+        assert!(app.needs_redraw());
+    }
+
+    #[test]
+    fn update_and_heartbeat_request_redraw() {
+        let mut app = app_with_sessions(0);
+        app.mark_drawn();
+        app.update(AppMessage::Resize(120, 40));
+        assert!(app.needs_redraw());
+
+        app.mark_drawn();
+        app.tick_count = REDRAW_HEARTBEAT_TICKS - 1;
+        app.tick();
+        assert!(app.needs_redraw());
+    }
+
+    #[test]
+    fn finish_sync_all_synced_shows_success() {
+        let mut app = App::new(24, 80, stub_backend(), test_db());
+        let id = SessionId::default();
+        app.worktree_sync_completed = vec![
+            (id, git::SyncResult::Synced),
+            (SessionId::default(), git::SyncResult::Synced),
+        ];
+        app.finish_sync();
+        let msg = app.status_message.as_ref().unwrap();
+        assert_eq!(msg.level, StatusLevel::Success);
+        assert!(msg.text.contains("2 worktree(s) synced"));
+    }
+
+    #[test]
+    fn finish_sync_with_errors_shows_error() {
+        let mut app = App::new(24, 80, stub_backend(), test_db());
+        app.worktree_sync_completed = vec![(
+            SessionId::default(),
+            git::SyncResult::Error("fetch failed".into()),
+        )];
+        app.finish_sync();
+        let msg = app.status_message.as_ref().unwrap();
+        assert_eq!(msg.level, StatusLevel::Error);
+        assert!(msg.text.contains("Sync failed"));
+        assert!(msg.text.contains("fetch failed"));
+        let Some(AppError::GitSync(errors)) = &msg.error else {
+            panic!("expected a sync error, got {:?}", msg.error);
+        };
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("fetch failed"));
+    }
+
+    #[test]
+    fn finish_sync_with_conflicts_shows_info() {
+        let mut app = App::new(24, 80, stub_backend(), test_db());
+        app.worktree_sync_completed = vec![
+            (SessionId::default(), git::SyncResult::Synced),
+            (
+                SessionId::default(),
+                git::SyncResult::Conflict {
+                    remote: "origin".into(),
+                    base_branch: "main".into(),
+                    strategy: SyncStrategy::Rebase,
+                    message: "merge conflict".into(),
+                },
+            ),
+        ];
+        app.finish_sync();
+        let msg = app.status_message.as_ref().unwrap();
+        assert_eq!(msg.level, StatusLevel::Info);
+        assert!(msg.text.contains("1 synced"));
+        assert!(msg.text.contains("1 conflict"));
+    }
+
+    #[test]
+    fn finish_sync_fast_forward_refusal_is_not_sent_to_claude() {
+        let mut app = App::new(24, 80, stub_backend(), test_db());
+        app.worktree_sync_completed = vec![(
+            SessionId::default(),
+            git::SyncResult::Conflict {
+                remote: "origin".into(),
+                base_branch: "main".into(),
+                strategy: SyncStrategy::FastForwardOnly,
+                message: "cannot fast-forward".into(),
+            },
+        )];
+        app.finish_sync();
+        let msg = app.status_message.as_ref().unwrap();
+        assert_eq!(msg.level, StatusLevel::Info);
+        assert!(msg.text.contains("1 cannot fast-forward"));
+        assert!(!msg.text.contains("sent to Claude"));
+    }
+
+    #[test]
+    fn finish_sync_errors_take_priority_over_conflicts() {
+        let mut app = App::new(24, 80, stub_backend(), test_db());
+        app.worktree_sync_completed = vec![
+            (
+                SessionId::default(),
+                git::SyncResult::Conflict {
+                    remote: "origin".into(),
+                    base_branch: "main".into(),
+                    strategy: SyncStrategy::Rebase,
+                    message: "merge conflict".into(),
+                },
+            ),
+            (
+                SessionId::default(),
+                git::SyncResult::Error("network error".into()),
+            ),
+        ];
+        app.finish_sync();
+        let msg = app.status_message.as_ref().unwrap();
+        assert_eq!(msg.level, StatusLevel::Error);
+        assert!(msg.text.contains("network error"));
+    }
+
+    #[test]
+    fn finish_sync_records_summary_counts() {
+        let mut app = App::new(24, 80, stub_backend(), test_db());
+        let conflict = |strategy| git::SyncResult::Conflict {
+            remote: "origin".into(),
+            base_branch: "main".into(),
+            strategy,
+            message: "conflict".into(),
+        };
+        app.worktree_sync_completed = vec![
+            (SessionId::default(), git::SyncResult::Synced),
+            (SessionId::default(), git::SyncResult::Synced),
+            (SessionId::default(), conflict(SyncStrategy::Rebase)),
+            (
+                SessionId::default(),
+                conflict(SyncStrategy::FastForwardOnly),
+            ),
+            (
+                SessionId::default(),
+                git::SyncResult::Error("fetch failed".into()),
+            ),
+        ];
+        app.finish_sync();
+
+        let summary = app.last_sync_summary.as_ref().unwrap();
+        assert_eq!(
+            *summary,
+            SyncSummary {
+                synced: 2,
+                conflicts: 1,
+                diverged: 1,
+                errors: vec!["fetch failed".to_string()],
+            }
+        );
+        assert_eq!(summary.level(), StatusLevel::Error);
+        assert_eq!(summary.badge(), "2✓ 2⚠ 1✗");
+    }
+
+    #[test]
+    fn f10_shows_last_sync_again() {
+        let mut app = App::new(24, 80, stub_backend(), test_db());
+        app.handle_key(KeyCode::F(10), KeyModifiers::NONE);
+        assert!(app
+            .status_message
+            .as_ref()
+            .is_some_and(|m| m.text.contains("No sync")));
+
+        app.worktree_sync_completed = vec![(SessionId::default(), git::SyncResult::Synced)];
+        app.finish_sync();
+        app.status_message = None;
+
+        app.handle_key(KeyCode::F(10), KeyModifiers::NONE);
+        let msg = app.status_message.as_ref().unwrap();
+        assert_eq!(msg.level, StatusLevel::Success);
+        assert_eq!(msg.text, "1 worktree(s) synced");
+    }
+
+    #[test]
+    fn drain_deferred_inputs_sends_at_correct_tick() {
+        let mut app = App::new(24, 80, stub_backend(), test_db());
+        let id = SessionId::default();
+        app.deferred_inputs.push((id, b"hello".to_vec(), 5));
+
+        // Before target tick: nothing drained
+        app.tick_count = 4;
+        app.drain_deferred_inputs();
+        assert_eq!(app.deferred_inputs.len(), 1);
+
+        // At target tick: drained (no matching session, but entry is removed)
+        app.tick_count = 5;
+        app.drain_deferred_inputs();
+        assert!(app.deferred_inputs.is_empty());
+    }
+
+    #[test]
+    fn drain_deferred_inputs_retains_future_items() {
+        let mut app = App::new(24, 80, stub_backend(), test_db());
+        let id = SessionId::default();
+        app.deferred_inputs.push((id, b"early".to_vec(), 5));
+        app.deferred_inputs.push((id, b"late".to_vec(), 20));
+
+        app.tick_count = 5;
+        app.drain_deferred_inputs();
+        assert_eq!(app.deferred_inputs.len(), 1);
+        assert_eq!(app.deferred_inputs[0].2, 20);
+    }
+
+    #[test]
+    fn send_conflict_prompt_noop_for_unknown_session() {
+        let mut app = App::new(24, 80, stub_backend(), test_db());
+        app.send_conflict_prompt(SessionId::default(), "origin", "main", SyncStrategy::Rebase);
+        assert!(app.deferred_inputs.is_empty());
+    }
+
+    #[test]
+    fn send_conflict_prompt_no_deferred_when_send_fails() {
+        let backend = stub_backend();
+        let mut app = App::new(24, 80, backend.clone(), test_db());
+        let session = Session::stub("test", &backend);
+        let sid = session.info.id;
+        app.sessions.push(session);
+
+        // Stub's channel rx is dropped, so send_input fails.
+        // No deferred input should be created.
+        app.send_conflict_prompt(sid, "origin", "main", SyncStrategy::Rebase);
+        assert!(app.deferred_inputs.is_empty());
+    }
+
+    #[test]
+    fn sync_conflict_prompt_interpolates_base_branch() {
+        let prompt = SYNC_CONFLICT_PROMPT
+            .replace("{remote}", "upstream")
+            .replace("{base}", "develop");
+        assert!(prompt.contains("git fetch upstream && git rebase upstream/develop"));
+        assert!(prompt.contains("sync this worktree with develop"));
+        assert!(!prompt.contains("main"));
+
+        let prompt = SYNC_MERGE_CONFLICT_PROMPT
+            .replace("{remote}", "origin")
+            .replace("{base}", "develop");
+        assert!(prompt.contains("git merge origin/develop"));
+        assert!(!prompt.contains("git rebase"));
+    }
+
+    #[test]
+    fn sync_settings_for_uses_project_overrides() {
+        let mut app = app_with_sessions(1);
+        let sid = app.sessions[0].info.id;
+        assert_eq!(app.sync_settings_for(sid).0, "origin");
+        let project = app
+            .projects
+            .iter_mut()
+            .find(|p| p.session_ids.contains(&sid))
+            .unwrap();
+        assert_eq!(project.config.sync_base_branch, None);
+        assert_eq!(project.config.sync_strategy, SyncStrategy::Rebase);
+
+        project.config.sync_base_branch = Some("develop".to_string());
+        project.config.remote = Some("upstream".to_string());
+        project.config.sync_strategy = SyncStrategy::Merge;
+        assert_eq!(
+            app.sync_settings_for(sid),
+            (
+                "upstream".to_string(),
+                Some("develop".to_string()),
+                SyncStrategy::Merge
+            )
+        );
+    }
+
+    #[test]
+    fn worktrees_by_repo_prefers_recorded_base_branch() {
+        let mut app = app_with_sessions(2);
+        app.projects[0].config.sync_base_branch = Some("develop".to_string());
+        for (session, base) in app.sessions.iter_mut().zip(["release/2.0", ""]) {
+            session.info.worktrees = vec![WorktreeInfo {
+                repo_path: PathBuf::from("/repo"),
+                worktree_path: PathBuf::from(format!("/repo/.git/wt/{}", session.info.name)),
+                branch: session.info.name.clone(),
+                base_branch: base.to_string(),
+            }];
+        }
+
+        let by_repo = app.worktrees_by_repo();
+        let bases: Vec<_> = by_repo[Path::new("/repo")]
+            .iter()
+            .map(|(_, _, _, _, base, _)| base.clone())
+            .collect();
+        assert_eq!(
+            bases,
+            vec![Some("release/2.0".to_string()), Some("develop".to_string())]
+        );
+    }
+
+    #[test]
+    fn poll_sync_results_triggers_finish_when_all_received() {
+        let mut app = App::new(24, 80, stub_backend(), test_db());
+        let (tx, rx) = mpsc::channel();
+        let id = SessionId::default();
+
+        tx.send((id, git::SyncResult::Synced)).unwrap();
+        drop(tx);
+
+        app.worktree_sync_in_progress = true;
+        app.worktree_sync_rx = Some(rx);
+        app.worktree_sync_pending = 1;
+
+        app.poll_sync_results();
+
+        assert!(!app.worktree_sync_in_progress);
+        assert!(app.worktree_sync_rx.is_none());
+        let msg = app.status_message.as_ref().unwrap();
+        assert_eq!(msg.level, StatusLevel::Success);
+    }
+
+    #[test]
+    fn poll_sync_results_waits_for_all_pending() {
+        let mut app = App::new(24, 80, stub_backend(), test_db());
+        let (tx, rx) = mpsc::channel();
+
+        tx.send((SessionId::default(), git::SyncResult::Synced))
+            .unwrap();
+        // Don't drop tx — second result hasn't arrived yet
+
+        app.worktree_sync_in_progress = true;
+        app.worktree_sync_rx = Some(rx);
+        app.worktree_sync_pending = 2;
+
+        app.poll_sync_results();
+
+        // Still in progress — only 1 of 2 received
+        assert!(app.worktree_sync_in_progress);
+        assert!(app.worktree_sync_rx.is_some());
+        assert_eq!(app.worktree_sync_completed.len(), 1);
+    }
+
+    fn preview_item(session_id: SessionId, counts: Result<(u32, u32), String>) -> SyncPreviewItem {
+        SyncPreviewItem {
+            session_id,
+            branch: "feat".to_string(),
+            base_branch: "main".to_string(),
+            counts,
+        }
+    }
+
+    #[test]
+    fn poll_sync_preview_opens_modal_when_all_received() {
+        let mut app = App::new(24, 80, stub_backend(), test_db());
+        let (tx, rx) = mpsc::channel();
+        app.sync_preview_rx = Some(rx);
+        app.sync_preview_pending = 2;
+
+        tx.send(preview_item(SessionId::default(), Ok((0, 3))))
+            .unwrap();
+        app.poll_sync_preview();
+        assert!(!app.show_sync_preview_modal);
+
+        tx.send(preview_item(SessionId::default(), Ok((1, 0))))
+            .unwrap();
+        app.poll_sync_preview();
+        assert!(app.show_sync_preview_modal);
+        assert!(app.sync_preview_rx.is_none());
+        assert_eq!(app.sync_preview.len(), 2);
+        // Nothing has been synced yet
+        assert!(!app.worktree_sync_in_progress);
+    }
+
+    #[test]
+    fn sync_preview_esc_cancels_without_syncing() {
+        let mut app = App::new(24, 80, stub_backend(), test_db());
+        app.sync_preview = vec![preview_item(SessionId::default(), Ok((0, 2)))];
+        app.show_sync_preview_modal = true;
+
+        app.handle_key(KeyCode::Esc, KeyModifiers::NONE);
+        assert!(!app.show_sync_preview_modal);
+        assert!(app.sync_preview.is_empty());
+        assert!(!app.worktree_sync_in_progress);
+        assert_eq!(app.status_message.as_ref().unwrap().text, "Sync cancelled");
+    }
+
+    #[test]
+    fn sync_preview_enter_starts_sync() {
+        let backend = stub_backend();
+        let config = test_project_config();
+        let mut app = App::new(24, 120, backend.clone(), test_db_with_project(&config));
+        let mut session = Session::stub("wt-session", &backend);
+        session.info.worktrees = vec![WorktreeInfo {
+            repo_path: PathBuf::from("/tmp/nonexistent-repo"),
+            worktree_path: PathBuf::from("/tmp/nonexistent-wt"),
+            branch: "test-branch".to_string(),
+            base_branch: "main".to_string(),
+        }];
+        let session_id = session.info.id;
+        app.sessions.push(session);
+        app.projects[0].session_ids.push(session_id);
+        app.sync_preview = vec![preview_item(session_id, Ok((0, 2)))];
+        app.show_sync_preview_modal = true;
+
+        app.handle_key(KeyCode::Enter, KeyModifiers::NONE);
+        assert!(!app.show_sync_preview_modal);
+        assert!(app.worktree_sync_in_progress);
+        assert_eq!(app.worktree_sync_pending, 1);
+    }
+
+    #[test]
+    fn ctrl_s_with_worktrees_fetches_preview_first() {
+        let backend = stub_backend();
+        let config = test_project_config();
+        let mut app = App::new(24, 120, backend.clone(), test_db_with_project(&config));
+        let mut session = Session::stub("wt-session", &backend);
+        session.info.worktrees = vec![WorktreeInfo {
+            repo_path: PathBuf::from("/tmp/nonexistent-repo"),
+            worktree_path: PathBuf::from("/tmp/nonexistent-wt"),
+            branch: "test-branch".to_string(),
+            base_branch: "main".to_string(),
+        }];
+        let session_id = session.info.id;
+        app.sessions.push(session);
+        app.projects[0].session_ids.push(session_id);
+
+        app.handle_key(KeyCode::Char('s'), KeyModifiers::CONTROL);
+        assert!(!app.worktree_sync_in_progress);
+        assert!(app.sync_preview_rx.is_some());
+        assert_eq!(app.sync_preview_pending, 1);
+        assert!(app
+            .status_message
+            .as_ref()
+            .unwrap()
+            .text
+            .contains("Fetching 1 worktree"));
+    }
+
+    // --- Branch push tests ---
+
+    #[test]
+    fn f5_on_non_worktree_session_reports_it() {
+        let mut app = App::new(24, 80, stub_backend(), test_db());
+        app.sessions.push(Session::stub("plain", &stub_backend()));
+        app.handle_key(KeyCode::F(5), KeyModifiers::NONE);
+        assert!(app.branch_push_rx.is_none());
+        let msg = app.status_message.as_ref().unwrap();
+        assert_eq!(msg.text, "Not a worktree session");
+    }
+
+    #[test]
+    fn add_project_with_missing_repo_prompts_for_clone_then_creates() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let dest = tmp.path().join("api");
+        let mut app = App::new(24, 80, stub_backend(), test_db());
+        let projects_before = app.projects.len();
+        app.show_add_project_modal = true;
+        app.add_project_name.set("api");
+        app.add_project_path.set(&dest.to_string_lossy());
+
+        app.submit_add_project();
+        assert_eq!(app.add_project_clone_dest.as_deref(), Some(dest.as_path()));
+        assert_eq!(app.projects.len(), projects_before);
+
+        app.handle_key(KeyCode::Enter, KeyModifiers::NONE);
+        assert!(app.status_message.as_ref().unwrap().text.contains("empty"));
+        assert!(app.add_project_clone_dest.is_some());
+        app.handle_key(KeyCode::Esc, KeyModifiers::NONE);
+        assert!(app.add_project_clone_dest.is_none());
+        assert!(app.show_add_project_modal);
+
+        // A finished clone resumes project creation
+        init_git_repo(&dest, &[]);
+        let (tx, rx) = mpsc::channel();
+        tx.send((dest.clone(), Ok(()))).unwrap();
+        app.repo_clone_rx = Some(rx);
+        app.poll_clone_result();
+
+        assert!(app.repo_clone_rx.is_none());
+        assert!(!app.show_add_project_modal);
+        assert_eq!(app.projects.len(), projects_before + 1);
+        assert_eq!(app.projects.last().unwrap().config.repos, vec![dest]);
+    }
+
+    #[test]
+    fn skipping_clone_creates_project_with_missing_repo() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let dest = tmp.path().join("notes");
+        let mut app = App::new(24, 80, stub_backend(), test_db());
+        let projects_before = app.projects.len();
+        app.show_add_project_modal = true;
+        app.add_project_name.set("notes");
+        app.add_project_path.set(&dest.to_string_lossy());
+
+        app.submit_add_project();
+        assert!(app.add_project_clone_dest.is_some());
+        app.handle_key(KeyCode::Tab, KeyModifiers::NONE);
+
+        assert!(app.add_project_clone_dest.is_none());
+        assert!(!app.show_add_project_modal);
+        assert!(app.add_project_clone_skipped.is_empty());
+        assert_eq!(app.projects.len(), projects_before + 1);
+        assert_eq!(app.projects.last().unwrap().config.repos, vec![dest]);
+    }
+
+    #[test]
+    fn failed_clone_keeps_add_project_modal_open() {
+        let mut app = App::new(24, 80, stub_backend(), test_db());
+        app.show_add_project_modal = true;
+        let (tx, rx) = mpsc::channel();
+        tx.send((
+            PathBuf::from("/src/api"),
+            Err("git clone failed: not found".to_string()),
+        ))
+        .unwrap();
+        app.repo_clone_rx = Some(rx);
+
+        app.poll_clone_result();
+
+        let msg = app.status_message.as_ref().unwrap();
+        assert_eq!(msg.level, StatusLevel::Error);
+        assert_eq!(msg.text, "git clone failed: not found");
+        assert!(app.show_add_project_modal);
+    }
+
+    #[test]
+    fn poll_push_result_reports_each_outcome() {
+        let cases = [
+            (
+                git::PushResult::Pushed,
+                StatusLevel::Success,
+                "Pushed 'feat' to origin",
+            ),
+            (
+                git::PushResult::UpToDate,
+                StatusLevel::Info,
+                "Nothing to push: 'feat' is up to date",
+            ),
+            (
+                git::PushResult::Rejected("non-fast-forward".into()),
+                StatusLevel::Error,
+                "Push of 'feat' failed: rejected (non-fast-forward)",
+            ),
+            (
+                git::PushResult::NoUpstream("no origin".into()),
+                StatusLevel::Error,
+                "Push of 'feat' failed: no upstream (no origin)",
+            ),
+        ];
+        for (result, level, text) in cases {
+            let mut app = App::new(24, 80, stub_backend(), test_db());
+            let (tx, rx) = mpsc::channel();
+            tx.send(("feat".to_string(), "origin".to_string(), vec![result]))
+                .unwrap();
+            app.branch_push_rx = Some(rx);
+
+            app.poll_push_result();
+
+            assert!(app.branch_push_rx.is_none());
+            let msg = app.status_message.as_ref().unwrap();
+            assert_eq!(msg.level, level);
+            assert_eq!(msg.text, text);
+        }
+
+        let mut app = App::new(24, 80, stub_backend(), test_db());
+        app.finish_push("feat", "upstream", vec![git::PushResult::Pushed]);
+        assert_eq!(
+            app.status_message.as_ref().unwrap().text,
+            "Pushed 'feat' to upstream"
+        );
+    }
+
+    // --- Commit modal tests ---
+
+    #[test]
+    fn f6_commits_worktree_with_multiline_message() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let repo = tmp.path().to_path_buf();
+        init_git_repo(&repo, &[]);
+        git(&repo, &["config", "user.name", "t"]);
+        git(&repo, &["config", "user.email", "t@t"]);
+        std::fs::write(repo.join("wip.txt"), "wip\n").unwrap();
+
+        let backend = stub_backend();
+        let mut app = App::new(24, 120, backend.clone(), test_db());
+        let mut session = Session::stub("wt-session", &backend);
+        session.info.worktrees = vec![WorktreeInfo {
+            repo_path: repo.clone(),
+            worktree_path: repo.clone(),
+            branch: "main".to_string(),
+            base_branch: "main".to_string(),
+        }];
+        app.sessions.push(session);
+
+        app.handle_key(KeyCode::F(6), KeyModifiers::NONE);
+        assert!(app.show_commit_modal);
+        for c in "wip".chars() {
+            app.handle_key(KeyCode::Char(c), KeyModifiers::NONE);
+        }
+        app.handle_key(KeyCode::Enter, KeyModifiers::ALT);
+        for c in "body".chars() {
+            app.handle_key(KeyCode::Char(c), KeyModifiers::NONE);
+        }
+        app.handle_key(KeyCode::Enter, KeyModifiers::NONE);
+
+        assert!(!app.show_commit_modal);
+        assert_eq!(git(&repo, &["log", "-1", "--format=%B"]), "wip\nbody");
+        let msg = app.status_message.as_ref().unwrap();
+        assert_eq!(msg.level, StatusLevel::Success);
+        assert_eq!(
+            msg.text,
+            format!(
+                "Committed {}",
+                git(&repo, &["rev-parse", "--short", "HEAD"])
+            )
+        );
+
+        // A second commit finds nothing left to commit
+        app.handle_key(KeyCode::F(6), KeyModifiers::NONE);
+        app.handle_key(KeyCode::Char('x'), KeyModifiers::NONE);
+        app.handle_key(KeyCode::Enter, KeyModifiers::NONE);
+        assert_eq!(
+            app.status_message.as_ref().unwrap().text,
+            "Nothing to commit"
+        );
+    }
+
+    #[test]
+    fn commit_modal_requires_a_message() {
+        let backend = stub_backend();
+        let mut app = App::new(24, 120, backend.clone(), test_db());
+        let mut session = Session::stub("wt-session", &backend);
+        session.info.worktrees = vec![WorktreeInfo {
+            repo_path: PathBuf::from("/tmp/nonexistent-repo"),
+            worktree_path: PathBuf::from("/tmp/nonexistent-wt"),
+            branch: "feat".to_string(),
+            base_branch: "main".to_string(),
+        }];
+        app.sessions.push(session);
+
+        app.open_commit_modal();
+        app.handle_key(KeyCode::Char(' '), KeyModifiers::NONE);
+        app.handle_key(KeyCode::Enter, KeyModifiers::NONE);
+
+        assert!(app.show_commit_modal);
+        let msg = app.status_message.as_ref().unwrap();
+        assert_eq!(msg.level, StatusLevel::Error);
+        assert_eq!(msg.text, "Commit message cannot be empty");
+    }
+
+    #[test]
+    fn commit_on_non_worktree_session_reports_it() {
+        let mut app = App::new(24, 80, stub_backend(), test_db());
+        app.sessions.push(Session::stub("plain", &stub_backend()));
+        app.handle_key(KeyCode::F(6), KeyModifiers::NONE);
+        assert!(!app.show_commit_modal);
+        assert_eq!(
+            app.status_message.as_ref().unwrap().text,
+            "Not a worktree session"
+        );
+    }
+
+    // --- Scrollback export tests ---
+
+    #[test]
+    fn f7_exports_full_scrollback_to_file() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join("out").join("scrollback.txt");
+        let mut app = app_with_sessions(1);
+        {
+            let mut parser = app.sessions[0].parser.lock().unwrap();
+            // More lines than the 24-row screen, so most are in scrollback
+            *parser = vt100::Parser::new(24, 80, 100);
+            for i in 0..60 {
+                parser.process(format!("line {i}   \r\n").as_bytes());
+            }
+        }
+
+        app.handle_key(KeyCode::F(7), KeyModifiers::NONE);
+        assert_eq!(app.scrollback_export.as_ref().unwrap().len(), 60);
+        app.export_path_input.set(&path.to_string_lossy());
+        app.handle_key(KeyCode::Enter, KeyModifiers::NONE);
+
+        assert!(app.scrollback_export.is_none());
+        let contents = std::fs::read_to_string(&path).unwrap();
+        let lines: Vec<&str> = contents.lines().collect();
+        assert_eq!(lines.len(), 60);
+        assert_eq!(lines[0], "line 0");
+        assert_eq!(lines[59], "line 59");
+        assert_eq!(
+            app.status_message.as_ref().unwrap().text,
+            format!("Exported 60 lines to {}", path.display())
+        );
+    }
+
+    #[test]
+    fn export_modal_rejects_empty_path() {
+        let mut app = app_with_sessions(1);
+        app.open_export_modal();
+        app.export_path_input.clear();
+        app.handle_key(KeyCode::Enter, KeyModifiers::NONE);
+
+        assert!(app.scrollback_export.is_some());
+        let msg = app.status_message.as_ref().unwrap();
+        assert_eq!(msg.level, StatusLevel::Error);
+        assert_eq!(msg.text, "Export path cannot be empty");
+
+        app.handle_key(KeyCode::Esc, KeyModifiers::NONE);
+        assert!(app.scrollback_export.is_none());
+    }
+
+    // --- Lazy restore tests ---
+
+    /// Backend that records every call and adopts or spawns whatever it is
+    /// asked for.
+    #[derive(Default)]
+    struct RecordingBackend {
+        calls: std::sync::Mutex<Vec<&'static str>>,
+        /// What `is_dead` reports for every pane.
+        dead: std::sync::atomic::AtomicBool,
+        /// Bytes written to spawned sessions.
+        input: Arc<std::sync::Mutex<Vec<u8>>>,
+    }
+
+    impl RecordingBackend {
+        fn record(&self, call: &'static str) {
+            self.calls.lock().unwrap().push(call);
+        }
+
+        fn calls(&self) -> Vec<&'static str> {
+            self.calls.lock().unwrap().clone()
+        }
+
+        fn input(&self) -> Vec<u8> {
+            self.input.lock().unwrap().clone()
+        }
+    }
+
+    /// Session input that appends to [`RecordingBackend::input`].
+    struct RecordedInput(Arc<std::sync::Mutex<Vec<u8>>>);
+
+    impl std::io::Write for RecordedInput {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl SessionBackend for RecordingBackend {
+        fn name(&self) -> &str {
+            "recording"
+        }
+        fn check_available(&self) -> anyhow::Result<()> {
+            Ok(())
+        }
+        fn ensure_ready(&self) -> anyhow::Result<()> {
+            Ok(())
+        }
+        fn spawn(
+            &self,
+            _: &str,
+            _: &str,
+            _: &[String],
+            _: Option<&Path>,
+            _: &std::collections::HashMap<String, String>,
+            _: u16,
+            _: u16,
+        ) -> anyhow::Result<crate::claude::backend::SpawnedSession> {
+            self.record("spawn");
+            Ok(crate::claude::backend::SpawnedSession {
+                backend_id: "%new".to_string(),
+                output: Box::new(std::io::empty()),
+                input: Box::new(RecordedInput(Arc::clone(&self.input))),
+                initial_screen: Vec::new(),
+            })
+        }
+        fn adopt(
+            &self,
+            _: &str,
+            _: u16,
+            _: u16,
+        ) -> anyhow::Result<crate::claude::backend::AdoptedSession> {
+            self.record("adopt");
+            Ok(crate::claude::backend::AdoptedSession {
+                output: Box::new(std::io::empty()),
+                input: Box::new(std::io::sink()),
+                initial_screen: Vec::new(),
+            })
+        }
+        fn discover(&self) -> anyhow::Result<Vec<DiscoveredSession>> {
+            self.record("discover");
+            Ok(["%1", "%2"]
+                .into_iter()
+                .map(|id| DiscoveredSession {
+                    backend_id: id.to_string(),
+                    name: format!("tb-{}", &id[1..]),
+                    is_alive: true,
+                })
+                .collect())
+        }
+        fn resize(&self, _: &str, _: u16, _: u16) -> anyhow::Result<()> {
+            self.record("resize");
+            Ok(())
+        }
+        fn is_dead(&self, _: &str) -> anyhow::Result<bool> {
+            Ok(self.dead.load(std::sync::atomic::Ordering::SeqCst))
+        }
+        fn kill(&self, _: &str) -> anyhow::Result<()> {
+            self.record("kill");
+            Ok(())
+        }
+        fn detach(&self, _: &str) -> anyhow::Result<()> {
+            self.record("detach");
+            Ok(())
+        }
+        fn rename(&self, _: &str, _: &str) -> anyhow::Result<()> {
+            self.record("rename");
+            Ok(())
+        }
+    }
+
+    fn persisted_session(
+        name: &str,
+        backend_id: &str,
+        project_id: ProjectId,
+    ) -> sync::SharedSession {
+        sync::SharedSession {
+            id: SessionId::default(),
+            name: name.to_string(),
+            project_id,
+            role: String::new(),
+            backend_id: backend_id.to_string(),
+            backend_type: "recording".to_string(),
+            claude_session_id: Some(format!("claude-{name}")),
+            cwd: None,
+            additional_dirs: Vec::new(),
+            worktrees: Vec::new(),
+            shell_backend_id: None,
+            is_pinned: false,
+            label: None,
+            label_color: None,
+            created_at: None,
+            tombstone: false,
+            tombstone_at: None,
+        }
+    }
+
+    fn lazily_restored_app() -> (App, Arc<RecordingBackend>) {
+        let backend = Arc::new(RecordingBackend::default());
+        let mut app = App::new(
+            24,
+            120,
+            backend.clone(),
+            test_db_with_project(&test_project_config()),
+        );
+        app.settings.restore.lazy = true;
+        let project_id = app.projects[0].id;
+        app.restore_sessions(
+            vec![
+                persisted_session("1", "%1", project_id),
+                persisted_session("2", "%2", project_id),
+            ],
+            2,
+        );
+        (app, backend)
+    }
+
+    #[test]
+    fn lazy_restore_leaves_backend_untouched() {
+        let (mut app, backend) = lazily_restored_app();
+        app.handle_resize(140, 40);
+
+        assert!(backend.calls().is_empty());
+        assert_eq!(app.sessions.len(), 2);
+        assert!(app.sessions.iter().all(Session::is_dormant));
+        assert_eq!(app.sessions[0].backend_id(), "%1");
+        assert_eq!(app.active_index, 1);
+    }
+
+    #[test]
+    fn closing_dormant_session_kills_its_shell_pane() {
+        let backend = Arc::new(RecordingBackend::default());
+        let mut app = App::new(
+            24,
+            120,
+            backend.clone(),
+            test_db_with_project(&test_project_config()),
+        );
+        app.settings.restore.lazy = true;
+        let project_id = app.projects[0].id;
+        let mut session = persisted_session("1", "%1", project_id);
+        session.shell_backend_id = Some("%9".to_string());
+        app.restore_sessions(vec![session], 1);
+        assert!(app.sessions[0].is_dormant());
+
+        app.focus = InputFocus::SessionList;
+        app.handle_key(KeyCode::Char('x'), KeyModifiers::CONTROL);
+        app.finalize_pending_delete();
+
+        assert_eq!(backend.calls(), ["kill", "kill"]);
+    }
+
+    #[tokio::test]
+    async fn dormant_session_attaches_on_first_focus() {
+        let (mut app, backend) = lazily_restored_app();
+        let ids: Vec<SessionId> = app.sessions.iter().map(|s| s.info.id).collect();
+
+        // The focused session wakes on the next tick; the other stays dormant.
+        app.tick();
+        assert_eq!(backend.calls(), ["discover", "adopt"]);
+        assert!(!app.sessions[1].is_dormant());
+        assert_eq!(app.sessions[0].info.status, SessionStatus::Dormant);
+
+        app.activate_session(0);
+        assert_eq!(backend.calls(), ["discover", "adopt", "discover", "adopt"]);
+        assert!(!app.sessions[0].is_dormant());
+        assert_eq!(app.sessions[0].info.name, "1");
+        assert_eq!(app.sessions[0].backend_id(), "%1");
+        assert_eq!(app.active_index, 0);
+        assert_eq!(app.projects[0].session_ids, ids);
+    }
+
+    #[tokio::test]
+    async fn remembered_role_skips_the_selector_until_forgotten() {
+        let backend = Arc::new(RecordingBackend::default());
+        let mut config = test_project_config();
+        config.roles = ["dev", "review"]
+            .map(|name| crate::session::RoleConfig {
+                name: name.to_string(),
+                description: String::new(),
+                permissions: Default::default(),
+            })
+            .to_vec();
+        let mut app = App::new(24, 120, backend.clone(), test_db_with_project(&config));
+
+        app.prepare_spawn(SessionConfig::default(), Vec::new());
+        assert!(app.show_role_selector);
+        app.handle_key(KeyCode::Char('j'), KeyModifiers::NONE);
+        app.handle_key(KeyCode::Char('r'), KeyModifiers::NONE);
+        app.handle_key(KeyCode::Enter, KeyModifiers::NONE);
+        assert_eq!(app.sessions.last().unwrap().info.role, "review");
+
+        // The next spawn in the project applies the remembered role.
+        app.prepare_spawn(SessionConfig::default(), Vec::new());
+        assert!(!app.show_role_selector);
+        assert_eq!(app.sessions.len(), 2);
+        assert_eq!(app.sessions[1].info.role, "review");
+
+        app.focus = InputFocus::SessionList;
+        app.handle_key(KeyCode::Char('R'), KeyModifiers::NONE);
+        app.prepare_spawn(SessionConfig::default(), Vec::new());
+        assert!(app.show_role_selector);
+        assert!(!app.role_selector_remember);
+    }
+
+    #[tokio::test]
+    async fn role_selector_without_remember_asks_every_time() {
+        let backend = Arc::new(RecordingBackend::default());
+        let mut config = test_project_config();
+        config.roles = ["dev", "review"]
+            .map(|name| crate::session::RoleConfig {
+                name: name.to_string(),
+                description: String::new(),
+                permissions: Default::default(),
+            })
+            .to_vec();
+        let mut app = App::new(24, 120, backend.clone(), test_db_with_project(&config));
+
+        app.prepare_spawn(SessionConfig::default(), Vec::new());
+        app.handle_key(KeyCode::Enter, KeyModifiers::NONE);
+        app.prepare_spawn(SessionConfig::default(), Vec::new());
+
+        assert!(app.show_role_selector);
+        assert!(app.project_default_spawn_role.is_empty());
     }
 
-    #[test]
-    fn session_to_shared_maps_additional_dirs() {
-        let backend = stub_backend();
+    #[tokio::test]
+    async fn restore_adopts_renamed_session_by_backend_id() {
+        let backend = Arc::new(RecordingBackend::default());
         let mut app = App::new(
             24,
             120,
             backend.clone(),
             test_db_with_project(&test_project_config()),
         );
+        let project_id = app.projects[0].id;
+        // Window "%2" is still labelled tb-2 from before the rename.
+        app.restore_sessions(vec![persisted_session("renamed", "%2", project_id)], 1);
 
-        let mut session = Session::stub("MultiDir", &backend);
-        session.info.additional_dirs = vec![PathBuf::from("/repo2"), PathBuf::from("/repo3")];
+        assert_eq!(backend.calls(), ["discover", "adopt", "rename"]);
+        assert_eq!(app.sessions.len(), 1);
+        assert_eq!(app.sessions[0].backend_id(), "%2");
+        assert_eq!(app.sessions[0].info.name, "renamed");
+    }
 
-        let sid = session.info.id;
-        app.sessions.push(session);
-        app.projects[0].session_ids.push(sid);
+    #[tokio::test]
+    async fn restore_matches_window_name_without_renaming() {
+        let backend = Arc::new(RecordingBackend::default());
+        let mut app = App::new(
+            24,
+            120,
+            backend.clone(),
+            test_db_with_project(&test_project_config()),
+        );
+        let project_id = app.projects[0].id;
+        // The stored pane id is stale; the window name still matches.
+        app.restore_sessions(vec![persisted_session("1", "%9", project_id)], 1);
 
-        let shared = app.session_to_shared(&app.sessions[0]);
-        assert_eq!(shared.additional_dirs.len(), 2);
-        assert_eq!(shared.additional_dirs[0], PathBuf::from("/repo2"));
-        assert_eq!(shared.additional_dirs[1], PathBuf::from("/repo3"));
+        assert_eq!(backend.calls(), ["discover", "adopt"]);
+        assert_eq!(app.sessions[0].backend_id(), "%1");
     }
 
-    #[test]
-    fn user_session_count_excludes_admin_project() {
-        let backend = stub_backend();
-        let config = ProjectConfig {
-            name: "UserProj".to_string(),
-            repos: vec![PathBuf::from("/repo")],
-            roles: Vec::new(),
-            mcp_servers: Vec::new(),
-            id: None,
-        };
-        let mut app = App::new(24, 120, backend.clone(), test_db_with_project(&config));
+    // --- Dead session detection tests ---
 
-        // User project has no sessions
-        assert_eq!(app.user_session_count(), 0);
+    #[test]
+    fn focusing_dead_session_marks_it_errored() {
+        let backend = Arc::new(RecordingBackend::default());
+        let dyn_backend: Arc<dyn SessionBackend> = backend.clone();
+        let mut app = App::new(
+            24,
+            120,
+            dyn_backend.clone(),
+            test_db_with_project(&test_project_config()),
+        );
+        for name in ["alive", "gone"] {
+            let session = Session::stub(name, &dyn_backend);
+            app.projects[0].session_ids.push(session.info.id);
+            app.sessions.push(session);
+        }
+        app.active_index = 0;
+        backend
+            .dead
+            .store(true, std::sync::atomic::Ordering::SeqCst);
 
-        // Add a session to the user project
-        let session = Session::stub("user-1", &backend);
-        let sid = session.info.id;
-        app.sessions.push(session);
-        app.projects[0].session_ids.push(sid);
-        assert_eq!(app.user_session_count(), 1);
+        app.switch_session_forward();
 
-        // Add an admin project with a session
-        let admin_config = ProjectConfig {
-            name: "Admin".to_string(),
-            repos: vec![],
-            roles: Vec::new(),
-            mcp_servers: Vec::new(),
-            id: None,
-        };
-        let mut admin_project = ProjectInfo::new_admin(admin_config);
-        let admin_session = Session::stub("admin", &backend);
-        let admin_sid = admin_session.info.id;
-        app.sessions.push(admin_session);
-        admin_project.session_ids.push(admin_sid);
-        app.projects.insert(0, admin_project);
+        assert_eq!(app.active_index, 1);
+        assert!(app.sessions[1].info.backend_dead);
+        assert_eq!(app.sessions[1].info.status, SessionStatus::Error);
+        assert_eq!(
+            app.status_message.as_ref().unwrap().text,
+            "'gone' died. Ctrl+R to restart"
+        );
+        // Status recomputation keeps the error until the session restarts
+        app.tick();
+        assert_eq!(app.sessions[1].info.status, SessionStatus::Error);
+        assert!(!app.sessions[0].info.backend_dead);
+    }
 
-        // Admin sessions should not count
-        assert_eq!(app.user_session_count(), 1);
+    #[test]
+    fn focusing_live_session_keeps_status() {
+        let mut app = app_with_sessions(2);
+        app.switch_session_forward();
+        assert!(!app.sessions[1].info.backend_dead);
+        assert_ne!(app.sessions[1].info.status, SessionStatus::Error);
     }
 
+    // --- Missing working directory tests ---
+
     #[test]
-    fn cannot_edit_admin_project() {
-        let backend = stub_backend();
-        let mut app = App::new(24, 120, backend, test_db());
+    fn spawn_into_missing_cwd_reports_error_without_spawning() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let gone = tmp.path().join("deleted-repo");
+        let backend = Arc::new(RecordingBackend::default());
+        let mut app = App::new(
+            24,
+            120,
+            backend.clone(),
+            test_db_with_project(&test_project_config()),
+        );
+        let config = SessionConfig {
+            cwd: Some(gone.clone()),
+            ..SessionConfig::default()
+        };
 
-        // Add an admin project and select it
-        let admin_project = ProjectInfo::new_admin(ProjectConfig {
-            name: "Admin".to_string(),
-            repos: vec![],
-            roles: Vec::new(),
-            mcp_servers: Vec::new(),
-            id: None,
-        });
-        app.projects.push(admin_project);
-        app.active_project_index = app.projects.len() - 1;
+        app.do_spawn_session("S".to_string(), &config, Vec::new(), None);
 
-        app.open_edit_project_modal();
-        assert!(!app.show_edit_project_modal);
+        assert!(backend.calls().is_empty());
+        assert!(app.sessions.is_empty());
+        let status = app.status_message.as_ref().unwrap();
+        assert_eq!(status.level, StatusLevel::Error);
         assert_eq!(
-            app.status_message.as_ref().map(|m| m.text.as_str()),
-            Some("Cannot edit admin project")
+            status.text,
+            format!("Working directory no longer exists: {}", gone.display())
         );
+        assert_eq!(status.error, Some(AppError::MissingCwd(gone)));
     }
 
     #[test]
-    fn cannot_delete_admin_project() {
-        let backend = stub_backend();
-        let mut app = App::new(24, 120, backend, test_db());
+    fn restore_with_missing_cwd_leaves_error_placeholder() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let gone = tmp.path().join("deleted-repo");
+        let backend = Arc::new(RecordingBackend::default());
+        let mut app = App::new(
+            24,
+            120,
+            backend.clone(),
+            test_db_with_project(&test_project_config()),
+        );
+        let project_id = app.projects[0].id;
+        let mut shared = persisted_session("old", "%9", project_id);
+        shared.cwd = Some(gone.clone());
 
-        // Add an admin project and select it
-        let admin_project = ProjectInfo::new_admin(ProjectConfig {
-            name: "Admin".to_string(),
-            repos: vec![],
-            roles: Vec::new(),
-            mcp_servers: Vec::new(),
-            id: None,
-        });
-        app.projects.push(admin_project);
-        app.active_project_index = app.projects.len() - 1;
+        app.restore_sessions(vec![shared.clone()], 1);
+        app.tick();
 
-        app.show_delete_project_modal();
-        assert!(!app.show_delete_project_modal_flag);
-        assert_eq!(
-            app.status_message.as_ref().map(|m| m.text.as_str()),
-            Some("Cannot delete admin project")
+        assert_eq!(backend.calls(), ["discover"]);
+        assert_eq!(app.sessions.len(), 1);
+        assert_eq!(app.sessions[0].info.id, shared.id);
+        assert!(app.sessions[0].info.cwd_missing);
+        assert_eq!(app.sessions[0].info.status, SessionStatus::Error);
+        assert_eq!(app.projects[0].session_ids, vec![shared.id]);
+        assert!(app
+            .status_message
+            .as_ref()
+            .unwrap()
+            .text
+            .contains(&gone.display().to_string()));
+        // Kept in the DB so it is not lost before the user decides
+        assert_eq!(app.db.list_active_sessions().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn restart_respawns_placeholder_once_cwd_is_back() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let dir = tmp.path().join("repo");
+        let backend = Arc::new(RecordingBackend::default());
+        let mut app = App::new(
+            24,
+            120,
+            backend.clone(),
+            test_db_with_project(&test_project_config()),
         );
+        let project_id = app.projects[0].id;
+        let mut shared = persisted_session("old", "%9", project_id);
+        shared.cwd = Some(dir.clone());
+        app.restore_sessions(vec![shared], 1);
+
+        // Still missing: the restart is refused with the same message
+        app.handle_key(KeyCode::Char('r'), KeyModifiers::CONTROL);
+        assert_eq!(backend.calls(), ["discover"]);
+        assert!(app.sessions[0].info.cwd_missing);
+
+        std::fs::create_dir(&dir).unwrap();
+        app.handle_key(KeyCode::Char('r'), KeyModifiers::CONTROL);
+        assert_eq!(backend.calls(), ["discover", "spawn"]);
+        assert!(!app.sessions[0].info.cwd_missing);
+        assert!(!app.sessions[0].is_dormant());
+        app.tick();
+        assert_ne!(app.sessions[0].info.status, SessionStatus::Error);
     }
 
-    #[test]
-    fn cannot_close_admin_session() {
-        let backend = stub_backend();
-        let mut app = App::new(24, 120, backend.clone(), test_db());
+    // --- Last active selection tests ---
 
-        // Add an admin project with a session and select it
-        let mut admin_project = ProjectInfo::new_admin(ProjectConfig {
-            name: "Admin".to_string(),
-            repos: vec![],
-            roles: Vec::new(),
-            mcp_servers: Vec::new(),
-            id: None,
-        });
-        let session = Session::stub("admin", &backend);
-        let sid = session.info.id;
-        app.sessions.push(session);
-        admin_project.session_ids.push(sid);
-        app.projects.push(admin_project);
-        app.active_project_index = app.projects.len() - 1;
-        app.active_index = 0;
+    #[test]
+    fn save_state_remembers_active_project_and_session() {
+        let mut app = app_with_sessions(2);
+        app.active_index = 1;
+        app.save_state();
 
-        app.handle_key(KeyCode::Char('c'), KeyModifiers::CONTROL);
-        assert_eq!(app.sessions.len(), 1); // Session not closed
         assert_eq!(
-            app.status_message.as_ref().map(|m| m.text.as_str()),
-            Some("Cannot close admin session")
+            app.db.get_last_active().unwrap(),
+            (Some(app.projects[0].id), Some(app.sessions[1].info.id))
         );
     }
 
-    // --- StatusMessage / set_error / set_status tests ---
-
     #[test]
-    fn set_error_creates_error_status() {
-        let mut app = App::new(24, 80, stub_backend(), test_db());
-        app.set_error("something failed");
-        let msg = app.status_message.as_ref().unwrap();
-        assert_eq!(msg.level, StatusLevel::Error);
-        assert_eq!(msg.text, "something failed");
+    fn app_new_selects_remembered_project() {
+        let db = test_db_with_project(&test_project_config());
+        let other = ProjectId::default();
+        db.insert_project(other, "other", &[]).unwrap();
+        db.set_last_active(Some(other), None).unwrap();
+
+        let app = App::new(24, 120, stub_backend(), db);
+        assert_eq!(app.projects[app.active_project_index].id, other);
     }
 
     #[test]
-    fn set_status_creates_typed_status() {
-        let mut app = App::new(24, 80, stub_backend(), test_db());
-        app.set_status(StatusLevel::Success, "all good");
-        let msg = app.status_message.as_ref().unwrap();
-        assert_eq!(msg.level, StatusLevel::Success);
-        assert_eq!(msg.text, "all good");
+    fn restore_selects_remembered_session() {
+        let backend = Arc::new(RecordingBackend::default());
+        let mut app = App::new(
+            24,
+            120,
+            backend.clone(),
+            test_db_with_project(&test_project_config()),
+        );
+        app.settings.restore.lazy = true;
+        let project_id = app.projects[0].id;
+        let sessions = vec![
+            persisted_session("1", "%1", project_id),
+            persisted_session("2", "%2", project_id),
+            persisted_session("3", "%3", project_id),
+        ];
+        app.db
+            .set_last_active(Some(project_id), Some(sessions[1].id))
+            .unwrap();
+
+        app.restore_sessions(sessions.clone(), 3);
+
+        assert_eq!(app.active_index, 1);
+        assert_eq!(app.sessions[1].info.id, sessions[1].id);
+        assert_eq!(app.active_project_index, 0);
+        assert!(backend.calls().is_empty());
     }
 
     #[test]
-    fn set_status_replaces_previous() {
-        let mut app = App::new(24, 80, stub_backend(), test_db());
-        app.set_error("old error");
-        app.set_status(StatusLevel::Info, "new info");
-        let msg = app.status_message.as_ref().unwrap();
-        assert_eq!(msg.level, StatusLevel::Info);
-        assert_eq!(msg.text, "new info");
+    fn stale_last_active_ids_fall_back() {
+        let db = test_db_with_project(&test_project_config());
+        db.set_last_active(Some(ProjectId::default()), Some(SessionId::default()))
+            .unwrap();
+        let backend = Arc::new(RecordingBackend::default());
+        let mut app = App::new(24, 120, backend.clone(), db);
+        assert_eq!(app.active_project_index, 0);
+
+        app.settings.restore.lazy = true;
+        let project_id = app.projects[0].id;
+        app.restore_sessions(
+            vec![
+                persisted_session("1", "%1", project_id),
+                persisted_session("2", "%2", project_id),
+            ],
+            2,
+        );
+        assert_eq!(app.active_index, 1);
+    }
+
+    // --- Duplicate session tests ---
+
+    fn app_with_two_roles() -> App {
+        let role = |name: &str| RoleConfig {
+            name: name.to_string(),
+            description: String::new(),
+            permissions: RolePermissions::default(),
+        };
+        let config = ProjectConfig {
+            roles: vec![role("developer"), role("reviewer")],
+            ..test_project_config()
+        };
+        let backend: Arc<dyn SessionBackend> = Arc::new(RecordingBackend::default());
+        let mut app = App::new(24, 120, backend.clone(), test_db_with_project(&config));
+        let mut source = Session::stub("1", &backend);
+        source.info.role = "reviewer".to_string();
+        source.info.cwd = Some(std::env::temp_dir());
+        source.info.claude_session_id = Some("claude-1".to_string());
+        app.projects[0].session_ids.push(source.info.id);
+        app.sessions.push(source);
+        app.session_counter = 1;
+        app.focus = InputFocus::SessionList;
+        app
+    }
+
+    #[tokio::test]
+    async fn duplicate_inherits_role_and_cwd_with_fresh_identity() {
+        let mut app = app_with_two_roles();
+        app.handle_key(KeyCode::Char('D'), KeyModifiers::SHIFT);
+
+        assert!(!app.show_role_selector);
+        assert_eq!(app.sessions.len(), 2);
+        let (source, copy) = (&app.sessions[0].info, &app.sessions[1].info);
+        assert_eq!(copy.role, "reviewer");
+        assert_eq!(copy.cwd, source.cwd);
+        assert_eq!(copy.name, "2");
+        assert_ne!(copy.id, source.id);
+        assert!(copy.claude_session_id.is_some());
+        assert_ne!(copy.claude_session_id, source.claude_session_id);
+        assert_eq!(app.projects[0].session_ids.len(), 2);
+        assert!(app.pending_spawn_role.is_none());
+    }
+
+    #[tokio::test]
+    async fn prepare_spawn_auto_assigns_default_role() {
+        let config = ProjectConfig {
+            roles: vec![
+                RoleConfig {
+                    name: "developer".to_string(),
+                    description: String::new(),
+                    permissions: RolePermissions::default(),
+                },
+                RoleConfig {
+                    name: "reviewer".to_string(),
+                    description: String::new(),
+                    permissions: RolePermissions {
+                        permission_mode: Some("plan".to_string()),
+                        ..RolePermissions::default()
+                    },
+                },
+            ],
+            default_role: Some("reviewer".to_string()),
+            ..test_project_config()
+        };
+        let backend: Arc<dyn SessionBackend> = Arc::new(RecordingBackend::default());
+        let mut app = App::new(24, 120, backend, test_db_with_project(&config));
+
+        app.prepare_spawn(SessionConfig::default(), Vec::new());
+
+        assert!(!app.show_role_selector);
+        assert!(app.pending_spawn_config.is_none());
+        assert_eq!(app.sessions.len(), 1);
+        assert_eq!(app.sessions[0].info.role, "reviewer");
     }
 
-    // --- Worktree sync tests ---
+    #[tokio::test]
+    async fn spawn_types_project_startup_command() {
+        let config = ProjectConfig {
+            session_init_command: Some("nvm use".to_string()),
+            ..test_project_config()
+        };
+        let backend = Arc::new(RecordingBackend::default());
+        let mut app = App::new(24, 120, backend.clone(), test_db_with_project(&config));
 
-    #[test]
-    fn start_sync_with_no_worktrees_shows_info() {
-        let mut app = App::new(24, 80, stub_backend(), test_db());
-        app.start_sync();
-        assert!(!app.worktree_sync_in_progress);
-        let msg = app.status_message.as_ref().unwrap();
-        assert_eq!(msg.level, StatusLevel::Info);
-        assert_eq!(msg.text, "No worktrees to sync");
-    }
+        app.prepare_spawn(SessionConfig::default(), Vec::new());
+        assert_eq!(app.sessions.len(), 1);
+        let id = app.sessions[0].info.id;
+        // Enter follows once the paste delay has passed
+        assert!(app
+            .deferred_inputs
+            .iter()
+            .any(|(session, data, _)| *session == id && data == b"\r"));
 
-    #[test]
-    fn start_sync_ignores_if_already_in_progress() {
-        let mut app = App::new(24, 80, stub_backend(), test_db());
-        app.worktree_sync_in_progress = true;
-        app.status_message = None;
-        app.start_sync();
-        // Should not set any new status message
-        assert!(app.status_message.is_none());
+        // The writer task delivers the command on its next turn
+        for _ in 0..10 {
+            if !backend.input().is_empty() {
+                break;
+            }
+            tokio::task::yield_now().await;
+        }
+        assert_eq!(backend.input(), b"nvm use");
     }
 
-    #[test]
-    fn ctrl_s_triggers_start_sync() {
-        let mut app = App::new(24, 80, stub_backend(), test_db());
-        app.handle_key(KeyCode::Char('s'), KeyModifiers::CONTROL);
-        // No worktrees → info message
-        let msg = app.status_message.as_ref().unwrap();
-        assert_eq!(msg.text, "No worktrees to sync");
-    }
+    #[tokio::test]
+    async fn send_prompt_modal_pastes_text_and_defers_enter() {
+        let backend = Arc::new(RecordingBackend::default());
+        let mut app = App::new(
+            24,
+            120,
+            backend.clone(),
+            test_db_with_project(&test_project_config()),
+        );
+        app.prepare_spawn(SessionConfig::default(), Vec::new());
+        let id = app.sessions[0].info.id;
 
-    #[test]
-    fn start_sync_with_worktree_sessions_sets_in_progress() {
-        let backend = stub_backend();
-        let config = test_project_config();
-        let mut app = App::new(24, 120, backend.clone(), test_db_with_project(&config));
-        let mut session = Session::stub("wt-session", &backend);
-        session.info.worktrees = vec![WorktreeInfo {
-            repo_path: PathBuf::from("/tmp/nonexistent-repo"),
-            worktree_path: PathBuf::from("/tmp/nonexistent-wt"),
-            branch: "test-branch".to_string(),
-        }];
-        let session_id = session.info.id;
-        app.sessions.push(session);
-        app.projects[0].session_ids.push(session_id);
+        app.focus = InputFocus::SessionList;
+        app.handle_key(KeyCode::Char('s'), KeyModifiers::NONE);
+        assert!(app.show_send_prompt_modal);
+        for c in "review".chars() {
+            app.handle_key(KeyCode::Char(c), KeyModifiers::NONE);
+        }
+        app.handle_key(KeyCode::Enter, KeyModifiers::ALT);
+        for c in "tests".chars() {
+            app.handle_key(KeyCode::Char(c), KeyModifiers::NONE);
+        }
+        app.handle_key(KeyCode::Enter, KeyModifiers::NONE);
 
-        app.start_sync();
-        assert!(app.worktree_sync_in_progress);
-        assert_eq!(app.worktree_sync_pending, 1);
-        let msg = app.status_message.as_ref().unwrap();
-        assert_eq!(msg.level, StatusLevel::Info);
-        assert!(msg.text.contains("Syncing 1 worktree"));
+        assert!(!app.show_send_prompt_modal);
+        assert!(app
+            .deferred_inputs
+            .iter()
+            .any(|(session, data, _)| *session == id && data == b"\r"));
+        for _ in 0..10 {
+            if !backend.input().is_empty() {
+                break;
+            }
+            tokio::task::yield_now().await;
+        }
+        assert_eq!(backend.input(), b"\x1b[200~review\ntests\x1b[201~");
     }
 
     #[test]
-    fn tick_increments_tick_count() {
+    fn send_text_to_unknown_session_fails() {
         let mut app = App::new(24, 80, stub_backend(), test_db());
-        assert_eq!(app.tick_count, 0);
-        app.tick();
-        assert_eq!(app.tick_count, 1);
-        app.tick();
-        assert_eq!(app.tick_count, 2);
+        assert!(app
+            .send_text_to_session(SessionId::default(), "hello")
+            .is_err());
+        assert!(app.deferred_inputs.is_empty());
     }
 
-    #[test]
-    fn finish_sync_all_synced_shows_success() {
-        let mut app = App::new(24, 80, stub_backend(), test_db());
-        let id = SessionId::default();
-        app.worktree_sync_completed = vec![
-            (id, git::SyncResult::Synced),
-            (SessionId::default(), git::SyncResult::Synced),
-        ];
-        app.finish_sync();
-        let msg = app.status_message.as_ref().unwrap();
-        assert_eq!(msg.level, StatusLevel::Success);
-        assert!(msg.text.contains("2 worktree(s) synced"));
-    }
+    #[tokio::test]
+    async fn spawn_without_startup_command_sends_nothing() {
+        let backend = Arc::new(RecordingBackend::default());
+        let mut app = App::new(
+            24,
+            120,
+            backend.clone(),
+            test_db_with_project(&test_project_config()),
+        );
 
-    #[test]
-    fn finish_sync_with_errors_shows_error() {
-        let mut app = App::new(24, 80, stub_backend(), test_db());
-        app.worktree_sync_completed = vec![(
-            SessionId::default(),
-            git::SyncResult::Error("fetch failed".into()),
-        )];
-        app.finish_sync();
-        let msg = app.status_message.as_ref().unwrap();
-        assert_eq!(msg.level, StatusLevel::Error);
-        assert!(msg.text.contains("Sync failed"));
-        assert!(msg.text.contains("fetch failed"));
+        app.prepare_spawn(SessionConfig::default(), Vec::new());
+        assert_eq!(app.sessions.len(), 1);
+        assert!(app.deferred_inputs.is_empty());
+        tokio::task::yield_now().await;
+        assert!(backend.input().is_empty());
     }
 
     #[test]
-    fn finish_sync_with_conflicts_shows_info() {
-        let mut app = App::new(24, 80, stub_backend(), test_db());
-        app.worktree_sync_completed = vec![
-            (SessionId::default(), git::SyncResult::Synced),
-            (
-                SessionId::default(),
-                git::SyncResult::Conflict("merge conflict".into()),
-            ),
-        ];
-        app.finish_sync();
-        let msg = app.status_message.as_ref().unwrap();
-        assert_eq!(msg.level, StatusLevel::Info);
-        assert!(msg.text.contains("1 synced"));
-        assert!(msg.text.contains("1 conflict"));
+    fn stale_default_role_falls_back_to_selector() {
+        let mut app = app_with_two_roles();
+        app.projects[0].config.default_role = Some("deleted".to_string());
+        app.prepare_spawn(SessionConfig::default(), Vec::new());
+        assert!(app.show_role_selector);
     }
 
     #[test]
-    fn finish_sync_errors_take_priority_over_conflicts() {
-        let mut app = App::new(24, 80, stub_backend(), test_db());
-        app.worktree_sync_completed = vec![
-            (
-                SessionId::default(),
-                git::SyncResult::Conflict("merge conflict".into()),
-            ),
-            (
-                SessionId::default(),
-                git::SyncResult::Error("network error".into()),
-            ),
-        ];
-        app.finish_sync();
-        let msg = app.status_message.as_ref().unwrap();
-        assert_eq!(msg.level, StatusLevel::Error);
-        assert!(msg.text.contains("network error"));
-    }
+    fn role_list_s_toggles_default_role_and_saves() {
+        let mut app = app_with_two_roles();
+        app.open_edit_project_modal();
+        app.edit_project_field = EditProjectField::Roles;
 
-    #[test]
-    fn drain_deferred_inputs_sends_at_correct_tick() {
-        let mut app = App::new(24, 80, stub_backend(), test_db());
-        let id = SessionId::default();
-        app.deferred_inputs.push((id, b"hello".to_vec(), 5));
+        app.handle_key(KeyCode::Char('j'), KeyModifiers::NONE);
+        app.handle_key(KeyCode::Char('s'), KeyModifiers::NONE);
+        assert_eq!(app.edit_project_default_role.as_deref(), Some("reviewer"));
+        app.submit_edit_project();
 
-        // Before target tick: nothing drained
-        app.tick_count = 4;
-        app.drain_deferred_inputs();
-        assert_eq!(app.deferred_inputs.len(), 1);
+        let project_id = app.projects[0].id;
+        assert_eq!(
+            app.projects[0].config.default_role.as_deref(),
+            Some("reviewer")
+        );
+        let stored = app.db.list_active_projects().unwrap();
+        let stored = stored.iter().find(|p| p.id == project_id).unwrap();
+        assert_eq!(stored.default_role.as_deref(), Some("reviewer"));
 
-        // At target tick: drained (no matching session, but entry is removed)
-        app.tick_count = 5;
-        app.drain_deferred_inputs();
-        assert!(app.deferred_inputs.is_empty());
+        // Pressing `s` on the default again clears it
+        app.open_edit_project_modal();
+        app.edit_project_field = EditProjectField::Roles;
+        app.handle_key(KeyCode::Char('j'), KeyModifiers::NONE);
+        app.handle_key(KeyCode::Char('s'), KeyModifiers::NONE);
+        app.submit_edit_project();
+        assert_eq!(app.projects[0].config.default_role, None);
     }
 
     #[test]
-    fn drain_deferred_inputs_retains_future_items() {
-        let mut app = App::new(24, 80, stub_backend(), test_db());
-        let id = SessionId::default();
-        app.deferred_inputs.push((id, b"early".to_vec(), 5));
-        app.deferred_inputs.push((id, b"late".to_vec(), 20));
+    fn duplicate_worktree_session_prompts_for_branch_from_its_branch() {
+        let mut app = app_with_two_roles();
+        app.sessions[0].info.worktrees = vec![WorktreeInfo {
+            repo_path: PathBuf::from("/test"),
+            worktree_path: PathBuf::from("/test/.git/thurbox-worktrees/feature"),
+            branch: "feature".to_string(),
+            base_branch: "main".to_string(),
+        }];
+        app.handle_key(KeyCode::Char('D'), KeyModifiers::SHIFT);
 
-        app.tick_count = 5;
-        app.drain_deferred_inputs();
-        assert_eq!(app.deferred_inputs.len(), 1);
-        assert_eq!(app.deferred_inputs[0].2, 20);
+        assert!(app.show_worktree_name_modal);
+        assert_eq!(app.worktree_name_input.value(), "feature-2");
+        assert_eq!(app.pending_base_branch.as_deref(), Some("feature"));
+        assert_eq!(app.pending_repo_path, Some(PathBuf::from("/test")));
+        assert_eq!(app.pending_spawn_role.as_deref(), Some("reviewer"));
+        assert_eq!(app.sessions.len(), 1);
+
+        app.handle_key(KeyCode::Esc, KeyModifiers::NONE);
+        assert!(app.pending_spawn_role.is_none());
     }
 
     #[test]
-    fn send_conflict_prompt_noop_for_unknown_session() {
-        let mut app = App::new(24, 80, stub_backend(), test_db());
-        app.send_conflict_prompt(SessionId::default());
-        assert!(app.deferred_inputs.is_empty());
+    fn invalid_branch_name_keeps_worktree_modal_open_with_reason() {
+        let mut app = app_with_sessions(0);
+        app.pending_base_branch = Some("main".to_string());
+        app.pending_repo_path = Some(PathBuf::from("/test"));
+        app.worktree_name_input.set("my branch");
+        app.show_worktree_name_modal = true;
+
+        app.handle_key(KeyCode::Enter, KeyModifiers::NONE);
+        assert!(app.show_worktree_name_modal);
+        assert!(app
+            .worktree_name_error
+            .as_deref()
+            .is_some_and(|e| e.contains("spaces")));
+        assert_eq!(app.pending_base_branch.as_deref(), Some("main"));
+
+        app.handle_key(KeyCode::Backspace, KeyModifiers::NONE);
+        assert!(app.worktree_name_error.is_none());
     }
 
-    #[test]
-    fn send_conflict_prompt_no_deferred_when_send_fails() {
-        let backend = stub_backend();
-        let mut app = App::new(24, 80, backend.clone(), test_db());
-        let session = Session::stub("test", &backend);
-        let sid = session.info.id;
-        app.sessions.push(session);
+    #[tokio::test]
+    async fn existing_branch_is_resumed_as_worktree() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let repo = tmp.path().to_path_buf();
+        init_git_repo(&repo, &["existing"]);
+        let backend: Arc<dyn SessionBackend> = Arc::new(RecordingBackend::default());
+        let mut app = App::new(
+            24,
+            120,
+            backend,
+            test_db_with_project(&test_project_config()),
+        );
 
-        // Stub's channel rx is dropped, so send_input fails.
-        // No deferred input should be created.
-        app.send_conflict_prompt(sid);
-        assert!(app.deferred_inputs.is_empty());
+        // `-b existing` would fail; the existing branch is checked out instead
+        app.spawn_worktree_session(std::slice::from_ref(&repo), "existing", "main");
+
+        assert_eq!(app.sessions.len(), 1);
+        let worktree = &app.sessions[0].info.worktrees[0];
+        assert_eq!(worktree.branch, "existing");
+        assert!(worktree.worktree_path.exists());
+        assert!(app
+            .status_message
+            .as_ref()
+            .is_some_and(|m| m.text.contains("Reused existing branch")));
+    }
+
+    /// App on a repo whose `feature` branch is checked out in a linked
+    /// worktree outside thurbox, returning that worktree's directory.
+    fn app_with_branch_checked_out_elsewhere() -> (tempfile::TempDir, App, PathBuf, PathBuf) {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let repo = tmp.path().join("repo");
+        let elsewhere = tmp.path().join("elsewhere");
+        init_git_repo(&repo, &[]);
+        git(
+            &repo,
+            &[
+                "worktree",
+                "add",
+                "-q",
+                "-b",
+                "feature",
+                elsewhere.to_str().unwrap(),
+            ],
+        );
+        let backend: Arc<dyn SessionBackend> = Arc::new(RecordingBackend::default());
+        let app = App::new(
+            24,
+            120,
+            backend,
+            test_db_with_project(&test_project_config()),
+        );
+        (tmp, app, repo, elsewhere)
     }
 
-    #[test]
-    fn poll_sync_results_triggers_finish_when_all_received() {
-        let mut app = App::new(24, 80, stub_backend(), test_db());
-        let (tx, rx) = mpsc::channel();
-        let id = SessionId::default();
+    #[tokio::test]
+    async fn branch_checked_out_elsewhere_reuses_that_worktree() {
+        let (_tmp, mut app, repo, elsewhere) = app_with_branch_checked_out_elsewhere();
 
-        tx.send((id, git::SyncResult::Synced)).unwrap();
-        drop(tx);
+        app.spawn_worktree_session(std::slice::from_ref(&repo), "feature", "main");
 
-        app.worktree_sync_in_progress = true;
-        app.worktree_sync_rx = Some(rx);
-        app.worktree_sync_pending = 1;
+        assert_eq!(app.sessions.len(), 1);
+        assert_eq!(app.sessions[0].info.worktrees[0].worktree_path, elsewhere);
+    }
 
-        app.poll_sync_results();
+    #[tokio::test]
+    async fn cancelling_role_selector_keeps_reused_worktree() {
+        let (_tmp, mut app, repo, elsewhere) = app_with_branch_checked_out_elsewhere();
+        let project = app.active_project_index;
+        app.projects[project].config.roles = ["a", "b"]
+            .map(|name| crate::session::RoleConfig {
+                name: name.to_string(),
+                description: String::new(),
+                permissions: Default::default(),
+            })
+            .to_vec();
+        std::fs::write(elsewhere.join("wip.txt"), "uncommitted\n").unwrap();
 
-        assert!(!app.worktree_sync_in_progress);
-        assert!(app.worktree_sync_rx.is_none());
-        let msg = app.status_message.as_ref().unwrap();
-        assert_eq!(msg.level, StatusLevel::Success);
+        app.spawn_worktree_session(std::slice::from_ref(&repo), "feature", "main");
+        assert!(app.show_role_selector);
+        app.handle_key(KeyCode::Esc, KeyModifiers::NONE);
+
+        assert!(app.pending_spawn_reused_worktrees.is_empty());
+        assert!(elsewhere.join("wip.txt").is_file());
     }
 
-    #[test]
-    fn poll_sync_results_waits_for_all_pending() {
-        let mut app = App::new(24, 80, stub_backend(), test_db());
-        let (tx, rx) = mpsc::channel();
+    #[tokio::test]
+    async fn branch_open_in_another_session_is_not_shared() {
+        let (_tmp, mut app, repo, _elsewhere) = app_with_branch_checked_out_elsewhere();
+        app.spawn_worktree_session(std::slice::from_ref(&repo), "feature", "main");
+        let first = app.sessions[0].info.name.clone();
 
-        tx.send((SessionId::default(), git::SyncResult::Synced))
-            .unwrap();
-        // Don't drop tx — second result hasn't arrived yet
+        app.spawn_worktree_session(std::slice::from_ref(&repo), "feature", "main");
 
-        app.worktree_sync_in_progress = true;
-        app.worktree_sync_rx = Some(rx);
-        app.worktree_sync_pending = 2;
+        assert_eq!(app.sessions.len(), 1);
+        let message = app.status_message.as_ref().unwrap();
+        assert!(matches!(message.error, Some(AppError::Worktree(_))));
+        assert!(message
+            .text
+            .contains(&format!("already open in session '{first}'")));
+    }
 
-        app.poll_sync_results();
+    #[tokio::test]
+    async fn branch_checked_out_in_main_repo_explains_the_conflict() {
+        let (_tmp, mut app, repo, _elsewhere) = app_with_branch_checked_out_elsewhere();
 
-        // Still in progress — only 1 of 2 received
-        assert!(app.worktree_sync_in_progress);
-        assert!(app.worktree_sync_rx.is_some());
-        assert_eq!(app.worktree_sync_completed.len(), 1);
+        app.spawn_worktree_session(std::slice::from_ref(&repo), "main", "main");
+
+        assert!(app.sessions.is_empty());
+        let message = app.status_message.as_ref().unwrap();
+        assert!(message.text.contains("Branch 'main' is checked out in"));
+        assert!(message.text.contains("pick a different branch"));
     }
 
     // --- find_project_index_for_session tests ---
@@ -5672,6 +15219,32 @@ mod tests {
         assert!(perms.disallowed_tools.is_empty());
     }
 
+    #[test]
+    fn admin_session_config_runs_claude_by_default() {
+        let app = App::new(24, 120, stub_backend(), test_db());
+        let config = app.admin_session_config(PathBuf::from("/tmp/admin"));
+        assert_eq!(config.command, None);
+        assert_eq!(
+            crate::claude::backend::build_spawn_command(&config).0,
+            "claude"
+        );
+        assert_eq!(config.permissions, super::admin_mcp_permissions());
+    }
+
+    #[test]
+    fn admin_session_config_uses_configured_command() {
+        let mut app = App::new(24, 120, stub_backend(), test_db());
+        app.settings.admin.command = Some("my-agent".to_string());
+        app.settings.admin.args = vec!["--mcp".to_string(), ".mcp.json".to_string()];
+
+        let config = app.admin_session_config(PathBuf::from("/tmp/admin"));
+        let (command, args) = crate::claude::backend::build_spawn_command(&config);
+        assert_eq!(command, "my-agent");
+        assert_eq!(args, vec!["--mcp", ".mcp.json"]);
+        assert_eq!(config.cwd, Some(PathBuf::from("/tmp/admin")));
+        assert_eq!(config.permissions, super::admin_mcp_permissions());
+    }
+
     #[test]
     fn resolve_role_permissions_returns_admin_tools_for_admin_project() {
         let backend = stub_backend();
@@ -5681,6 +15254,13 @@ mod tests {
             repos: vec![],
             roles: vec![],
             mcp_servers: vec![],
+            sync_base_branch: None,
+            remote: None,
+            sync_strategy: SyncStrategy::default(),
+            activity_timeout_ms: None,
+            worktree_root: None,
+            default_role: None,
+            session_init_command: None,
             id: None,
         });
         app.projects.push(admin_project);
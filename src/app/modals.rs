@@ -329,7 +329,7 @@ pub enum Modal {
     SessionMode(SessionModeModal),
     BranchSelector(BranchSelectorModal),
     WorktreeName(WorktreeNameModal),
-    RoleSelector(RoleSelectorModal),
+    RoleSelector(Box<RoleSelectorModal>),
     RoleEditor(RoleEditorModal),
 }
 
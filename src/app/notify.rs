@@ -0,0 +1,61 @@
+//! Desktop notifications for sessions that finish working or ring the
+//! terminal bell (`[notifications]`).
+
+use crate::session::SessionStatus;
+
+/// Whether a status change means Claude finished a turn and is now waiting
+/// for the user: `Busy` to `Waiting`, or to `NeedsInput` when the prompt
+/// marker was seen. Only the edge counts, so a session sitting in `Waiting`
+/// notifies once rather than on every tick.
+pub(crate) fn finished_work(previous: SessionStatus, current: SessionStatus) -> bool {
+    previous == SessionStatus::Busy
+        && matches!(current, SessionStatus::Waiting | SessionStatus::NeedsInput)
+}
+
+/// Show a desktop notification that `session` in `project` is waiting.
+pub(crate) fn session_waiting(project: &str, session: &str) {
+    show(project, session, "Waiting for input");
+}
+
+/// Show a desktop notification that `session` in `project` rang the bell.
+pub(crate) fn session_bell(project: &str, session: &str) {
+    show(project, session, "Bell");
+}
+
+/// Sent from a background thread because the D-Bus round trip can stall the
+/// event loop. Does nothing under `cfg(test)`.
+fn show(project: &str, session: &str, body: &'static str) {
+    #[cfg(not(test))]
+    {
+        let summary = format!("{project}: {session}");
+        std::thread::spawn(move || {
+            if let Err(e) = notify_rust::Notification::new()
+                .appname("thurbox")
+                .summary(&summary)
+                .body(body)
+                .show()
+            {
+                tracing::debug!("Failed to show desktop notification: {e}");
+            }
+        });
+    }
+    #[cfg(test)]
+    let _ = (project, session, body);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn busy_to_waiting_or_needs_input_is_finished_work() {
+        use SessionStatus::*;
+        assert!(finished_work(Busy, Waiting));
+        assert!(finished_work(Busy, NeedsInput));
+        assert!(!finished_work(Waiting, Waiting));
+        assert!(!finished_work(NeedsInput, NeedsInput));
+        assert!(!finished_work(Waiting, NeedsInput));
+        assert!(!finished_work(Idle, Waiting));
+        assert!(!finished_work(Waiting, Busy));
+    }
+}
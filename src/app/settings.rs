@@ -0,0 +1,873 @@
+//! User preferences loaded from `config.toml`.
+//!
+//! Preferences are per-user UI behavior, not shared state, so they live in
+//! the TOML config file (ADR-8) rather than the SQLite database. Every field
+//! has a default, so a missing or partial file is always valid.
+
+use std::collections::HashMap;
+
+use serde::Deserialize;
+use tracing::warn;
+
+use crate::claude::output_log;
+use crate::ui::layout::PanelRatios;
+
+/// What `Ctrl+C` does while the terminal panel has focus.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum CtrlCBehavior {
+    /// Send `Ctrl+C` (SIGINT) to the session's PTY, interrupting Claude.
+    #[default]
+    Forward,
+    /// Close the active session, same as in the project/session lists.
+    Close,
+}
+
+/// `[terminal]` section of the config file.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct TerminalSettings {
+    pub ctrl_c: CtrlCBehavior,
+}
+
+/// `[session_logs]` section: teeing raw session output to files.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct SessionLogSettings {
+    /// Log output of every new session without toggling it by hand.
+    pub enabled: bool,
+    /// Size in bytes at which a session log is truncated.
+    pub max_bytes: u64,
+}
+
+impl Default for SessionLogSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_bytes: output_log::DEFAULT_MAX_BYTES,
+        }
+    }
+}
+
+/// `[auto_restart]` section: restarting sessions whose Claude process exits
+/// on its own.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct AutoRestartSettings {
+    /// Restart crashed sessions automatically, with exponential backoff.
+    pub enabled: bool,
+    /// Consecutive restarts attempted before giving up on a session.
+    pub max_attempts: u32,
+}
+
+impl Default for AutoRestartSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_attempts: 3,
+        }
+    }
+}
+
+/// `[sync]` section: multi-instance change detection.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct SyncSettings {
+    /// How often to check the database for other instances' changes.
+    pub poll_interval_ms: u64,
+}
+
+impl Default for SyncSettings {
+    fn default() -> Self {
+        Self {
+            poll_interval_ms: crate::sync::DEFAULT_POLL_INTERVAL.as_millis() as u64,
+        }
+    }
+}
+
+/// `[worktree_sync]` section: how `Ctrl+S` runs its git work.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct WorktreeSyncSettings {
+    /// Repos synced (or previewed) at the same time. `0` is treated as `1`.
+    pub max_concurrent: usize,
+    /// Prompt pasted into Claude when a rebase conflicts, replacing the
+    /// built-in one. `{branch}`, `{base}` and `{remote}` are filled in.
+    pub conflict_prompt: Option<String>,
+    /// Same for projects using the merge strategy.
+    pub merge_conflict_prompt: Option<String>,
+}
+
+impl Default for WorktreeSyncSettings {
+    fn default() -> Self {
+        Self {
+            max_concurrent: 3,
+            conflict_prompt: None,
+            merge_conflict_prompt: None,
+        }
+    }
+}
+
+/// `[limits]` section: soft caps that ask before going further.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct LimitSettings {
+    /// User sessions (all projects, admin excluded) running before a new
+    /// spawn asks for confirmation. `0` disables the check.
+    pub max_sessions: usize,
+}
+
+impl Default for LimitSettings {
+    fn default() -> Self {
+        Self { max_sessions: 32 }
+    }
+}
+
+/// How new sessions are named.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SessionNameScheme {
+    /// The session counter: `1`, `2`, …
+    #[default]
+    Integer,
+    /// The worktree branch as a slug (`feat/login` → `feat-login`); plain
+    /// sessions fall back to the counter.
+    Branch,
+    /// `prefix` followed by the counter, e.g. `session-3`.
+    Prefix,
+}
+
+/// `[session_names]` section: names given to new sessions.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct SessionNameSettings {
+    pub scheme: SessionNameScheme,
+    /// Prepended to the counter by the `prefix` scheme.
+    pub prefix: String,
+}
+
+impl Default for SessionNameSettings {
+    fn default() -> Self {
+        Self {
+            scheme: SessionNameScheme::default(),
+            prefix: "session-".to_string(),
+        }
+    }
+}
+
+/// `[status_server]` section: a read-only JSON endpoint for dashboards.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct StatusServerSettings {
+    /// Serve `GET /status` on localhost.
+    pub enabled: bool,
+    /// Port the endpoint listens on.
+    pub port: u16,
+}
+
+impl Default for StatusServerSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: 7433,
+        }
+    }
+}
+
+/// `[paste]` section: how prompts pasted into a session are submitted.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct PasteSettings {
+    /// Milliseconds between a paste and the Enter that submits it, for a
+    /// session that has been quiet for a while.
+    pub enter_delay_ms: u64,
+    /// Wait longer when the session printed output recently, since a busy
+    /// session takes longer to ingest the paste.
+    pub adaptive: bool,
+}
+
+impl Default for PasteSettings {
+    fn default() -> Self {
+        Self {
+            enter_delay_ms: 100,
+            adaptive: true,
+        }
+    }
+}
+
+/// What closing a session does with its git worktrees.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum WorktreeOnClose {
+    /// Force-remove them once the undo window passes, asking first only
+    /// when that would lose uncommitted or unpushed work.
+    #[default]
+    Remove,
+    /// Leave the worktrees and branches on disk.
+    Keep,
+    /// Always ask, offering to keep them.
+    Ask,
+}
+
+/// `[close]` section: what closing a session cleans up.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct CloseSettings {
+    pub worktrees: WorktreeOnClose,
+}
+
+/// `[quit]` section: what Ctrl+Q does while sessions are running.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct QuitSettings {
+    /// Ask before quitting when user sessions would be detached.
+    pub confirm: bool,
+}
+
+impl Default for QuitSettings {
+    fn default() -> Self {
+        Self { confirm: true }
+    }
+}
+
+/// `[status]` section: how long status bar messages stay up, and how
+/// session statuses are drawn. The icon set is resolved into
+/// [`StatusIcons`](crate::ui::status_icons::StatusIcons) by the app.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct StatusSettings {
+    /// Milliseconds before an info or success message is cleared
+    /// (0 keeps it until replaced).
+    pub ttl_ms: u64,
+    /// Milliseconds before an error message is cleared (0 keeps it until
+    /// replaced). Longer than `ttl_ms` so failures aren't missed.
+    pub error_ttl_ms: u64,
+    /// Built-in icon set (`unicode`, `ascii`).
+    pub preset: Option<String>,
+    /// Status name (e.g. `busy`) → icon.
+    pub icons: HashMap<String, String>,
+    /// Status name → label shown next to the icon.
+    pub labels: HashMap<String, String>,
+}
+
+impl Default for StatusSettings {
+    fn default() -> Self {
+        Self {
+            ttl_ms: 5_000,
+            error_ttl_ms: 15_000,
+            preset: None,
+            icons: HashMap::new(),
+            labels: HashMap::new(),
+        }
+    }
+}
+
+/// `[restore]` section: how persisted sessions come back at startup.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct RestoreSettings {
+    /// Restore sessions as dormant placeholders that are only adopted or
+    /// resumed when first focused, instead of all at launch.
+    pub lazy: bool,
+}
+
+/// `[notifications]` section: desktop notifications.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct NotificationSettings {
+    /// Notify when a session finishes working and waits for input.
+    pub enabled: bool,
+    /// Notify when a session rings the terminal bell.
+    pub bell: bool,
+}
+
+/// `[layout]` section: panel widths as percentages of the terminal width.
+/// Validated into [`PanelRatios`] by the app.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct LayoutSettings {
+    /// Project/session list width while the info panel is hidden.
+    pub list_percent: u16,
+    /// Info panel width.
+    pub info_percent: u16,
+}
+
+impl Default for LayoutSettings {
+    fn default() -> Self {
+        let ratios = PanelRatios::default();
+        Self {
+            list_percent: ratios.list_percent,
+            info_percent: ratios.info_percent,
+        }
+    }
+}
+
+/// `[scroll]` section: how far the terminal scrolls through scrollback.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct ScrollSettings {
+    /// Lines per mouse wheel notch.
+    pub mouse_lines: usize,
+    /// Lines per `Shift+Up` / `Shift+Down`.
+    pub line_step: usize,
+    /// Share of the terminal height scrolled by `Shift+PageUp` /
+    /// `Shift+PageDown`, in `(0, 1]`.
+    pub page_fraction: f64,
+    /// Scrollback lines kept for sessions other than the active one. The
+    /// active session keeps the full history; lines beyond this are
+    /// dropped when a session moves to the background.
+    pub background_lines: usize,
+}
+
+impl Default for ScrollSettings {
+    fn default() -> Self {
+        Self {
+            mouse_lines: 3,
+            line_step: 1,
+            page_fraction: 0.5,
+            background_lines: 200,
+        }
+    }
+}
+
+impl ScrollSettings {
+    /// Reject steps that would make scrolling a no-op or jump past a page.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.mouse_lines == 0 {
+            return Err("scroll.mouse_lines must be at least 1".to_string());
+        }
+        if self.line_step == 0 {
+            return Err("scroll.line_step must be at least 1".to_string());
+        }
+        if !(self.page_fraction > 0.0 && self.page_fraction <= 1.0) {
+            return Err(format!(
+                "scroll.page_fraction ({}) must be greater than 0 and at most 1",
+                self.page_fraction
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// `[admin]` section: whether there is an admin session, and what it runs.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct AdminSettings {
+    /// Create the Admin project and session at startup. When off, neither
+    /// exists and the admin `.mcp.json` is not written.
+    pub enabled: bool,
+    /// Program run instead of `claude`. The `thurbox` MCP `.mcp.json` is
+    /// still written to the admin directory.
+    pub command: Option<String>,
+    /// Arguments passed to `command`.
+    pub args: Vec<String>,
+}
+
+impl Default for AdminSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            command: None,
+            args: Vec::new(),
+        }
+    }
+}
+
+impl AdminSettings {
+    /// Configured program and arguments, or `None` to run `claude`.
+    pub fn spawn_command(&self) -> Option<(String, Vec<String>)> {
+        self.command
+            .as_ref()
+            .filter(|c| !c.trim().is_empty())
+            .map(|c| (c.clone(), self.args.clone()))
+    }
+}
+
+/// `[theme]` section: a built-in base theme plus per-slot color overrides.
+/// Resolved into a [`Theme`](crate::ui::theme::Theme) by the app.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct ThemeSettings {
+    /// Built-in theme to start from (`default`, `monochrome`).
+    pub name: Option<String>,
+    /// Slot name → color (e.g. `status_busy = "light-green"`).
+    #[serde(flatten)]
+    pub colors: HashMap<String, String>,
+}
+
+/// Top-level user preferences.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub terminal: TerminalSettings,
+    pub paste: PasteSettings,
+    pub quit: QuitSettings,
+    pub close: CloseSettings,
+    pub limits: LimitSettings,
+    pub session_names: SessionNameSettings,
+    pub session_logs: SessionLogSettings,
+    pub auto_restart: AutoRestartSettings,
+    pub sync: SyncSettings,
+    pub worktree_sync: WorktreeSyncSettings,
+    pub notifications: NotificationSettings,
+    pub status_server: StatusServerSettings,
+    pub restore: RestoreSettings,
+    pub status: StatusSettings,
+    pub layout: LayoutSettings,
+    pub scroll: ScrollSettings,
+    pub admin: AdminSettings,
+    pub theme: ThemeSettings,
+    /// `[keybindings]` overrides: action name → key (e.g. `quit = "ctrl+q"`).
+    /// Validated when building the [`KeyMap`](super::keymap::KeyMap).
+    pub keybindings: HashMap<String, String>,
+}
+
+impl Settings {
+    /// Parse settings from TOML. Unknown keys (e.g. legacy `[[projects]]`)
+    /// are ignored.
+    pub fn parse(contents: &str) -> Result<Self, toml::de::Error> {
+        toml::from_str(contents)
+    }
+
+    /// Load settings from the config file. A missing file gives defaults;
+    /// an unreadable or invalid one gives a one-line error for the status
+    /// bar, so a typo is not silently ignored.
+    pub fn load() -> Result<Self, String> {
+        let Some(path) = crate::paths::config_file() else {
+            return Ok(Self::default());
+        };
+        let contents = match std::fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(e) => {
+                warn!("Cannot read config {}: {e}", path.display());
+                return Err(format!("Cannot read config.toml: {e}"));
+            }
+        };
+        Self::parse(&contents).map_err(|e| {
+            warn!("Ignoring invalid config {}: {e}", path.display());
+            let line = e
+                .span()
+                .map(|span| contents[..span.start].lines().count().max(1));
+            let message = e.message().trim_end();
+            match line {
+                Some(line) => format!("Invalid config.toml (line {line}): {message}"),
+                None => format!("Invalid config.toml: {message}"),
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_config_uses_defaults() {
+        let settings = Settings::parse("").unwrap();
+        assert_eq!(settings, Settings::default());
+        assert_eq!(settings.terminal.ctrl_c, CtrlCBehavior::Forward);
+    }
+
+    #[test]
+    fn session_names_parse() {
+        let settings =
+            Settings::parse("[session_names]\nscheme = \"prefix\"\nprefix = \"wt-\"\n").unwrap();
+        assert_eq!(settings.session_names.scheme, SessionNameScheme::Prefix);
+        assert_eq!(settings.session_names.prefix, "wt-");
+        assert_eq!(
+            Settings::parse("[session_names]\nscheme = \"branch\"\n")
+                .unwrap()
+                .session_names
+                .prefix,
+            "session-"
+        );
+        assert!(Settings::parse("[session_names]\nscheme = \"emoji\"\n").is_err());
+    }
+
+    #[test]
+    fn close_worktrees_parses() {
+        let settings = Settings::parse("").unwrap();
+        assert_eq!(settings.close.worktrees, WorktreeOnClose::Remove);
+        let settings = Settings::parse("[close]\nworktrees = \"ask\"\n").unwrap();
+        assert_eq!(settings.close.worktrees, WorktreeOnClose::Ask);
+        let settings = Settings::parse("[close]\nworktrees = \"keep\"\n").unwrap();
+        assert_eq!(settings.close.worktrees, WorktreeOnClose::Keep);
+    }
+
+    #[test]
+    fn ctrl_c_close_parses() {
+        let settings = Settings::parse("[terminal]\nctrl_c = \"close\"\n").unwrap();
+        assert_eq!(settings.terminal.ctrl_c, CtrlCBehavior::Close);
+    }
+
+    #[test]
+    fn ctrl_c_forward_parses() {
+        let settings = Settings::parse("[terminal]\nctrl_c = \"forward\"\n").unwrap();
+        assert_eq!(settings.terminal.ctrl_c, CtrlCBehavior::Forward);
+    }
+
+    #[test]
+    fn invalid_ctrl_c_value_is_error() {
+        assert!(Settings::parse("[terminal]\nctrl_c = \"explode\"\n").is_err());
+    }
+
+    #[test]
+    fn legacy_projects_section_is_ignored() {
+        let contents =
+            "[[projects]]\nname = \"old\"\nrepos = []\n\n[terminal]\nctrl_c = \"close\"\n";
+        let settings = Settings::parse(contents).unwrap();
+        assert_eq!(settings.terminal.ctrl_c, CtrlCBehavior::Close);
+    }
+
+    #[test]
+    fn keybindings_table_parses() {
+        let settings =
+            Settings::parse("[keybindings]\nclose_session = \"ctrl+w\"\nhelp = \"f10\"\n").unwrap();
+        assert_eq!(settings.keybindings.len(), 2);
+        assert_eq!(settings.keybindings["close_session"], "ctrl+w");
+    }
+
+    #[test]
+    fn session_logs_default_off() {
+        let settings = Settings::parse("").unwrap();
+        assert!(!settings.session_logs.enabled);
+        assert_eq!(
+            settings.session_logs.max_bytes,
+            output_log::DEFAULT_MAX_BYTES
+        );
+    }
+
+    #[test]
+    fn session_logs_section_parses() {
+        let settings = Settings::parse(
+            "[session_logs]
+enabled = true
+max_bytes = 1048576
+",
+        )
+        .unwrap();
+        assert!(settings.session_logs.enabled);
+        assert_eq!(settings.session_logs.max_bytes, 1_048_576);
+    }
+
+    #[test]
+    fn auto_restart_default_off() {
+        let settings = Settings::parse("").unwrap();
+        assert!(!settings.auto_restart.enabled);
+        assert_eq!(settings.auto_restart.max_attempts, 3);
+    }
+
+    #[test]
+    fn sync_poll_interval_defaults_to_one_second() {
+        let settings = Settings::parse("").unwrap();
+        assert_eq!(settings.sync.poll_interval_ms, 1000);
+    }
+
+    #[test]
+    fn sync_section_parses() {
+        let settings = Settings::parse("[sync]\npoll_interval_ms = 5000\n").unwrap();
+        assert_eq!(settings.sync.poll_interval_ms, 5000);
+    }
+
+    #[test]
+    fn paste_section_parses() {
+        let settings = Settings::parse("").unwrap();
+        assert_eq!(settings.paste.enter_delay_ms, 100);
+        assert!(settings.paste.adaptive);
+
+        let settings =
+            Settings::parse("[paste]\nenter_delay_ms = 250\nadaptive = false\n").unwrap();
+        assert_eq!(settings.paste.enter_delay_ms, 250);
+        assert!(!settings.paste.adaptive);
+    }
+
+    #[test]
+    fn conflict_prompts_parse() {
+        let settings = Settings::parse("").unwrap();
+        assert_eq!(settings.worktree_sync.conflict_prompt, None);
+
+        let settings =
+            Settings::parse("[worktree_sync]\nconflict_prompt = \"Rebase {branch} onto {base}\"\n")
+                .unwrap();
+        assert_eq!(
+            settings.worktree_sync.conflict_prompt.as_deref(),
+            Some("Rebase {branch} onto {base}")
+        );
+        assert_eq!(settings.worktree_sync.merge_conflict_prompt, None);
+    }
+
+    #[test]
+    fn quit_confirmation_is_on_by_default() {
+        let settings = Settings::parse("").unwrap();
+        assert!(settings.quit.confirm);
+
+        let settings = Settings::parse("[quit]\nconfirm = false\n").unwrap();
+        assert!(!settings.quit.confirm);
+    }
+
+    #[test]
+    fn lazy_restore_is_off_by_default() {
+        let settings = Settings::parse("").unwrap();
+        assert!(!settings.restore.lazy);
+    }
+
+    #[test]
+    fn restore_section_parses() {
+        let settings = Settings::parse("[restore]\nlazy = true\n").unwrap();
+        assert!(settings.restore.lazy);
+    }
+
+    #[test]
+    fn status_ttls_default_to_longer_errors() {
+        let settings = Settings::parse("").unwrap();
+        assert_eq!(settings.status.ttl_ms, 5_000);
+        assert_eq!(settings.status.error_ttl_ms, 15_000);
+    }
+
+    #[test]
+    fn status_section_parses() {
+        let settings = Settings::parse("[status]\nttl_ms = 2000\nerror_ttl_ms = 0\n").unwrap();
+        assert_eq!(settings.status.ttl_ms, 2_000);
+        assert_eq!(settings.status.error_ttl_ms, 0);
+    }
+
+    #[test]
+    fn layout_defaults_to_fixed_splits() {
+        let settings = Settings::parse("").unwrap();
+        assert_eq!(settings.layout.list_percent, 25);
+        assert_eq!(settings.layout.info_percent, 15);
+    }
+
+    #[test]
+    fn layout_section_parses() {
+        let settings = Settings::parse("[layout]\nlist_percent = 30\n").unwrap();
+        assert_eq!(settings.layout.list_percent, 30);
+        assert_eq!(settings.layout.info_percent, 15);
+    }
+
+    #[test]
+    fn session_limit_defaults_to_32() {
+        let settings = Settings::parse("").unwrap();
+        assert_eq!(settings.limits.max_sessions, 32);
+    }
+
+    #[test]
+    fn limits_section_parses() {
+        let settings = Settings::parse("[limits]\nmax_sessions = 0\n").unwrap();
+        assert_eq!(settings.limits.max_sessions, 0);
+    }
+
+    #[test]
+    fn status_icon_tables_parse() {
+        let settings = Settings::parse(
+            "[status]\npreset = \"ascii\"\n[status.icons]\nbusy = \">\"\n[status.labels]\nwaiting = \"Ready\"\n",
+        )
+        .unwrap();
+        assert_eq!(settings.status.preset.as_deref(), Some("ascii"));
+        assert_eq!(settings.status.icons["busy"], ">");
+        assert_eq!(settings.status.labels["waiting"], "Ready");
+        assert_eq!(settings.status.ttl_ms, 5_000);
+    }
+
+    #[test]
+    fn status_server_is_off_by_default() {
+        let settings = Settings::parse("").unwrap();
+        assert!(!settings.status_server.enabled);
+        assert_eq!(settings.status_server.port, 7433);
+    }
+
+    #[test]
+    fn status_server_section_parses() {
+        let settings = Settings::parse("[status_server]\nenabled = true\nport = 9000\n").unwrap();
+        assert!(settings.status_server.enabled);
+        assert_eq!(settings.status_server.port, 9000);
+    }
+
+    #[test]
+    fn worktree_sync_defaults_to_three_at_once() {
+        let settings = Settings::parse("").unwrap();
+        assert_eq!(settings.worktree_sync.max_concurrent, 3);
+    }
+
+    #[test]
+    fn worktree_sync_section_parses() {
+        let settings = Settings::parse("[worktree_sync]\nmax_concurrent = 1\n").unwrap();
+        assert_eq!(settings.worktree_sync.max_concurrent, 1);
+    }
+
+    #[test]
+    fn admin_defaults_to_claude() {
+        let settings = Settings::parse("").unwrap();
+        assert_eq!(settings.admin.spawn_command(), None);
+    }
+
+    #[test]
+    fn admin_can_be_disabled() {
+        assert!(Settings::parse("").unwrap().admin.enabled);
+        let settings = Settings::parse("[admin]\nenabled = false\n").unwrap();
+        assert!(!settings.admin.enabled);
+    }
+
+    #[test]
+    fn admin_section_parses_command_and_args() {
+        let settings =
+            Settings::parse("[admin]\ncommand = \"my-agent\"\nargs = [\"--fast\"]\n").unwrap();
+        assert_eq!(
+            settings.admin.spawn_command(),
+            Some(("my-agent".to_string(), vec!["--fast".to_string()]))
+        );
+    }
+
+    #[test]
+    fn admin_blank_command_falls_back_to_claude() {
+        let settings = Settings::parse("[admin]\ncommand = \"  \"\n").unwrap();
+        assert_eq!(settings.admin.spawn_command(), None);
+    }
+
+    #[test]
+    fn scroll_defaults_match_built_in_steps() {
+        let settings = Settings::parse("").unwrap();
+        assert_eq!(settings.scroll, ScrollSettings::default());
+        assert_eq!(settings.scroll.mouse_lines, 3);
+        assert_eq!(settings.scroll.line_step, 1);
+        assert_eq!(settings.scroll.page_fraction, 0.5);
+        assert!(settings.scroll.validate().is_ok());
+    }
+
+    #[test]
+    fn scroll_section_parses() {
+        let settings = Settings::parse(
+            "[scroll]
+mouse_lines = 5
+page_fraction = 0.25
+background_lines = 50
+",
+        )
+        .unwrap();
+        assert_eq!(settings.scroll.mouse_lines, 5);
+        assert_eq!(settings.scroll.line_step, 1);
+        assert_eq!(settings.scroll.page_fraction, 0.25);
+        assert_eq!(settings.scroll.background_lines, 50);
+    }
+
+    #[test]
+    fn scroll_rejects_non_positive_steps() {
+        for contents in [
+            "[scroll]\nmouse_lines = 0\n",
+            "[scroll]\nline_step = 0\n",
+            "[scroll]\npage_fraction = 0.0\n",
+            "[scroll]\npage_fraction = 1.5\n",
+        ] {
+            let settings = Settings::parse(contents).unwrap();
+            assert!(settings.scroll.validate().is_err(), "{contents}");
+        }
+    }
+
+    #[test]
+    fn notifications_are_off_by_default() {
+        let settings = Settings::parse("").unwrap();
+        assert!(!settings.notifications.enabled);
+        assert!(!settings.notifications.bell);
+    }
+
+    #[test]
+    fn notifications_section_parses() {
+        let settings = Settings::parse("[notifications]\nenabled = true\n").unwrap();
+        assert!(settings.notifications.enabled);
+        assert!(!settings.notifications.bell);
+
+        let settings = Settings::parse("[notifications]\nbell = true\n").unwrap();
+        assert!(settings.notifications.bell);
+    }
+
+    #[test]
+    fn auto_restart_section_parses() {
+        let settings =
+            Settings::parse("[auto_restart]\nenabled = true\nmax_attempts = 5\n").unwrap();
+        assert!(settings.auto_restart.enabled);
+        assert_eq!(settings.auto_restart.max_attempts, 5);
+    }
+
+    #[test]
+    fn theme_default_is_empty() {
+        let settings = Settings::parse("").unwrap();
+        assert_eq!(settings.theme, ThemeSettings::default());
+    }
+
+    #[test]
+    fn custom_theme_overrides_status_color() {
+        use crate::session::SessionStatus;
+        use crate::ui::theme::Theme;
+        use ratatui::style::Color;
+
+        let settings = Settings::parse(
+            "[theme]
+name = \"monochrome\"
+status_busy = \"blue\"
+",
+        )
+        .unwrap();
+        assert_eq!(settings.theme.name.as_deref(), Some("monochrome"));
+
+        let theme =
+            Theme::from_config(settings.theme.name.as_deref(), &settings.theme.colors).unwrap();
+        assert_eq!(
+            crate::ui::status_color(SessionStatus::Busy, &theme),
+            Color::Blue
+        );
+        assert_eq!(
+            crate::ui::status_color(SessionStatus::Idle, &theme),
+            Theme::MONOCHROME.status_idle
+        );
+    }
+
+    #[test]
+    fn load_missing_file_uses_defaults() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let _guard = crate::paths::TestPathGuard::new(tmp.path());
+        assert_eq!(Settings::load(), Ok(Settings::default()));
+    }
+
+    #[test]
+    fn load_reads_config_file() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let _guard = crate::paths::TestPathGuard::new(tmp.path());
+        std::fs::write(
+            tmp.path().join("config.toml"),
+            "[terminal]\nctrl_c = \"close\"\n",
+        )
+        .unwrap();
+        assert_eq!(
+            Settings::load().unwrap().terminal.ctrl_c,
+            CtrlCBehavior::Close
+        );
+    }
+
+    #[test]
+    fn load_invalid_file_reports_error() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let _guard = crate::paths::TestPathGuard::new(tmp.path());
+        std::fs::write(tmp.path().join("config.toml"), "not = [valid").unwrap();
+        let error = Settings::load().unwrap_err();
+        assert!(error.starts_with("Invalid config.toml"), "{error}");
+    }
+
+    #[test]
+    fn load_reports_bad_enum_value_with_line() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let _guard = crate::paths::TestPathGuard::new(tmp.path());
+        std::fs::write(
+            tmp.path().join("config.toml"),
+            "[terminal]\nctrl_c = \"clsoe\"\n",
+        )
+        .unwrap();
+        let error = Settings::load().unwrap_err();
+        assert!(
+            error.starts_with("Invalid config.toml (line 2): "),
+            "{error}"
+        );
+        assert!(error.contains("clsoe"), "{error}");
+        assert!(!error.contains('\n'), "{error}");
+    }
+}
@@ -0,0 +1,315 @@
+//! Read-only HTTP status endpoint (`[status_server]`).
+//!
+//! `tick` serializes projects and sessions into a shared snapshot whenever
+//! they change; a background thread accepts connections and answers each
+//! `GET /status` on its own thread with the latest copy, so a slow client
+//! never holds up the UI loop or other clients.
+
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::project::ProjectInfo;
+use crate::session::SessionInfo;
+
+/// How long a client may take to send its whole request.
+const READ_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// How long a client may stall reading the response before it is dropped.
+const WRITE_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Largest request (request line plus headers) read before giving up.
+const MAX_REQUEST_BYTES: u64 = 8 * 1024;
+
+/// Projects with their sessions and statuses nested, in list order.
+pub(crate) fn snapshot(projects: &[ProjectInfo], sessions: &[&SessionInfo]) -> serde_json::Value {
+    let projects: Vec<_> = projects
+        .iter()
+        .map(|p| {
+            let project_sessions: Vec<_> = p
+                .session_ids
+                .iter()
+                .filter_map(|id| sessions.iter().find(|s| s.id == *id))
+                .map(|s| {
+                    serde_json::json!({
+                        "id": s.id.to_string(),
+                        "name": s.name,
+                        "role": s.role,
+                        "status": s.status.as_str(),
+                        "branches": s.worktrees.iter().map(|w| &w.branch).collect::<Vec<_>>(),
+                    })
+                })
+                .collect();
+            serde_json::json!({
+                "id": p.id.to_string(),
+                "name": p.config.name,
+                "admin": p.is_admin,
+                "sessions": project_sessions,
+            })
+        })
+        .collect();
+    serde_json::json!({ "projects": projects })
+}
+
+/// A running endpoint and the snapshot it serves.
+pub(crate) struct StatusServer {
+    snapshot: Arc<Mutex<String>>,
+    addr: SocketAddr,
+}
+
+impl StatusServer {
+    /// Bind `127.0.0.1:<port>` and accept on a background thread, answering
+    /// each connection on a thread of its own.
+    pub(crate) fn start(port: u16) -> std::io::Result<Self> {
+        let listener = TcpListener::bind(("127.0.0.1", port))?;
+        let addr = listener.local_addr()?;
+        let snapshot = Arc::new(Mutex::new(String::from("{}")));
+        let served = Arc::clone(&snapshot);
+        std::thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let served = Arc::clone(&served);
+                std::thread::spawn(move || {
+                    if let Err(e) = respond(stream, &served) {
+                        tracing::debug!("Status endpoint request failed: {e}");
+                    }
+                });
+            }
+        });
+        Ok(Self { snapshot, addr })
+    }
+
+    /// Replace the JSON served to the next request.
+    pub(crate) fn publish(&self, value: &serde_json::Value) {
+        *self.snapshot.lock().unwrap_or_else(|e| e.into_inner()) = value.to_string();
+    }
+
+    pub(crate) fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// The JSON the next request would get (unit tests only).
+    #[cfg(test)]
+    pub(crate) fn published(&self) -> String {
+        self.snapshot.lock().unwrap().clone()
+    }
+}
+
+/// Answer one request: the snapshot for `GET /` and `GET /status`, 405 for
+/// other methods and 404 for other paths.
+///
+/// The headers are drained before replying: closing a socket with unread
+/// input makes the kernel send a RST, which clients can report as a reset
+/// connection instead of the response. Reading stops at
+/// [`MAX_REQUEST_BYTES`] or after [`READ_TIMEOUT`] in total, so a client
+/// trickling bytes cannot hold the connection open.
+fn respond(mut stream: TcpStream, snapshot: &Mutex<String>) -> std::io::Result<()> {
+    stream.set_write_timeout(Some(WRITE_TIMEOUT))?;
+    let deadline = DeadlineReader {
+        stream: &stream,
+        deadline: Instant::now() + READ_TIMEOUT,
+    };
+    let mut reader = BufReader::new(deadline.take(MAX_REQUEST_BYTES));
+    let mut request_line = String::new();
+    read_full_line(&mut reader, &mut request_line)?;
+    let mut header = String::new();
+    loop {
+        header.clear();
+        read_full_line(&mut reader, &mut header)?;
+        if header.trim_end().is_empty() {
+            break;
+        }
+    }
+
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or("");
+    let path = parts.next().unwrap_or("/");
+    let (status, allow, body) = match (method, path) {
+        ("GET", "/" | "/status") => (
+            "200 OK",
+            "",
+            snapshot.lock().unwrap_or_else(|e| e.into_inner()).clone(),
+        ),
+        ("GET", _) => ("404 Not Found", "", r#"{"error":"not found"}"#.to_string()),
+        _ => (
+            "405 Method Not Allowed",
+            "Allow: GET\r\n",
+            r#"{"error":"method not allowed"}"#.to_string(),
+        ),
+    };
+    write!(
+        stream,
+        "HTTP/1.1 {status}\r\nContent-Type: application/json\r\n{allow}\
+         Content-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    )
+}
+
+/// Read one line into `line`, failing if the request ends (or hits
+/// [`MAX_REQUEST_BYTES`]) before the line does.
+fn read_full_line(reader: &mut impl BufRead, line: &mut String) -> std::io::Result<()> {
+    if reader.read_line(line)? == 0 || !line.ends_with('\n') {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            "request ended before its headers did",
+        ));
+    }
+    Ok(())
+}
+
+/// Reads from `stream`, shrinking the socket timeout before every read so
+/// the whole request must arrive by `deadline`.
+struct DeadlineReader<'a> {
+    stream: &'a TcpStream,
+    deadline: Instant,
+}
+
+impl Read for DeadlineReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let left = self
+            .deadline
+            .checked_duration_since(Instant::now())
+            .filter(|left| !left.is_zero())
+            .ok_or_else(|| std::io::Error::from(std::io::ErrorKind::TimedOut))?;
+        self.stream.set_read_timeout(Some(left))?;
+        self.stream.read(buf)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::project::ProjectConfig;
+    use crate::session::{SessionStatus, WorktreeInfo};
+    use std::io::Read;
+
+    #[test]
+    fn snapshot_nests_sessions_under_projects() {
+        let mut project = ProjectInfo::new(ProjectConfig {
+            name: "api".to_string(),
+            repos: Vec::new(),
+            roles: Vec::new(),
+            mcp_servers: Vec::new(),
+            sync_base_branch: None,
+            remote: None,
+            sync_strategy: Default::default(),
+            activity_timeout_ms: None,
+            worktree_root: None,
+            default_role: None,
+            session_init_command: None,
+            id: None,
+        });
+        let mut busy = SessionInfo::new("fix-auth".to_string());
+        busy.worktrees = vec![WorktreeInfo {
+            repo_path: "/repo".into(),
+            worktree_path: "/repo/.git/wt/fix-auth".into(),
+            branch: "fix-auth".to_string(),
+            base_branch: "main".to_string(),
+        }];
+        let mut waiting = SessionInfo::new("docs".to_string());
+        waiting.status = SessionStatus::NeedsInput;
+        waiting.role = "reviewer".to_string();
+        project.session_ids = vec![waiting.id, busy.id];
+
+        let value = snapshot(std::slice::from_ref(&project), &[&busy, &waiting]);
+
+        assert_eq!(
+            value,
+            serde_json::json!({
+                "projects": [{
+                    "id": project.id.to_string(),
+                    "name": "api",
+                    "admin": false,
+                    "sessions": [
+                        {
+                            "id": waiting.id.to_string(),
+                            "name": "docs",
+                            "role": "reviewer",
+                            "status": "needs-input",
+                            "branches": [],
+                        },
+                        {
+                            "id": busy.id.to_string(),
+                            "name": "fix-auth",
+                            "role": "developer",
+                            "status": "busy",
+                            "branches": ["fix-auth"],
+                        },
+                    ],
+                }],
+            })
+        );
+    }
+
+    fn request(server: &StatusServer, method: &str, path: &str) -> String {
+        let mut stream = TcpStream::connect(server.addr()).unwrap();
+        write!(
+            stream,
+            "{method} {path} HTTP/1.1\r\nHost: localhost\r\nUser-Agent: test\r\n\
+             Accept: */*\r\n\r\n"
+        )
+        .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        response
+    }
+
+    fn get(server: &StatusServer, path: &str) -> String {
+        request(server, "GET", path)
+    }
+
+    #[test]
+    fn serves_latest_published_snapshot() {
+        let server = StatusServer::start(0).unwrap();
+        server.publish(&serde_json::json!({ "projects": [] }));
+
+        let response = get(&server, "/status");
+        assert!(response.starts_with("HTTP/1.1 200 OK"));
+        assert!(response.ends_with(r#"{"projects":[]}"#));
+
+        assert!(get(&server, "/other").starts_with("HTTP/1.1 404"));
+    }
+
+    #[test]
+    fn trickling_client_does_not_block_others() {
+        let server = StatusServer::start(0).unwrap();
+        let mut slow = TcpStream::connect(server.addr()).unwrap();
+        write!(slow, "GET /status HTTP/1.1\r\nX-Slow: ").unwrap();
+
+        let started = Instant::now();
+        assert!(get(&server, "/status").starts_with("HTTP/1.1 200"));
+        assert!(started.elapsed() < READ_TIMEOUT);
+
+        // The slow client is dropped once the deadline passes, even though
+        // it keeps sending
+        for _ in 0..6 {
+            std::thread::sleep(READ_TIMEOUT / 4);
+            if slow.write_all(b"x").is_err() {
+                break;
+            }
+        }
+        let mut response = String::new();
+        let _ = slow.read_to_string(&mut response);
+        assert!(response.is_empty());
+    }
+
+    #[test]
+    fn oversized_request_is_dropped() {
+        let server = StatusServer::start(0).unwrap();
+        let mut stream = TcpStream::connect(server.addr()).unwrap();
+        let header = "x".repeat(MAX_REQUEST_BYTES as usize);
+        let _ = write!(stream, "GET /status HTTP/1.1\r\nX-Big: {header}\r\n\r\n");
+        let mut response = String::new();
+        let _ = stream.read_to_string(&mut response);
+        assert!(response.is_empty());
+    }
+
+    #[test]
+    fn rejects_methods_other_than_get() {
+        let server = StatusServer::start(0).unwrap();
+
+        let response = request(&server, "POST", "/status");
+        assert!(response.starts_with("HTTP/1.1 405"));
+        assert!(response.contains("Allow: GET\r\n"));
+    }
+}
@@ -0,0 +1,140 @@
+//! Fuzzy project/session switcher (Ctrl+P).
+//!
+//! Every project and session gets a "project / session" label; typing
+//! filters them by subsequence match and ranks the survivors with
+//! [`fuzzy_match`].
+
+/// Where choosing a switcher entry takes you.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum SwitcherTarget {
+    /// Index into `App::projects`.
+    Project(usize),
+    /// Indices into `App::projects` and `App::sessions`.
+    Session { project: usize, session: usize },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct SwitcherEntry {
+    pub(crate) label: String,
+    pub(crate) target: SwitcherTarget,
+}
+
+/// Bonus for a match at the start of the candidate or after a separator.
+const BOUNDARY_BONUS: i64 = 3;
+/// Bonus for a match directly after the previous match.
+const CONSECUTIVE_BONUS: i64 = 5;
+
+/// Score `candidate` against `query` as a case-insensitive subsequence
+/// match, or `None` when some query character is missing.
+///
+/// Higher is better. Matches at word starts and runs of consecutive
+/// characters earn bonuses; every candidate character skipped between two
+/// matches costs a point. An empty query matches everything with 0.
+pub(crate) fn fuzzy_match(query: &str, candidate: &str) -> Option<i64> {
+    let mut query_chars = query.chars().flat_map(char::to_lowercase).peekable();
+    let mut score = 0;
+    let mut matched_any = false;
+    let mut prev_matched = false;
+    let mut prev_char: Option<char> = None;
+    let mut skipped = 0;
+
+    for c in candidate.chars().flat_map(char::to_lowercase) {
+        let Some(&q) = query_chars.peek() else {
+            break;
+        };
+        if c == q {
+            query_chars.next();
+            score += 1;
+            if prev_matched {
+                score += CONSECUTIVE_BONUS;
+            }
+            if prev_char.map_or(true, |p| matches!(p, ' ' | '/' | '-' | '_' | '.')) {
+                score += BOUNDARY_BONUS;
+            }
+            if matched_any {
+                score -= skipped;
+            }
+            skipped = 0;
+            matched_any = true;
+            prev_matched = true;
+        } else {
+            skipped += 1;
+            prev_matched = false;
+        }
+        prev_char = Some(c);
+    }
+
+    query_chars.peek().is_none().then_some(score)
+}
+
+/// Indices of `entries` matching `query`, best first. Ties keep list order.
+pub(crate) fn rank_entries(query: &str, entries: &[SwitcherEntry]) -> Vec<usize> {
+    let mut scored: Vec<(usize, i64)> = entries
+        .iter()
+        .enumerate()
+        .filter_map(|(i, entry)| fuzzy_match(query, &entry.label).map(|score| (i, score)))
+        .collect();
+    scored.sort_by_key(|&(_, score)| std::cmp::Reverse(score));
+    scored.into_iter().map(|(i, _)| i).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(label: &str) -> SwitcherEntry {
+        SwitcherEntry {
+            label: label.to_string(),
+            target: SwitcherTarget::Project(0),
+        }
+    }
+
+    #[test]
+    fn empty_query_matches_everything() {
+        assert_eq!(fuzzy_match("", "anything"), Some(0));
+        assert_eq!(fuzzy_match("", ""), Some(0));
+    }
+
+    #[test]
+    fn missing_characters_do_not_match() {
+        assert_eq!(fuzzy_match("xyz", "thurbox / Session 1"), None);
+        // Order matters
+        assert_eq!(fuzzy_match("ba", "ab"), None);
+    }
+
+    #[test]
+    fn matching_is_case_insensitive() {
+        assert!(fuzzy_match("SESS", "api / session 2").is_some());
+        assert!(fuzzy_match("api", "API / Session 2").is_some());
+    }
+
+    #[test]
+    fn exact_prefix_beats_scattered_subsequence() {
+        let prefix = fuzzy_match("thu", "thurbox / Session 1").unwrap();
+        let scattered = fuzzy_match("thu", "api / the hub").unwrap();
+        assert!(prefix > scattered, "{prefix} <= {scattered}");
+    }
+
+    #[test]
+    fn word_start_beats_mid_word() {
+        let word_start = fuzzy_match("s", "api / session").unwrap();
+        let mid_word = fuzzy_match("s", "best").unwrap();
+        assert!(word_start > mid_word);
+    }
+
+    #[test]
+    fn rank_orders_by_score_and_drops_misses() {
+        let entries = vec![
+            entry("api / the hub"),
+            entry("docs"),
+            entry("thurbox / Session 1"),
+        ];
+        assert_eq!(rank_entries("thu", &entries), vec![2, 0]);
+    }
+
+    #[test]
+    fn rank_keeps_list_order_for_empty_query() {
+        let entries = vec![entry("b"), entry("a"), entry("c")];
+        assert_eq!(rank_entries("", &entries), vec![0, 1, 2]);
+    }
+}
@@ -0,0 +1,82 @@
+//! Bounded pool for background git jobs (`[worktree_sync] max_concurrent`).
+//!
+//! Worktree syncs and sync previews run off the UI thread. Starting one
+//! thread per job lets a dozen worktrees hammer the same git index at once,
+//! so jobs are queued and drained by at most `limit` worker threads.
+
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+/// A unit of background work. Results are reported by the job itself,
+/// typically through an `mpsc::Sender` it captured.
+pub(crate) type Job = Box<dyn FnOnce() + Send>;
+
+/// Run `jobs` on at most `limit` threads (at least one) without blocking the
+/// caller. Jobs start in order as workers free up.
+pub(crate) fn spawn_bounded(jobs: Vec<Job>, limit: usize) {
+    let workers = limit.max(1).min(jobs.len());
+    let queue = Arc::new(Mutex::new(VecDeque::from(jobs)));
+    for _ in 0..workers {
+        let queue = Arc::clone(&queue);
+        std::thread::spawn(move || loop {
+            let Some(job) = queue.lock().unwrap_or_else(|e| e.into_inner()).pop_front() else {
+                break;
+            };
+            job();
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::mpsc;
+    use std::time::Duration;
+
+    #[test]
+    fn never_runs_more_than_limit_at_once() {
+        let running = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+        let (tx, rx) = mpsc::channel();
+
+        let jobs: Vec<Job> = (0..5)
+            .map(|i| {
+                let running = Arc::clone(&running);
+                let peak = Arc::clone(&peak);
+                let tx = tx.clone();
+                Box::new(move || {
+                    let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+                    peak.fetch_max(now, Ordering::SeqCst);
+                    std::thread::sleep(Duration::from_millis(30));
+                    running.fetch_sub(1, Ordering::SeqCst);
+                    tx.send(i).unwrap();
+                }) as Job
+            })
+            .collect();
+        drop(tx);
+
+        spawn_bounded(jobs, 2);
+
+        let mut done: Vec<i32> = rx.iter().collect();
+        done.sort_unstable();
+        assert_eq!(done, vec![0, 1, 2, 3, 4]);
+        assert!(peak.load(Ordering::SeqCst) <= 2);
+    }
+
+    #[test]
+    fn zero_limit_still_runs_jobs() {
+        let (tx, rx) = mpsc::channel();
+        let jobs: Vec<Job> = (0..3)
+            .map(|i| {
+                let tx = tx.clone();
+                Box::new(move || tx.send(i).unwrap()) as Job
+            })
+            .collect();
+        drop(tx);
+
+        spawn_bounded(jobs, 0);
+
+        assert_eq!(rx.iter().count(), 3);
+    }
+}
@@ -51,8 +51,7 @@ async fn main() -> anyhow::Result<()> {
         )
         .init();
 
-    let db_path = thurbox::paths::database_file()
-        .ok_or_else(|| anyhow::anyhow!("Cannot resolve database path (is HOME set?)"))?;
+    let db_path = thurbox::paths::require(thurbox::paths::PathKind::Database)?;
 
     tracing::info!("Opening database at {}", db_path.display());
 
@@ -1,7 +1,7 @@
 use std::io::{Read, Write};
 use std::path::Path;
 use std::sync::{
-    atomic::{AtomicBool, AtomicU64, Ordering},
+    atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
     Arc, Mutex,
 };
 use std::time::SystemTime;
@@ -12,11 +12,20 @@ use tracing::{debug, error};
 
 use std::collections::HashMap;
 
-use crate::session::{SessionConfig, SessionInfo};
+use super::bell::BellDetector;
+use super::output_log::{OutputLog, OutputLogHandle};
+use super::reflow::{reflow, set_scrollback_len};
+use super::title::{TitleChange, TitleDetector};
+use crate::session::{SessionConfig, SessionInfo, SessionKind};
 
 /// Default permission mode passed to the Claude CLI when no explicit mode is configured.
 const DEFAULT_PERMISSION_MODE: &str = "default";
 
+/// Scrollback lines kept by the active session's terminal parser, and by
+/// every shell pane. Background sessions can be capped lower with
+/// [`Session::set_scrollback_cap`].
+pub const SCROLLBACK_LINES: usize = 1000;
+
 pub(crate) fn now_millis() -> u64 {
     SystemTime::now()
         .duration_since(SystemTime::UNIX_EPOCH)
@@ -24,6 +33,16 @@ pub(crate) fn now_millis() -> u64 {
         .as_millis() as u64
 }
 
+/// Resize a session parser. With `wrap`, a width change rewraps its
+/// contents (see [`reflow`]); otherwise rows are truncated or padded.
+fn resize_parser(parser: &mut vt100::Parser, rows: u16, cols: u16, wrap: bool, scrollback: usize) {
+    if wrap && parser.screen().size().1 != cols {
+        reflow(parser, rows, cols, scrollback);
+    } else {
+        parser.screen_mut().set_size(rows, cols);
+    }
+}
+
 /// Build the CLI argument list from a SessionConfig.
 ///
 /// This is extracted as a pure function for testability.
@@ -68,9 +87,32 @@ pub fn build_claude_args(config: &SessionConfig) -> Vec<String> {
         args.push(dir.display().to_string());
     }
 
+    if let Some(ref path) = config.mcp_config {
+        args.push("--mcp-config".to_string());
+        args.push(path.display().to_string());
+    }
+
     args
 }
 
+/// Program and arguments to spawn for a session: the `claude` CLI (or the
+/// config's command override), or the user's shell (without any Claude
+/// flags) for [`SessionKind::Shell`].
+pub fn build_spawn_command(config: &SessionConfig) -> (String, Vec<String>) {
+    match config.kind {
+        SessionKind::Claude => match config.command {
+            Some(ref command) => command.clone(),
+            None => ("claude".to_string(), build_claude_args(config)),
+        },
+        SessionKind::Shell => (user_shell(), Vec::new()),
+    }
+}
+
+/// `$SHELL`, falling back to `/bin/sh`.
+fn user_shell() -> String {
+    std::env::var("SHELL").unwrap_or_else(|_| "/bin/sh".to_string())
+}
+
 /// Metadata returned when discovering existing sessions from the backend.
 pub struct DiscoveredSession {
     /// Backend-specific ID (e.g., tmux pane_id).
@@ -144,6 +186,17 @@ pub trait SessionBackend: Send + Sync {
 
     /// Detach from a session without killing it (for Ctrl+Q quit).
     fn detach(&self, backend_id: &str) -> Result<()>;
+
+    /// Whether sessions keep running after [`detach`](Self::detach) and can
+    /// be adopted on the next start. Backends without a server to hand
+    /// sessions to stop them instead.
+    fn keeps_sessions_on_detach(&self) -> bool {
+        true
+    }
+
+    /// Rename the window holding a session (the `tb-<name>` label that
+    /// discovery reports).
+    fn rename(&self, backend_id: &str, window_name: &str) -> Result<()>;
 }
 
 /// Internal bundle of I/O handles before wiring.
@@ -160,6 +213,8 @@ struct WiredState {
     input_tx: mpsc::UnboundedSender<Vec<u8>>,
     exited: Arc<AtomicBool>,
     last_output_at: Arc<AtomicU64>,
+    bell_count: Arc<AtomicU64>,
+    title: Arc<Mutex<Option<String>>>,
 }
 
 /// A companion shell pane running alongside a Claude session.
@@ -170,7 +225,6 @@ pub struct ShellPane {
     /// Kept alive so the reader loop's Arc clone has a peer.
     #[allow(dead_code)]
     exited: Arc<AtomicBool>,
-    #[allow(dead_code)]
     last_output_at: Arc<AtomicU64>,
 }
 
@@ -202,9 +256,25 @@ pub struct Session {
     backend: Arc<dyn SessionBackend>,
     exited: Arc<AtomicBool>,
     last_output_at: Arc<AtomicU64>,
+    /// Terminal bells rung so far, counted by the reader loop.
+    bell_count: Arc<AtomicU64>,
+    /// `bell_count` value at the last [`Session::take_bell`] call.
+    bells_seen: AtomicU64,
+    /// Latest window title set by the output (OSC 0/2), if any.
+    title: Arc<Mutex<Option<String>>>,
     pub shell_pane: Option<ShellPane>,
     /// Environment variables from the role, passed to shell pane spawns.
     env: HashMap<String, String>,
+    /// `last_output_at` value at the last prompt scan (avoids rescanning).
+    prompt_checked_at: AtomicU64,
+    /// Cached result of the last prompt scan.
+    awaiting_input: AtomicBool,
+    /// Raw output tee, kept across restarts so a log survives them.
+    output_log: OutputLogHandle,
+    /// Placeholder from a lazy restore, not yet attached to its backend.
+    dormant: bool,
+    /// Scrollback lines `parser` was built to keep.
+    scrollback_cap: AtomicUsize,
 }
 
 impl Session {
@@ -216,12 +286,12 @@ impl Session {
         config: &SessionConfig,
         backend: &Arc<dyn SessionBackend>,
     ) -> Result<Self> {
-        let args = build_claude_args(config);
+        let (command, args) = build_spawn_command(config);
         let window_name = format!("tb-{name}");
 
         let spawned = backend.spawn(
             &window_name,
-            "claude",
+            &command,
             &args,
             config.cwd.as_deref(),
             &config.permissions.env,
@@ -236,6 +306,8 @@ impl Session {
         if !config.role.is_empty() {
             info.role = config.role.clone();
         }
+        info.log_output = config.log_output;
+        info.extra_mcp_servers = config.extra_mcp_servers.clone();
         info.backend_id = Some(spawned.backend_id.clone());
         debug!(session_id = %info.id, backend_id = %spawned.backend_id, "Spawned session via backend");
 
@@ -293,8 +365,14 @@ impl Session {
     }
 
     /// Create parser, spawn reader/writer loops for the given I/O handles.
-    fn wire_up(rows: u16, cols: u16, io: SessionIo) -> (WiredState, String) {
-        let parser = Arc::new(Mutex::new(vt100::Parser::new(rows, cols, 1000)));
+    /// Output is mirrored into `output_log` whenever a log is open in it.
+    fn wire_up(
+        rows: u16,
+        cols: u16,
+        io: SessionIo,
+        output_log: OutputLogHandle,
+    ) -> (WiredState, String) {
+        let parser = Arc::new(Mutex::new(vt100::Parser::new(rows, cols, SCROLLBACK_LINES)));
 
         if !io.initial_screen.is_empty() {
             if let Ok(mut p) = parser.lock() {
@@ -304,6 +382,8 @@ impl Session {
 
         let exited = Arc::new(AtomicBool::new(false));
         let last_output_at = Arc::new(AtomicU64::new(now_millis()));
+        let bell_count = Arc::new(AtomicU64::new(0));
+        let title = Arc::new(Mutex::new(None));
 
         let (input_tx, input_rx) = mpsc::unbounded_channel();
         tokio::spawn(Self::writer_loop(io.input, input_rx));
@@ -311,8 +391,18 @@ impl Session {
         let parser_clone = Arc::clone(&parser);
         let exited_clone = Arc::clone(&exited);
         let last_output_clone = Arc::clone(&last_output_at);
+        let bell_clone = Arc::clone(&bell_count);
+        let title_clone = Arc::clone(&title);
         tokio::task::spawn_blocking(move || {
-            Self::reader_loop(io.output, parser_clone, exited_clone, last_output_clone);
+            Self::reader_loop(
+                io.output,
+                parser_clone,
+                exited_clone,
+                last_output_clone,
+                bell_clone,
+                title_clone,
+                output_log,
+            );
         });
 
         let state = WiredState {
@@ -320,6 +410,8 @@ impl Session {
             input_tx,
             exited,
             last_output_at,
+            bell_count,
+            title,
         };
         (state, io.backend_id)
     }
@@ -333,7 +425,8 @@ impl Session {
         backend: &Arc<dyn SessionBackend>,
         env: HashMap<String, String>,
     ) -> Self {
-        let (state, backend_id) = Self::wire_up(rows, cols, io);
+        let output_log = OutputLogHandle::default();
+        let (state, backend_id) = Self::wire_up(rows, cols, io, Arc::clone(&output_log));
         Self {
             info,
             parser: state.parser,
@@ -342,8 +435,16 @@ impl Session {
             backend: Arc::clone(backend),
             exited: state.exited,
             last_output_at: state.last_output_at,
+            bell_count: state.bell_count,
+            bells_seen: AtomicU64::new(0),
+            title: state.title,
             shell_pane: None,
             env,
+            prompt_checked_at: AtomicU64::new(0),
+            awaiting_input: AtomicBool::new(false),
+            output_log,
+            dormant: false,
+            scrollback_cap: AtomicUsize::new(SCROLLBACK_LINES),
         }
     }
 
@@ -352,8 +453,13 @@ impl Session {
         parser: Arc<Mutex<vt100::Parser>>,
         exited: Arc<AtomicBool>,
         last_output_at: Arc<AtomicU64>,
+        bell_count: Arc<AtomicU64>,
+        title: Arc<Mutex<Option<String>>>,
+        output_log: OutputLogHandle,
     ) {
         let mut buf = [0u8; 4096];
+        let mut bells = BellDetector::default();
+        let mut titles = TitleDetector::default();
         loop {
             match reader.read(&mut buf) {
                 Ok(0) => {
@@ -366,6 +472,25 @@ impl Session {
                     if let Ok(mut p) = parser.lock() {
                         p.process(data);
                     }
+                    let rung = bells.feed(data);
+                    if rung > 0 {
+                        bell_count.fetch_add(rung, Ordering::Relaxed);
+                    }
+                    if let Some(change) = titles.feed(data) {
+                        if let Ok(mut t) = title.lock() {
+                            *t = match change {
+                                TitleChange::Set(s) => Some(s),
+                                TitleChange::Cleared => None,
+                            };
+                        }
+                    }
+                    if let Ok(mut log) = output_log.lock() {
+                        if let Some(Err(e)) = log.as_mut().map(|l| l.write(data)) {
+                            // Stop logging rather than failing on every chunk
+                            tracing::warn!("Session output log write failed, closing log: {e}");
+                            *log = None;
+                        }
+                    }
                 }
                 Err(e) => {
                     debug!("Session reader error: {e}");
@@ -399,13 +524,19 @@ impl Session {
             .map_err(|_| anyhow::anyhow!("Session input channel closed"))
     }
 
-    pub fn resize(&self, rows: u16, cols: u16) {
+    /// Resize the backend panes and parsers. With `wrap`, the parsers'
+    /// contents are rewrapped to the new width instead of truncated.
+    pub fn resize(&self, rows: u16, cols: u16, wrap: bool) {
+        if self.dormant {
+            return;
+        }
         if let Err(e) = self.backend.resize(&self.backend_id, rows, cols) {
             tracing::warn!("Failed to resize session: {e}");
             return;
         }
         if let Ok(mut parser) = self.parser.lock() {
-            parser.screen_mut().set_size(rows, cols);
+            let cap = self.scrollback_cap.load(Ordering::Relaxed);
+            resize_parser(&mut parser, rows, cols, wrap, cap);
         }
         if let Some(shell) = &self.shell_pane {
             if let Err(e) = self.backend.resize(&shell.backend_id, rows, cols) {
@@ -413,11 +544,38 @@ impl Session {
                 return;
             }
             if let Ok(mut parser) = shell.parser.lock() {
-                parser.screen_mut().set_size(rows, cols);
+                resize_parser(&mut parser, rows, cols, wrap, SCROLLBACK_LINES);
             }
         }
     }
 
+    /// Scrollback lines the session's parser currently keeps.
+    pub fn scrollback_cap(&self) -> usize {
+        self.scrollback_cap.load(Ordering::Relaxed)
+    }
+
+    /// Rebuild the parser to keep `lines` of scrollback, so sessions in the
+    /// background hold less history in memory. The visible screen is kept;
+    /// shrinking drops the oldest lines. A no-op for dormant sessions and
+    /// while a full-screen program has the alternate screen up (retried on
+    /// the next call).
+    pub fn set_scrollback_cap(&self, lines: usize) {
+        if self.dormant || self.scrollback_cap() == lines {
+            return;
+        }
+        let Ok(mut parser) = self.parser.lock() else {
+            return;
+        };
+        if set_scrollback_len(&mut parser, lines) {
+            self.scrollback_cap.store(lines, Ordering::Relaxed);
+        }
+    }
+
+    /// Whether this is a lazy-restore placeholder (see [`Session::dormant`]).
+    pub fn is_dormant(&self) -> bool {
+        self.dormant
+    }
+
     pub fn has_exited(&self) -> bool {
         self.exited.load(Ordering::SeqCst)
     }
@@ -426,6 +584,69 @@ impl Session {
         now_millis().saturating_sub(self.last_output_at.load(Ordering::Relaxed))
     }
 
+    /// When the session or its shell pane last produced output (Unix ms).
+    pub fn last_output_at(&self) -> u64 {
+        let shell = self
+            .shell_pane
+            .as_ref()
+            .map_or(0, |shell| shell.last_output_at.load(Ordering::Relaxed));
+        self.last_output_at.load(Ordering::Relaxed).max(shell)
+    }
+
+    /// Whether the terminal bell rang since the last call.
+    pub fn take_bell(&self) -> bool {
+        let count = self.bell_count.load(Ordering::Relaxed);
+        self.bells_seen.swap(count, Ordering::Relaxed) != count
+    }
+
+    /// Latest window title the session's output set, if any.
+    pub fn title(&self) -> Option<String> {
+        self.title.lock().ok().and_then(|t| t.clone())
+    }
+
+    /// Whether Claude's screen shows a prompt waiting on the user.
+    ///
+    /// Best-effort marker scan (see [`super::prompt`]). The screen is only
+    /// rescanned when new output has arrived since the last call, so callers
+    /// should invoke this once output has gone quiet.
+    pub fn is_awaiting_input(&self) -> bool {
+        let last_output = self.last_output_at.load(Ordering::Relaxed);
+        if self.prompt_checked_at.swap(last_output, Ordering::Relaxed) != last_output {
+            let awaiting = self
+                .parser
+                .lock()
+                .map(|p| super::prompt::screen_awaits_input(&p.screen().contents()))
+                .unwrap_or(false);
+            self.awaiting_input.store(awaiting, Ordering::Relaxed);
+        }
+        self.awaiting_input.load(Ordering::Relaxed)
+    }
+
+    /// Start teeing raw output to `path`, replacing any open log.
+    /// Records the path in [`SessionInfo::log_path`].
+    pub fn start_output_log(&mut self, path: &Path, max_bytes: u64) -> std::io::Result<()> {
+        let log = OutputLog::open(path, max_bytes)?;
+        if let Ok(mut slot) = self.output_log.lock() {
+            *slot = Some(log);
+        }
+        self.info.log_path = Some(path.to_path_buf());
+        Ok(())
+    }
+
+    /// Stop teeing output and close the log file.
+    pub fn stop_output_log(&mut self) {
+        if let Ok(mut slot) = self.output_log.lock() {
+            *slot = None;
+        }
+        self.info.log_path = None;
+    }
+
+    /// Whether an output log is open. It can close on its own after a
+    /// write error, so this may disagree with [`SessionInfo::log_path`].
+    pub fn is_logging_output(&self) -> bool {
+        self.output_log.lock().is_ok_and(|slot| slot.is_some())
+    }
+
     /// Return the backend-specific session identifier.
     pub fn backend_id(&self) -> &str {
         &self.backend_id
@@ -441,13 +662,16 @@ impl Session {
     /// Uses `--resume` so Claude picks up the conversation while getting
     /// freshly-resolved role permissions.
     pub fn restart(&mut self, config: &SessionConfig, rows: u16, cols: u16) -> Result<()> {
-        self.backend.kill(&self.backend_id)?;
+        // A placeholder or a dead pane has no backend session to kill
+        if !self.dormant && !self.info.backend_dead {
+            self.backend.kill(&self.backend_id)?;
+        }
 
-        let args = build_claude_args(config);
+        let (command, args) = build_spawn_command(config);
         let window_name = format!("tb-{}", self.info.name);
         let spawned = self.backend.spawn(
             &window_name,
-            "claude",
+            &command,
             &args,
             config.cwd.as_deref(),
             &config.permissions.env,
@@ -464,14 +688,22 @@ impl Session {
                 initial_screen: spawned.initial_screen,
                 backend_id: spawned.backend_id,
             },
+            Arc::clone(&self.output_log),
         );
 
         self.backend_id = backend_id;
         self.parser = state.parser;
+        self.scrollback_cap
+            .store(SCROLLBACK_LINES, Ordering::Relaxed);
         self.input_tx = state.input_tx;
         self.exited = state.exited;
         self.last_output_at = state.last_output_at;
+        self.bell_count = state.bell_count;
+        self.bells_seen.store(0, Ordering::Relaxed);
+        self.title = state.title;
         self.env = config.permissions.env.clone();
+        self.dormant = false;
+        self.info.backend_dead = false;
         self.info.backend_id = Some(self.backend_id.clone());
         if !config.role.is_empty() {
             self.info.role = config.role.clone();
@@ -491,6 +723,9 @@ impl Session {
 
     /// Detach from the backend session without killing it (for Ctrl+Q quit).
     pub fn detach(self) {
+        if self.dormant {
+            return;
+        }
         if let Some(shell) = &self.shell_pane {
             if let Err(e) = self.backend.detach(&shell.backend_id) {
                 tracing::warn!("Failed to detach shell pane: {e}");
@@ -512,7 +747,7 @@ impl Session {
             return Ok(());
         }
 
-        let shell_cmd = std::env::var("SHELL").unwrap_or_else(|_| "/bin/sh".to_string());
+        let shell_cmd = user_shell();
         let window_name = format!("tbs-{}", self.info.name);
 
         let spawned = self.backend.spawn(
@@ -534,6 +769,7 @@ impl Session {
                 initial_screen: spawned.initial_screen,
                 backend_id: spawned.backend_id,
             },
+            OutputLogHandle::default(),
         );
 
         self.info.shell_backend_id = Some(backend_id.clone());
@@ -556,6 +792,7 @@ impl Session {
                 initial_screen: adopted.initial_screen,
                 backend_id: backend_id.to_string(),
             },
+            OutputLogHandle::default(),
         );
 
         self.info.shell_backend_id = Some(bid.clone());
@@ -565,15 +802,78 @@ impl Session {
         Ok(())
     }
 
-    /// Kill the shell pane if it exists.
+    /// Kill the shell pane if it exists. A dormant session never attached
+    /// its shell pane, so fall back to the persisted `shell_backend_id`.
     fn kill_shell_pane(&self) {
-        if let Some(shell) = &self.shell_pane {
-            if let Err(e) = self.backend.kill(&shell.backend_id) {
+        let backend_id = match &self.shell_pane {
+            Some(shell) => Some(shell.backend_id.as_str()),
+            None => self.info.shell_backend_id.as_deref(),
+        };
+        if let Some(backend_id) = backend_id {
+            if let Err(e) = self.backend.kill(backend_id) {
                 tracing::warn!("Failed to kill shell pane: {e}");
             }
         }
     }
 
+    /// Placeholder for a persisted session restored lazily.
+    ///
+    /// Holds the session's metadata and `backend_id` (so saved state is
+    /// unchanged) but never attaches to the backend; resizes and detach are
+    /// no-ops. The app swaps it for an adopted or resumed session on first
+    /// focus.
+    pub fn dormant(
+        info: SessionInfo,
+        backend_id: String,
+        backend: &Arc<dyn SessionBackend>,
+    ) -> Self {
+        let (input_tx, _input_rx) = mpsc::unbounded_channel();
+        Self {
+            info,
+            parser: Arc::new(Mutex::new(vt100::Parser::new(24, 80, 0))),
+            input_tx,
+            backend_id,
+            backend: Arc::clone(backend),
+            exited: Arc::new(AtomicBool::new(false)),
+            last_output_at: Arc::new(AtomicU64::new(now_millis())),
+            bell_count: Arc::new(AtomicU64::new(0)),
+            bells_seen: AtomicU64::new(0),
+            title: Arc::new(Mutex::new(None)),
+            shell_pane: None,
+            env: HashMap::new(),
+            prompt_checked_at: AtomicU64::new(0),
+            awaiting_input: AtomicBool::new(false),
+            output_log: OutputLogHandle::default(),
+            dormant: true,
+            scrollback_cap: AtomicUsize::new(0),
+        }
+    }
+
+    /// Pretend the last output arrived `millis` ago (unit tests only).
+    #[cfg(test)]
+    pub fn backdate_output(&self, millis: u64) {
+        self.last_output_at
+            .store(now_millis().saturating_sub(millis), Ordering::Relaxed);
+    }
+
+    /// Pretend the session's process exited (unit tests only).
+    #[cfg(test)]
+    pub fn mark_exited(&self) {
+        self.exited.store(true, Ordering::SeqCst);
+    }
+
+    /// Pretend the session's output rang the terminal bell (unit tests only).
+    #[cfg(test)]
+    pub fn ring_bell(&self) {
+        self.bell_count.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Pretend the session's output set its window title (unit tests only).
+    #[cfg(test)]
+    pub fn set_title(&self, title: Option<&str>) {
+        *self.title.lock().unwrap() = title.map(String::from);
+    }
+
     /// Create a lightweight stub for unit tests (no real backend process).
     #[cfg(test)]
     pub fn stub(name: &str, backend: &Arc<dyn SessionBackend>) -> Self {
@@ -586,9 +886,173 @@ impl Session {
             backend: Arc::clone(backend),
             exited: Arc::new(AtomicBool::new(false)),
             last_output_at: Arc::new(AtomicU64::new(now_millis())),
+            bell_count: Arc::new(AtomicU64::new(0)),
+            bells_seen: AtomicU64::new(0),
+            title: Arc::new(Mutex::new(None)),
             shell_pane: None,
             env: HashMap::new(),
+            prompt_checked_at: AtomicU64::new(0),
+            awaiting_input: AtomicBool::new(false),
+            output_log: OutputLogHandle::default(),
+            dormant: false,
+            scrollback_cap: AtomicUsize::new(0),
+        }
+    }
+}
+
+/// Behavior every [`SessionBackend`] must share. Each backend's test module
+/// runs these against its own implementation.
+#[cfg(test)]
+pub(crate) mod conformance {
+    use std::collections::HashMap;
+    use std::io::{Read, Write};
+    use std::path::Path;
+    use std::sync::mpsc;
+    use std::time::{Duration, Instant};
+
+    use super::{SessionBackend, SpawnedSession};
+
+    const TIMEOUT: Duration = Duration::from_secs(10);
+
+    pub(crate) fn spawn_sh(
+        backend: &dyn SessionBackend,
+        script: &str,
+        cwd: Option<&Path>,
+        env: &HashMap<String, String>,
+    ) -> SpawnedSession {
+        backend.ensure_ready().unwrap();
+        backend
+            .spawn(
+                "tb-conformance",
+                "sh",
+                &["-c".to_string(), script.to_string()],
+                cwd,
+                env,
+                24,
+                80,
+            )
+            .unwrap()
+    }
+
+    /// Read the session's output on a helper thread until `needle` shows up
+    /// (in the seeded screen or the stream) or [`TIMEOUT`] passes.
+    pub(crate) fn wait_for_output(
+        initial_screen: &[u8],
+        mut output: Box<dyn Read + Send>,
+        needle: &str,
+    ) -> bool {
+        let mut seen = String::from_utf8_lossy(initial_screen).to_string();
+        if seen.contains(needle) {
+            return true;
+        }
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            let mut buf = [0u8; 4096];
+            while let Ok(n) = output.read(&mut buf) {
+                if n == 0 || tx.send(buf[..n].to_vec()).is_err() {
+                    break;
+                }
+            }
+        });
+        let deadline = Instant::now() + TIMEOUT;
+        while let Some(left) = deadline.checked_duration_since(Instant::now()) {
+            match rx.recv_timeout(left) {
+                Ok(chunk) => {
+                    seen.push_str(&String::from_utf8_lossy(&chunk));
+                    if seen.contains(needle) {
+                        return true;
+                    }
+                }
+                Err(_) => break,
+            }
         }
+        false
+    }
+
+    fn wait_until_dead(backend: &dyn SessionBackend, backend_id: &str) -> bool {
+        let deadline = Instant::now() + TIMEOUT;
+        while Instant::now() < deadline {
+            if backend.is_dead(backend_id).unwrap_or(false) {
+                return true;
+            }
+            std::thread::sleep(Duration::from_millis(20));
+        }
+        false
+    }
+
+    pub fn spawn_streams_output(backend: &dyn SessionBackend) {
+        let spawned = spawn_sh(
+            backend,
+            "printf 'conformance-ready'; sleep 30",
+            None,
+            &HashMap::new(),
+        );
+        let id = spawned.backend_id.clone();
+        assert!(wait_for_output(
+            &spawned.initial_screen,
+            spawned.output,
+            "conformance-ready"
+        ));
+        backend.kill(&id).unwrap();
+    }
+
+    pub fn input_reaches_process(backend: &dyn SessionBackend) {
+        let spawned = spawn_sh(
+            backend,
+            "read line; printf 'got:%s' \"$line\"; sleep 30",
+            None,
+            &HashMap::new(),
+        );
+        let id = spawned.backend_id.clone();
+        let mut input = spawned.input;
+        input.write_all(b"ping\r").unwrap();
+        input.flush().unwrap();
+        assert!(wait_for_output(
+            &spawned.initial_screen,
+            spawned.output,
+            "got:ping"
+        ));
+        backend.kill(&id).unwrap();
+    }
+
+    pub fn spawn_applies_cwd_and_env(backend: &dyn SessionBackend) {
+        let dir = tempfile::TempDir::new().unwrap();
+        let cwd = dir.path().canonicalize().unwrap();
+        let env = HashMap::from([("THURBOX_CONFORMANCE".to_string(), "env-ok".to_string())]);
+        let spawned = spawn_sh(
+            backend,
+            "printf '%s|%s|' \"$THURBOX_CONFORMANCE\" \"$(pwd -P)\"; sleep 30",
+            Some(&cwd),
+            &env,
+        );
+        let id = spawned.backend_id.clone();
+        let expected = format!("env-ok|{}|", cwd.display());
+        assert!(wait_for_output(
+            &spawned.initial_screen,
+            spawned.output,
+            &expected
+        ));
+        backend.kill(&id).unwrap();
+    }
+
+    pub fn resize_live_session(backend: &dyn SessionBackend) {
+        let spawned = spawn_sh(backend, "sleep 30", None, &HashMap::new());
+        backend.resize(&spawned.backend_id, 40, 120).unwrap();
+        backend.kill(&spawned.backend_id).unwrap();
+    }
+
+    pub fn exited_process_is_dead(backend: &dyn SessionBackend) {
+        let spawned = spawn_sh(backend, "sleep 0.5; exit 0", None, &HashMap::new());
+        assert!(wait_until_dead(backend, &spawned.backend_id));
+        backend.kill(&spawned.backend_id).unwrap();
+    }
+
+    pub fn kill_stops_process(backend: &dyn SessionBackend) {
+        let spawned = spawn_sh(backend, "sleep 30", None, &HashMap::new());
+        assert!(!backend.is_dead(&spawned.backend_id).unwrap());
+        backend.kill(&spawned.backend_id).unwrap();
+        // A killed session is either reported dead or no longer known.
+        assert!(backend.is_dead(&spawned.backend_id).unwrap_or(true));
     }
 }
 
@@ -599,6 +1063,86 @@ mod tests {
     use super::*;
     use crate::session::RolePermissions;
 
+    /// Backend that records the env of every spawn and hands back inert I/O.
+    #[derive(Default)]
+    struct EnvCaptureBackend {
+        spawned_env: Mutex<Vec<HashMap<String, String>>>,
+    }
+
+    impl SessionBackend for EnvCaptureBackend {
+        fn name(&self) -> &str {
+            "env-capture"
+        }
+        fn check_available(&self) -> Result<()> {
+            Ok(())
+        }
+        fn ensure_ready(&self) -> Result<()> {
+            Ok(())
+        }
+        fn spawn(
+            &self,
+            _: &str,
+            _: &str,
+            _: &[String],
+            _: Option<&Path>,
+            env: &HashMap<String, String>,
+            _: u16,
+            _: u16,
+        ) -> Result<SpawnedSession> {
+            self.spawned_env.lock().unwrap().push(env.clone());
+            Ok(SpawnedSession {
+                backend_id: "%env".to_string(),
+                output: Box::new(std::io::empty()),
+                input: Box::new(std::io::sink()),
+                initial_screen: Vec::new(),
+            })
+        }
+        fn adopt(&self, _: &str, _: u16, _: u16) -> Result<AdoptedSession> {
+            anyhow::bail!("not supported")
+        }
+        fn discover(&self) -> Result<Vec<DiscoveredSession>> {
+            Ok(Vec::new())
+        }
+        fn resize(&self, _: &str, _: u16, _: u16) -> Result<()> {
+            Ok(())
+        }
+        fn is_dead(&self, _: &str) -> Result<bool> {
+            Ok(false)
+        }
+        fn kill(&self, _: &str) -> Result<()> {
+            Ok(())
+        }
+        fn detach(&self, _: &str) -> Result<()> {
+            Ok(())
+        }
+        fn rename(&self, _: &str, _: &str) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn spawn_and_restart_apply_role_env() {
+        let capture = Arc::new(EnvCaptureBackend::default());
+        let backend: Arc<dyn SessionBackend> = capture.clone();
+        let env = HashMap::from([(
+            "ANTHROPIC_BASE_URL".to_string(),
+            "https://staging.example".to_string(),
+        )]);
+        let config = SessionConfig {
+            role: "staging".to_string(),
+            permissions: RolePermissions {
+                env: env.clone(),
+                ..RolePermissions::default()
+            },
+            ..SessionConfig::default()
+        };
+
+        let mut session = Session::spawn("s".to_string(), 24, 80, &config, &backend).unwrap();
+        session.restart(&config, 24, 80).unwrap();
+
+        assert_eq!(*capture.spawned_env.lock().unwrap(), vec![env.clone(), env]);
+    }
+
     #[test]
     fn build_args_empty_config() {
         let config = SessionConfig::default();
@@ -724,6 +1268,64 @@ mod tests {
         assert!(ms > 1_704_067_200_000);
     }
 
+    #[test]
+    fn claude_kind_spawns_claude_with_args() {
+        let config = SessionConfig {
+            claude_session_id: Some("abc-123".to_string()),
+            ..SessionConfig::default()
+        };
+        let (command, args) = build_spawn_command(&config);
+        assert_eq!(command, "claude");
+        assert_eq!(args, build_claude_args(&config));
+    }
+
+    #[test]
+    fn shell_kind_spawns_shell_without_claude_flags() {
+        let config = SessionConfig {
+            kind: SessionKind::Shell,
+            resume_session_id: Some("resume-id".to_string()),
+            permissions: RolePermissions {
+                permission_mode: Some("plan".to_string()),
+                ..RolePermissions::default()
+            },
+            ..SessionConfig::default()
+        };
+        let (command, args) = build_spawn_command(&config);
+        assert_eq!(command, user_shell());
+        assert_ne!(command, "claude");
+        assert!(args.is_empty());
+    }
+
+    #[test]
+    fn build_spawn_command_uses_command_override() {
+        let config = SessionConfig {
+            command: Some(("my-agent".to_string(), vec!["--fast".to_string()])),
+            mcp_config: Some(PathBuf::from("/data/mcp-configs/abc.json")),
+            ..SessionConfig::default()
+        };
+        let (command, args) = build_spawn_command(&config);
+        assert_eq!(command, "my-agent");
+        assert_eq!(args, vec!["--fast"]);
+    }
+
+    #[test]
+    fn build_args_with_mcp_config() {
+        let config = SessionConfig {
+            mcp_config: Some(PathBuf::from("/data/mcp-configs/abc.json")),
+            ..SessionConfig::default()
+        };
+        let args = build_claude_args(&config);
+        assert_eq!(
+            args,
+            vec![
+                "--permission-mode",
+                "default",
+                "--mcp-config",
+                "/data/mcp-configs/abc.json",
+            ]
+        );
+    }
+
     #[test]
     fn build_args_with_additional_dirs() {
         let config = SessionConfig {
@@ -783,4 +1385,43 @@ mod tests {
             ]
         );
     }
+
+    /// Run the reader loop to EOF over `chunks`, returning the parser.
+    fn run_reader_loop(chunks: &[&[u8]], output_log: OutputLogHandle) -> vt100::Parser {
+        let data: Vec<u8> = chunks.concat();
+        let parser = Arc::new(Mutex::new(vt100::Parser::new(5, 40, 0)));
+        Session::reader_loop(
+            Box::new(std::io::Cursor::new(data)),
+            Arc::clone(&parser),
+            Arc::new(AtomicBool::new(false)),
+            Arc::new(AtomicU64::new(0)),
+            Arc::new(AtomicU64::new(0)),
+            Arc::new(Mutex::new(None)),
+            output_log,
+        );
+        Arc::try_unwrap(parser).ok().unwrap().into_inner().unwrap()
+    }
+
+    #[test]
+    fn reader_loop_mirrors_output_into_log() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("session-logs/s.log");
+        let log = OutputLog::open(&path, 1024).unwrap();
+        let handle: OutputLogHandle = Arc::new(Mutex::new(Some(log)));
+
+        let bytes: &[u8] = b"\x1b[1mhello\x1b[0m\r\nworld";
+        let parser = run_reader_loop(&[bytes], Arc::clone(&handle));
+
+        assert_eq!(parser.screen().contents(), "hello\nworld");
+        // The log receives the raw bytes, escape sequences included
+        assert_eq!(std::fs::read(&path).unwrap(), bytes);
+    }
+
+    #[test]
+    fn reader_loop_without_log_only_feeds_parser() {
+        let handle = OutputLogHandle::default();
+        let parser = run_reader_loop(&[b"plain"], Arc::clone(&handle));
+        assert_eq!(parser.screen().contents(), "plain");
+        assert!(handle.lock().unwrap().is_none());
+    }
 }
@@ -0,0 +1,89 @@
+//! Terminal bell detection for session output.
+//!
+//! vt100 0.16 only reports bells through its `Callbacks` type parameter, so
+//! the reader loop runs this small scanner over each chunk instead. A BEL
+//! that terminates an OSC string (e.g. a window title) is not a bell, and
+//! escape sequences may be split across reads, so state carries over.
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+enum State {
+    #[default]
+    Ground,
+    /// After ESC.
+    Escape,
+    /// Inside an OSC, DCS, APC, PM or SOS string.
+    String,
+    /// After ESC inside a string; `\` ends it.
+    StringEscape,
+}
+
+/// Counts BEL characters in a byte stream, skipping string terminators.
+#[derive(Debug, Default)]
+pub struct BellDetector {
+    state: State,
+}
+
+impl BellDetector {
+    /// Scan the next chunk of output and return how many bells it rang.
+    pub fn feed(&mut self, data: &[u8]) -> u64 {
+        let mut bells = 0;
+        for &byte in data {
+            if self.state == State::StringEscape {
+                if byte == b'\\' {
+                    self.state = State::Ground;
+                    continue;
+                }
+                // Any other escape aborts the string and starts a new one
+                self.state = State::Escape;
+            }
+            self.state = match (self.state, byte) {
+                (State::String, 0x07 | 0x18 | 0x1a) => State::Ground,
+                (State::String, 0x1b) => State::StringEscape,
+                (State::String, _) => State::String,
+                (state, 0x07) => {
+                    bells += 1;
+                    state
+                }
+                (_, 0x1b) => State::Escape,
+                (State::Escape, b']' | b'P' | b'_' | b'^' | b'X') => State::String,
+                _ => State::Ground,
+            };
+        }
+        bells
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_bare_bells() {
+        let mut detector = BellDetector::default();
+        assert_eq!(detector.feed(b"done\x07"), 1);
+        assert_eq!(detector.feed(b"\x07\x07"), 2);
+        assert_eq!(detector.feed(b"plain output"), 0);
+    }
+
+    #[test]
+    fn osc_terminator_is_not_a_bell() {
+        let mut detector = BellDetector::default();
+        assert_eq!(detector.feed(b"\x1b]0;title\x07"), 0);
+        assert_eq!(detector.feed(b"\x1b]0;title\x1b\\\x07"), 1);
+    }
+
+    #[test]
+    fn sequences_split_across_reads() {
+        let mut detector = BellDetector::default();
+        assert_eq!(detector.feed(b"\x1b"), 0);
+        assert_eq!(detector.feed(b"]2;ti"), 0);
+        assert_eq!(detector.feed(b"tle\x07\x07"), 1);
+    }
+
+    #[test]
+    fn csi_sequences_do_not_hide_bells() {
+        let mut detector = BellDetector::default();
+        assert_eq!(detector.feed(b"\x1b[31mred\x1b[m\x07"), 1);
+        assert_eq!(detector.feed(b"\x1b\x07"), 1);
+    }
+}
@@ -0,0 +1,114 @@
+//! Per-session MCP config files passed to Claude via `--mcp-config`.
+//!
+//! A session's project servers and its extra servers are merged and written
+//! to `<log dir>/mcp-configs/<claude session id>.json`, outside the repo, so
+//! a checked-in `.mcp.json` is never touched. The file is keyed by the
+//! Claude session ID, which survives restarts, so `--resume` rewrites the
+//! same file.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde_json::{json, Map, Value};
+
+use crate::paths;
+use crate::session::McpServerConfig;
+
+/// Config file path for a Claude session ID.
+pub fn session_mcp_config_path(claude_session_id: &str) -> Option<PathBuf> {
+    paths::log_directory().map(|dir| {
+        dir.join("mcp-configs")
+            .join(format!("{claude_session_id}.json"))
+    })
+}
+
+/// Build the `{"mcpServers": {...}}` document Claude expects. Disabled
+/// servers are left out.
+pub fn mcp_config_json(servers: &[McpServerConfig]) -> Value {
+    let mut map = Map::new();
+    for server in servers.iter().filter(|s| s.enabled) {
+        let mut entry = json!({
+            "command": server.command,
+            "args": server.args,
+        });
+        if !server.env.is_empty() {
+            entry["env"] = json!(server.env);
+        }
+        map.insert(server.name.clone(), entry);
+    }
+    json!({ "mcpServers": map })
+}
+
+/// Write `servers` to `path`, creating parent directories as needed.
+pub fn write_mcp_config(path: &Path, servers: &[McpServerConfig]) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let contents = serde_json::to_string_pretty(&mcp_config_json(servers))?;
+    fs::write(path, contents)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    fn server(name: &str) -> McpServerConfig {
+        McpServerConfig {
+            name: name.to_string(),
+            command: "npx".to_string(),
+            args: vec!["-y".to_string(), format!("@mcp/{name}")],
+            env: HashMap::new(),
+            enabled: true,
+        }
+    }
+
+    #[test]
+    fn json_keys_servers_by_name() {
+        let mut with_env = server("db");
+        with_env
+            .env
+            .insert("DB_URL".to_string(), "postgres://x".to_string());
+        let value = mcp_config_json(&[server("github"), with_env]);
+
+        let servers = value["mcpServers"].as_object().unwrap();
+        assert_eq!(servers.len(), 2);
+        assert_eq!(servers["github"]["command"], "npx");
+        assert_eq!(servers["github"]["args"][1], "@mcp/github");
+        assert!(servers["github"].get("env").is_none());
+        assert_eq!(servers["db"]["env"]["DB_URL"], "postgres://x");
+    }
+
+    #[test]
+    fn json_omits_disabled_servers() {
+        let mut disabled = server("db");
+        disabled.enabled = false;
+        let value = mcp_config_json(&[server("github"), disabled]);
+
+        let servers = value["mcpServers"].as_object().unwrap();
+        assert_eq!(servers.len(), 1);
+        assert!(servers.contains_key("github"));
+        assert!(!servers.contains_key("db"));
+    }
+
+    #[test]
+    fn write_creates_parent_directories() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("mcp-configs/abc.json");
+        write_mcp_config(&path, &[server("github")]).unwrap();
+
+        let written: Value = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(written, mcp_config_json(&[server("github")]));
+    }
+
+    #[test]
+    fn path_uses_mcp_configs_dir() {
+        let Some(path) = session_mcp_config_path("abc-123") else {
+            return;
+        };
+        assert_eq!(path.file_name().unwrap(), "abc-123.json");
+        assert_eq!(path.parent().unwrap().file_name().unwrap(), "mcp-configs");
+    }
+}
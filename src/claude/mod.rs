@@ -1,5 +1,12 @@
 pub mod backend;
+pub mod bell;
 pub mod input;
+pub mod mcp_config;
+pub mod output_log;
+pub mod prompt;
+pub mod pty;
+pub mod reflow;
+pub mod title;
 pub mod tmux;
 
 pub use backend::{Session, SessionBackend};
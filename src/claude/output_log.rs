@@ -0,0 +1,184 @@
+//! Optional tee of a session's raw PTY output to a log file.
+//!
+//! The reader loop mirrors every chunk it feeds to the vt100 parser into the
+//! session's [`OutputLogHandle`] when a log is open. Files live under
+//! `<log dir>/session-logs/` and are truncated once they would grow past the
+//! configured size, so a long-running agent cannot fill the disk.
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use crate::paths;
+use crate::session::SessionId;
+
+/// Default size at which a session log is truncated (10 MiB).
+pub const DEFAULT_MAX_BYTES: u64 = 10 * 1024 * 1024;
+
+/// Shared slot for a session's log, written by the reader loop. `None` while
+/// logging is off.
+pub type OutputLogHandle = Arc<Mutex<Option<OutputLog>>>;
+
+/// An append-only log file that truncates itself when it grows too large.
+pub struct OutputLog {
+    file: File,
+    path: PathBuf,
+    len: u64,
+    max_bytes: u64,
+}
+
+impl OutputLog {
+    /// Open (or create) the log at `path`, appending to existing content.
+    /// Parent directories are created as needed.
+    pub fn open(path: &Path, max_bytes: u64) -> io::Result<Self> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        let len = file.metadata()?.len();
+        Ok(Self {
+            file,
+            path: path.to_path_buf(),
+            len,
+            max_bytes,
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Append `data`, first truncating the file if the write would push it
+    /// past `max_bytes`.
+    pub fn write(&mut self, data: &[u8]) -> io::Result<()> {
+        if self.len > 0 && self.len + data.len() as u64 > self.max_bytes {
+            self.file.set_len(0)?;
+            self.len = 0;
+        }
+        self.file.write_all(data)?;
+        self.len += data.len() as u64;
+        Ok(())
+    }
+}
+
+/// Log file path for a session: `<log dir>/session-logs/<name>-<id>.log`.
+///
+/// Characters in the name that are unsafe in file names are replaced with `_`.
+pub fn session_log_path(name: &str, id: SessionId) -> Option<PathBuf> {
+    let file_name = format!("{}-{id}.log", sanitize_name(name));
+    paths::log_directory().map(|dir| dir.join("session-logs").join(file_name))
+}
+
+/// Suggested file for a scrollback export (F7):
+/// `<log dir>/scrollback/<name>-<id>.txt`.
+pub fn scrollback_export_path(name: &str, id: SessionId) -> Option<PathBuf> {
+    let file_name = format!("{}-{id}.txt", sanitize_name(name));
+    paths::log_directory().map(|dir| dir.join("scrollback").join(file_name))
+}
+
+fn sanitize_name(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn open_creates_parent_directories() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nested/session-logs/a.log");
+        let log = OutputLog::open(&path, 100).unwrap();
+        assert_eq!(log.path(), path);
+        assert!(path.exists());
+    }
+
+    #[test]
+    fn write_appends() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("a.log");
+        let mut log = OutputLog::open(&path, 100).unwrap();
+        log.write(b"hello ").unwrap();
+        log.write(b"world").unwrap();
+        assert_eq!(fs::read(&path).unwrap(), b"hello world");
+    }
+
+    #[test]
+    fn open_appends_to_existing_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("a.log");
+        fs::write(&path, b"before\n").unwrap();
+        let mut log = OutputLog::open(&path, 100).unwrap();
+        log.write(b"after").unwrap();
+        assert_eq!(fs::read(&path).unwrap(), b"before\nafter");
+    }
+
+    #[test]
+    fn write_truncates_when_over_max() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("a.log");
+        let mut log = OutputLog::open(&path, 10).unwrap();
+        log.write(b"12345678").unwrap();
+        log.write(b"abc").unwrap();
+        assert_eq!(fs::read(&path).unwrap(), b"abc");
+        log.write(b"defg").unwrap();
+        assert_eq!(fs::read(&path).unwrap(), b"abcdefg");
+    }
+
+    #[test]
+    fn write_counts_existing_length_toward_max() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("a.log");
+        fs::write(&path, b"123456789").unwrap();
+        let mut log = OutputLog::open(&path, 10).unwrap();
+        log.write(b"xy").unwrap();
+        assert_eq!(fs::read(&path).unwrap(), b"xy");
+    }
+
+    #[test]
+    fn oversized_chunk_is_still_written() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("a.log");
+        let mut log = OutputLog::open(&path, 4).unwrap();
+        log.write(b"0123456789").unwrap();
+        assert_eq!(fs::read(&path).unwrap(), b"0123456789");
+    }
+
+    #[test]
+    fn sanitize_replaces_path_characters() {
+        assert_eq!(sanitize_name("Session 1"), "Session_1");
+        assert_eq!(sanitize_name("../etc/passwd"), ".._etc_passwd");
+        assert_eq!(sanitize_name("api-v2_fix.x"), "api-v2_fix.x");
+    }
+
+    #[test]
+    fn session_log_path_uses_session_logs_dir() {
+        let Some(path) = session_log_path("My Session", SessionId::default()) else {
+            return;
+        };
+        let file_name = path.file_name().unwrap().to_string_lossy().to_string();
+        assert!(file_name.starts_with("My_Session-"));
+        assert!(file_name.ends_with(".log"));
+        assert_eq!(path.parent().unwrap().file_name().unwrap(), "session-logs");
+    }
+
+    #[test]
+    fn scrollback_export_path_uses_scrollback_dir() {
+        let Some(path) = scrollback_export_path("My Session", SessionId::default()) else {
+            return;
+        };
+        let file_name = path.file_name().unwrap().to_string_lossy().to_string();
+        assert!(file_name.starts_with("My_Session-"));
+        assert!(file_name.ends_with(".txt"));
+        assert_eq!(path.parent().unwrap().file_name().unwrap(), "scrollback");
+    }
+}
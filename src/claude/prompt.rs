@@ -0,0 +1,94 @@
+//! Best-effort detection of Claude prompts that need a user answer.
+//!
+//! Claude Code renders permission requests and questions as a numbered
+//! option list under a question line. Spotting those markers on the visible
+//! screen lets the UI tell "Claude is asking me something" apart from
+//! "Claude went quiet". When no marker matches, callers fall back to the
+//! plain output-timeout heuristic.
+
+/// Substrings that indicate Claude is blocked on a user decision.
+const INPUT_PROMPT_MARKERS: &[&str] = &["Do you want to", "Would you like to", "❯ 1.", "(y/n)"];
+
+/// Substrings that indicate Claude is still working (overrides markers).
+const BUSY_MARKERS: &[&str] = &["esc to interrupt"];
+
+/// Only the bottom of the screen is considered, so prompts answered long
+/// ago that are still in view don't keep matching.
+const SCAN_TAIL_LINES: usize = 12;
+
+/// Check whether the visible screen contents show a prompt awaiting input.
+pub fn screen_awaits_input(contents: &str) -> bool {
+    let tail: Vec<&str> = contents
+        .lines()
+        .rev()
+        .filter(|line| !line.trim().is_empty())
+        .take(SCAN_TAIL_LINES)
+        .collect();
+
+    if tail
+        .iter()
+        .any(|line| BUSY_MARKERS.iter().any(|m| line.contains(m)))
+    {
+        return false;
+    }
+
+    tail.iter()
+        .any(|line| INPUT_PROMPT_MARKERS.iter().any(|m| line.contains(m)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_screen_is_not_awaiting() {
+        assert!(!screen_awaits_input(""));
+    }
+
+    #[test]
+    fn plain_output_is_not_awaiting() {
+        let screen = "Reading src/main.rs\nEditing 3 files\n> \n";
+        assert!(!screen_awaits_input(screen));
+    }
+
+    #[test]
+    fn permission_prompt_is_awaiting() {
+        let screen = "\
+Bash command
+  cargo test
+
+Do you want to proceed?
+❯ 1. Yes
+  2. Yes, and don't ask again for cargo test commands
+  3. No, and tell Claude what to do differently (esc)
+";
+        assert!(screen_awaits_input(screen));
+    }
+
+    #[test]
+    fn plan_approval_is_awaiting() {
+        let screen = "Here is the plan...\nWould you like to proceed?\n";
+        assert!(screen_awaits_input(screen));
+    }
+
+    #[test]
+    fn busy_marker_overrides_prompt() {
+        let screen = "Do you want to proceed?\n✻ Thinking… (esc to interrupt)\n";
+        assert!(!screen_awaits_input(screen));
+    }
+
+    #[test]
+    fn prompt_scrolled_above_tail_is_ignored() {
+        let mut screen = String::from("Do you want to proceed?\n");
+        for i in 0..SCAN_TAIL_LINES {
+            screen.push_str(&format!("output line {i}\n"));
+        }
+        assert!(!screen_awaits_input(&screen));
+    }
+
+    #[test]
+    fn trailing_blank_lines_are_skipped() {
+        let screen = "Do you want to proceed?\n❯ 1. Yes\n\n\n\n\n\n\n\n\n\n\n\n\n\n\n";
+        assert!(screen_awaits_input(screen));
+    }
+}
@@ -0,0 +1,319 @@
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+use anyhow::{bail, Context, Result};
+use portable_pty::{native_pty_system, Child, CommandBuilder, MasterPty, PtySize};
+use tracing::{debug, warn};
+
+use crate::claude::backend::{AdoptedSession, DiscoveredSession, SessionBackend, SpawnedSession};
+
+/// Plain-PTY backend — each session is a child process on a PTY owned by
+/// this thurbox process. For hosts without tmux (minimal containers, CI).
+///
+/// Unlike [`LocalTmuxBackend`](super::tmux::LocalTmuxBackend), nothing
+/// outlives thurbox: quitting drops every PTY, which hangs up its process.
+/// [`discover`](SessionBackend::discover) therefore always returns an empty
+/// list and [`adopt`](SessionBackend::adopt) always fails, so restore falls
+/// back to spawning `claude --resume` for each persisted session.
+pub struct LocalPtyBackend {
+    panes: Mutex<HashMap<String, PtyPane>>,
+    next_id: AtomicU64,
+}
+
+/// A running child and the master side of its PTY. Dropping the master
+/// closes the PTY, which ends the reader returned from `spawn`.
+struct PtyPane {
+    master: Box<dyn MasterPty + Send>,
+    child: Box<dyn Child + Send + Sync>,
+}
+
+impl Default for LocalPtyBackend {
+    fn default() -> Self {
+        Self {
+            panes: Mutex::new(HashMap::new()),
+            next_id: AtomicU64::new(1),
+        }
+    }
+}
+
+impl LocalPtyBackend {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn lock_panes(&self) -> Result<std::sync::MutexGuard<'_, HashMap<String, PtyPane>>> {
+        self.panes
+            .lock()
+            .map_err(|e| anyhow::anyhow!("pty panes lock: {e}"))
+    }
+
+    /// IDs include the PID so they never collide with another instance's
+    /// sessions in the shared database.
+    fn next_backend_id(&self) -> String {
+        let n = self.next_id.fetch_add(1, Ordering::Relaxed);
+        format!("pty-{}-{n}", std::process::id())
+    }
+}
+
+fn pty_size(rows: u16, cols: u16) -> PtySize {
+    PtySize {
+        rows,
+        cols,
+        pixel_width: 0,
+        pixel_height: 0,
+    }
+}
+
+impl SessionBackend for LocalPtyBackend {
+    fn name(&self) -> &str {
+        "local-pty"
+    }
+
+    fn check_available(&self) -> Result<()> {
+        native_pty_system()
+            .openpty(pty_size(24, 80))
+            .context("Cannot open a pseudo-terminal")?;
+        Ok(())
+    }
+
+    fn ensure_ready(&self) -> Result<()> {
+        Ok(())
+    }
+
+    fn spawn(
+        &self,
+        window_name: &str,
+        command: &str,
+        args: &[String],
+        cwd: Option<&Path>,
+        env: &HashMap<String, String>,
+        rows: u16,
+        cols: u16,
+    ) -> Result<SpawnedSession> {
+        let pair = native_pty_system()
+            .openpty(pty_size(rows, cols))
+            .context("Failed to open pty")?;
+
+        let mut cmd = CommandBuilder::new(command);
+        cmd.args(args);
+        if let Some(dir) = cwd {
+            cmd.cwd(dir);
+        }
+        for (key, value) in env {
+            cmd.env(key, value);
+        }
+
+        let child = pair
+            .slave
+            .spawn_command(cmd)
+            .with_context(|| format!("Failed to spawn {command}"))?;
+        // The child holds its own handle to the slave side.
+        drop(pair.slave);
+
+        let output = pair
+            .master
+            .try_clone_reader()
+            .context("Failed to get pty reader")?;
+        let input = pair
+            .master
+            .take_writer()
+            .context("Failed to get pty writer")?;
+
+        let backend_id = self.next_backend_id();
+        debug!(backend_id = %backend_id, window_name, "pty session spawned");
+        self.lock_panes()?.insert(
+            backend_id.clone(),
+            PtyPane {
+                master: pair.master,
+                child,
+            },
+        );
+
+        Ok(SpawnedSession {
+            backend_id,
+            output,
+            input,
+            initial_screen: Vec::new(),
+        })
+    }
+
+    fn adopt(&self, backend_id: &str, _rows: u16, _cols: u16) -> Result<AdoptedSession> {
+        bail!(
+            "pty session {backend_id} cannot be adopted: plain-PTY sessions do not outlive thurbox"
+        )
+    }
+
+    fn discover(&self) -> Result<Vec<DiscoveredSession>> {
+        // Nothing survives a restart, so there is never anything to find.
+        Ok(Vec::new())
+    }
+
+    fn resize(&self, backend_id: &str, rows: u16, cols: u16) -> Result<()> {
+        let panes = self.lock_panes()?;
+        let pane = panes
+            .get(backend_id)
+            .with_context(|| format!("Unknown pty session {backend_id}"))?;
+        pane.master.resize(pty_size(rows, cols))
+    }
+
+    fn is_dead(&self, backend_id: &str) -> Result<bool> {
+        let mut panes = self.lock_panes()?;
+        match panes.get_mut(backend_id) {
+            Some(pane) => Ok(pane.child.try_wait()?.is_some()),
+            None => Ok(true),
+        }
+    }
+
+    fn kill(&self, backend_id: &str) -> Result<()> {
+        let Some(mut pane) = self.lock_panes()?.remove(backend_id) else {
+            return Ok(());
+        };
+        if pane.child.try_wait()?.is_none() {
+            pane.child.kill().context("Failed to kill pty child")?;
+        }
+        // Reap the child so it does not linger as a zombie until exit.
+        pane.child.wait().context("Failed to reap pty child")?;
+        Ok(())
+    }
+
+    fn detach(&self, backend_id: &str) -> Result<()> {
+        // There is no server to hand the session to: dropping the PTY hangs
+        // up the child. Kill it outright so it does not linger half-dead.
+        if let Err(e) = self.kill(backend_id) {
+            warn!("Failed to stop pty session {backend_id} on detach: {e}");
+        }
+        Ok(())
+    }
+
+    fn keeps_sessions_on_detach(&self) -> bool {
+        false
+    }
+
+    fn rename(&self, _backend_id: &str, _window_name: &str) -> Result<()> {
+        // Window names only matter for discovery, which plain PTYs lack.
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::claude::backend::conformance;
+
+    #[test]
+    fn spawn_streams_output() {
+        conformance::spawn_streams_output(&LocalPtyBackend::new());
+    }
+
+    #[test]
+    fn input_reaches_process() {
+        conformance::input_reaches_process(&LocalPtyBackend::new());
+    }
+
+    #[test]
+    fn spawn_applies_cwd_and_env() {
+        conformance::spawn_applies_cwd_and_env(&LocalPtyBackend::new());
+    }
+
+    #[test]
+    fn resize_live_session() {
+        conformance::resize_live_session(&LocalPtyBackend::new());
+    }
+
+    #[test]
+    fn exited_process_is_dead() {
+        conformance::exited_process_is_dead(&LocalPtyBackend::new());
+    }
+
+    #[test]
+    fn kill_stops_process() {
+        conformance::kill_stops_process(&LocalPtyBackend::new());
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn kill_reaps_child() {
+        let backend = LocalPtyBackend::new();
+        // Ignoring SIGHUP forces the SIGKILL path, which does not reap
+        let spawned = conformance::spawn_sh(
+            &backend,
+            "trap '' HUP; echo ready; sleep 30",
+            None,
+            &HashMap::new(),
+        );
+        assert!(conformance::wait_for_output(
+            &spawned.initial_screen,
+            spawned.output,
+            "ready"
+        ));
+        let pid = backend.lock_panes().unwrap()[&spawned.backend_id]
+            .child
+            .process_id()
+            .unwrap();
+        backend.kill(&spawned.backend_id).unwrap();
+        // A zombie keeps its /proc entry until it is waited on
+        assert!(!Path::new(&format!("/proc/{pid}")).exists());
+    }
+
+    #[test]
+    fn discover_is_always_empty() {
+        let backend = LocalPtyBackend::new();
+        let spawned = backend
+            .spawn(
+                "tb-x",
+                "sleep",
+                &["30".to_string()],
+                None,
+                &HashMap::new(),
+                24,
+                80,
+            )
+            .unwrap();
+        assert!(backend.discover().unwrap().is_empty());
+        backend.kill(&spawned.backend_id).unwrap();
+    }
+
+    #[test]
+    fn adopt_fails() {
+        let backend = LocalPtyBackend::new();
+        let err = backend.adopt("pty-1-1", 24, 80).err().unwrap();
+        assert!(err.to_string().contains("cannot be adopted"));
+    }
+
+    #[test]
+    fn backend_ids_are_unique() {
+        let backend = LocalPtyBackend::new();
+        let a = backend.next_backend_id();
+        let b = backend.next_backend_id();
+        assert_ne!(a, b);
+        assert!(a.starts_with(&format!("pty-{}-", std::process::id())));
+    }
+
+    #[test]
+    fn unknown_session_is_dead() {
+        let backend = LocalPtyBackend::new();
+        assert!(backend.is_dead("pty-0-0").unwrap());
+        assert!(backend.kill("pty-0-0").is_ok());
+        assert!(backend.resize("pty-0-0", 24, 80).is_err());
+    }
+
+    #[test]
+    fn detach_stops_process() {
+        let backend = LocalPtyBackend::new();
+        let spawned = backend
+            .spawn(
+                "tb-x",
+                "sleep",
+                &["30".to_string()],
+                None,
+                &HashMap::new(),
+                24,
+                80,
+            )
+            .unwrap();
+        backend.detach(&spawned.backend_id).unwrap();
+        assert!(backend.lock_panes().unwrap().is_empty());
+    }
+}
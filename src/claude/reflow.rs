@@ -0,0 +1,309 @@
+//! Soft-wrap reflow for resized terminal buffers.
+//!
+//! vt100 truncates rows when its screen shrinks. [`reflow`] instead rebuilds
+//! the buffer at the new size: soft-wrapped rows are joined back into logical
+//! lines and replayed into a fresh parser, so long lines rewrap to the new
+//! width. Colors and text attributes are kept; terminal modes are not.
+
+use vt100::Color;
+
+/// Resize `parser` to `rows` x `cols`, rewrapping its scrollback and screen.
+///
+/// The alternate screen belongs to full-screen programs that redraw on
+/// resize, so it is only resized in place. After a reflow the cursor sits at
+/// the end of the line it was on.
+pub fn reflow(parser: &mut vt100::Parser, rows: u16, cols: u16, scrollback: usize) {
+    if parser.screen().alternate_screen() {
+        parser.screen_mut().set_size(rows, cols);
+        return;
+    }
+
+    let mut replay = Vec::new();
+    for (i, line) in logical_lines(parser).iter().enumerate() {
+        if i > 0 {
+            replay.extend_from_slice(b"\r\n");
+        }
+        replay.extend_from_slice(line);
+    }
+
+    let mut reflowed = vt100::Parser::new(rows, cols, scrollback);
+    reflowed.process(&replay);
+    *parser = reflowed;
+}
+
+/// Rebuild `parser` at its current size with room for `scrollback` lines of
+/// history, dropping the oldest lines beyond that. vt100 fixes the
+/// scrollback length when a parser is created, so the buffer is replayed
+/// like [`reflow`]; input modes and the scroll offset are carried over.
+///
+/// Returns `false`, leaving the parser alone, while the alternate screen is
+/// active: its contents cannot be replayed.
+pub fn set_scrollback_len(parser: &mut vt100::Parser, scrollback: usize) -> bool {
+    if parser.screen().alternate_screen() {
+        return false;
+    }
+    let offset = parser.screen().scrollback();
+    let modes = parser.screen().input_mode_formatted();
+    let (rows, cols) = parser.screen().size();
+    reflow(parser, rows, cols, scrollback);
+    parser.process(&modes);
+    parser.screen_mut().set_scrollback(offset);
+    true
+}
+
+/// Every logical line in the buffer, oldest first, as styled bytes. Trailing
+/// blank lines below the cursor are dropped.
+fn logical_lines(parser: &mut vt100::Parser) -> Vec<Vec<u8>> {
+    parser.screen_mut().set_scrollback(0);
+    let (cursor_row, cursor_col) = parser.screen().cursor_position();
+    parser.screen_mut().set_scrollback(usize::MAX);
+    let total = parser.screen().scrollback();
+    let (rows, cols) = parser.screen().size();
+    let rows = usize::from(rows);
+    let cursor_line = total + usize::from(cursor_row);
+
+    // Walk one page at a time; vt100 only exposes rows at the current offset
+    let mut lines = Vec::new();
+    let mut current = Vec::new();
+    let mut last_content = 0;
+    let mut next = 0;
+    while next < total + rows {
+        let top = next.min(total);
+        parser.screen_mut().set_scrollback(total - top);
+        let screen = parser.screen();
+        for row in (next - top)..rows {
+            let row = row as u16;
+            let line = top + usize::from(row);
+            let wrapped = screen.row_wrapped(row);
+            let min_width = if line == cursor_line { cursor_col } else { 0 };
+            let has_content = write_row(&mut current, screen, row, cols, wrapped, min_width);
+            if !wrapped {
+                lines.push(std::mem::take(&mut current));
+            }
+            if has_content || line == cursor_line {
+                last_content = lines.len() + usize::from(wrapped);
+            }
+        }
+        next = top + rows;
+    }
+    if !current.is_empty() {
+        lines.push(current);
+    }
+
+    lines.truncate(last_content);
+    lines
+}
+
+/// Append one row's cells to `out` with SGR codes for their attributes.
+/// Unless the row wraps, trailing blank cells beyond `min_width` are left
+/// out. Returns whether the row had any visible content.
+fn write_row(
+    out: &mut Vec<u8>,
+    screen: &vt100::Screen,
+    row: u16,
+    cols: u16,
+    wrapped: bool,
+    min_width: u16,
+) -> bool {
+    let cell_at = |col| screen.cell(row, col);
+    let end = if wrapped {
+        cols
+    } else {
+        let used = (0..cols)
+            .rev()
+            .find(|&col| cell_at(col).is_some_and(|c| c.has_contents()))
+            .map_or(0, |col| col + 1);
+        used.max(min_width.min(cols))
+    };
+
+    let mut style = Style::default();
+    for col in 0..end {
+        let Some(cell) = cell_at(col) else {
+            continue;
+        };
+        if cell.is_wide_continuation() {
+            continue;
+        }
+        let cell_style = Style::of(cell);
+        if cell_style != style {
+            cell_style.write_sgr(out);
+            style = cell_style;
+        }
+        if cell.has_contents() {
+            out.extend_from_slice(cell.contents().as_bytes());
+        } else {
+            out.push(b' ');
+        }
+    }
+    if style != Style::default() {
+        out.extend_from_slice(b"\x1b[m");
+    }
+    (0..end).any(|col| cell_at(col).is_some_and(|c| c.has_contents()))
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct Style {
+    fg: Color,
+    bg: Color,
+    bold: bool,
+    dim: bool,
+    italic: bool,
+    underline: bool,
+    inverse: bool,
+}
+
+impl Style {
+    fn of(cell: &vt100::Cell) -> Self {
+        Self {
+            fg: cell.fgcolor(),
+            bg: cell.bgcolor(),
+            bold: cell.bold(),
+            dim: cell.dim(),
+            italic: cell.italic(),
+            underline: cell.underline(),
+            inverse: cell.inverse(),
+        }
+    }
+
+    /// Reset, then set every attribute of this style.
+    fn write_sgr(&self, out: &mut Vec<u8>) {
+        let mut params = vec!["0".to_string()];
+        for (on, code) in [
+            (self.bold, "1"),
+            (self.dim, "2"),
+            (self.italic, "3"),
+            (self.underline, "4"),
+            (self.inverse, "7"),
+        ] {
+            if on {
+                params.push(code.to_string());
+            }
+        }
+        params.extend(color_param(self.fg, 30, 38));
+        params.extend(color_param(self.bg, 40, 48));
+        out.extend_from_slice(format!("\x1b[{}m", params.join(";")).as_bytes());
+    }
+}
+
+/// SGR parameter for `color`; `base` is 30 (fg) or 40 (bg), `extended` 38 or 48.
+fn color_param(color: Color, base: u8, extended: u8) -> Option<String> {
+    match color {
+        Color::Default => None,
+        Color::Idx(i) if i < 8 => Some((base + i).to_string()),
+        Color::Idx(i) if i < 16 => Some((base + 60 + i - 8).to_string()),
+        Color::Idx(i) => Some(format!("{extended};5;{i}")),
+        Color::Rgb(r, g, b) => Some(format!("{extended};2;{r};{g};{b}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rows(parser: &vt100::Parser) -> Vec<String> {
+        let cols = parser.screen().size().1;
+        parser
+            .screen()
+            .rows(0, cols)
+            .map(|row| row.trim_end().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn narrowing_wraps_long_line() {
+        let mut parser = vt100::Parser::new(5, 20, 100);
+        parser.process(b"0123456789abcdefghij\r\n$ ");
+
+        reflow(&mut parser, 5, 10, 100);
+
+        assert_eq!(parser.screen().size(), (5, 10));
+        assert_eq!(rows(&parser)[..3], ["0123456789", "abcdefghij", "$"]);
+        assert!(parser.screen().row_wrapped(0));
+        assert_eq!(parser.screen().cursor_position(), (2, 2));
+    }
+
+    #[test]
+    fn widening_joins_wrapped_rows() {
+        let mut parser = vt100::Parser::new(5, 10, 100);
+        parser.process(b"0123456789abcdefghij\r\nnext");
+
+        reflow(&mut parser, 5, 20, 100);
+
+        assert_eq!(rows(&parser)[..2], ["0123456789abcdefghij", "next"]);
+    }
+
+    #[test]
+    fn overflow_moves_into_scrollback() {
+        let mut parser = vt100::Parser::new(3, 10, 100);
+        parser.process(b"aaaaaaaaaabbbbbbbbbb\r\nc");
+
+        reflow(&mut parser, 3, 5, 100);
+
+        assert_eq!(rows(&parser), ["bbbbb", "bbbbb", "c"]);
+        parser.screen_mut().set_scrollback(usize::MAX);
+        assert_eq!(parser.screen().scrollback(), 2);
+    }
+
+    #[test]
+    fn keeps_colors() {
+        let mut parser = vt100::Parser::new(3, 10, 0);
+        parser.process(b"\x1b[1;31mredredred\x1b[m!");
+
+        reflow(&mut parser, 3, 5, 0);
+
+        let cell = parser.screen().cell(1, 0).unwrap();
+        assert_eq!(cell.contents(), "d");
+        assert_eq!(cell.fgcolor(), Color::Idx(1));
+        assert!(cell.bold());
+        assert_eq!(
+            parser.screen().cell(1, 4).unwrap().fgcolor(),
+            Color::Default
+        );
+    }
+
+    fn history_len(parser: &mut vt100::Parser) -> usize {
+        parser.screen_mut().set_scrollback(usize::MAX);
+        let len = parser.screen().scrollback();
+        parser.screen_mut().set_scrollback(0);
+        len
+    }
+
+    #[test]
+    fn scrollback_len_shrinks_history_and_keeps_screen() {
+        let mut parser = vt100::Parser::new(3, 10, 100);
+        for i in 0..10 {
+            parser.process(format!("line {i}\r\n").as_bytes());
+        }
+        parser.process(b"\x1b[?2004h$ ");
+        let screen = rows(&parser);
+
+        assert!(set_scrollback_len(&mut parser, 4));
+
+        assert_eq!(rows(&parser), screen);
+        assert_eq!(history_len(&mut parser), 4);
+        assert!(parser.screen().bracketed_paste());
+
+        // A larger cap keeps new history beyond the old one.
+        assert!(set_scrollback_len(&mut parser, 100));
+        parser.process(b"\r\nmore\r\nmore\r\n");
+        assert_eq!(history_len(&mut parser), 7);
+    }
+
+    #[test]
+    fn scrollback_len_leaves_alternate_screen_alone() {
+        let mut parser = vt100::Parser::new(3, 10, 100);
+        parser.process(b"\x1b[?1049hfull");
+        assert!(!set_scrollback_len(&mut parser, 0));
+        assert_eq!(rows(&parser)[0], "full");
+    }
+
+    #[test]
+    fn alternate_screen_is_resized_in_place() {
+        let mut parser = vt100::Parser::new(3, 10, 0);
+        parser.process(b"\x1b[?1049h0123456789");
+
+        reflow(&mut parser, 3, 5, 0);
+
+        assert_eq!(rows(&parser), ["01234", "", ""]);
+    }
+}
@@ -0,0 +1,172 @@
+//! Window title capture for session output.
+//!
+//! Like bells, vt100 0.16 only reports OSC 0/2 titles through its
+//! `Callbacks` type parameter and keeps no title on the `Screen`, so the
+//! reader loop extracts them itself. Sequences may be split across reads,
+//! so state carries over between chunks.
+
+/// Longest title kept; anything past it is dropped.
+const MAX_TITLE_BYTES: usize = 256;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+enum State {
+    #[default]
+    Ground,
+    /// After ESC.
+    Escape,
+    /// Inside an OSC string, collecting its payload.
+    Osc,
+    /// After ESC inside an OSC string; `\` ends it.
+    OscEscape,
+    /// Inside a DCS, APC, PM or SOS string, which is skipped.
+    Other,
+    /// After ESC inside a skipped string.
+    OtherEscape,
+}
+
+/// Extracts window titles (`ESC ] 0;…` and `ESC ] 2;…`) from a byte stream.
+#[derive(Debug, Default)]
+pub struct TitleDetector {
+    state: State,
+    payload: Vec<u8>,
+}
+
+/// A title change reported by [`TitleDetector::feed`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TitleChange {
+    Set(String),
+    /// An empty title resets it.
+    Cleared,
+}
+
+impl TitleDetector {
+    /// Scan the next chunk of output and return the last title it set.
+    pub fn feed(&mut self, data: &[u8]) -> Option<TitleChange> {
+        let mut change = None;
+        for &byte in data {
+            match (self.state, byte) {
+                (State::OscEscape, b'\\') => {
+                    change = self.finish().or(change);
+                    self.state = State::Ground;
+                }
+                (State::OtherEscape, b'\\') => self.state = State::Ground,
+                // Any other escape aborts the string and starts a new one
+                (State::OscEscape | State::OtherEscape, _) => {
+                    self.state = State::Escape;
+                    self.payload.clear();
+                    self.escape(byte);
+                }
+                (State::Osc, 0x07) => {
+                    change = self.finish().or(change);
+                    self.state = State::Ground;
+                }
+                (State::Osc, 0x1b) => self.state = State::OscEscape,
+                (State::Osc | State::Other, 0x18 | 0x1a) => {
+                    self.payload.clear();
+                    self.state = State::Ground;
+                }
+                (State::Osc, _) => {
+                    if self.payload.len() < MAX_TITLE_BYTES + 2 {
+                        self.payload.push(byte);
+                    }
+                }
+                (State::Other, 0x07) => self.state = State::Ground,
+                (State::Other, 0x1b) => self.state = State::OtherEscape,
+                (State::Other, _) => {}
+                (State::Escape, _) => self.escape(byte),
+                (State::Ground, 0x1b) => self.state = State::Escape,
+                (State::Ground, _) => {}
+            }
+        }
+        change
+    }
+
+    /// Handle the byte after ESC outside a string.
+    fn escape(&mut self, byte: u8) {
+        self.state = match byte {
+            b']' => State::Osc,
+            b'P' | b'_' | b'^' | b'X' => State::Other,
+            0x1b => State::Escape,
+            _ => State::Ground,
+        };
+    }
+
+    /// Interpret a completed OSC payload; only 0 (icon and title) and 2
+    /// (title) change the title.
+    fn finish(&mut self) -> Option<TitleChange> {
+        let payload = std::mem::take(&mut self.payload);
+        let title = payload
+            .strip_prefix(b"0;")
+            .or_else(|| payload.strip_prefix(b"2;"))?;
+        let title = String::from_utf8_lossy(&title[..title.len().min(MAX_TITLE_BYTES)]);
+        let title = title.trim();
+        Some(if title.is_empty() {
+            TitleChange::Cleared
+        } else {
+            TitleChange::Set(title.to_string())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set(title: &str) -> Option<TitleChange> {
+        Some(TitleChange::Set(title.to_string()))
+    }
+
+    #[test]
+    fn extracts_osc_0_and_2_titles() {
+        let mut detector = TitleDetector::default();
+        assert_eq!(
+            detector.feed(b"\x1b]0;Running tests\x07"),
+            set("Running tests")
+        );
+        assert_eq!(detector.feed(b"out\x1b]2;Editing\x1b\\put"), set("Editing"));
+        assert_eq!(detector.feed(b"plain output"), None);
+    }
+
+    #[test]
+    fn last_title_in_a_chunk_wins() {
+        let mut detector = TitleDetector::default();
+        assert_eq!(
+            detector.feed(b"\x1b]2;first\x07\x1b]2;second\x07"),
+            set("second")
+        );
+    }
+
+    #[test]
+    fn sequences_split_across_reads() {
+        let mut detector = TitleDetector::default();
+        assert_eq!(detector.feed(b"\x1b"), None);
+        assert_eq!(detector.feed(b"]2;Thin"), None);
+        assert_eq!(detector.feed(b"king\x1b"), None);
+        assert_eq!(detector.feed(b"\\"), set("Thinking"));
+    }
+
+    #[test]
+    fn other_sequences_do_not_set_a_title() {
+        let mut detector = TitleDetector::default();
+        assert_eq!(detector.feed(b"\x1b]1;icon\x07"), None);
+        assert_eq!(detector.feed(b"\x1b]8;;https://example.com\x1b\\"), None);
+        assert_eq!(detector.feed(b"\x1bP0;not a title\x1b\\"), None);
+        assert_eq!(detector.feed(b"\x1b[31m0;red\x07"), None);
+    }
+
+    #[test]
+    fn empty_title_clears() {
+        let mut detector = TitleDetector::default();
+        assert_eq!(detector.feed(b"\x1b]0;\x07"), Some(TitleChange::Cleared));
+    }
+
+    #[test]
+    fn parser_output_is_unaffected_by_titles() {
+        let mut parser = vt100::Parser::new(5, 40, 0);
+        let mut detector = TitleDetector::default();
+        let bytes: &[u8] = b"\x1b]0;Claude: fixing auth\x07hello";
+        parser.process(bytes);
+        assert_eq!(detector.feed(bytes), set("Claude: fixing auth"));
+        assert_eq!(parser.screen().contents(), "hello");
+    }
+}
@@ -831,6 +831,13 @@ impl SessionBackend for LocalTmuxBackend {
         let _ = self.unregister_pane(backend_id);
         Ok(())
     }
+    fn rename(&self, backend_id: &str, window_name: &str) -> Result<()> {
+        self.ctrl_command(&format!(
+            "rename-window -t {backend_id} {}",
+            shell_escape(window_name)
+        ))?;
+        Ok(())
+    }
 }
 
 /// Decode tmux control mode octal escapes in `%output` data.
@@ -946,6 +953,49 @@ fn shell_escape(s: &str) -> String {
 mod tests {
     use super::*;
 
+    // --- SessionBackend conformance (needs a tmux server) ---
+
+    mod conformance {
+        use super::LocalTmuxBackend;
+        use crate::claude::backend::conformance;
+
+        #[test]
+        #[ignore = "starts windows in the thurbox tmux server"]
+        fn spawn_streams_output() {
+            conformance::spawn_streams_output(&LocalTmuxBackend::new());
+        }
+
+        #[test]
+        #[ignore = "starts windows in the thurbox tmux server"]
+        fn input_reaches_process() {
+            conformance::input_reaches_process(&LocalTmuxBackend::new());
+        }
+
+        #[test]
+        #[ignore = "starts windows in the thurbox tmux server"]
+        fn spawn_applies_cwd_and_env() {
+            conformance::spawn_applies_cwd_and_env(&LocalTmuxBackend::new());
+        }
+
+        #[test]
+        #[ignore = "starts windows in the thurbox tmux server"]
+        fn resize_live_session() {
+            conformance::resize_live_session(&LocalTmuxBackend::new());
+        }
+
+        #[test]
+        #[ignore = "starts windows in the thurbox tmux server"]
+        fn exited_process_is_dead() {
+            conformance::exited_process_is_dead(&LocalTmuxBackend::new());
+        }
+
+        #[test]
+        #[ignore = "starts windows in the thurbox tmux server"]
+        fn kill_stops_process() {
+            conformance::kill_stops_process(&LocalTmuxBackend::new());
+        }
+    }
+
     // --- shell_escape tests ---
 
     #[test]
@@ -0,0 +1,319 @@
+//! Headless subcommands for scripting (`thurbox ls`, `thurbox kill <id>`).
+//!
+//! These work directly on the SQLite database and never start the TUI or a
+//! session backend. A running TUI picks up changes (e.g. a killed session)
+//! through its usual database sync.
+
+use std::io::Write;
+
+use anyhow::{bail, Context, Result};
+
+use crate::session::SessionId;
+use crate::storage::Database;
+use crate::sync::{SharedProject, SharedSession};
+
+const USAGE: &str = "usage: thurbox [ls [--json] | kill <session-id>]";
+
+/// A headless subcommand parsed from argv.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Command {
+    /// List active projects and their sessions.
+    Ls { json: bool },
+    /// Soft-delete a session by UUID.
+    Kill { session: String },
+}
+
+impl Command {
+    /// Parse the arguments after the program name. `Ok(None)` means no
+    /// subcommand was given and the TUI should start.
+    pub fn parse(args: &[String]) -> Result<Option<Self>> {
+        let args: Vec<&str> = args.iter().map(String::as_str).collect();
+        let command = match args.as_slice() {
+            [] => return Ok(None),
+            ["ls"] => Self::Ls { json: false },
+            ["ls", "--json"] => Self::Ls { json: true },
+            ["kill", session] => Self::Kill {
+                session: (*session).to_string(),
+            },
+            _ => bail!("{USAGE}"),
+        };
+        Ok(Some(command))
+    }
+}
+
+/// Run `command` against `db`, writing its output to `out`.
+pub fn run(command: &Command, db: &Database, out: &mut impl Write) -> Result<()> {
+    match command {
+        Command::Ls { json } => {
+            let projects = db.list_active_projects()?;
+            let sessions = db.list_active_sessions()?;
+            let text = if *json {
+                format_json(&projects, &sessions)
+            } else {
+                format_table(&projects, &sessions)
+            };
+            writeln!(out, "{text}")?;
+        }
+        Command::Kill { session } => {
+            let id: SessionId = session
+                .parse()
+                .with_context(|| format!("Invalid session UUID: {session}"))?;
+            let Some(found) = db.get_session_by_id(id)? else {
+                bail!("Session not found: {session}");
+            };
+            db.soft_delete_session(id)?;
+            writeln!(out, "Deleted session '{}' ({id})", found.name)?;
+        }
+    }
+    Ok(())
+}
+
+/// Branches of the session's worktrees, comma-separated.
+fn branches(session: &SharedSession) -> String {
+    session
+        .worktrees
+        .iter()
+        .map(|w| w.branch.as_str())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Projects with their sessions nested, as pretty-printed JSON.
+fn format_json(projects: &[SharedProject], sessions: &[SharedSession]) -> String {
+    let value: Vec<_> = projects
+        .iter()
+        .map(|p| {
+            let project_sessions: Vec<_> = sessions
+                .iter()
+                .filter(|s| s.project_id == p.id)
+                .map(|s| {
+                    serde_json::json!({
+                        "id": s.id.to_string(),
+                        "name": s.name,
+                        "role": s.role,
+                        "cwd": s.cwd,
+                        "branches": s.worktrees.iter().map(|w| &w.branch).collect::<Vec<_>>(),
+                    })
+                })
+                .collect();
+            serde_json::json!({
+                "id": p.id.to_string(),
+                "name": p.name,
+                "sessions": project_sessions,
+            })
+        })
+        .collect();
+    serde_json::to_string_pretty(&value).unwrap_or_default()
+}
+
+/// One row per session (projects without sessions get a `-` row), with
+/// columns padded to their widest cell.
+fn format_table(projects: &[SharedProject], sessions: &[SharedSession]) -> String {
+    let mut rows = vec![["PROJECT", "SESSION", "ROLE", "BRANCH", "ID"].map(String::from)];
+    for project in projects {
+        let mut project_sessions = sessions
+            .iter()
+            .filter(|s| s.project_id == project.id)
+            .peekable();
+        if project_sessions.peek().is_none() {
+            rows.push([project.name.as_str(), "-", "-", "-", "-"].map(String::from));
+        }
+        for s in project_sessions {
+            let branch = branches(s);
+            rows.push([
+                project.name.clone(),
+                s.name.clone(),
+                s.role.clone(),
+                if branch.is_empty() {
+                    "-".to_string()
+                } else {
+                    branch
+                },
+                s.id.to_string(),
+            ]);
+        }
+    }
+
+    let mut widths = [0; 5];
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+    rows.iter()
+        .map(|row| {
+            row.iter()
+                .zip(widths)
+                .map(|(cell, width)| format!("{cell:<width$}"))
+                .collect::<Vec<_>>()
+                .join("  ")
+                .trim_end()
+                .to_string()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::project::ProjectId;
+    use crate::sync::SharedWorktree;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    fn session(name: &str, project_id: ProjectId, branch: Option<&str>) -> SharedSession {
+        SharedSession {
+            id: SessionId::default(),
+            name: name.to_string(),
+            project_id,
+            role: "developer".to_string(),
+            backend_id: String::new(),
+            backend_type: "tmux".to_string(),
+            claude_session_id: None,
+            cwd: None,
+            additional_dirs: Vec::new(),
+            worktrees: branch
+                .map(|b| SharedWorktree {
+                    repo_path: "/repo".into(),
+                    worktree_path: format!("/repo/.git/thurbox-worktrees/{b}").into(),
+                    branch: b.to_string(),
+                    base_branch: "main".to_string(),
+                })
+                .into_iter()
+                .collect(),
+            shell_backend_id: None,
+            is_pinned: false,
+            label: None,
+            label_color: None,
+            created_at: None,
+            tombstone: false,
+            tombstone_at: None,
+        }
+    }
+
+    /// A database with project "api" (two sessions) and an empty "web".
+    fn seeded_db() -> (Database, Vec<SharedSession>) {
+        let db = Database::open_in_memory().unwrap();
+        let api = ProjectId::default();
+        db.insert_project(api, "api", &[]).unwrap();
+        db.insert_project(ProjectId::default(), "web", &[]).unwrap();
+
+        let sessions = vec![
+            session("1", api, None),
+            session("feature", api, Some("feat/login")),
+        ];
+        for s in &sessions {
+            db.upsert_session(s).unwrap();
+        }
+        db.set_session_order(&[sessions[0].id, sessions[1].id])
+            .unwrap();
+        (db, sessions)
+    }
+
+    fn run_to_string(command: &Command, db: &Database) -> String {
+        let mut out = Vec::new();
+        run(command, db, &mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn parse_dispatches_subcommands() {
+        assert_eq!(Command::parse(&[]).unwrap(), None);
+        assert_eq!(
+            Command::parse(&args(&["ls"])).unwrap(),
+            Some(Command::Ls { json: false })
+        );
+        assert_eq!(
+            Command::parse(&args(&["ls", "--json"])).unwrap(),
+            Some(Command::Ls { json: true })
+        );
+        assert_eq!(
+            Command::parse(&args(&["kill", "abc"])).unwrap(),
+            Some(Command::Kill {
+                session: "abc".to_string()
+            })
+        );
+        assert!(Command::parse(&args(&["kill"])).is_err());
+        assert!(Command::parse(&args(&["frobnicate"])).is_err());
+    }
+
+    #[test]
+    fn ls_prints_aligned_table() {
+        let (db, sessions) = seeded_db();
+        let output = run_to_string(&Command::Ls { json: false }, &db);
+        let lines: Vec<&str> = output.lines().collect();
+
+        assert_eq!(lines.len(), 4);
+        assert_eq!(lines[0], "PROJECT  SESSION  ROLE       BRANCH      ID");
+        assert!(lines.contains(
+            &format!(
+                "api      1        developer  -           {}",
+                sessions[0].id
+            )
+            .as_str()
+        ));
+        assert!(lines.contains(
+            &format!(
+                "api      feature  developer  feat/login  {}",
+                sessions[1].id
+            )
+            .as_str()
+        ));
+        assert!(lines.contains(&"web      -        -          -           -"));
+    }
+
+    #[test]
+    fn ls_json_nests_sessions_under_projects() {
+        let (db, sessions) = seeded_db();
+        let output = run_to_string(&Command::Ls { json: true }, &db);
+        let value: serde_json::Value = serde_json::from_str(&output).unwrap();
+
+        let projects = value.as_array().unwrap();
+        assert_eq!(projects.len(), 2);
+        let api = projects.iter().find(|p| p["name"] == "api").unwrap();
+        let api_sessions = api["sessions"].as_array().unwrap();
+        assert_eq!(api_sessions.len(), 2);
+        let feature = api_sessions
+            .iter()
+            .find(|s| s["id"] == sessions[1].id.to_string())
+            .unwrap();
+        assert_eq!(feature["branches"], serde_json::json!(["feat/login"]));
+        let web = projects.iter().find(|p| p["name"] == "web").unwrap();
+        assert_eq!(web["sessions"], serde_json::json!([]));
+    }
+
+    #[test]
+    fn kill_soft_deletes_session() {
+        let (db, sessions) = seeded_db();
+        let id = sessions[0].id;
+
+        let output = run_to_string(
+            &Command::Kill {
+                session: id.to_string(),
+            },
+            &db,
+        );
+        assert_eq!(output, format!("Deleted session '1' ({id})\n"));
+        assert!(db.get_session_by_id(id).unwrap().is_none());
+        assert_eq!(db.list_active_sessions().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn kill_rejects_unknown_session() {
+        let (db, _) = seeded_db();
+        let mut out = Vec::new();
+        let missing = SessionId::default().to_string();
+        assert!(run(&Command::Kill { session: missing }, &db, &mut out).is_err());
+        assert!(run(
+            &Command::Kill {
+                session: "nope".to_string()
+            },
+            &db,
+            &mut out
+        )
+        .is_err());
+    }
+}
@@ -1,10 +1,13 @@
-use std::path::{Path, PathBuf};
+use std::path::{Component, Path, PathBuf};
 use std::process::{Command, Stdio};
 use std::time::Duration;
 
 use anyhow::{Context, Result};
 use tracing::warn;
 
+use crate::project::SyncStrategy;
+use crate::session::WorktreeStatus;
+
 /// List local branch names for a repo.
 pub fn list_branches(repo_path: &Path) -> Result<Vec<String>> {
     let output = Command::new("git")
@@ -29,10 +32,15 @@ pub fn list_branches(repo_path: &Path) -> Result<Vec<String>> {
 
 /// Create a git worktree on a new branch and return the worktree directory path.
 ///
-/// Creates `new_branch` starting from `base_branch`.
-/// Path format: `<repo>/.git/thurbox-worktrees/<sanitized-new-branch>`
-pub fn create_worktree(repo_path: &Path, new_branch: &str, base_branch: &str) -> Result<PathBuf> {
-    let wt_path = worktree_path(repo_path, new_branch);
+/// Creates `new_branch` starting from `base_branch`. The directory is placed
+/// by [`worktree_path`] under `worktree_root`, or inside `.git` when `None`.
+pub fn create_worktree(
+    repo_path: &Path,
+    new_branch: &str,
+    base_branch: &str,
+    worktree_root: Option<&Path>,
+) -> Result<PathBuf> {
+    let wt_path = worktree_path(repo_path, new_branch, worktree_root);
 
     let output = Command::new("git")
         .args([
@@ -76,12 +84,106 @@ pub fn remove_worktree(repo_path: &Path, worktree_path: &Path) -> Result<()> {
     Ok(())
 }
 
+/// Check whether a worktree has uncommitted changes, untracked files
+/// included, that a forced `git worktree remove` would discard.
+///
+/// A worktree directory that no longer exists has nothing to lose and is
+/// reported clean.
+pub fn worktree_is_dirty(worktree_path: &Path) -> Result<bool> {
+    if !worktree_path.exists() {
+        return Ok(false);
+    }
+
+    Ok(porcelain_is_dirty(&git_status_porcelain(worktree_path)?))
+}
+
+/// Whether `git status --porcelain` output lists any changed or untracked
+/// path. Ignored entries (`!!`) do not count.
+fn porcelain_is_dirty(output: &str) -> bool {
+    output
+        .lines()
+        .any(|line| !line.trim().is_empty() && !line.starts_with("!!"))
+}
+
+/// Check whether a worktree's branch has commits that were never pushed:
+/// those ahead of its upstream (`@{u}..HEAD`), or ahead of `base` when the
+/// branch has no upstream yet.
+///
+/// A worktree directory that no longer exists reports none.
+pub fn branch_has_unpushed_commits(worktree_path: &Path, base: &str) -> Result<bool> {
+    if !worktree_path.exists() {
+        return Ok(false);
+    }
+
+    let output = match git_rev_list(worktree_path, "@{u}..HEAD") {
+        Ok(output) => output,
+        // No upstream configured: compare against the base branch instead.
+        Err(_) => git_rev_list(worktree_path, &format!("{base}..HEAD"))?,
+    };
+    Ok(rev_list_has_commits(&output))
+}
+
+/// Whether `git rev-list` output lists at least one commit.
+fn rev_list_has_commits(output: &str) -> bool {
+    output.lines().any(|line| !line.trim().is_empty())
+}
+
+/// Read a worktree's branch, upstream ahead/behind counts and number of
+/// dirty paths from `git status -b --porcelain=v2`.
+pub fn worktree_status(worktree_path: &Path) -> Result<WorktreeStatus> {
+    let output = Command::new("git")
+        .args(["status", "-b", "--porcelain=v2"])
+        .current_dir(worktree_path)
+        .output()
+        .context("failed to run git status")?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        anyhow::bail!("git status failed: {stderr}");
+    }
+
+    Ok(parse_porcelain_v2(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// Parse `git status -b --porcelain=v2` output.
+///
+/// Header lines (`# branch.*`) carry the branch and ahead/behind counts;
+/// every ordinary (`1`), renamed (`2`), unmerged (`u`) or untracked (`?`)
+/// entry counts as one dirty path. Ignored entries (`!`) do not.
+fn parse_porcelain_v2(output: &str) -> WorktreeStatus {
+    let mut status = WorktreeStatus::default();
+    for line in output.lines() {
+        if let Some(header) = line.strip_prefix("# ") {
+            let (key, value) = header.split_once(' ').unwrap_or((header, ""));
+            match key {
+                "branch.head" if value != "(detached)" => {
+                    status.branch = Some(value.to_string());
+                }
+                "branch.ab" => status.ahead_behind = parse_ahead_behind(value),
+                _ => {}
+            }
+        } else if matches!(line.split(' ').next(), Some("1" | "2" | "u" | "?")) {
+            status.dirty_files += 1;
+        }
+    }
+    status
+}
+
+/// Parse the `+<ahead> -<behind>` value of a `# branch.ab` header.
+fn parse_ahead_behind(value: &str) -> Option<(u32, u32)> {
+    let (ahead, behind) = value.split_once(' ')?;
+    Some((
+        ahead.strip_prefix('+')?.parse().ok()?,
+        behind.strip_prefix('-')?.parse().ok()?,
+    ))
+}
+
 /// Detect the repository's default branch name.
 ///
-/// Tries `git symbolic-ref refs/remotes/origin/HEAD` first (most reliable),
+/// Tries `git symbolic-ref refs/remotes/<remote>/HEAD` first (most reliable),
 /// then falls back to checking for `main` or `master` among local branches.
-pub fn default_branch(repo_path: &Path, local_branches: &[String]) -> Option<String> {
-    if let Some(name) = default_branch_from_remote(repo_path) {
+pub fn default_branch(repo_path: &Path, remote: &str, local_branches: &[String]) -> Option<String> {
+    if let Some(name) = default_branch_from_remote(repo_path, remote) {
         if local_branches.iter().any(|b| b == &name) {
             return Some(name);
         }
@@ -97,10 +199,22 @@ pub fn default_branch(repo_path: &Path, local_branches: &[String]) -> Option<Str
     None
 }
 
+/// Resolve the branch a worktree sync rebases onto.
+///
+/// A non-empty `configured` branch (the project's `sync_base_branch`) wins;
+/// otherwise the repo's default branch is detected, falling back to `main`.
+pub fn sync_base_branch(repo_path: &Path, remote: &str, configured: Option<&str>) -> String {
+    if let Some(branch) = configured.map(str::trim).filter(|b| !b.is_empty()) {
+        return branch.to_string();
+    }
+    let local_branches = list_branches(repo_path).unwrap_or_default();
+    default_branch(repo_path, remote, &local_branches).unwrap_or_else(|| "main".to_string())
+}
+
 /// Query the remote's default branch via `git symbolic-ref`.
-fn default_branch_from_remote(repo_path: &Path) -> Option<String> {
+fn default_branch_from_remote(repo_path: &Path, remote: &str) -> Option<String> {
     let output = Command::new("git")
-        .args(["symbolic-ref", "refs/remotes/origin/HEAD", "--short"])
+        .args(remote_head_args(remote))
         .current_dir(repo_path)
         .stderr(Stdio::null())
         .output()
@@ -110,16 +224,32 @@ fn default_branch_from_remote(repo_path: &Path) -> Option<String> {
         return None;
     }
 
-    let full_ref = String::from_utf8_lossy(&output.stdout).trim().to_string();
-    full_ref.strip_prefix("origin/").map(|s| s.to_string())
+    let full_ref = String::from_utf8_lossy(&output.stdout);
+    full_ref
+        .trim()
+        .strip_prefix(&format!("{remote}/"))
+        .map(|s| s.to_string())
+}
+
+/// `git symbolic-ref` arguments resolving `<remote>/HEAD`.
+fn remote_head_args(remote: &str) -> Vec<String> {
+    vec![
+        "symbolic-ref".to_string(),
+        format!("refs/remotes/{remote}/HEAD"),
+        "--short".to_string(),
+    ]
 }
 
 /// Add an existing branch as a worktree (no `-b` flag — branch must already exist).
 ///
 /// Returns the worktree directory path. If the worktree path already exists on
 /// disk the function returns early with `Ok(path)`.
-pub fn add_existing_worktree(repo_path: &Path, branch: &str) -> Result<PathBuf> {
-    let wt_path = worktree_path(repo_path, branch);
+pub fn add_existing_worktree(
+    repo_path: &Path,
+    branch: &str,
+    worktree_root: Option<&Path>,
+) -> Result<PathBuf> {
+    let wt_path = worktree_path(repo_path, branch, worktree_root);
 
     if wt_path.exists() {
         return Ok(wt_path);
@@ -139,6 +269,105 @@ pub fn add_existing_worktree(repo_path: &Path, branch: &str) -> Result<PathBuf>
     Ok(wt_path)
 }
 
+/// Whether `err` is git refusing to check out a branch that another worktree
+/// already has checked out ("already checked out at" before git 2.42,
+/// "already used by worktree at" since).
+pub fn is_checked_out_elsewhere(err: &anyhow::Error) -> bool {
+    let message = format!("{err:#}");
+    message.contains("already checked out") || message.contains("already used by worktree")
+}
+
+/// The worktree that has `branch` checked out, from `git worktree list
+/// --porcelain`. This may be the repository's main checkout.
+pub fn existing_worktree_for_branch(repo_path: &Path, branch: &str) -> Option<PathBuf> {
+    let output = Command::new("git")
+        .args(["worktree", "list", "--porcelain"])
+        .current_dir(repo_path)
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    parse_worktree_list(&String::from_utf8_lossy(&output.stdout), branch)
+}
+
+/// Find the `worktree <path>` record whose `branch` line is `branch`.
+/// Records are separated by blank lines; detached and bare worktrees have no
+/// `branch` line.
+fn parse_worktree_list(output: &str, branch: &str) -> Option<PathBuf> {
+    let target = format!("refs/heads/{branch}");
+    let mut path = None;
+    for line in output.lines() {
+        if let Some(p) = line.strip_prefix("worktree ") {
+            path = Some(PathBuf::from(p));
+        } else if line.is_empty() {
+            path = None;
+        } else if line.strip_prefix("branch ") == Some(target.as_str()) {
+            if let Some(found) = path.take() {
+                return Some(found);
+            }
+        }
+    }
+    None
+}
+
+/// Whether `path` is a directory inside a git work tree.
+pub fn is_git_repo(path: &Path) -> bool {
+    path.is_dir()
+        && Command::new("git")
+            .args(["rev-parse", "--is-inside-work-tree"])
+            .current_dir(path)
+            .stderr(Stdio::null())
+            .output()
+            .is_ok_and(|o| {
+                o.status.success() && String::from_utf8_lossy(&o.stdout).trim() == "true"
+            })
+}
+
+/// Whether a project repo path still has to be cloned: it is missing or an
+/// empty directory. A non-empty directory outside git is taken to be a
+/// deliberately non-git project and left alone.
+pub fn needs_clone(path: &Path) -> bool {
+    if is_git_repo(path) {
+        return false;
+    }
+    match std::fs::read_dir(path) {
+        Ok(mut entries) => entries.next().is_none(),
+        Err(e) => e.kind() == std::io::ErrorKind::NotFound,
+    }
+}
+
+/// Arguments for `git clone`; `--` keeps a URL starting with `-` from
+/// being read as an option.
+fn clone_args(url: &str, dest: &Path) -> Vec<String> {
+    vec![
+        "clone".to_string(),
+        "--".to_string(),
+        url.to_string(),
+        dest.display().to_string(),
+    ]
+}
+
+/// Clone `url` into `dest`, creating its parent directories.
+pub fn clone(url: &str, dest: &Path) -> Result<()> {
+    if let Some(parent) = dest.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("failed to create {}", parent.display()))?;
+    }
+    let output = Command::new("git")
+        .args(clone_args(url, dest))
+        .stdin(Stdio::null())
+        .output()
+        .context("failed to run git clone")?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        anyhow::bail!("git clone failed: {}", stderr.trim());
+    }
+    Ok(())
+}
+
 /// Check whether a local branch exists in the repository.
 pub fn branch_exists(repo_path: &Path, branch: &str) -> bool {
     Command::new("git")
@@ -151,22 +380,100 @@ pub fn branch_exists(repo_path: &Path, branch: &str) -> bool {
         .unwrap_or(false)
 }
 
+/// Check a new branch name against git's ref naming rules, so bad input is
+/// rejected with a reason instead of an opaque `git worktree add` failure.
+///
+/// A subset of `git check-ref-format`: no whitespace or control characters,
+/// none of `~^:?*[\`, no `..`, `@{` or `//`, no leading `-`, and no leading,
+/// trailing or dot-prefixed path components.
+pub fn validate_branch_name(name: &str) -> Result<(), String> {
+    if name.is_empty() {
+        return Err("Branch name cannot be empty".to_string());
+    }
+    if name.chars().any(char::is_whitespace) {
+        return Err("Branch name cannot contain spaces".to_string());
+    }
+    if name.chars().any(char::is_control) {
+        return Err("Branch name cannot contain control characters".to_string());
+    }
+    if let Some(c) = name.chars().find(|c| "~^:?*[\\".contains(*c)) {
+        return Err(format!("Branch name cannot contain '{c}'"));
+    }
+    if name.contains("..") {
+        return Err("Branch name cannot contain '..'".to_string());
+    }
+    if name.contains("@{") || name == "@" {
+        return Err("Branch name cannot contain '@{' or be '@'".to_string());
+    }
+    if name.starts_with('-') {
+        return Err("Branch name cannot start with '-'".to_string());
+    }
+    if name.starts_with('/') || name.ends_with('/') || name.contains("//") {
+        return Err("Branch name has an empty path component".to_string());
+    }
+    if name.ends_with('.') || name.ends_with(".lock") {
+        return Err("Branch name cannot end with '.' or '.lock'".to_string());
+    }
+    if name.split('/').any(|part| part.starts_with('.')) {
+        return Err("Branch name components cannot start with '.'".to_string());
+    }
+    Ok(())
+}
+
 /// Deterministic worktree directory path for a repo + branch.
-fn worktree_path(repo_path: &Path, branch: &str) -> PathBuf {
+///
+/// Without a root: `<repo>/.git/thurbox-worktrees/<sanitized-branch>`.
+/// With one: `<root>/<repo-name>/<sanitized-branch>`, where a relative root
+/// is resolved against the repo (so `../thurbox-worktrees` is a sibling).
+fn worktree_path(repo_path: &Path, branch: &str, worktree_root: Option<&Path>) -> PathBuf {
     let sanitized = branch.replace('/', "-");
-    repo_path
-        .join(".git")
-        .join("thurbox-worktrees")
+    let Some(root) = worktree_root else {
+        return repo_path
+            .join(".git")
+            .join("thurbox-worktrees")
+            .join(sanitized);
+    };
+
+    let repo_name = repo_path
+        .file_name()
+        .map(|n| n.to_os_string())
+        .unwrap_or_else(|| "repo".into());
+    normalize_lexically(&repo_path.join(root))
+        .join(repo_name)
         .join(sanitized)
 }
 
-/// Result of attempting to sync a worktree with origin/main.
+/// Collapse `.` and `..` components without touching the filesystem, so a
+/// relative worktree root doesn't leave `repo/../` in stored paths.
+fn normalize_lexically(path: &Path) -> PathBuf {
+    let mut out = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if !out.pop() {
+                    out.push(component);
+                }
+            }
+            other => out.push(other),
+        }
+    }
+    out
+}
+
+/// Result of attempting to sync a worktree with its base branch.
 #[derive(Debug)]
 pub enum SyncResult {
-    /// Rebase succeeded (includes already-up-to-date).
+    /// Sync succeeded (includes already-up-to-date).
     Synced,
-    /// Rebase failed due to conflicts (aborted, stash restored).
-    Conflict(String),
+    /// Integrating `<remote>/<base_branch>` failed due to conflicts, or a
+    /// fast-forward was not possible (aborted, stash restored).
+    Conflict {
+        remote: String,
+        base_branch: String,
+        strategy: SyncStrategy,
+        message: String,
+    },
     /// Unexpected failure.
     Error(String),
 }
@@ -189,10 +496,10 @@ fn git_stash(worktree_path: &Path) -> Result<bool> {
     Ok(!stdout.contains("No local changes to save"))
 }
 
-/// Fetch from origin.
-fn git_fetch(worktree_path: &Path) -> Result<()> {
+/// Fetch from `remote`.
+fn git_fetch(worktree_path: &Path, remote: &str) -> Result<()> {
     let output = Command::new("git")
-        .args(["fetch", "origin"])
+        .args(fetch_args(remote))
         .current_dir(worktree_path)
         .output()
         .context("failed to run git fetch")?;
@@ -205,24 +512,66 @@ fn git_fetch(worktree_path: &Path) -> Result<()> {
     Ok(())
 }
 
-/// Rebase current branch onto origin/main. Returns `Ok(())` on success,
-/// or an error if there are conflicts (rebase is aborted before returning).
-fn git_rebase_main(worktree_path: &Path) -> Result<()> {
+fn fetch_args(remote: &str) -> [&str; 2] {
+    ["fetch", remote]
+}
+
+/// `git` arguments integrating `<remote>/<base_branch>` with `strategy`, and
+/// the command that aborts a failed attempt.
+fn integrate_args(
+    remote: &str,
+    base_branch: &str,
+    strategy: SyncStrategy,
+) -> (Vec<String>, Option<[&'static str; 2]>) {
+    let upstream = format!("{remote}/{base_branch}");
+    let (args, abort) = match strategy {
+        SyncStrategy::Rebase => (vec!["rebase"], Some(["rebase", "--abort"])),
+        SyncStrategy::Merge => (vec!["merge", "--no-edit"], Some(["merge", "--abort"])),
+        // A refused fast-forward leaves nothing to abort.
+        SyncStrategy::FastForwardOnly => (vec!["merge", "--ff-only"], None),
+    };
+    let args = args
+        .into_iter()
+        .map(String::from)
+        .chain([upstream])
+        .collect();
+    (args, abort)
+}
+
+/// Bring the current branch up to date with `<remote>/<base_branch>` using
+/// `strategy`. Returns `Ok(())` on success, or an error if the branch could
+/// not be integrated cleanly (any in-progress rebase/merge is aborted before
+/// returning).
+fn git_integrate(
+    worktree_path: &Path,
+    remote: &str,
+    base_branch: &str,
+    strategy: SyncStrategy,
+) -> Result<()> {
+    let (args, abort) = integrate_args(remote, base_branch, strategy);
+
     let output = Command::new("git")
-        .args(["rebase", "origin/main"])
+        .args(&args)
         .current_dir(worktree_path)
         .output()
-        .context("failed to run git rebase")?;
+        .with_context(|| format!("failed to run git {}", args[0]))?;
 
     if !output.status.success() {
-        // Abort the failed rebase
-        let _ = Command::new("git")
-            .args(["rebase", "--abort"])
-            .current_dir(worktree_path)
-            .output();
+        if let Some(abort) = abort {
+            let _ = Command::new("git")
+                .args(abort)
+                .current_dir(worktree_path)
+                .output();
+        }
 
         let stderr = String::from_utf8_lossy(&output.stderr);
-        anyhow::bail!("rebase conflict: {stderr}");
+        match strategy {
+            SyncStrategy::Rebase => anyhow::bail!("rebase conflict: {stderr}"),
+            SyncStrategy::Merge => anyhow::bail!("merge conflict: {stderr}"),
+            SyncStrategy::FastForwardOnly => {
+                anyhow::bail!("cannot fast-forward to {remote}/{base_branch}: {stderr}")
+            }
+        }
     }
 
     Ok(())
@@ -391,13 +740,31 @@ fn stash_with_retry(worktree_path: &Path) -> Result<bool> {
     anyhow::bail!("transient error persisted after retries: {last_err}")
 }
 
-/// High-level sync: stash, fetch, rebase origin/main, pop stash.
+/// High-level sync: fetch `remote`, stash, integrate
+/// `<remote>/<base_branch>` with `strategy`, pop stash.
 ///
-/// On conflict the rebase is aborted and any stash is restored.
+/// A worktree with no local changes that is not behind the base after the
+/// fetch is already synced and skips the stash entirely. Otherwise the
+/// stash/integrate/pop wrapper is identical for every strategy. On conflict
+/// (or a refused fast-forward) the operation is aborted and any stash is
+/// restored.
 /// Retries `git stash` on transient index-lock errors.
-pub fn sync_worktree(worktree_path: &Path) -> SyncResult {
+pub fn sync_worktree(
+    worktree_path: &Path,
+    remote: &str,
+    base_branch: &str,
+    strategy: SyncStrategy,
+) -> SyncResult {
     cleanup_stale_index_lock(worktree_path);
 
+    if let Err(e) = git_fetch(worktree_path, remote) {
+        return SyncResult::Error(format!("fetch: {e:#}"));
+    }
+
+    if already_synced(worktree_path, remote, base_branch) {
+        return SyncResult::Synced;
+    }
+
     let stashed = match stash_with_retry(worktree_path) {
         Ok(s) => s,
         Err(e) => return SyncResult::Error(format!("stash: {e:#}")),
@@ -409,14 +776,14 @@ pub fn sync_worktree(worktree_path: &Path) -> SyncResult {
         }
     };
 
-    if let Err(e) = git_fetch(worktree_path) {
-        restore_stash();
-        return SyncResult::Error(format!("fetch: {e:#}"));
-    }
-
-    if let Err(e) = git_rebase_main(worktree_path) {
+    if let Err(e) = git_integrate(worktree_path, remote, base_branch, strategy) {
         restore_stash();
-        return SyncResult::Conflict(format!("{e:#}"));
+        return SyncResult::Conflict {
+            remote: remote.to_string(),
+            base_branch: base_branch.to_string(),
+            strategy,
+            message: format!("{e:#}"),
+        };
     }
 
     if stashed {
@@ -428,14 +795,290 @@ pub fn sync_worktree(worktree_path: &Path) -> SyncResult {
     SyncResult::Synced
 }
 
+/// Fetch `remote` and count the commits `HEAD` is ahead of and behind
+/// `<remote>/<base_branch>`, without touching the worktree. Used to preview
+/// a sync before running it.
+pub fn sync_preview(worktree_path: &Path, remote: &str, base_branch: &str) -> Result<(u32, u32)> {
+    git_fetch(worktree_path, remote)?;
+    let output = git_rev_list_counts(worktree_path, remote, base_branch)?;
+    parse_rev_list_counts(&output)
+        .with_context(|| format!("unexpected git rev-list output: {}", output.trim()))
+}
+
+/// Raw `git rev-list --left-right --count HEAD...<remote>/<base_branch>`
+/// output.
+fn git_rev_list_counts(worktree_path: &Path, remote: &str, base_branch: &str) -> Result<String> {
+    let range = format!("HEAD...{remote}/{base_branch}");
+    let output = Command::new("git")
+        .args(["rev-list", "--left-right", "--count", &range])
+        .current_dir(worktree_path)
+        .output()
+        .context("failed to run git rev-list")?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        anyhow::bail!("git rev-list failed: {stderr}");
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Raw `git rev-list <range>` output, one commit hash per line.
+fn git_rev_list(worktree_path: &Path, range: &str) -> Result<String> {
+    let output = Command::new("git")
+        .args(["rev-list", range])
+        .current_dir(worktree_path)
+        .output()
+        .context("failed to run git rev-list")?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        anyhow::bail!("git rev-list failed: {stderr}");
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Raw `git status --porcelain` output.
+fn git_status_porcelain(worktree_path: &Path) -> Result<String> {
+    let output = Command::new("git")
+        .args(["status", "--porcelain"])
+        .current_dir(worktree_path)
+        .output()
+        .context("failed to run git status")?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        anyhow::bail!("git status failed: {stderr}");
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Whether a worktree needs no sync at all: `git status --porcelain` shows
+/// no local changes and `git rev-list --left-right --count` shows nothing
+/// behind the base. Local commits ahead of the base don't matter.
+fn is_clean_and_up_to_date(porcelain: &str, rev_list_counts: &str) -> bool {
+    !porcelain_is_dirty(porcelain)
+        && parse_rev_list_counts(rev_list_counts).is_some_and(|(_, behind)| behind == 0)
+}
+
+/// Whether the freshly fetched worktree can skip the stash/integrate/pop
+/// cycle. Any failure to check counts as "needs sync".
+fn already_synced(worktree_path: &Path, remote: &str, base_branch: &str) -> bool {
+    let Ok(porcelain) = git_status_porcelain(worktree_path) else {
+        return false;
+    };
+    let Ok(counts) = git_rev_list_counts(worktree_path, remote, base_branch) else {
+        return false;
+    };
+    is_clean_and_up_to_date(&porcelain, &counts)
+}
+
+/// Parse `git rev-list --left-right --count` output (`<ahead>\t<behind>`).
+fn parse_rev_list_counts(output: &str) -> Option<(u32, u32)> {
+    let mut counts = output.split_whitespace();
+    let ahead = counts.next()?.parse().ok()?;
+    let behind = counts.next()?.parse().ok()?;
+    counts.next().is_none().then_some((ahead, behind))
+}
+
+/// Result of pushing a worktree branch to the project's sync remote.
+#[derive(Debug, PartialEq)]
+pub enum PushResult {
+    /// New commits were pushed.
+    Pushed,
+    /// The remote branch already matched; nothing was sent.
+    UpToDate,
+    /// The configured remote does not exist, or there is no upstream to push to.
+    NoUpstream(String),
+    /// The remote refused the push, e.g. because it is not a fast-forward.
+    Rejected(String),
+    /// Unexpected failure.
+    Error(String),
+}
+
+/// Push `branch` to `remote` from `worktree_path`, passing `-u` when
+/// `set_upstream` is set so later pulls and pushes track it.
+pub fn push_branch(
+    worktree_path: &Path,
+    remote: &str,
+    branch: &str,
+    set_upstream: bool,
+) -> PushResult {
+    let mut cmd = Command::new("git");
+    cmd.arg("push");
+    if set_upstream {
+        cmd.arg("-u");
+    }
+    let output = match cmd
+        .args([remote, branch])
+        .current_dir(worktree_path)
+        .output()
+    {
+        Ok(o) => o,
+        Err(e) => return PushResult::Error(format!("failed to run git push: {e}")),
+    };
+
+    classify_push(
+        output.status.success(),
+        &String::from_utf8_lossy(&output.stderr),
+    )
+}
+
+/// Classify `git push` output. Git reports progress and outcomes on stderr
+/// even when the push succeeds.
+fn classify_push(success: bool, stderr: &str) -> PushResult {
+    if success {
+        return if stderr.contains("Everything up-to-date") {
+            PushResult::UpToDate
+        } else {
+            PushResult::Pushed
+        };
+    }
+
+    let reason = stderr
+        .lines()
+        .map(str::trim)
+        .find(|l| l.contains("rejected") || l.starts_with("fatal:") || l.starts_with("error:"))
+        .or_else(|| stderr.lines().map(str::trim).find(|l| !l.is_empty()))
+        .unwrap_or("git push failed")
+        .to_string();
+
+    if stderr.contains("[rejected]")
+        || stderr.contains("[remote rejected]")
+        || stderr.contains("non-fast-forward")
+    {
+        PushResult::Rejected(reason)
+    } else if stderr.contains("has no upstream branch")
+        || stderr.contains("does not appear to be a git repository")
+        || stderr.contains("No configured push destination")
+    {
+        PushResult::NoUpstream(reason)
+    } else {
+        PushResult::Error(reason)
+    }
+}
+
+/// Outcome of [`commit_all`].
+#[derive(Debug, PartialEq)]
+pub enum CommitResult {
+    /// A commit was created; holds its abbreviated hash.
+    Committed(String),
+    /// The worktree had no changes, so no commit was made.
+    NothingToCommit,
+}
+
+/// Stage everything in `worktree_path` (`git add -A`) and commit it with
+/// `message`. A clean worktree yields [`CommitResult::NothingToCommit`]
+/// rather than an error.
+pub fn commit_all(worktree_path: &Path, message: &str) -> Result<CommitResult> {
+    let output = Command::new("git")
+        .args(["add", "-A"])
+        .current_dir(worktree_path)
+        .output()
+        .context("failed to run git add")?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        anyhow::bail!("git add failed: {stderr}");
+    }
+
+    // `--quiet` exits 0 when nothing is staged and 1 when something is.
+    let staged = Command::new("git")
+        .args(["diff", "--cached", "--quiet"])
+        .current_dir(worktree_path)
+        .status()
+        .context("failed to run git diff")?;
+    if staged.success() {
+        return Ok(CommitResult::NothingToCommit);
+    }
+
+    let output = Command::new("git")
+        .args(["commit", "-q", "-m", message])
+        .current_dir(worktree_path)
+        .output()
+        .context("failed to run git commit")?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        anyhow::bail!("git commit failed: {stderr}");
+    }
+
+    let output = Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .current_dir(worktree_path)
+        .output()
+        .context("failed to run git rev-parse")?;
+    Ok(CommitResult::Committed(
+        String::from_utf8_lossy(&output.stdout).trim().to_string(),
+    ))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn validate_branch_name_accepts_common_names() {
+        for name in ["main", "feature/foo", "fix-123", "user/team/task_2", "v1.2"] {
+            assert_eq!(validate_branch_name(name), Ok(()), "{name}");
+        }
+    }
+
+    #[test]
+    fn validate_branch_name_rejects_empty() {
+        assert!(validate_branch_name("").is_err());
+    }
+
+    #[test]
+    fn validate_branch_name_rejects_whitespace() {
+        for name in ["my branch", "tab\there", " lead"] {
+            let err = validate_branch_name(name).unwrap_err();
+            assert!(err.contains("spaces"), "{name}: {err}");
+        }
+    }
+
+    #[test]
+    fn validate_branch_name_rejects_control_chars() {
+        for name in ["bell\u{7}", "del\u{7f}"] {
+            let err = validate_branch_name(name).unwrap_err();
+            assert!(err.contains("control"), "{name:?}: {err}");
+        }
+    }
+
+    #[test]
+    fn validate_branch_name_rejects_special_chars() {
+        for c in ['~', '^', ':', '?', '*', '[', '\\'] {
+            let name = format!("a{c}b");
+            let err = validate_branch_name(&name).unwrap_err();
+            assert!(err.contains(c), "{name}: {err}");
+        }
+    }
+
+    #[test]
+    fn validate_branch_name_rejects_double_dot() {
+        let err = validate_branch_name("a..b").unwrap_err();
+        assert!(err.contains(".."));
+    }
+
+    #[test]
+    fn validate_branch_name_rejects_leading_dash() {
+        let err = validate_branch_name("-f").unwrap_err();
+        assert!(err.contains("'-'"));
+    }
+
+    #[test]
+    fn validate_branch_name_rejects_bad_components() {
+        for name in [
+            "/lead", "trail/", "a//b", "a.", "a.lock", ".hidden", "a/.b", "@", "a@{b",
+        ] {
+            assert!(validate_branch_name(name).is_err(), "{name}");
+        }
+    }
+
     #[test]
     fn worktree_path_simple_branch() {
         let repo = Path::new("/home/user/repo");
-        let result = worktree_path(repo, "main");
+        let result = worktree_path(repo, "main", None);
         assert_eq!(
             result,
             PathBuf::from("/home/user/repo/.git/thurbox-worktrees/main")
@@ -445,7 +1088,7 @@ mod tests {
     #[test]
     fn worktree_path_slash_branch() {
         let repo = Path::new("/home/user/repo");
-        let result = worktree_path(repo, "feature/foo");
+        let result = worktree_path(repo, "feature/foo", None);
         assert_eq!(
             result,
             PathBuf::from("/home/user/repo/.git/thurbox-worktrees/feature-foo")
@@ -455,7 +1098,7 @@ mod tests {
     #[test]
     fn worktree_path_nested_slashes() {
         let repo = Path::new("/home/user/repo");
-        let result = worktree_path(repo, "feature/team/task");
+        let result = worktree_path(repo, "feature/team/task", None);
         assert_eq!(
             result,
             PathBuf::from("/home/user/repo/.git/thurbox-worktrees/feature-team-task")
@@ -465,7 +1108,7 @@ mod tests {
     #[test]
     fn worktree_path_no_slashes_unchanged() {
         let repo = Path::new("/repo");
-        let result = worktree_path(repo, "my-branch");
+        let result = worktree_path(repo, "my-branch", None);
         assert_eq!(
             result,
             PathBuf::from("/repo/.git/thurbox-worktrees/my-branch")
@@ -475,7 +1118,7 @@ mod tests {
     #[test]
     fn worktree_path_trailing_slash() {
         let repo = Path::new("/repo");
-        let result = worktree_path(repo, "branch/");
+        let result = worktree_path(repo, "branch/", None);
         assert_eq!(
             result,
             PathBuf::from("/repo/.git/thurbox-worktrees/branch-")
@@ -485,13 +1128,30 @@ mod tests {
     #[test]
     fn worktree_path_leading_slash() {
         let repo = Path::new("/repo");
-        let result = worktree_path(repo, "/branch");
+        let result = worktree_path(repo, "/branch", None);
         assert_eq!(
             result,
             PathBuf::from("/repo/.git/thurbox-worktrees/-branch")
         );
     }
 
+    #[test]
+    fn worktree_path_absolute_root() {
+        let repo = Path::new("/home/user/repo");
+        let result = worktree_path(repo, "feature/foo", Some(Path::new("/srv/worktrees")));
+        assert_eq!(result, PathBuf::from("/srv/worktrees/repo/feature-foo"));
+    }
+
+    #[test]
+    fn worktree_path_relative_root_is_resolved_against_repo() {
+        let repo = Path::new("/home/user/repo");
+        let result = worktree_path(repo, "main", Some(Path::new("../thurbox-worktrees")));
+        assert_eq!(
+            result,
+            PathBuf::from("/home/user/thurbox-worktrees/repo/main")
+        );
+    }
+
     #[test]
     fn default_branch_prefers_main_over_master() {
         let branches = vec![
@@ -500,30 +1160,631 @@ mod tests {
             "main".to_string(),
         ];
         // Uses a non-existent path so the git command fails, exercising the fallback.
-        let result = default_branch(Path::new("/nonexistent"), &branches);
+        let result = default_branch(Path::new("/nonexistent"), "origin", &branches);
         assert_eq!(result, Some("main".to_string()));
     }
 
     #[test]
     fn default_branch_falls_back_to_master() {
         let branches = vec!["develop".to_string(), "master".to_string()];
-        let result = default_branch(Path::new("/nonexistent"), &branches);
+        let result = default_branch(Path::new("/nonexistent"), "origin", &branches);
         assert_eq!(result, Some("master".to_string()));
     }
 
     #[test]
     fn default_branch_returns_none_when_no_candidates() {
         let branches = vec!["develop".to_string(), "feature".to_string()];
-        let result = default_branch(Path::new("/nonexistent"), &branches);
+        let result = default_branch(Path::new("/nonexistent"), "origin", &branches);
         assert_eq!(result, None);
     }
 
     #[test]
     fn default_branch_returns_none_for_empty_branches() {
-        let result = default_branch(Path::new("/nonexistent"), &[]);
+        let result = default_branch(Path::new("/nonexistent"), "origin", &[]);
         assert_eq!(result, None);
     }
 
+    #[test]
+    fn remote_name_is_substituted_into_git_args() {
+        assert_eq!(fetch_args("upstream"), ["fetch", "upstream"]);
+        assert_eq!(
+            remote_head_args("upstream"),
+            ["symbolic-ref", "refs/remotes/upstream/HEAD", "--short"]
+        );
+
+        let (args, abort) = integrate_args("upstream", "develop", SyncStrategy::Rebase);
+        assert_eq!(args, ["rebase", "upstream/develop"]);
+        assert_eq!(abort, Some(["rebase", "--abort"]));
+        let (args, _) = integrate_args("upstream", "develop", SyncStrategy::Merge);
+        assert_eq!(args, ["merge", "--no-edit", "upstream/develop"]);
+        let (args, abort) = integrate_args("origin", "main", SyncStrategy::FastForwardOnly);
+        assert_eq!(args, ["merge", "--ff-only", "origin/main"]);
+        assert_eq!(abort, None);
+    }
+
+    #[test]
+    fn sync_base_branch_uses_explicit_override() {
+        let result = sync_base_branch(Path::new("/nonexistent"), "origin", Some("develop"));
+        assert_eq!(result, "develop");
+    }
+
+    #[test]
+    fn sync_base_branch_blank_override_is_unset() {
+        let result = sync_base_branch(Path::new("/nonexistent"), "origin", Some("  "));
+        assert_eq!(result, "main");
+    }
+
+    #[test]
+    fn sync_base_branch_falls_back_to_main_without_repo() {
+        let result = sync_base_branch(Path::new("/nonexistent"), "origin", None);
+        assert_eq!(result, "main");
+    }
+
+    /// Run a git command in `dir` with a fixed identity, asserting success.
+    fn git(dir: &Path, args: &[&str]) -> String {
+        let output = Command::new("git")
+            .args(["-c", "user.name=t", "-c", "user.email=t@t"])
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .unwrap();
+        assert!(
+            output.status.success(),
+            "git {args:?} failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        String::from_utf8_lossy(&output.stdout).trim().to_string()
+    }
+
+    fn commit_file(dir: &Path, file: &str, contents: &str, message: &str) {
+        std::fs::write(dir.join(file), contents).unwrap();
+        git(dir, &["add", file]);
+        git(dir, &["commit", "-q", "-m", message]);
+    }
+
+    /// An `origin` repo on `main` and a `work` clone with identity configured.
+    /// Returns `(tmp, origin, work)`.
+    fn origin_and_clone() -> (tempfile::TempDir, PathBuf, PathBuf) {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let origin = tmp.path().join("origin");
+        let work = tmp.path().join("work");
+        std::fs::create_dir(&origin).unwrap();
+        git(&origin, &["init", "-q", "-b", "main"]);
+        commit_file(&origin, "base.txt", "base\n", "base");
+        git(
+            tmp.path(),
+            &[
+                "clone",
+                "-q",
+                origin.to_str().unwrap(),
+                work.to_str().unwrap(),
+            ],
+        );
+        git(&work, &["config", "user.name", "t"]);
+        git(&work, &["config", "user.email", "t@t"]);
+        (tmp, origin, work)
+    }
+
+    #[test]
+    fn rev_list_has_commits_parses_output() {
+        assert!(!rev_list_has_commits(""));
+        assert!(!rev_list_has_commits("\n"));
+        assert!(rev_list_has_commits(
+            "3f2a9c1e0b7d4a5f6e8c9b0a1d2e3f4a5b6c7d8e\n"
+        ));
+        assert!(rev_list_has_commits(
+            "3f2a9c1e0b7d4a5f6e8c9b0a1d2e3f4a5b6c7d8e\n\
+             9b0a1d2e3f4a5b6c7d8e3f2a9c1e0b7d4a5f6e8c\n"
+        ));
+    }
+
+    #[test]
+    fn unpushed_commits_compare_against_upstream() {
+        let (_tmp, _origin, work) = origin_and_clone();
+        assert!(!branch_has_unpushed_commits(&work, "main").unwrap());
+
+        commit_file(&work, "local.txt", "local\n", "local");
+        assert!(branch_has_unpushed_commits(&work, "main").unwrap());
+
+        git(&work, &["reset", "-q", "--hard", "origin/main"]);
+        assert!(!branch_has_unpushed_commits(&work, "main").unwrap());
+    }
+
+    #[test]
+    fn unpushed_commits_fall_back_to_base_without_upstream() {
+        let (_tmp, _origin, work) = origin_and_clone();
+        git(&work, &["checkout", "-q", "-b", "feature"]);
+        assert!(!branch_has_unpushed_commits(&work, "main").unwrap());
+
+        commit_file(&work, "feature.txt", "feature\n", "feature");
+        assert!(branch_has_unpushed_commits(&work, "main").unwrap());
+    }
+
+    #[test]
+    fn unpushed_commits_missing_worktree_reports_none() {
+        assert!(!branch_has_unpushed_commits(Path::new("/nonexistent/wt"), "main").unwrap());
+    }
+
+    fn parent_count(dir: &Path) -> usize {
+        git(dir, &["rev-list", "--parents", "-n", "1", "HEAD"])
+            .split_whitespace()
+            .count()
+            - 1
+    }
+
+    #[test]
+    fn clone_args_put_url_after_separator() {
+        assert_eq!(
+            clone_args("git@github.com:org/api.git", Path::new("/src/api")),
+            ["clone", "--", "git@github.com:org/api.git", "/src/api"]
+        );
+        assert_eq!(
+            clone_args("-evil", Path::new("dest"))[1..3],
+            ["--", "-evil"]
+        );
+    }
+
+    #[test]
+    fn git_repo_check_and_clone_detection() {
+        let (tmp, origin, work) = origin_and_clone();
+        assert!(is_git_repo(&work));
+        assert!(!needs_clone(&work));
+
+        let plain = tmp.path().join("plain");
+        std::fs::create_dir(&plain).unwrap();
+        assert!(!is_git_repo(&plain));
+        // Empty or missing directories are clone targets
+        assert!(needs_clone(&plain));
+        assert!(needs_clone(&tmp.path().join("missing")));
+        // A non-git directory with content is a non-git project
+        std::fs::write(plain.join("notes.txt"), "notes\n").unwrap();
+        assert!(!needs_clone(&plain));
+
+        let dest = tmp.path().join("nested/copy");
+        clone(&origin.display().to_string(), &dest).unwrap();
+        assert!(is_git_repo(&dest));
+        assert!(clone("/nonexistent/repo.git", &tmp.path().join("bad")).is_err());
+    }
+
+    const WORKTREE_LIST: &str = "\
+worktree /src/repo
+HEAD 1111111111111111111111111111111111111111
+branch refs/heads/main
+
+worktree /src/repo/.git/thurbox-worktrees/fix-auth
+HEAD 2222222222222222222222222222222222222222
+branch refs/heads/fix-auth
+
+worktree /tmp/review
+HEAD 3333333333333333333333333333333333333333
+detached
+
+worktree /src/repo/.git/thurbox-worktrees/feature/login
+HEAD 4444444444444444444444444444444444444444
+branch refs/heads/feature/login
+locked
+";
+
+    #[test]
+    fn worktree_list_finds_branch_path() {
+        assert_eq!(
+            parse_worktree_list(WORKTREE_LIST, "fix-auth"),
+            Some(PathBuf::from("/src/repo/.git/thurbox-worktrees/fix-auth"))
+        );
+        assert_eq!(
+            parse_worktree_list(WORKTREE_LIST, "main"),
+            Some(PathBuf::from("/src/repo"))
+        );
+        assert_eq!(
+            parse_worktree_list(WORKTREE_LIST, "feature/login"),
+            Some(PathBuf::from(
+                "/src/repo/.git/thurbox-worktrees/feature/login"
+            ))
+        );
+    }
+
+    #[test]
+    fn worktree_list_ignores_other_and_prefix_branches() {
+        assert_eq!(parse_worktree_list(WORKTREE_LIST, "fix"), None);
+        assert_eq!(parse_worktree_list(WORKTREE_LIST, "feature"), None);
+        assert_eq!(parse_worktree_list(WORKTREE_LIST, "missing"), None);
+        assert_eq!(parse_worktree_list("", "main"), None);
+    }
+
+    #[test]
+    fn checked_out_elsewhere_matches_old_and_new_git_messages() {
+        let old = anyhow::anyhow!(
+            "git worktree add (existing) failed: fatal: 'x' is already checked out at '/wt'"
+        );
+        let new = anyhow::anyhow!(
+            "git worktree add (existing) failed: fatal: 'x' is already used by worktree at '/wt'"
+        );
+        let other = anyhow::anyhow!("git worktree add (existing) failed: fatal: invalid reference");
+        assert!(is_checked_out_elsewhere(&old));
+        assert!(is_checked_out_elsewhere(&new));
+        assert!(!is_checked_out_elsewhere(&other));
+    }
+
+    #[test]
+    fn porcelain_empty_output_is_clean() {
+        assert!(!porcelain_is_dirty(""));
+        assert!(!porcelain_is_dirty("\n"));
+    }
+
+    #[test]
+    fn porcelain_modified_and_staged_are_dirty() {
+        assert!(porcelain_is_dirty(" M src/main.rs\n"));
+        assert!(porcelain_is_dirty("M  src/main.rs\n"));
+        assert!(porcelain_is_dirty("MM src/main.rs\nA  new.rs\n"));
+        assert!(porcelain_is_dirty("R  old.rs -> new.rs\n"));
+        assert!(porcelain_is_dirty(" D gone.rs\n"));
+        assert!(porcelain_is_dirty("UU conflicted.rs\n"));
+    }
+
+    #[test]
+    fn porcelain_untracked_is_dirty() {
+        assert!(porcelain_is_dirty("?? notes.txt\n"));
+    }
+
+    #[test]
+    fn porcelain_ignored_only_is_clean() {
+        assert!(!porcelain_is_dirty("!! target/\n"));
+        assert!(porcelain_is_dirty("!! target/\n?? notes.txt\n"));
+    }
+
+    const PORCELAIN_V2_CLEAN: &str = "\
+# branch.oid 4b825dc642cb6eb9a060e54bf8d69288fbee4904
+# branch.head main
+# branch.upstream origin/main
+# branch.ab +0 -0
+";
+
+    const PORCELAIN_V2_DIRTY: &str = "\
+# branch.oid 4b825dc642cb6eb9a060e54bf8d69288fbee4904
+# branch.head feature/login
+# branch.upstream origin/feature/login
+# branch.ab +3 -12
+1 .M N... 100644 100644 100644 e69de29bb2d1d6434b8b29ae775ad8c2e48c5391 e69de29bb2d1d6434b8b29ae775ad8c2e48c5391 src/main.rs
+1 A. N... 000000 100644 100644 0000000000000000000000000000000000000000 e69de29bb2d1d6434b8b29ae775ad8c2e48c5391 src/new.rs
+2 R. N... 100644 100644 100644 e69de29bb2d1d6434b8b29ae775ad8c2e48c5391 e69de29bb2d1d6434b8b29ae775ad8c2e48c5391 R100 src/b.rs\tsrc/a.rs
+u UU N... 100644 100644 100644 100644 e69de29bb2d1d6434b8b29ae775ad8c2e48c5391 e69de29bb2d1d6434b8b29ae775ad8c2e48c5391 e69de29bb2d1d6434b8b29ae775ad8c2e48c5391 src/conflict.rs
+? notes.txt
+! target/
+";
+
+    const PORCELAIN_V2_DETACHED: &str = "\
+# branch.oid 4b825dc642cb6eb9a060e54bf8d69288fbee4904
+# branch.head (detached)
+? scratch.txt
+";
+
+    const PORCELAIN_V2_NO_UPSTREAM: &str = "\
+# branch.oid (initial)
+# branch.head wip
+";
+
+    #[test]
+    fn porcelain_v2_clean_tracking_branch() {
+        let status = parse_porcelain_v2(PORCELAIN_V2_CLEAN);
+        assert_eq!(
+            status,
+            WorktreeStatus {
+                branch: Some("main".to_string()),
+                ahead_behind: Some((0, 0)),
+                dirty_files: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn porcelain_v2_counts_dirty_entries() {
+        let status = parse_porcelain_v2(PORCELAIN_V2_DIRTY);
+        assert_eq!(status.branch.as_deref(), Some("feature/login"));
+        assert_eq!(status.ahead_behind, Some((3, 12)));
+        // modified, added, renamed, unmerged, untracked; ignored excluded
+        assert_eq!(status.dirty_files, 5);
+    }
+
+    #[test]
+    fn porcelain_v2_detached_head() {
+        let status = parse_porcelain_v2(PORCELAIN_V2_DETACHED);
+        assert_eq!(status.branch, None);
+        assert_eq!(status.ahead_behind, None);
+        assert_eq!(status.dirty_files, 1);
+    }
+
+    #[test]
+    fn porcelain_v2_without_upstream() {
+        let status = parse_porcelain_v2(PORCELAIN_V2_NO_UPSTREAM);
+        assert_eq!(status.branch.as_deref(), Some("wip"));
+        assert_eq!(status.ahead_behind, None);
+    }
+
+    #[test]
+    fn clean_worktree_not_behind_is_up_to_date() {
+        assert!(is_clean_and_up_to_date("", "0\t0\n"));
+        // Local commits ahead of the base need no sync.
+        assert!(is_clean_and_up_to_date("", "4\t0\n"));
+        // Ignored files don't count as changes.
+        assert!(is_clean_and_up_to_date("!! target/\n", "0\t0\n"));
+    }
+
+    #[test]
+    fn dirty_or_behind_worktree_needs_sync() {
+        assert!(!is_clean_and_up_to_date(" M src/main.rs\n", "0\t0\n"));
+        assert!(!is_clean_and_up_to_date("?? notes.txt\n", "0\t0\n"));
+        assert!(!is_clean_and_up_to_date("", "0\t2\n"));
+        assert!(!is_clean_and_up_to_date("", "3\t1\n"));
+        // Unreadable counts never skip the sync.
+        assert!(!is_clean_and_up_to_date("", ""));
+    }
+
+    #[test]
+    fn rev_list_counts_parse_ahead_and_behind() {
+        assert_eq!(parse_rev_list_counts("3\t12\n"), Some((3, 12)));
+        assert_eq!(parse_rev_list_counts("0\t0"), Some((0, 0)));
+    }
+
+    #[test]
+    fn rev_list_counts_reject_malformed_output() {
+        assert_eq!(parse_rev_list_counts(""), None);
+        assert_eq!(parse_rev_list_counts("3\n"), None);
+        assert_eq!(parse_rev_list_counts("x\t2"), None);
+        assert_eq!(parse_rev_list_counts("1\t2\t3"), None);
+    }
+
+    #[test]
+    fn ahead_behind_rejects_malformed_values() {
+        assert_eq!(parse_ahead_behind("+1 -2"), Some((1, 2)));
+        assert_eq!(parse_ahead_behind("1 2"), None);
+        assert_eq!(parse_ahead_behind("+x -2"), None);
+        assert_eq!(parse_ahead_behind(""), None);
+    }
+
+    #[test]
+    fn worktree_status_reads_real_repo() {
+        let (_tmp, _origin, work) = origin_and_clone();
+        commit_file(&work, "local.txt", "local\n", "local");
+        std::fs::write(work.join("untracked.txt"), "x\n").unwrap();
+
+        let status = worktree_status(&work).unwrap();
+        assert_eq!(status.branch.as_deref(), Some("main"));
+        assert_eq!(status.ahead_behind, Some((1, 0)));
+        assert_eq!(status.dirty_files, 1);
+    }
+
+    #[test]
+    fn worktree_is_dirty_detects_changes() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        git(tmp.path(), &["init", "-q", "-b", "main"]);
+        commit_file(tmp.path(), "a.txt", "a\n", "init");
+        assert!(!worktree_is_dirty(tmp.path()).unwrap());
+
+        std::fs::write(tmp.path().join("b.txt"), "untracked\n").unwrap();
+        assert!(worktree_is_dirty(tmp.path()).unwrap());
+
+        std::fs::remove_file(tmp.path().join("b.txt")).unwrap();
+        std::fs::write(tmp.path().join("a.txt"), "changed\n").unwrap();
+        assert!(worktree_is_dirty(tmp.path()).unwrap());
+    }
+
+    #[test]
+    fn worktree_is_dirty_missing_path_is_clean() {
+        assert!(!worktree_is_dirty(Path::new("/nonexistent/thurbox-wt")).unwrap());
+    }
+
+    #[test]
+    fn sync_base_branch_detects_default_branch() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        git(tmp.path(), &["init", "-q", "-b", "master"]);
+        git(tmp.path(), &["commit", "-q", "--allow-empty", "-m", "init"]);
+
+        assert_eq!(sync_base_branch(tmp.path(), "origin", None), "master");
+        assert_eq!(
+            sync_base_branch(tmp.path(), "origin", Some("develop")),
+            "develop"
+        );
+    }
+
+    #[test]
+    fn sync_rebase_replays_local_commits() {
+        let (_tmp, origin, work) = origin_and_clone();
+        commit_file(&work, "local.txt", "local\n", "local");
+        commit_file(&origin, "upstream.txt", "upstream\n", "upstream");
+        std::fs::write(work.join("base.txt"), "dirty\n").unwrap();
+
+        let result = sync_worktree(&work, "origin", "main", SyncStrategy::Rebase);
+
+        assert!(matches!(result, SyncResult::Synced), "{result:?}");
+        assert_eq!(parent_count(&work), 1);
+        assert_eq!(
+            git(&work, &["rev-parse", "HEAD~1"]),
+            git(&work, &["rev-parse", "origin/main"])
+        );
+        assert_eq!(
+            std::fs::read_to_string(work.join("base.txt")).unwrap(),
+            "dirty\n"
+        );
+    }
+
+    #[test]
+    fn sync_merge_keeps_local_commits() {
+        let (_tmp, origin, work) = origin_and_clone();
+        commit_file(&work, "local.txt", "local\n", "local");
+        let local_head = git(&work, &["rev-parse", "HEAD"]);
+        commit_file(&origin, "upstream.txt", "upstream\n", "upstream");
+        std::fs::write(work.join("base.txt"), "dirty\n").unwrap();
+
+        let result = sync_worktree(&work, "origin", "main", SyncStrategy::Merge);
+
+        assert!(matches!(result, SyncResult::Synced), "{result:?}");
+        assert_eq!(parent_count(&work), 2);
+        assert_eq!(git(&work, &["rev-parse", "HEAD^1"]), local_head);
+        assert!(work.join("upstream.txt").exists());
+        assert_eq!(
+            std::fs::read_to_string(work.join("base.txt")).unwrap(),
+            "dirty\n"
+        );
+    }
+
+    #[test]
+    fn sync_merge_conflict_is_aborted() {
+        let (_tmp, origin, work) = origin_and_clone();
+        commit_file(&work, "base.txt", "local\n", "local");
+        let local_head = git(&work, &["rev-parse", "HEAD"]);
+        commit_file(&origin, "base.txt", "upstream\n", "upstream");
+
+        let result = sync_worktree(&work, "origin", "main", SyncStrategy::Merge);
+
+        assert!(
+            matches!(result, SyncResult::Conflict { ref base_branch, .. } if base_branch == "main"),
+            "{result:?}"
+        );
+        assert_eq!(git(&work, &["rev-parse", "HEAD"]), local_head);
+        assert!(!work.join(".git").join("MERGE_HEAD").exists());
+    }
+
+    #[test]
+    fn sync_fast_forward_only_advances_clean_branch() {
+        let (_tmp, origin, work) = origin_and_clone();
+        commit_file(&origin, "upstream.txt", "upstream\n", "upstream");
+        std::fs::write(work.join("base.txt"), "dirty\n").unwrap();
+
+        let result = sync_worktree(&work, "origin", "main", SyncStrategy::FastForwardOnly);
+
+        assert!(matches!(result, SyncResult::Synced), "{result:?}");
+        assert_eq!(
+            git(&work, &["rev-parse", "HEAD"]),
+            git(&work, &["rev-parse", "origin/main"])
+        );
+        assert_eq!(
+            std::fs::read_to_string(work.join("base.txt")).unwrap(),
+            "dirty\n"
+        );
+    }
+
+    #[test]
+    fn sync_fast_forward_only_reports_conflict_when_diverged() {
+        let (_tmp, origin, work) = origin_and_clone();
+        commit_file(&work, "local.txt", "local\n", "local");
+        let local_head = git(&work, &["rev-parse", "HEAD"]);
+        commit_file(&origin, "upstream.txt", "upstream\n", "upstream");
+        std::fs::write(work.join("base.txt"), "dirty\n").unwrap();
+
+        let result = sync_worktree(&work, "origin", "main", SyncStrategy::FastForwardOnly);
+
+        assert!(
+            matches!(result, SyncResult::Conflict { ref message, .. } if message.contains("cannot fast-forward")),
+            "{result:?}"
+        );
+        assert_eq!(git(&work, &["rev-parse", "HEAD"]), local_head);
+        assert_eq!(
+            std::fs::read_to_string(work.join("base.txt")).unwrap(),
+            "dirty\n"
+        );
+    }
+
+    #[test]
+    fn classify_push_distinguishes_outcomes() {
+        assert_eq!(
+            classify_push(true, "To /tmp/origin\n * [new branch]      feat -> feat\n"),
+            PushResult::Pushed
+        );
+        assert_eq!(
+            classify_push(true, "Everything up-to-date\n"),
+            PushResult::UpToDate
+        );
+        assert_eq!(
+            classify_push(
+                false,
+                "To /tmp/origin\n ! [rejected]        feat -> feat (fetch first)\n\
+                 error: failed to push some refs to '/tmp/origin'\n"
+            ),
+            PushResult::Rejected("! [rejected]        feat -> feat (fetch first)".to_string())
+        );
+        assert_eq!(
+            classify_push(
+                false,
+                "fatal: 'origin' does not appear to be a git repository\n\
+                 fatal: Could not read from remote repository.\n"
+            ),
+            PushResult::NoUpstream(
+                "fatal: 'origin' does not appear to be a git repository".to_string()
+            )
+        );
+        assert_eq!(
+            classify_push(false, "fatal: Authentication failed\n"),
+            PushResult::Error("fatal: Authentication failed".to_string())
+        );
+    }
+
+    #[test]
+    fn push_branch_reports_pushed_then_up_to_date() {
+        let (_tmp, origin, work) = origin_and_clone();
+        git(&work, &["checkout", "-q", "-b", "feat"]);
+        commit_file(&work, "feat.txt", "feat\n", "feat");
+
+        assert_eq!(
+            push_branch(&work, "origin", "feat", true),
+            PushResult::Pushed
+        );
+        assert_eq!(
+            git(&origin, &["rev-parse", "feat"]),
+            git(&work, &["rev-parse", "HEAD"])
+        );
+        assert_eq!(
+            git(&work, &["rev-parse", "--abbrev-ref", "feat@{u}"]),
+            "origin/feat"
+        );
+        assert_eq!(
+            push_branch(&work, "origin", "feat", false),
+            PushResult::UpToDate
+        );
+
+        // A project pinned to another remote pushes there instead
+        git(&work, &["remote", "rename", "origin", "upstream"]);
+        commit_file(&work, "more.txt", "more\n", "more");
+        assert_eq!(
+            push_branch(&work, "upstream", "feat", false),
+            PushResult::Pushed
+        );
+        assert!(matches!(
+            push_branch(&work, "origin", "feat", false),
+            PushResult::NoUpstream(_) | PushResult::Error(_)
+        ));
+    }
+
+    #[test]
+    fn commit_all_commits_staged_and_untracked_changes() {
+        let (_tmp, _origin, work) = origin_and_clone();
+        std::fs::write(work.join("base.txt"), "changed\n").unwrap();
+        std::fs::write(work.join("new.txt"), "new\n").unwrap();
+
+        let result = commit_all(&work, "checkpoint\n\nbody").unwrap();
+
+        let CommitResult::Committed(hash) = result else {
+            panic!("expected a commit, got {result:?}");
+        };
+        assert_eq!(hash, git(&work, &["rev-parse", "--short", "HEAD"]));
+        assert_eq!(
+            git(&work, &["log", "-1", "--format=%B"]),
+            "checkpoint\n\nbody"
+        );
+        assert_eq!(git(&work, &["status", "--porcelain"]), "");
+    }
+
+    #[test]
+    fn commit_all_on_clean_worktree_is_nothing_to_commit() {
+        let (_tmp, _origin, work) = origin_and_clone();
+        let head = git(&work, &["rev-parse", "HEAD"]);
+
+        assert_eq!(
+            commit_all(&work, "checkpoint").unwrap(),
+            CommitResult::NothingToCommit
+        );
+        assert_eq!(git(&work, &["rev-parse", "HEAD"]), head);
+    }
+
     #[test]
     fn transient_error_detects_could_not_write_index() {
         assert!(is_transient_error("error: could not write index"));
@@ -2,6 +2,7 @@
 
 pub mod app;
 pub mod claude;
+pub mod cli;
 pub mod git;
 pub mod mcp;
 pub mod paths;
@@ -8,12 +8,28 @@ use crossterm::event::{
 use crossterm::execute;
 
 use thurbox::app::{App, AppMessage};
+use thurbox::claude::pty::LocalPtyBackend;
 use thurbox::claude::tmux::LocalTmuxBackend;
 use thurbox::claude::SessionBackend;
+use thurbox::paths::PathKind;
 use thurbox::storage::Database;
 
+/// How long soft-deleted sessions and projects stay restorable before
+/// startup removes them for good.
+const TOMBSTONE_RETENTION: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
+/// Startup database backups kept in the backup directory.
+const BACKUPS_KEPT: usize = 5;
+
 #[tokio::main]
 async fn main() -> Result<()> {
+    // Headless subcommands (`thurbox ls`, `thurbox kill <id>`) skip the TUI
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if let Some(command) = thurbox::cli::Command::parse(&args)? {
+        let db = Database::open(&thurbox::paths::require(PathKind::Database)?)?;
+        return thurbox::cli::run(&command, &db, &mut std::io::stdout().lock());
+    }
+
     // Set up panic hook that restores terminal before printing the panic
     let original_hook = std::panic::take_hook();
     std::panic::set_hook(Box::new(move |panic_info| {
@@ -21,8 +37,12 @@ async fn main() -> Result<()> {
         original_hook(panic_info);
     }));
 
+    // Refuse to start rather than scatter the log and database into the
+    // current directory when HOME/XDG cannot be resolved.
+    let log_dir = thurbox::paths::require(PathKind::LogDir)?;
+    let db_path = thurbox::paths::require(PathKind::Database)?;
+
     // File-based logging (stdout is owned by the TUI)
-    let log_dir = thurbox::paths::log_directory().unwrap_or_else(|| std::path::PathBuf::from("."));
     std::fs::create_dir_all(&log_dir).ok();
     let file_appender = tracing_appender::rolling::daily(log_dir, "thurbox.log");
     tracing_subscriber::fmt()
@@ -34,28 +54,30 @@ async fn main() -> Result<()> {
         .with_ansi(false)
         .init();
 
-    // Initialize the session backend (local tmux).
-    let backend: Arc<dyn SessionBackend> = Arc::new(LocalTmuxBackend::new());
-    backend.check_available()?;
+    // Initialize the session backend (tmux unless THURBOX_BACKEND says otherwise).
+    let backend = select_backend()?;
     backend.ensure_ready()?;
 
     // Open SQLite database for persistent state
-    let db_path = thurbox::paths::database_file().unwrap_or_else(|| {
-        let mut p = std::path::PathBuf::from(std::env::var_os("HOME").unwrap_or_default());
-        p.push(if cfg!(dev_build) {
-            ".local/share/thurbox-dev/thurbox.db"
-        } else {
-            ".local/share/thurbox/thurbox.db"
-        });
-        p
-    });
     let db = Database::open(&db_path).expect("Failed to open database");
+    // Back up before purging so the newest backup still has the tombstones.
+    if let Some(dir) = thurbox::paths::backup_directory() {
+        if let Err(e) = db.rolling_backup(&dir, BACKUPS_KEPT) {
+            tracing::warn!("Failed to back up database: {e:#}");
+        }
+    }
+    match db.purge_tombstones(TOMBSTONE_RETENTION) {
+        Ok(0) => {}
+        Ok(n) => tracing::info!("Purged {n} deleted sessions/projects older than 7 days"),
+        Err(e) => tracing::warn!("Failed to purge deleted sessions/projects: {e}"),
+    }
 
     let mut terminal = ratatui::init();
     execute!(std::io::stdout(), EnableMouseCapture)?;
     let size = terminal.size()?;
 
     let mut app = App::new(size.height, size.width, backend, db);
+    app.load_settings();
 
     // Load session state from DB and restore
     if let Some((sessions, counter)) = app.load_persisted_state_from_db() {
@@ -79,9 +101,37 @@ async fn main() -> Result<()> {
     res
 }
 
+/// Pick the session backend. `THURBOX_BACKEND=tmux` or `=pty` forces one;
+/// otherwise tmux is used when it is installed, falling back to a plain PTY.
+/// PTY sessions die with thurbox, so they are resumed rather than adopted on
+/// the next launch.
+fn select_backend() -> Result<Arc<dyn SessionBackend>> {
+    let choice = std::env::var("THURBOX_BACKEND").unwrap_or_default();
+    let backend: Arc<dyn SessionBackend> = match choice.as_str() {
+        "tmux" => Arc::new(LocalTmuxBackend::new()),
+        "pty" => Arc::new(LocalPtyBackend::new()),
+        "" => {
+            let tmux = LocalTmuxBackend::new();
+            match tmux.check_available() {
+                Ok(()) => return Ok(Arc::new(tmux)),
+                Err(e) => {
+                    tracing::warn!("tmux unavailable ({e:#}), using plain PTY sessions");
+                    Arc::new(LocalPtyBackend::new())
+                }
+            }
+        }
+        other => anyhow::bail!("Unknown THURBOX_BACKEND '{other}' (expected 'tmux' or 'pty')"),
+    };
+    backend.check_available()?;
+    Ok(backend)
+}
+
 async fn run_loop(terminal: &mut ratatui::DefaultTerminal, app: &mut App) -> Result<()> {
     loop {
-        terminal.draw(|f| app.view(f))?;
+        if app.needs_redraw() {
+            terminal.draw(|f| app.view(f))?;
+            app.mark_drawn();
+        }
 
         if event::poll(Duration::from_millis(10))? {
             let msg = match event::read()? {
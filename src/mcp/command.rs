@@ -0,0 +1,145 @@
+//! Lightweight command lookup for MCP server configurations.
+//!
+//! Resolves a server's `command` the same way a shell would before spawning
+//! it, so typos can be flagged when the server is saved instead of surfacing
+//! later as an opaque handshake failure.
+
+use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
+
+/// Resolve an MCP server command to an executable path.
+///
+/// Commands containing a path separator are checked directly (relative paths
+/// resolve against the current directory). Bare names are searched for in
+/// each `$PATH` entry. Returns `None` if no executable file is found.
+pub fn resolve_command(command: &str) -> Option<PathBuf> {
+    resolve_command_in(command, std::env::var_os("PATH").as_deref())
+}
+
+/// Resolve `command` against an explicit `PATH` value.
+fn resolve_command_in(command: &str, path_var: Option<&OsStr>) -> Option<PathBuf> {
+    let command = command.trim();
+    if command.is_empty() {
+        return None;
+    }
+
+    if command.contains(std::path::MAIN_SEPARATOR) {
+        let path = PathBuf::from(command);
+        return is_executable(&path).then_some(path);
+    }
+
+    std::env::split_paths(path_var?)
+        .map(|dir| dir.join(command))
+        .find(|candidate| is_executable(candidate))
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    path.metadata()
+        .map(|m| m.is_file() && m.permissions().mode() & 0o111 != 0)
+        .unwrap_or(false)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+    path.is_file()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ffi::OsString;
+
+    use super::*;
+
+    fn make_executable(dir: &Path, name: &str) -> PathBuf {
+        let path = dir.join(name);
+        std::fs::write(&path, "#!/bin/sh\n").unwrap();
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+        }
+        path
+    }
+
+    #[test]
+    fn empty_command_is_not_found() {
+        assert_eq!(resolve_command_in("", Some(OsStr::new("/usr/bin"))), None);
+        assert_eq!(
+            resolve_command_in("   ", Some(OsStr::new("/usr/bin"))),
+            None
+        );
+    }
+
+    #[test]
+    fn bare_name_found_on_path() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let expected = make_executable(tmp.path(), "my-server");
+        let path_var = OsString::from(tmp.path());
+
+        assert_eq!(
+            resolve_command_in("my-server", Some(&path_var)),
+            Some(expected)
+        );
+    }
+
+    #[test]
+    fn bare_name_searches_all_path_entries() {
+        let first = tempfile::TempDir::new().unwrap();
+        let second = tempfile::TempDir::new().unwrap();
+        let expected = make_executable(second.path(), "my-server");
+        let path_var = std::env::join_paths([first.path(), second.path()]).unwrap();
+
+        assert_eq!(
+            resolve_command_in("my-server", Some(&path_var)),
+            Some(expected)
+        );
+    }
+
+    #[test]
+    fn bare_name_missing_from_path() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path_var = OsString::from(tmp.path());
+        assert_eq!(resolve_command_in("nope-xyz", Some(&path_var)), None);
+    }
+
+    #[test]
+    fn bare_name_without_path_var() {
+        assert_eq!(resolve_command_in("npx", None), None);
+    }
+
+    #[test]
+    fn absolute_path_found() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let expected = make_executable(tmp.path(), "server");
+        let command = expected.display().to_string();
+
+        assert_eq!(resolve_command_in(&command, None), Some(expected));
+    }
+
+    #[test]
+    fn absolute_path_missing() {
+        assert_eq!(
+            resolve_command_in("/nonexistent/dir/server", Some(OsStr::new("/usr/bin"))),
+            None
+        );
+    }
+
+    #[test]
+    fn directory_is_not_a_command() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        std::fs::create_dir(tmp.path().join("subdir")).unwrap();
+        let path_var = OsString::from(tmp.path());
+        assert_eq!(resolve_command_in("subdir", Some(&path_var)), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn non_executable_file_is_not_a_command() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        std::fs::write(tmp.path().join("plain"), "data").unwrap();
+        let path_var = OsString::from(tmp.path());
+        assert_eq!(resolve_command_in("plain", Some(&path_var)), None);
+    }
+}
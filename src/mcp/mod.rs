@@ -4,6 +4,7 @@
 //! and session listing — so external agents can set up workspaces without
 //! manual TUI interaction.
 
+pub mod command;
 mod tools;
 pub mod types;
 
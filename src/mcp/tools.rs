@@ -5,7 +5,7 @@ use std::path::PathBuf;
 use rmcp::handler::server::wrapper::Parameters;
 use rmcp::{tool, tool_router};
 
-use crate::project::{ProjectConfig, ProjectId};
+use crate::project::{ProjectConfig, ProjectId, SyncStrategy};
 use crate::session::{McpServerConfig, RoleConfig, RolePermissions, SessionId};
 use crate::storage::Database;
 use crate::sync::{SharedProject, SharedSession};
@@ -65,6 +65,9 @@ fn project_to_response(p: &SharedProject) -> ProjectResponse {
         repos: p.repos.clone(),
         roles: p.roles.iter().map(role_to_response).collect(),
         mcp_servers: p.mcp_servers.iter().map(mcp_server_to_response).collect(),
+        sync_base_branch: p.sync_base_branch.clone(),
+        remote: p.remote.clone(),
+        sync_strategy: p.sync_strategy.as_str().to_string(),
     }
 }
 
@@ -74,6 +77,7 @@ fn mcp_server_to_response(s: &McpServerConfig) -> McpServerResponse {
         command: s.command.clone(),
         args: s.args.clone(),
         env: s.env.clone(),
+        enabled: s.enabled,
     }
 }
 
@@ -152,6 +156,13 @@ impl ThurboxMcp {
             repos: repos.clone(),
             roles: Vec::new(),
             mcp_servers: Vec::new(),
+            sync_base_branch: None,
+            remote: None,
+            sync_strategy: SyncStrategy::default(),
+            activity_timeout_ms: None,
+            worktree_root: None,
+            default_role: None,
+            session_init_command: None,
             id: None,
         };
         let id = config.deterministic_id();
@@ -171,6 +182,9 @@ impl ThurboxMcp {
                     repos,
                     roles: vec![],
                     mcp_servers: vec![],
+                    sync_base_branch: None,
+                    remote: None,
+                    sync_strategy: SyncStrategy::default().as_str().to_string(),
                 }),
             },
             Err(_) => json_text(&ProjectResponse {
@@ -179,11 +193,16 @@ impl ThurboxMcp {
                 repos,
                 roles: vec![],
                 mcp_servers: vec![],
+                sync_base_branch: None,
+                remote: None,
+                sync_strategy: SyncStrategy::default().as_str().to_string(),
             }),
         }
     }
 
-    #[tool(description = "Update an existing project's name and/or repository paths")]
+    #[tool(
+        description = "Update an existing project's name, repository paths, and/or worktree sync settings (base branch, remote, strategy)"
+    )]
     fn update_project(&self, Parameters(params): Parameters<UpdateProjectParams>) -> String {
         let db = self.db.lock().unwrap();
         let (projects, idx) = match require_project(&db, &params.project) {
@@ -202,6 +221,32 @@ impl ThurboxMcp {
             return error_json(&e.to_string());
         }
 
+        if let Some(ref strategy) = params.sync_strategy {
+            let strategy = match strategy.parse::<SyncStrategy>() {
+                Ok(s) => s,
+                Err(e) => return error_json(&e),
+            };
+            if let Err(e) = db.set_project_sync_strategy(project.id, strategy) {
+                return error_json(&e.to_string());
+            }
+        }
+
+        if let Some(ref branch) = params.sync_base_branch {
+            let branch = branch.trim();
+            let branch = (!branch.is_empty()).then_some(branch);
+            if let Err(e) = db.set_project_sync_base_branch(project.id, branch) {
+                return error_json(&e.to_string());
+            }
+        }
+
+        if let Some(ref remote) = params.remote {
+            let remote = remote.trim();
+            let remote = (!remote.is_empty()).then_some(remote);
+            if let Err(e) = db.set_project_remote(project.id, remote) {
+                return error_json(&e.to_string());
+            }
+        }
+
         match db.list_active_projects() {
             Ok(updated) => match updated.iter().find(|p| p.id == project.id) {
                 Some(p) => json_text(&project_to_response(p)),
@@ -322,6 +367,7 @@ impl ThurboxMcp {
                 command: s.command,
                 args: s.args,
                 env: s.env,
+                enabled: s.enabled,
             })
             .collect();
 
@@ -465,6 +511,13 @@ mod tests {
             repos: vec![],
             roles: vec![],
             mcp_servers: vec![],
+            sync_base_branch: None,
+            remote: None,
+            sync_strategy: SyncStrategy::default(),
+            activity_timeout_ms: None,
+            worktree_root: None,
+            default_role: None,
+            session_init_command: None,
             id: None,
         };
         config.deterministic_id()
@@ -484,6 +537,13 @@ mod tests {
             repos: vec![],
             roles: vec![],
             mcp_servers: vec![],
+            sync_base_branch: None,
+            remote: None,
+            sync_strategy: SyncStrategy::default(),
+            activity_timeout_ms: None,
+            worktree_root: None,
+            default_role: None,
+            session_init_command: None,
         }];
         assert!(resolve_project(&projects, "myproject").is_some());
         assert!(resolve_project(&projects, "MYPROJECT").is_some());
@@ -499,6 +559,13 @@ mod tests {
             repos: vec![],
             roles: vec![],
             mcp_servers: vec![],
+            sync_base_branch: None,
+            remote: None,
+            sync_strategy: SyncStrategy::default(),
+            activity_timeout_ms: None,
+            worktree_root: None,
+            default_role: None,
+            session_init_command: None,
         }];
         assert!(resolve_project(&projects, &pid.to_string()).is_some());
     }
@@ -511,6 +578,13 @@ mod tests {
             repos: vec![],
             roles: vec![],
             mcp_servers: vec![],
+            sync_base_branch: None,
+            remote: None,
+            sync_strategy: SyncStrategy::default(),
+            activity_timeout_ms: None,
+            worktree_root: None,
+            default_role: None,
+            session_init_command: None,
         }];
         assert!(resolve_project(&projects, "nonexistent").is_none());
     }
@@ -613,6 +687,9 @@ mod tests {
             project: "oldname".to_string(),
             name: Some("newname".to_string()),
             repos: None,
+            sync_base_branch: None,
+            remote: None,
+            sync_strategy: None,
         }));
         let v = parse_json(&result);
         assert_eq!(v["name"], "newname");
@@ -632,12 +709,117 @@ mod tests {
             project: "proj".to_string(),
             name: None,
             repos: Some(vec!["/new1".to_string(), "/new2".to_string()]),
+            sync_base_branch: None,
+            remote: None,
+            sync_strategy: None,
         }));
         let v = parse_json(&result);
         assert_eq!(v["name"], "proj");
         assert_eq!(v["repos"].as_array().unwrap().len(), 2);
     }
 
+    #[test]
+    fn update_project_sync_base_branch() {
+        let server = test_server();
+        server.create_project(Parameters(CreateProjectParams {
+            name: "proj".to_string(),
+            repos: vec!["/repo".to_string()],
+        }));
+
+        let result = server.update_project(Parameters(UpdateProjectParams {
+            project: "proj".to_string(),
+            name: None,
+            repos: None,
+            sync_base_branch: Some("develop".to_string()),
+            remote: None,
+            sync_strategy: None,
+        }));
+        let v = parse_json(&result);
+        assert_eq!(v["sync_base_branch"], "develop");
+
+        // Empty string clears the override.
+        let result = server.update_project(Parameters(UpdateProjectParams {
+            project: "proj".to_string(),
+            name: None,
+            repos: None,
+            sync_base_branch: Some(String::new()),
+            remote: None,
+            sync_strategy: None,
+        }));
+        let v = parse_json(&result);
+        assert!(v.get("sync_base_branch").is_none());
+    }
+
+    #[test]
+    fn update_project_remote() {
+        let server = test_server();
+        server.create_project(Parameters(CreateProjectParams {
+            name: "proj".to_string(),
+            repos: vec!["/repo".to_string()],
+        }));
+
+        let result = server.update_project(Parameters(UpdateProjectParams {
+            project: "proj".to_string(),
+            name: None,
+            repos: None,
+            sync_base_branch: None,
+            remote: Some("upstream".to_string()),
+            sync_strategy: None,
+        }));
+        let v = parse_json(&result);
+        assert_eq!(v["remote"], "upstream");
+
+        // Empty string restores the default remote.
+        let result = server.update_project(Parameters(UpdateProjectParams {
+            project: "proj".to_string(),
+            name: None,
+            repos: None,
+            sync_base_branch: None,
+            remote: Some(String::new()),
+            sync_strategy: None,
+        }));
+        let v = parse_json(&result);
+        assert!(v.get("remote").is_none());
+    }
+
+    #[test]
+    fn update_project_sync_strategy() {
+        let server = test_server();
+        server.create_project(Parameters(CreateProjectParams {
+            name: "proj".to_string(),
+            repos: vec!["/repo".to_string()],
+        }));
+        let v = parse_json(&server.get_project(Parameters(GetProjectParams {
+            project: "proj".to_string(),
+        })));
+        assert_eq!(v["sync_strategy"], "rebase");
+
+        let result = server.update_project(Parameters(UpdateProjectParams {
+            project: "proj".to_string(),
+            name: None,
+            repos: None,
+            sync_base_branch: None,
+            remote: None,
+            sync_strategy: Some("ff-only".to_string()),
+        }));
+        let v = parse_json(&result);
+        assert_eq!(v["sync_strategy"], "ff-only");
+
+        let result = server.update_project(Parameters(UpdateProjectParams {
+            project: "proj".to_string(),
+            name: None,
+            repos: None,
+            sync_base_branch: None,
+            remote: None,
+            sync_strategy: Some("squash".to_string()),
+        }));
+        let v = parse_json(&result);
+        assert!(v["error"]
+            .as_str()
+            .unwrap()
+            .contains("unknown sync strategy"));
+    }
+
     #[test]
     fn update_nonexistent_project() {
         let server = test_server();
@@ -645,6 +827,9 @@ mod tests {
             project: "nope".to_string(),
             name: Some("renamed".to_string()),
             repos: None,
+            sync_base_branch: None,
+            remote: None,
+            sync_strategy: None,
         }));
         let v = parse_json(&result);
         assert!(v["error"].as_str().unwrap().contains("Project not found"));
@@ -1020,12 +1205,14 @@ mod tests {
                         "@modelcontextprotocol/server-filesystem".to_string(),
                     ],
                     env: HashMap::new(),
+                    enabled: true,
                 },
                 McpServerInput {
                     name: "github".to_string(),
                     command: "gh-mcp".to_string(),
                     args: vec![],
                     env: HashMap::from([("GITHUB_TOKEN".to_string(), "tok-123".to_string())]),
+                    enabled: true,
                 },
             ],
         }));
@@ -1068,6 +1255,7 @@ mod tests {
                     command: "test-cmd".to_string(),
                     args: vec!["--flag".to_string()],
                     env: HashMap::from([("KEY".to_string(), "VAL".to_string())]),
+                    enabled: true,
                 }],
             )
             .unwrap();
@@ -1101,6 +1289,10 @@ mod tests {
             additional_dirs: Vec::new(),
             worktrees: Vec::new(),
             shell_backend_id: None,
+            is_pinned: false,
+            label: None,
+            label_color: None,
+            created_at: None,
             tombstone: false,
             tombstone_at: None,
         };
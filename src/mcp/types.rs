@@ -30,6 +30,16 @@ pub struct UpdateProjectParams {
     pub name: Option<String>,
     #[schemars(description = "New list of repository directory paths (replaces existing)")]
     pub repos: Option<Vec<String>>,
+    #[schemars(
+        description = "Branch that worktree sync rebases onto (e.g. \"develop\"). Empty string restores default-branch detection"
+    )]
+    pub sync_base_branch: Option<String>,
+    #[schemars(
+        description = "Git remote that worktree sync fetches from (e.g. \"upstream\"). Empty string restores \"origin\""
+    )]
+    pub remote: Option<String>,
+    #[schemars(description = "Worktree sync strategy: \"rebase\", \"merge\", or \"ff-only\"")]
+    pub sync_strategy: Option<String>,
 }
 
 #[derive(Debug, Deserialize, schemars::JsonSchema)]
@@ -148,6 +158,13 @@ pub struct McpServerInput {
     #[serde(default)]
     #[schemars(description = "Environment variables (key-value pairs)")]
     pub env: HashMap<String, String>,
+    #[serde(default = "default_true")]
+    #[schemars(description = "Whether sessions get this server (default: true)")]
+    pub enabled: bool,
+}
+
+fn default_true() -> bool {
+    true
 }
 
 #[derive(Debug, Deserialize, schemars::JsonSchema)]
@@ -168,6 +185,11 @@ pub struct ProjectResponse {
     pub roles: Vec<RoleResponse>,
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub mcp_servers: Vec<McpServerResponse>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sync_base_branch: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remote: Option<String>,
+    pub sync_strategy: String,
 }
 
 #[derive(Debug, Serialize)]
@@ -196,6 +218,7 @@ pub struct McpServerResponse {
     pub args: Vec<String>,
     #[serde(skip_serializing_if = "HashMap::is_empty")]
     pub env: HashMap<String, String>,
+    pub enabled: bool,
 }
 
 #[derive(Debug, Serialize)]
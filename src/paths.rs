@@ -13,6 +13,8 @@
 //! By default, uses XDG Base Directory Specification:
 //! - Prefers `$XDG_CONFIG_HOME` for config, fallback to `$HOME/.config`
 //! - Prefers `$XDG_DATA_HOME` for data, fallback to `$HOME/.local/share`
+//! - Empty or relative values are ignored; with nothing usable left the path
+//!   is unresolved and `main` refuses to start (see [`require`])
 //!
 //! ## Testing Behavior
 //!
@@ -30,6 +32,8 @@
 //! ```
 
 use std::cell::RefCell;
+use std::ffi::OsString;
+use std::fmt;
 use std::path::{Path, PathBuf};
 
 /// Returns "thurbox-dev" for dev builds, "thurbox" for release builds.
@@ -52,6 +56,8 @@ pub enum PathKind {
     Database,
     /// Admin session directory: `~/.local/share/thurbox/admin/`
     AdminDir,
+    /// Database backups: `~/.local/share/thurbox/backups/`
+    BackupDir,
 }
 
 /// Path resolution strategy (thread-local).
@@ -85,77 +91,33 @@ pub fn resolve(kind: PathKind) -> Option<PathBuf> {
 
 /// Resolve a path using XDG Base Directory Specification.
 fn resolve_xdg(kind: PathKind) -> Option<PathBuf> {
-    match kind {
-        PathKind::Config => {
-            // Prefer $XDG_CONFIG_HOME, fall back to $HOME/.config
-            if let Some(xdg) = std::env::var_os("XDG_CONFIG_HOME") {
-                let mut p = PathBuf::from(xdg);
-                p.push(app_dir_name());
-                p.push("config.toml");
-                return Some(p);
-            }
-
-            std::env::var_os("HOME").map(|h| {
-                let mut p = PathBuf::from(h);
-                p.push(".config");
-                p.push(app_dir_name());
-                p.push("config.toml");
-                p
-            })
-        }
-        PathKind::Database => {
-            // Prefer $XDG_DATA_HOME, fall back to $HOME/.local/share
-            if let Some(xdg) = std::env::var_os("XDG_DATA_HOME") {
-                let mut p = PathBuf::from(xdg);
-                p.push(app_dir_name());
-                p.push("thurbox.db");
-                return Some(p);
-            }
-
-            std::env::var_os("HOME").map(|h| {
-                let mut p = PathBuf::from(h);
-                p.push(".local");
-                p.push("share");
-                p.push(app_dir_name());
-                p.push("thurbox.db");
-                p
-            })
-        }
-        PathKind::LogDir => {
-            // Prefer $XDG_DATA_HOME, fall back to $HOME/.local/share
-            if let Some(xdg) = std::env::var_os("XDG_DATA_HOME") {
-                let mut p = PathBuf::from(xdg);
-                p.push(app_dir_name());
-                return Some(p);
-            }
-
-            std::env::var_os("HOME").map(|h| {
-                let mut p = PathBuf::from(h);
-                p.push(".local");
-                p.push("share");
-                p.push(app_dir_name());
-                p
-            })
-        }
-        PathKind::AdminDir => {
-            // Prefer $XDG_DATA_HOME, fall back to $HOME/.local/share
-            if let Some(xdg) = std::env::var_os("XDG_DATA_HOME") {
-                let mut p = PathBuf::from(xdg);
-                p.push(app_dir_name());
-                p.push("admin");
-                return Some(p);
-            }
+    resolve_xdg_with(kind, |var| std::env::var_os(var))
+}
 
-            std::env::var_os("HOME").map(|h| {
-                let mut p = PathBuf::from(h);
-                p.push(".local");
-                p.push("share");
-                p.push(app_dir_name());
-                p.push("admin");
-                p
-            })
+/// [`resolve_xdg`] with environment lookups going through `env`.
+///
+/// Empty and relative values are ignored, as the XDG spec requires:
+/// honoring them would put the database under whatever directory thurbox
+/// was launched from.
+fn resolve_xdg_with(kind: PathKind, env: impl Fn(&str) -> Option<OsString>) -> Option<PathBuf> {
+    let absolute = |var: &str| env(var).map(PathBuf::from).filter(|p| p.is_absolute());
+    let base = match kind {
+        // Prefer $XDG_CONFIG_HOME, fall back to $HOME/.config
+        PathKind::Config => {
+            absolute("XDG_CONFIG_HOME").or_else(|| absolute("HOME").map(|h| h.join(".config")))?
         }
-    }
+        // Prefer $XDG_DATA_HOME, fall back to $HOME/.local/share
+        _ => absolute("XDG_DATA_HOME")
+            .or_else(|| absolute("HOME").map(|h| h.join(".local").join("share")))?,
+    };
+    let dir = base.join(app_dir_name());
+    Some(match kind {
+        PathKind::Config => dir.join("config.toml"),
+        PathKind::LogDir => dir,
+        PathKind::Database => dir.join("thurbox.db"),
+        PathKind::AdminDir => dir.join("admin"),
+        PathKind::BackupDir => dir.join("backups"),
+    })
 }
 
 /// Resolve a path using a custom base directory (for testing).
@@ -165,9 +127,38 @@ fn resolve_override(base: &Path, kind: PathKind) -> PathBuf {
         PathKind::LogDir => base.to_path_buf(),
         PathKind::Database => base.join("thurbox.db"),
         PathKind::AdminDir => base.join("admin"),
+        PathKind::BackupDir => base.join("backups"),
     }
 }
 
+/// A path that could not be resolved because neither the XDG variable
+/// nor `$HOME` is set to an absolute directory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnresolvedPath(pub PathKind);
+
+impl fmt::Display for UnresolvedPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (what, xdg) = match self.0 {
+            PathKind::Config => ("config file", "XDG_CONFIG_HOME"),
+            PathKind::LogDir => ("log directory", "XDG_DATA_HOME"),
+            PathKind::Database => ("database", "XDG_DATA_HOME"),
+            PathKind::AdminDir => ("admin directory", "XDG_DATA_HOME"),
+            PathKind::BackupDir => ("backup directory", "XDG_DATA_HOME"),
+        };
+        write!(
+            f,
+            "Cannot resolve the {what} path: set $HOME or ${xdg} to an absolute directory"
+        )
+    }
+}
+
+impl std::error::Error for UnresolvedPath {}
+
+/// Like [`resolve`], for paths thurbox cannot run without.
+pub fn require(kind: PathKind) -> Result<PathBuf, UnresolvedPath> {
+    resolve(kind).ok_or(UnresolvedPath(kind))
+}
+
 /// Resolve the config file path.
 ///
 /// Returns: `$XDG_CONFIG_HOME/thurbox/config.toml` or `$HOME/.config/thurbox/config.toml`
@@ -196,6 +187,13 @@ pub fn admin_directory() -> Option<PathBuf> {
     resolve(PathKind::AdminDir)
 }
 
+/// Resolve the database backup directory path.
+///
+/// Returns: `$XDG_DATA_HOME/thurbox/backups/` or `$HOME/.local/share/thurbox/backups/`
+pub fn backup_directory() -> Option<PathBuf> {
+    resolve(PathKind::BackupDir)
+}
+
 /// Resolve the path to the `thurbox-mcp` binary.
 ///
 /// Checks for a sibling of `current_exe()` first (works for both installed and dev builds),
@@ -210,6 +208,14 @@ pub fn thurbox_mcp_binary() -> String {
     "thurbox-mcp".to_string()
 }
 
+/// Whether `path` exists and is a directory.
+///
+/// Checked before spawning into a session's working directory, which may
+/// have been deleted while Thurbox was not looking.
+pub fn dir_exists(path: &Path) -> bool {
+    path.is_dir()
+}
+
 /// Override path resolution for all paths to use a custom base directory.
 ///
 /// This is primarily intended for testing. All paths will resolve under the given base:
@@ -429,6 +435,7 @@ mod tests {
         assert_eq!(resolve(PathKind::LogDir), Some(base.clone()));
         assert_eq!(resolve(PathKind::Database), Some(base.join("thurbox.db")));
         assert_eq!(resolve(PathKind::AdminDir), Some(base.join("admin")));
+        assert_eq!(resolve(PathKind::BackupDir), Some(base.join("backups")));
 
         reset_to_xdg();
     }
@@ -477,6 +484,17 @@ mod tests {
         reset_to_xdg();
     }
 
+    #[test]
+    fn backup_directory_convenience() {
+        let base = PathBuf::from("/custom");
+        set_test_dir(&base);
+
+        let path = backup_directory().unwrap();
+        assert_eq!(path, base.join("backups"));
+
+        reset_to_xdg();
+    }
+
     #[test]
     fn thurbox_mcp_binary_returns_string() {
         // Without a sibling binary, falls back to bare name for $PATH lookup
@@ -561,6 +579,96 @@ mod tests {
         );
     }
 
+    fn env(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<OsString> {
+        let vars: Vec<(String, OsString)> = vars
+            .iter()
+            .map(|(k, v)| (k.to_string(), OsString::from(v)))
+            .collect();
+        move |var| vars.iter().find(|(k, _)| k == var).map(|(_, v)| v.clone())
+    }
+
+    #[test]
+    fn xdg_data_home_takes_precedence_over_home() {
+        let vars = env(&[("HOME", "/home/me"), ("XDG_DATA_HOME", "/data")]);
+        assert_eq!(
+            resolve_xdg_with(PathKind::Database, &vars),
+            Some(
+                PathBuf::from("/data")
+                    .join(app_dir_name())
+                    .join("thurbox.db")
+            )
+        );
+        assert_eq!(
+            resolve_xdg_with(PathKind::Config, &vars),
+            Some(
+                PathBuf::from("/home/me/.config")
+                    .join(app_dir_name())
+                    .join("config.toml")
+            )
+        );
+    }
+
+    #[test]
+    fn home_is_used_without_xdg_vars() {
+        let vars = env(&[("HOME", "/home/me")]);
+        let data = PathBuf::from("/home/me/.local/share").join(app_dir_name());
+        assert_eq!(
+            resolve_xdg_with(PathKind::LogDir, &vars),
+            Some(data.clone())
+        );
+        assert_eq!(
+            resolve_xdg_with(PathKind::BackupDir, &vars),
+            Some(data.join("backups"))
+        );
+    }
+
+    #[test]
+    fn unset_empty_or_relative_vars_do_not_resolve() {
+        for vars in [
+            env(&[]),
+            env(&[("HOME", "")]),
+            env(&[("HOME", "relative/home"), ("XDG_DATA_HOME", "")]),
+        ] {
+            assert_eq!(resolve_xdg_with(PathKind::Database, &vars), None);
+            assert_eq!(resolve_xdg_with(PathKind::LogDir, &vars), None);
+        }
+        // A relative XDG variable falls through to HOME.
+        let vars = env(&[("HOME", "/home/me"), ("XDG_DATA_HOME", "data")]);
+        assert_eq!(
+            resolve_xdg_with(PathKind::AdminDir, &vars),
+            Some(
+                PathBuf::from("/home/me/.local/share")
+                    .join(app_dir_name())
+                    .join("admin")
+            )
+        );
+    }
+
+    #[test]
+    fn require_names_the_missing_variable() {
+        let err = UnresolvedPath(PathKind::Database);
+        assert_eq!(
+            err.to_string(),
+            "Cannot resolve the database path: set $HOME or $XDG_DATA_HOME to an absolute directory"
+        );
+        let _guard = TestPathGuard::new("/base");
+        assert_eq!(
+            require(PathKind::Database),
+            Ok(PathBuf::from("/base/thurbox.db"))
+        );
+    }
+
+    #[test]
+    fn dir_exists_only_for_directories() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let file = tmp.path().join("file.txt");
+        std::fs::write(&file, "x").unwrap();
+
+        assert!(dir_exists(tmp.path()));
+        assert!(!dir_exists(&file));
+        assert!(!dir_exists(&tmp.path().join("gone")));
+    }
+
     #[test]
     fn longest_common_prefix_empty() {
         assert_eq!(longest_common_prefix(&[]), "");
@@ -6,6 +6,9 @@ use uuid::Uuid;
 
 use crate::session::{McpServerConfig, RoleConfig, SessionId};
 
+/// Remote worktree sync fetches from unless a project sets its own.
+pub const DEFAULT_REMOTE: &str = "origin";
+
 // Keep serde on ProjectId for backward compat (used in session/mod.rs serialization)
 
 /// Namespace UUID for deriving deterministic project IDs.
@@ -28,6 +31,11 @@ impl ProjectId {
     pub fn from_uuid(uuid: Uuid) -> Self {
         Self(uuid)
     }
+
+    /// The deterministic ID of a project named `name`.
+    pub fn from_name(name: &str) -> Self {
+        Self(Uuid::new_v5(&PROJECT_ID_NAMESPACE, name.as_bytes()))
+    }
 }
 
 impl Default for ProjectId {
@@ -42,12 +50,91 @@ impl fmt::Display for ProjectId {
     }
 }
 
+/// How worktree sync brings a branch up to date with its base branch.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SyncStrategy {
+    /// `git rebase origin/<base>` — linear history, rewrites local commits.
+    #[default]
+    Rebase,
+    /// `git merge origin/<base>` — keeps pushed commits intact.
+    Merge,
+    /// `git merge --ff-only origin/<base>` — never creates commits.
+    FastForwardOnly,
+}
+
+impl SyncStrategy {
+    pub const ALL: [SyncStrategy; 3] = [Self::Rebase, Self::Merge, Self::FastForwardOnly];
+
+    /// Stable identifier used for persistence.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Rebase => "rebase",
+            Self::Merge => "merge",
+            Self::FastForwardOnly => "ff-only",
+        }
+    }
+
+    /// The next strategy in [`Self::ALL`], wrapping around.
+    pub fn next(self) -> Self {
+        let idx = Self::ALL.iter().position(|s| *s == self).unwrap_or(0);
+        Self::ALL[(idx + 1) % Self::ALL.len()]
+    }
+
+    /// The previous strategy in [`Self::ALL`], wrapping around.
+    pub fn prev(self) -> Self {
+        let idx = Self::ALL.iter().position(|s| *s == self).unwrap_or(0);
+        Self::ALL[(idx + Self::ALL.len() - 1) % Self::ALL.len()]
+    }
+}
+
+impl fmt::Display for SyncStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            Self::Rebase => "Rebase",
+            Self::Merge => "Merge",
+            Self::FastForwardOnly => "Fast-forward only",
+        };
+        write!(f, "{label}")
+    }
+}
+
+impl std::str::FromStr for SyncStrategy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|strategy| strategy.as_str() == s)
+            .ok_or_else(|| format!("unknown sync strategy '{s}'"))
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct ProjectConfig {
     pub name: String,
     pub repos: Vec<PathBuf>,
     pub roles: Vec<RoleConfig>,
     pub mcp_servers: Vec<McpServerConfig>,
+    /// Branch that worktree sync rebases onto. When `None`, the repo's
+    /// default branch is detected.
+    pub sync_base_branch: Option<String>,
+    /// Git remote that worktree sync fetches and integrates from. When
+    /// `None`, `origin` is used.
+    pub remote: Option<String>,
+    /// How worktree sync integrates the base branch.
+    pub sync_strategy: SyncStrategy,
+    /// Milliseconds without output before a session flips from Busy to
+    /// Waiting. When `None`, the app-wide default applies.
+    pub activity_timeout_ms: Option<u64>,
+    /// Directory worktrees are created under, as
+    /// `<root>/<repo-name>/<branch>`. Relative paths resolve against each
+    /// repo. When `None`, worktrees live in `<repo>/.git/thurbox-worktrees`.
+    pub worktree_root: Option<PathBuf>,
+    /// Role auto-assigned to new sessions. When `None` and the project has
+    /// several roles, the role selector is shown.
+    pub default_role: Option<String>,
+    /// Typed into every new session right after it spawns (e.g. `nvm use`).
+    pub session_init_command: Option<String>,
     /// Stable project ID preserved across renames. When present, this takes
     /// precedence over the name-derived deterministic ID.
     pub id: Option<String>,
@@ -60,7 +147,17 @@ impl ProjectConfig {
     /// the same project name always produces the same ID across instances.
     /// This is critical for multi-instance session synchronization.
     pub fn deterministic_id(&self) -> ProjectId {
-        ProjectId(Uuid::new_v5(&PROJECT_ID_NAMESPACE, self.name.as_bytes()))
+        ProjectId::from_name(&self.name)
+    }
+
+    /// The git remote worktree sync uses: `remote`, or [`DEFAULT_REMOTE`]
+    /// when unset or blank.
+    pub fn effective_remote(&self) -> &str {
+        self.remote
+            .as_deref()
+            .map(str::trim)
+            .filter(|r| !r.is_empty())
+            .unwrap_or(DEFAULT_REMOTE)
     }
 
     /// Return the effective project ID: the persisted `id` if present,
@@ -111,6 +208,25 @@ impl ProjectInfo {
 mod tests {
     use super::*;
 
+    #[test]
+    fn sync_strategy_cycles_through_all() {
+        let mut strategy = SyncStrategy::default();
+        assert_eq!(strategy, SyncStrategy::Rebase);
+        for _ in 0..SyncStrategy::ALL.len() {
+            assert_eq!(strategy.next().prev(), strategy);
+            strategy = strategy.next();
+        }
+        assert_eq!(strategy, SyncStrategy::Rebase);
+    }
+
+    #[test]
+    fn sync_strategy_str_roundtrip() {
+        for strategy in SyncStrategy::ALL {
+            assert_eq!(strategy.as_str().parse::<SyncStrategy>(), Ok(strategy));
+        }
+        assert!("squash".parse::<SyncStrategy>().is_err());
+    }
+
     #[test]
     fn project_id_display_is_uuid_format() {
         let id = ProjectId::default();
@@ -133,6 +249,13 @@ mod tests {
             repos: vec![PathBuf::from("/tmp/test")],
             roles: Vec::new(),
             mcp_servers: Vec::new(),
+            sync_base_branch: None,
+            remote: None,
+            sync_strategy: SyncStrategy::default(),
+            activity_timeout_ms: None,
+            worktree_root: None,
+            default_role: None,
+            session_init_command: None,
             id: None,
         };
         let info = ProjectInfo::new(config);
@@ -148,6 +271,13 @@ mod tests {
             repos: vec![PathBuf::from("/admin")],
             roles: Vec::new(),
             mcp_servers: Vec::new(),
+            sync_base_branch: None,
+            remote: None,
+            sync_strategy: SyncStrategy::default(),
+            activity_timeout_ms: None,
+            worktree_root: None,
+            default_role: None,
+            session_init_command: None,
             id: None,
         };
         let info = ProjectInfo::new_admin(config);
@@ -163,6 +293,13 @@ mod tests {
             repos: vec![PathBuf::from("/repo1"), PathBuf::from("/repo2")],
             roles: Vec::new(),
             mcp_servers: Vec::new(),
+            sync_base_branch: None,
+            remote: None,
+            sync_strategy: SyncStrategy::default(),
+            activity_timeout_ms: None,
+            worktree_root: None,
+            default_role: None,
+            session_init_command: None,
             id: None,
         };
 
@@ -179,6 +316,13 @@ mod tests {
             repos: vec![PathBuf::from("/repo")],
             roles: Vec::new(),
             mcp_servers: Vec::new(),
+            sync_base_branch: None,
+            remote: None,
+            sync_strategy: SyncStrategy::default(),
+            activity_timeout_ms: None,
+            worktree_root: None,
+            default_role: None,
+            session_init_command: None,
             id: None,
         };
         let config2 = ProjectConfig {
@@ -186,6 +330,13 @@ mod tests {
             repos: vec![PathBuf::from("/repo")],
             roles: Vec::new(),
             mcp_servers: Vec::new(),
+            sync_base_branch: None,
+            remote: None,
+            sync_strategy: SyncStrategy::default(),
+            activity_timeout_ms: None,
+            worktree_root: None,
+            default_role: None,
+            session_init_command: None,
             id: None,
         };
 
@@ -199,6 +350,13 @@ mod tests {
             repos: vec![PathBuf::from("/repo")],
             roles: Vec::new(),
             mcp_servers: Vec::new(),
+            sync_base_branch: None,
+            remote: None,
+            sync_strategy: SyncStrategy::default(),
+            activity_timeout_ms: None,
+            worktree_root: None,
+            default_role: None,
+            session_init_command: None,
             id: None,
         };
 
@@ -213,6 +371,13 @@ mod tests {
             repos: vec![PathBuf::from("/shared/repo")],
             roles: Vec::new(),
             mcp_servers: Vec::new(),
+            sync_base_branch: None,
+            remote: None,
+            sync_strategy: SyncStrategy::default(),
+            activity_timeout_ms: None,
+            worktree_root: None,
+            default_role: None,
+            session_init_command: None,
             id: None,
         };
 
@@ -229,6 +394,13 @@ mod tests {
             repos: vec![PathBuf::from("/repo")],
             roles: Vec::new(),
             mcp_servers: Vec::new(),
+            sync_base_branch: None,
+            remote: None,
+            sync_strategy: SyncStrategy::default(),
+            activity_timeout_ms: None,
+            worktree_root: None,
+            default_role: None,
+            session_init_command: None,
             id: None,
         };
         assert_eq!(config.effective_id(), config.deterministic_id());
@@ -241,6 +413,13 @@ mod tests {
             repos: vec![],
             roles: Vec::new(),
             mcp_servers: Vec::new(),
+            sync_base_branch: None,
+            remote: None,
+            sync_strategy: SyncStrategy::default(),
+            activity_timeout_ms: None,
+            worktree_root: None,
+            default_role: None,
+            session_init_command: None,
             id: None,
         };
         let original_id = original_config.deterministic_id();
@@ -250,6 +429,13 @@ mod tests {
             repos: vec![],
             roles: Vec::new(),
             mcp_servers: Vec::new(),
+            sync_base_branch: None,
+            remote: None,
+            sync_strategy: SyncStrategy::default(),
+            activity_timeout_ms: None,
+            worktree_root: None,
+            default_role: None,
+            session_init_command: None,
             id: Some(original_id.to_string()),
         };
 
@@ -8,6 +8,13 @@ use uuid::Uuid;
 /// Default role name assigned when no explicit role is configured.
 pub const DEFAULT_ROLE_NAME: &str = "developer";
 
+/// Restarts tolerated within [`CRASH_LOOP_WINDOW_MS`] before a session is
+/// flagged as crash looping.
+pub const CRASH_LOOP_MAX_RESTARTS: usize = 3;
+
+/// Sliding window (millis) used for crash-loop detection.
+pub const CRASH_LOOP_WINDOW_MS: u64 = 60_000;
+
 /// Validated role name type that prevents invalid states.
 /// Role names must be non-empty and at most 64 characters.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
@@ -127,6 +134,92 @@ pub struct RoleConfig {
     pub permissions: RolePermissions,
 }
 
+/// Built-in Claude Code tool names. Used to catch miscapitalized entries;
+/// names outside this list are still accepted so new tools work without
+/// a Thurbox update.
+pub const KNOWN_TOOLS: &[&str] = &[
+    "Bash",
+    "BashOutput",
+    "Edit",
+    "ExitPlanMode",
+    "Glob",
+    "Grep",
+    "KillShell",
+    "LS",
+    "MultiEdit",
+    "NotebookEdit",
+    "NotebookRead",
+    "Read",
+    "SlashCommand",
+    "Task",
+    "TodoWrite",
+    "WebFetch",
+    "WebSearch",
+    "Write",
+];
+
+/// Check that an allowed/disallowed tool entry is well formed.
+///
+/// Accepts a bare tool name (`Read`, `mcp__github__create_issue`) or a
+/// scoped `Tool(arg)` pattern (`Bash(git:*)`) whose parentheses balance and
+/// close at the end. Unknown names are allowed; a name that differs from a
+/// [`KNOWN_TOOLS`] entry only by case is rejected.
+pub fn validate_tool_pattern(pattern: &str) -> Result<(), String> {
+    let pattern = pattern.trim();
+    if pattern.is_empty() {
+        return Err("Tool pattern cannot be empty".to_string());
+    }
+
+    let (name, arg) = match pattern.find('(') {
+        Some(open) => (&pattern[..open], Some(&pattern[open + 1..])),
+        None => (pattern, None),
+    };
+
+    if name.is_empty() {
+        return Err(format!("Missing tool name in '{pattern}'"));
+    }
+    if !name.starts_with(|c: char| c.is_ascii_alphabetic())
+        || !name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+    {
+        return Err(format!("Invalid tool name '{name}'"));
+    }
+    if let Some(known) = KNOWN_TOOLS
+        .iter()
+        .find(|known| **known != name && known.eq_ignore_ascii_case(name))
+    {
+        return Err(format!("Unknown tool '{name}', did you mean '{known}'?"));
+    }
+
+    let Some(arg) = arg else {
+        return Ok(());
+    };
+
+    // `arg` is everything after the first '(' and must end with the ')'
+    // that closes it.
+    let mut depth = 1usize;
+    for (i, c) in arg.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => {
+                depth -= 1;
+                if depth == 0 {
+                    if i + 1 != arg.len() {
+                        return Err(format!("Unexpected text after ')' in '{pattern}'"));
+                    }
+                    if i == 0 {
+                        return Err(format!("Empty argument in '{pattern}'"));
+                    }
+                    return Ok(());
+                }
+            }
+            _ => {}
+        }
+    }
+    Err(format!("Unbalanced parentheses in '{pattern}'"))
+}
+
 /// MCP server configuration matching Claude Code's `mcpServers` format.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct McpServerConfig {
@@ -136,6 +229,50 @@ pub struct McpServerConfig {
     pub args: Vec<String>,
     #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub env: HashMap<String, String>,
+    /// Disabled servers stay in the project but are left out of the
+    /// generated `--mcp-config` file.
+    #[serde(default = "default_enabled", skip_serializing_if = "is_enabled")]
+    pub enabled: bool,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn is_enabled(enabled: &bool) -> bool {
+    *enabled
+}
+
+/// Layer a session's extra MCP servers over its project's. A session server
+/// replaces the project server with the same name in place; new names are
+/// appended.
+pub fn merge_mcp_servers(
+    project: &[McpServerConfig],
+    session: &[McpServerConfig],
+) -> Vec<McpServerConfig> {
+    let mut merged = project.to_vec();
+    for server in session {
+        match merged.iter_mut().find(|s| s.name == server.name) {
+            Some(slot) => *slot = server.clone(),
+            None => merged.push(server.clone()),
+        }
+    }
+    merged
+}
+
+/// Check that `id` looks like a Claude conversation id: a hyphenated UUID
+/// (`xxxxxxxx-xxxx-xxxx-xxxx-xxxxxxxxxxxx`), as passed to `--resume`.
+pub fn validate_claude_session_id(id: &str) -> Result<(), String> {
+    let id = id.trim();
+    if id.is_empty() {
+        return Err("Session id cannot be empty".to_string());
+    }
+    if id.len() != 36 || Uuid::try_parse(id).is_err() {
+        return Err(format!(
+            "'{id}' is not a session id (xxxxxxxx-xxxx-xxxx-xxxx-xxxxxxxxxxxx)"
+        ));
+    }
+    Ok(())
 }
 
 #[derive(Debug, Clone)]
@@ -143,6 +280,19 @@ pub struct WorktreeInfo {
     pub repo_path: PathBuf,
     pub worktree_path: PathBuf,
     pub branch: String,
+    /// Branch this worktree syncs onto; empty when unknown.
+    pub base_branch: String,
+}
+
+/// Live git state of a worktree, shown in the info panel.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WorktreeStatus {
+    /// Checked-out branch; `None` when HEAD is detached.
+    pub branch: Option<String>,
+    /// Commits `(ahead, behind)` the upstream branch, if one is set.
+    pub ahead_behind: Option<(u32, u32)>,
+    /// Changed, unmerged and untracked paths.
+    pub dirty_files: usize,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -171,18 +321,45 @@ impl std::str::FromStr for SessionId {
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum SessionStatus {
     Busy,
+    /// Quiet, and Claude's screen shows a question or permission prompt.
+    NeedsInput,
     Waiting,
     Idle,
     Error,
+    /// Restored lazily and not yet attached to its backend session.
+    Dormant,
 }
 
 impl SessionStatus {
+    pub const ALL: [SessionStatus; 6] = [
+        Self::Busy,
+        Self::NeedsInput,
+        Self::Waiting,
+        Self::Idle,
+        Self::Error,
+        Self::Dormant,
+    ];
+
+    /// Stable identifier used for persistence.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Busy => "busy",
+            Self::NeedsInput => "needs-input",
+            Self::Waiting => "waiting",
+            Self::Idle => "idle",
+            Self::Error => "error",
+            Self::Dormant => "dormant",
+        }
+    }
+
     pub fn icon(self) -> &'static str {
         match self {
             Self::Busy => "●",
+            Self::NeedsInput => "◆",
             Self::Waiting => "◉",
             Self::Idle => "○",
             Self::Error => "✗",
+            Self::Dormant => "◌",
         }
     }
 }
@@ -191,13 +368,26 @@ impl fmt::Display for SessionStatus {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Self::Busy => write!(f, "Busy"),
+            Self::NeedsInput => write!(f, "Input"),
             Self::Waiting => write!(f, "Waiting"),
             Self::Idle => write!(f, "Idle"),
             Self::Error => write!(f, "Error"),
+            Self::Dormant => write!(f, "Dormant"),
         }
     }
 }
 
+impl std::str::FromStr for SessionStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|status| status.as_str() == s)
+            .ok_or_else(|| format!("unknown session status '{s}'"))
+    }
+}
+
 pub struct SessionInfo {
     pub id: SessionId,
     pub name: String,
@@ -209,6 +399,40 @@ pub struct SessionInfo {
     pub additional_dirs: Vec<PathBuf>,
     pub backend_id: Option<String>,
     pub shell_backend_id: Option<String>,
+    /// Total restarts since the session was spawned.
+    pub restart_count: u32,
+    /// Timestamps (millis) of restarts inside the crash-loop window.
+    pub recent_restarts: Vec<u64>,
+    /// Set once restarts exceed [`CRASH_LOOP_MAX_RESTARTS`] within the window.
+    /// Automatic restarts are suppressed until the user resets it.
+    pub crash_looping: bool,
+    /// The working directory was gone at restore, so the session was left
+    /// unspawned in `Error` status until it is restarted.
+    pub cwd_missing: bool,
+    /// The backend pane was found dead when the session was focused; the
+    /// session stays in `Error` status until it is restarted.
+    pub backend_dead: bool,
+    /// Whether raw output should be teed to a log file.
+    pub log_output: bool,
+    /// Log file currently receiving output, if logging is active.
+    pub log_path: Option<PathBuf>,
+    /// Session-only MCP servers layered over the project's at spawn.
+    pub extra_mcp_servers: Vec<McpServerConfig>,
+    /// Pinned sessions are listed before the rest of their project's.
+    pub is_pinned: bool,
+    /// Short tag shown before the name in the session list.
+    pub label: Option<String>,
+    /// Color of the label dot, as a color name or `#rrggbb`.
+    pub label_color: Option<String>,
+    /// When the session was first spawned (millis since epoch); `None` for
+    /// sessions that predate the timestamp being recorded.
+    pub created_at: Option<u64>,
+    /// Window title last set by the session's output (OSC 0/2); not
+    /// persisted.
+    pub title: Option<String>,
+    /// Ticks left to highlight a terminal bell in the session list; 0 when
+    /// no bell rang recently.
+    pub bell_ticks: u16,
 }
 
 impl SessionInfo {
@@ -224,8 +448,42 @@ impl SessionInfo {
             additional_dirs: Vec::new(),
             backend_id: None,
             shell_backend_id: None,
+            restart_count: 0,
+            recent_restarts: Vec::new(),
+            crash_looping: false,
+            cwd_missing: false,
+            backend_dead: false,
+            log_output: false,
+            log_path: None,
+            extra_mcp_servers: Vec::new(),
+            is_pinned: false,
+            label: None,
+            label_color: None,
+            created_at: None,
+            title: None,
+            bell_ticks: 0,
         }
     }
+
+    /// Record a restart at `now_ms` and re-evaluate crash-loop state.
+    ///
+    /// Returns `true` if the session is now considered crash looping.
+    pub fn record_restart(&mut self, now_ms: u64) -> bool {
+        self.restart_count = self.restart_count.saturating_add(1);
+        self.recent_restarts
+            .retain(|&t| now_ms.saturating_sub(t) < CRASH_LOOP_WINDOW_MS);
+        self.recent_restarts.push(now_ms);
+        if self.recent_restarts.len() > CRASH_LOOP_MAX_RESTARTS {
+            self.crash_looping = true;
+        }
+        self.crash_looping
+    }
+
+    /// Clear crash-loop state so the session can be restarted again.
+    pub fn reset_crash_loop(&mut self) {
+        self.recent_restarts.clear();
+        self.crash_looping = false;
+    }
 }
 
 /// A queued command for a session, inserted by MCP and processed by the TUI.
@@ -237,19 +495,168 @@ pub struct SessionCommand {
     pub created_at: u64,
 }
 
+/// What a session runs.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SessionKind {
+    /// The `claude` CLI, with role permissions and `--resume`.
+    #[default]
+    Claude,
+    /// The user's `$SHELL`, for running commands by hand in the session's
+    /// cwd or worktree.
+    Shell,
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct SessionConfig {
+    pub kind: SessionKind,
     pub resume_session_id: Option<String>,
     pub claude_session_id: Option<String>,
     pub cwd: Option<PathBuf>,
     pub additional_dirs: Vec<PathBuf>,
     pub role: String,
     pub permissions: RolePermissions,
+    /// Tee the session's raw PTY output to a log file.
+    pub log_output: bool,
+    /// MCP servers for this session only, merged over the project's with
+    /// [`merge_mcp_servers`].
+    pub extra_mcp_servers: Vec<McpServerConfig>,
+    /// Generated MCP config file passed as `--mcp-config`.
+    pub mcp_config: Option<PathBuf>,
+    /// Program and arguments run instead of `claude` (and its flags) for a
+    /// [`SessionKind::Claude`] session, e.g. a configured admin command.
+    pub command: Option<(String, Vec<String>)>,
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+
+    fn mcp(name: &str, command: &str) -> McpServerConfig {
+        McpServerConfig {
+            name: name.to_string(),
+            command: command.to_string(),
+            args: Vec::new(),
+            env: HashMap::new(),
+            enabled: true,
+        }
+    }
+
+    #[test]
+    fn validate_claude_session_id_accepts_hyphenated_uuids() {
+        for id in [
+            "0b5e3a8c-1f2d-4e6a-9c7b-8d9e0f1a2b3c",
+            "0B5E3A8C-1F2D-4E6A-9C7B-8D9E0F1A2B3C",
+            "  0b5e3a8c-1f2d-4e6a-9c7b-8d9e0f1a2b3c ",
+        ] {
+            assert_eq!(validate_claude_session_id(id), Ok(()), "{id}");
+        }
+    }
+
+    #[test]
+    fn validate_claude_session_id_rejects_other_formats() {
+        for id in [
+            "",
+            "   ",
+            "not-a-session",
+            "0b5e3a8c1f2d4e6a9c7b8d9e0f1a2b3c",
+            "{0b5e3a8c-1f2d-4e6a-9c7b-8d9e0f1a2b3c}",
+            "0b5e3a8c-1f2d-4e6a-9c7b-8d9e0f1a2b3g",
+        ] {
+            assert!(validate_claude_session_id(id).is_err(), "{id}");
+        }
+    }
+
+    #[test]
+    fn merge_mcp_servers_session_overrides_project() {
+        let project = vec![mcp("github", "gh-mcp"), mcp("db", "pg-mcp")];
+        let session = vec![mcp("debug", "dbg-mcp"), mcp("github", "gh-mcp-dev")];
+        let merged = merge_mcp_servers(&project, &session);
+        assert_eq!(
+            merged,
+            vec![
+                mcp("github", "gh-mcp-dev"),
+                mcp("db", "pg-mcp"),
+                mcp("debug", "dbg-mcp"),
+            ]
+        );
+    }
+
+    #[test]
+    fn merge_mcp_servers_empty_sides() {
+        let servers = vec![mcp("a", "x")];
+        assert_eq!(merge_mcp_servers(&servers, &[]), servers);
+        assert_eq!(merge_mcp_servers(&[], &servers), servers);
+        assert!(merge_mcp_servers(&[], &[]).is_empty());
+    }
+
+    #[test]
+    fn tool_pattern_valid() {
+        for pattern in [
+            "Read",
+            "Bash(git:*)",
+            "Bash(cargo test:*)",
+            "Read(./src/**)",
+            "WebFetch(domain:example.com)",
+            "Bash(echo (nested))",
+            "mcp__github__create_issue",
+            "mcp__my-server",
+            "  Edit  ",
+            // Unknown but well formed: kept for forward compatibility
+            "FutureTool",
+            "FutureTool(scope)",
+        ] {
+            assert_eq!(validate_tool_pattern(pattern), Ok(()), "{pattern}");
+        }
+    }
+
+    #[test]
+    fn tool_pattern_unbalanced_parens() {
+        for pattern in ["Bash(git:*", "Bash(echo (x)", "Bash(git:*))", "Read)"] {
+            assert!(validate_tool_pattern(pattern).is_err(), "{pattern}");
+        }
+        assert_eq!(
+            validate_tool_pattern("Bash(git:*"),
+            Err("Unbalanced parentheses in 'Bash(git:*'".to_string())
+        );
+    }
+
+    #[test]
+    fn tool_pattern_trailing_text_after_close() {
+        assert_eq!(
+            validate_tool_pattern("Bash(git:*)x"),
+            Err("Unexpected text after ')' in 'Bash(git:*)x'".to_string())
+        );
+    }
+
+    #[test]
+    fn tool_pattern_empty_parts() {
+        assert!(validate_tool_pattern("").is_err());
+        assert!(validate_tool_pattern("   ").is_err());
+        assert_eq!(
+            validate_tool_pattern("(git:*)"),
+            Err("Missing tool name in '(git:*)'".to_string())
+        );
+        assert_eq!(
+            validate_tool_pattern("Bash()"),
+            Err("Empty argument in 'Bash()'".to_string())
+        );
+    }
+
+    #[test]
+    fn tool_pattern_invalid_name_characters() {
+        for pattern in ["Bash git", "1Read", "Re.ad", "Bash git(x)"] {
+            assert!(validate_tool_pattern(pattern).is_err(), "{pattern}");
+        }
+    }
+
+    #[test]
+    fn tool_pattern_miscapitalized_known_tool() {
+        assert_eq!(
+            validate_tool_pattern("bash(git:*)"),
+            Err("Unknown tool 'bash', did you mean 'Bash'?".to_string())
+        );
+        assert!(validate_tool_pattern("webfetch").is_err());
+    }
     use std::str::FromStr;
 
     #[test]
@@ -337,17 +744,29 @@ mod tests {
     #[test]
     fn session_status_display() {
         assert_eq!(SessionStatus::Busy.to_string(), "Busy");
+        assert_eq!(SessionStatus::NeedsInput.to_string(), "Input");
         assert_eq!(SessionStatus::Waiting.to_string(), "Waiting");
         assert_eq!(SessionStatus::Idle.to_string(), "Idle");
         assert_eq!(SessionStatus::Error.to_string(), "Error");
+        assert_eq!(SessionStatus::Dormant.to_string(), "Dormant");
     }
 
     #[test]
     fn session_status_icon() {
         assert_eq!(SessionStatus::Busy.icon(), "●");
+        assert_eq!(SessionStatus::NeedsInput.icon(), "◆");
         assert_eq!(SessionStatus::Waiting.icon(), "◉");
         assert_eq!(SessionStatus::Idle.icon(), "○");
         assert_eq!(SessionStatus::Error.icon(), "✗");
+        assert_eq!(SessionStatus::Dormant.icon(), "◌");
+    }
+
+    #[test]
+    fn session_status_str_roundtrip() {
+        for status in SessionStatus::ALL {
+            assert_eq!(status.as_str().parse::<SessionStatus>(), Ok(status));
+        }
+        assert!("bogus".parse::<SessionStatus>().is_err());
     }
 
     #[test]
@@ -393,6 +812,61 @@ mod tests {
         assert_eq!(info.role, DEFAULT_ROLE_NAME);
     }
 
+    #[test]
+    fn session_info_new_has_no_restarts() {
+        let info = SessionInfo::new("Test".to_string());
+        assert_eq!(info.restart_count, 0);
+        assert!(info.recent_restarts.is_empty());
+        assert!(!info.crash_looping);
+    }
+
+    #[test]
+    fn record_restart_below_threshold_is_not_crash_loop() {
+        let mut info = SessionInfo::new("Test".to_string());
+        for i in 0..CRASH_LOOP_MAX_RESTARTS as u64 {
+            assert!(!info.record_restart(1_000 + i * 1_000));
+        }
+        assert_eq!(info.restart_count, CRASH_LOOP_MAX_RESTARTS as u32);
+        assert!(!info.crash_looping);
+    }
+
+    #[test]
+    fn record_restart_over_threshold_flags_crash_loop() {
+        let mut info = SessionInfo::new("Test".to_string());
+        for i in 0..CRASH_LOOP_MAX_RESTARTS as u64 {
+            info.record_restart(1_000 + i * 1_000);
+        }
+        assert!(info.record_restart(10_000));
+        assert!(info.crash_looping);
+    }
+
+    #[test]
+    fn record_restart_prunes_outside_window() {
+        let mut info = SessionInfo::new("Test".to_string());
+        for i in 0..CRASH_LOOP_MAX_RESTARTS as u64 {
+            info.record_restart(1_000 + i * 1_000);
+        }
+        // Next restart lands after the earlier ones aged out of the window
+        let later = 1_000 + CRASH_LOOP_WINDOW_MS + 10_000;
+        assert!(!info.record_restart(later));
+        assert_eq!(info.recent_restarts, vec![later]);
+        assert_eq!(info.restart_count, CRASH_LOOP_MAX_RESTARTS as u32 + 1);
+    }
+
+    #[test]
+    fn reset_crash_loop_clears_state_but_keeps_total() {
+        let mut info = SessionInfo::new("Test".to_string());
+        for i in 0..=CRASH_LOOP_MAX_RESTARTS as u64 {
+            info.record_restart(1_000 + i);
+        }
+        assert!(info.crash_looping);
+
+        info.reset_crash_loop();
+        assert!(!info.crash_looping);
+        assert!(info.recent_restarts.is_empty());
+        assert_eq!(info.restart_count, CRASH_LOOP_MAX_RESTARTS as u32 + 1);
+    }
+
     #[test]
     fn default_role_name_is_developer() {
         assert_eq!(DEFAULT_ROLE_NAME, "developer");
@@ -464,6 +938,19 @@ mod tests {
         assert_eq!(perms, deserialized);
     }
 
+    #[test]
+    fn role_permissions_without_env_deserialize_empty() {
+        // Roles saved before `env` existed have no such key
+        let perms: RolePermissions =
+            toml::from_str("permission_mode = \"plan\"\nallowed_tools = [\"Read\"]\n").unwrap();
+        assert!(perms.env.is_empty());
+        let perms: RolePermissions = serde_json::from_str(r#"{"allowed_tools":["Read"]}"#).unwrap();
+        assert!(perms.env.is_empty());
+
+        // ...and a role without env serializes without the key
+        assert!(!toml::to_string(&perms).unwrap().contains("env"));
+    }
+
     #[test]
     fn role_config_serde_roundtrip() {
         let role = RoleConfig {
@@ -505,6 +992,7 @@ mod tests {
                 ("API_KEY".to_string(), "secret".to_string()),
                 ("DEBUG".to_string(), "1".to_string()),
             ]),
+            enabled: true,
         };
         let json = serde_json::to_string(&config).unwrap();
         let deserialized: McpServerConfig = serde_json::from_str(&json).unwrap();
@@ -518,10 +1006,32 @@ mod tests {
             command: "server".to_string(),
             args: vec![],
             env: HashMap::new(),
+            enabled: true,
         };
         let json = serde_json::to_string(&config).unwrap();
         assert!(!json.contains("args"));
         assert!(!json.contains("env"));
+        assert!(!json.contains("enabled"));
+    }
+
+    #[test]
+    fn mcp_server_config_defaults_to_enabled() {
+        // Configs saved before servers could be disabled have no `enabled` key.
+        let config: McpServerConfig =
+            serde_json::from_str(r#"{"name":"old","command":"server"}"#).unwrap();
+        assert!(config.enabled);
+    }
+
+    #[test]
+    fn mcp_server_config_disabled_roundtrip() {
+        let config = McpServerConfig {
+            enabled: false,
+            ..mcp("off", "server")
+        };
+        let json = serde_json::to_string(&config).unwrap();
+        assert!(json.contains(r#""enabled":false"#));
+        let deserialized: McpServerConfig = serde_json::from_str(&json).unwrap();
+        assert_eq!(config, deserialized);
     }
 
     #[test]
@@ -530,6 +1040,7 @@ mod tests {
             repo_path: PathBuf::from("/repo"),
             worktree_path: PathBuf::from("/repo/.git/thurbox-worktrees/feat"),
             branch: "feat".to_string(),
+            base_branch: "main".to_string(),
         };
         assert_eq!(wt.repo_path, PathBuf::from("/repo"));
         assert_eq!(
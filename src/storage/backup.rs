@@ -0,0 +1,143 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use rusqlite::DatabaseName;
+
+use crate::sync::current_time_millis;
+
+use super::Database;
+
+const BACKUP_PREFIX: &str = "thurbox-";
+const BACKUP_SUFFIX: &str = ".db";
+
+impl Database {
+    /// Copy the whole database to `dest` with SQLite's online backup API.
+    /// Safe while this (or another) connection is open; `dest` is
+    /// overwritten if it exists.
+    pub fn backup_to(&self, dest: &Path) -> rusqlite::Result<()> {
+        self.conn.backup(DatabaseName::Main, dest, None)
+    }
+
+    /// Write a timestamped backup into `dir` and delete all but the newest
+    /// `keep` backups there. Returns the path of the new backup.
+    pub fn rolling_backup(&self, dir: &Path, keep: usize) -> Result<PathBuf> {
+        std::fs::create_dir_all(dir)
+            .with_context(|| format!("Failed to create {}", dir.display()))?;
+
+        let dest = dir.join(format!(
+            "{BACKUP_PREFIX}{}{BACKUP_SUFFIX}",
+            current_time_millis()
+        ));
+        self.backup_to(&dest)
+            .with_context(|| format!("Failed to back up database to {}", dest.display()))?;
+
+        let mut backups = list_backups(dir)?;
+        let excess = backups.len().saturating_sub(keep);
+        for old in backups.drain(..excess) {
+            std::fs::remove_file(&old)
+                .with_context(|| format!("Failed to remove old backup {}", old.display()))?;
+        }
+
+        Ok(dest)
+    }
+}
+
+/// Backups in `dir`, oldest first by the millisecond timestamp in the name.
+fn list_backups(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut backups: Vec<(u64, PathBuf)> = std::fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter_map(|path| {
+            let stamp = path
+                .file_name()?
+                .to_str()?
+                .strip_prefix(BACKUP_PREFIX)?
+                .strip_suffix(BACKUP_SUFFIX)?
+                .parse()
+                .ok()?;
+            Some((stamp, path))
+        })
+        .collect();
+    backups.sort();
+    Ok(backups.into_iter().map(|(_, path)| path).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::project::ProjectId;
+
+    fn db_with_project(name: &str) -> (Database, ProjectId) {
+        let db = Database::open_in_memory().unwrap();
+        let id = ProjectId::default();
+        db.insert_project(id, name, &[PathBuf::from("/repo")])
+            .unwrap();
+        (db, id)
+    }
+
+    #[test]
+    fn backup_preserves_projects() {
+        let (db, id) = db_with_project("backed-up");
+        let dir = tempfile::TempDir::new().unwrap();
+        let dest = dir.path().join("copy.db");
+
+        db.backup_to(&dest).unwrap();
+
+        let restored = Database::open(&dest).unwrap();
+        let projects = restored.list_active_projects().unwrap();
+        assert_eq!(projects.len(), 1);
+        assert_eq!(projects[0].id, id);
+        assert_eq!(projects[0].name, "backed-up");
+        assert_eq!(projects[0].repos, vec![PathBuf::from("/repo")]);
+    }
+
+    #[test]
+    fn backup_overwrites_existing_file() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let dest = dir.path().join("copy.db");
+        let (first, _) = db_with_project("first");
+        first.backup_to(&dest).unwrap();
+
+        let (second, _) = db_with_project("second");
+        second.backup_to(&dest).unwrap();
+
+        let restored = Database::open(&dest).unwrap();
+        let projects = restored.list_active_projects().unwrap();
+        assert_eq!(projects.len(), 1);
+        assert_eq!(projects[0].name, "second");
+    }
+
+    #[test]
+    fn rolling_backup_keeps_newest() {
+        let (db, _) = db_with_project("test");
+        let dir = tempfile::TempDir::new().unwrap();
+        for stamp in ["1000", "2000", "3000"] {
+            std::fs::write(dir.path().join(format!("thurbox-{stamp}.db")), "").unwrap();
+        }
+        std::fs::write(dir.path().join("notes.txt"), "").unwrap();
+
+        let newest = db.rolling_backup(dir.path(), 2).unwrap();
+
+        let kept = list_backups(dir.path()).unwrap();
+        assert_eq!(kept, vec![dir.path().join("thurbox-3000.db"), newest]);
+        assert!(dir.path().join("notes.txt").exists());
+    }
+
+    #[test]
+    fn rolling_backup_creates_directory() {
+        let (db, _) = db_with_project("test");
+        let dir = tempfile::TempDir::new().unwrap();
+        let backups = dir.path().join("backups");
+
+        let path = db.rolling_backup(&backups, 3).unwrap();
+
+        assert!(path.starts_with(&backups));
+        assert_eq!(
+            Database::open(&path)
+                .unwrap()
+                .list_active_projects()
+                .unwrap()
+                .len(),
+            1
+        );
+    }
+}
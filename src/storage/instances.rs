@@ -0,0 +1,176 @@
+use std::time::Duration;
+
+use rusqlite::params;
+
+use crate::sync::current_time_millis;
+
+use super::Database;
+
+/// A thurbox instance that has written a heartbeat recently, as read back
+/// from the `instances` table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LiveInstance {
+    /// Random id the instance picked when it opened the database.
+    pub instance_id: String,
+    /// Host the instance runs on, so instances on a shared database can be
+    /// told apart.
+    pub hostname: String,
+    /// Unix time of the last heartbeat, in milliseconds.
+    pub last_seen: u64,
+}
+
+impl Database {
+    /// Upsert this instance's heartbeat row with the current time, and
+    /// delete rows not refreshed within `window`.
+    ///
+    /// Crashed instances never remove their own row, so without the sweep
+    /// the table would grow with every unclean exit.
+    pub fn record_heartbeat(&self, window: Duration) -> rusqlite::Result<()> {
+        let now = current_time_millis();
+        self.conn.execute(
+            "INSERT INTO instances (instance_id, hostname, last_seen) VALUES (?1, ?2, ?3) \
+             ON CONFLICT(instance_id) DO UPDATE SET \
+             hostname = excluded.hostname, last_seen = excluded.last_seen",
+            params![self.instance_id, local_hostname(), now as i64],
+        )?;
+        self.conn.execute(
+            "DELETE FROM instances WHERE last_seen < ?1",
+            params![now.saturating_sub(window.as_millis() as u64) as i64],
+        )?;
+        Ok(())
+    }
+
+    /// Drop this instance's heartbeat row on clean shutdown, so other
+    /// instances stop counting it right away instead of after the window.
+    pub fn remove_heartbeat(&self) -> rusqlite::Result<()> {
+        self.conn.execute(
+            "DELETE FROM instances WHERE instance_id = ?1",
+            params![self.instance_id],
+        )?;
+        Ok(())
+    }
+
+    /// Instances (including this one) whose last heartbeat is within
+    /// `window`, oldest first. Rows older than that are ignored even if a
+    /// sweep has not removed them yet.
+    pub fn list_live_instances(&self, window: Duration) -> rusqlite::Result<Vec<LiveInstance>> {
+        let cutoff = current_time_millis().saturating_sub(window.as_millis() as u64) as i64;
+        let mut stmt = self.conn.prepare(
+            "SELECT instance_id, hostname, last_seen FROM instances \
+             WHERE last_seen >= ?1 ORDER BY last_seen, instance_id",
+        )?;
+        let instances = stmt
+            .query_map(params![cutoff], |row| {
+                let last_seen: i64 = row.get(2)?;
+                Ok(LiveInstance {
+                    instance_id: row.get(0)?,
+                    hostname: row.get(1)?,
+                    last_seen: last_seen as u64,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(instances)
+    }
+}
+
+/// Best-effort host name for the heartbeat row.
+fn local_hostname() -> String {
+    std::env::var("HOSTNAME")
+        .ok()
+        .or_else(|| std::fs::read_to_string("/proc/sys/kernel/hostname").ok())
+        .or_else(|| std::fs::read_to_string("/etc/hostname").ok())
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn count(db: &Database) -> i64 {
+        db.conn
+            .query_row("SELECT COUNT(*) FROM instances", [], |row| row.get(0))
+            .unwrap()
+    }
+
+    #[test]
+    fn heartbeat_upserts_single_row() {
+        let db = Database::open_in_memory().unwrap();
+        db.record_heartbeat(Duration::from_secs(60)).unwrap();
+        let first = db.list_live_instances(Duration::from_secs(60)).unwrap();
+
+        std::thread::sleep(Duration::from_millis(5));
+        db.record_heartbeat(Duration::from_secs(60)).unwrap();
+        let second = db.list_live_instances(Duration::from_secs(60)).unwrap();
+
+        assert_eq!(count(&db), 1);
+        assert_eq!(second.len(), 1);
+        assert_eq!(second[0].instance_id, db.instance_id);
+        assert!(!second[0].hostname.is_empty());
+        assert!(second[0].last_seen > first[0].last_seen);
+    }
+
+    #[test]
+    fn stale_instances_are_filtered_out() {
+        let db = Database::open_in_memory().unwrap();
+        db.record_heartbeat(Duration::from_secs(60)).unwrap();
+        let stale = current_time_millis() as i64 - 120_000;
+        db.conn
+            .execute(
+                "INSERT INTO instances (instance_id, hostname, last_seen) VALUES ('old', 'h', ?1)",
+                params![stale],
+            )
+            .unwrap();
+
+        let live = db.list_live_instances(Duration::from_secs(60)).unwrap();
+        assert_eq!(live.len(), 1);
+        assert_eq!(live[0].instance_id, db.instance_id);
+
+        let all = db.list_live_instances(Duration::from_secs(600)).unwrap();
+        assert_eq!(all.len(), 2);
+        assert_eq!(all[0].instance_id, "old");
+    }
+
+    #[test]
+    fn heartbeat_deletes_rows_outside_window() {
+        let db = Database::open_in_memory().unwrap();
+        let stale = current_time_millis() as i64 - 120_000;
+        db.conn
+            .execute(
+                "INSERT INTO instances (instance_id, hostname, last_seen) VALUES ('crashed', 'h', ?1)",
+                params![stale],
+            )
+            .unwrap();
+
+        db.record_heartbeat(Duration::from_secs(600)).unwrap();
+        assert_eq!(count(&db), 2);
+
+        db.record_heartbeat(Duration::from_secs(60)).unwrap();
+        assert_eq!(count(&db), 1);
+        let live = db.list_live_instances(Duration::from_secs(600)).unwrap();
+        assert_eq!(live[0].instance_id, db.instance_id);
+    }
+
+    #[test]
+    fn instances_sharing_a_database_see_each_other() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join("thurbox.db");
+        let a = Database::open(&path).unwrap();
+        let b = Database::open(&path).unwrap();
+        a.record_heartbeat(Duration::from_secs(60)).unwrap();
+        b.record_heartbeat(Duration::from_secs(60)).unwrap();
+
+        assert_eq!(
+            a.list_live_instances(Duration::from_secs(60))
+                .unwrap()
+                .len(),
+            2
+        );
+
+        b.remove_heartbeat().unwrap();
+        let live = a.list_live_instances(Duration::from_secs(60)).unwrap();
+        assert_eq!(live.len(), 1);
+        assert_eq!(live[0].instance_id, a.instance_id);
+    }
+}
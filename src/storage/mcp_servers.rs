@@ -16,7 +16,7 @@ impl Database {
     ) -> rusqlite::Result<Vec<McpServerConfig>> {
         let id_str = project_id.to_string();
         let mut stmt = self.conn.prepare(
-            "SELECT server_name, command, args, env \
+            "SELECT server_name, command, args, env, enabled \
              FROM project_mcp_servers WHERE project_id = ?1 ORDER BY server_name",
         )?;
 
@@ -26,12 +26,14 @@ impl Database {
                 let command: String = row.get(1)?;
                 let args_str: String = row.get(2)?;
                 let env_str: String = row.get(3)?;
+                let enabled: bool = row.get(4)?;
 
                 Ok(McpServerConfig {
                     name,
                     command,
                     args: str_to_args(&args_str),
                     env: str_to_env(&env_str),
+                    enabled,
                 })
             })?
             .collect::<Result<Vec<_>, _>>()?;
@@ -44,7 +46,7 @@ impl Database {
         &self,
     ) -> rusqlite::Result<HashMap<ProjectId, Vec<McpServerConfig>>> {
         let mut stmt = self.conn.prepare(
-            "SELECT ms.project_id, ms.server_name, ms.command, ms.args, ms.env \
+            "SELECT ms.project_id, ms.server_name, ms.command, ms.args, ms.env, ms.enabled \
              FROM project_mcp_servers ms \
              INNER JOIN projects p ON p.id = ms.project_id AND p.deleted_at IS NULL \
              ORDER BY ms.project_id, ms.server_name",
@@ -58,6 +60,7 @@ impl Database {
             let command: String = row.get(2)?;
             let args_str: String = row.get(3)?;
             let env_str: String = row.get(4)?;
+            let enabled: bool = row.get(5)?;
 
             Ok((
                 pid_str,
@@ -66,6 +69,7 @@ impl Database {
                     command,
                     args: str_to_args(&args_str),
                     env: str_to_env(&env_str),
+                    enabled,
                 },
             ))
         })?;
@@ -88,32 +92,35 @@ impl Database {
         project_id: ProjectId,
         servers: &[McpServerConfig],
     ) -> rusqlite::Result<()> {
-        let id_str = project_id.to_string();
-        let now = current_time_millis() as i64;
-
-        self.conn.execute(
-            "DELETE FROM project_mcp_servers WHERE project_id = ?1",
-            params![id_str],
-        )?;
+        self.write_with_retry(|| {
+            let id_str = project_id.to_string();
+            let now = current_time_millis() as i64;
 
-        for server in servers {
             self.conn.execute(
-                "INSERT INTO project_mcp_servers \
-                 (project_id, server_name, command, args, env, created_at, updated_at) \
-                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
-                params![
-                    id_str,
-                    server.name,
-                    server.command,
-                    args_to_str(&server.args),
-                    env_to_str(&server.env),
-                    now,
-                    now,
-                ],
+                "DELETE FROM project_mcp_servers WHERE project_id = ?1",
+                params![id_str],
             )?;
-        }
 
-        Ok(())
+            for server in servers {
+                self.conn.execute(
+                    "INSERT INTO project_mcp_servers \
+                     (project_id, server_name, command, args, env, enabled, created_at, updated_at) \
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+                    params![
+                        id_str,
+                        server.name,
+                        server.command,
+                        args_to_str(&server.args),
+                        env_to_str(&server.env),
+                        server.enabled,
+                        now,
+                        now,
+                    ],
+                )?;
+            }
+
+            Ok(())
+        })
     }
 }
 
@@ -157,7 +164,7 @@ mod tests {
     use std::collections::HashMap;
     use std::path::PathBuf;
 
-    use crate::project::ProjectConfig;
+    use crate::project::{ProjectConfig, SyncStrategy};
 
     use super::*;
 
@@ -167,6 +174,13 @@ mod tests {
             repos: vec![],
             roles: vec![],
             mcp_servers: vec![],
+            sync_base_branch: None,
+            remote: None,
+            sync_strategy: SyncStrategy::default(),
+            activity_timeout_ms: None,
+            worktree_root: None,
+            default_role: None,
+            session_init_command: None,
             id: None,
         };
         config.deterministic_id()
@@ -200,6 +214,7 @@ mod tests {
                     "@modelcontextprotocol/server-filesystem".to_string(),
                 ],
                 env: HashMap::new(),
+                enabled: true,
             },
             McpServerConfig {
                 name: "github".to_string(),
@@ -209,6 +224,7 @@ mod tests {
                     "@modelcontextprotocol/server-github".to_string(),
                 ],
                 env: HashMap::from([("GITHUB_TOKEN".to_string(), "ghp_xxx".to_string())]),
+                enabled: true,
             },
         ];
 
@@ -228,6 +244,34 @@ mod tests {
         );
     }
 
+    #[test]
+    fn replace_mcp_servers_keeps_enabled_flag() {
+        let (db, pid) = setup_db_with_project("test");
+        let servers = vec![
+            McpServerConfig {
+                name: "off".to_string(),
+                command: "cmd".to_string(),
+                args: vec![],
+                env: HashMap::new(),
+                enabled: false,
+            },
+            McpServerConfig {
+                name: "on".to_string(),
+                command: "cmd".to_string(),
+                args: vec![],
+                env: HashMap::new(),
+                enabled: true,
+            },
+        ];
+        db.replace_mcp_servers(pid, &servers).unwrap();
+
+        let loaded = db.list_mcp_servers(pid).unwrap();
+        assert!(!loaded[0].enabled);
+        assert!(loaded[1].enabled);
+        let all = db.list_all_mcp_servers().unwrap();
+        assert!(!all[&pid][0].enabled);
+    }
+
     #[test]
     fn replace_mcp_servers_overwrites_existing() {
         let (db, pid) = setup_db_with_project("test");
@@ -237,6 +281,7 @@ mod tests {
             command: "old-cmd".to_string(),
             args: vec![],
             env: HashMap::new(),
+            enabled: true,
         }];
         db.replace_mcp_servers(pid, &initial).unwrap();
 
@@ -245,6 +290,7 @@ mod tests {
             command: "new-cmd".to_string(),
             args: vec!["--flag".to_string()],
             env: HashMap::new(),
+            enabled: true,
         }];
         db.replace_mcp_servers(pid, &updated).unwrap();
 
@@ -262,6 +308,7 @@ mod tests {
             command: "cmd".to_string(),
             args: vec![],
             env: HashMap::new(),
+            enabled: true,
         }];
         db.replace_mcp_servers(pid, &servers).unwrap();
         db.replace_mcp_servers(pid, &[]).unwrap();
@@ -286,6 +333,7 @@ mod tests {
                 command: "cmd1".to_string(),
                 args: vec![],
                 env: HashMap::new(),
+                enabled: true,
             }],
         )
         .unwrap();
@@ -297,12 +345,14 @@ mod tests {
                     command: "cmd2a".to_string(),
                     args: vec![],
                     env: HashMap::new(),
+                    enabled: true,
                 },
                 McpServerConfig {
                     name: "s2b".to_string(),
                     command: "cmd2b".to_string(),
                     args: vec![],
                     env: HashMap::new(),
+                    enabled: true,
                 },
             ],
         )
@@ -325,6 +375,7 @@ mod tests {
                 command: "c".to_string(),
                 args: vec![],
                 env: HashMap::new(),
+                enabled: true,
             }],
         )
         .unwrap();
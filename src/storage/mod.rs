@@ -12,20 +12,44 @@
 //! ```
 
 pub mod audit;
+mod backup;
+mod instances;
+pub use instances::LiveInstance;
 mod mcp_servers;
 mod projects;
 mod roles;
+pub use roles::{merge_roles, parse_roles, serialize_roles, RoleFileFormat};
 mod schema;
+mod session_events;
+pub use session_events::{ActivitySummary, StatusSpan};
 mod sessions;
 pub use sessions::DeletedSessionInfo;
 pub mod sync;
+mod tombstones;
+mod ui_prefs;
+pub use ui_prefs::ProjectUiPrefs;
 mod worktrees;
 
 use std::path::Path;
+use std::time::Duration;
 
-use rusqlite::Connection;
+use rusqlite::{Connection, ErrorCode};
 use uuid::Uuid;
 
+/// How long SQLite itself waits on a lock held by another instance before
+/// reporting `SQLITE_BUSY`.
+const BUSY_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Per-attempt delays for [`Database::write_with_retry`]. The first entry
+/// (zero) is the initial attempt; later entries are the backoff before each
+/// retry.
+const BUSY_RETRY_DELAYS: &[Duration] = &[
+    Duration::ZERO,
+    Duration::from_millis(50),
+    Duration::from_millis(200),
+    Duration::from_millis(500),
+];
+
 /// SQLite-backed database for application state.
 pub struct Database {
     conn: Connection,
@@ -37,19 +61,46 @@ pub struct Database {
 
 impl Database {
     /// Open or create a database at the given path. Runs schema migrations.
+    ///
+    /// The connection waits up to [`BUSY_TIMEOUT`] for locks held by other
+    /// instances before failing with `SQLITE_BUSY`.
     pub fn open(path: &Path) -> rusqlite::Result<Self> {
         if let Some(parent) = path.parent() {
             std::fs::create_dir_all(parent).ok();
         }
         let conn = Connection::open(path)?;
+        conn.busy_timeout(BUSY_TIMEOUT)?;
         schema::initialize(&conn)?;
 
         let last_data_version = conn.query_row("PRAGMA data_version", [], |row| row.get(0))?;
 
-        Ok(Self {
+        let db = Self {
             conn,
             instance_id: Uuid::new_v4().to_string(),
             last_data_version,
+        };
+        db.run_migrations()?;
+        Ok(db)
+    }
+
+    /// Bring the schema up to date with the ordered migrations in
+    /// `schema.rs`. Idempotent; returns the schema version reached.
+    pub fn run_migrations(&self) -> rusqlite::Result<u32> {
+        schema::run_migrations(&self.conn)
+    }
+
+    /// Run a multi-statement write in a transaction, retrying the whole
+    /// transaction while another instance holds the database lock. Without
+    /// this a busy write is only logged and the instances silently diverge.
+    fn write_with_retry<T>(
+        &self,
+        mut op: impl FnMut() -> rusqlite::Result<T>,
+    ) -> rusqlite::Result<T> {
+        retry_on_busy(BUSY_RETRY_DELAYS, || {
+            let tx = self.conn.unchecked_transaction()?;
+            let value = op()?;
+            tx.commit()?;
+            Ok(value)
         })
     }
 
@@ -63,11 +114,49 @@ impl Database {
         let conn = Connection::open_in_memory()?;
         schema::initialize(&conn)?;
 
-        Ok(Self {
+        let db = Self {
             conn,
             instance_id: Uuid::new_v4().to_string(),
             last_data_version: 0,
-        })
+        };
+        db.run_migrations()?;
+        Ok(db)
+    }
+}
+
+/// Whether `err` means another connection holds the lock, so the same
+/// statement may succeed if tried again.
+fn is_busy(err: &rusqlite::Error) -> bool {
+    matches!(
+        err,
+        rusqlite::Error::SqliteFailure(e, _)
+            if matches!(e.code, ErrorCode::DatabaseBusy | ErrorCode::DatabaseLocked)
+    )
+}
+
+/// Call `op`, sleeping through `delays` and retrying while it fails with
+/// [`is_busy`]. Other errors, and the last busy error, are returned as is.
+fn retry_on_busy<T>(
+    delays: &[Duration],
+    mut op: impl FnMut() -> rusqlite::Result<T>,
+) -> rusqlite::Result<T> {
+    let mut last_err = None;
+    for (attempt, delay) in delays.iter().enumerate() {
+        if attempt > 0 {
+            tracing::warn!(
+                "Database busy, retrying write ({attempt}/{})",
+                delays.len() - 1
+            );
+            std::thread::sleep(*delay);
+        }
+        match op() {
+            Err(e) if is_busy(&e) => last_err = Some(e),
+            result => return result,
+        }
+    }
+    match last_err {
+        Some(e) => Err(e),
+        None => op(),
     }
 }
 
@@ -75,6 +164,60 @@ impl Database {
 mod tests {
     use super::*;
 
+    fn busy_error() -> rusqlite::Error {
+        rusqlite::Error::SqliteFailure(
+            rusqlite::ffi::Error::new(rusqlite::ffi::SQLITE_BUSY),
+            Some("database is locked".to_string()),
+        )
+    }
+
+    #[test]
+    fn busy_write_succeeds_on_retry() {
+        let mut attempts = 0;
+        let result = retry_on_busy(&[Duration::ZERO; 3], || {
+            attempts += 1;
+            if attempts < 3 {
+                Err(busy_error())
+            } else {
+                Ok(attempts)
+            }
+        });
+        assert_eq!(result.unwrap(), 3);
+    }
+
+    #[test]
+    fn busy_write_gives_up_after_bounded_attempts() {
+        let mut attempts = 0;
+        let result: rusqlite::Result<()> = retry_on_busy(&[Duration::ZERO; 3], || {
+            attempts += 1;
+            Err(busy_error())
+        });
+        assert!(is_busy(&result.unwrap_err()));
+        assert_eq!(attempts, 3);
+    }
+
+    #[test]
+    fn other_errors_are_not_retried() {
+        let mut attempts = 0;
+        let result: rusqlite::Result<()> = retry_on_busy(&[Duration::ZERO; 3], || {
+            attempts += 1;
+            Err(rusqlite::Error::QueryReturnedNoRows)
+        });
+        assert!(result.is_err());
+        assert_eq!(attempts, 1);
+    }
+
+    #[test]
+    fn file_database_sets_busy_timeout() {
+        let temp = tempfile::NamedTempFile::new().unwrap();
+        let db = Database::open(temp.path()).unwrap();
+        let timeout_ms: i64 = db
+            .conn_ref()
+            .query_row("PRAGMA busy_timeout", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(timeout_ms, BUSY_TIMEOUT.as_millis() as i64);
+    }
+
     #[test]
     fn open_in_memory() {
         let db = Database::open_in_memory();
@@ -98,6 +241,16 @@ mod tests {
         assert!(path.exists());
     }
 
+    #[test]
+    fn open_runs_migrations_to_latest() {
+        let db = Database::open_in_memory().unwrap();
+        assert_eq!(
+            schema::schema_version(db.conn_ref()),
+            schema::SCHEMA_VERSION
+        );
+        assert_eq!(db.run_migrations().unwrap(), schema::SCHEMA_VERSION);
+    }
+
     #[test]
     fn instance_id_is_unique() {
         let db1 = Database::open_in_memory().unwrap();
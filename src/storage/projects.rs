@@ -1,14 +1,29 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use rusqlite::params;
 
-use crate::project::ProjectId;
+use crate::project::{ProjectId, SyncStrategy};
 use crate::sync::current_time_millis;
 use crate::sync::SharedProject;
 
 use super::audit::{AuditAction, EntityType};
 use super::Database;
 
+/// `(id, name, sync_base_branch, remote, sync_strategy, activity_timeout_ms,
+/// worktree_root, default_role, session_init_command)` as read from the
+/// `projects` table.
+type ProjectRow = (
+    String,
+    String,
+    Option<String>,
+    Option<String>,
+    String,
+    Option<i64>,
+    Option<String>,
+    Option<String>,
+    Option<String>,
+);
+
 impl Database {
     /// Insert a new project with its repos.
     pub fn insert_project(
@@ -17,32 +32,34 @@ impl Database {
         name: &str,
         repos: &[PathBuf],
     ) -> rusqlite::Result<()> {
-        let now = current_time_millis() as i64;
-        let id_str = id.to_string();
-
-        self.conn.execute(
-            "INSERT INTO projects (id, name, is_default, created_at, updated_at) \
-             VALUES (?1, ?2, 0, ?3, ?4)",
-            params![id_str, name, now, now],
-        )?;
+        self.write_with_retry(|| {
+            let now = current_time_millis() as i64;
+            let id_str = id.to_string();
 
-        for repo in repos {
             self.conn.execute(
-                "INSERT INTO project_repos (project_id, repo_path) VALUES (?1, ?2)",
-                params![id_str, repo.display().to_string()],
+                "INSERT INTO projects (id, name, is_default, created_at, updated_at) \
+                 VALUES (?1, ?2, 0, ?3, ?4)",
+                params![id_str, name, now, now],
             )?;
-        }
 
-        self.log_audit(
-            EntityType::Project,
-            &id_str,
-            AuditAction::Created,
-            None,
-            None,
-            Some(name),
-        )?;
+            for repo in repos {
+                self.conn.execute(
+                    "INSERT INTO project_repos (project_id, repo_path) VALUES (?1, ?2)",
+                    params![id_str, repo.display().to_string()],
+                )?;
+            }
 
-        Ok(())
+            self.log_audit(
+                EntityType::Project,
+                &id_str,
+                AuditAction::Created,
+                None,
+                None,
+                Some(name),
+            )?;
+
+            Ok(())
+        })
     }
 
     /// Update a project's name and repos.
@@ -52,47 +69,147 @@ impl Database {
         name: &str,
         repos: &[PathBuf],
     ) -> rusqlite::Result<()> {
-        let now = current_time_millis() as i64;
-        let id_str = id.to_string();
+        self.write_with_retry(|| {
+            let now = current_time_millis() as i64;
+            let id_str = id.to_string();
+
+            // Get old name for audit
+            let old_name: Option<String> = self
+                .conn
+                .query_row(
+                    "SELECT name FROM projects WHERE id = ?1",
+                    params![id_str],
+                    |row| row.get(0),
+                )
+                .ok();
 
-        // Get old name for audit
-        let old_name: Option<String> = self
-            .conn
-            .query_row(
-                "SELECT name FROM projects WHERE id = ?1",
+            self.conn.execute(
+                "UPDATE projects SET name = ?1, updated_at = ?2 WHERE id = ?3",
+                params![name, now, id_str],
+            )?;
+
+            // Replace repos
+            self.conn.execute(
+                "DELETE FROM project_repos WHERE project_id = ?1",
                 params![id_str],
-                |row| row.get(0),
-            )
-            .ok();
+            )?;
+            for repo in repos {
+                self.conn.execute(
+                    "INSERT INTO project_repos (project_id, repo_path) VALUES (?1, ?2)",
+                    params![id_str, repo.display().to_string()],
+                )?;
+            }
+
+            if old_name.as_deref() != Some(name) {
+                self.log_audit(
+                    EntityType::Project,
+                    &id_str,
+                    AuditAction::Updated,
+                    Some("name"),
+                    old_name.as_deref(),
+                    Some(name),
+                )?;
+            }
+
+            Ok(())
+        })
+    }
 
+    /// Set (or clear, with `None`) the branch worktree sync rebases onto.
+    pub fn set_project_sync_base_branch(
+        &self,
+        id: ProjectId,
+        branch: Option<&str>,
+    ) -> rusqlite::Result<()> {
+        let now = current_time_millis() as i64;
         self.conn.execute(
-            "UPDATE projects SET name = ?1, updated_at = ?2 WHERE id = ?3",
-            params![name, now, id_str],
+            "UPDATE projects SET sync_base_branch = ?1, updated_at = ?2 WHERE id = ?3",
+            params![branch, now, id.to_string()],
         )?;
+        Ok(())
+    }
 
-        // Replace repos
+    /// Set (or clear, with `None`) the git remote worktree sync fetches from.
+    pub fn set_project_remote(&self, id: ProjectId, remote: Option<&str>) -> rusqlite::Result<()> {
+        let now = current_time_millis() as i64;
         self.conn.execute(
-            "DELETE FROM project_repos WHERE project_id = ?1",
-            params![id_str],
+            "UPDATE projects SET remote = ?1, updated_at = ?2 WHERE id = ?3",
+            params![remote, now, id.to_string()],
         )?;
-        for repo in repos {
-            self.conn.execute(
-                "INSERT INTO project_repos (project_id, repo_path) VALUES (?1, ?2)",
-                params![id_str, repo.display().to_string()],
-            )?;
-        }
+        Ok(())
+    }
 
-        if old_name.as_deref() != Some(name) {
-            self.log_audit(
-                EntityType::Project,
-                &id_str,
-                AuditAction::Updated,
-                Some("name"),
-                old_name.as_deref(),
-                Some(name),
-            )?;
-        }
+    /// Set the strategy worktree sync uses to integrate the base branch.
+    pub fn set_project_sync_strategy(
+        &self,
+        id: ProjectId,
+        strategy: SyncStrategy,
+    ) -> rusqlite::Result<()> {
+        let now = current_time_millis() as i64;
+        self.conn.execute(
+            "UPDATE projects SET sync_strategy = ?1, updated_at = ?2 WHERE id = ?3",
+            params![strategy.as_str(), now, id.to_string()],
+        )?;
+        Ok(())
+    }
+
+    /// Set (or clear, with `None`) the output-silence timeout after which a
+    /// session in this project counts as waiting.
+    pub fn set_project_activity_timeout(
+        &self,
+        id: ProjectId,
+        timeout_ms: Option<u64>,
+    ) -> rusqlite::Result<()> {
+        let now = current_time_millis() as i64;
+        self.conn.execute(
+            "UPDATE projects SET activity_timeout_ms = ?1, updated_at = ?2 WHERE id = ?3",
+            params![timeout_ms.map(|ms| ms as i64), now, id.to_string()],
+        )?;
+        Ok(())
+    }
 
+    /// Set (or clear, with `None`) the directory this project's worktrees are
+    /// created under.
+    pub fn set_project_worktree_root(
+        &self,
+        id: ProjectId,
+        root: Option<&Path>,
+    ) -> rusqlite::Result<()> {
+        let now = current_time_millis() as i64;
+        self.conn.execute(
+            "UPDATE projects SET worktree_root = ?1, updated_at = ?2 WHERE id = ?3",
+            params![root.map(|p| p.display().to_string()), now, id.to_string()],
+        )?;
+        Ok(())
+    }
+
+    /// Set (or clear, with `None`) the role new sessions in this project get
+    /// without asking.
+    pub fn set_project_default_role(
+        &self,
+        id: ProjectId,
+        role: Option<&str>,
+    ) -> rusqlite::Result<()> {
+        let now = current_time_millis() as i64;
+        self.conn.execute(
+            "UPDATE projects SET default_role = ?1, updated_at = ?2 WHERE id = ?3",
+            params![role, now, id.to_string()],
+        )?;
+        Ok(())
+    }
+
+    /// Set (or clear, with `None`) the command typed into each new session
+    /// of this project.
+    pub fn set_project_session_init_command(
+        &self,
+        id: ProjectId,
+        command: Option<&str>,
+    ) -> rusqlite::Result<()> {
+        let now = current_time_millis() as i64;
+        self.conn.execute(
+            "UPDATE projects SET session_init_command = ?1, updated_at = ?2 WHERE id = ?3",
+            params![command, now, id.to_string()],
+        )?;
         Ok(())
     }
 
@@ -120,24 +237,26 @@ impl Database {
 
     /// Restore a soft-deleted project.
     pub fn restore_project(&self, id: ProjectId) -> rusqlite::Result<()> {
-        let now = current_time_millis() as i64;
-        let id_str = id.to_string();
+        self.write_with_retry(|| {
+            let now = current_time_millis() as i64;
+            let id_str = id.to_string();
 
-        self.conn.execute(
-            "UPDATE projects SET deleted_at = NULL, updated_at = ?1 WHERE id = ?2 AND deleted_at IS NOT NULL",
-            params![now, id_str],
-        )?;
+            self.conn.execute(
+                "UPDATE projects SET deleted_at = NULL, updated_at = ?1 WHERE id = ?2 AND deleted_at IS NOT NULL",
+                params![now, id_str],
+            )?;
 
-        self.log_audit(
-            EntityType::Project,
-            &id_str,
-            AuditAction::Restored,
-            None,
-            None,
-            None,
-        )?;
+            self.log_audit(
+                EntityType::Project,
+                &id_str,
+                AuditAction::Restored,
+                None,
+                None,
+                None,
+            )?;
 
-        Ok(())
+            Ok(())
+        })
     }
 
     /// List only active (non-deleted) projects.
@@ -151,14 +270,40 @@ impl Database {
     }
 
     fn list_projects_where(&self, condition: &str) -> rusqlite::Result<Vec<SharedProject>> {
-        let sql = format!("SELECT id, name FROM projects WHERE {condition} ORDER BY created_at");
+        let sql = format!(
+            "SELECT id, name, sync_base_branch, remote, sync_strategy, activity_timeout_ms, \
+             worktree_root, default_role, session_init_command FROM projects WHERE {condition} ORDER BY created_at"
+        );
         let mut stmt = self.conn.prepare(&sql)?;
-        let rows: Vec<(String, String)> = stmt
-            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+        let rows: Vec<ProjectRow> = stmt
+            .query_map([], |row| {
+                Ok((
+                    row.get(0)?,
+                    row.get(1)?,
+                    row.get(2)?,
+                    row.get(3)?,
+                    row.get(4)?,
+                    row.get(5)?,
+                    row.get(6)?,
+                    row.get(7)?,
+                    row.get(8)?,
+                ))
+            })?
             .collect::<Result<_, _>>()?;
 
         let mut projects = Vec::new();
-        for (id_str, name) in rows {
+        for (
+            id_str,
+            name,
+            sync_base_branch,
+            remote,
+            sync_strategy,
+            activity_timeout_ms,
+            worktree_root,
+            default_role,
+            session_init_command,
+        ) in rows
+        {
             let id: ProjectId = id_str
                 .parse::<uuid::Uuid>()
                 .map(ProjectId::from_uuid)
@@ -183,6 +328,13 @@ impl Database {
                 repos,
                 roles,
                 mcp_servers,
+                sync_base_branch,
+                remote,
+                sync_strategy: sync_strategy.parse().unwrap_or_default(),
+                activity_timeout_ms: activity_timeout_ms.map(|ms| ms as u64),
+                worktree_root: worktree_root.map(PathBuf::from),
+                default_role,
+                session_init_command,
             });
         }
 
@@ -212,6 +364,13 @@ mod tests {
             repos: vec![],
             roles: vec![],
             mcp_servers: vec![],
+            sync_base_branch: None,
+            remote: None,
+            sync_strategy: SyncStrategy::default(),
+            activity_timeout_ms: None,
+            worktree_root: None,
+            default_role: None,
+            session_init_command: None,
             id: None,
         };
         config.deterministic_id()
@@ -246,6 +405,168 @@ mod tests {
         assert_eq!(projects[0].repos, vec![PathBuf::from("/repo2")]);
     }
 
+    #[test]
+    fn sync_base_branch_defaults_to_none() {
+        let db = Database::open_in_memory().unwrap();
+        let id = test_project_id("test");
+
+        db.insert_project(id, "test", &[]).unwrap();
+
+        let projects = db.list_active_projects().unwrap();
+        assert_eq!(projects[0].sync_base_branch, None);
+    }
+
+    #[test]
+    fn set_and_clear_sync_base_branch() {
+        let db = Database::open_in_memory().unwrap();
+        let id = test_project_id("test");
+
+        db.insert_project(id, "test", &[]).unwrap();
+        db.set_project_sync_base_branch(id, Some("develop"))
+            .unwrap();
+        assert_eq!(
+            db.list_active_projects().unwrap()[0].sync_base_branch,
+            Some("develop".to_string())
+        );
+
+        // Renaming keeps the override
+        db.update_project(id, "renamed", &[]).unwrap();
+        assert_eq!(
+            db.list_active_projects().unwrap()[0].sync_base_branch,
+            Some("develop".to_string())
+        );
+
+        db.set_project_sync_base_branch(id, None).unwrap();
+        assert_eq!(db.list_active_projects().unwrap()[0].sync_base_branch, None);
+    }
+
+    #[test]
+    fn set_and_clear_remote() {
+        let db = Database::open_in_memory().unwrap();
+        let id = test_project_id("test");
+
+        db.insert_project(id, "test", &[]).unwrap();
+        assert_eq!(db.list_active_projects().unwrap()[0].remote, None);
+
+        db.set_project_remote(id, Some("upstream")).unwrap();
+        assert_eq!(
+            db.list_active_projects().unwrap()[0].remote,
+            Some("upstream".to_string())
+        );
+
+        db.set_project_remote(id, None).unwrap();
+        assert_eq!(db.list_active_projects().unwrap()[0].remote, None);
+    }
+
+    #[test]
+    fn set_sync_strategy_roundtrip() {
+        let db = Database::open_in_memory().unwrap();
+        let id = test_project_id("test");
+
+        db.insert_project(id, "test", &[]).unwrap();
+        assert_eq!(
+            db.list_active_projects().unwrap()[0].sync_strategy,
+            SyncStrategy::Rebase
+        );
+
+        db.set_project_sync_strategy(id, SyncStrategy::Merge)
+            .unwrap();
+        assert_eq!(
+            db.list_active_projects().unwrap()[0].sync_strategy,
+            SyncStrategy::Merge
+        );
+    }
+
+    #[test]
+    fn set_activity_timeout_roundtrip() {
+        let db = Database::open_in_memory().unwrap();
+        let id = test_project_id("test");
+
+        db.insert_project(id, "test", &[]).unwrap();
+        assert_eq!(
+            db.list_active_projects().unwrap()[0].activity_timeout_ms,
+            None
+        );
+
+        db.set_project_activity_timeout(id, Some(5000)).unwrap();
+        assert_eq!(
+            db.list_active_projects().unwrap()[0].activity_timeout_ms,
+            Some(5000)
+        );
+
+        db.set_project_activity_timeout(id, None).unwrap();
+        assert_eq!(
+            db.list_active_projects().unwrap()[0].activity_timeout_ms,
+            None
+        );
+    }
+
+    #[test]
+    fn set_worktree_root_roundtrip() {
+        let db = Database::open_in_memory().unwrap();
+        let id = test_project_id("test");
+
+        db.insert_project(id, "test", &[]).unwrap();
+        assert_eq!(db.list_active_projects().unwrap()[0].worktree_root, None);
+
+        db.set_project_worktree_root(id, Some(Path::new("../thurbox-worktrees")))
+            .unwrap();
+        assert_eq!(
+            db.list_active_projects().unwrap()[0].worktree_root,
+            Some(PathBuf::from("../thurbox-worktrees"))
+        );
+
+        db.set_project_worktree_root(id, None).unwrap();
+        assert_eq!(db.list_active_projects().unwrap()[0].worktree_root, None);
+    }
+
+    #[test]
+    fn set_session_init_command_roundtrip() {
+        let db = Database::open_in_memory().unwrap();
+        let id = test_project_id("test");
+
+        db.insert_project(id, "test", &[]).unwrap();
+        assert_eq!(
+            db.list_active_projects().unwrap()[0].session_init_command,
+            None
+        );
+
+        db.set_project_session_init_command(id, Some("nvm use"))
+            .unwrap();
+        assert_eq!(
+            db.list_active_projects().unwrap()[0]
+                .session_init_command
+                .as_deref(),
+            Some("nvm use")
+        );
+
+        db.set_project_session_init_command(id, None).unwrap();
+        assert_eq!(
+            db.list_active_projects().unwrap()[0].session_init_command,
+            None
+        );
+    }
+
+    #[test]
+    fn set_default_role_roundtrip() {
+        let db = Database::open_in_memory().unwrap();
+        let id = test_project_id("test");
+
+        db.insert_project(id, "test", &[]).unwrap();
+        assert_eq!(db.list_active_projects().unwrap()[0].default_role, None);
+
+        db.set_project_default_role(id, Some("reviewer")).unwrap();
+        assert_eq!(
+            db.list_active_projects().unwrap()[0]
+                .default_role
+                .as_deref(),
+            Some("reviewer")
+        );
+
+        db.set_project_default_role(id, None).unwrap();
+        assert_eq!(db.list_active_projects().unwrap()[0].default_role, None);
+    }
+
     #[test]
     fn soft_delete_hides_from_active() {
         let db = Database::open_in_memory().unwrap();
@@ -1,9 +1,12 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
 
+use anyhow::{bail, Context};
 use rusqlite::params;
+use serde::{Deserialize, Serialize};
 
 use crate::project::ProjectId;
-use crate::session::{RoleConfig, RolePermissions};
+use crate::session::{RoleConfig, RoleName, RolePermissions};
 use crate::sync::current_time_millis;
 
 use super::Database;
@@ -105,38 +108,126 @@ impl Database {
         project_id: ProjectId,
         roles: &[RoleConfig],
     ) -> rusqlite::Result<()> {
-        let id_str = project_id.to_string();
-        let now = current_time_millis() as i64;
-
-        self.conn.execute(
-            "DELETE FROM project_roles WHERE project_id = ?1",
-            params![id_str],
-        )?;
+        self.write_with_retry(|| {
+            let id_str = project_id.to_string();
+            let now = current_time_millis() as i64;
 
-        for role in roles {
             self.conn.execute(
-                "INSERT INTO project_roles \
-                 (project_id, role_name, description, permission_mode, \
-                  allowed_tools, disallowed_tools, tools, append_system_prompt, env, \
-                  created_at, updated_at) \
-                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
-                params![
-                    id_str,
-                    role.name,
-                    role.description,
-                    role.permissions.permission_mode,
-                    vec_to_csv(&role.permissions.allowed_tools),
-                    vec_to_csv(&role.permissions.disallowed_tools),
-                    role.permissions.tools,
-                    role.permissions.append_system_prompt,
-                    env_to_json(&role.permissions.env),
-                    now,
-                    now,
-                ],
+                "DELETE FROM project_roles WHERE project_id = ?1",
+                params![id_str],
             )?;
+
+            for role in roles {
+                self.conn.execute(
+                    "INSERT INTO project_roles \
+                     (project_id, role_name, description, permission_mode, \
+                      allowed_tools, disallowed_tools, tools, append_system_prompt, env, \
+                      created_at, updated_at) \
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+                    params![
+                        id_str,
+                        role.name,
+                        role.description,
+                        role.permissions.permission_mode,
+                        vec_to_csv(&role.permissions.allowed_tools),
+                        vec_to_csv(&role.permissions.disallowed_tools),
+                        role.permissions.tools,
+                        role.permissions.append_system_prompt,
+                        env_to_json(&role.permissions.env),
+                        now,
+                        now,
+                    ],
+                )?;
+            }
+
+            Ok(())
+        })
+    }
+
+    /// Serialize a project's roles as a TOML role file.
+    pub fn export_roles(&self, project_id: ProjectId) -> anyhow::Result<String> {
+        let roles = self.list_roles(project_id)?;
+        serialize_roles(&roles, RoleFileFormat::Toml)
+    }
+
+    /// Merge roles from a TOML role file into a project, overwriting roles
+    /// with the same name. Returns the number of roles imported.
+    pub fn import_roles(&self, project_id: ProjectId, toml_str: &str) -> anyhow::Result<usize> {
+        let imported = parse_roles(toml_str, RoleFileFormat::Toml)?;
+        let count = imported.len();
+        let mut roles = self.list_roles(project_id)?;
+        merge_roles(&mut roles, imported);
+        self.replace_roles(project_id, &roles)?;
+        Ok(count)
+    }
+}
+
+/// Encoding of a role import/export file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RoleFileFormat {
+    Toml,
+    Json,
+}
+
+impl RoleFileFormat {
+    /// `.json` files are JSON; anything else is treated as TOML.
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("json") => Self::Json,
+            _ => Self::Toml,
+        }
+    }
+}
+
+/// Top-level shape of a role file: a `[[roles]]` array of tables in TOML,
+/// `{"roles": [...]}` in JSON.
+#[derive(Debug, Serialize, Deserialize)]
+struct RoleFile {
+    #[serde(default)]
+    roles: Vec<RoleConfig>,
+}
+
+/// Serialize roles into a role file.
+pub fn serialize_roles(roles: &[RoleConfig], format: RoleFileFormat) -> anyhow::Result<String> {
+    let file = RoleFile {
+        roles: roles.to_vec(),
+    };
+    let contents = match format {
+        RoleFileFormat::Toml => toml::to_string_pretty(&file)?,
+        RoleFileFormat::Json => serde_json::to_string_pretty(&file)?,
+    };
+    Ok(contents)
+}
+
+/// Parse and validate a role file. Role names are trimmed and must be
+/// valid [`RoleName`]s, unique within the file.
+pub fn parse_roles(contents: &str, format: RoleFileFormat) -> anyhow::Result<Vec<RoleConfig>> {
+    let file: RoleFile = match format {
+        RoleFileFormat::Toml => toml::from_str(contents).context("Invalid role file")?,
+        RoleFileFormat::Json => serde_json::from_str(contents).context("Invalid role file")?,
+    };
+
+    let mut seen = HashSet::new();
+    let mut roles = Vec::with_capacity(file.roles.len());
+    for mut role in file.roles {
+        let name = RoleName::new(&role.name)?.into_string();
+        if !seen.insert(name.clone()) {
+            bail!("Duplicate role '{name}' in role file");
         }
+        role.name = name;
+        roles.push(role);
+    }
+    Ok(roles)
+}
 
-        Ok(())
+/// Merge `imported` into `existing` by name: matching roles are replaced in
+/// place, new ones are appended.
+pub fn merge_roles(existing: &mut Vec<RoleConfig>, imported: Vec<RoleConfig>) {
+    for role in imported {
+        match existing.iter_mut().find(|r| r.name == role.name) {
+            Some(slot) => *slot = role,
+            None => existing.push(role),
+        }
     }
 }
 
@@ -176,7 +267,7 @@ fn env_to_json(env: &HashMap<String, String>) -> String {
 mod tests {
     use std::path::PathBuf;
 
-    use crate::project::ProjectConfig;
+    use crate::project::{ProjectConfig, SyncStrategy};
 
     use super::*;
 
@@ -186,6 +277,13 @@ mod tests {
             repos: vec![],
             roles: vec![],
             mcp_servers: vec![],
+            sync_base_branch: None,
+            remote: None,
+            sync_strategy: SyncStrategy::default(),
+            activity_timeout_ms: None,
+            worktree_root: None,
+            default_role: None,
+            session_init_command: None,
             id: None,
         };
         config.deterministic_id()
@@ -430,4 +528,124 @@ mod tests {
             "Read,Edit"
         );
     }
+    fn role(name: &str, mode: Option<&str>) -> RoleConfig {
+        RoleConfig {
+            name: name.to_string(),
+            description: format!("{name} role"),
+            permissions: RolePermissions {
+                permission_mode: mode.map(str::to_string),
+                allowed_tools: vec!["Read".to_string(), "Bash(git:*)".to_string()],
+                disallowed_tools: vec!["Edit".to_string()],
+                tools: None,
+                append_system_prompt: Some("Be careful".to_string()),
+                env: HashMap::from([("FOO".to_string(), "bar".to_string())]),
+            },
+        }
+    }
+
+    #[test]
+    fn export_import_roundtrip() {
+        let (db, pid) = setup_db_with_project("source");
+        let roles = vec![role("developer", None), role("reviewer", Some("plan"))];
+        db.replace_roles(pid, &roles).unwrap();
+
+        let exported = db.export_roles(pid).unwrap();
+        assert!(exported.contains("[[roles]]"));
+
+        let target = test_project_id("target");
+        db.insert_project(target, "target", &[PathBuf::from("/other")])
+            .unwrap();
+        assert_eq!(db.import_roles(target, &exported).unwrap(), 2);
+        assert_eq!(db.list_roles(target).unwrap(), roles);
+    }
+
+    #[test]
+    fn import_merges_by_name() {
+        let (db, pid) = setup_db_with_project("test");
+        db.replace_roles(pid, &[role("developer", None), role("ops", None)])
+            .unwrap();
+
+        let file = serialize_roles(
+            &[role("developer", Some("plan")), role("reviewer", None)],
+            RoleFileFormat::Toml,
+        )
+        .unwrap();
+        assert_eq!(db.import_roles(pid, &file).unwrap(), 2);
+
+        let roles = db.list_roles(pid).unwrap();
+        let names: Vec<&str> = roles.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, vec!["developer", "ops", "reviewer"]);
+        assert_eq!(
+            roles[0].permissions.permission_mode.as_deref(),
+            Some("plan")
+        );
+    }
+
+    #[test]
+    fn import_rejects_malformed_toml() {
+        let (db, pid) = setup_db_with_project("test");
+        db.replace_roles(pid, &[role("developer", None)]).unwrap();
+
+        assert!(db.import_roles(pid, "[[roles]\nname = ").is_err());
+        // Missing required `name`
+        assert!(db
+            .import_roles(pid, "[[roles]]\ndescription = \"x\"\n")
+            .is_err());
+        // Existing roles are untouched on failure
+        assert_eq!(db.list_roles(pid).unwrap(), vec![role("developer", None)]);
+    }
+
+    #[test]
+    fn parse_rejects_invalid_and_duplicate_names() {
+        let blank = "[[roles]]\nname = \"  \"\n";
+        let err = parse_roles(blank, RoleFileFormat::Toml).unwrap_err();
+        assert_eq!(err.to_string(), "Role name cannot be empty");
+
+        let dup = "[[roles]]\nname = \"a\"\n\n[[roles]]\nname = \" a \"\n";
+        let err = parse_roles(dup, RoleFileFormat::Toml).unwrap_err();
+        assert!(err.to_string().contains("Duplicate role 'a'"));
+    }
+
+    #[test]
+    fn parse_trims_names() {
+        let roles = parse_roles("[[roles]]\nname = \" dev \"\n", RoleFileFormat::Toml).unwrap();
+        assert_eq!(roles[0].name, "dev");
+    }
+
+    #[test]
+    fn json_roundtrip() {
+        let roles = vec![role("developer", Some("acceptEdits"))];
+        let json = serialize_roles(&roles, RoleFileFormat::Json).unwrap();
+        assert_eq!(parse_roles(&json, RoleFileFormat::Json).unwrap(), roles);
+        assert!(parse_roles(&json, RoleFileFormat::Toml).is_err());
+    }
+
+    #[test]
+    fn empty_file_parses_to_no_roles() {
+        assert!(parse_roles("", RoleFileFormat::Toml).unwrap().is_empty());
+        let exported = serialize_roles(&[], RoleFileFormat::Toml).unwrap();
+        assert!(parse_roles(&exported, RoleFileFormat::Toml)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn format_from_path_extension() {
+        assert_eq!(
+            RoleFileFormat::from_path(Path::new("roles.json")),
+            RoleFileFormat::Json
+        );
+        assert_eq!(
+            RoleFileFormat::from_path(Path::new("roles.JSON")),
+            RoleFileFormat::Json
+        );
+        assert_eq!(
+            RoleFileFormat::from_path(Path::new("roles.toml")),
+            RoleFileFormat::Toml
+        );
+        assert_eq!(
+            RoleFileFormat::from_path(Path::new("roles")),
+            RoleFileFormat::Toml
+        );
+    }
 }
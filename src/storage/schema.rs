@@ -1,9 +1,11 @@
 use rusqlite::Connection;
 
-/// Current schema version. Incremented when schema changes.
-pub const SCHEMA_VERSION: u32 = 8;
+/// Current schema version: the version of the last entry in [`MIGRATIONS`].
+pub const SCHEMA_VERSION: u32 = MIGRATIONS[MIGRATIONS.len() - 1].0;
 
-/// Create all tables and indexes if they don't exist.
+/// Create all tables and indexes if they don't exist. A new database is
+/// stamped with [`SCHEMA_VERSION`]; run [`run_migrations`] afterwards to
+/// bring an older one up to date.
 pub fn initialize(conn: &Connection) -> rusqlite::Result<()> {
     conn.execute_batch("PRAGMA journal_mode = WAL;")?;
     conn.execute_batch("PRAGMA foreign_keys = ON;")?;
@@ -19,6 +21,13 @@ pub fn initialize(conn: &Connection) -> rusqlite::Result<()> {
             id         TEXT PRIMARY KEY,
             name       TEXT NOT NULL,
             is_default INTEGER NOT NULL DEFAULT 0,
+            sync_base_branch TEXT,
+            remote TEXT,
+            sync_strategy TEXT NOT NULL DEFAULT 'rebase',
+            activity_timeout_ms INTEGER,
+            worktree_root TEXT,
+            default_role TEXT,
+            session_init_command TEXT,
             created_at INTEGER NOT NULL,
             updated_at INTEGER NOT NULL,
             deleted_at INTEGER
@@ -41,6 +50,10 @@ pub fn initialize(conn: &Connection) -> rusqlite::Result<()> {
             cwd               TEXT,
             additional_dirs   TEXT NOT NULL DEFAULT '',
             shell_backend_id  TEXT,
+            ordinal           INTEGER NOT NULL DEFAULT 0,
+            is_pinned         INTEGER NOT NULL DEFAULT 0,
+            label             TEXT,
+            label_color       TEXT,
             created_at        INTEGER NOT NULL,
             updated_at        INTEGER NOT NULL,
             deleted_at        INTEGER
@@ -51,6 +64,7 @@ pub fn initialize(conn: &Connection) -> rusqlite::Result<()> {
             repo_path     TEXT NOT NULL,
             worktree_path TEXT NOT NULL,
             branch        TEXT NOT NULL,
+            base_branch   TEXT NOT NULL DEFAULT '',
             created_at    INTEGER NOT NULL,
             deleted_at    INTEGER,
             PRIMARY KEY (session_id, repo_path)
@@ -100,6 +114,7 @@ pub fn initialize(conn: &Connection) -> rusqlite::Result<()> {
             command     TEXT NOT NULL DEFAULT '',
             args        TEXT NOT NULL DEFAULT '',
             env         TEXT NOT NULL DEFAULT '',
+            enabled     INTEGER NOT NULL DEFAULT 1,
             created_at  INTEGER NOT NULL,
             updated_at  INTEGER NOT NULL,
             PRIMARY KEY (project_id, server_name)
@@ -114,6 +129,30 @@ pub fn initialize(conn: &Connection) -> rusqlite::Result<()> {
         );
         CREATE INDEX IF NOT EXISTS idx_session_commands_pending
             ON session_commands(id) WHERE processed_at IS NULL;
+
+        CREATE TABLE IF NOT EXISTS instances (
+            instance_id TEXT PRIMARY KEY,
+            hostname    TEXT NOT NULL,
+            last_seen   INTEGER NOT NULL
+        );
+
+        CREATE TABLE IF NOT EXISTS session_events (
+            id          INTEGER PRIMARY KEY AUTOINCREMENT,
+            session_id  TEXT NOT NULL,
+            from_status TEXT NOT NULL,
+            to_status   TEXT NOT NULL,
+            at          INTEGER NOT NULL
+        );
+        CREATE INDEX IF NOT EXISTS idx_session_events_session
+            ON session_events(session_id, at);
+
+        CREATE TABLE IF NOT EXISTS project_ui_prefs (
+            project_id     TEXT PRIMARY KEY,
+            info_panel     INTEGER NOT NULL DEFAULT 0,
+            session_tabs   INTEGER NOT NULL DEFAULT 0,
+            session_filter TEXT NOT NULL DEFAULT 'all',
+            updated_at     INTEGER NOT NULL
+        );
         ",
     )?;
 
@@ -127,34 +166,28 @@ pub fn initialize(conn: &Connection) -> rusqlite::Result<()> {
         [],
     )?;
 
-    migrate(conn)?;
-
     Ok(())
 }
 
-/// Run schema migrations for existing databases.
-fn migrate(conn: &Connection) -> rusqlite::Result<()> {
-    let version: u32 = conn
-        .query_row(
-            "SELECT value FROM metadata WHERE key = 'schema_version'",
-            [],
-            |row| {
-                let val: String = row.get(0)?;
-                Ok(val.parse().unwrap_or(0))
-            },
-        )
-        .unwrap_or(0);
-
-    if version < 3 {
-        // v2 → v3: add additional_dirs column to sessions
+/// A schema migration. Must be idempotent: `ALTER TABLE ... ADD COLUMN`
+/// results are ignored so a half-applied step can simply run again.
+type Migration = fn(&Connection) -> rusqlite::Result<()>;
+
+/// Ordered migrations, each tagged with the schema version it produces.
+/// Append new steps here; [`SCHEMA_VERSION`] follows the last entry.
+/// Fresh databases are created at the latest schema by [`initialize`] and
+/// skip them all.
+const MIGRATIONS: &[(u32, Migration)] = &[
+    (3, |conn| {
+        // add additional_dirs column to sessions
         let _ = conn.execute(
             "ALTER TABLE sessions ADD COLUMN additional_dirs TEXT NOT NULL DEFAULT ''",
             [],
         );
-    }
-
-    if version < 4 {
-        // v3 → v4: add project_mcp_servers table
+        Ok(())
+    }),
+    (4, |conn| {
+        // add project_mcp_servers table
         conn.execute_batch(
             "CREATE TABLE IF NOT EXISTS project_mcp_servers (
                 project_id  TEXT NOT NULL REFERENCES projects(id),
@@ -166,11 +199,10 @@ fn migrate(conn: &Connection) -> rusqlite::Result<()> {
                 updated_at  INTEGER NOT NULL,
                 PRIMARY KEY (project_id, server_name)
             );",
-        )?;
-    }
-
-    if version < 5 {
-        // v4 → v5: add session_commands table for MCP-driven session operations
+        )
+    }),
+    (5, |conn| {
+        // add session_commands table for MCP-driven session operations
         conn.execute_batch(
             "CREATE TABLE IF NOT EXISTS session_commands (
                 id           INTEGER PRIMARY KEY AUTOINCREMENT,
@@ -181,11 +213,10 @@ fn migrate(conn: &Connection) -> rusqlite::Result<()> {
             );
             CREATE INDEX IF NOT EXISTS idx_session_commands_pending
                 ON session_commands(id) WHERE processed_at IS NULL;",
-        )?;
-    }
-
-    if version < 6 {
-        // v5 → v6: change worktrees PK from session_id to (session_id, repo_path)
+        )
+    }),
+    (6, |conn| {
+        // change worktrees PK from session_id to (session_id, repo_path)
         // to support multiple worktrees per session (multi-repo projects).
         conn.execute_batch(
             "CREATE TABLE IF NOT EXISTS worktrees_new (
@@ -202,36 +233,313 @@ fn migrate(conn: &Connection) -> rusqlite::Result<()> {
                 FROM worktrees;
             DROP TABLE IF EXISTS worktrees;
             ALTER TABLE worktrees_new RENAME TO worktrees;",
-        )?;
-    }
-
-    if version < 7 {
-        // v6 → v7: add shell_backend_id column to sessions
+        )
+    }),
+    (7, |conn| {
+        // add shell_backend_id column to sessions
         let _ = conn.execute("ALTER TABLE sessions ADD COLUMN shell_backend_id TEXT", []);
-    }
-
-    if version < 8 {
-        // v7 → v8: add env column to project_roles (JSON-encoded environment variables)
+        Ok(())
+    }),
+    (8, |conn| {
+        // add env column to project_roles (JSON-encoded environment variables)
         let _ = conn.execute(
             "ALTER TABLE project_roles ADD COLUMN env TEXT NOT NULL DEFAULT ''",
             [],
         );
-    }
+        Ok(())
+    }),
+    (9, |conn| {
+        // add per-project sync base branch
+        let _ = conn.execute("ALTER TABLE projects ADD COLUMN sync_base_branch TEXT", []);
+        Ok(())
+    }),
+    (10, |conn| {
+        // add per-project sync strategy
+        let _ = conn.execute(
+            "ALTER TABLE projects ADD COLUMN sync_strategy TEXT NOT NULL DEFAULT 'rebase'",
+            [],
+        );
+        Ok(())
+    }),
+    (11, |conn| {
+        // add per-project activity timeout
+        let _ = conn.execute(
+            "ALTER TABLE projects ADD COLUMN activity_timeout_ms INTEGER",
+            [],
+        );
+        Ok(())
+    }),
+    (12, |conn| {
+        // allow MCP servers to be disabled without deleting them
+        let _ = conn.execute(
+            "ALTER TABLE project_mcp_servers ADD COLUMN enabled INTEGER NOT NULL DEFAULT 1",
+            [],
+        );
+        Ok(())
+    }),
+    (13, |conn| {
+        // add instances table for presence heartbeats
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS instances (
+                instance_id TEXT PRIMARY KEY,
+                hostname    TEXT NOT NULL,
+                last_seen   INTEGER NOT NULL
+            );",
+        )
+    }),
+    (14, |conn| {
+        // add per-project session ordering, seeded from creation order
+        if conn
+            .execute(
+                "ALTER TABLE sessions ADD COLUMN ordinal INTEGER NOT NULL DEFAULT 0",
+                [],
+            )
+            .is_ok()
+        {
+            conn.execute(
+                "UPDATE sessions SET ordinal = (
+                    SELECT COUNT(*) FROM sessions s
+                    WHERE s.project_id = sessions.project_id
+                    AND (s.created_at < sessions.created_at
+                        OR (s.created_at = sessions.created_at AND s.id < sessions.id))
+                )",
+                [],
+            )?;
+        }
+        Ok(())
+    }),
+    (15, |conn| {
+        // allow sessions to be pinned to the top of their project
+        let _ = conn.execute(
+            "ALTER TABLE sessions ADD COLUMN is_pinned INTEGER NOT NULL DEFAULT 0",
+            [],
+        );
+        Ok(())
+    }),
+    (16, |conn| {
+        // add per-project worktree location
+        let _ = conn.execute("ALTER TABLE projects ADD COLUMN worktree_root TEXT", []);
+        Ok(())
+    }),
+    (17, |conn| {
+        // add session status timeline
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS session_events (
+                id          INTEGER PRIMARY KEY AUTOINCREMENT,
+                session_id  TEXT NOT NULL,
+                from_status TEXT NOT NULL,
+                to_status   TEXT NOT NULL,
+                at          INTEGER NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS idx_session_events_session
+                ON session_events(session_id, at);",
+        )
+    }),
+    (18, |conn| {
+        // add per-project default role
+        let _ = conn.execute("ALTER TABLE projects ADD COLUMN default_role TEXT", []);
+        Ok(())
+    }),
+    (19, |conn| {
+        // record the base branch each worktree syncs onto
+        let _ = conn.execute(
+            "ALTER TABLE worktrees ADD COLUMN base_branch TEXT NOT NULL DEFAULT ''",
+            [],
+        );
+        Ok(())
+    }),
+    (20, |conn| {
+        // add per-project git remote
+        let _ = conn.execute("ALTER TABLE projects ADD COLUMN remote TEXT", []);
+        Ok(())
+    }),
+    (21, |conn| {
+        // add per-session label and label color
+        let _ = conn.execute("ALTER TABLE sessions ADD COLUMN label TEXT", []);
+        let _ = conn.execute("ALTER TABLE sessions ADD COLUMN label_color TEXT", []);
+        Ok(())
+    }),
+    (22, |conn| {
+        // add per-project session startup command
+        let _ = conn.execute(
+            "ALTER TABLE projects ADD COLUMN session_init_command TEXT",
+            [],
+        );
+        Ok(())
+    }),
+    (23, |conn| {
+        // add per-project UI preferences
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS project_ui_prefs (
+                project_id     TEXT PRIMARY KEY,
+                info_panel     INTEGER NOT NULL DEFAULT 0,
+                session_tabs   INTEGER NOT NULL DEFAULT 0,
+                session_filter TEXT NOT NULL DEFAULT 'all',
+                updated_at     INTEGER NOT NULL
+            );",
+        )
+    }),
+];
 
-    if version < SCHEMA_VERSION {
+/// Read the recorded schema version (0 when missing or unparsable).
+pub fn schema_version(conn: &Connection) -> u32 {
+    conn.query_row(
+        "SELECT value FROM metadata WHERE key = 'schema_version'",
+        [],
+        |row| {
+            let val: String = row.get(0)?;
+            Ok(val.parse().unwrap_or(0))
+        },
+    )
+    .unwrap_or(0)
+}
+
+/// Apply every migration newer than the recorded schema version, in order,
+/// recording the version after each step so an interrupted run resumes
+/// where it stopped. Returns the version reached.
+pub fn run_migrations(conn: &Connection) -> rusqlite::Result<u32> {
+    let mut version = schema_version(conn);
+
+    for &(target, migration) in MIGRATIONS {
+        if target <= version {
+            continue;
+        }
+        migration(conn)?;
         conn.execute(
-            "UPDATE metadata SET value = ?1 WHERE key = 'schema_version'",
-            [SCHEMA_VERSION.to_string()],
+            "INSERT OR REPLACE INTO metadata (key, value) VALUES ('schema_version', ?1)",
+            [target.to_string()],
         )?;
+        version = target;
     }
 
-    Ok(())
+    Ok(version)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn columns(conn: &Connection, table: &str) -> Vec<String> {
+        conn.prepare(&format!("PRAGMA table_info({table})"))
+            .unwrap()
+            .query_map([], |row| row.get(1))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap()
+    }
+
+    #[test]
+    fn migrations_are_strictly_ordered() {
+        assert!(MIGRATIONS.windows(2).all(|w| w[0].0 < w[1].0));
+        assert_eq!(SCHEMA_VERSION, MIGRATIONS.last().unwrap().0);
+    }
+
+    #[test]
+    fn fresh_db_is_at_latest_version() {
+        let conn = Connection::open_in_memory().unwrap();
+        initialize(&conn).unwrap();
+
+        assert_eq!(run_migrations(&conn).unwrap(), SCHEMA_VERSION);
+        assert_eq!(schema_version(&conn), SCHEMA_VERSION);
+    }
+
+    #[test]
+    fn version_zero_db_applies_all_migrations() {
+        let conn = Connection::open_in_memory().unwrap();
+        // Tables as they looked before any migration existed.
+        conn.execute_batch(
+            "CREATE TABLE metadata (key TEXT PRIMARY KEY, value TEXT NOT NULL);
+            INSERT INTO metadata (key, value) VALUES ('schema_version', '0');
+            CREATE TABLE projects (
+                id TEXT PRIMARY KEY, name TEXT NOT NULL,
+                is_default INTEGER NOT NULL DEFAULT 0,
+                created_at INTEGER NOT NULL, updated_at INTEGER NOT NULL,
+                deleted_at INTEGER
+            );
+            CREATE TABLE sessions (
+                id TEXT PRIMARY KEY, name TEXT NOT NULL,
+                project_id TEXT NOT NULL REFERENCES projects(id),
+                role TEXT NOT NULL DEFAULT 'developer',
+                backend_id TEXT NOT NULL DEFAULT '',
+                backend_type TEXT NOT NULL DEFAULT 'tmux',
+                claude_session_id TEXT, cwd TEXT,
+                created_at INTEGER NOT NULL, updated_at INTEGER NOT NULL,
+                deleted_at INTEGER
+            );",
+        )
+        .unwrap();
+        initialize(&conn).unwrap();
+        assert_eq!(schema_version(&conn), 0);
+
+        assert_eq!(run_migrations(&conn).unwrap(), SCHEMA_VERSION);
+        assert_eq!(schema_version(&conn), SCHEMA_VERSION);
+
+        let sessions = columns(&conn, "sessions");
+        assert!(sessions.contains(&"additional_dirs".to_string()));
+        assert!(sessions.contains(&"shell_backend_id".to_string()));
+        assert!(sessions.contains(&"ordinal".to_string()));
+        assert!(sessions.contains(&"is_pinned".to_string()));
+        assert!(sessions.contains(&"label".to_string()));
+        assert!(sessions.contains(&"label_color".to_string()));
+        let projects = columns(&conn, "projects");
+        assert!(projects.contains(&"sync_strategy".to_string()));
+        assert!(projects.contains(&"activity_timeout_ms".to_string()));
+        assert!(projects.contains(&"worktree_root".to_string()));
+        assert!(projects.contains(&"default_role".to_string()));
+        assert!(projects.contains(&"session_init_command".to_string()));
+        assert!(projects.contains(&"remote".to_string()));
+        assert!(columns(&conn, "worktrees").contains(&"base_branch".to_string()));
+        assert!(columns(&conn, "session_events").contains(&"to_status".to_string()));
+        assert!(columns(&conn, "project_ui_prefs").contains(&"session_tabs".to_string()));
+    }
+
+    #[test]
+    fn ordinal_migration_seeds_creation_order_per_project() {
+        let conn = Connection::open_in_memory().unwrap();
+        initialize(&conn).unwrap();
+        conn.execute_batch(
+            "ALTER TABLE sessions DROP COLUMN ordinal;
+            UPDATE metadata SET value = '13' WHERE key = 'schema_version';
+            INSERT INTO projects (id, name, created_at, updated_at) VALUES ('p', 'P', 0, 0);
+            INSERT INTO projects (id, name, created_at, updated_at) VALUES ('q', 'Q', 0, 0);
+            INSERT INTO sessions (id, name, project_id, created_at, updated_at)
+                VALUES ('b', 'B', 'p', 20, 20), ('a', 'A', 'p', 10, 10), ('c', 'C', 'q', 30, 30);",
+        )
+        .unwrap();
+
+        assert_eq!(run_migrations(&conn).unwrap(), SCHEMA_VERSION);
+
+        let ordinals: Vec<(String, i64)> = conn
+            .prepare("SELECT id, ordinal FROM sessions ORDER BY id")
+            .unwrap()
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(
+            ordinals,
+            vec![
+                ("a".to_string(), 0),
+                ("b".to_string(), 1),
+                ("c".to_string(), 0)
+            ]
+        );
+    }
+
+    #[test]
+    fn rerunning_migrations_is_harmless() {
+        let conn = Connection::open_in_memory().unwrap();
+        initialize(&conn).unwrap();
+        conn.execute(
+            "UPDATE metadata SET value = '0' WHERE key = 'schema_version'",
+            [],
+        )
+        .unwrap();
+
+        assert_eq!(run_migrations(&conn).unwrap(), SCHEMA_VERSION);
+        assert_eq!(run_migrations(&conn).unwrap(), SCHEMA_VERSION);
+    }
+
     #[test]
     fn schema_creates_all_tables() {
         let conn = Connection::open_in_memory().unwrap();
@@ -254,6 +562,7 @@ mod tests {
         assert!(tables.contains(&"worktrees".to_string()));
         assert!(tables.contains(&"audit_log".to_string()));
         assert!(tables.contains(&"session_commands".to_string()));
+        assert!(tables.contains(&"instances".to_string()));
     }
 
     #[test]
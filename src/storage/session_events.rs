@@ -0,0 +1,182 @@
+use rusqlite::params;
+
+use crate::session::{SessionId, SessionStatus};
+use crate::sync::current_time_millis;
+
+use super::Database;
+
+/// A stretch of time a session spent in one status, in Unix milliseconds.
+/// The latest span is still open and ends at the time of the query.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StatusSpan {
+    pub status: SessionStatus,
+    pub start: u64,
+    pub end: u64,
+}
+
+impl StatusSpan {
+    pub fn duration_ms(&self) -> u64 {
+        self.end.saturating_sub(self.start)
+    }
+}
+
+/// Busy share and transition count over a session's recorded timeline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ActivitySummary {
+    /// Percentage (0–100) of the recorded time spent Busy.
+    pub busy_percent: u8,
+    pub transitions: usize,
+}
+
+impl ActivitySummary {
+    /// Summarize `spans`; `None` when nothing has been recorded yet.
+    pub fn from_spans(spans: &[StatusSpan]) -> Option<Self> {
+        if spans.is_empty() {
+            return None;
+        }
+        let total: u64 = spans.iter().map(StatusSpan::duration_ms).sum();
+        let busy: u64 = spans
+            .iter()
+            .filter(|s| s.status == SessionStatus::Busy)
+            .map(StatusSpan::duration_ms)
+            .sum();
+        let busy_percent = busy
+            .saturating_mul(100)
+            .checked_div(total)
+            .map_or(0, |p| p.min(100) as u8);
+        Some(Self {
+            busy_percent,
+            transitions: spans.len(),
+        })
+    }
+}
+
+impl Database {
+    /// Record that a session moved from `from` to `to` at `at` (Unix ms).
+    pub fn record_status_change(
+        &self,
+        session_id: SessionId,
+        from: SessionStatus,
+        to: SessionStatus,
+        at: u64,
+    ) -> rusqlite::Result<()> {
+        self.conn.execute(
+            "INSERT INTO session_events (session_id, from_status, to_status, at) \
+             VALUES (?1, ?2, ?3, ?4)",
+            params![
+                session_id.to_string(),
+                from.as_str(),
+                to.as_str(),
+                at as i64
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// The session's status timeline: one span per recorded transition,
+    /// each lasting until the next. The last span ends now.
+    pub fn session_activity(&self, session_id: SessionId) -> rusqlite::Result<Vec<StatusSpan>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT to_status, at FROM session_events WHERE session_id = ?1 ORDER BY at, id",
+        )?;
+        let events = stmt
+            .query_map(params![session_id.to_string()], |row| {
+                let status: String = row.get(0)?;
+                let at: i64 = row.get(1)?;
+                Ok((status, at as u64))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let events: Vec<(SessionStatus, u64)> = events
+            .into_iter()
+            .filter_map(|(status, at)| Some((status.parse().ok()?, at)))
+            .collect();
+        Ok(status_spans(&events, current_time_millis()))
+    }
+}
+
+/// Turn `(status entered, at)` events, oldest first, into spans; the last
+/// one stays open until `now`.
+fn status_spans(events: &[(SessionStatus, u64)], now: u64) -> Vec<StatusSpan> {
+    events
+        .iter()
+        .enumerate()
+        .map(|(i, &(status, start))| StatusSpan {
+            status,
+            start,
+            end: events.get(i + 1).map_or(now.max(start), |&(_, next)| next),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use SessionStatus::*;
+
+    #[test]
+    fn records_events_in_order() {
+        let db = Database::open_in_memory().unwrap();
+        let id = SessionId::default();
+        let other = SessionId::default();
+
+        db.record_status_change(id, Busy, Waiting, 2_000).unwrap();
+        db.record_status_change(id, Idle, Busy, 1_000).unwrap();
+        db.record_status_change(other, Busy, Error, 1_500).unwrap();
+
+        let spans = db.session_activity(id).unwrap();
+        assert_eq!(spans.len(), 2);
+        assert_eq!(
+            spans[0],
+            StatusSpan {
+                status: Busy,
+                start: 1_000,
+                end: 2_000
+            }
+        );
+        assert_eq!(spans[1].status, Waiting);
+        assert_eq!(spans[1].start, 2_000);
+        assert!(spans[1].end >= 2_000);
+    }
+
+    #[test]
+    fn unknown_session_has_no_activity() {
+        let db = Database::open_in_memory().unwrap();
+        assert!(db
+            .session_activity(SessionId::default())
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn spans_close_at_next_event_and_now() {
+        let spans = status_spans(&[(Busy, 0), (Waiting, 300), (Busy, 400)], 1_000);
+        let durations: Vec<_> = spans.iter().map(|s| (s.status, s.duration_ms())).collect();
+        assert_eq!(durations, vec![(Busy, 300), (Waiting, 100), (Busy, 600)]);
+    }
+
+    #[test]
+    fn summary_aggregates_busy_share() {
+        let spans = status_spans(&[(Busy, 0), (Waiting, 300), (Busy, 900)], 1_000);
+        assert_eq!(
+            ActivitySummary::from_spans(&spans),
+            Some(ActivitySummary {
+                busy_percent: 40,
+                transitions: 3
+            })
+        );
+        assert_eq!(ActivitySummary::from_spans(&[]), None);
+    }
+
+    #[test]
+    fn summary_survives_long_and_empty_timelines() {
+        // busy * 100 would overflow u64 here; saturating keeps it in range
+        let spans = status_spans(&[(Busy, 0)], u64::MAX / 10);
+        let summary = ActivitySummary::from_spans(&spans).unwrap();
+        assert!(summary.busy_percent <= 100);
+
+        let spans = status_spans(&[(Busy, 500)], 500);
+        let summary = ActivitySummary::from_spans(&spans).unwrap();
+        assert_eq!(summary.busy_percent, 0);
+    }
+}
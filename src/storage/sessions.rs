@@ -1,6 +1,6 @@
 use std::path::PathBuf;
 
-use rusqlite::params;
+use rusqlite::{params, OptionalExtension};
 
 use crate::project::ProjectId;
 use crate::session::{SessionCommand, SessionId};
@@ -25,93 +25,119 @@ pub struct DeletedSessionInfo {
 impl Database {
     /// Insert or update a session.
     pub fn upsert_session(&self, session: &SharedSession) -> rusqlite::Result<()> {
-        let now = current_time_millis() as i64;
-        let id_str = session.id.to_string();
-        let project_id_str = session.project_id.to_string();
+        self.write_with_retry(|| {
+            let now = current_time_millis() as i64;
+            let id_str = session.id.to_string();
+            let project_id_str = session.project_id.to_string();
+
+            let existing: Option<String> = self
+                .conn
+                .query_row(
+                    "SELECT id FROM sessions WHERE id = ?1",
+                    params![id_str],
+                    |row| row.get(0),
+                )
+                .ok();
+
+            let additional_dirs_str: String = session
+                .additional_dirs
+                .iter()
+                .map(|p| p.display().to_string())
+                .collect::<Vec<_>>()
+                .join("\n");
+
+            if existing.is_some() {
+                self.conn.execute(
+                    "UPDATE sessions SET name = ?1, project_id = ?2, role = ?3, \
+                     backend_id = ?4, backend_type = ?5, claude_session_id = ?6, \
+                     cwd = ?7, additional_dirs = ?8, shell_backend_id = ?9, \
+                     is_pinned = ?10, label = ?11, label_color = ?12, updated_at = ?13, \
+                     deleted_at = NULL WHERE id = ?14",
+                    params![
+                        session.name,
+                        project_id_str,
+                        session.role,
+                        session.backend_id,
+                        session.backend_type,
+                        session.claude_session_id,
+                        session.cwd.as_ref().map(|p| p.display().to_string()),
+                        additional_dirs_str,
+                        session.shell_backend_id,
+                        session.is_pinned,
+                        session.label,
+                        session.label_color,
+                        now,
+                        id_str,
+                    ],
+                )?;
+
+                self.log_audit(
+                    EntityType::Session,
+                    &id_str,
+                    AuditAction::Updated,
+                    None,
+                    None,
+                    None,
+                )?;
+            } else {
+                // New sessions go to the end of their project's ordering.
+                self.conn.execute(
+                    "INSERT INTO sessions (id, name, project_id, role, backend_id, backend_type, \
+                     claude_session_id, cwd, additional_dirs, shell_backend_id, is_pinned, label, \
+                     label_color, ordinal, created_at, updated_at) \
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, \
+                     (SELECT COALESCE(MAX(ordinal) + 1, 0) FROM sessions WHERE project_id = ?3), \
+                     ?14, ?15)",
+                    params![
+                        id_str,
+                        session.name,
+                        project_id_str,
+                        session.role,
+                        session.backend_id,
+                        session.backend_type,
+                        session.claude_session_id,
+                        session.cwd.as_ref().map(|p| p.display().to_string()),
+                        additional_dirs_str,
+                        session.shell_backend_id,
+                        session.is_pinned,
+                        session.label,
+                        session.label_color,
+                        session.created_at.map_or(now, |t| t as i64),
+                        now,
+                    ],
+                )?;
+
+                self.log_audit(
+                    EntityType::Session,
+                    &id_str,
+                    AuditAction::Created,
+                    None,
+                    None,
+                    Some(&session.name),
+                )?;
+            }
 
-        let existing: Option<String> = self
-            .conn
-            .query_row(
-                "SELECT id FROM sessions WHERE id = ?1",
-                params![id_str],
-                |row| row.get(0),
-            )
-            .ok();
-
-        let additional_dirs_str: String = session
-            .additional_dirs
-            .iter()
-            .map(|p| p.display().to_string())
-            .collect::<Vec<_>>()
-            .join("\n");
-
-        if existing.is_some() {
-            self.conn.execute(
-                "UPDATE sessions SET name = ?1, project_id = ?2, role = ?3, \
-                 backend_id = ?4, backend_type = ?5, claude_session_id = ?6, \
-                 cwd = ?7, additional_dirs = ?8, shell_backend_id = ?9, \
-                 updated_at = ?10, deleted_at = NULL \
-                 WHERE id = ?11",
-                params![
-                    session.name,
-                    project_id_str,
-                    session.role,
-                    session.backend_id,
-                    session.backend_type,
-                    session.claude_session_id,
-                    session.cwd.as_ref().map(|p| p.display().to_string()),
-                    additional_dirs_str,
-                    session.shell_backend_id,
-                    now,
-                    id_str,
-                ],
-            )?;
+            // Upsert worktrees if present
+            if !session.worktrees.is_empty() {
+                self.upsert_worktrees(session.id, &session.worktrees)?;
+            }
 
-            self.log_audit(
-                EntityType::Session,
-                &id_str,
-                AuditAction::Updated,
-                None,
-                None,
-                None,
-            )?;
-        } else {
-            self.conn.execute(
-                "INSERT INTO sessions (id, name, project_id, role, backend_id, backend_type, \
-                 claude_session_id, cwd, additional_dirs, shell_backend_id, created_at, updated_at) \
-                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
-                params![
-                    id_str,
-                    session.name,
-                    project_id_str,
-                    session.role,
-                    session.backend_id,
-                    session.backend_type,
-                    session.claude_session_id,
-                    session.cwd.as_ref().map(|p| p.display().to_string()),
-                    additional_dirs_str,
-                    session.shell_backend_id,
-                    now,
-                    now,
-                ],
-            )?;
+            Ok(())
+        })
+    }
 
-            self.log_audit(
-                EntityType::Session,
-                &id_str,
-                AuditAction::Created,
-                None,
-                None,
-                Some(&session.name),
+    /// Store the display order of a project's sessions: each ID gets its
+    /// index in `ids` as its ordinal.
+    pub fn set_session_order(&self, ids: &[SessionId]) -> rusqlite::Result<()> {
+        let now = current_time_millis() as i64;
+        let tx = self.conn.unchecked_transaction()?;
+        for (ordinal, id) in ids.iter().enumerate() {
+            tx.execute(
+                "UPDATE sessions SET ordinal = ?1, updated_at = ?2 WHERE id = ?3",
+                params![ordinal as i64, now, id.to_string()],
             )?;
         }
-
-        // Upsert worktrees if present
-        if !session.worktrees.is_empty() {
-            self.upsert_worktrees(session.id, &session.worktrees)?;
-        }
-
-        Ok(())
+        tx.commit()
     }
 
     /// Soft-delete a session.
@@ -178,11 +204,12 @@ impl Database {
         let sql = format!(
             "SELECT s.id, s.name, s.project_id, s.role, s.backend_id, s.backend_type, \
              s.claude_session_id, s.cwd, s.additional_dirs, s.shell_backend_id, \
-             w.repo_path, w.worktree_path, w.branch \
+             w.repo_path, w.worktree_path, w.branch, s.is_pinned, w.base_branch, s.label, \
+             s.label_color, s.created_at \
              FROM sessions s \
              LEFT JOIN worktrees w ON s.id = w.session_id AND w.deleted_at IS NULL \
              WHERE {condition} \
-             ORDER BY s.created_at, w.created_at"
+             ORDER BY s.ordinal, s.created_at, w.created_at"
         );
 
         let mut stmt = self.conn.prepare(&sql)?;
@@ -195,6 +222,11 @@ impl Database {
             let wt_repo: Option<String> = row.get(10)?;
             let wt_path: Option<String> = row.get(11)?;
             let wt_branch: Option<String> = row.get(12)?;
+            let is_pinned: bool = row.get(13)?;
+            let wt_base: Option<String> = row.get(14)?;
+            let label: Option<String> = row.get(15)?;
+            let label_color: Option<String> = row.get(16)?;
+            let created_at: i64 = row.get(17)?;
 
             let additional_dirs: Vec<PathBuf> = if dirs_str.is_empty() {
                 Vec::new()
@@ -207,6 +239,7 @@ impl Database {
                     repo_path: PathBuf::from(repo),
                     worktree_path: PathBuf::from(path),
                     branch,
+                    base_branch: wt_base.unwrap_or_default(),
                 }),
                 _ => None,
             };
@@ -227,6 +260,10 @@ impl Database {
                     additional_dirs,
                     worktrees: Vec::new(),
                     shell_backend_id,
+                    is_pinned,
+                    label,
+                    label_color,
+                    created_at: Some(created_at as u64),
                     tombstone: false,
                     tombstone_at: None,
                 },
@@ -285,6 +322,57 @@ impl Database {
         Ok(next)
     }
 
+    /// Remember the active project and session so the next startup can
+    /// select them again. `None` forgets the stored value.
+    pub fn set_last_active(
+        &self,
+        project: Option<ProjectId>,
+        session: Option<SessionId>,
+    ) -> rusqlite::Result<()> {
+        let values = [
+            (
+                "last_active_project",
+                project.map(|p| p.as_uuid().to_string()),
+            ),
+            ("last_active_session", session.map(|s| s.to_string())),
+        ];
+        for (key, value) in values {
+            match value {
+                Some(value) => self.conn.execute(
+                    "INSERT OR REPLACE INTO metadata (key, value) VALUES (?1, ?2)",
+                    params![key, value],
+                )?,
+                None => self
+                    .conn
+                    .execute("DELETE FROM metadata WHERE key = ?1", params![key])?,
+            };
+        }
+        Ok(())
+    }
+
+    /// The project and session stored by [`Self::set_last_active`]. Missing
+    /// or unparsable values come back as `None`.
+    pub fn get_last_active(&self) -> rusqlite::Result<(Option<ProjectId>, Option<SessionId>)> {
+        let project = self
+            .metadata_value("last_active_project")?
+            .and_then(|v| v.parse::<uuid::Uuid>().ok())
+            .map(ProjectId::from_uuid);
+        let session = self
+            .metadata_value("last_active_session")?
+            .and_then(|v| v.parse().ok());
+        Ok((project, session))
+    }
+
+    fn metadata_value(&self, key: &str) -> rusqlite::Result<Option<String>> {
+        self.conn
+            .query_row(
+                "SELECT value FROM metadata WHERE key = ?1",
+                params![key],
+                |row| row.get(0),
+            )
+            .optional()
+    }
+
     /// Get a single active (non-deleted) session by its ID.
     pub fn get_session_by_id(&self, id: SessionId) -> rusqlite::Result<Option<SharedSession>> {
         let sessions = self.query_sessions(&format!("s.deleted_at IS NULL AND s.id = '{id}'"))?;
@@ -316,7 +404,7 @@ impl Database {
         let sql = format!(
             "SELECT s.id, s.name, s.project_id, s.role, s.claude_session_id, \
              s.cwd, s.deleted_at, \
-             w.repo_path, w.worktree_path, w.branch \
+             w.repo_path, w.worktree_path, w.branch, w.base_branch \
              FROM sessions s \
              LEFT JOIN worktrees w ON s.id = w.session_id \
              WHERE {condition} \
@@ -332,12 +420,14 @@ impl Database {
             let wt_repo: Option<String> = row.get(7)?;
             let wt_path: Option<String> = row.get(8)?;
             let wt_branch: Option<String> = row.get(9)?;
+            let wt_base: Option<String> = row.get(10)?;
 
             let worktree = match (wt_repo, wt_path, wt_branch) {
                 (Some(repo), Some(path), Some(branch)) => Some(SharedWorktree {
                     repo_path: PathBuf::from(repo),
                     worktree_path: PathBuf::from(path),
                     branch,
+                    base_branch: wt_base.unwrap_or_default(),
                 }),
                 _ => None,
             };
@@ -430,7 +520,7 @@ impl Database {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::project::ProjectConfig;
+    use crate::project::{ProjectConfig, SyncStrategy};
 
     fn test_project_id(name: &str) -> ProjectId {
         let config = ProjectConfig {
@@ -438,6 +528,13 @@ mod tests {
             repos: vec![],
             roles: vec![],
             mcp_servers: vec![],
+            sync_base_branch: None,
+            remote: None,
+            sync_strategy: SyncStrategy::default(),
+            activity_timeout_ms: None,
+            worktree_root: None,
+            default_role: None,
+            session_init_command: None,
             id: None,
         };
         config.deterministic_id()
@@ -456,6 +553,10 @@ mod tests {
             additional_dirs: Vec::new(),
             worktrees: Vec::new(),
             shell_backend_id: None,
+            is_pinned: false,
+            label: None,
+            label_color: None,
+            created_at: None,
             tombstone: false,
             tombstone_at: None,
         }
@@ -543,6 +644,111 @@ mod tests {
         assert_eq!(proj1_sessions[0].name, "S1");
     }
 
+    #[test]
+    fn upsert_persists_pin_state() {
+        let (db, pid) = setup_db_with_project();
+        let mut session = make_session("S", pid);
+        session.is_pinned = true;
+        db.upsert_session(&session).unwrap();
+        assert!(db.list_active_sessions().unwrap()[0].is_pinned);
+
+        session.is_pinned = false;
+        db.upsert_session(&session).unwrap();
+        assert!(!db.list_active_sessions().unwrap()[0].is_pinned);
+    }
+
+    #[test]
+    fn upsert_persists_label() {
+        let (db, pid) = setup_db_with_project();
+        let mut session = make_session("S", pid);
+        db.upsert_session(&session).unwrap();
+        let stored = &db.list_active_sessions().unwrap()[0];
+        assert_eq!(stored.label, None);
+        assert_eq!(stored.label_color, None);
+
+        session.label = Some("frontend".to_string());
+        session.label_color = Some("#ff8800".to_string());
+        db.upsert_session(&session).unwrap();
+        let stored = &db.list_active_sessions().unwrap()[0];
+        assert_eq!(stored.label.as_deref(), Some("frontend"));
+        assert_eq!(stored.label_color.as_deref(), Some("#ff8800"));
+    }
+
+    #[test]
+    fn upsert_round_trips_created_at() {
+        let (db, pid) = setup_db_with_project();
+        let mut session = make_session("S", pid);
+        session.created_at = Some(1_700_000_000_000);
+        db.upsert_session(&session).unwrap();
+        assert_eq!(
+            db.list_active_sessions().unwrap()[0].created_at,
+            Some(1_700_000_000_000)
+        );
+
+        // Updates never move the creation time.
+        session.created_at = Some(1_800_000_000_000);
+        session.name = "renamed".to_string();
+        db.upsert_session(&session).unwrap();
+        assert_eq!(
+            db.list_active_sessions().unwrap()[0].created_at,
+            Some(1_700_000_000_000)
+        );
+    }
+
+    #[test]
+    fn upsert_without_created_at_stamps_insert_time() {
+        let (db, pid) = setup_db_with_project();
+        let before = current_time_millis();
+        db.upsert_session(&make_session("S", pid)).unwrap();
+        let created_at = db.list_active_sessions().unwrap()[0].created_at.unwrap();
+        assert!(created_at >= before);
+    }
+
+    #[test]
+    fn set_session_order_persists_order() {
+        let (db, pid) = setup_db_with_project();
+        let sessions: Vec<_> = ["A", "B", "C"]
+            .into_iter()
+            .map(|name| make_session(name, pid))
+            .collect();
+        for session in &sessions {
+            db.upsert_session(session).unwrap();
+        }
+
+        db.set_session_order(&[sessions[2].id, sessions[0].id, sessions[1].id])
+            .unwrap();
+
+        let names: Vec<_> = db
+            .list_active_sessions()
+            .unwrap()
+            .into_iter()
+            .map(|s| s.name)
+            .collect();
+        assert_eq!(names, vec!["C", "A", "B"]);
+    }
+
+    #[test]
+    fn new_session_goes_after_reordered_ones() {
+        let (db, pid) = setup_db_with_project();
+        let a = make_session("A", pid);
+        let b = make_session("B", pid);
+        db.upsert_session(&a).unwrap();
+        db.upsert_session(&b).unwrap();
+        db.set_session_order(&[b.id, a.id]).unwrap();
+
+        db.upsert_session(&make_session("C", pid)).unwrap();
+        // Updating an existing session keeps its place.
+        db.upsert_session(&b).unwrap();
+
+        let names: Vec<_> = db
+            .list_active_sessions()
+            .unwrap()
+            .into_iter()
+            .map(|s| s.name)
+            .collect();
+        assert_eq!(names, vec!["B", "A", "C"]);
+    }
+
     #[test]
     fn session_with_worktree() {
         let (db, pid) = setup_db_with_project();
@@ -551,6 +757,7 @@ mod tests {
             repo_path: PathBuf::from("/repo"),
             worktree_path: PathBuf::from("/repo/.git/wt/feat"),
             branch: "feat".to_string(),
+            base_branch: "main".to_string(),
         }];
 
         db.upsert_session(&session).unwrap();
@@ -570,11 +777,13 @@ mod tests {
                 repo_path: PathBuf::from("/repo1"),
                 worktree_path: PathBuf::from("/repo1/.git/wt/feat"),
                 branch: "feat".to_string(),
+                base_branch: "main".to_string(),
             },
             SharedWorktree {
                 repo_path: PathBuf::from("/repo2"),
                 worktree_path: PathBuf::from("/repo2/.git/wt/feat"),
                 branch: "feat".to_string(),
+                base_branch: "main".to_string(),
             },
         ];
 
@@ -614,6 +823,39 @@ mod tests {
         assert_eq!(db.get_session_counter().unwrap(), 6);
     }
 
+    #[test]
+    fn last_active_roundtrip() {
+        let db = Database::open_in_memory().unwrap();
+        assert_eq!(db.get_last_active().unwrap(), (None, None));
+
+        let project = test_project_id("api");
+        let session = SessionId::default();
+        db.set_last_active(Some(project), Some(session)).unwrap();
+        assert_eq!(
+            db.get_last_active().unwrap(),
+            (Some(project), Some(session))
+        );
+
+        // Overwrites, and None forgets just that value
+        let other = SessionId::default();
+        db.set_last_active(Some(project), Some(other)).unwrap();
+        assert_eq!(db.get_last_active().unwrap(), (Some(project), Some(other)));
+        db.set_last_active(Some(project), None).unwrap();
+        assert_eq!(db.get_last_active().unwrap(), (Some(project), None));
+    }
+
+    #[test]
+    fn last_active_ignores_garbage_values() {
+        let db = Database::open_in_memory().unwrap();
+        db.conn
+            .execute(
+                "INSERT INTO metadata (key, value) VALUES ('last_active_session', 'nope')",
+                [],
+            )
+            .unwrap();
+        assert_eq!(db.get_last_active().unwrap(), (None, None));
+    }
+
     #[test]
     fn session_additional_dirs_preserved() {
         let (db, pid) = setup_db_with_project();
@@ -841,6 +1083,7 @@ mod tests {
             repo_path: PathBuf::from("/repo"),
             worktree_path: PathBuf::from("/repo/.git/wt/feat"),
             branch: "feat".to_string(),
+            base_branch: "main".to_string(),
         }];
         let sid = session.id;
         db.upsert_session(&session).unwrap();
@@ -48,7 +48,7 @@ impl Database {
 mod tests {
     use std::path::PathBuf;
 
-    use crate::project::ProjectConfig;
+    use crate::project::{ProjectConfig, SyncStrategy};
     use crate::session::SessionId;
     use crate::sync::{SharedSession, SharedState};
 
@@ -60,6 +60,13 @@ mod tests {
             repos: vec![],
             roles: vec![],
             mcp_servers: vec![],
+            sync_base_branch: None,
+            remote: None,
+            sync_strategy: SyncStrategy::default(),
+            activity_timeout_ms: None,
+            worktree_root: None,
+            default_role: None,
+            session_init_command: None,
             id: None,
         };
         config.deterministic_id()
@@ -78,6 +85,10 @@ mod tests {
             additional_dirs: Vec::new(),
             worktrees: Vec::new(),
             shell_backend_id: None,
+            is_pinned: false,
+            label: None,
+            label_color: None,
+            created_at: None,
             tombstone: false,
             tombstone_at: None,
         }
@@ -0,0 +1,210 @@
+use std::time::Duration;
+
+use rusqlite::params;
+
+use crate::sync::current_time_millis;
+
+use super::Database;
+
+/// Soft-deleted sessions whose tombstone is older than the cutoff (`?1`).
+const AGED_SESSIONS: &str =
+    "SELECT id FROM sessions WHERE deleted_at IS NOT NULL AND deleted_at < ?1";
+
+/// Soft-deleted projects past the cutoff that no session row still
+/// references (sessions hold a foreign key to their project).
+const AGED_PROJECTS: &str = "SELECT p.id FROM projects p \
+     WHERE p.deleted_at IS NOT NULL AND p.deleted_at < ?1 \
+     AND NOT EXISTS (SELECT 1 FROM sessions s WHERE s.project_id = p.id)";
+
+impl Database {
+    /// Hard-delete sessions and projects that were soft-deleted more than
+    /// `older_than` ago, along with their worktrees, repos, roles, MCP
+    /// servers, UI preferences, queued commands and status timelines.
+    /// Returns how many sessions and projects were removed. The audit log
+    /// is kept.
+    ///
+    /// Other instances never read tombstone rows: they notice a deletion
+    /// when the row leaves the active set on their next poll. Nothing
+    /// records whether a tombstone has been observed, so the age threshold
+    /// is the only safety margin and should be far longer than any poll.
+    pub fn purge_tombstones(&self, older_than: Duration) -> rusqlite::Result<usize> {
+        let cutoff = current_time_millis().saturating_sub(older_than.as_millis() as u64) as i64;
+        let tx = self.conn.unchecked_transaction()?;
+
+        tx.execute(
+            &format!("DELETE FROM worktrees WHERE session_id IN ({AGED_SESSIONS})"),
+            params![cutoff],
+        )?;
+        tx.execute(
+            &format!("DELETE FROM session_commands WHERE session_id IN ({AGED_SESSIONS})"),
+            params![cutoff],
+        )?;
+        tx.execute(
+            &format!("DELETE FROM session_events WHERE session_id IN ({AGED_SESSIONS})"),
+            params![cutoff],
+        )?;
+        let sessions = tx.execute(
+            "DELETE FROM sessions WHERE deleted_at IS NOT NULL AND deleted_at < ?1",
+            params![cutoff],
+        )?;
+
+        for table in [
+            "project_repos",
+            "project_roles",
+            "project_mcp_servers",
+            "project_ui_prefs",
+        ] {
+            tx.execute(
+                &format!("DELETE FROM {table} WHERE project_id IN ({AGED_PROJECTS})"),
+                params![cutoff],
+            )?;
+        }
+        let projects = tx.execute(
+            &format!("DELETE FROM projects WHERE id IN ({AGED_PROJECTS})"),
+            params![cutoff],
+        )?;
+
+        tx.commit()?;
+        Ok(sessions + projects)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+    use crate::project::ProjectId;
+    use crate::session::SessionId;
+    use crate::sync::{SharedSession, SharedWorktree};
+
+    const WEEK: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
+    fn make_session(project_id: ProjectId) -> SharedSession {
+        SharedSession {
+            id: SessionId::default(),
+            name: "Session".to_string(),
+            project_id,
+            role: "developer".to_string(),
+            backend_id: "thurbox:@0".to_string(),
+            backend_type: "tmux".to_string(),
+            claude_session_id: None,
+            cwd: None,
+            additional_dirs: Vec::new(),
+            worktrees: vec![SharedWorktree {
+                repo_path: PathBuf::from("/repo"),
+                worktree_path: PathBuf::from("/repo/.git/thurbox-worktrees/feat"),
+                branch: "feat".to_string(),
+                base_branch: "main".to_string(),
+            }],
+            shell_backend_id: None,
+            is_pinned: false,
+            label: None,
+            label_color: None,
+            created_at: None,
+            tombstone: false,
+            tombstone_at: None,
+        }
+    }
+
+    fn new_project(db: &Database) -> ProjectId {
+        let id = ProjectId::default();
+        db.insert_project(id, "test", &[PathBuf::from("/repo")])
+            .unwrap();
+        id
+    }
+
+    /// Backdate a tombstone by `age`.
+    fn age_tombstone(db: &Database, table: &str, id: &str, age: Duration) {
+        let at = current_time_millis() as i64 - age.as_millis() as i64;
+        db.conn
+            .execute(
+                &format!("UPDATE {table} SET deleted_at = ?1 WHERE id = ?2"),
+                params![at, id],
+            )
+            .unwrap();
+    }
+
+    fn count(db: &Database, sql: &str) -> i64 {
+        db.conn.query_row(sql, [], |row| row.get(0)).unwrap()
+    }
+
+    #[test]
+    fn purges_aged_session_tombstones_only() {
+        let db = Database::open_in_memory().unwrap();
+        let pid = new_project(&db);
+        let aged = make_session(pid);
+        let fresh = make_session(pid);
+        let active = make_session(pid);
+        for session in [&aged, &fresh, &active] {
+            db.upsert_session(session).unwrap();
+        }
+        db.soft_delete_session(aged.id).unwrap();
+        db.soft_delete_session(fresh.id).unwrap();
+        age_tombstone(&db, "sessions", &aged.id.to_string(), WEEK * 2);
+        db.enqueue_session_command(aged.id, "restart").unwrap();
+
+        assert_eq!(db.purge_tombstones(WEEK).unwrap(), 1);
+
+        assert!(db.get_deleted_session_by_id(aged.id).unwrap().is_none());
+        assert!(db.get_deleted_session_by_id(fresh.id).unwrap().is_some());
+        assert_eq!(db.list_active_sessions().unwrap().len(), 1);
+        assert_eq!(count(&db, "SELECT COUNT(*) FROM worktrees"), 2);
+        assert_eq!(count(&db, "SELECT COUNT(*) FROM session_commands"), 0);
+    }
+
+    #[test]
+    fn purges_aged_project_tombstones() {
+        let db = Database::open_in_memory().unwrap();
+        let aged = new_project(&db);
+        let fresh = new_project(&db);
+        db.soft_delete_project(aged).unwrap();
+        db.soft_delete_project(fresh).unwrap();
+        age_tombstone(&db, "projects", &aged.to_string(), WEEK * 2);
+
+        assert_eq!(db.purge_tombstones(WEEK).unwrap(), 1);
+
+        let remaining = db.list_all_projects().unwrap();
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].id, fresh);
+        assert_eq!(count(&db, "SELECT COUNT(*) FROM project_repos"), 1);
+    }
+
+    #[test]
+    fn keeps_aged_project_with_remaining_sessions() {
+        let db = Database::open_in_memory().unwrap();
+        let pid = new_project(&db);
+        let session = make_session(pid);
+        db.upsert_session(&session).unwrap();
+        db.soft_delete_project(pid).unwrap();
+        age_tombstone(&db, "projects", &pid.to_string(), WEEK * 2);
+
+        assert_eq!(db.purge_tombstones(WEEK).unwrap(), 0);
+        assert_eq!(db.list_all_projects().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn purges_project_once_its_sessions_are_purged() {
+        let db = Database::open_in_memory().unwrap();
+        let pid = new_project(&db);
+        let session = make_session(pid);
+        db.upsert_session(&session).unwrap();
+        db.soft_delete_session(session.id).unwrap();
+        db.soft_delete_project(pid).unwrap();
+        age_tombstone(&db, "sessions", &session.id.to_string(), WEEK * 2);
+        age_tombstone(&db, "projects", &pid.to_string(), WEEK * 2);
+
+        assert_eq!(db.purge_tombstones(WEEK).unwrap(), 2);
+        assert!(db.list_all_projects().unwrap().is_empty());
+    }
+
+    #[test]
+    fn nothing_to_purge() {
+        let db = Database::open_in_memory().unwrap();
+        let pid = new_project(&db);
+        db.upsert_session(&make_session(pid)).unwrap();
+
+        assert_eq!(db.purge_tombstones(WEEK).unwrap(), 0);
+        assert_eq!(db.list_active_sessions().unwrap().len(), 1);
+    }
+}
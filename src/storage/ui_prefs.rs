@@ -0,0 +1,86 @@
+use rusqlite::{params, OptionalExtension};
+
+use crate::project::ProjectId;
+use crate::sync::current_time_millis;
+
+use super::Database;
+
+/// UI choices remembered per project and restored when it becomes active.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProjectUiPrefs {
+    pub info_panel: bool,
+    /// Session tab bar above the terminal instead of the left panel.
+    pub session_tabs: bool,
+    /// Session list filter (`all`, `waiting` or `busy`).
+    pub session_filter: String,
+}
+
+impl Database {
+    /// The UI preferences stored for a project, if any.
+    pub fn get_project_ui_prefs(&self, id: ProjectId) -> rusqlite::Result<Option<ProjectUiPrefs>> {
+        self.conn
+            .query_row(
+                "SELECT info_panel, session_tabs, session_filter \
+                 FROM project_ui_prefs WHERE project_id = ?1",
+                params![id.to_string()],
+                |row| {
+                    Ok(ProjectUiPrefs {
+                        info_panel: row.get(0)?,
+                        session_tabs: row.get(1)?,
+                        session_filter: row.get(2)?,
+                    })
+                },
+            )
+            .optional()
+    }
+
+    /// Store a project's UI preferences, replacing earlier ones.
+    pub fn set_project_ui_prefs(
+        &self,
+        id: ProjectId,
+        prefs: &ProjectUiPrefs,
+    ) -> rusqlite::Result<()> {
+        self.conn.execute(
+            "INSERT OR REPLACE INTO project_ui_prefs \
+             (project_id, info_panel, session_tabs, session_filter, updated_at) \
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                id.to_string(),
+                prefs.info_panel,
+                prefs.session_tabs,
+                prefs.session_filter,
+                current_time_millis() as i64
+            ],
+        )?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prefs_roundtrip_per_project() {
+        let db = Database::open_in_memory().unwrap();
+        let a = ProjectId::from_name("a");
+        let b = ProjectId::from_name("b");
+        assert_eq!(db.get_project_ui_prefs(a).unwrap(), None);
+
+        let prefs = ProjectUiPrefs {
+            info_panel: true,
+            session_tabs: false,
+            session_filter: "waiting".to_string(),
+        };
+        db.set_project_ui_prefs(a, &prefs).unwrap();
+        assert_eq!(db.get_project_ui_prefs(a).unwrap(), Some(prefs));
+        assert_eq!(db.get_project_ui_prefs(b).unwrap(), None);
+
+        let updated = ProjectUiPrefs {
+            session_tabs: true,
+            ..ProjectUiPrefs::default()
+        };
+        db.set_project_ui_prefs(a, &updated).unwrap();
+        assert_eq!(db.get_project_ui_prefs(a).unwrap(), Some(updated));
+    }
+}
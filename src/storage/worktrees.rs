@@ -27,13 +27,14 @@ impl Database {
         // Insert all new rows
         for wt in worktrees {
             self.conn.execute(
-                "INSERT INTO worktrees (session_id, repo_path, worktree_path, branch, created_at, deleted_at) \
-                 VALUES (?1, ?2, ?3, ?4, ?5, NULL)",
+                "INSERT INTO worktrees (session_id, repo_path, worktree_path, branch, base_branch, created_at, deleted_at) \
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, NULL)",
                 params![
                     sid,
                     wt.repo_path.display().to_string(),
                     wt.worktree_path.display().to_string(),
                     wt.branch,
+                    wt.base_branch,
                     now,
                 ],
             )?;
@@ -90,7 +91,7 @@ impl Database {
         let sid = session_id.to_string();
 
         let mut stmt = self.conn.prepare(
-            "SELECT repo_path, worktree_path, branch FROM worktrees \
+            "SELECT repo_path, worktree_path, branch, base_branch FROM worktrees \
              WHERE session_id = ?1 AND deleted_at IS NULL \
              ORDER BY created_at",
         )?;
@@ -99,10 +100,12 @@ impl Database {
             let repo: String = row.get(0)?;
             let wt_path: String = row.get(1)?;
             let branch: String = row.get(2)?;
+            let base_branch: String = row.get(3)?;
             Ok(SharedWorktree {
                 repo_path: std::path::PathBuf::from(repo),
                 worktree_path: std::path::PathBuf::from(wt_path),
                 branch,
+                base_branch,
             })
         })?;
 
@@ -114,7 +117,7 @@ impl Database {
 mod tests {
     use std::path::PathBuf;
 
-    use crate::project::ProjectConfig;
+    use crate::project::{ProjectConfig, SyncStrategy};
     use crate::sync::SharedSession;
 
     use super::*;
@@ -125,6 +128,13 @@ mod tests {
             repos: vec![],
             roles: vec![],
             mcp_servers: vec![],
+            sync_base_branch: None,
+            remote: None,
+            sync_strategy: SyncStrategy::default(),
+            activity_timeout_ms: None,
+            worktree_root: None,
+            default_role: None,
+            session_init_command: None,
             id: None,
         };
         config.deterministic_id()
@@ -147,6 +157,10 @@ mod tests {
             additional_dirs: Vec::new(),
             worktrees: Vec::new(),
             shell_backend_id: None,
+            is_pinned: false,
+            label: None,
+            label_color: None,
+            created_at: None,
             tombstone: false,
             tombstone_at: None,
         };
@@ -162,6 +176,7 @@ mod tests {
             repo_path: PathBuf::from("/repo"),
             worktree_path: PathBuf::from("/repo/.git/wt/feat"),
             branch: "feat".to_string(),
+            base_branch: "main".to_string(),
         };
 
         db.upsert_worktrees(sid, &[wt]).unwrap();
@@ -169,6 +184,7 @@ mod tests {
         let result = db.get_worktrees(sid).unwrap();
         assert_eq!(result.len(), 1);
         assert_eq!(result[0].branch, "feat");
+        assert_eq!(result[0].base_branch, "main");
         assert_eq!(result[0].repo_path, PathBuf::from("/repo"));
     }
 
@@ -180,11 +196,13 @@ mod tests {
                 repo_path: PathBuf::from("/repo1"),
                 worktree_path: PathBuf::from("/repo1/.git/wt/feat"),
                 branch: "feat".to_string(),
+                base_branch: "main".to_string(),
             },
             SharedWorktree {
                 repo_path: PathBuf::from("/repo2"),
                 worktree_path: PathBuf::from("/repo2/.git/wt/feat"),
                 branch: "feat".to_string(),
+                base_branch: "main".to_string(),
             },
         ];
 
@@ -203,6 +221,7 @@ mod tests {
             repo_path: PathBuf::from("/repo"),
             worktree_path: PathBuf::from("/repo/.git/wt/feat"),
             branch: "feat".to_string(),
+            base_branch: "main".to_string(),
         };
 
         db.upsert_worktrees(sid, &[wt]).unwrap();
@@ -218,6 +237,7 @@ mod tests {
             repo_path: PathBuf::from("/repo"),
             worktree_path: PathBuf::from("/repo/.git/wt/feat"),
             branch: "feat".to_string(),
+            base_branch: "main".to_string(),
         };
 
         db.upsert_worktrees(sid, &[wt]).unwrap();
@@ -247,6 +267,7 @@ mod tests {
             repo_path: PathBuf::from("/repo"),
             worktree_path: PathBuf::from("/repo/.git/wt/old"),
             branch: "old".to_string(),
+            base_branch: "main".to_string(),
         };
         db.upsert_worktrees(sid, &[wt1]).unwrap();
 
@@ -254,6 +275,7 @@ mod tests {
             repo_path: PathBuf::from("/repo"),
             worktree_path: PathBuf::from("/repo/.git/wt/new"),
             branch: "new".to_string(),
+            base_branch: "main".to_string(),
         };
         db.upsert_worktrees(sid, &[wt2]).unwrap();
 
@@ -139,6 +139,9 @@ fn session_changed(old: &SharedSession, new: &SharedSession) -> bool {
         || old.cwd != new.cwd
         || old.additional_dirs != new.additional_dirs
         || old.worktrees != new.worktrees
+        || old.is_pinned != new.is_pinned
+        || old.label != new.label
+        || old.label_color != new.label_color
 }
 
 /// Check if a project's key metadata changed.
@@ -147,12 +150,19 @@ fn project_changed(old: &SharedProject, new: &SharedProject) -> bool {
         || old.repos != new.repos
         || old.roles != new.roles
         || old.mcp_servers != new.mcp_servers
+        || old.sync_base_branch != new.sync_base_branch
+        || old.remote != new.remote
+        || old.sync_strategy != new.sync_strategy
+        || old.activity_timeout_ms != new.activity_timeout_ms
+        || old.worktree_root != new.worktree_root
+        || old.default_role != new.default_role
+        || old.session_init_command != new.session_init_command
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::project::ProjectId;
+    use crate::project::{ProjectId, SyncStrategy};
     use std::path::PathBuf;
 
     #[test]
@@ -179,6 +189,10 @@ mod tests {
             additional_dirs: Vec::new(),
             worktrees: Vec::new(),
             shell_backend_id: None,
+            is_pinned: false,
+            label: None,
+            label_color: None,
+            created_at: None,
             tombstone: false,
             tombstone_at: None,
         };
@@ -208,6 +222,10 @@ mod tests {
             additional_dirs: Vec::new(),
             worktrees: Vec::new(),
             shell_backend_id: None,
+            is_pinned: false,
+            label: None,
+            label_color: None,
+            created_at: None,
             tombstone: false,
             tombstone_at: None,
         };
@@ -240,6 +258,10 @@ mod tests {
             additional_dirs: Vec::new(),
             worktrees: Vec::new(),
             shell_backend_id: None,
+            is_pinned: false,
+            label: None,
+            label_color: None,
+            created_at: None,
             tombstone: false,
             tombstone_at: None,
         };
@@ -258,6 +280,10 @@ mod tests {
             additional_dirs: Vec::new(),
             worktrees: Vec::new(),
             shell_backend_id: None,
+            is_pinned: false,
+            label: None,
+            label_color: None,
+            created_at: None,
             tombstone: false,
             tombstone_at: None,
         };
@@ -288,6 +314,10 @@ mod tests {
             additional_dirs: Vec::new(),
             worktrees: Vec::new(),
             shell_backend_id: None,
+            is_pinned: false,
+            label: None,
+            label_color: None,
+            created_at: None,
             tombstone: false,
             tombstone_at: None,
         };
@@ -306,6 +336,10 @@ mod tests {
             additional_dirs: Vec::new(),
             worktrees: Vec::new(),
             shell_backend_id: None,
+            is_pinned: false,
+            label: None,
+            label_color: None,
+            created_at: None,
             tombstone: true, // Marked as deleted
             tombstone_at: Some(0),
         };
@@ -353,6 +387,10 @@ mod tests {
             additional_dirs: Vec::new(),
             worktrees: Vec::new(),
             shell_backend_id: None,
+            is_pinned: false,
+            label: None,
+            label_color: None,
+            created_at: None,
             tombstone: false,
             tombstone_at: None,
         });
@@ -368,6 +406,10 @@ mod tests {
             additional_dirs: Vec::new(),
             worktrees: Vec::new(),
             shell_backend_id: None,
+            is_pinned: false,
+            label: None,
+            label_color: None,
+            created_at: None,
             tombstone: false,
             tombstone_at: None,
         });
@@ -387,6 +429,10 @@ mod tests {
             additional_dirs: Vec::new(),
             worktrees: Vec::new(),
             shell_backend_id: None,
+            is_pinned: false,
+            label: None,
+            label_color: None,
+            created_at: None,
             tombstone: false,
             tombstone_at: None,
         });
@@ -403,6 +449,10 @@ mod tests {
             additional_dirs: Vec::new(),
             worktrees: Vec::new(),
             shell_backend_id: None,
+            is_pinned: false,
+            label: None,
+            label_color: None,
+            created_at: None,
             tombstone: true,
             tombstone_at: Some(0),
         });
@@ -419,6 +469,10 @@ mod tests {
             additional_dirs: Vec::new(),
             worktrees: Vec::new(),
             shell_backend_id: None,
+            is_pinned: false,
+            label: None,
+            label_color: None,
+            created_at: None,
             tombstone: false,
             tombstone_at: None,
         });
@@ -449,6 +503,10 @@ mod tests {
             additional_dirs: Vec::new(),
             worktrees: Vec::new(),
             shell_backend_id: None,
+            is_pinned: false,
+            label: None,
+            label_color: None,
+            created_at: None,
             tombstone: false,
             tombstone_at: None,
         };
@@ -467,6 +525,10 @@ mod tests {
             additional_dirs: Vec::new(),
             worktrees: Vec::new(),
             shell_backend_id: None,
+            is_pinned: false,
+            label: None,
+            label_color: None,
+            created_at: None,
             tombstone: false,
             tombstone_at: None,
         };
@@ -498,6 +560,10 @@ mod tests {
             additional_dirs: Vec::new(),
             worktrees: Vec::new(),
             shell_backend_id: None,
+            is_pinned: false,
+            label: None,
+            label_color: None,
+            created_at: None,
             tombstone: false,
             tombstone_at: None,
         };
@@ -516,6 +582,10 @@ mod tests {
             additional_dirs: Vec::new(),
             worktrees: Vec::new(),
             shell_backend_id: None,
+            is_pinned: false,
+            label: None,
+            label_color: None,
+            created_at: None,
             tombstone: false,
             tombstone_at: None,
         };
@@ -526,6 +596,45 @@ mod tests {
         assert_eq!(delta.updated_sessions.len(), 1);
     }
 
+    #[test]
+    fn session_changed_detects_pin_change() {
+        let old_session = SharedSession {
+            id: SessionId::default(),
+            name: "Session".to_string(),
+            project_id: ProjectId::default(),
+            role: "developer".to_string(),
+            backend_id: "thurbox:@0".to_string(),
+            backend_type: "tmux".to_string(),
+            claude_session_id: None,
+            cwd: None,
+            additional_dirs: Vec::new(),
+            worktrees: Vec::new(),
+            shell_backend_id: None,
+            is_pinned: false,
+            label: None,
+            label_color: None,
+            created_at: None,
+            tombstone: false,
+            tombstone_at: None,
+        };
+        let new_session = SharedSession {
+            is_pinned: true,
+            label: None,
+            label_color: None,
+            ..old_session.clone()
+        };
+
+        let mut old_state = SharedState::new();
+        old_state.sessions.push(old_session);
+        let mut new_state = SharedState::new();
+        new_state.sessions.push(new_session);
+
+        let delta = StateDelta::compute(&old_state, &new_state);
+
+        assert_eq!(delta.updated_sessions.len(), 1);
+        assert!(delta.updated_sessions[0].is_pinned);
+    }
+
     #[test]
     fn session_changed_detects_cwd_change() {
         let session_id = SessionId::default();
@@ -544,6 +653,10 @@ mod tests {
             additional_dirs: Vec::new(),
             worktrees: Vec::new(),
             shell_backend_id: None,
+            is_pinned: false,
+            label: None,
+            label_color: None,
+            created_at: None,
             tombstone: false,
             tombstone_at: None,
         };
@@ -562,6 +675,10 @@ mod tests {
             additional_dirs: Vec::new(),
             worktrees: Vec::new(),
             shell_backend_id: None,
+            is_pinned: false,
+            label: None,
+            label_color: None,
+            created_at: None,
             tombstone: false,
             tombstone_at: None,
         };
@@ -594,8 +711,13 @@ mod tests {
                 repo_path: PathBuf::from("/repo"),
                 worktree_path: PathBuf::from("/repo/.git/worktrees/old"),
                 branch: "old-branch".to_string(),
+                base_branch: "main".to_string(),
             }],
             shell_backend_id: None,
+            is_pinned: false,
+            label: None,
+            label_color: None,
+            created_at: None,
             tombstone: false,
             tombstone_at: None,
         };
@@ -616,8 +738,13 @@ mod tests {
                 repo_path: PathBuf::from("/repo"),
                 worktree_path: PathBuf::from("/repo/.git/worktrees/new"),
                 branch: "new-branch".to_string(),
+                base_branch: "main".to_string(),
             }],
             shell_backend_id: None,
+            is_pinned: false,
+            label: None,
+            label_color: None,
+            created_at: None,
             tombstone: false,
             tombstone_at: None,
         };
@@ -650,8 +777,13 @@ mod tests {
                 repo_path: PathBuf::from("/repo1"),
                 worktree_path: PathBuf::from("/repo1/.git/wt/feat"),
                 branch: "feat".to_string(),
+                base_branch: "main".to_string(),
             }],
             shell_backend_id: None,
+            is_pinned: false,
+            label: None,
+            label_color: None,
+            created_at: None,
             tombstone: false,
             tombstone_at: None,
         });
@@ -672,14 +804,20 @@ mod tests {
                     repo_path: PathBuf::from("/repo1"),
                     worktree_path: PathBuf::from("/repo1/.git/wt/feat"),
                     branch: "feat".to_string(),
+                    base_branch: "main".to_string(),
                 },
                 SharedWorktree {
                     repo_path: PathBuf::from("/repo2"),
                     worktree_path: PathBuf::from("/repo2/.git/wt/feat"),
                     branch: "feat".to_string(),
+                    base_branch: "main".to_string(),
                 },
             ],
             shell_backend_id: None,
+            is_pinned: false,
+            label: None,
+            label_color: None,
+            created_at: None,
             tombstone: false,
             tombstone_at: None,
         });
@@ -706,6 +844,10 @@ mod tests {
             additional_dirs: Vec::new(),
             worktrees: Vec::new(),
             shell_backend_id: None,
+            is_pinned: false,
+            label: None,
+            label_color: None,
+            created_at: None,
             tombstone: false,
             tombstone_at: None,
         };
@@ -724,6 +866,10 @@ mod tests {
             additional_dirs: Vec::new(),
             worktrees: Vec::new(),
             shell_backend_id: None,
+            is_pinned: false,
+            label: None,
+            label_color: None,
+            created_at: None,
             tombstone: false,
             tombstone_at: None,
         };
@@ -752,6 +898,10 @@ mod tests {
             additional_dirs: Vec::new(),
             worktrees: Vec::new(),
             shell_backend_id: None,
+            is_pinned: false,
+            label: None,
+            label_color: None,
+            created_at: None,
             tombstone: false,
             tombstone_at: None,
         };
@@ -770,6 +920,10 @@ mod tests {
             additional_dirs: Vec::new(),
             worktrees: Vec::new(),
             shell_backend_id: None,
+            is_pinned: false,
+            label: None,
+            label_color: None,
+            created_at: None,
             tombstone: false,
             tombstone_at: None,
         };
@@ -799,6 +953,10 @@ mod tests {
             additional_dirs: vec![PathBuf::from("/repo2")],
             worktrees: Vec::new(),
             shell_backend_id: None,
+            is_pinned: false,
+            label: None,
+            label_color: None,
+            created_at: None,
             tombstone: false,
             tombstone_at: None,
         });
@@ -816,6 +974,10 @@ mod tests {
             additional_dirs: vec![PathBuf::from("/repo2"), PathBuf::from("/repo3")],
             worktrees: Vec::new(),
             shell_backend_id: None,
+            is_pinned: false,
+            label: None,
+            label_color: None,
+            created_at: None,
             tombstone: false,
             tombstone_at: None,
         });
@@ -839,6 +1001,13 @@ mod tests {
             repos: vec![PathBuf::from("/repo")],
             roles: vec![],
             mcp_servers: vec![],
+            sync_base_branch: None,
+            remote: None,
+            sync_strategy: SyncStrategy::default(),
+            activity_timeout_ms: None,
+            worktree_root: None,
+            default_role: None,
+            session_init_command: None,
         });
 
         let mut new_state = SharedState::new();
@@ -852,7 +1021,15 @@ mod tests {
                 command: "npx".to_string(),
                 args: vec![],
                 env: std::collections::HashMap::new(),
+                enabled: true,
             }],
+            sync_base_branch: None,
+            remote: None,
+            sync_strategy: SyncStrategy::default(),
+            activity_timeout_ms: None,
+            worktree_root: None,
+            default_role: None,
+            session_init_command: None,
         });
 
         let delta = StateDelta::compute(&old_state, &new_state);
@@ -6,10 +6,12 @@
 //! # Design
 //!
 //! - **Shared state**: SQLite database at `~/.local/share/thurbox/thurbox.db`
-//! - **Polling interval**: 250ms (configurable)
+//! - **Polling interval**: 1s (`[sync] poll_interval_ms` in `config.toml`)
 //! - **Change detection**: SQLite `PRAGMA data_version` (increments on external writes in WAL mode)
 //! - **Write protocol**: SQLite WAL mode handles concurrency automatically
 //! - **Conflict resolution**: SQLite serializes writes via WAL
+//! - **Presence**: each instance upserts a heartbeat row every 5s; instances
+//!   seen within the last 15s count as live
 //!
 //! # Usage
 //!
@@ -26,9 +28,20 @@ use std::time::{Duration, Instant};
 
 use tracing::debug;
 
+use crate::storage::LiveInstance;
+
 pub use delta::StateDelta;
 pub use state::{current_time_millis, SharedProject, SharedSession, SharedState, SharedWorktree};
 
+/// How often to poll for external changes unless configured otherwise.
+pub const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// How often this instance refreshes its heartbeat row.
+const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Instances whose heartbeat is older than this are considered gone.
+const LIVE_INSTANCE_WINDOW: Duration = Duration::from_secs(15);
+
 /// Tracks polling state for external change detection.
 ///
 /// Uses a time-based polling interval to avoid checking the database
@@ -47,26 +60,29 @@ pub struct SyncState {
 
     /// Whether syncing is enabled.
     enabled: bool,
+
+    /// When we last wrote our heartbeat (`None` until the first poll).
+    last_heartbeat: Option<Instant>,
+
+    /// Instances with a recent heartbeat, refreshed with each heartbeat.
+    live_instances: Vec<LiveInstance>,
 }
 
 impl SyncState {
-    /// Create a new sync state with the default 250ms poll interval.
+    /// Create a new sync state with the [`DEFAULT_POLL_INTERVAL`].
     pub fn new() -> Self {
-        Self {
-            local_state_snapshot: SharedState::default(),
-            last_poll_time: Instant::now(),
-            poll_interval: Duration::from_millis(250),
-            enabled: true,
-        }
+        Self::with_interval(DEFAULT_POLL_INTERVAL)
     }
 
-    /// Create sync state with a custom poll interval (for testing).
+    /// Create sync state with a custom poll interval.
     pub fn with_interval(interval: Duration) -> Self {
         Self {
             local_state_snapshot: SharedState::default(),
             last_poll_time: Instant::now(),
             poll_interval: interval,
             enabled: true,
+            last_heartbeat: None,
+            live_instances: Vec::new(),
         }
     }
 
@@ -78,14 +94,36 @@ impl SyncState {
         self.local_state_snapshot = state;
     }
 
+    /// Change the poll interval (from `[sync] poll_interval_ms`).
+    pub fn set_interval(&mut self, interval: Duration) {
+        self.poll_interval = interval;
+    }
+
     /// Disable sync (useful for single-instance deployments or testing).
     pub fn disable(&mut self) {
         self.enabled = false;
     }
 
     /// Check if it's time to poll based on the configured interval.
-    fn should_poll(&self) -> bool {
-        self.enabled && self.last_poll_time.elapsed() >= self.poll_interval
+    fn should_poll(&self, now: Instant) -> bool {
+        self.enabled && now.duration_since(self.last_poll_time) >= self.poll_interval
+    }
+
+    fn heartbeat_due(&self, now: Instant) -> bool {
+        self.last_heartbeat
+            .map_or(true, |at| now.duration_since(at) >= HEARTBEAT_INTERVAL)
+    }
+
+    /// Heartbeats only happen when we poll, so with a slow poll interval
+    /// the live window has to stretch to cover a few of them.
+    fn live_window(&self) -> Duration {
+        LIVE_INSTANCE_WINDOW.max(self.poll_interval * 3)
+    }
+
+    /// Instances (this one included) seen within the live window as of
+    /// the last heartbeat.
+    pub fn live_instances(&self) -> &[LiveInstance] {
+        &self.live_instances
     }
 }
 
@@ -105,15 +143,31 @@ impl Default for SyncState {
 /// - `Ok(Some(delta))` - Changes detected, apply delta to local state
 /// - `Ok(None)` - No changes detected or not time to poll yet
 /// - `Err(e)` - Error querying database
+///
+/// Also writes this instance's heartbeat and refreshes
+/// [`SyncState::live_instances`] every [`HEARTBEAT_INTERVAL`].
 pub fn poll_for_changes(
     sync_state: &mut SyncState,
     db: &mut crate::storage::Database,
 ) -> std::io::Result<Option<StateDelta>> {
-    if !sync_state.should_poll() {
+    let now = Instant::now();
+    if !sync_state.should_poll(now) {
         return Ok(None);
     }
 
-    sync_state.last_poll_time = Instant::now();
+    sync_state.last_poll_time = now;
+
+    if sync_state.heartbeat_due(now) {
+        sync_state.last_heartbeat = Some(now);
+        let window = sync_state.live_window();
+        let live = db
+            .record_heartbeat(window)
+            .and_then(|()| db.list_live_instances(window));
+        match live {
+            Ok(live) => sync_state.live_instances = live,
+            Err(e) => debug!("Heartbeat failed: {e}"),
+        }
+    }
 
     let changed = db
         .has_external_changes()
@@ -149,31 +203,55 @@ mod tests {
     #[test]
     fn should_poll_respects_interval() {
         let mut sync = SyncState::with_interval(Duration::from_millis(100));
+        let start = sync.last_poll_time;
 
         // First check should not poll (too soon)
-        assert!(!sync.should_poll());
-
-        // Wait for interval to pass
-        std::thread::sleep(Duration::from_millis(110));
+        assert!(!sync.should_poll(start + Duration::from_millis(50)));
 
         // Now should poll
-        assert!(sync.should_poll());
+        assert!(sync.should_poll(start + Duration::from_millis(110)));
 
         // Update poll time
-        sync.last_poll_time = Instant::now();
+        sync.last_poll_time = start + Duration::from_millis(110);
 
         // Should not poll again immediately
-        assert!(!sync.should_poll());
+        assert!(!sync.should_poll(start + Duration::from_millis(120)));
+    }
+
+    #[test]
+    fn new_sync_state_uses_default_interval() {
+        let sync = SyncState::new();
+        assert_eq!(sync.poll_interval, DEFAULT_POLL_INTERVAL);
+        assert!(!sync.should_poll(sync.last_poll_time + Duration::from_millis(250)));
+        assert!(sync.should_poll(sync.last_poll_time + DEFAULT_POLL_INTERVAL));
+    }
+
+    #[test]
+    fn set_interval_changes_poll_cadence() {
+        let mut sync = SyncState::new();
+        let start = sync.last_poll_time;
+        sync.set_interval(Duration::from_secs(5));
+
+        assert!(!sync.should_poll(start + Duration::from_secs(4)));
+        assert!(sync.should_poll(start + Duration::from_secs(5)));
+    }
+
+    #[test]
+    fn live_window_covers_slow_polls() {
+        let mut sync = SyncState::new();
+        assert_eq!(sync.live_window(), LIVE_INSTANCE_WINDOW);
+        sync.set_interval(Duration::from_secs(30));
+        assert_eq!(sync.live_window(), Duration::from_secs(90));
     }
 
     #[test]
     fn disable_prevents_polling() {
         let mut sync = SyncState::new();
-        sync.last_poll_time = Instant::now() - Duration::from_secs(10);
-        assert!(sync.should_poll());
+        let later = sync.last_poll_time + Duration::from_secs(10);
+        assert!(sync.should_poll(later));
 
         sync.disable();
-        assert!(!sync.should_poll());
+        assert!(!sync.should_poll(later));
     }
 
     #[test]
@@ -190,6 +268,13 @@ mod tests {
                 repos: vec![],
                 roles: vec![],
                 mcp_servers: vec![],
+                sync_base_branch: None,
+                remote: None,
+                sync_strategy: crate::project::SyncStrategy::default(),
+                activity_timeout_ms: None,
+                worktree_root: None,
+                default_role: None,
+                session_init_command: None,
             }],
             ..Default::default()
         };
@@ -199,4 +284,31 @@ mod tests {
         assert_eq!(sync.local_state_snapshot.session_counter, 42);
         assert_eq!(sync.local_state_snapshot.projects.len(), 1);
     }
+
+    #[test]
+    fn poll_writes_heartbeat_and_lists_live_instances() {
+        let mut db = crate::storage::Database::open_in_memory().unwrap();
+        let mut sync = SyncState::with_interval(Duration::ZERO);
+        assert!(sync.live_instances().is_empty());
+
+        poll_for_changes(&mut sync, &mut db).unwrap();
+
+        assert_eq!(sync.live_instances().len(), 1);
+        assert!(!sync.heartbeat_due(Instant::now()));
+    }
+
+    #[test]
+    fn disabled_sync_writes_no_heartbeat() {
+        let mut db = crate::storage::Database::open_in_memory().unwrap();
+        let mut sync = SyncState::with_interval(Duration::ZERO);
+        sync.disable();
+
+        poll_for_changes(&mut sync, &mut db).unwrap();
+
+        assert!(sync.live_instances().is_empty());
+        assert!(db
+            .list_live_instances(LIVE_INSTANCE_WINDOW)
+            .unwrap()
+            .is_empty());
+    }
 }
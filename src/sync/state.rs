@@ -1,6 +1,6 @@
 use std::path::PathBuf;
 
-use crate::project::ProjectId;
+use crate::project::{ProjectId, SyncStrategy};
 use crate::session::{McpServerConfig, RoleConfig, SessionId};
 
 /// Current shared state format version.
@@ -85,6 +85,18 @@ pub struct SharedSession {
     /// Backend ID of the companion shell pane (if spawned).
     pub shell_backend_id: Option<String>,
 
+    /// Whether the session is pinned to the top of its project's list.
+    pub is_pinned: bool,
+
+    /// Short tag shown before the session name (e.g. "frontend").
+    pub label: Option<String>,
+
+    /// Color name of the session's label dot (e.g. "green", "#ff8800").
+    pub label_color: Option<String>,
+
+    /// When the session was first spawned (millis since epoch).
+    pub created_at: Option<u64>,
+
     /// Tombstone flag: true if this session was soft-deleted.
     /// Soft-deleted sessions are excluded from active listings.
     pub tombstone: bool,
@@ -110,6 +122,27 @@ pub struct SharedProject {
 
     /// MCP server configurations for this project.
     pub mcp_servers: Vec<McpServerConfig>,
+
+    /// Branch that worktree sync rebases onto (`None` = auto-detect).
+    pub sync_base_branch: Option<String>,
+
+    /// Git remote worktree sync fetches from (`None` = `origin`).
+    pub remote: Option<String>,
+
+    /// How worktree sync integrates the base branch.
+    pub sync_strategy: SyncStrategy,
+
+    /// Output silence (ms) before a session counts as waiting (`None` = default).
+    pub activity_timeout_ms: Option<u64>,
+
+    /// Directory worktrees are created under (`None` = inside `.git`).
+    pub worktree_root: Option<PathBuf>,
+
+    /// Role auto-assigned to new sessions (`None` = ask when ambiguous).
+    pub default_role: Option<String>,
+
+    /// Typed into each new session after it spawns (`None` = nothing).
+    pub session_init_command: Option<String>,
 }
 
 /// Worktree information embedded in shared session.
@@ -123,6 +156,10 @@ pub struct SharedWorktree {
 
     /// Branch name for this worktree.
     pub branch: String,
+
+    /// Branch the worktree was created from and syncs onto. Empty when
+    /// unknown (rows written before it was recorded).
+    pub base_branch: String,
 }
 
 impl From<crate::session::WorktreeInfo> for SharedWorktree {
@@ -131,6 +168,7 @@ impl From<crate::session::WorktreeInfo> for SharedWorktree {
             repo_path: wt.repo_path,
             worktree_path: wt.worktree_path,
             branch: wt.branch,
+            base_branch: wt.base_branch,
         }
     }
 }
@@ -141,6 +179,7 @@ impl From<SharedWorktree> for crate::session::WorktreeInfo {
             repo_path: wt.repo_path,
             worktree_path: wt.worktree_path,
             branch: wt.branch,
+            base_branch: wt.base_branch,
         }
     }
 }
@@ -181,6 +220,7 @@ mod tests {
             repo_path: PathBuf::from("/repo"),
             worktree_path: PathBuf::from("/repo/.git/wt/feat"),
             branch: "feat".to_string(),
+            base_branch: "main".to_string(),
         };
 
         let shared: SharedWorktree = wt.into();
@@ -195,6 +235,7 @@ mod tests {
             repo_path: PathBuf::from("/repo"),
             worktree_path: PathBuf::from("/repo/.git/wt/feat"),
             branch: "feat".to_string(),
+            base_branch: "main".to_string(),
         };
 
         let wt: crate::session::WorktreeInfo = shared.into();
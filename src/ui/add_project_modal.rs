@@ -23,7 +23,11 @@ pub struct AddProjectModalState<'a> {
     pub focused_field: AddProjectField,
 }
 
-pub fn render_add_project_modal(frame: &mut Frame, state: &AddProjectModalState<'_>) {
+pub fn render_add_project_modal(
+    frame: &mut Frame,
+    theme: &Theme,
+    state: &AddProjectModalState<'_>,
+) {
     // Dynamic height: name(3) + path(3) + repo_list(max 6 items + 2 border) + footer(1) + outer border(2)
     let repo_list_inner = if state.repos.is_empty() {
         1
@@ -40,7 +44,7 @@ pub fn render_add_project_modal(frame: &mut Frame, state: &AddProjectModalState<
     let block = Block::default()
         .title(" Add Project ")
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Theme::ACCENT));
+        .border_style(Style::default().fg(theme.accent));
 
     let inner = block.inner(area);
     frame.render_widget(block, area);
@@ -57,6 +61,7 @@ pub fn render_add_project_modal(frame: &mut Frame, state: &AddProjectModalState<
 
     render_text_field(
         frame,
+        theme,
         chunks[0],
         "Name",
         state.name,
@@ -66,6 +71,7 @@ pub fn render_add_project_modal(frame: &mut Frame, state: &AddProjectModalState<
 
     render_text_field_with_suggestion(
         frame,
+        theme,
         chunks[1],
         "Repo Path",
         state.path,
@@ -77,9 +83,9 @@ pub fn render_add_project_modal(frame: &mut Frame, state: &AddProjectModalState<
     // Repo list
     let list_focused = state.focused_field == AddProjectField::RepoList;
     let list_border_color = if list_focused {
-        Theme::BORDER_FOCUSED
+        theme.border_focused
     } else {
-        Theme::BORDER_UNFOCUSED
+        theme.border_unfocused
     };
 
     let list_block = Block::default()
@@ -93,7 +99,7 @@ pub fn render_add_project_modal(frame: &mut Frame, state: &AddProjectModalState<
     if state.repos.is_empty() {
         let placeholder = Paragraph::new(Line::from(Span::styled(
             "(none — add via Path field above)",
-            Style::default().fg(Theme::TEXT_MUTED),
+            Style::default().fg(theme.text_muted),
         )));
         frame.render_widget(placeholder, list_inner);
     } else {
@@ -116,9 +122,9 @@ pub fn render_add_project_modal(frame: &mut Frame, state: &AddProjectModalState<
                 let marker = if selected { "▸ " } else { "  " };
                 let path_str = path.display().to_string();
                 let (marker_color, path_color) = if selected {
-                    (Theme::ACCENT, Theme::TEXT_PRIMARY)
+                    (theme.accent, theme.text_primary)
                 } else {
-                    (Theme::TEXT_MUTED, Theme::TEXT_SECONDARY)
+                    (theme.text_muted, theme.text_secondary)
                 };
                 Line::from(vec![
                     Span::styled(marker, Style::default().fg(marker_color)),
@@ -133,12 +139,12 @@ pub fn render_add_project_modal(frame: &mut Frame, state: &AddProjectModalState<
     // Context-sensitive footer
     let footer = match state.focused_field {
         AddProjectField::Name => Line::from(vec![
-            Span::styled("Tab", Theme::keybind()),
-            Span::styled(" next  ", Theme::keybind_desc()),
-            Span::styled("Enter", Theme::keybind()),
-            Span::styled(" submit  ", Theme::keybind_desc()),
-            Span::styled("Esc", Theme::keybind()),
-            Span::styled(" cancel", Theme::keybind_desc()),
+            Span::styled("Tab", theme.keybind()),
+            Span::styled(" next  ", theme.keybind_desc()),
+            Span::styled("Enter", theme.keybind()),
+            Span::styled(" submit  ", theme.keybind_desc()),
+            Span::styled("Esc", theme.keybind()),
+            Span::styled(" cancel", theme.keybind_desc()),
         ]),
         AddProjectField::Path => {
             let tab_hint = if state.path_suggestion.is_some() {
@@ -147,23 +153,23 @@ pub fn render_add_project_modal(frame: &mut Frame, state: &AddProjectModalState<
                 " next  "
             };
             Line::from(vec![
-                Span::styled("Tab", Theme::keybind()),
-                Span::styled(tab_hint, Theme::keybind_desc()),
-                Span::styled("Enter", Theme::keybind()),
-                Span::styled(" add repo  ", Theme::keybind_desc()),
-                Span::styled("Esc", Theme::keybind()),
-                Span::styled(" cancel", Theme::keybind_desc()),
+                Span::styled("Tab", theme.keybind()),
+                Span::styled(tab_hint, theme.keybind_desc()),
+                Span::styled("Enter", theme.keybind()),
+                Span::styled(" add repo  ", theme.keybind_desc()),
+                Span::styled("Esc", theme.keybind()),
+                Span::styled(" cancel", theme.keybind_desc()),
             ])
         }
         AddProjectField::RepoList => Line::from(vec![
-            Span::styled("j/k", Theme::keybind()),
-            Span::styled(" navigate  ", Theme::keybind_desc()),
-            Span::styled("d", Theme::keybind()),
-            Span::styled(" delete  ", Theme::keybind_desc()),
-            Span::styled("Enter", Theme::keybind()),
-            Span::styled(" submit  ", Theme::keybind_desc()),
-            Span::styled("Esc", Theme::keybind()),
-            Span::styled(" cancel", Theme::keybind_desc()),
+            Span::styled("j/k", theme.keybind()),
+            Span::styled(" navigate  ", theme.keybind_desc()),
+            Span::styled("d", theme.keybind()),
+            Span::styled(" delete  ", theme.keybind_desc()),
+            Span::styled("Enter", theme.keybind()),
+            Span::styled(" submit  ", theme.keybind_desc()),
+            Span::styled("Esc", theme.keybind()),
+            Span::styled(" cancel", theme.keybind_desc()),
         ]),
     };
     frame.render_widget(Paragraph::new(footer), chunks[3]);
@@ -0,0 +1,67 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout},
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use super::centered_fixed_height_rect;
+use super::theme::Theme;
+
+pub struct CloneRepoState<'a> {
+    /// Repo path from the add-project modal that has no checkout yet.
+    pub dest: &'a str,
+    pub url: &'a str,
+    pub cursor: usize,
+}
+
+/// URL prompt for cloning a missing repo while adding a project.
+pub fn render_clone_repo_modal(frame: &mut Frame, theme: &Theme, state: &CloneRepoState<'_>) {
+    let area = centered_fixed_height_rect(60, 7, frame.area());
+
+    frame.render_widget(Clear, area);
+
+    let block = Block::default()
+        .title(" Clone Repository ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.accent));
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1), // Destination
+            Constraint::Length(3), // URL field
+            Constraint::Min(1),    // Footer
+        ])
+        .split(inner);
+
+    let dest = Line::from(vec![
+        Span::styled("Not a git repo: ", theme.keybind_desc()),
+        Span::raw(state.dest),
+    ]);
+    frame.render_widget(Paragraph::new(dest), chunks[0]);
+
+    super::render_text_field(
+        frame,
+        theme,
+        chunks[1],
+        "Clone URL",
+        state.url,
+        state.cursor,
+        true,
+    );
+
+    let footer = Line::from(vec![
+        Span::styled("Enter", theme.keybind()),
+        Span::styled(" clone  ", theme.keybind_desc()),
+        Span::styled("Tab", theme.keybind()),
+        Span::styled(" add without cloning  ", theme.keybind_desc()),
+        Span::styled("Esc", theme.keybind()),
+        Span::styled(" cancel", theme.keybind_desc()),
+    ]);
+    frame.render_widget(Paragraph::new(footer), chunks[2]);
+}
@@ -0,0 +1,87 @@
+use ratatui::{
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use super::centered_fixed_height_rect;
+use super::theme::Theme;
+
+pub struct CloseAllState<'a> {
+    pub project_name: &'a str,
+    pub session_count: usize,
+    /// Sessions whose worktrees have uncommitted changes.
+    pub dirty_count: usize,
+    /// Offer `k` to keep the worktrees (`[close] worktrees = "ask"`).
+    pub offer_keep: bool,
+}
+
+/// Confirmation for closing every session of a project (`x` in the
+/// project list).
+pub fn render_close_all_modal(frame: &mut Frame, theme: &Theme, state: &CloseAllState<'_>) {
+    let height = if state.dirty_count > 0 { 7 } else { 5 };
+    let area = centered_fixed_height_rect(60, height, frame.area());
+
+    frame.render_widget(Clear, area);
+
+    let block = Block::default()
+        .title(" Close All Sessions ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.danger));
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let noun = if state.session_count == 1 {
+        "session"
+    } else {
+        "sessions"
+    };
+    let mut lines = vec![
+        Line::from(vec![
+            Span::styled("Close ", Style::default().fg(theme.text_primary)),
+            Span::styled(
+                format!("{} {noun}", state.session_count),
+                Style::default()
+                    .fg(theme.accent)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(" in ", Style::default().fg(theme.text_primary)),
+            Span::styled(
+                state.project_name,
+                Style::default()
+                    .fg(theme.accent)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled("?", Style::default().fg(theme.text_primary)),
+        ]),
+        Line::from(""),
+    ];
+    if state.dirty_count > 0 {
+        lines.push(Line::from(Span::styled(
+            format!(
+                "{} with uncommitted worktree changes will be discarded.",
+                state.dirty_count
+            ),
+            Style::default().fg(theme.danger),
+        )));
+        lines.push(Line::from(""));
+    }
+    let mut keys = vec![
+        Span::styled("y/Enter", theme.keybind()),
+        Span::styled(" close all  ", theme.keybind_desc()),
+    ];
+    if state.offer_keep {
+        keys.push(Span::styled("k", theme.keybind()));
+        keys.push(Span::styled(
+            " close, keep worktrees  ",
+            theme.keybind_desc(),
+        ));
+    }
+    keys.push(Span::styled("n/Esc", theme.keybind()));
+    keys.push(Span::styled(" cancel", theme.keybind_desc()));
+    lines.push(Line::from(keys));
+
+    frame.render_widget(Paragraph::new(lines), inner);
+}
@@ -0,0 +1,85 @@
+use ratatui::{
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use super::centered_fixed_height_rect;
+use super::theme::Theme;
+
+pub struct CloseSessionState<'a> {
+    pub session_name: &'a str,
+    /// Branches of all the session's worktrees, listed when nothing is at
+    /// risk (`[close] worktrees = "ask"`).
+    pub branches: &'a [String],
+    /// Branches of the worktrees that have uncommitted changes.
+    pub dirty_branches: &'a [String],
+    /// Branches no other session has checked out whose commits were never
+    /// pushed.
+    pub unpushed_branches: &'a [String],
+}
+
+pub fn render_close_session_modal(frame: &mut Frame, theme: &Theme, state: &CloseSessionState<'_>) {
+    let sections = [
+        (state.dirty_branches, " has uncommitted changes in:"),
+        (
+            state.unpushed_branches,
+            " is the last session on branches with unpushed commits:",
+        ),
+    ];
+    let mut sections: Vec<_> = sections
+        .into_iter()
+        .filter(|(branches, _)| !branches.is_empty())
+        .collect();
+    if sections.is_empty() {
+        sections.push((state.branches, " has worktrees on:"));
+    }
+    // Borders and footer, plus a heading, list and two blank lines per section.
+    let height = 3 + sections
+        .iter()
+        .map(|(branches, _)| 3 + branches.len() as u16)
+        .sum::<u16>();
+    let area = centered_fixed_height_rect(60, height, frame.area());
+
+    frame.render_widget(Clear, area);
+
+    let block = Block::default()
+        .title(" Close Session ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.danger));
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let mut lines = Vec::new();
+    for (branches, message) in sections {
+        lines.push(Line::from(vec![
+            Span::styled(
+                state.session_name,
+                Style::default()
+                    .fg(theme.accent)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(message, Style::default().fg(theme.text_primary)),
+        ]));
+        lines.push(Line::from(""));
+        lines.extend(branches.iter().map(|branch| {
+            Line::from(Span::styled(
+                format!("  {branch}"),
+                Style::default().fg(theme.danger),
+            ))
+        }));
+        lines.push(Line::from(""));
+    }
+    lines.push(Line::from(vec![
+        Span::styled("y", theme.keybind()),
+        Span::styled(" close and discard  ", theme.keybind_desc()),
+        Span::styled("k", theme.keybind()),
+        Span::styled(" close, keep worktree  ", theme.keybind_desc()),
+        Span::styled("n/Esc", theme.keybind()),
+        Span::styled(" keep session", theme.keybind_desc()),
+    ]));
+
+    frame.render_widget(Paragraph::new(lines), inner);
+}
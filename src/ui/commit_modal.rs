@@ -0,0 +1,64 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout},
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use super::centered_fixed_height_rect;
+use super::theme::Theme;
+
+/// Message lines visible at once; longer messages scroll to the cursor.
+const MESSAGE_LINES: usize = 5;
+
+pub struct CommitState<'a> {
+    pub branch: &'a str,
+    pub message: &'a str,
+    pub cursor: usize,
+}
+
+/// Commit message prompt for a worktree session. The message may span
+/// several lines; `cursor` is a char offset into it.
+pub fn render_commit_modal(frame: &mut Frame, theme: &Theme, state: &CommitState<'_>) {
+    // message(MESSAGE_LINES + 2) + footer(1) + outer border(2)
+    let area = centered_fixed_height_rect(60, MESSAGE_LINES as u16 + 5, frame.area());
+
+    frame.render_widget(Clear, area);
+
+    let block = Block::default()
+        .title(format!(" Commit ({}) ", state.branch))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.accent));
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(MESSAGE_LINES as u16 + 2), // Message
+            Constraint::Min(1),                           // Footer
+        ])
+        .split(inner);
+
+    super::render_multiline_field(
+        frame,
+        theme,
+        chunks[0],
+        "Message",
+        state.message,
+        state.cursor,
+        MESSAGE_LINES,
+    );
+
+    let footer = Line::from(vec![
+        Span::styled("Enter", theme.keybind()),
+        Span::styled(" commit  ", theme.keybind_desc()),
+        Span::styled("Alt+Enter", theme.keybind()),
+        Span::styled(" new line  ", theme.keybind_desc()),
+        Span::styled("Esc", theme.keybind()),
+        Span::styled(" cancel", theme.keybind_desc()),
+    ]);
+    frame.render_widget(Paragraph::new(footer), chunks[1]);
+}
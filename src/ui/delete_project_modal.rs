@@ -16,7 +16,11 @@ pub struct DeleteProjectModalState<'a> {
     pub error: Option<&'a str>,
 }
 
-pub fn render_delete_project_modal(frame: &mut Frame, state: &DeleteProjectModalState<'_>) {
+pub fn render_delete_project_modal(
+    frame: &mut Frame,
+    theme: &Theme,
+    state: &DeleteProjectModalState<'_>,
+) {
     let area = centered_fixed_height_rect(60, 13, frame.area());
 
     frame.render_widget(Clear, area);
@@ -24,7 +28,7 @@ pub fn render_delete_project_modal(frame: &mut Frame, state: &DeleteProjectModal
     let block = Block::default()
         .title(" Delete Project ")
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Theme::DANGER));
+        .border_style(Style::default().fg(theme.danger));
 
     let inner = block.inner(area);
     frame.render_widget(block, area);
@@ -46,7 +50,7 @@ pub fn render_delete_project_modal(frame: &mut Frame, state: &DeleteProjectModal
         Line::from(Span::styled(
             "⚠ Delete Project",
             Style::default()
-                .fg(Theme::DANGER)
+                .fg(theme.danger)
                 .add_modifier(Modifier::BOLD),
         )),
         Line::from(""),
@@ -55,7 +59,7 @@ pub fn render_delete_project_modal(frame: &mut Frame, state: &DeleteProjectModal
             Span::styled(
                 state.project_name,
                 Style::default()
-                    .fg(Theme::ACCENT)
+                    .fg(theme.accent)
                     .add_modifier(Modifier::BOLD),
             ),
             Span::raw(" to confirm"),
@@ -65,32 +69,37 @@ pub fn render_delete_project_modal(frame: &mut Frame, state: &DeleteProjectModal
     frame.render_widget(warning, chunks[0]);
 
     // Confirmation input
-    render_confirmation_input(frame, chunks[2], state);
+    render_confirmation_input(frame, theme, chunks[2], state);
 
     // Error message
     if let Some(error) = state.error {
         let error_msg = Paragraph::new(error)
-            .style(Style::default().fg(Theme::DANGER))
+            .style(Style::default().fg(theme.danger))
             .alignment(Alignment::Left);
         frame.render_widget(error_msg, chunks[3]);
     }
 
     // Help text
     let help = Line::from(vec![
-        Span::styled("Enter", Theme::keybind()),
+        Span::styled("Enter", theme.keybind()),
         Span::raw(" confirm  "),
-        Span::styled("Esc", Theme::keybind()),
+        Span::styled("Esc", theme.keybind()),
         Span::raw(" cancel"),
     ]);
     let help_paragraph = Paragraph::new(help).alignment(Alignment::Left);
     frame.render_widget(help_paragraph, chunks[4]);
 }
 
-fn render_confirmation_input(frame: &mut Frame, area: Rect, state: &DeleteProjectModalState<'_>) {
+fn render_confirmation_input(
+    frame: &mut Frame,
+    theme: &Theme,
+    area: Rect,
+    state: &DeleteProjectModalState<'_>,
+) {
     let block = Block::default()
         .title(" Confirmation ")
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Theme::TEXT_PRIMARY));
+        .border_style(Style::default().fg(theme.text_primary));
 
     let inner = block.inner(area);
     frame.render_widget(block, area);
@@ -107,7 +116,7 @@ fn render_confirmation_input(frame: &mut Frame, area: Rect, state: &DeleteProjec
             let before: String = chars[..cursor].iter().collect();
             spans.push(Span::styled(
                 before,
-                Style::default().fg(Theme::TEXT_PRIMARY),
+                Style::default().fg(theme.text_primary),
             ));
         }
 
@@ -117,15 +126,12 @@ fn render_confirmation_input(frame: &mut Frame, area: Rect, state: &DeleteProjec
         } else {
             " ".to_string()
         };
-        spans.push(Span::styled(cursor_char, Theme::cursor()));
+        spans.push(Span::styled(cursor_char, theme.cursor()));
 
         // Text after cursor
         if cursor + 1 < chars.len() {
             let after: String = chars[cursor + 1..].iter().collect();
-            spans.push(Span::styled(
-                after,
-                Style::default().fg(Theme::TEXT_PRIMARY),
-            ));
+            spans.push(Span::styled(after, Style::default().fg(theme.text_primary)));
         }
     }
 
@@ -11,6 +11,7 @@ use ratatui::{
 use super::theme::Theme;
 use super::{centered_fixed_height_rect, render_text_field, render_text_field_with_suggestion};
 use crate::app::EditProjectField;
+use crate::project::SyncStrategy;
 use crate::session::{McpServerConfig, RoleConfig};
 
 pub struct EditProjectModalState<'a> {
@@ -23,13 +24,28 @@ pub struct EditProjectModalState<'a> {
     pub repo_index: usize,
     pub roles: &'a [RoleConfig],
     pub role_index: usize,
+    /// Role marked as the project default, shown with a suffix.
+    pub default_role: Option<&'a str>,
     pub mcp_servers: &'a [McpServerConfig],
     pub mcp_server_index: usize,
+    pub sync_strategy: SyncStrategy,
+    pub activity_timeout: &'a str,
+    pub activity_timeout_cursor: usize,
+    pub worktree_root: &'a str,
+    pub worktree_root_cursor: usize,
+    pub init_command: &'a str,
+    pub init_command_cursor: usize,
     pub focused_field: EditProjectField,
 }
 
-pub fn render_edit_project_modal(frame: &mut Frame, state: &EditProjectModalState<'_>) {
-    // Dynamic height: name(3) + path(3) + repo_list + roles_list + footer(1) + outer border(2)
+pub fn render_edit_project_modal(
+    frame: &mut Frame,
+    theme: &Theme,
+    state: &EditProjectModalState<'_>,
+) {
+    // Dynamic height: name(3) + path(3) + repo_list + roles_list + mcp_list
+    // + sync_strategy(3) + activity_timeout(3) + worktree_root(3)
+    // + init_command(3) + footer(1) + outer border(2)
     let repo_list_inner = if state.repos.is_empty() {
         1
     } else {
@@ -51,7 +67,8 @@ pub fn render_edit_project_modal(frame: &mut Frame, state: &EditProjectModalStat
     };
     let mcp_list_height = mcp_list_inner as u16 + 2; // +2 for borders
 
-    let total_height = 3 + 3 + repo_list_height + roles_list_height + mcp_list_height + 1 + 2;
+    let total_height =
+        3 + 3 + repo_list_height + roles_list_height + mcp_list_height + 3 + 3 + 3 + 3 + 1 + 2;
 
     let area = centered_fixed_height_rect(50, total_height, frame.area());
 
@@ -60,7 +77,7 @@ pub fn render_edit_project_modal(frame: &mut Frame, state: &EditProjectModalStat
     let block = Block::default()
         .title(" Edit Project ")
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Theme::ACCENT));
+        .border_style(Style::default().fg(theme.accent));
 
     let inner = block.inner(area);
     frame.render_widget(block, area);
@@ -73,12 +90,17 @@ pub fn render_edit_project_modal(frame: &mut Frame, state: &EditProjectModalStat
             Constraint::Length(repo_list_height),  // Repo list
             Constraint::Length(roles_list_height), // Roles list
             Constraint::Length(mcp_list_height),   // MCP servers list
+            Constraint::Length(3),                 // Sync strategy selector
+            Constraint::Length(3),                 // Activity timeout field
+            Constraint::Length(3),                 // Worktree root field
+            Constraint::Length(3),                 // Startup command field
             Constraint::Min(1),                    // Footer
         ])
         .split(inner);
 
     render_text_field(
         frame,
+        theme,
         chunks[0],
         "Name",
         state.name,
@@ -88,6 +110,7 @@ pub fn render_edit_project_modal(frame: &mut Frame, state: &EditProjectModalStat
 
     render_text_field_with_suggestion(
         frame,
+        theme,
         chunks[1],
         "Add Repo Path",
         state.path,
@@ -99,12 +122,13 @@ pub fn render_edit_project_modal(frame: &mut Frame, state: &EditProjectModalStat
     // Repo list
     render_item_list(
         frame,
+        theme,
         chunks[2],
         "Repos",
         &state
             .repos
             .iter()
-            .map(|p| p.display().to_string())
+            .map(|p| (p.display().to_string(), true))
             .collect::<Vec<_>>(),
         state.repo_index,
         state.focused_field == EditProjectField::RepoList,
@@ -114,12 +138,19 @@ pub fn render_edit_project_modal(frame: &mut Frame, state: &EditProjectModalStat
     // Roles list
     render_item_list(
         frame,
+        theme,
         chunks[3],
         "Roles",
         &state
             .roles
             .iter()
-            .map(|r| r.name.clone())
+            .map(|r| {
+                if state.default_role == Some(r.name.as_str()) {
+                    (format!("{} (default)", r.name), true)
+                } else {
+                    (r.name.clone(), true)
+                }
+            })
             .collect::<Vec<_>>(),
         state.role_index,
         state.focused_field == EditProjectField::Roles,
@@ -129,27 +160,66 @@ pub fn render_edit_project_modal(frame: &mut Frame, state: &EditProjectModalStat
     // MCP servers list
     render_item_list(
         frame,
+        theme,
         chunks[4],
         "MCP Servers",
         &state
             .mcp_servers
             .iter()
-            .map(|s| s.name.clone())
+            .map(|s| (s.name.clone(), s.enabled))
             .collect::<Vec<_>>(),
         state.mcp_server_index,
         state.focused_field == EditProjectField::McpServers,
         "  No MCP servers defined",
     );
 
+    render_sync_strategy(
+        frame,
+        theme,
+        chunks[5],
+        state.sync_strategy,
+        state.focused_field == EditProjectField::SyncStrategy,
+    );
+
+    render_text_field(
+        frame,
+        theme,
+        chunks[6],
+        "Activity Timeout (ms, empty = default)",
+        state.activity_timeout,
+        state.activity_timeout_cursor,
+        state.focused_field == EditProjectField::ActivityTimeout,
+    );
+
+    render_text_field(
+        frame,
+        theme,
+        chunks[7],
+        "Worktree Root (empty = inside .git)",
+        state.worktree_root,
+        state.worktree_root_cursor,
+        state.focused_field == EditProjectField::WorktreeRoot,
+    );
+
+    render_text_field(
+        frame,
+        theme,
+        chunks[8],
+        "Session Startup Command (empty = none)",
+        state.init_command,
+        state.init_command_cursor,
+        state.focused_field == EditProjectField::InitCommand,
+    );
+
     // Context-sensitive footer
     let footer = match state.focused_field {
         EditProjectField::Name => Line::from(vec![
-            Span::styled("Tab", Theme::keybind()),
-            Span::styled(" next  ", Theme::keybind_desc()),
-            Span::styled("Enter", Theme::keybind()),
-            Span::styled(" save  ", Theme::keybind_desc()),
-            Span::styled("Esc", Theme::keybind()),
-            Span::styled(" cancel", Theme::keybind_desc()),
+            Span::styled("Tab", theme.keybind()),
+            Span::styled(" next  ", theme.keybind_desc()),
+            Span::styled("Enter", theme.keybind()),
+            Span::styled(" save  ", theme.keybind_desc()),
+            Span::styled("Esc", theme.keybind()),
+            Span::styled(" cancel", theme.keybind_desc()),
         ]),
         EditProjectField::Path => {
             let tab_hint = if state.path_suggestion.is_some() {
@@ -158,54 +228,124 @@ pub fn render_edit_project_modal(frame: &mut Frame, state: &EditProjectModalStat
                 " next  "
             };
             Line::from(vec![
-                Span::styled("Tab", Theme::keybind()),
-                Span::styled(tab_hint, Theme::keybind_desc()),
-                Span::styled("Enter", Theme::keybind()),
-                Span::styled(" add repo  ", Theme::keybind_desc()),
-                Span::styled("Esc", Theme::keybind()),
-                Span::styled(" cancel", Theme::keybind_desc()),
+                Span::styled("Tab", theme.keybind()),
+                Span::styled(tab_hint, theme.keybind_desc()),
+                Span::styled("Enter", theme.keybind()),
+                Span::styled(" add repo  ", theme.keybind_desc()),
+                Span::styled("Esc", theme.keybind()),
+                Span::styled(" cancel", theme.keybind_desc()),
             ])
         }
         EditProjectField::RepoList => Line::from(vec![
-            Span::styled("j/k", Theme::keybind()),
-            Span::styled(" navigate  ", Theme::keybind_desc()),
-            Span::styled("d", Theme::keybind()),
-            Span::styled(" delete  ", Theme::keybind_desc()),
-            Span::styled("Enter", Theme::keybind()),
-            Span::styled(" save  ", Theme::keybind_desc()),
-            Span::styled("Esc", Theme::keybind()),
-            Span::styled(" cancel", Theme::keybind_desc()),
+            Span::styled("j/k", theme.keybind()),
+            Span::styled(" navigate  ", theme.keybind_desc()),
+            Span::styled("d", theme.keybind()),
+            Span::styled(" delete  ", theme.keybind_desc()),
+            Span::styled("Enter", theme.keybind()),
+            Span::styled(" save  ", theme.keybind_desc()),
+            Span::styled("Esc", theme.keybind()),
+            Span::styled(" cancel", theme.keybind_desc()),
+        ]),
+        EditProjectField::Roles => Line::from(vec![
+            Span::styled("j/k", theme.keybind()),
+            Span::styled(" navigate  ", theme.keybind_desc()),
+            Span::styled("a", theme.keybind()),
+            Span::styled(" add  ", theme.keybind_desc()),
+            Span::styled("e", theme.keybind()),
+            Span::styled(" edit  ", theme.keybind_desc()),
+            Span::styled("d", theme.keybind()),
+            Span::styled(" delete  ", theme.keybind_desc()),
+            Span::styled("s", theme.keybind()),
+            Span::styled(" default  ", theme.keybind_desc()),
+            Span::styled("x/i", theme.keybind()),
+            Span::styled(" export/import  ", theme.keybind_desc()),
+            Span::styled("Esc", theme.keybind()),
+            Span::styled(" save", theme.keybind_desc()),
+        ]),
+        EditProjectField::McpServers => Line::from(vec![
+            Span::styled("j/k", theme.keybind()),
+            Span::styled(" navigate  ", theme.keybind_desc()),
+            Span::styled("a", theme.keybind()),
+            Span::styled(" add  ", theme.keybind_desc()),
+            Span::styled("e", theme.keybind()),
+            Span::styled(" edit  ", theme.keybind_desc()),
+            Span::styled("Space", theme.keybind()),
+            Span::styled(" toggle  ", theme.keybind_desc()),
+            Span::styled("d", theme.keybind()),
+            Span::styled(" delete  ", theme.keybind_desc()),
+            Span::styled("Esc", theme.keybind()),
+            Span::styled(" save", theme.keybind_desc()),
         ]),
-        EditProjectField::Roles | EditProjectField::McpServers => Line::from(vec![
-            Span::styled("j/k", Theme::keybind()),
-            Span::styled(" navigate  ", Theme::keybind_desc()),
-            Span::styled("a", Theme::keybind()),
-            Span::styled(" add  ", Theme::keybind_desc()),
-            Span::styled("e", Theme::keybind()),
-            Span::styled(" edit  ", Theme::keybind_desc()),
-            Span::styled("d", Theme::keybind()),
-            Span::styled(" delete  ", Theme::keybind_desc()),
-            Span::styled("Esc", Theme::keybind()),
-            Span::styled(" save", Theme::keybind_desc()),
+        EditProjectField::SyncStrategy => Line::from(vec![
+            Span::styled("←/→", theme.keybind()),
+            Span::styled(" change  ", theme.keybind_desc()),
+            Span::styled("Enter", theme.keybind()),
+            Span::styled(" save  ", theme.keybind_desc()),
+            Span::styled("Esc", theme.keybind()),
+            Span::styled(" cancel", theme.keybind_desc()),
         ]),
+        EditProjectField::ActivityTimeout
+        | EditProjectField::WorktreeRoot
+        | EditProjectField::InitCommand => Line::from(vec![
+            Span::styled("Tab", theme.keybind()),
+            Span::styled(" next  ", theme.keybind_desc()),
+            Span::styled("Enter", theme.keybind()),
+            Span::styled(" save  ", theme.keybind_desc()),
+            Span::styled("Esc", theme.keybind()),
+            Span::styled(" cancel", theme.keybind_desc()),
+        ]),
+    };
+    frame.render_widget(Paragraph::new(footer), chunks[9]);
+}
+
+/// Render the sync strategy selector as a bordered `◂ value ▸` row.
+fn render_sync_strategy(
+    frame: &mut Frame,
+    theme: &Theme,
+    area: ratatui::layout::Rect,
+    strategy: SyncStrategy,
+    focused: bool,
+) {
+    let border_color = if focused {
+        theme.border_focused
+    } else {
+        theme.border_unfocused
+    };
+
+    let block = Block::default()
+        .title(" Sync Strategy ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(border_color));
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let style = if focused {
+        theme.selected_item()
+    } else {
+        theme.normal_item()
     };
-    frame.render_widget(Paragraph::new(footer), chunks[5]);
+    let line = Line::from(Span::styled(format!("◂ {strategy} ▸"), style));
+    frame.render_widget(Paragraph::new(line), inner);
 }
 
-/// Render a bordered item list with selection highlighting.
+/// Render a bordered item list with selection highlighting. Each item is
+/// a label and whether it is enabled; disabled items are dimmed.
+#[allow(clippy::too_many_arguments)]
 fn render_item_list(
     frame: &mut Frame,
+    theme: &Theme,
     area: ratatui::layout::Rect,
     label: &str,
-    items: &[String],
+    items: &[(String, bool)],
     selected_index: usize,
     focused: bool,
     empty_text: &str,
 ) {
     let border_color = if focused {
-        Theme::BORDER_FOCUSED
+        theme.border_focused
     } else {
-        Theme::BORDER_UNFOCUSED
+        theme.border_unfocused
     };
 
     let block = Block::default()
@@ -219,7 +359,7 @@ fn render_item_list(
     if items.is_empty() {
         let placeholder = Paragraph::new(Line::from(Span::styled(
             empty_text,
-            Style::default().fg(Theme::TEXT_MUTED),
+            Style::default().fg(theme.text_muted),
         )));
         frame.render_widget(placeholder, inner);
     } else {
@@ -235,15 +375,21 @@ fn render_item_list(
             .enumerate()
             .skip(scroll_offset)
             .take(visible_count)
-            .map(|(i, item)| {
+            .map(|(i, (item, enabled))| {
                 let is_selected = i == selected_index && focused;
                 let style = if is_selected {
-                    Theme::selected_item()
+                    theme.selected_item()
+                } else if !enabled {
+                    Style::default().fg(theme.text_muted)
                 } else {
-                    Theme::normal_item()
+                    theme.normal_item()
                 };
                 let prefix = if is_selected { "▸ " } else { "  " };
-                ListItem::new(Line::from(Span::styled(format!("{prefix}{item}"), style)))
+                let suffix = if *enabled { "" } else { " (disabled)" };
+                ListItem::new(Line::from(Span::styled(
+                    format!("{prefix}{item}{suffix}"),
+                    style,
+                )))
             })
             .collect();
 
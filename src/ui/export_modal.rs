@@ -0,0 +1,63 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout},
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use super::centered_fixed_height_rect;
+use super::theme::Theme;
+
+pub struct ExportState<'a> {
+    /// Lines in the scrollback snapshot being exported.
+    pub line_count: usize,
+    pub path: &'a str,
+    pub cursor: usize,
+}
+
+/// Path prompt for writing the active session's scrollback to a file (F7).
+pub fn render_export_modal(frame: &mut Frame, theme: &Theme, state: &ExportState<'_>) {
+    let area = centered_fixed_height_rect(60, 6, frame.area());
+
+    frame.render_widget(Clear, area);
+
+    let noun = if state.line_count == 1 {
+        "line"
+    } else {
+        "lines"
+    };
+    let block = Block::default()
+        .title(format!(" Export Scrollback ({} {noun}) ", state.line_count))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.accent));
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Path field
+            Constraint::Min(1),    // Footer
+        ])
+        .split(inner);
+
+    super::render_text_field(
+        frame,
+        theme,
+        chunks[0],
+        "File",
+        state.path,
+        state.cursor,
+        true,
+    );
+
+    let footer = Line::from(vec![
+        Span::styled("Enter", theme.keybind()),
+        Span::styled(" export  ", theme.keybind_desc()),
+        Span::styled("Esc", theme.keybind()),
+        Span::styled(" cancel", theme.keybind_desc()),
+    ]);
+    frame.render_widget(Paragraph::new(footer), chunks[1]);
+}
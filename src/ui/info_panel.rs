@@ -6,31 +6,102 @@ use ratatui::{
     Frame,
 };
 
+use super::status_icons::StatusIcons;
 use super::theme::Theme;
 use crate::project::ProjectInfo;
-use crate::session::{RoleConfig, SessionInfo};
-
-pub fn render_info_panel(
-    frame: &mut Frame,
-    area: Rect,
-    info: &SessionInfo,
-    project: Option<&ProjectInfo>,
-) {
+use crate::session::{RoleConfig, RolePermissions, SessionInfo, SessionStatus, WorktreeStatus};
+use crate::storage::ActivitySummary;
+
+/// What the info panel shows, cycled with a key (F9 by default).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum InfoPanelMode {
+    /// Project and session metadata.
+    #[default]
+    Session,
+    /// Live git state of each worktree.
+    Git,
+    /// The role's resolved permissions.
+    Role,
+}
+
+impl InfoPanelMode {
+    pub fn next(self) -> Self {
+        match self {
+            Self::Session => Self::Git,
+            Self::Git => Self::Role,
+            Self::Role => Self::Session,
+        }
+    }
+
+    pub fn title(self) -> &'static str {
+        match self {
+            Self::Session => " Info ",
+            Self::Git => " Git ",
+            Self::Role => " Role ",
+        }
+    }
+}
+
+pub struct InfoPanelState<'a> {
+    pub mode: InfoPanelMode,
+    pub info: &'a SessionInfo,
+    pub project: Option<&'a ProjectInfo>,
+    pub log_toggle_key: &'a str,
+    pub worktree_statuses: &'a [Option<WorktreeStatus>],
+    /// Time since the session last produced output, shown as "Idle for"
+    /// while it is not busy.
+    pub idle_ms: u64,
+    /// How long ago the session was created, if that is known.
+    pub created: Option<String>,
+    /// Summary of the session's recorded status timeline, if there is one.
+    pub activity: Option<ActivitySummary>,
+    /// Permissions the session's role resolves to (only needed in
+    /// [`InfoPanelMode::Role`]).
+    pub permissions: Option<&'a RolePermissions>,
+    pub status_icons: &'a StatusIcons,
+}
+
+pub fn render_info_panel(frame: &mut Frame, theme: &Theme, area: Rect, state: &InfoPanelState<'_>) {
+    let lines = match state.mode {
+        InfoPanelMode::Session => session_lines(theme, state),
+        InfoPanelMode::Git => git_lines(theme, state.info, state.worktree_statuses),
+        InfoPanelMode::Role => role_lines(theme, state.info, state.project, state.permissions),
+    };
+
     let block = Block::default()
-        .title(" Info ")
+        .title(state.mode.title())
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Theme::BORDER_UNFOCUSED));
+        .border_style(Style::default().fg(theme.border_unfocused));
+    let paragraph = Paragraph::new(lines)
+        .block(block)
+        .wrap(Wrap { trim: false });
+    frame.render_widget(paragraph, area);
+}
+
+/// Project and session metadata, with a summary of worktrees and role.
+fn session_lines<'a>(theme: &Theme, state: &InfoPanelState<'a>) -> Vec<Line<'a>> {
+    let InfoPanelState {
+        info,
+        project,
+        log_toggle_key,
+        worktree_statuses,
+        idle_ms,
+        ref created,
+        activity,
+        status_icons,
+        ..
+    } = *state;
 
     let mut lines = Vec::new();
 
     // ── Project section ──
     if let Some(proj) = project {
         let project_line = vec![
-            Span::styled("Project: ", Theme::label()),
+            Span::styled("Project: ", theme.label()),
             Span::styled(
                 &proj.config.name,
                 Style::default()
-                    .fg(Theme::ACCENT)
+                    .fg(theme.accent)
                     .add_modifier(Modifier::BOLD),
             ),
         ];
@@ -38,27 +109,27 @@ pub fn render_info_panel(
 
         if proj.config.repos.len() == 1 {
             lines.push(Line::from(vec![
-                Span::styled("Repo: ", Theme::label()),
+                Span::styled("Repo: ", theme.label()),
                 Span::styled(
                     proj.config.repos[0].display().to_string(),
-                    Style::default().fg(Theme::TEXT_MUTED),
+                    Style::default().fg(theme.text_muted),
                 ),
             ]));
         } else {
-            lines.push(Line::from(Span::styled("Repos:", Theme::label())));
+            lines.push(Line::from(Span::styled("Repos:", theme.label())));
             for repo in &proj.config.repos {
                 lines.push(Line::from(Span::styled(
                     format!("  {}", repo.display()),
-                    Style::default().fg(Theme::TEXT_MUTED),
+                    Style::default().fg(theme.text_muted),
                 )));
             }
         }
 
         lines.push(Line::from(vec![
-            Span::styled("Sessions: ", Theme::label()),
+            Span::styled("Sessions: ", theme.label()),
             Span::styled(
                 proj.session_ids.len().to_string(),
-                Style::default().fg(Theme::TEXT_PRIMARY),
+                Style::default().fg(theme.text_primary),
             ),
         ]));
 
@@ -73,95 +144,174 @@ pub fn render_info_panel(
                 .join(", ")
         };
         lines.push(Line::from(vec![
-            Span::styled("Roles: ", Theme::label()),
-            Span::styled(roles_text, Style::default().fg(Theme::TEXT_PRIMARY)),
+            Span::styled("Roles: ", theme.label()),
+            Span::styled(roles_text, Style::default().fg(theme.text_primary)),
         ]));
 
-        lines.push(separator());
+        lines.push(separator(theme));
     }
 
     // ── Session section ──
     lines.push(Line::from(vec![
-        Span::styled("Name: ", Theme::label()),
-        Span::styled(&info.name, Style::default().fg(Theme::TEXT_PRIMARY)),
+        Span::styled("Name: ", theme.label()),
+        Span::styled(&info.name, Style::default().fg(theme.text_primary)),
     ]));
+    let status = format!(
+        "{} {}",
+        status_icons.icon(info.status),
+        status_icons.label(info.status)
+    );
+    let status_text = if info.crash_looping {
+        format!("{status} (crash looping)")
+    } else if info.cwd_missing {
+        format!("{status} (cwd missing)")
+    } else if info.backend_dead {
+        format!("{status} (pane died)")
+    } else {
+        status
+    };
     lines.push(Line::from(vec![
-        Span::styled("Status: ", Theme::label()),
+        Span::styled("Status: ", theme.label()),
         Span::styled(
-            format!("{} {}", info.status.icon(), info.status),
+            status_text,
             Style::default()
-                .fg(super::status_color(info.status))
+                .fg(super::status_color(info.status, theme))
                 .add_modifier(Modifier::BOLD),
         ),
     ]));
+    if matches!(
+        info.status,
+        SessionStatus::NeedsInput | SessionStatus::Waiting | SessionStatus::Idle
+    ) {
+        lines.push(Line::from(vec![
+            Span::styled("Idle for: ", theme.label()),
+            Span::styled(
+                super::format_duration_short(idle_ms),
+                Style::default().fg(theme.text_primary),
+            ),
+        ]));
+    }
+    if let Some(created) = created {
+        lines.push(Line::from(vec![
+            Span::styled("Created: ", theme.label()),
+            Span::styled(created.clone(), Style::default().fg(theme.text_primary)),
+        ]));
+    }
+    if let Some(activity) = activity {
+        let noun = if activity.transitions == 1 {
+            "transition"
+        } else {
+            "transitions"
+        };
+        lines.push(Line::from(vec![
+            Span::styled("Activity: ", theme.label()),
+            Span::styled(
+                format!(
+                    "busy {}%, {} {noun}",
+                    activity.busy_percent, activity.transitions
+                ),
+                Style::default().fg(theme.text_primary),
+            ),
+        ]));
+    }
+    if info.restart_count > 0 {
+        lines.push(Line::from(vec![
+            Span::styled("Restarts: ", theme.label()),
+            Span::styled(
+                info.restart_count.to_string(),
+                Style::default().fg(theme.text_primary),
+            ),
+        ]));
+    }
     lines.push(Line::from(vec![
-        Span::styled("Role: ", Theme::label()),
+        Span::styled("Role: ", theme.label()),
         Span::styled(
             &info.role,
             Style::default()
-                .fg(Theme::ROLE_NAME)
+                .fg(theme.role_name)
                 .add_modifier(Modifier::BOLD),
         ),
     ]));
     lines.push(Line::from(vec![
-        Span::styled("ID: ", Theme::label()),
-        Span::styled(info.id.to_string(), Style::default().fg(Theme::TEXT_MUTED)),
+        Span::styled("ID: ", theme.label()),
+        Span::styled(info.id.to_string(), Style::default().fg(theme.text_muted)),
     ]));
     lines.push(Line::from(vec![
-        Span::styled("Claude: ", Theme::label()),
+        Span::styled("Claude: ", theme.label()),
         Span::styled(
             info.claude_session_id.as_deref().unwrap_or("(none)"),
-            Style::default().fg(Theme::TEXT_MUTED),
+            Style::default().fg(theme.text_muted),
         ),
     ]));
     lines.push(Line::from(vec![
-        Span::styled("Backend: ", Theme::label()),
+        Span::styled("Backend: ", theme.label()),
         Span::styled(
             info.backend_id.as_deref().unwrap_or("(none)"),
-            Style::default().fg(Theme::TEXT_MUTED),
+            Style::default().fg(theme.text_muted),
         ),
     ]));
+    lines.push(output_log_line(info, log_toggle_key, theme));
+    if !info.extra_mcp_servers.is_empty() {
+        let names: Vec<&str> = info
+            .extra_mcp_servers
+            .iter()
+            .map(|s| s.name.as_str())
+            .collect();
+        lines.push(Line::from(vec![
+            Span::styled("Extra MCP: ", theme.label()),
+            Span::styled(names.join(", "), Style::default().fg(theme.text_muted)),
+        ]));
+    }
 
     // ── Directories section ──
     if info.cwd.is_some() || !info.additional_dirs.is_empty() {
-        lines.push(separator());
+        lines.push(separator(theme));
         lines.push(Line::from(Span::styled(
             "Directories",
-            Theme::section_header(),
+            theme.section_header(),
         )));
         if let Some(cwd) = &info.cwd {
             lines.push(Line::from(Span::styled(
                 format!("  {} (cwd)", cwd.display()),
-                Style::default().fg(Theme::TEXT_MUTED),
+                Style::default().fg(theme.text_muted),
             )));
         }
         for dir in &info.additional_dirs {
             lines.push(Line::from(Span::styled(
                 format!("  {}", dir.display()),
-                Style::default().fg(Theme::TEXT_MUTED),
+                Style::default().fg(theme.text_muted),
             )));
         }
     }
 
     // ── Worktrees section ──
     if !info.worktrees.is_empty() {
-        lines.push(separator());
+        lines.push(separator(theme));
         let header = if info.worktrees.len() == 1 {
             "Worktree"
         } else {
             "Worktrees"
         };
-        lines.push(Line::from(Span::styled(header, Theme::section_header())));
-        for wt in &info.worktrees {
+        lines.push(Line::from(Span::styled(header, theme.section_header())));
+        for (i, wt) in info.worktrees.iter().enumerate() {
+            let status = worktree_statuses.get(i).and_then(Option::as_ref);
+            // Prefer the live branch: the agent may have switched it.
+            let branch = match status {
+                Some(status) => status.branch.as_deref().unwrap_or("(detached)"),
+                None => &wt.branch,
+            };
             lines.push(Line::from(vec![
-                Span::styled("Branch: ", Theme::label()),
-                Span::styled(&wt.branch, Style::default().fg(Theme::BRANCH_NAME)),
+                Span::styled("Branch: ", theme.label()),
+                Span::styled(branch, Style::default().fg(theme.branch_name)),
             ]));
+            if let Some(status) = status {
+                lines.push(git_status_line(status, theme));
+            }
             lines.push(Line::from(vec![
-                Span::styled("Path: ", Theme::label()),
+                Span::styled("Path: ", theme.label()),
                 Span::styled(
                     wt.worktree_path.display().to_string(),
-                    Style::default().fg(Theme::TEXT_MUTED),
+                    Style::default().fg(theme.text_muted),
                 ),
             ]));
         }
@@ -169,63 +319,391 @@ pub fn render_info_panel(
 
     // ── Role Details section ──
     if let Some(role_config) = project.and_then(|p| find_role(&p.config.roles, &info.role)) {
-        lines.push(separator());
+        lines.push(separator(theme));
         lines.push(Line::from(Span::styled(
             "Role Details",
-            Theme::section_header(),
+            theme.section_header(),
         )));
 
         if !role_config.description.is_empty() {
             lines.push(Line::from(vec![
-                Span::styled("Desc: ", Theme::label()),
+                Span::styled("Desc: ", theme.label()),
                 Span::styled(
                     &role_config.description,
-                    Style::default().fg(Theme::TEXT_PRIMARY),
+                    Style::default().fg(theme.text_primary),
                 ),
             ]));
         }
 
         if let Some(mode) = &role_config.permissions.permission_mode {
             lines.push(Line::from(vec![
-                Span::styled("Mode: ", Theme::label()),
-                Span::styled(mode, Style::default().fg(Theme::KEYBIND_HINT)),
+                Span::styled("Mode: ", theme.label()),
+                Span::styled(mode, Style::default().fg(theme.keybind_hint)),
             ]));
         }
 
         if !role_config.permissions.allowed_tools.is_empty() {
             lines.push(Line::from(vec![
-                Span::styled("Allowed: ", Theme::label()),
+                Span::styled("Allowed: ", theme.label()),
                 Span::styled(
                     role_config.permissions.allowed_tools.join(", "),
-                    Style::default().fg(Theme::TOOL_ALLOWED),
+                    Style::default().fg(theme.tool_allowed),
                 ),
             ]));
         }
 
         if !role_config.permissions.disallowed_tools.is_empty() {
             lines.push(Line::from(vec![
-                Span::styled("Disallowed: ", Theme::label()),
+                Span::styled("Disallowed: ", theme.label()),
                 Span::styled(
                     role_config.permissions.disallowed_tools.join(", "),
-                    Style::default().fg(Theme::TOOL_DISALLOWED),
+                    Style::default().fg(theme.tool_disallowed),
                 ),
             ]));
         }
     }
 
-    let paragraph = Paragraph::new(lines)
-        .block(block)
-        .wrap(Wrap { trim: false });
-    frame.render_widget(paragraph, area);
+    lines
+}
+
+/// Every worktree's branch, upstream counts and local changes.
+fn git_lines<'a>(
+    theme: &Theme,
+    info: &'a SessionInfo,
+    worktree_statuses: &[Option<WorktreeStatus>],
+) -> Vec<Line<'a>> {
+    if info.worktrees.is_empty() {
+        return vec![Line::from(Span::styled(
+            "Not a worktree session",
+            Style::default().fg(theme.text_muted),
+        ))];
+    }
+
+    let mut lines = Vec::new();
+    for (i, wt) in info.worktrees.iter().enumerate() {
+        if i > 0 {
+            lines.push(separator(theme));
+        }
+        let status = worktree_statuses.get(i).and_then(Option::as_ref);
+        let branch = match status {
+            Some(status) => status.branch.as_deref().unwrap_or("(detached)"),
+            None => &wt.branch,
+        };
+        lines.push(Line::from(vec![
+            Span::styled("Branch: ", theme.label()),
+            Span::styled(branch.to_string(), Style::default().fg(theme.branch_name)),
+        ]));
+        match status {
+            Some(status) => {
+                let upstream = match status.ahead_behind {
+                    Some((ahead, behind)) => format!("↑{ahead} ↓{behind}"),
+                    None => "(none)".to_string(),
+                };
+                lines.push(Line::from(vec![
+                    Span::styled("Upstream: ", theme.label()),
+                    Span::styled(upstream, Style::default().fg(theme.text_primary)),
+                ]));
+                lines.push(git_status_line(status, theme));
+            }
+            None => lines.push(Line::from(vec![
+                Span::styled("Git: ", theme.label()),
+                Span::styled("(unknown)", Style::default().fg(theme.text_muted)),
+            ])),
+        }
+        lines.push(Line::from(vec![
+            Span::styled("Repo: ", theme.label()),
+            Span::styled(
+                wt.repo_path.display().to_string(),
+                Style::default().fg(theme.text_muted),
+            ),
+        ]));
+        lines.push(Line::from(vec![
+            Span::styled("Path: ", theme.label()),
+            Span::styled(
+                wt.worktree_path.display().to_string(),
+                Style::default().fg(theme.text_muted),
+            ),
+        ]));
+    }
+    lines
+}
+
+/// The role name and description plus the permissions it resolves to.
+fn role_lines<'a>(
+    theme: &Theme,
+    info: &'a SessionInfo,
+    project: Option<&'a ProjectInfo>,
+    permissions: Option<&'a RolePermissions>,
+) -> Vec<Line<'a>> {
+    let mut lines = vec![Line::from(vec![
+        Span::styled("Role: ", theme.label()),
+        Span::styled(
+            &info.role,
+            Style::default()
+                .fg(theme.role_name)
+                .add_modifier(Modifier::BOLD),
+        ),
+    ])];
+    if let Some(role_config) = project.and_then(|p| find_role(&p.config.roles, &info.role)) {
+        if !role_config.description.is_empty() {
+            lines.push(Line::from(vec![
+                Span::styled("Desc: ", theme.label()),
+                Span::styled(
+                    &role_config.description,
+                    Style::default().fg(theme.text_primary),
+                ),
+            ]));
+        }
+    }
+    lines.push(separator(theme));
+
+    let Some(permissions) = permissions else {
+        lines.push(Line::from(Span::styled(
+            "No permissions resolved",
+            Style::default().fg(theme.text_muted),
+        )));
+        return lines;
+    };
+    let list = |items: &[String]| {
+        if items.is_empty() {
+            "(none)".to_string()
+        } else {
+            items.join(", ")
+        }
+    };
+    lines.push(Line::from(vec![
+        Span::styled("Mode: ", theme.label()),
+        Span::styled(
+            permissions.permission_mode.as_deref().unwrap_or("default"),
+            Style::default().fg(theme.keybind_hint),
+        ),
+    ]));
+    lines.push(Line::from(vec![
+        Span::styled("Allowed: ", theme.label()),
+        Span::styled(
+            list(&permissions.allowed_tools),
+            Style::default().fg(theme.tool_allowed),
+        ),
+    ]));
+    lines.push(Line::from(vec![
+        Span::styled("Disallowed: ", theme.label()),
+        Span::styled(
+            list(&permissions.disallowed_tools),
+            Style::default().fg(theme.tool_disallowed),
+        ),
+    ]));
+    if let Some(tools) = &permissions.tools {
+        lines.push(Line::from(vec![
+            Span::styled("Tools: ", theme.label()),
+            Span::styled(tools, Style::default().fg(theme.text_primary)),
+        ]));
+    }
+    if let Some(prompt) = &permissions.append_system_prompt {
+        lines.push(Line::from(vec![
+            Span::styled("Prompt: ", theme.label()),
+            Span::styled(prompt, Style::default().fg(theme.text_muted)),
+        ]));
+    }
+    if !permissions.env.is_empty() {
+        let mut keys: Vec<&str> = permissions.env.keys().map(String::as_str).collect();
+        keys.sort_unstable();
+        lines.push(Line::from(vec![
+            Span::styled("Env: ", theme.label()),
+            Span::styled(keys.join(", "), Style::default().fg(theme.text_primary)),
+        ]));
+    }
+    lines
+}
+
+/// "Git:" row with upstream ahead/behind counts and the dirty-file count.
+fn git_status_line<'a>(status: &WorktreeStatus, theme: &Theme) -> Line<'a> {
+    let mut spans = vec![Span::styled("Git: ", theme.label())];
+    if let Some((ahead, behind)) = status.ahead_behind {
+        spans.push(Span::styled(
+            format!("↑{ahead} ↓{behind} "),
+            Style::default().fg(theme.text_primary),
+        ));
+    }
+    spans.push(if status.dirty_files == 0 {
+        Span::styled("clean", Style::default().fg(theme.status_busy))
+    } else {
+        Span::styled(
+            format!("{} dirty", status.dirty_files),
+            Style::default().fg(theme.status_waiting),
+        )
+    });
+    Line::from(spans)
+}
+
+/// "Log:" row showing the active log file, or how to turn logging on.
+fn output_log_line<'a>(info: &'a SessionInfo, toggle_key: &str, theme: &Theme) -> Line<'a> {
+    let value = match &info.log_path {
+        Some(path) => Span::styled(
+            format!("{} ({toggle_key} to stop)", path.display()),
+            Style::default().fg(theme.text_muted),
+        ),
+        None => Span::styled(
+            format!("off ({toggle_key} to start)"),
+            Style::default().fg(theme.text_muted),
+        ),
+    };
+    Line::from(vec![Span::styled("Log: ", theme.label()), value])
 }
 
-fn separator<'a>() -> Line<'a> {
+fn separator<'a>(theme: &Theme) -> Line<'a> {
     Line::from(Span::styled(
         "──────────────────────",
-        Style::default().fg(Theme::BORDER_UNFOCUSED),
+        Style::default().fg(theme.border_unfocused),
     ))
 }
 
 fn find_role<'a>(roles: &'a [RoleConfig], name: &str) -> Option<&'a RoleConfig> {
     roles.iter().find(|r| r.name == name)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::{backend::TestBackend, Terminal};
+    use std::collections::HashMap;
+    use std::sync::OnceLock;
+
+    use crate::session::WorktreeInfo;
+
+    static ICONS: OnceLock<StatusIcons> = OnceLock::new();
+
+    fn render(state: &InfoPanelState<'_>) -> String {
+        let mut terminal = Terminal::new(TestBackend::new(50, 20)).unwrap();
+        terminal
+            .draw(|frame| {
+                let area = frame.area();
+                render_info_panel(frame, &Theme::default(), area, state);
+            })
+            .unwrap();
+        terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect()
+    }
+
+    fn state<'a>(
+        mode: InfoPanelMode,
+        info: &'a SessionInfo,
+        permissions: Option<&'a RolePermissions>,
+    ) -> InfoPanelState<'a> {
+        InfoPanelState {
+            mode,
+            info,
+            project: None,
+            log_toggle_key: "F3",
+            worktree_statuses: &[],
+            idle_ms: 0,
+            created: None,
+            activity: None,
+            permissions,
+            status_icons: ICONS.get_or_init(StatusIcons::default),
+        }
+    }
+
+    #[test]
+    fn custom_status_icons_replace_the_builtin_glyphs() {
+        let icons: HashMap<String, String> = SessionStatus::ALL
+            .iter()
+            .enumerate()
+            .map(|(i, status)| (status.as_str().to_string(), format!("<{i}>")))
+            .collect();
+        let custom = StatusIcons::from_config(None, &icons, &HashMap::new()).unwrap();
+
+        for (i, status) in SessionStatus::ALL.into_iter().enumerate() {
+            let mut info = SessionInfo::new("alpha".to_string());
+            info.status = status;
+            let state = InfoPanelState {
+                status_icons: &custom,
+                ..state(InfoPanelMode::Session, &info, None)
+            };
+            let text = render(&state);
+            assert!(
+                text.contains(&format!("Status: <{i}> {status}")),
+                "{status:?}"
+            );
+            assert!(!text.contains(status.icon()), "{status:?}");
+        }
+    }
+
+    #[test]
+    fn created_line_only_shown_when_known() {
+        let info = SessionInfo::new("alpha".to_string());
+        let unknown = render(&state(InfoPanelMode::Session, &info, None));
+        assert!(!unknown.contains("Created:"));
+
+        let known = InfoPanelState {
+            created: Some("3h ago".to_string()),
+            ..state(InfoPanelMode::Session, &info, None)
+        };
+        assert!(render(&known).contains("Created: 3h ago"));
+    }
+
+    #[test]
+    fn mode_cycles_through_all_views() {
+        let mode = InfoPanelMode::default();
+        assert_eq!(mode, InfoPanelMode::Session);
+        assert_eq!(mode.next(), InfoPanelMode::Git);
+        assert_eq!(mode.next().next(), InfoPanelMode::Role);
+        assert_eq!(mode.next().next().next(), InfoPanelMode::Session);
+    }
+
+    #[test]
+    fn session_mode_shows_metadata() {
+        let info = SessionInfo::new("alpha".to_string());
+        let text = render(&state(InfoPanelMode::Session, &info, None));
+        assert!(text.contains(" Info "));
+        assert!(text.contains("Name: alpha"));
+        assert!(text.contains("Log: off"));
+    }
+
+    #[test]
+    fn git_mode_shows_worktree_status() {
+        let mut info = SessionInfo::new("alpha".to_string());
+        let text = render(&state(InfoPanelMode::Git, &info, None));
+        assert!(text.contains(" Git "));
+        assert!(text.contains("Not a worktree session"));
+
+        info.worktrees = vec![WorktreeInfo {
+            repo_path: "/repo".into(),
+            worktree_path: "/repo/wt".into(),
+            branch: "feat".to_string(),
+            base_branch: "main".to_string(),
+        }];
+        let statuses = [Some(WorktreeStatus {
+            branch: Some("feat".to_string()),
+            ahead_behind: Some((2, 5)),
+            dirty_files: 3,
+        })];
+        let mut git = state(InfoPanelMode::Git, &info, None);
+        git.worktree_statuses = &statuses;
+        let text = render(&git);
+        assert!(text.contains("Branch: feat"));
+        assert!(text.contains("Upstream: ↑2 ↓5"));
+        assert!(text.contains("3 dirty"));
+        assert!(!text.contains("Name: alpha"));
+    }
+
+    #[test]
+    fn role_mode_shows_resolved_permissions() {
+        let info = SessionInfo::new("alpha".to_string());
+        let permissions = RolePermissions {
+            permission_mode: Some("plan".to_string()),
+            allowed_tools: vec!["Read".to_string()],
+            ..RolePermissions::default()
+        };
+        let text = render(&state(InfoPanelMode::Role, &info, Some(&permissions)));
+        assert!(text.contains(" Role "));
+        assert!(text.contains("Mode: plan"));
+        assert!(text.contains("Allowed: Read"));
+        assert!(text.contains("Disallowed: (none)"));
+        assert!(!text.contains("Name: alpha"));
+    }
+}
@@ -1,15 +1,117 @@
+use std::fmt;
+
 use ratatui::layout::{Constraint, Direction, Layout, Rect};
 
+/// Narrowest terminal that shows the info panel; below it the panel is
+/// hidden regardless of the user's toggle.
+pub const INFO_PANEL_MIN_WIDTH: u16 = 120;
+
+/// Where the session list goes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LayoutMode {
+    /// Project and session lists in a panel left of the terminal.
+    #[default]
+    Panels,
+    /// The active project's sessions as a one-line tab bar above the
+    /// terminal, giving the terminal the full width.
+    Tabs,
+}
+
+/// Panel widths as percentages of the terminal width, from the `[layout]`
+/// config table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PanelRatios {
+    /// Project/session list width while the info panel is hidden.
+    pub list_percent: u16,
+    /// Info panel width.
+    pub info_percent: u16,
+}
+
+impl Default for PanelRatios {
+    fn default() -> Self {
+        Self {
+            list_percent: 25,
+            info_percent: 15,
+        }
+    }
+}
+
+/// Error produced by [`PanelRatios::new`] for ratios that leave no room.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LayoutError {
+    ZeroWidth(&'static str),
+    TooWide {
+        list_percent: u16,
+        info_percent: u16,
+    },
+}
+
+impl fmt::Display for LayoutError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ZeroWidth(field) => write!(f, "layout.{field} must be at least 1"),
+            Self::TooWide {
+                list_percent,
+                info_percent,
+            } => write!(
+                f,
+                "layout.list_percent ({list_percent}) + layout.info_percent \
+                 ({info_percent}) must be under 100"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for LayoutError {}
+
+impl PanelRatios {
+    /// Validate ratios: both non-zero, and together under 100% so the
+    /// terminal keeps some width.
+    pub fn new(list_percent: u16, info_percent: u16) -> Result<Self, LayoutError> {
+        if list_percent == 0 {
+            return Err(LayoutError::ZeroWidth("list_percent"));
+        }
+        if info_percent == 0 {
+            return Err(LayoutError::ZeroWidth("info_percent"));
+        }
+        if list_percent.saturating_add(info_percent) >= 100 {
+            return Err(LayoutError::TooWide {
+                list_percent,
+                info_percent,
+            });
+        }
+        Ok(Self {
+            list_percent,
+            info_percent,
+        })
+    }
+
+    /// Sidebar width while the info panel is shown. The info panel takes
+    /// half its width from the list and half from the terminal (25% and
+    /// 15% give an 18% list).
+    fn sidebar_percent(&self) -> u16 {
+        ((self.list_percent * 2).saturating_sub(self.info_percent) + 1).max(2) / 2
+    }
+}
+
 pub struct PanelAreas {
     pub header: Rect,
     pub left_panel: Option<Rect>,
+    /// Session tab bar, only in [`LayoutMode::Tabs`].
+    pub session_tabs: Option<Rect>,
     pub info_panel: Option<Rect>,
     pub terminal: Rect,
     pub footer: Rect,
 }
 
-/// Compute panel layout areas based on terminal dimensions and info panel visibility.
-pub fn compute_layout(area: Rect, show_info_panel: bool) -> PanelAreas {
+/// Compute panel layout areas based on terminal dimensions, info panel
+/// visibility, layout mode and panel ratios.
+pub fn compute_layout(
+    area: Rect,
+    show_info_panel: bool,
+    mode: LayoutMode,
+    ratios: PanelRatios,
+) -> PanelAreas {
     // Vertical split: header | content | footer
     let vertical = Layout::default()
         .direction(Direction::Vertical)
@@ -24,45 +126,62 @@ pub fn compute_layout(area: Rect, show_info_panel: bool) -> PanelAreas {
     let content = vertical[1];
     let footer = vertical[2];
 
+    if mode == LayoutMode::Tabs {
+        return tab_layout(
+            header,
+            content,
+            footer,
+            (show_info_panel && area.width >= INFO_PANEL_MIN_WIDTH)
+                .then(|| ratios.sidebar_percent()),
+        );
+    }
+
     // If terminal is too narrow, show terminal only
     if area.width < 80 {
         return PanelAreas {
             header,
             left_panel: None,
+            session_tabs: None,
             info_panel: None,
             terminal: content,
             footer,
         };
     }
 
-    if show_info_panel && area.width >= 120 {
-        // 3-panel mode: 18% left panel | 15% info | 67% terminal
+    if show_info_panel && area.width >= INFO_PANEL_MIN_WIDTH {
+        // 3-panel mode: left panel | info | terminal (18% | 15% | 67% by default)
+        let list = ratios.sidebar_percent();
         let horizontal = Layout::default()
             .direction(Direction::Horizontal)
             .constraints([
-                Constraint::Percentage(18),
-                Constraint::Percentage(15),
-                Constraint::Percentage(67),
+                Constraint::Percentage(list),
+                Constraint::Percentage(ratios.info_percent),
+                Constraint::Percentage(100 - list - ratios.info_percent),
             ])
             .split(content);
 
         PanelAreas {
             header,
             left_panel: Some(horizontal[0]),
+            session_tabs: None,
             info_panel: Some(horizontal[1]),
             terminal: horizontal[2],
             footer,
         }
     } else {
-        // 2-panel mode: 25% left panel | 75% terminal
+        // 2-panel mode: left panel | terminal (25% | 75% by default)
         let horizontal = Layout::default()
             .direction(Direction::Horizontal)
-            .constraints([Constraint::Percentage(25), Constraint::Percentage(75)])
+            .constraints([
+                Constraint::Percentage(ratios.list_percent),
+                Constraint::Percentage(100 - ratios.list_percent),
+            ])
             .split(content);
 
         PanelAreas {
             header,
             left_panel: Some(horizontal[0]),
+            session_tabs: None,
             info_panel: None,
             terminal: horizontal[1],
             footer,
@@ -70,6 +189,37 @@ pub fn compute_layout(area: Rect, show_info_panel: bool) -> PanelAreas {
     }
 }
 
+/// Tab bar above the terminal, with the info panel (if shown, at
+/// `info_percent` of the width) to the left of both.
+fn tab_layout(header: Rect, content: Rect, footer: Rect, info_percent: Option<u16>) -> PanelAreas {
+    let (info_panel, main) = if let Some(info_percent) = info_percent {
+        let horizontal = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Percentage(info_percent),
+                Constraint::Percentage(100 - info_percent),
+            ])
+            .split(content);
+        (Some(horizontal[0]), horizontal[1])
+    } else {
+        (None, content)
+    };
+
+    let vertical = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(1), Constraint::Min(1)])
+        .split(main);
+
+    PanelAreas {
+        header,
+        left_panel: None,
+        session_tabs: Some(vertical[0]),
+        info_panel,
+        terminal: vertical[1],
+        footer,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -78,44 +228,49 @@ mod tests {
         Rect::new(0, 0, width, height)
     }
 
+    /// Layout with the default panel ratios.
+    fn compute_layout(area: Rect, show_info_panel: bool, mode: LayoutMode) -> PanelAreas {
+        super::compute_layout(area, show_info_panel, mode, PanelRatios::default())
+    }
+
     #[test]
     fn narrow_terminal_hides_left_panel() {
-        let areas = compute_layout(area(79, 24), false);
+        let areas = compute_layout(area(79, 24), false, LayoutMode::Panels);
         assert!(areas.left_panel.is_none());
         assert!(areas.info_panel.is_none());
     }
 
     #[test]
     fn normal_width_shows_two_panels() {
-        let areas = compute_layout(area(100, 24), false);
+        let areas = compute_layout(area(100, 24), false, LayoutMode::Panels);
         assert!(areas.left_panel.is_some());
         assert!(areas.info_panel.is_none());
     }
 
     #[test]
     fn wide_terminal_with_info_panel_shows_three_panels() {
-        let areas = compute_layout(area(120, 24), true);
+        let areas = compute_layout(area(120, 24), true, LayoutMode::Panels);
         assert!(areas.left_panel.is_some());
         assert!(areas.info_panel.is_some());
     }
 
     #[test]
     fn wide_terminal_without_info_panel_shows_two_panels() {
-        let areas = compute_layout(area(120, 24), false);
+        let areas = compute_layout(area(120, 24), false, LayoutMode::Panels);
         assert!(areas.left_panel.is_some());
         assert!(areas.info_panel.is_none());
     }
 
     #[test]
     fn header_and_footer_are_one_line() {
-        let areas = compute_layout(area(100, 24), false);
+        let areas = compute_layout(area(100, 24), false, LayoutMode::Panels);
         assert_eq!(areas.header.height, 1);
         assert_eq!(areas.footer.height, 1);
     }
 
     #[test]
     fn info_panel_ignored_below_120_cols() {
-        let areas = compute_layout(area(119, 24), true);
+        let areas = compute_layout(area(119, 24), true, LayoutMode::Panels);
         assert!(areas.info_panel.is_none());
     }
 
@@ -123,7 +278,7 @@ mod tests {
     /// matching what `content_area_size()` computes for tmux/vt100 sizing.
     fn terminal_inner(width: u16, height: u16, show_info: bool) -> (u16, u16) {
         use ratatui::widgets::{Block, Borders};
-        let terminal = compute_layout(area(width, height), show_info).terminal;
+        let terminal = compute_layout(area(width, height), show_info, LayoutMode::Panels).terminal;
         let inner = Block::default().borders(Borders::ALL).inner(terminal);
         (inner.height, inner.width)
     }
@@ -159,4 +314,82 @@ mod tests {
         assert_eq!(cols, 58);
         assert_eq!(rows, 20);
     }
+
+    #[test]
+    fn tab_mode_reserves_one_row_and_no_left_panel() {
+        let areas = compute_layout(area(100, 24), false, LayoutMode::Tabs);
+        assert!(areas.left_panel.is_none());
+        assert!(areas.info_panel.is_none());
+
+        let tabs = areas.session_tabs.unwrap();
+        assert_eq!(tabs.height, 1);
+        assert_eq!(tabs.width, 100);
+        assert_eq!(areas.terminal.y, tabs.y + 1);
+        assert_eq!(areas.terminal.width, 100);
+        // 24 - header(1) - footer(1) - tabs(1)
+        assert_eq!(areas.terminal.height, 21);
+    }
+
+    #[test]
+    fn tab_mode_keeps_tabs_on_narrow_terminals() {
+        let areas = compute_layout(area(60, 24), false, LayoutMode::Tabs);
+        assert!(areas.session_tabs.is_some());
+        assert_eq!(areas.terminal.width, 60);
+    }
+
+    #[test]
+    fn tab_mode_with_info_panel_on_wide_terminal() {
+        let areas = compute_layout(area(160, 40), true, LayoutMode::Tabs);
+        let info = areas.info_panel.unwrap();
+        let tabs = areas.session_tabs.unwrap();
+        assert!(areas.left_panel.is_none());
+        assert_eq!(tabs.x, info.x + info.width);
+        assert_eq!(tabs.width, areas.terminal.width);
+    }
+
+    #[test]
+    fn default_ratios_match_fixed_splits() {
+        let ratios = PanelRatios::default();
+        assert_eq!(PanelRatios::new(25, 15), Ok(ratios));
+        assert_eq!(ratios.sidebar_percent(), 18);
+    }
+
+    #[test]
+    fn ratios_must_leave_room_for_terminal() {
+        assert_eq!(
+            PanelRatios::new(60, 40),
+            Err(LayoutError::TooWide {
+                list_percent: 60,
+                info_percent: 40
+            })
+        );
+        assert_eq!(
+            PanelRatios::new(0, 15),
+            Err(LayoutError::ZeroWidth("list_percent"))
+        );
+        assert_eq!(
+            PanelRatios::new(25, 0),
+            Err(LayoutError::ZeroWidth("info_percent"))
+        );
+        assert!(PanelRatios::new(1, 98).is_ok());
+    }
+
+    #[test]
+    fn custom_ratios_resize_panels() {
+        let ratios = PanelRatios::new(40, 20).unwrap();
+        let areas = super::compute_layout(area(200, 40), false, LayoutMode::Panels, ratios);
+        assert_eq!(areas.left_panel.unwrap().width, 80);
+        assert_eq!(areas.terminal.width, 120);
+
+        let areas = super::compute_layout(area(200, 40), true, LayoutMode::Panels, ratios);
+        assert_eq!(areas.left_panel.unwrap().width, 60);
+        assert_eq!(areas.info_panel.unwrap().width, 40);
+        assert_eq!(areas.terminal.width, 100);
+    }
+
+    #[test]
+    fn panels_mode_has_no_tab_bar() {
+        let areas = compute_layout(area(100, 24), false, LayoutMode::Panels);
+        assert!(areas.session_tabs.is_none());
+    }
 }
@@ -12,7 +12,8 @@ use crate::app::mcp_editor_modal::McpEditorField;
 use crate::ui::role_editor_modal::ToolListMode;
 
 pub struct McpEditorState<'a> {
-    pub project_name: &'a str,
+    /// Breadcrumb trail leading to this form, e.g. `Edit "api" > MCP Servers`.
+    pub parent: &'a str,
     pub name: &'a str,
     pub name_cursor: usize,
     pub command: &'a str,
@@ -28,9 +29,11 @@ pub struct McpEditorState<'a> {
     pub env_input: &'a str,
     pub env_input_cursor: usize,
     pub focused_field: McpEditorField,
+    /// Command that could not be resolved on the last save attempt.
+    pub command_warning: Option<&'a str>,
 }
 
-pub fn render_mcp_editor_modal(frame: &mut Frame, state: &McpEditorState<'_>) {
+pub fn render_mcp_editor_modal(frame: &mut Frame, theme: &Theme, state: &McpEditorState<'_>) {
     let args_rows = super::role_editor_modal::tool_list_height(
         state.args,
         state.args_mode,
@@ -42,7 +45,8 @@ pub fn render_mcp_editor_modal(frame: &mut Frame, state: &McpEditorState<'_>) {
         state.focused_field == McpEditorField::Env,
     );
 
-    let content_height = 1 + 3 + 3 + args_rows + env_rows + 1; // +1 breadcrumb
+    let warning_rows = u16::from(state.command_warning.is_some());
+    let content_height = 1 + 3 + 3 + warning_rows + args_rows + env_rows + 1; // +1 breadcrumb
     let max_height = frame.area().height.saturating_sub(4);
     let height = (content_height + 2).min(max_height);
     let area = centered_fixed_height_rect(60, height, frame.area());
@@ -52,7 +56,7 @@ pub fn render_mcp_editor_modal(frame: &mut Frame, state: &McpEditorState<'_>) {
     let block = Block::default()
         .title(" Edit MCP Server ")
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Theme::ACCENT));
+        .border_style(Style::default().fg(theme.accent));
 
     let inner = block.inner(area);
     frame.render_widget(block, area);
@@ -60,12 +64,13 @@ pub fn render_mcp_editor_modal(frame: &mut Frame, state: &McpEditorState<'_>) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Length(1),         // Breadcrumb
-            Constraint::Length(3),         // Name
-            Constraint::Length(3),         // Command
-            Constraint::Length(args_rows), // Args
-            Constraint::Length(env_rows),  // Env
-            Constraint::Length(1),         // Footer
+            Constraint::Length(1),            // Breadcrumb
+            Constraint::Length(3),            // Name
+            Constraint::Length(3),            // Command
+            Constraint::Length(warning_rows), // Command warning
+            Constraint::Length(args_rows),    // Args
+            Constraint::Length(env_rows),     // Env
+            Constraint::Length(1),            // Footer
         ])
         .split(inner);
 
@@ -77,18 +82,16 @@ pub fn render_mcp_editor_modal(frame: &mut Frame, state: &McpEditorState<'_>) {
     };
     let breadcrumb = Line::from(vec![
         Span::styled(
-            format!(" Edit \"{}\"", state.project_name),
-            Style::default().fg(Theme::TEXT_MUTED),
+            format!(" {} > ", state.parent),
+            Style::default().fg(theme.text_muted),
         ),
-        Span::styled(" > ", Style::default().fg(Theme::TEXT_MUTED)),
-        Span::styled("MCP Servers", Style::default().fg(Theme::TEXT_MUTED)),
-        Span::styled(" > ", Style::default().fg(Theme::TEXT_MUTED)),
-        Span::styled(mcp_label, Style::default().fg(Theme::ACCENT)),
+        Span::styled(mcp_label, Style::default().fg(theme.accent)),
     ]);
     frame.render_widget(Paragraph::new(breadcrumb), chunks[0]);
 
     render_text_field(
         frame,
+        theme,
         chunks[1],
         "Name",
         state.name,
@@ -98,6 +101,7 @@ pub fn render_mcp_editor_modal(frame: &mut Frame, state: &McpEditorState<'_>) {
 
     render_text_field(
         frame,
+        theme,
         chunks[2],
         "Command",
         state.command,
@@ -105,9 +109,22 @@ pub fn render_mcp_editor_modal(frame: &mut Frame, state: &McpEditorState<'_>) {
         state.focused_field == McpEditorField::Command,
     );
 
+    if let Some(command) = state.command_warning {
+        let warning = Line::from(vec![
+            Span::styled(
+                format!(" ⚠ '{command}' not found on PATH. "),
+                Style::default().fg(theme.status_waiting),
+            ),
+            Span::styled("Enter", theme.keybind()),
+            Span::styled(" to save anyway", theme.keybind_desc()),
+        ]);
+        frame.render_widget(Paragraph::new(warning), chunks[3]);
+    }
+
     super::role_editor_modal::render_tool_list(
         frame,
-        chunks[3],
+        theme,
+        chunks[4],
         "Args",
         state.args,
         state.args_index,
@@ -119,7 +136,8 @@ pub fn render_mcp_editor_modal(frame: &mut Frame, state: &McpEditorState<'_>) {
 
     super::role_editor_modal::render_tool_list(
         frame,
-        chunks[4],
+        theme,
+        chunks[5],
         "Env (KEY=VALUE)",
         state.env,
         state.env_index,
@@ -142,33 +160,33 @@ pub fn render_mcp_editor_modal(frame: &mut Frame, state: &McpEditorState<'_>) {
 
     let footer = if is_list_field && list_mode == ToolListMode::Adding {
         Line::from(vec![
-            Span::styled("Enter", Theme::keybind()),
-            Span::styled(" confirm  ", Theme::keybind_desc()),
-            Span::styled("Esc", Theme::keybind()),
-            Span::styled(" cancel", Theme::keybind_desc()),
+            Span::styled("Enter", theme.keybind()),
+            Span::styled(" confirm  ", theme.keybind_desc()),
+            Span::styled("Esc", theme.keybind()),
+            Span::styled(" cancel", theme.keybind_desc()),
         ])
     } else if is_list_field {
         Line::from(vec![
-            Span::styled("a", Theme::keybind()),
-            Span::styled(" add  ", Theme::keybind_desc()),
-            Span::styled("d", Theme::keybind()),
-            Span::styled(" delete  ", Theme::keybind_desc()),
-            Span::styled("Tab", Theme::keybind()),
-            Span::styled(" next  ", Theme::keybind_desc()),
-            Span::styled("Enter", Theme::keybind()),
-            Span::styled(" save  ", Theme::keybind_desc()),
-            Span::styled("Esc", Theme::keybind()),
-            Span::styled(" discard", Theme::keybind_desc()),
+            Span::styled("a", theme.keybind()),
+            Span::styled(" add  ", theme.keybind_desc()),
+            Span::styled("d", theme.keybind()),
+            Span::styled(" delete  ", theme.keybind_desc()),
+            Span::styled("Tab", theme.keybind()),
+            Span::styled(" next  ", theme.keybind_desc()),
+            Span::styled("Enter", theme.keybind()),
+            Span::styled(" save  ", theme.keybind_desc()),
+            Span::styled("Esc", theme.keybind()),
+            Span::styled(" discard", theme.keybind_desc()),
         ])
     } else {
         Line::from(vec![
-            Span::styled("Tab", Theme::keybind()),
-            Span::styled(" next  ", Theme::keybind_desc()),
-            Span::styled("Enter", Theme::keybind()),
-            Span::styled(" save  ", Theme::keybind_desc()),
-            Span::styled("Esc", Theme::keybind()),
-            Span::styled(" discard", Theme::keybind_desc()),
+            Span::styled("Tab", theme.keybind()),
+            Span::styled(" next  ", theme.keybind_desc()),
+            Span::styled("Enter", theme.keybind()),
+            Span::styled(" save  ", theme.keybind_desc()),
+            Span::styled("Esc", theme.keybind()),
+            Span::styled(" discard", theme.keybind_desc()),
         ])
     };
-    frame.render_widget(Paragraph::new(footer), chunks[5]);
+    frame.render_widget(Paragraph::new(footer), chunks[6]);
 }
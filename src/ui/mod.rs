@@ -1,18 +1,38 @@
 pub mod add_project_modal;
 pub mod branch_selector_modal;
+pub mod clone_repo_modal;
+pub mod close_all_modal;
+pub mod close_session_modal;
+pub mod commit_modal;
 pub mod delete_project_modal;
 pub mod edit_project_modal;
+pub mod export_modal;
 pub mod info_panel;
 pub mod layout;
 pub mod links;
 pub mod mcp_editor_modal;
+pub mod move_session_modal;
 pub mod project_list;
+pub mod quit_modal;
+pub mod rename_session_modal;
 pub mod repo_selector_modal;
 pub mod restore_sessions_modal;
+pub mod resume_session_modal;
 pub mod role_editor_modal;
+pub mod role_file_modal;
 pub mod role_selector_modal;
+pub mod search;
+pub mod selection;
+pub mod send_prompt_modal;
+pub mod session_dirs_modal;
+pub mod session_label_modal;
+pub mod session_limit_modal;
 pub mod session_mode_modal;
+pub mod session_tabs;
 pub mod status_bar;
+pub mod status_icons;
+pub mod switcher_modal;
+pub mod sync_preview_modal;
 pub mod terminal_view;
 pub mod theme;
 pub mod worktree_name_modal;
@@ -28,12 +48,37 @@ use ratatui::{
 use crate::session::SessionStatus;
 use theme::Theme;
 
-pub fn status_color(status: SessionStatus) -> Color {
+/// Parse a user-supplied color: a name (`green`, `lightblue`), `#rrggbb`,
+/// or a 256-color index.
+pub fn parse_color(name: &str) -> Option<Color> {
+    name.trim().parse().ok()
+}
+
+pub fn status_color(status: SessionStatus, theme: &Theme) -> Color {
     match status {
-        SessionStatus::Busy => Theme::STATUS_BUSY,
-        SessionStatus::Waiting => Theme::STATUS_WAITING,
-        SessionStatus::Idle => Theme::STATUS_IDLE,
-        SessionStatus::Error => Theme::STATUS_ERROR,
+        SessionStatus::Busy => theme.status_busy,
+        SessionStatus::NeedsInput => theme.status_needs_input,
+        SessionStatus::Waiting => theme.status_waiting,
+        SessionStatus::Idle => theme.status_idle,
+        SessionStatus::Error => theme.status_error,
+        SessionStatus::Dormant => theme.text_muted,
+    }
+}
+
+/// Format a duration in milliseconds compactly: `45s`, `12m`, `3h 5m`.
+pub fn format_duration_short(ms: u64) -> String {
+    let secs = ms / 1_000;
+    if secs < 60 {
+        format!("{secs}s")
+    } else if secs < 3_600 {
+        format!("{}m", secs / 60)
+    } else {
+        let (hours, mins) = (secs / 3_600, secs % 3_600 / 60);
+        if mins == 0 {
+            format!("{hours}h")
+        } else {
+            format!("{hours}h {mins}m")
+        }
     }
 }
 
@@ -49,29 +94,29 @@ pub enum FocusLevel {
 }
 
 /// Build a [`Block`] with tri-state focus styling.
-pub fn focus_block(title_text: &str, level: FocusLevel) -> Block<'_> {
+pub fn focus_block<'a>(title_text: &'a str, level: FocusLevel, theme: &Theme) -> Block<'a> {
     match level {
         FocusLevel::Focused => Block::default()
-            .title(Line::from(Span::styled(title_text, Theme::focused_title())))
+            .title(Line::from(Span::styled(title_text, theme.focused_title())))
             .borders(Borders::ALL)
             .border_type(BorderType::Thick)
-            .border_style(Style::default().fg(Theme::BORDER_FOCUSED)),
+            .border_style(Style::default().fg(theme.border_focused)),
         FocusLevel::Active => Block::default()
             .title(Line::from(Span::styled(
                 title_text,
-                Style::default().fg(Theme::ACCENT),
+                Style::default().fg(theme.accent),
             )))
             .borders(Borders::ALL)
             .border_type(BorderType::Plain)
-            .border_style(Style::default().fg(Theme::ACCENT)),
+            .border_style(Style::default().fg(theme.accent)),
         FocusLevel::Inactive => Block::default()
             .title(Line::from(Span::styled(
                 title_text,
-                Theme::unfocused_title(),
+                theme.unfocused_title(),
             )))
             .borders(Borders::ALL)
             .border_type(BorderType::Plain)
-            .border_style(Style::default().fg(Theme::BORDER_UNFOCUSED)),
+            .border_style(Style::default().fg(theme.border_unfocused)),
     }
 }
 
@@ -80,26 +125,26 @@ pub fn focus_block(title_text: &str, level: FocusLevel) -> Block<'_> {
 /// Focused/Active use `ADMIN_BORDER` (yellow); Inactive falls back to
 /// the standard unfocused gray, keeping the admin chrome unobtrusive
 /// when the panel is in the background.
-pub fn admin_block(title_text: &str, level: FocusLevel) -> Block<'_> {
+pub fn admin_block<'a>(title_text: &'a str, level: FocusLevel, theme: &Theme) -> Block<'a> {
     match level {
         FocusLevel::Focused => Block::default()
-            .title(Line::from(Span::styled(title_text, Theme::admin_title())))
+            .title(Line::from(Span::styled(title_text, theme.admin_title())))
             .borders(Borders::ALL)
             .border_type(BorderType::Thick)
-            .border_style(Style::default().fg(Theme::ADMIN_BORDER)),
+            .border_style(Style::default().fg(theme.admin_border)),
         FocusLevel::Active => Block::default()
-            .title(Line::from(Span::styled(title_text, Theme::admin_title())))
+            .title(Line::from(Span::styled(title_text, theme.admin_title())))
             .borders(Borders::ALL)
             .border_type(BorderType::Plain)
-            .border_style(Style::default().fg(Theme::ADMIN_BORDER)),
+            .border_style(Style::default().fg(theme.admin_border)),
         FocusLevel::Inactive => Block::default()
             .title(Line::from(Span::styled(
                 title_text,
-                Theme::unfocused_title(),
+                theme.unfocused_title(),
             )))
             .borders(Borders::ALL)
             .border_type(BorderType::Plain)
-            .border_style(Style::default().fg(Theme::BORDER_UNFOCUSED)),
+            .border_style(Style::default().fg(theme.border_unfocused)),
     }
 }
 
@@ -107,7 +152,7 @@ pub fn admin_block(title_text: &str, level: FocusLevel) -> Block<'_> {
 ///
 /// Focused: thick borders in accent color with a highlighted title badge.
 /// Unfocused: plain borders in gray with a dimmed title.
-pub fn focused_block(title_text: &str, focused: bool) -> Block<'_> {
+pub fn focused_block<'a>(title_text: &'a str, focused: bool, theme: &Theme) -> Block<'a> {
     focus_block(
         title_text,
         if focused {
@@ -115,9 +160,31 @@ pub fn focused_block(title_text: &str, focused: bool) -> Block<'_> {
         } else {
             FocusLevel::Inactive
         },
+        theme,
     )
 }
 
+/// Create a centered rectangle within the given area.
+pub fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
+    let vertical = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(area);
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(vertical[1])[1]
+}
+
 /// Create a centered rectangle with a fixed width percentage and a fixed height in lines.
 pub fn centered_fixed_height_rect(percent_x: u16, height: u16, area: Rect) -> Rect {
     let vertical = Layout::default()
@@ -148,13 +215,14 @@ pub fn centered_fixed_height_rect(percent_x: u16, height: u16, area: Rect) -> Re
 /// When unfocused, the value is displayed as plain text with a dimmed border.
 pub fn render_text_field(
     frame: &mut Frame,
+    theme: &Theme,
     area: Rect,
     label: &str,
     value: &str,
     cursor: usize,
     focused: bool,
 ) {
-    render_text_field_with_suggestion(frame, area, label, value, cursor, focused, None);
+    render_text_field_with_suggestion(frame, theme, area, label, value, cursor, focused, None);
 }
 
 /// Render a text field with an optional inline suggestion (fish-style).
@@ -162,8 +230,10 @@ pub fn render_text_field(
 /// When `focused`, cursor at end, and `suggestion` is `Some`, the suggestion
 /// text is rendered in dark gray after the cursor block. Pass `None` for a
 /// plain text field (identical to [`render_text_field`]).
+#[allow(clippy::too_many_arguments)]
 pub fn render_text_field_with_suggestion(
     frame: &mut Frame,
+    theme: &Theme,
     area: Rect,
     label: &str,
     value: &str,
@@ -172,9 +242,9 @@ pub fn render_text_field_with_suggestion(
     suggestion: Option<&str>,
 ) {
     let border_color = if focused {
-        Theme::BORDER_FOCUSED
+        theme.border_focused
     } else {
-        Theme::BORDER_UNFOCUSED
+        theme.border_unfocused
     };
 
     let block = Block::default()
@@ -222,7 +292,7 @@ pub fn render_text_field_with_suggestion(
         let mut spans = Vec::new();
 
         if has_left_overflow {
-            spans.push(Span::styled("◀", Style::default().fg(Theme::TEXT_MUTED)));
+            spans.push(Span::styled("◀", Style::default().fg(theme.text_muted)));
         }
 
         let visible_end = (content_start + content_width).min(chars.len());
@@ -242,15 +312,12 @@ pub fn render_text_field_with_suggestion(
             if !before.is_empty() {
                 spans.push(Span::styled(
                     before,
-                    Style::default().fg(Theme::TEXT_PRIMARY),
+                    Style::default().fg(theme.text_primary),
                 ));
             }
-            spans.push(Span::styled(cursor_char, Theme::cursor()));
+            spans.push(Span::styled(cursor_char, theme.cursor()));
             if !after.is_empty() {
-                spans.push(Span::styled(
-                    after,
-                    Style::default().fg(Theme::TEXT_PRIMARY),
-                ));
+                spans.push(Span::styled(after, Style::default().fg(theme.text_primary)));
             }
 
             if !suggestion_text.is_empty() {
@@ -262,7 +329,7 @@ pub fn render_text_field_with_suggestion(
                 if remaining > 0 {
                     let sug: String = suggestion_text.chars().take(remaining).collect();
                     if !sug.is_empty() {
-                        spans.push(Span::styled(sug, Style::default().fg(Theme::TEXT_MUTED)));
+                        spans.push(Span::styled(sug, Style::default().fg(theme.text_muted)));
                     }
                 }
             }
@@ -270,12 +337,12 @@ pub fn render_text_field_with_suggestion(
             let visible: String = chars[content_start..visible_end].iter().collect();
             spans.push(Span::styled(
                 visible,
-                Style::default().fg(Theme::TEXT_PRIMARY),
+                Style::default().fg(theme.text_primary),
             ));
         }
 
         if has_right_overflow {
-            spans.push(Span::styled("▶", Style::default().fg(Theme::TEXT_MUTED)));
+            spans.push(Span::styled("▶", Style::default().fg(theme.text_muted)));
         }
 
         Line::from(spans)
@@ -283,14 +350,11 @@ pub fn render_text_field_with_suggestion(
         if chars.len() > width {
             let truncated: String = chars[..width - 1].iter().collect();
             Line::from(vec![
-                Span::styled(truncated, Style::default().fg(Theme::TEXT_PRIMARY)),
-                Span::styled("…", Style::default().fg(Theme::TEXT_MUTED)),
+                Span::styled(truncated, Style::default().fg(theme.text_primary)),
+                Span::styled("…", Style::default().fg(theme.text_muted)),
             ])
         } else {
-            Line::from(Span::styled(
-                value,
-                Style::default().fg(Theme::TEXT_PRIMARY),
-            ))
+            Line::from(Span::styled(value, Style::default().fg(theme.text_primary)))
         }
     } else {
         Line::from("")
@@ -300,10 +364,70 @@ pub fn render_text_field_with_suggestion(
     frame.render_widget(Paragraph::new(display), inner);
 }
 
+/// Render a multi-line text field showing `visible_lines` lines, scrolled
+/// so the line holding `cursor` (a char offset into `value`) stays in view.
+pub fn render_multiline_field(
+    frame: &mut Frame,
+    theme: &Theme,
+    area: Rect,
+    label: &str,
+    value: &str,
+    cursor: usize,
+    visible_lines: usize,
+) {
+    let block = Block::default()
+        .title(format!(" {label} "))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.border_focused));
+    let (cursor_line, cursor_col) = cursor_line_col(value, cursor);
+    let first_visible = (cursor_line + 1).saturating_sub(visible_lines);
+
+    let lines: Vec<Line<'_>> = value
+        .split('\n')
+        .enumerate()
+        .skip(first_visible)
+        .take(visible_lines)
+        .map(|(i, text)| {
+            let text_style = Style::default().fg(theme.text_primary);
+            if i != cursor_line {
+                return Line::from(Span::styled(text, text_style));
+            }
+            let chars: Vec<char> = text.chars().collect();
+            let before: String = chars[..cursor_col].iter().collect();
+            let at = chars
+                .get(cursor_col)
+                .map_or(" ".to_string(), char::to_string);
+            let after: String = chars.iter().skip(cursor_col + 1).collect();
+            Line::from(vec![
+                Span::styled(before, text_style),
+                Span::styled(at, theme.cursor()),
+                Span::styled(after, text_style),
+            ])
+        })
+        .collect();
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+/// Line index and column (both in chars) of char offset `cursor`.
+fn cursor_line_col(value: &str, cursor: usize) -> (usize, usize) {
+    let before: Vec<char> = value.chars().take(cursor).collect();
+    let line = before.iter().filter(|&&c| c == '\n').count();
+    let col = before.iter().rev().take_while(|&&c| c != '\n').count();
+    (line, col)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn cursor_line_col_tracks_newlines() {
+        assert_eq!(cursor_line_col("", 0), (0, 0));
+        assert_eq!(cursor_line_col("fix", 2), (0, 2));
+        assert_eq!(cursor_line_col("fix\n\nbody", 4), (1, 0));
+        assert_eq!(cursor_line_col("fix\n\nbody", 9), (2, 4));
+    }
+
     fn area(width: u16, height: u16) -> Rect {
         Rect::new(0, 0, width, height)
     }
@@ -338,16 +462,55 @@ mod tests {
 
     #[test]
     fn status_color_maps_all_variants() {
-        assert_eq!(status_color(SessionStatus::Busy), Color::Green);
-        assert_eq!(status_color(SessionStatus::Waiting), Color::Yellow);
-        assert_eq!(status_color(SessionStatus::Idle), Color::DarkGray);
-        assert_eq!(status_color(SessionStatus::Error), Color::Red);
+        assert_eq!(
+            status_color(SessionStatus::Busy, &Theme::default()),
+            Color::Green
+        );
+        assert_eq!(
+            status_color(SessionStatus::NeedsInput, &Theme::default()),
+            Color::LightMagenta
+        );
+        assert_eq!(
+            status_color(SessionStatus::Waiting, &Theme::default()),
+            Color::Yellow
+        );
+        assert_eq!(
+            status_color(SessionStatus::Idle, &Theme::default()),
+            Color::DarkGray
+        );
+        assert_eq!(
+            status_color(SessionStatus::Error, &Theme::default()),
+            Color::Red
+        );
+        assert_eq!(
+            status_color(SessionStatus::Dormant, &Theme::default()),
+            Theme::default().text_muted
+        );
+    }
+
+    #[test]
+    fn format_duration_short_sub_minute() {
+        assert_eq!(format_duration_short(0), "0s");
+        assert_eq!(format_duration_short(59_999), "59s");
+    }
+
+    #[test]
+    fn format_duration_short_minutes() {
+        assert_eq!(format_duration_short(60_000), "1m");
+        assert_eq!(format_duration_short(3_599_000), "59m");
+    }
+
+    #[test]
+    fn format_duration_short_hours() {
+        assert_eq!(format_duration_short(3_600_000), "1h");
+        assert_eq!(format_duration_short(3_600_000 + 5 * 60_000), "1h 5m");
+        assert_eq!(format_duration_short(26 * 3_600_000), "26h");
     }
 
     #[test]
     fn focused_block_returns_block_for_both_states() {
-        let focused = focused_block(" Test ", true);
-        let unfocused = focused_block(" Test ", false);
+        let focused = focused_block(" Test ", true, &Theme::default());
+        let unfocused = focused_block(" Test ", false, &Theme::default());
         // Verify both produce valid blocks that can compute inner area
         let test_area = area(40, 10);
         let inner_focused = focused.inner(test_area);
@@ -367,7 +530,7 @@ mod tests {
             FocusLevel::Active,
             FocusLevel::Inactive,
         ] {
-            let block = admin_block(" Admin ", level);
+            let block = admin_block(" Admin ", level, &Theme::default());
             let inner = block.inner(test_area);
             assert!(inner.width < test_area.width);
             assert!(inner.height < test_area.height);
@@ -0,0 +1,73 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout},
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, Paragraph},
+    Frame,
+};
+
+use super::centered_fixed_height_rect;
+use super::theme::Theme;
+
+pub struct MoveSessionState<'a> {
+    pub session_name: &'a str,
+    /// Names of the projects the session can move to.
+    pub projects: &'a [&'a str],
+    pub selected_index: usize,
+}
+
+/// Project picker for moving the active session (`m` in the session list).
+pub fn render_move_session_modal(frame: &mut Frame, theme: &Theme, state: &MoveSessionState<'_>) {
+    let height = (state.projects.len().min(15) + 4) as u16;
+    let area = centered_fixed_height_rect(50, height, frame.area());
+
+    frame.render_widget(Clear, area);
+
+    let block = Block::default()
+        .title(format!(" Move '{}' to Project ", state.session_name))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.accent));
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(1),    // Project list
+            Constraint::Length(1), // Footer
+        ])
+        .split(inner);
+
+    let items: Vec<ListItem<'_>> = state
+        .projects
+        .iter()
+        .enumerate()
+        .map(|(i, name)| {
+            let style = if i == state.selected_index {
+                theme.selected_item()
+            } else {
+                theme.normal_item()
+            };
+            let prefix = if i == state.selected_index {
+                "▸ "
+            } else {
+                "  "
+            };
+            ListItem::new(Line::from(Span::styled(format!("{prefix}{name}"), style)))
+        })
+        .collect();
+
+    let list = List::new(items);
+    frame.render_widget(list, chunks[0]);
+
+    let footer = Line::from(vec![
+        Span::styled("j/k", theme.keybind()),
+        Span::styled(" navigate  ", theme.keybind_desc()),
+        Span::styled("Enter", theme.keybind()),
+        Span::styled(" move  ", theme.keybind_desc()),
+        Span::styled("Esc", theme.keybind()),
+        Span::styled(" cancel", theme.keybind_desc()),
+    ]);
+    frame.render_widget(Paragraph::new(footer), chunks[1]);
+}
@@ -1,23 +1,27 @@
 use ratatui::{
-    layout::{Constraint, Direction, Layout, Rect},
-    style::{Color, Modifier, Style},
+    layout::{Constraint, Direction, Layout, Position, Rect},
+    style::{Modifier, Style},
     text::{Line, Span},
-    widgets::{List, ListItem, ListState, Paragraph},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
     Frame,
 };
 
+use super::status_icons::StatusIcons;
 use super::theme::Theme;
 use super::FocusLevel;
 use super::{admin_block, focus_block};
-use crate::session::SessionInfo;
+use crate::session::{SessionInfo, SessionStatus};
 
 pub struct ProjectEntry<'a> {
+    /// Index into the full project list.
+    pub index: usize,
     pub name: &'a str,
     pub is_admin: bool,
     pub repo_count: usize,
     pub repo_short: Option<&'a str>,
     pub role_count: usize,
     pub busy_count: usize,
+    pub needs_input_count: usize,
     pub waiting_count: usize,
     pub error_count: usize,
 }
@@ -29,7 +33,12 @@ pub enum LeftPanelFocus {
 }
 
 pub struct LeftPanelState<'a> {
+    /// Projects to show, which may be a filtered subset.
     pub projects: &'a [ProjectEntry<'a>],
+    /// Project name filter, shown in the project section title.
+    pub project_filter: Option<&'a str>,
+    /// The filter is being typed; a cursor is drawn after it.
+    pub project_filter_editing: bool,
     pub active_project: usize,
     pub sessions: &'a [&'a SessionInfo],
     pub active_session: usize,
@@ -41,21 +50,61 @@ pub struct LeftPanelState<'a> {
     pub project_focus: FocusLevel,
     /// Focus level for the session sub-section.
     pub session_focus: FocusLevel,
+    pub status_icons: &'a StatusIcons,
 }
 
-pub fn render_left_panel(frame: &mut Frame, area: Rect, state: &LeftPanelState<'_>) {
+/// A left-panel item a mouse click can land on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PanelItem {
+    /// Index into the full project list.
+    Project(usize),
+    /// Index into the active project's session list.
+    Session(usize),
+}
+
+/// A list as last drawn in the left panel, so clicks can be mapped back to
+/// the item under the cursor.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ListRegion {
+    /// Area inside the block border.
+    pub inner: Rect,
+    /// Rows per item.
+    pub item_height: u16,
+    /// Index of the first visible item (the list's scroll offset).
+    pub offset: usize,
+    /// Items in display order.
+    pub items: Vec<PanelItem>,
+}
+
+/// The item drawn at (`x`, `y`), or `None` for borders and empty space.
+pub fn item_at(regions: &[ListRegion], x: u16, y: u16) -> Option<PanelItem> {
+    let region = regions
+        .iter()
+        .find(|r| r.inner.contains(Position::new(x, y)))?;
+    let row = usize::from((y - region.inner.y) / region.item_height.max(1));
+    region.items.get(region.offset + row).copied()
+}
+
+/// Render the project and session lists. Returns where each list was drawn,
+/// for [`item_at`].
+pub fn render_left_panel(
+    frame: &mut Frame,
+    theme: &Theme,
+    area: Rect,
+    state: &LeftPanelState<'_>,
+) -> Vec<ListRegion> {
     // Partition projects into regular and admin groups, keeping original indices
     let regular: Vec<(usize, &ProjectEntry<'_>)> = state
         .projects
         .iter()
-        .enumerate()
-        .filter(|(_, p)| !p.is_admin)
+        .filter(|p| !p.is_admin)
+        .map(|p| (p.index, p))
         .collect();
     let admin: Vec<(usize, &ProjectEntry<'_>)> = state
         .projects
         .iter()
-        .enumerate()
-        .filter(|(_, p)| p.is_admin)
+        .filter(|p| p.is_admin)
+        .map(|p| (p.index, p))
         .collect();
 
     // 2 lines per regular project, 1 line per admin entry, +2 for borders per section
@@ -81,59 +130,115 @@ pub fn render_left_panel(frame: &mut Frame, area: Rect, state: &LeftPanelState<'
         .constraints(constraints)
         .split(area);
 
+    let mut regions = Vec::new();
+
     // Render regular projects section
-    render_project_section(
+    let title = project_section_title(state.project_filter, state.project_filter_editing);
+    let offset = render_project_section(
         frame,
+        theme,
+        state.status_icons,
         chunks[0],
+        &title,
         &regular,
         state.active_project,
         state.project_focus,
     );
+    regions.push(ListRegion {
+        inner: inner(chunks[0]),
+        item_height: 2,
+        offset,
+        items: regular
+            .iter()
+            .map(|&(i, _)| PanelItem::Project(i))
+            .collect(),
+    });
 
     let session_chunk_idx = if has_admin {
         // Render admin section
-        render_admin_section(
+        let offset = render_admin_section(
             frame,
+            theme,
+            state.status_icons,
             chunks[1],
             &admin,
             state.active_project,
             state.project_focus,
         );
+        regions.push(ListRegion {
+            inner: inner(chunks[1]),
+            item_height: 1,
+            offset,
+            items: admin.iter().map(|&(i, _)| PanelItem::Project(i)).collect(),
+        });
         2
     } else {
         1
     };
 
-    render_session_section(
+    let offset = render_session_section(
         frame,
+        theme,
+        state.status_icons,
         chunks[session_chunk_idx],
         state.sessions,
         state.active_session,
         state.session_elapsed_ms,
         state.session_focus,
     );
+    regions.push(ListRegion {
+        inner: inner(chunks[session_chunk_idx]),
+        item_height: 2,
+        offset,
+        items: (0..state.sessions.len()).map(PanelItem::Session).collect(),
+    });
+
+    regions
 }
 
-/// Build status dot spans for a project's aggregate session statuses.
-fn status_dots<'a>(project: &ProjectEntry<'a>) -> Vec<Span<'a>> {
-    let mut dots = Vec::new();
-    for _ in 0..project.busy_count {
-        dots.push(Span::styled("●", Style::default().fg(Theme::STATUS_BUSY)));
-    }
-    for _ in 0..project.waiting_count {
-        dots.push(Span::styled(
-            "◉",
-            Style::default().fg(Theme::STATUS_WAITING),
-        ));
-    }
-    for _ in 0..project.error_count {
-        dots.push(Span::styled("✗", Style::default().fg(Theme::STATUS_ERROR)));
+/// Project section title, with the filter query (and a cursor while it is
+/// being typed) when one is set.
+fn project_section_title(filter: Option<&str>, editing: bool) -> String {
+    match filter {
+        Some(query) => {
+            let cursor = if editing { "▏" } else { "" };
+            format!(" Projects /{query}{cursor} ")
+        }
+        None => " Projects ".to_string(),
     }
-    dots
+}
+
+/// Area inside a section's 1-cell border.
+fn inner(area: Rect) -> Rect {
+    Block::default().borders(Borders::ALL).inner(area)
+}
+
+/// Build status dot spans for a project's aggregate session statuses.
+fn status_dots<'a>(
+    project: &ProjectEntry<'a>,
+    theme: &Theme,
+    icons: &StatusIcons,
+) -> Vec<Span<'a>> {
+    let counts = [
+        (SessionStatus::Busy, project.busy_count),
+        (SessionStatus::NeedsInput, project.needs_input_count),
+        (SessionStatus::Waiting, project.waiting_count),
+        (SessionStatus::Error, project.error_count),
+    ];
+    counts
+        .into_iter()
+        .flat_map(|(status, count)| {
+            let dot = Span::styled(
+                icons.icon(status).to_string(),
+                Style::default().fg(super::status_color(status, theme)),
+            );
+            std::iter::repeat(dot).take(count)
+        })
+        .collect()
 }
 
 /// Build the metadata line (line 2) for a regular project entry.
-fn project_meta_line<'a>(project: &ProjectEntry<'a>) -> Line<'a> {
+fn project_meta_line<'a>(project: &ProjectEntry<'a>, theme: &Theme) -> Line<'a> {
     let repo_text = if project.repo_count == 1 {
         project.repo_short.unwrap_or("1 repo").to_string()
     } else if project.repo_count > 1 {
@@ -150,18 +255,22 @@ fn project_meta_line<'a>(project: &ProjectEntry<'a>) -> Line<'a> {
 
     Line::from(vec![Span::styled(
         format!("    {repo_text} · {role_text}"),
-        Theme::project_meta(),
+        theme.project_meta(),
     )])
 }
 
+#[allow(clippy::too_many_arguments)]
 fn render_project_section(
     frame: &mut Frame,
+    theme: &Theme,
+    icons: &StatusIcons,
     area: Rect,
+    title: &str,
     projects: &[(usize, &ProjectEntry<'_>)],
     active_index: usize,
     level: FocusLevel,
-) {
-    let block = focus_block(" Projects ", level);
+) -> usize {
+    let block = focus_block(title, level, theme);
 
     let items: Vec<ListItem> = projects
         .iter()
@@ -169,10 +278,10 @@ fn render_project_section(
             let is_active = orig_idx == active_index;
             let name_style = if is_active {
                 Style::default()
-                    .fg(Theme::ACCENT)
+                    .fg(theme.accent)
                     .add_modifier(Modifier::BOLD)
             } else {
-                Style::default().fg(Theme::TEXT_PRIMARY)
+                Style::default().fg(theme.text_primary)
             };
 
             let indicator = if is_active { "▸" } else { " " };
@@ -183,10 +292,10 @@ fn render_project_section(
                 Span::styled(project.name, name_style),
                 Span::raw("  "),
             ];
-            line1_spans.extend(status_dots(project));
+            line1_spans.extend(status_dots(project, theme, icons));
 
             let line1 = Line::from(line1_spans);
-            let line2 = project_meta_line(project);
+            let line2 = project_meta_line(project, theme);
 
             ListItem::new(vec![line1, line2])
         })
@@ -197,25 +306,26 @@ fn render_project_section(
         .iter()
         .position(|&(orig_idx, _)| orig_idx == active_index);
 
-    let list = List::new(items).block(block).highlight_style(
-        Style::default()
-            .bg(Color::DarkGray)
-            .add_modifier(Modifier::BOLD),
-    );
+    let list = List::new(items)
+        .block(block)
+        .highlight_style(theme.list_highlight());
 
     let mut list_state = ListState::default();
     list_state.select(list_active);
     frame.render_stateful_widget(list, area, &mut list_state);
+    list_state.offset()
 }
 
 fn render_admin_section(
     frame: &mut Frame,
+    theme: &Theme,
+    icons: &StatusIcons,
     area: Rect,
     projects: &[(usize, &ProjectEntry<'_>)],
     active_index: usize,
     level: FocusLevel,
-) {
-    let block = admin_block(" Admin ", level);
+) -> usize {
+    let block = admin_block(" Admin ", level, theme);
 
     let items: Vec<ListItem> = projects
         .iter()
@@ -223,10 +333,10 @@ fn render_admin_section(
             let is_active = orig_idx == active_index;
             let name_style = if is_active {
                 Style::default()
-                    .fg(Theme::ADMIN_BADGE)
+                    .fg(theme.admin_badge)
                     .add_modifier(Modifier::BOLD)
             } else {
-                Style::default().fg(Theme::ADMIN_BADGE)
+                Style::default().fg(theme.admin_badge)
             };
 
             let indicator = if is_active { "▸" } else { " " };
@@ -236,7 +346,7 @@ fn render_admin_section(
                 Span::styled(project.name, name_style),
                 Span::raw("  "),
             ];
-            line_spans.extend(status_dots(project));
+            line_spans.extend(status_dots(project, theme, icons));
 
             ListItem::new(Line::from(line_spans))
         })
@@ -246,33 +356,46 @@ fn render_admin_section(
         .iter()
         .position(|&(orig_idx, _)| orig_idx == active_index);
 
-    let list = List::new(items).block(block).highlight_style(
-        Style::default()
-            .bg(Color::DarkGray)
-            .add_modifier(Modifier::BOLD),
-    );
+    let list = List::new(items)
+        .block(block)
+        .highlight_style(theme.list_highlight());
 
     let mut list_state = ListState::default();
     list_state.select(list_active);
     frame.render_stateful_widget(list, area, &mut list_state);
+    list_state.offset()
+}
+
+/// Shown after the name of a pinned session.
+const PIN_MARKER: &str = " ⚑";
+
+/// Name highlight for a session that just rang the terminal bell.
+fn bell_style(theme: &Theme) -> Style {
+    Style::default()
+        .fg(theme.inverted_fg)
+        .bg(theme.status_needs_input)
+        .add_modifier(Modifier::BOLD)
 }
 
+#[allow(clippy::too_many_arguments)]
 fn render_session_section(
     frame: &mut Frame,
+    theme: &Theme,
+    icons: &StatusIcons,
     area: Rect,
     sessions: &[&SessionInfo],
     active_index: usize,
     elapsed_ms: &[u64],
     level: FocusLevel,
-) {
-    let block = focus_block(" Sessions ", level);
+) -> usize {
+    let block = focus_block(" Sessions ", level, theme);
 
     if sessions.is_empty() {
         let text = Paragraph::new("Ctrl+N to create session")
             .block(block)
-            .style(Style::default().fg(Theme::TEXT_MUTED));
+            .style(Style::default().fg(theme.text_muted));
         frame.render_widget(text, area);
-        return;
+        return 0;
     }
 
     // Available width inside the block (subtract 2 for borders)
@@ -285,16 +408,33 @@ fn render_session_section(
             let is_active = i == active_index;
             let prefix = if is_active { "▸" } else { " " };
 
-            let status_text = format_status_with_elapsed(info.status, elapsed_ms.get(i).copied());
-            let name_style = if is_active {
-                Theme::selected_item()
+            let status_text = if info.crash_looping {
+                "Crash loop".to_string()
+            } else if info.cwd_missing {
+                "No cwd".to_string()
+            } else if info.backend_dead {
+                "Died".to_string()
+            } else {
+                format_status_with_elapsed(
+                    info.status,
+                    icons.label(info.status),
+                    elapsed_ms.get(i).copied(),
+                )
+            };
+            let name_style = if info.bell_ticks > 0 {
+                bell_style(theme)
+            } else if is_active {
+                theme.selected_item()
             } else {
-                Theme::normal_item()
+                theme.normal_item()
             };
 
             // "▸ ● " prefix is 4 chars wide (indicator + space + icon + space)
             let prefix_width = 4;
-            let name_len = info.name.chars().count();
+            let pin = if info.is_pinned { PIN_MARKER } else { "" };
+            let label = label_span(info, theme);
+            let label_len = label.as_ref().map_or(0, |l| l.content.chars().count());
+            let name_len = label_len + info.name.chars().count() + pin.chars().count();
             let status_len = status_text.chars().count();
             let used = prefix_width + name_len + status_len;
             let gap = if used < inner_width {
@@ -303,24 +443,36 @@ fn render_session_section(
                 1
             };
 
-            let status_style = Style::default().fg(super::status_color(info.status));
-            let line1 = Line::from(vec![
-                Span::styled(format!("{prefix} {} ", info.status.icon()), status_style),
+            let status_style = Style::default().fg(super::status_color(info.status, theme));
+            let mut line1_spans = vec![Span::styled(
+                format!("{prefix} {} ", icons.icon(info.status)),
+                status_style,
+            )];
+            line1_spans.extend(label);
+            line1_spans.extend([
                 Span::styled(&info.name, name_style),
+                Span::styled(pin, Style::default().fg(theme.accent)),
                 Span::raw(" ".repeat(gap)),
                 Span::styled(status_text, status_style),
             ]);
+            let line1 = Line::from(line1_spans);
 
             // Line 2: indented role name + optional · branch
             let mut line2_spans = vec![Span::styled(
                 format!("    {}", info.role),
-                Style::default().fg(Theme::ROLE_NAME),
+                Style::default().fg(theme.role_name),
             )];
             if let Some(wt) = info.worktrees.first() {
-                line2_spans.push(Span::styled(" · ", Style::default().fg(Theme::TEXT_MUTED)));
+                line2_spans.push(Span::styled(" · ", Style::default().fg(theme.text_muted)));
                 line2_spans.push(Span::styled(
                     &wt.branch,
-                    Style::default().fg(Theme::BRANCH_NAME),
+                    Style::default().fg(theme.branch_name),
+                ));
+            }
+            if let Some(title) = &info.title {
+                line2_spans.push(Span::styled(
+                    format!(" · {title}"),
+                    Style::default().fg(theme.text_muted),
                 ));
             }
             let line2 = Line::from(line2_spans);
@@ -329,40 +481,54 @@ fn render_session_section(
         })
         .collect();
 
-    let list = List::new(items).block(block).highlight_style(
-        Style::default()
-            .bg(Color::DarkGray)
-            .add_modifier(Modifier::BOLD),
-    );
+    let list = List::new(items)
+        .block(block)
+        .highlight_style(theme.list_highlight());
 
     let mut state = ListState::default();
     state.select(Some(active_index));
     frame.render_stateful_widget(list, area, &mut state);
+    state.offset()
+}
+
+/// The session's label drawn before its name: a dot in the label color
+/// followed by the label text. Unset or unparsable colors use the accent.
+fn label_span(info: &SessionInfo, theme: &Theme) -> Option<Span<'static>> {
+    if info.label.is_none() && info.label_color.is_none() {
+        return None;
+    }
+    let color = info
+        .label_color
+        .as_deref()
+        .and_then(super::parse_color)
+        .unwrap_or(theme.accent);
+    let text = match info.label.as_deref() {
+        Some(label) => format!("● {label} "),
+        None => "● ".to_string(),
+    };
+    Some(Span::styled(text, Style::default().fg(color)))
 }
 
 /// Format status text with elapsed time for Waiting/Idle sessions.
 fn format_status_with_elapsed(
-    status: crate::session::SessionStatus,
+    status: SessionStatus,
+    label: &str,
     elapsed_ms: Option<u64>,
 ) -> String {
-    use crate::session::SessionStatus;
     match (status, elapsed_ms) {
-        (SessionStatus::Waiting | SessionStatus::Idle, Some(ms)) if ms >= 60_000 => {
-            let mins = ms / 60_000;
-            format!("{status} {mins}m")
-        }
-        (SessionStatus::Waiting | SessionStatus::Idle, Some(ms)) if ms >= 10_000 => {
-            let secs = ms / 1_000;
-            format!("{status} {secs}s")
+        (SessionStatus::NeedsInput | SessionStatus::Waiting | SessionStatus::Idle, Some(ms))
+            if ms >= 10_000 =>
+        {
+            format!("{label} {}", super::format_duration_short(ms))
         }
-        _ => format!("{status}"),
+        _ => label.to_string(),
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::session::SessionStatus;
+    use ratatui::style::Color;
 
     fn test_entry<'a>(
         name: &'a str,
@@ -374,36 +540,80 @@ mod tests {
         role_count: usize,
     ) -> ProjectEntry<'a> {
         ProjectEntry {
+            index: 0,
             name,
             is_admin: false,
             repo_count,
             repo_short,
             role_count,
             busy_count: busy,
+            needs_input_count: 0,
             waiting_count: waiting,
             error_count: error,
         }
     }
 
+    #[test]
+    fn project_title_shows_filter() {
+        assert_eq!(project_section_title(None, false), " Projects ");
+        assert_eq!(project_section_title(Some("api"), false), " Projects /api ");
+        assert_eq!(project_section_title(Some("api"), true), " Projects /api▏ ");
+    }
+
+    // --- label_span ---
+
+    #[test]
+    fn label_span_uses_label_color() {
+        let mut info = SessionInfo::new("api".to_string());
+        assert!(label_span(&info, &Theme::default()).is_none());
+
+        info.label = Some("backend".to_string());
+        info.label_color = Some("blue".to_string());
+        let span = label_span(&info, &Theme::default()).unwrap();
+        assert_eq!(span.content, "● backend ");
+        assert_eq!(span.style.fg, Some(Color::Blue));
+
+        info.label_color = Some("#ff8800".to_string());
+        let span = label_span(&info, &Theme::default()).unwrap();
+        assert_eq!(span.style.fg, Some(Color::Rgb(0xff, 0x88, 0x00)));
+    }
+
+    #[test]
+    fn label_span_color_only_and_invalid_color_fallback() {
+        let theme = Theme::default();
+        let mut info = SessionInfo::new("api".to_string());
+        info.label_color = Some("green".to_string());
+        let span = label_span(&info, &theme).unwrap();
+        assert_eq!(span.content, "● ");
+        assert_eq!(span.style.fg, Some(Color::Green));
+
+        info.label = Some("ui".to_string());
+        info.label_color = Some("not-a-color".to_string());
+        assert_eq!(
+            label_span(&info, &theme).unwrap().style.fg,
+            Some(theme.accent)
+        );
+    }
+
     // --- status_dots ---
 
     #[test]
     fn status_dots_empty_for_no_sessions() {
         let entry = test_entry("P", 0, 0, 0, 0, None, 0);
-        assert!(status_dots(&entry).is_empty());
+        assert!(status_dots(&entry, &Theme::default(), &StatusIcons::default()).is_empty());
     }
 
     #[test]
     fn status_dots_counts_match_input() {
         let entry = test_entry("P", 2, 1, 3, 0, None, 0);
-        let dots = status_dots(&entry);
+        let dots = status_dots(&entry, &Theme::default(), &StatusIcons::default());
         assert_eq!(dots.len(), 6); // 2 busy + 1 waiting + 3 error
     }
 
     #[test]
     fn status_dots_ordering_is_busy_waiting_error() {
         let entry = test_entry("P", 1, 1, 1, 0, None, 0);
-        let dots = status_dots(&entry);
+        let dots = status_dots(&entry, &Theme::default(), &StatusIcons::default());
         assert_eq!(dots.len(), 3);
         // Busy dot uses ●
         assert_eq!(dots[0].content, "●");
@@ -418,7 +628,7 @@ mod tests {
     #[test]
     fn meta_line_single_repo_shows_name() {
         let entry = test_entry("P", 0, 0, 0, 1, Some("myrepo"), 2);
-        let line = project_meta_line(&entry);
+        let line = project_meta_line(&entry, &Theme::default());
         let text: String = line.spans.iter().map(|s| s.content.as_ref()).collect();
         assert!(text.contains("myrepo"));
         assert!(text.contains("2 roles"));
@@ -427,7 +637,7 @@ mod tests {
     #[test]
     fn meta_line_multiple_repos_shows_count() {
         let entry = test_entry("P", 0, 0, 0, 3, None, 1);
-        let line = project_meta_line(&entry);
+        let line = project_meta_line(&entry, &Theme::default());
         let text: String = line.spans.iter().map(|s| s.content.as_ref()).collect();
         assert!(text.contains("3 repos"));
         assert!(text.contains("1 role"));
@@ -436,7 +646,7 @@ mod tests {
     #[test]
     fn meta_line_no_repos() {
         let entry = test_entry("P", 0, 0, 0, 0, None, 0);
-        let line = project_meta_line(&entry);
+        let line = project_meta_line(&entry, &Theme::default());
         let text: String = line.spans.iter().map(|s| s.content.as_ref()).collect();
         assert!(text.contains("no repos"));
         assert!(text.contains("0 roles"));
@@ -445,41 +655,121 @@ mod tests {
     #[test]
     fn meta_line_single_repo_without_short_name() {
         let entry = test_entry("P", 0, 0, 0, 1, None, 1);
-        let line = project_meta_line(&entry);
+        let line = project_meta_line(&entry, &Theme::default());
         let text: String = line.spans.iter().map(|s| s.content.as_ref()).collect();
         assert!(text.contains("1 repo"));
         assert!(text.contains("1 role"));
     }
 
+    #[test]
+    fn status_dots_include_needs_input() {
+        let mut entry = test_entry("P", 1, 1, 0, 0, None, 0);
+        entry.needs_input_count = 2;
+        let dots = status_dots(&entry, &Theme::default(), &StatusIcons::default());
+        assert_eq!(dots.len(), 4);
+        assert_eq!(dots[1].content, "◆");
+    }
+
     // --- format_status_with_elapsed ---
 
     #[test]
     fn elapsed_minutes_shown_above_60s() {
-        let text = format_status_with_elapsed(SessionStatus::Waiting, Some(120_000));
+        let text = format_status_with_elapsed(SessionStatus::Waiting, "Waiting", Some(120_000));
         assert_eq!(text, "Waiting 2m");
     }
 
     #[test]
     fn elapsed_seconds_shown_between_10s_and_60s() {
-        let text = format_status_with_elapsed(SessionStatus::Idle, Some(30_000));
+        let text = format_status_with_elapsed(SessionStatus::Idle, "Idle", Some(30_000));
         assert_eq!(text, "Idle 30s");
     }
 
+    #[test]
+    fn elapsed_hours_shown_above_60m() {
+        let text =
+            format_status_with_elapsed(SessionStatus::Waiting, "Waiting", Some(2 * 3_600_000));
+        assert_eq!(text, "Waiting 2h");
+    }
+
     #[test]
     fn elapsed_not_shown_below_10s() {
-        let text = format_status_with_elapsed(SessionStatus::Waiting, Some(5_000));
+        let text = format_status_with_elapsed(SessionStatus::Waiting, "Waiting", Some(5_000));
         assert_eq!(text, "Waiting");
     }
 
+    #[test]
+    fn elapsed_shown_for_needs_input() {
+        let text = format_status_with_elapsed(SessionStatus::NeedsInput, "Input", Some(30_000));
+        assert_eq!(text, "Input 30s");
+    }
+
     #[test]
     fn elapsed_not_shown_for_busy() {
-        let text = format_status_with_elapsed(SessionStatus::Busy, Some(120_000));
+        let text = format_status_with_elapsed(SessionStatus::Busy, "Busy", Some(120_000));
         assert_eq!(text, "Busy");
     }
 
     #[test]
     fn elapsed_none_shows_plain_status() {
-        let text = format_status_with_elapsed(SessionStatus::Error, None);
+        let text = format_status_with_elapsed(SessionStatus::Error, "Error", None);
         assert_eq!(text, "Error");
     }
+
+    // --- item_at ---
+
+    /// Projects at rows 1..=4 (two 2-row items), sessions from row 8
+    /// scrolled by one item, all inside a 20-column panel at x = 0.
+    fn sample_regions() -> Vec<ListRegion> {
+        vec![
+            ListRegion {
+                inner: Rect::new(1, 1, 18, 4),
+                item_height: 2,
+                offset: 0,
+                items: vec![PanelItem::Project(0), PanelItem::Project(2)],
+            },
+            ListRegion {
+                inner: Rect::new(1, 8, 18, 6),
+                item_height: 2,
+                offset: 1,
+                items: (0..4).map(PanelItem::Session).collect(),
+            },
+        ]
+    }
+
+    #[test]
+    fn item_at_maps_rows_to_items() {
+        let regions = sample_regions();
+        assert_eq!(item_at(&regions, 1, 1), Some(PanelItem::Project(0)));
+        assert_eq!(item_at(&regions, 18, 2), Some(PanelItem::Project(0)));
+        assert_eq!(item_at(&regions, 5, 3), Some(PanelItem::Project(2)));
+        assert_eq!(item_at(&regions, 5, 4), Some(PanelItem::Project(2)));
+    }
+
+    #[test]
+    fn item_at_accounts_for_scroll_offset() {
+        let regions = sample_regions();
+        assert_eq!(item_at(&regions, 5, 8), Some(PanelItem::Session(1)));
+        assert_eq!(item_at(&regions, 5, 11), Some(PanelItem::Session(2)));
+        assert_eq!(item_at(&regions, 5, 12), Some(PanelItem::Session(3)));
+    }
+
+    #[test]
+    fn item_at_ignores_borders_and_empty_space() {
+        let regions = sample_regions();
+        // Border row and column
+        assert_eq!(item_at(&regions, 5, 0), None);
+        assert_eq!(item_at(&regions, 0, 2), None);
+        // Between the sections
+        assert_eq!(item_at(&regions, 5, 6), None);
+        // Outside the panel
+        assert_eq!(item_at(&regions, 40, 2), None);
+        // Below the last item of a list
+        let regions = vec![ListRegion {
+            inner: Rect::new(1, 1, 18, 10),
+            item_height: 2,
+            offset: 0,
+            items: vec![PanelItem::Session(0)],
+        }];
+        assert_eq!(item_at(&regions, 5, 4), None);
+    }
 }
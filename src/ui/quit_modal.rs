@@ -0,0 +1,74 @@
+use ratatui::{
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use super::centered_fixed_height_rect;
+use super::theme::Theme;
+
+/// Confirmation shown on Ctrl+Q while user sessions are running.
+/// `session_count` is the number of sessions that will be detached, or
+/// stopped when the backend cannot keep them alive (`keeps_sessions`).
+pub fn render_quit_modal(
+    frame: &mut Frame,
+    theme: &Theme,
+    session_count: usize,
+    keeps_sessions: bool,
+) {
+    let area = centered_fixed_height_rect(60, 7, frame.area());
+
+    frame.render_widget(Clear, area);
+
+    let block = Block::default()
+        .title(" Quit Thurbox ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.border_focused));
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let noun = if session_count == 1 {
+        "session"
+    } else {
+        "sessions"
+    };
+    let (verb, note) = if keeps_sessions {
+        (
+            "Detach ",
+            "Sessions keep running and are reattached on next start.",
+        )
+    } else {
+        (
+            "Stop ",
+            "This backend cannot keep sessions running; they will end.",
+        )
+    };
+    let lines = vec![
+        Line::from(vec![
+            Span::styled(verb, Style::default().fg(theme.text_primary)),
+            Span::styled(
+                format!("{session_count} {noun}"),
+                Style::default()
+                    .fg(theme.accent)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(" and quit?", Style::default().fg(theme.text_primary)),
+        ]),
+        Line::from(""),
+        Line::from(Span::styled(
+            note,
+            Style::default().fg(theme.text_secondary),
+        )),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("y/Enter/Ctrl+Q", theme.keybind()),
+            Span::styled(" quit  ", theme.keybind_desc()),
+            Span::styled("n/Esc", theme.keybind()),
+            Span::styled(" cancel", theme.keybind_desc()),
+        ]),
+    ];
+
+    frame.render_widget(Paragraph::new(lines), inner);
+}
@@ -0,0 +1,59 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout},
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use super::centered_fixed_height_rect;
+use super::theme::Theme;
+
+pub struct RenameSessionState<'a> {
+    pub name: &'a str,
+    pub cursor: usize,
+}
+
+pub fn render_rename_session_modal(
+    frame: &mut Frame,
+    theme: &Theme,
+    state: &RenameSessionState<'_>,
+) {
+    let area = centered_fixed_height_rect(50, 6, frame.area());
+
+    frame.render_widget(Clear, area);
+
+    let block = Block::default()
+        .title(" Rename Session ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.accent));
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Name field
+            Constraint::Min(1),    // Footer
+        ])
+        .split(inner);
+
+    super::render_text_field(
+        frame,
+        theme,
+        chunks[0],
+        "Name",
+        state.name,
+        state.cursor,
+        true,
+    );
+
+    let footer = Line::from(vec![
+        Span::styled("Enter", theme.keybind()),
+        Span::styled(" rename  ", theme.keybind_desc()),
+        Span::styled("Esc", theme.keybind()),
+        Span::styled(" cancel", theme.keybind_desc()),
+    ]);
+    frame.render_widget(Paragraph::new(footer), chunks[1]);
+}
@@ -16,7 +16,7 @@ pub struct RepoSelectorState<'a> {
     pub selected_index: usize,
 }
 
-pub fn render_repo_selector_modal(frame: &mut Frame, state: &RepoSelectorState<'_>) {
+pub fn render_repo_selector_modal(frame: &mut Frame, theme: &Theme, state: &RepoSelectorState<'_>) {
     let height = (state.repos.len().min(15) + 4) as u16;
     let area = centered_fixed_height_rect(50, height, frame.area());
 
@@ -25,7 +25,7 @@ pub fn render_repo_selector_modal(frame: &mut Frame, state: &RepoSelectorState<'
     let block = Block::default()
         .title(" Select Repo ")
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Theme::ACCENT));
+        .border_style(Style::default().fg(theme.accent));
 
     let inner = block.inner(area);
     frame.render_widget(block, area);
@@ -45,9 +45,9 @@ pub fn render_repo_selector_modal(frame: &mut Frame, state: &RepoSelectorState<'
         .map(|(i, path)| {
             let display = path.display().to_string();
             let style = if i == state.selected_index {
-                Theme::selected_item()
+                theme.selected_item()
             } else {
-                Theme::normal_item()
+                theme.normal_item()
             };
             let prefix = if i == state.selected_index {
                 "▸ "
@@ -65,12 +65,12 @@ pub fn render_repo_selector_modal(frame: &mut Frame, state: &RepoSelectorState<'
     frame.render_widget(list, chunks[0]);
 
     let footer = Line::from(vec![
-        Span::styled("j/k", Theme::keybind()),
-        Span::styled(" navigate  ", Theme::keybind_desc()),
-        Span::styled("Enter", Theme::keybind()),
-        Span::styled(" select  ", Theme::keybind_desc()),
-        Span::styled("Esc", Theme::keybind()),
-        Span::styled(" cancel", Theme::keybind_desc()),
+        Span::styled("j/k", theme.keybind()),
+        Span::styled(" navigate  ", theme.keybind_desc()),
+        Span::styled("Enter", theme.keybind()),
+        Span::styled(" select  ", theme.keybind_desc()),
+        Span::styled("Esc", theme.keybind()),
+        Span::styled(" cancel", theme.keybind_desc()),
     ]);
     frame.render_widget(Paragraph::new(footer), chunks[1]);
 }
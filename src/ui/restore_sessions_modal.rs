@@ -22,7 +22,11 @@ pub struct RestoreSessionsModalState<'a> {
     pub selected_index: usize,
 }
 
-pub fn render_restore_sessions_modal(frame: &mut Frame, state: &RestoreSessionsModalState<'_>) {
+pub fn render_restore_sessions_modal(
+    frame: &mut Frame,
+    theme: &Theme,
+    state: &RestoreSessionsModalState<'_>,
+) {
     let list_height = state.entries.len().max(1) as u16;
     // 2 (borders) + list_height + 1 (footer) + 2 (padding)
     let total_height = (list_height + 5).min(20);
@@ -33,7 +37,7 @@ pub fn render_restore_sessions_modal(frame: &mut Frame, state: &RestoreSessionsM
     let block = Block::default()
         .title(" Restore Deleted Sessions ")
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Theme::ACCENT));
+        .border_style(Style::default().fg(theme.accent));
 
     let inner = block.inner(area);
     frame.render_widget(block, area);
@@ -46,13 +50,13 @@ pub fn render_restore_sessions_modal(frame: &mut Frame, state: &RestoreSessionsM
 
         let empty = Paragraph::new(Line::from(Span::styled(
             "No deleted sessions",
-            Style::default().fg(Theme::TEXT_MUTED),
+            Style::default().fg(theme.text_muted),
         )))
         .alignment(Alignment::Center);
         frame.render_widget(empty, chunks[0]);
 
         let help = Line::from(vec![
-            Span::styled("Esc", Theme::keybind()),
+            Span::styled("Esc", theme.keybind()),
             Span::raw(" close"),
         ]);
         frame.render_widget(Paragraph::new(help), chunks[1]);
@@ -77,11 +81,11 @@ pub fn render_restore_sessions_modal(frame: &mut Frame, state: &RestoreSessionsM
                 entry.name, entry.role, entry.deleted_ago, wt_indicator
             );
             if selected {
-                Line::from(Span::styled(text, Theme::selected_item()))
+                Line::from(Span::styled(text, theme.selected_item()))
             } else {
                 Line::from(Span::styled(
                     text,
-                    Style::default().fg(Theme::TEXT_SECONDARY),
+                    Style::default().fg(theme.text_secondary),
                 ))
             }
         })
@@ -91,9 +95,9 @@ pub fn render_restore_sessions_modal(frame: &mut Frame, state: &RestoreSessionsM
 
     // Footer
     let help = Line::from(vec![
-        Span::styled("Enter", Theme::keybind()),
+        Span::styled("Enter", theme.keybind()),
         Span::raw(" restore  "),
-        Span::styled("Esc", Theme::keybind()),
+        Span::styled("Esc", theme.keybind()),
         Span::raw(" close"),
     ]);
     frame.render_widget(Paragraph::new(help), chunks[1]);
@@ -0,0 +1,70 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout},
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use super::centered_fixed_height_rect;
+use super::theme::Theme;
+
+pub struct ResumeSessionState<'a> {
+    pub session_id: &'a str,
+    pub cursor: usize,
+    /// Validation error for the typed id, if any.
+    pub error: Option<&'a str>,
+}
+
+/// Prompt for the Claude conversation id a new session resumes
+/// ("Resume by id" in the session mode modal).
+pub fn render_resume_session_modal(
+    frame: &mut Frame,
+    theme: &Theme,
+    state: &ResumeSessionState<'_>,
+) {
+    let area = centered_fixed_height_rect(50, 8, frame.area());
+
+    frame.render_widget(Clear, area);
+
+    let block = Block::default()
+        .title(" Resume Claude Session ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.accent));
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Id field
+            Constraint::Min(1),    // Footer
+        ])
+        .split(inner);
+
+    super::render_text_field(
+        frame,
+        theme,
+        chunks[0],
+        "Session Id",
+        state.session_id,
+        state.cursor,
+        true,
+    );
+
+    let mut footer = Vec::new();
+    if let Some(error) = state.error {
+        footer.push(Line::from(Span::styled(
+            error,
+            Style::default().fg(theme.danger),
+        )));
+    }
+    footer.push(Line::from(vec![
+        Span::styled("Enter", theme.keybind()),
+        Span::styled(" resume  ", theme.keybind_desc()),
+        Span::styled("Esc", theme.keybind()),
+        Span::styled(" cancel", theme.keybind_desc()),
+    ]));
+    frame.render_widget(Paragraph::new(footer), chunks[1]);
+}
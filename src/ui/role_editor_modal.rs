@@ -56,7 +56,7 @@ pub struct RoleEditorState<'a> {
     pub focused_field: RoleEditorField,
 }
 
-pub fn render_role_editor_modal(frame: &mut Frame, state: &RoleEditorState<'_>) {
+pub fn render_role_editor_modal(frame: &mut Frame, theme: &Theme, state: &RoleEditorState<'_>) {
     // Dynamic height: 2 (border) + 3 (name) + 3 (desc) + tool lists + 3 (prompt) + env list + 1 (footer)
     let allowed_rows = tool_list_height(
         state.allowed_tools,
@@ -84,7 +84,7 @@ pub fn render_role_editor_modal(frame: &mut Frame, state: &RoleEditorState<'_>)
     let block = Block::default()
         .title(" Edit Role ")
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Theme::ACCENT));
+        .border_style(Style::default().fg(theme.accent));
 
     let inner = block.inner(area);
     frame.render_widget(block, area);
@@ -112,17 +112,18 @@ pub fn render_role_editor_modal(frame: &mut Frame, state: &RoleEditorState<'_>)
     let breadcrumb = Line::from(vec![
         Span::styled(
             format!(" Edit \"{}\"", state.project_name),
-            Style::default().fg(Theme::TEXT_MUTED),
+            Style::default().fg(theme.text_muted),
         ),
-        Span::styled(" > ", Style::default().fg(Theme::TEXT_MUTED)),
-        Span::styled("Roles", Style::default().fg(Theme::TEXT_MUTED)),
-        Span::styled(" > ", Style::default().fg(Theme::TEXT_MUTED)),
-        Span::styled(role_label, Style::default().fg(Theme::ACCENT)),
+        Span::styled(" > ", Style::default().fg(theme.text_muted)),
+        Span::styled("Roles", Style::default().fg(theme.text_muted)),
+        Span::styled(" > ", Style::default().fg(theme.text_muted)),
+        Span::styled(role_label, Style::default().fg(theme.accent)),
     ]);
     frame.render_widget(Paragraph::new(breadcrumb), chunks[0]);
 
     render_text_field(
         frame,
+        theme,
         chunks[1],
         "Name",
         state.name,
@@ -132,6 +133,7 @@ pub fn render_role_editor_modal(frame: &mut Frame, state: &RoleEditorState<'_>)
 
     render_text_field(
         frame,
+        theme,
         chunks[2],
         "Description",
         state.description,
@@ -141,6 +143,7 @@ pub fn render_role_editor_modal(frame: &mut Frame, state: &RoleEditorState<'_>)
 
     render_tool_list(
         frame,
+        theme,
         chunks[3],
         "Allowed Tools",
         state.allowed_tools,
@@ -153,6 +156,7 @@ pub fn render_role_editor_modal(frame: &mut Frame, state: &RoleEditorState<'_>)
 
     render_tool_list(
         frame,
+        theme,
         chunks[4],
         "Disallowed Tools",
         state.disallowed_tools,
@@ -165,6 +169,7 @@ pub fn render_role_editor_modal(frame: &mut Frame, state: &RoleEditorState<'_>)
 
     render_text_field(
         frame,
+        theme,
         chunks[5],
         "System Prompt",
         state.system_prompt,
@@ -174,6 +179,7 @@ pub fn render_role_editor_modal(frame: &mut Frame, state: &RoleEditorState<'_>)
 
     render_tool_list(
         frame,
+        theme,
         chunks[6],
         "Env (KEY=VALUE)",
         state.env,
@@ -198,32 +204,32 @@ pub fn render_role_editor_modal(frame: &mut Frame, state: &RoleEditorState<'_>)
 
     let footer = if is_list_field && tool_mode == ToolListMode::Adding {
         Line::from(vec![
-            Span::styled("Enter", Theme::keybind()),
-            Span::styled(" confirm  ", Theme::keybind_desc()),
-            Span::styled("Esc", Theme::keybind()),
-            Span::styled(" cancel", Theme::keybind_desc()),
+            Span::styled("Enter", theme.keybind()),
+            Span::styled(" confirm  ", theme.keybind_desc()),
+            Span::styled("Esc", theme.keybind()),
+            Span::styled(" cancel", theme.keybind_desc()),
         ])
     } else if is_list_field {
         Line::from(vec![
-            Span::styled("a", Theme::keybind()),
-            Span::styled(" add  ", Theme::keybind_desc()),
-            Span::styled("d", Theme::keybind()),
-            Span::styled(" delete  ", Theme::keybind_desc()),
-            Span::styled("Tab", Theme::keybind()),
-            Span::styled(" next  ", Theme::keybind_desc()),
-            Span::styled("Enter", Theme::keybind()),
-            Span::styled(" save  ", Theme::keybind_desc()),
-            Span::styled("Esc", Theme::keybind()),
-            Span::styled(" discard", Theme::keybind_desc()),
+            Span::styled("a", theme.keybind()),
+            Span::styled(" add  ", theme.keybind_desc()),
+            Span::styled("d", theme.keybind()),
+            Span::styled(" delete  ", theme.keybind_desc()),
+            Span::styled("Tab", theme.keybind()),
+            Span::styled(" next  ", theme.keybind_desc()),
+            Span::styled("Enter", theme.keybind()),
+            Span::styled(" save  ", theme.keybind_desc()),
+            Span::styled("Esc", theme.keybind()),
+            Span::styled(" discard", theme.keybind_desc()),
         ])
     } else {
         Line::from(vec![
-            Span::styled("Tab", Theme::keybind()),
-            Span::styled(" next  ", Theme::keybind_desc()),
-            Span::styled("Enter", Theme::keybind()),
-            Span::styled(" save  ", Theme::keybind_desc()),
-            Span::styled("Esc", Theme::keybind()),
-            Span::styled(" discard", Theme::keybind_desc()),
+            Span::styled("Tab", theme.keybind()),
+            Span::styled(" next  ", theme.keybind_desc()),
+            Span::styled("Enter", theme.keybind()),
+            Span::styled(" save  ", theme.keybind_desc()),
+            Span::styled("Esc", theme.keybind()),
+            Span::styled(" discard", theme.keybind_desc()),
         ])
     };
     frame.render_widget(Paragraph::new(footer), chunks[7]);
@@ -251,6 +257,7 @@ pub fn tool_list_height(tools: &[String], mode: ToolListMode, focused: bool) ->
 #[allow(clippy::too_many_arguments)]
 pub fn render_tool_list(
     frame: &mut Frame,
+    theme: &Theme,
     area: ratatui::layout::Rect,
     label: &str,
     tools: &[String],
@@ -261,9 +268,9 @@ pub fn render_tool_list(
     focused: bool,
 ) {
     let border_color = if focused {
-        Theme::BORDER_FOCUSED
+        theme.border_focused
     } else {
-        Theme::BORDER_UNFOCUSED
+        theme.border_unfocused
     };
     let block = Block::default()
         .title(format!(" {label} "))
@@ -289,7 +296,7 @@ pub fn render_tool_list(
     if tools.is_empty() {
         let empty = Paragraph::new(Line::from(Span::styled(
             "  (none)",
-            Style::default().fg(Theme::TEXT_MUTED),
+            Style::default().fg(theme.text_muted),
         )));
         frame.render_widget(empty, parts[0]);
     } else {
@@ -299,9 +306,9 @@ pub fn render_tool_list(
             .map(|(i, tool)| {
                 let is_selected = focused && mode == ToolListMode::Browse && i == selected_index;
                 let style = if is_selected {
-                    Theme::selected_item()
+                    theme.selected_item()
                 } else {
-                    Theme::normal_item()
+                    theme.normal_item()
                 };
                 let prefix = if is_selected { "▸ " } else { "  " };
                 ListItem::new(Line::from(Span::styled(format!("{prefix}{tool}"), style)))
@@ -312,12 +319,18 @@ pub fn render_tool_list(
 
     // Render inline input row when adding.
     if has_input {
-        render_inline_input(frame, parts[1], input_value, input_cursor);
+        render_inline_input(frame, theme, parts[1], input_value, input_cursor);
     }
 }
 
 /// Render a single-line inline text input (no border, just cursor + text).
-fn render_inline_input(frame: &mut Frame, area: ratatui::layout::Rect, value: &str, cursor: usize) {
+fn render_inline_input(
+    frame: &mut Frame,
+    theme: &Theme,
+    area: ratatui::layout::Rect,
+    value: &str,
+    cursor: usize,
+) {
     let chars: Vec<char> = value.chars().collect();
     let cursor = cursor.min(chars.len());
 
@@ -334,10 +347,10 @@ fn render_inline_input(frame: &mut Frame, area: ratatui::layout::Rect, value: &s
     };
 
     let line = Line::from(vec![
-        Span::styled("+ ", Style::default().fg(Theme::TOOL_ALLOWED)),
-        Span::styled(before, Style::default().fg(Theme::TEXT_PRIMARY)),
-        Span::styled(cursor_char, Theme::cursor()),
-        Span::styled(after, Style::default().fg(Theme::TEXT_PRIMARY)),
+        Span::styled("+ ", Style::default().fg(theme.tool_allowed)),
+        Span::styled(before, Style::default().fg(theme.text_primary)),
+        Span::styled(cursor_char, theme.cursor()),
+        Span::styled(after, Style::default().fg(theme.text_primary)),
     ]);
 
     frame.render_widget(Paragraph::new(line), area);
@@ -0,0 +1,62 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout},
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use super::centered_fixed_height_rect;
+use super::theme::Theme;
+use crate::app::RoleFileAction;
+
+pub struct RoleFileState<'a> {
+    pub action: RoleFileAction,
+    pub path: &'a str,
+    pub cursor: usize,
+}
+
+pub fn render_role_file_modal(frame: &mut Frame, theme: &Theme, state: &RoleFileState<'_>) {
+    let area = centered_fixed_height_rect(60, 6, frame.area());
+
+    frame.render_widget(Clear, area);
+
+    let (title, verb) = match state.action {
+        RoleFileAction::Export => (" Export Roles ", " export  "),
+        RoleFileAction::Import => (" Import Roles ", " import  "),
+    };
+
+    let block = Block::default()
+        .title(title)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.accent));
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Path field
+            Constraint::Min(1),    // Footer
+        ])
+        .split(inner);
+
+    super::render_text_field(
+        frame,
+        theme,
+        chunks[0],
+        "File (.toml or .json)",
+        state.path,
+        state.cursor,
+        true,
+    );
+
+    let footer = Line::from(vec![
+        Span::styled("Enter", theme.keybind()),
+        Span::styled(verb, theme.keybind_desc()),
+        Span::styled("Esc", theme.keybind()),
+        Span::styled(" cancel", theme.keybind_desc()),
+    ]);
+    frame.render_widget(Paragraph::new(footer), chunks[1]);
+}
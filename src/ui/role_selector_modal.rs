@@ -13,9 +13,11 @@ use crate::session::RoleConfig;
 pub struct RoleSelectorState<'a> {
     pub roles: &'a [RoleConfig],
     pub selected_index: usize,
+    /// Whether the choice will be remembered for the project this run.
+    pub remember: bool,
 }
 
-pub fn render_role_selector_modal(frame: &mut Frame, state: &RoleSelectorState<'_>) {
+pub fn render_role_selector_modal(frame: &mut Frame, theme: &Theme, state: &RoleSelectorState<'_>) {
     // 2 (border) + roles count + 1 (description) + 1 (footer)
     let height = (state.roles.len() as u16) + 4;
     let area = centered_fixed_height_rect(50, height, frame.area());
@@ -25,7 +27,7 @@ pub fn render_role_selector_modal(frame: &mut Frame, state: &RoleSelectorState<'
     let block = Block::default()
         .title(" Session Role ")
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Theme::ACCENT));
+        .border_style(Style::default().fg(theme.accent));
 
     let inner = block.inner(area);
     frame.render_widget(block, area);
@@ -45,9 +47,9 @@ pub fn render_role_selector_modal(frame: &mut Frame, state: &RoleSelectorState<'
         .enumerate()
         .map(|(i, role)| {
             let style = if i == state.selected_index {
-                Theme::selected_item()
+                theme.selected_item()
             } else {
-                Theme::normal_item()
+                theme.normal_item()
             };
             let prefix = if i == state.selected_index {
                 "▸ "
@@ -68,18 +70,27 @@ pub fn render_role_selector_modal(frame: &mut Frame, state: &RoleSelectorState<'
     if let Some(role) = state.roles.get(state.selected_index) {
         let desc = Line::from(Span::styled(
             &role.description,
-            Style::default().fg(Theme::TEXT_MUTED),
+            Style::default().fg(theme.text_muted),
         ));
         frame.render_widget(Paragraph::new(desc), chunks[1]);
     }
 
     let footer = Line::from(vec![
-        Span::styled("j/k", Theme::keybind()),
-        Span::styled(" navigate  ", Theme::keybind_desc()),
-        Span::styled("Enter", Theme::keybind()),
-        Span::styled(" select  ", Theme::keybind_desc()),
-        Span::styled("Esc", Theme::keybind()),
-        Span::styled(" cancel", Theme::keybind_desc()),
+        Span::styled("j/k", theme.keybind()),
+        Span::styled(" navigate  ", theme.keybind_desc()),
+        Span::styled("Enter", theme.keybind()),
+        Span::styled(" select  ", theme.keybind_desc()),
+        Span::styled("r", theme.keybind()),
+        Span::styled(
+            if state.remember {
+                " remember [x]  "
+            } else {
+                " remember [ ]  "
+            },
+            theme.keybind_desc(),
+        ),
+        Span::styled("Esc", theme.keybind()),
+        Span::styled(" cancel", theme.keybind_desc()),
     ]);
     frame.render_widget(Paragraph::new(footer), chunks[2]);
 }
@@ -0,0 +1,303 @@
+use std::ops::Range;
+
+/// A search hit in the terminal buffer.
+///
+/// `line` counts from the oldest scrollback line (0) down to the last row of
+/// the live screen. Columns are vt100 cell columns, so wide characters span
+/// two columns.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SearchMatch {
+    pub line: usize,
+    pub start_col: u16,
+    pub end_col: u16,
+}
+
+/// Number of lines currently held in scrollback above the live screen.
+///
+/// Probes by setting the offset to the maximum and reading it back, then
+/// restores the caller's offset.
+pub fn scrollback_len(parser: &mut vt100::Parser) -> usize {
+    let saved = parser.screen().scrollback();
+    parser.screen_mut().set_scrollback(usize::MAX);
+    let total = parser.screen().scrollback();
+    parser.screen_mut().set_scrollback(saved);
+    total
+}
+
+/// Case-fold a query into the per-cell form used by [`match_row`].
+pub fn fold_query(query: &str) -> Vec<char> {
+    query.chars().map(fold_char).collect()
+}
+
+fn fold_char(c: char) -> char {
+    c.to_lowercase().next().unwrap_or(c)
+}
+
+/// Call `f(screen, row, line)` for each absolute line in `lines` that exists
+/// in the buffer, oldest first.
+///
+/// vt100 only exposes the rows visible at the current scrollback offset, so
+/// the buffer is walked one page at a time. The caller's offset is restored
+/// before returning.
+pub fn visit_lines(
+    parser: &mut vt100::Parser,
+    lines: Range<usize>,
+    mut f: impl FnMut(&vt100::Screen, u16, usize),
+) {
+    let saved = parser.screen().scrollback();
+    let total = scrollback_len(parser);
+    let rows = parser.screen().size().0 as usize;
+    let end = lines.end.min(total + rows);
+
+    let mut next = lines.start;
+    while next < end {
+        let top = next.min(total);
+        parser.screen_mut().set_scrollback(total - top);
+        for row in (next - top)..rows {
+            let line = top + row;
+            if line >= end {
+                break;
+            }
+            f(parser.screen(), row as u16, line);
+        }
+        next = top + rows;
+    }
+
+    parser.screen_mut().set_scrollback(saved);
+}
+
+/// Find every case-insensitive occurrence of `query` in the scrollback and
+/// live screen, oldest first. The caller's scrollback offset is preserved.
+pub fn find_matches(parser: &mut vt100::Parser, query: &str) -> Vec<SearchMatch> {
+    let needle = fold_query(query);
+    if needle.is_empty() {
+        return Vec::new();
+    }
+
+    let mut matches = Vec::new();
+    visit_lines(parser, 0..usize::MAX, |screen, row, line| {
+        for (start_col, end_col) in match_row(screen, row, &needle) {
+            matches.push(SearchMatch {
+                line,
+                start_col,
+                end_col,
+            });
+        }
+    });
+    matches
+}
+
+/// Column ranges (`start..end`) where `needle` occurs in a visible row.
+pub fn match_row(screen: &vt100::Screen, row: u16, needle: &[char]) -> Vec<(u16, u16)> {
+    let (_, cols) = screen.size();
+    // (folded char, column, cell width)
+    let cells: Vec<(char, u16, u16)> = (0..cols)
+        .filter_map(|col| {
+            let cell = screen.cell(row, col)?;
+            if cell.is_wide_continuation() {
+                return None;
+            }
+            let c = cell.contents().chars().next().unwrap_or(' ');
+            let width = if cell.is_wide() { 2 } else { 1 };
+            Some((fold_char(c), col, width))
+        })
+        .collect();
+
+    let mut ranges = Vec::new();
+    if needle.is_empty() || needle.len() > cells.len() {
+        return ranges;
+    }
+
+    let mut i = 0;
+    while i + needle.len() <= cells.len() {
+        let window = &cells[i..i + needle.len()];
+        if window.iter().zip(needle).all(|(cell, c)| cell.0 == *c) {
+            let (_, last_col, last_width) = window[window.len() - 1];
+            ranges.push((window[0].1, last_col + last_width));
+            i += needle.len();
+        } else {
+            i += 1;
+        }
+    }
+    ranges
+}
+
+/// Index of the last match at or above `line`, wrapping to the newest match
+/// when nothing lies above.
+pub fn nearest_at_or_above(matches: &[SearchMatch], line: usize) -> Option<usize> {
+    if matches.is_empty() {
+        return None;
+    }
+    Some(
+        matches
+            .iter()
+            .rposition(|m| m.line <= line)
+            .unwrap_or(matches.len() - 1),
+    )
+}
+
+/// Scrollback offset that brings `line` to the middle of a `rows`-tall view.
+pub fn scrollback_for_line(line: usize, total: usize, rows: usize) -> usize {
+    (total + rows / 2).saturating_sub(line).min(total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 5-row parser with `count` numbered lines written to it.
+    fn parser_with_lines(count: usize) -> vt100::Parser {
+        let mut parser = vt100::Parser::new(5, 20, 100);
+        let text: Vec<String> = (0..count).map(|i| format!("line {i}")).collect();
+        parser.process(text.join("\r\n").as_bytes());
+        parser
+    }
+
+    #[test]
+    fn scrollback_len_counts_lines_above_screen() {
+        let mut parser = parser_with_lines(12);
+        assert_eq!(scrollback_len(&mut parser), 7);
+    }
+
+    #[test]
+    fn scrollback_len_restores_offset() {
+        let mut parser = parser_with_lines(12);
+        parser.screen_mut().set_scrollback(3);
+        scrollback_len(&mut parser);
+        assert_eq!(parser.screen().scrollback(), 3);
+    }
+
+    #[test]
+    fn find_matches_covers_scrollback_and_screen() {
+        let mut parser = parser_with_lines(12);
+        let matches = find_matches(&mut parser, "line 1");
+        // "line 1", "line 10", "line 11"
+        let lines: Vec<usize> = matches.iter().map(|m| m.line).collect();
+        assert_eq!(lines, vec![1, 10, 11]);
+    }
+
+    #[test]
+    fn find_matches_reports_columns() {
+        let mut parser = parser_with_lines(3);
+        let matches = find_matches(&mut parser, "2");
+        assert_eq!(
+            matches,
+            vec![SearchMatch {
+                line: 2,
+                start_col: 5,
+                end_col: 6,
+            }]
+        );
+    }
+
+    #[test]
+    fn find_matches_is_case_insensitive() {
+        let mut parser = vt100::Parser::new(3, 20, 10);
+        parser.process(b"Error: boom");
+        assert_eq!(find_matches(&mut parser, "error").len(), 1);
+        assert_eq!(find_matches(&mut parser, "BOOM").len(), 1);
+    }
+
+    #[test]
+    fn find_matches_visits_each_line_once() {
+        // Scrollback length that is not a multiple of the screen height.
+        let mut parser = parser_with_lines(13);
+        let matches = find_matches(&mut parser, "line");
+        let lines: Vec<usize> = matches.iter().map(|m| m.line).collect();
+        assert_eq!(lines, (0..13).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn visit_lines_yields_requested_range() {
+        let mut parser = parser_with_lines(12);
+        let mut seen = Vec::new();
+        visit_lines(&mut parser, 3..9, |screen, row, line| {
+            let text: String = (0..6)
+                .filter_map(|col| screen.cell(row, col))
+                .map(|cell| cell.contents().to_string())
+                .collect();
+            seen.push((line, text));
+        });
+        let expected: Vec<(usize, String)> = (3..9).map(|i| (i, format!("line {i}"))).collect();
+        assert_eq!(seen, expected);
+    }
+
+    #[test]
+    fn visit_lines_clamps_to_buffer() {
+        let mut parser = parser_with_lines(12);
+        let mut lines = Vec::new();
+        visit_lines(&mut parser, 10..100, |_, _, line| lines.push(line));
+        assert_eq!(lines, vec![10, 11]);
+    }
+
+    #[test]
+    fn find_matches_restores_offset() {
+        let mut parser = parser_with_lines(12);
+        parser.screen_mut().set_scrollback(4);
+        find_matches(&mut parser, "line");
+        assert_eq!(parser.screen().scrollback(), 4);
+    }
+
+    #[test]
+    fn find_matches_empty_query_returns_nothing() {
+        let mut parser = parser_with_lines(12);
+        assert!(find_matches(&mut parser, "").is_empty());
+    }
+
+    #[test]
+    fn match_row_finds_non_overlapping_hits() {
+        let mut parser = vt100::Parser::new(1, 20, 0);
+        parser.process(b"aaaa");
+        let ranges = match_row(parser.screen(), 0, &fold_query("aa"));
+        assert_eq!(ranges, vec![(0, 2), (2, 4)]);
+    }
+
+    #[test]
+    fn match_row_spans_wide_characters() {
+        let mut parser = vt100::Parser::new(1, 20, 0);
+        parser.process("x漢字y".as_bytes());
+        let ranges = match_row(parser.screen(), 0, &fold_query("漢字"));
+        assert_eq!(ranges, vec![(1, 5)]);
+        let ranges = match_row(parser.screen(), 0, &fold_query("y"));
+        assert_eq!(ranges, vec![(5, 6)]);
+    }
+
+    #[test]
+    fn nearest_at_or_above_picks_closest_older_match() {
+        let matches = [3, 8, 15].map(|line| SearchMatch {
+            line,
+            start_col: 0,
+            end_col: 1,
+        });
+        assert_eq!(nearest_at_or_above(&matches, 10), Some(1));
+        assert_eq!(nearest_at_or_above(&matches, 15), Some(2));
+        // Nothing above line 2: wrap to the newest match
+        assert_eq!(nearest_at_or_above(&matches, 2), Some(2));
+        assert_eq!(nearest_at_or_above(&[], 2), None);
+    }
+
+    #[test]
+    fn scrollback_for_line_centers_and_clamps() {
+        // 20 lines of scrollback, 10-row screen
+        assert_eq!(scrollback_for_line(0, 20, 10), 20);
+        assert_eq!(scrollback_for_line(10, 20, 10), 15);
+        assert_eq!(scrollback_for_line(29, 20, 10), 0);
+    }
+
+    #[test]
+    fn scrollback_for_line_brings_match_into_view() {
+        let mut parser = parser_with_lines(40);
+        let total = scrollback_len(&mut parser);
+        let matches = find_matches(&mut parser, "line 7");
+        let target = matches[0].line;
+        let offset = scrollback_for_line(target, total, 5);
+        parser.screen_mut().set_scrollback(offset);
+        let top = total - parser.screen().scrollback();
+        assert!((top..top + 5).contains(&target));
+        let row = (target - top) as u16;
+        assert_eq!(
+            match_row(parser.screen(), row, &fold_query("line 7")).len(),
+            1
+        );
+    }
+}
@@ -0,0 +1,215 @@
+use super::search;
+
+/// A cell position in the terminal buffer, addressed like
+/// [`search::SearchMatch`]: `line` 0 is the oldest scrollback line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct CellPos {
+    pub line: usize,
+    pub col: u16,
+}
+
+/// Order two selection ends so the first is the earlier one in reading order.
+pub fn ordered(a: CellPos, b: CellPos) -> (CellPos, CellPos) {
+    if a <= b {
+        (a, b)
+    } else {
+        (b, a)
+    }
+}
+
+/// Whether `pos` lies inside the selection spanning `a` and `b` (inclusive).
+pub fn contains(a: CellPos, b: CellPos, pos: CellPos) -> bool {
+    let (start, end) = ordered(a, b);
+    start <= pos && pos <= end
+}
+
+/// Extract the text of a stream selection from `a` to `b` (inclusive).
+///
+/// Each cell contributes its full contents, so combining sequences and wide
+/// characters survive intact; the trailing half of a wide character is
+/// skipped. Trailing blanks are trimmed from every line, and rows that vt100
+/// marks as soft-wrapped are joined without a newline.
+pub fn selected_text(parser: &mut vt100::Parser, a: CellPos, b: CellPos) -> String {
+    let (start, end) = ordered(a, b);
+    let mut text = String::new();
+
+    search::visit_lines(parser, start.line..end.line + 1, |screen, row, line| {
+        let (_, cols) = screen.size();
+        let first = if line == start.line { start.col } else { 0 };
+        let last = if line == end.line {
+            end.col.min(cols.saturating_sub(1))
+        } else {
+            cols.saturating_sub(1)
+        };
+
+        let row_text = row_text(screen, row, first, last);
+        if line != end.line && screen.row_wrapped(row) {
+            text.push_str(&row_text);
+        } else {
+            text.push_str(row_text.trim_end());
+            if line != end.line {
+                text.push('\n');
+            }
+        }
+    });
+
+    text
+}
+
+/// Every line of the scrollback and live screen, oldest first, one string
+/// per terminal row with trailing blanks trimmed. Blank rows below the last
+/// output are dropped. The caller's scrollback offset is preserved.
+pub fn buffer_lines(parser: &mut vt100::Parser) -> Vec<String> {
+    let mut lines = Vec::new();
+    search::visit_lines(parser, 0..usize::MAX, |screen, row, _| {
+        let last = screen.size().1.saturating_sub(1);
+        lines.push(row_text(screen, row, 0, last).trim_end().to_string());
+    });
+    while lines.last().is_some_and(String::is_empty) {
+        lines.pop();
+    }
+    lines
+}
+
+/// Contents of columns `first..=last` of `row`. Empty cells become spaces
+/// and the trailing half of a wide character is skipped.
+fn row_text(screen: &vt100::Screen, row: u16, first: u16, last: u16) -> String {
+    let mut text = String::new();
+    for col in first..=last {
+        let Some(cell) = screen.cell(row, col) else {
+            continue;
+        };
+        if cell.is_wide_continuation() {
+            continue;
+        }
+        match cell.contents() {
+            "" => text.push(' '),
+            contents => text.push_str(contents),
+        }
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pos(line: usize, col: u16) -> CellPos {
+        CellPos { line, col }
+    }
+
+    /// 3-row, 10-column parser with 6 lines written (3 in scrollback).
+    fn parser_with_lines() -> vt100::Parser {
+        let mut parser = vt100::Parser::new(3, 10, 100);
+        parser.process(b"alpha\r\nbravo\r\ncharlie\r\ndelta\r\necho\r\nfoxtrot");
+        parser
+    }
+
+    #[test]
+    fn ordered_swaps_reversed_ends() {
+        assert_eq!(ordered(pos(4, 2), pos(1, 7)), (pos(1, 7), pos(4, 2)));
+        assert_eq!(ordered(pos(1, 2), pos(1, 5)), (pos(1, 2), pos(1, 5)));
+        assert_eq!(ordered(pos(1, 5), pos(1, 2)), (pos(1, 2), pos(1, 5)));
+    }
+
+    #[test]
+    fn contains_is_stream_inclusive() {
+        let (a, b) = (pos(1, 6), pos(3, 2));
+        assert!(contains(a, b, pos(1, 6)));
+        assert!(contains(a, b, pos(2, 0)));
+        assert!(contains(a, b, pos(2, 9)));
+        assert!(contains(b, a, pos(3, 2)));
+        assert!(!contains(a, b, pos(1, 5)));
+        assert!(!contains(a, b, pos(3, 3)));
+    }
+
+    #[test]
+    fn single_line_range() {
+        let mut parser = parser_with_lines();
+        assert_eq!(selected_text(&mut parser, pos(2, 1), pos(2, 4)), "harl");
+    }
+
+    #[test]
+    fn multi_line_range_spans_scrollback_and_screen() {
+        let mut parser = parser_with_lines();
+        // Line 1 is in scrollback, line 4 on the live screen
+        let text = selected_text(&mut parser, pos(1, 2), pos(4, 1));
+        assert_eq!(text, "avo\ncharlie\ndelta\nec");
+    }
+
+    #[test]
+    fn reversed_ends_give_same_text() {
+        let mut parser = parser_with_lines();
+        assert_eq!(
+            selected_text(&mut parser, pos(4, 1), pos(1, 2)),
+            selected_text(&mut parser, pos(1, 2), pos(4, 1))
+        );
+    }
+
+    #[test]
+    fn trailing_blanks_are_trimmed() {
+        let mut parser = parser_with_lines();
+        assert_eq!(selected_text(&mut parser, pos(0, 0), pos(0, 9)), "alpha");
+    }
+
+    #[test]
+    fn preserves_scrollback_offset() {
+        let mut parser = parser_with_lines();
+        parser.screen_mut().set_scrollback(2);
+        selected_text(&mut parser, pos(0, 0), pos(5, 9));
+        assert_eq!(parser.screen().scrollback(), 2);
+    }
+
+    #[test]
+    fn multibyte_and_wide_cells() {
+        let mut parser = vt100::Parser::new(2, 10, 0);
+        parser.process("é漢字ü".as_bytes());
+        // é(0) 漢(1-2) 字(3-4) ü(5)
+        assert_eq!(selected_text(&mut parser, pos(0, 0), pos(0, 5)), "é漢字ü");
+        // Ending on the first half of a wide char includes it whole
+        assert_eq!(selected_text(&mut parser, pos(0, 1), pos(0, 3)), "漢字");
+        // Starting on the trailing half skips it
+        assert_eq!(selected_text(&mut parser, pos(0, 2), pos(0, 5)), "字ü");
+    }
+
+    #[test]
+    fn combining_characters_are_kept() {
+        let mut parser = vt100::Parser::new(1, 10, 0);
+        parser.process("e\u{301}x".as_bytes());
+        assert_eq!(
+            selected_text(&mut parser, pos(0, 0), pos(0, 1)),
+            "e\u{301}x"
+        );
+    }
+
+    #[test]
+    fn buffer_lines_walks_scrollback_and_screen() {
+        let mut parser = parser_with_lines();
+        parser.screen_mut().set_scrollback(1);
+        assert_eq!(
+            buffer_lines(&mut parser),
+            ["alpha", "bravo", "charlie", "delta", "echo", "foxtrot"]
+        );
+        assert_eq!(parser.screen().scrollback(), 1);
+    }
+
+    #[test]
+    fn buffer_lines_trims_blanks_and_keeps_wrapped_rows_apart() {
+        let mut parser = vt100::Parser::new(4, 5, 10);
+        parser.process(b"a  \r\n\r\nabcdefgh");
+        assert_eq!(buffer_lines(&mut parser), ["a", "", "abcde", "fgh"]);
+
+        let mut empty = vt100::Parser::new(3, 5, 10);
+        assert!(buffer_lines(&mut empty).is_empty());
+    }
+
+    #[test]
+    fn soft_wrapped_rows_are_joined() {
+        let mut parser = vt100::Parser::new(3, 5, 0);
+        parser.process(b"abcdefgh\r\nxyz");
+        assert_eq!(
+            selected_text(&mut parser, pos(0, 0), pos(2, 4)),
+            "abcdefgh\nxyz"
+        );
+    }
+}
@@ -0,0 +1,63 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout},
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use super::centered_fixed_height_rect;
+use super::theme::Theme;
+
+/// Prompt lines visible at once; longer prompts scroll to the cursor.
+const PROMPT_LINES: usize = 8;
+
+pub struct SendPromptState<'a> {
+    pub session: &'a str,
+    pub prompt: &'a str,
+    pub cursor: usize,
+}
+
+/// Prompt pasted into a session in one go (`s` in the session list).
+pub fn render_send_prompt_modal(frame: &mut Frame, theme: &Theme, state: &SendPromptState<'_>) {
+    // prompt(PROMPT_LINES + 2) + footer(1) + outer border(2)
+    let area = centered_fixed_height_rect(70, PROMPT_LINES as u16 + 5, frame.area());
+
+    frame.render_widget(Clear, area);
+
+    let block = Block::default()
+        .title(format!(" Send Prompt ({}) ", state.session))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.accent));
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(PROMPT_LINES as u16 + 2), // Prompt
+            Constraint::Min(1),                          // Footer
+        ])
+        .split(inner);
+
+    super::render_multiline_field(
+        frame,
+        theme,
+        chunks[0],
+        "Prompt",
+        state.prompt,
+        state.cursor,
+        PROMPT_LINES,
+    );
+
+    let footer = Line::from(vec![
+        Span::styled("Enter", theme.keybind()),
+        Span::styled(" send  ", theme.keybind_desc()),
+        Span::styled("Alt+Enter", theme.keybind()),
+        Span::styled(" new line  ", theme.keybind_desc()),
+        Span::styled("Esc", theme.keybind()),
+        Span::styled(" cancel", theme.keybind_desc()),
+    ]);
+    frame.render_widget(Paragraph::new(footer), chunks[1]);
+}
@@ -0,0 +1,87 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout},
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use super::centered_fixed_height_rect;
+use super::role_editor_modal::{render_tool_list, tool_list_height, ToolListMode};
+use super::theme::Theme;
+
+pub struct SessionDirsState<'a> {
+    pub session_name: &'a str,
+    pub dirs: &'a [String],
+    pub selected_index: usize,
+    pub mode: ToolListMode,
+    pub input: &'a str,
+    pub input_cursor: usize,
+}
+
+pub fn render_session_dirs_modal(frame: &mut Frame, theme: &Theme, state: &SessionDirsState<'_>) {
+    let list_height = tool_list_height(state.dirs, state.mode, true);
+    // Borders + hint line + list + footer
+    let area = centered_fixed_height_rect(60, list_height + 4, frame.area());
+
+    frame.render_widget(Clear, area);
+
+    let block = Block::default()
+        .title(format!(" Extra Directories: {} ", state.session_name))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.accent));
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1),           // Hint
+            Constraint::Length(list_height), // Directory list
+            Constraint::Min(1),              // Footer
+        ])
+        .split(inner);
+
+    let hint = Paragraph::new(Line::from(Span::styled(
+        "Passed as --add-dir; applied on next restart",
+        Style::default().fg(theme.text_muted),
+    )));
+    frame.render_widget(hint, chunks[0]);
+
+    render_tool_list(
+        frame,
+        theme,
+        chunks[1],
+        "Directories",
+        state.dirs,
+        state.selected_index,
+        state.mode,
+        state.input,
+        state.input_cursor,
+        true,
+    );
+
+    let footer = if state.mode == ToolListMode::Adding {
+        Line::from(vec![
+            Span::styled("Enter", theme.keybind()),
+            Span::styled(" confirm  ", theme.keybind_desc()),
+            Span::styled("Esc", theme.keybind()),
+            Span::styled(" cancel", theme.keybind_desc()),
+        ])
+    } else {
+        Line::from(vec![
+            Span::styled("a", theme.keybind()),
+            Span::styled(" add  ", theme.keybind_desc()),
+            Span::styled("d", theme.keybind()),
+            Span::styled(" delete  ", theme.keybind_desc()),
+            Span::styled("J/K", theme.keybind()),
+            Span::styled(" reorder  ", theme.keybind_desc()),
+            Span::styled("Enter", theme.keybind()),
+            Span::styled(" save  ", theme.keybind_desc()),
+            Span::styled("Esc", theme.keybind()),
+            Span::styled(" cancel", theme.keybind_desc()),
+        ])
+    };
+    frame.render_widget(Paragraph::new(footer), chunks[2]);
+}
@@ -0,0 +1,72 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout},
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use super::centered_fixed_height_rect;
+use super::theme::Theme;
+
+pub struct SessionLabelState<'a> {
+    pub label: &'a str,
+    pub label_cursor: usize,
+    pub color: &'a str,
+    pub color_cursor: usize,
+    /// Whether the color field (rather than the label field) has focus.
+    pub color_focused: bool,
+}
+
+/// Label and color editor for the active session (`t` in the session list).
+pub fn render_session_label_modal(frame: &mut Frame, theme: &Theme, state: &SessionLabelState<'_>) {
+    let area = centered_fixed_height_rect(50, 9, frame.area());
+
+    frame.render_widget(Clear, area);
+
+    let block = Block::default()
+        .title(" Label Session ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.accent));
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Label field
+            Constraint::Length(3), // Color field
+            Constraint::Min(1),    // Footer
+        ])
+        .split(inner);
+
+    super::render_text_field(
+        frame,
+        theme,
+        chunks[0],
+        "Label",
+        state.label,
+        state.label_cursor,
+        !state.color_focused,
+    );
+    super::render_text_field(
+        frame,
+        theme,
+        chunks[1],
+        "Color (e.g. green, #ff8800)",
+        state.color,
+        state.color_cursor,
+        state.color_focused,
+    );
+
+    let footer = Line::from(vec![
+        Span::styled("Tab", theme.keybind()),
+        Span::styled(" switch field  ", theme.keybind_desc()),
+        Span::styled("Enter", theme.keybind()),
+        Span::styled(" save  ", theme.keybind_desc()),
+        Span::styled("Esc", theme.keybind()),
+        Span::styled(" cancel", theme.keybind_desc()),
+    ]);
+    frame.render_widget(Paragraph::new(footer), chunks[2]);
+}
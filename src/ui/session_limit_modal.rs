@@ -0,0 +1,59 @@
+use ratatui::{
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use super::centered_fixed_height_rect;
+use super::theme::Theme;
+
+/// Confirmation shown before spawning once `session_count` user sessions
+/// have reached the configured `limit` (`[limits] max_sessions`).
+pub fn render_session_limit_modal(
+    frame: &mut Frame,
+    theme: &Theme,
+    session_count: usize,
+    limit: usize,
+) {
+    let area = centered_fixed_height_rect(60, 7, frame.area());
+
+    frame.render_widget(Clear, area);
+
+    let block = Block::default()
+        .title(" Session Limit ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.danger));
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let lines = vec![
+        Line::from(vec![
+            Span::styled(
+                format!("{session_count} sessions"),
+                Style::default()
+                    .fg(theme.accent)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                format!(" already running (limit {limit})."),
+                Style::default().fg(theme.text_primary),
+            ),
+        ]),
+        Line::from(""),
+        Line::from(Span::styled(
+            "Each session holds a tmux window and a PTY. Spawn anyway?",
+            Style::default().fg(theme.text_secondary),
+        )),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("y/Enter", theme.keybind()),
+            Span::styled(" spawn  ", theme.keybind_desc()),
+            Span::styled("n/Esc", theme.keybind()),
+            Span::styled(" cancel", theme.keybind_desc()),
+        ]),
+    ];
+
+    frame.render_widget(Paragraph::new(lines), inner);
+}
@@ -9,21 +9,31 @@ use ratatui::{
 use super::centered_fixed_height_rect;
 use super::theme::Theme;
 
-const MODES: [&str; 2] = ["Normal", "Worktree"];
+/// Options in display order; `selected_index` indexes into this.
+pub const MODES: [&str; 5] = [
+    "Normal",
+    "Worktree",
+    "Shell",
+    "Shell in worktree",
+    "Resume by id",
+];
 
-pub struct SessionModeState {
+pub struct SessionModeState<'a> {
     pub selected_index: usize,
+    /// Names of the extra MCP servers added for this spawn only.
+    pub extra_mcp_servers: &'a [&'a str],
 }
 
-pub fn render_session_mode_modal(frame: &mut Frame, state: &SessionModeState) {
-    let area = centered_fixed_height_rect(50, 6, frame.area());
+pub fn render_session_mode_modal(frame: &mut Frame, theme: &Theme, state: &SessionModeState<'_>) {
+    let extras_rows = u16::from(!state.extra_mcp_servers.is_empty());
+    let area = centered_fixed_height_rect(50, 4 + MODES.len() as u16 + extras_rows, frame.area());
 
     frame.render_widget(Clear, area);
 
     let block = Block::default()
         .title(" Session Mode ")
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Theme::ACCENT));
+        .border_style(Style::default().fg(theme.accent));
 
     let inner = block.inner(area);
     frame.render_widget(block, area);
@@ -31,8 +41,9 @@ pub fn render_session_mode_modal(frame: &mut Frame, state: &SessionModeState) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Min(1),    // Mode list
-            Constraint::Length(1), // Footer
+            Constraint::Min(1),              // Mode list
+            Constraint::Length(extras_rows), // Extra MCP servers
+            Constraint::Length(1),           // Footer
         ])
         .split(inner);
 
@@ -41,9 +52,9 @@ pub fn render_session_mode_modal(frame: &mut Frame, state: &SessionModeState) {
         .enumerate()
         .map(|(i, mode)| {
             let style = if i == state.selected_index {
-                Theme::selected_item()
+                theme.selected_item()
             } else {
-                Theme::normal_item()
+                theme.normal_item()
             };
             let prefix = if i == state.selected_index {
                 "▸ "
@@ -57,13 +68,26 @@ pub fn render_session_mode_modal(frame: &mut Frame, state: &SessionModeState) {
     let list = List::new(items);
     frame.render_widget(list, chunks[0]);
 
+    if !state.extra_mcp_servers.is_empty() {
+        let extras = Line::from(vec![
+            Span::styled("Extra MCP: ", theme.label()),
+            Span::styled(
+                state.extra_mcp_servers.join(", "),
+                Style::default().fg(theme.text_secondary),
+            ),
+        ]);
+        frame.render_widget(Paragraph::new(extras), chunks[1]);
+    }
+
     let footer = Line::from(vec![
-        Span::styled("j/k", Theme::keybind()),
-        Span::styled(" navigate  ", Theme::keybind_desc()),
-        Span::styled("Enter", Theme::keybind()),
-        Span::styled(" select  ", Theme::keybind_desc()),
-        Span::styled("Esc", Theme::keybind()),
-        Span::styled(" cancel", Theme::keybind_desc()),
+        Span::styled("j/k", theme.keybind()),
+        Span::styled(" navigate  ", theme.keybind_desc()),
+        Span::styled("Enter", theme.keybind()),
+        Span::styled(" select  ", theme.keybind_desc()),
+        Span::styled("m", theme.keybind()),
+        Span::styled(" MCP  ", theme.keybind_desc()),
+        Span::styled("Esc", theme.keybind()),
+        Span::styled(" cancel", theme.keybind_desc()),
     ]);
-    frame.render_widget(Paragraph::new(footer), chunks[1]);
+    frame.render_widget(Paragraph::new(footer), chunks[2]);
 }
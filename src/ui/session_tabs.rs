@@ -0,0 +1,49 @@
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::Tabs,
+    Frame,
+};
+
+use super::status_color;
+use super::status_icons::StatusIcons;
+use super::theme::Theme;
+use crate::session::SessionInfo;
+
+/// One-line tab bar of the active project's sessions, used in place of the
+/// left panel in tab layout mode.
+pub fn render_session_tabs(
+    frame: &mut Frame,
+    theme: &Theme,
+    icons: &StatusIcons,
+    area: Rect,
+    sessions: &[&SessionInfo],
+    active_session: usize,
+) {
+    let titles: Vec<Line<'_>> = sessions
+        .iter()
+        .map(|s| {
+            Line::from(vec![
+                Span::styled(
+                    icons.icon(s.status),
+                    Style::default().fg(status_color(s.status, theme)),
+                ),
+                Span::raw(" "),
+                Span::raw(s.name.as_str()),
+            ])
+        })
+        .collect();
+
+    let tabs = Tabs::new(titles)
+        .select(active_session.min(sessions.len().saturating_sub(1)))
+        .style(Style::default().fg(theme.text_secondary))
+        .highlight_style(
+            Style::default()
+                .fg(theme.accent)
+                .add_modifier(Modifier::BOLD | Modifier::REVERSED),
+        )
+        .divider(Span::styled("│", Style::default().fg(theme.text_muted)));
+
+    frame.render_widget(tabs, area);
+}
@@ -7,20 +7,20 @@ use ratatui::{
 };
 
 use super::theme::Theme;
-use crate::app::{StatusLevel, StatusMessage};
+use crate::app::{StatusLevel, StatusMessage, SyncSummary};
 
 const SPINNER_CHARS: &[char] = &['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
 
-pub fn render_header(frame: &mut Frame, area: Rect) {
+pub fn render_header(frame: &mut Frame, theme: &Theme, area: Rect) {
     let header = Paragraph::new(Line::from(vec![
-        Span::styled(" thurbox ", Theme::focused_title()),
+        Span::styled(" thurbox ", theme.focused_title()),
         Span::styled(
             " Multi-Session Claude Code Orchestrator",
-            Style::default().fg(Theme::TEXT_SECONDARY),
+            Style::default().fg(theme.text_secondary),
         ),
         Span::styled(
             concat!("  v", env!("THURBOX_VERSION")),
-            Style::default().fg(Theme::TEXT_MUTED),
+            Style::default().fg(theme.text_muted),
         ),
     ]));
     frame.render_widget(header, area);
@@ -32,43 +32,69 @@ pub struct FooterState<'a> {
     pub project_count: usize,
     pub status: Option<&'a StatusMessage>,
     pub focus_label: &'a str,
+    /// Active session list filter, if any (e.g. `"Waiting"`).
+    pub session_filter: Option<&'a str>,
     pub sync_in_progress: bool,
+    /// Outcome of the last finished sync, if any.
+    pub last_sync: Option<&'a SyncSummary>,
     pub tick_count: u64,
+    /// Running thurbox instances sharing the database, this one included.
+    pub instance_count: usize,
 }
 
-pub fn render_footer(frame: &mut Frame, area: Rect, state: &FooterState<'_>) {
-    let focus_badge = Span::styled(format!(" {} ", state.focus_label), Theme::focused_title());
+pub fn render_footer(frame: &mut Frame, theme: &Theme, area: Rect, state: &FooterState<'_>) {
+    let mut spans = vec![Span::styled(
+        format!(" {} ", state.focus_label),
+        theme.focused_title(),
+    )];
+    if let Some(filter) = state.session_filter {
+        spans.push(Span::styled(
+            format!(" Filter: {filter} "),
+            Style::default()
+                .fg(theme.text_primary)
+                .bg(theme.status_waiting),
+        ));
+    }
+    if let (Some(summary), false) = (state.last_sync, state.sync_in_progress) {
+        let bg = match summary.level() {
+            StatusLevel::Success => theme.status_busy,
+            StatusLevel::Info => theme.status_waiting,
+            StatusLevel::Error => theme.status_error,
+        };
+        spans.push(Span::styled(
+            format!(" Sync {} ", summary.badge()),
+            Style::default().fg(theme.text_primary).bg(bg),
+        ));
+    }
 
-    let line = if state.sync_in_progress {
+    spans.extend(if state.sync_in_progress {
         let idx = (state.tick_count as usize / 10) % SPINNER_CHARS.len();
         let spinner = SPINNER_CHARS[idx];
         let text = state
             .status
             .map_or("Syncing...".to_string(), |s| s.text.clone());
-        Line::from(vec![
-            focus_badge,
+        vec![
             Span::styled(
                 format!(" {spinner} SYNC "),
-                Style::default().fg(Theme::TEXT_PRIMARY).bg(Theme::ACCENT),
+                Style::default().fg(theme.text_primary).bg(theme.accent),
             ),
-            Span::styled(format!(" {text}"), Style::default().fg(Theme::ACCENT)),
-        ])
+            Span::styled(format!(" {text}"), Style::default().fg(theme.accent)),
+        ]
     } else if let Some(msg) = state.status {
         let (badge_text, badge_bg, text_color) = match msg.level {
-            StatusLevel::Info => (" INFO ", Theme::ACCENT, Theme::TEXT_SECONDARY),
-            StatusLevel::Success => (" ✓ SYNC ", Theme::STATUS_BUSY, Theme::STATUS_BUSY),
-            StatusLevel::Error => (" ERROR ", Theme::STATUS_ERROR, Theme::STATUS_ERROR),
+            StatusLevel::Info => (" INFO ", theme.accent, theme.text_secondary),
+            StatusLevel::Success => (" ✓ SYNC ", theme.status_busy, theme.status_busy),
+            StatusLevel::Error => (" ERROR ", theme.status_error, theme.status_error),
         };
-        Line::from(vec![
-            focus_badge,
+        vec![
             Span::styled(
                 badge_text,
-                Style::default().fg(Theme::TEXT_PRIMARY).bg(badge_bg),
+                Style::default().fg(theme.text_primary).bg(badge_bg),
             ),
             Span::styled(format!(" {}", msg.text), Style::default().fg(text_color)),
-        ])
+        ]
     } else {
-        let counts = if state.project_count > 0 {
+        let mut counts = if state.project_count > 0 {
             format!(
                 " {} project(s) | {} session(s) ",
                 state.project_count, state.session_count
@@ -76,15 +102,17 @@ pub fn render_footer(frame: &mut Frame, area: Rect, state: &FooterState<'_>) {
         } else {
             format!(" {} session(s) ", state.session_count)
         };
-        Line::from(vec![
-            focus_badge,
-            Span::styled(counts, Style::default().fg(Theme::TEXT_SECONDARY)),
+        if state.instance_count > 1 {
+            counts.push_str(&format!("| {} instances ", state.instance_count));
+        }
+        vec![
+            Span::styled(counts, Style::default().fg(theme.text_secondary)),
             Span::styled(
-                " ^N New  ^C Close  ^D Delete  ^E Edit  ^R Restart  ^S Sync  ^T Shell  ^Z Undo  ^U Restore  ^H/J/K/L Nav  F1 Help  F2 Info  ^Q Quit ",
-                Style::default().fg(Theme::TEXT_MUTED),
+                " ^N New  ^X Close  ^D Delete  ^E Edit  ^R Restart  ^S Sync  ^T Shell  ^Z Undo  ^U Restore  ^H/J/K/L Nav  F1 Help  F2 Info  ^Q Quit ",
+                Style::default().fg(theme.text_muted),
             ),
-        ])
-    };
+        ]
+    });
 
-    frame.render_widget(Paragraph::new(line), area);
+    frame.render_widget(Paragraph::new(Line::from(spans)), area);
 }
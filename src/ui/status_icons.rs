@@ -0,0 +1,184 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::session::SessionStatus;
+
+/// Glyph and label drawn for each session status.
+///
+/// Built from the `[status]` table in `config.toml`: a preset plus
+/// per-status overrides keyed by the status name (`busy`, `needs-input`,
+/// `waiting`, `idle`, `error`, `dormant`):
+///
+/// ```toml
+/// [status]
+/// preset = "ascii"       # built-in set (default: "unicode")
+///
+/// [status.icons]
+/// busy = ">"
+///
+/// [status.labels]
+/// waiting = "Ready"
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StatusIcons {
+    /// Indexed like [`SessionStatus::ALL`].
+    icons: [String; 6],
+    labels: [String; 6],
+}
+
+/// Error produced while building [`StatusIcons`] from the config file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StatusIconsError {
+    UnknownPreset(String),
+    UnknownStatus(String),
+}
+
+impl fmt::Display for StatusIconsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownPreset(name) => write!(
+                f,
+                "Unknown status preset '{name}' (built-in: {})",
+                StatusIcons::PRESET_NAMES.join(", ")
+            ),
+            Self::UnknownStatus(name) => write!(f, "Unknown status '{name}'"),
+        }
+    }
+}
+
+impl std::error::Error for StatusIconsError {}
+
+impl Default for StatusIcons {
+    fn default() -> Self {
+        Self::unicode()
+    }
+}
+
+impl StatusIcons {
+    pub const PRESET_NAMES: [&'static str; 2] = ["unicode", "ascii"];
+
+    /// The glyphs of [`SessionStatus::icon`] (`●◆◉○✗◌`).
+    pub fn unicode() -> Self {
+        Self::with_icons(SessionStatus::ALL.map(SessionStatus::icon))
+    }
+
+    /// Plain ASCII for terminals and fonts without the Unicode glyphs.
+    pub fn ascii() -> Self {
+        Self::with_icons(["*", "!", "?", "-", "x", "."])
+    }
+
+    fn with_icons(icons: [&str; 6]) -> Self {
+        Self {
+            icons: icons.map(String::from),
+            labels: SessionStatus::ALL.map(|status| status.to_string()),
+        }
+    }
+
+    fn preset(name: &str) -> Option<Self> {
+        match name {
+            "unicode" => Some(Self::unicode()),
+            "ascii" => Some(Self::ascii()),
+            _ => None,
+        }
+    }
+
+    /// Build the set from the `preset` (unicode when `None`) with per-status
+    /// icon and label overrides applied on top.
+    pub fn from_config(
+        preset: Option<&str>,
+        icons: &HashMap<String, String>,
+        labels: &HashMap<String, String>,
+    ) -> Result<Self, StatusIconsError> {
+        let mut set = match preset {
+            Some(name) => Self::preset(name)
+                .ok_or_else(|| StatusIconsError::UnknownPreset(name.to_string()))?,
+            None => Self::unicode(),
+        };
+        for (name, icon) in icons {
+            set.icons[Self::index_of(name)?] = icon.clone();
+        }
+        for (name, label) in labels {
+            set.labels[Self::index_of(name)?] = label.clone();
+        }
+        Ok(set)
+    }
+
+    fn index_of(name: &str) -> Result<usize, StatusIconsError> {
+        SessionStatus::ALL
+            .iter()
+            .position(|status| status.as_str() == name)
+            .ok_or_else(|| StatusIconsError::UnknownStatus(name.to_string()))
+    }
+
+    fn index(status: SessionStatus) -> usize {
+        SessionStatus::ALL
+            .iter()
+            .position(|s| *s == status)
+            .unwrap_or_default()
+    }
+
+    pub fn icon(&self, status: SessionStatus) -> &str {
+        &self.icons[Self::index(status)]
+    }
+
+    pub fn label(&self, status: SessionStatus) -> &str {
+        &self.labels[Self::index(status)]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn map(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn default_matches_builtin_glyphs_and_labels() {
+        let set = StatusIcons::default();
+        for status in SessionStatus::ALL {
+            assert_eq!(set.icon(status), status.icon());
+            assert_eq!(set.label(status), status.to_string());
+        }
+    }
+
+    #[test]
+    fn ascii_preset_is_ascii() {
+        let set =
+            StatusIcons::from_config(Some("ascii"), &HashMap::new(), &HashMap::new()).unwrap();
+        assert!(SessionStatus::ALL
+            .into_iter()
+            .all(|status| set.icon(status).is_ascii()));
+    }
+
+    #[test]
+    fn overrides_apply_per_status() {
+        let set = StatusIcons::from_config(
+            Some("ascii"),
+            &map(&[("busy", ">"), ("needs-input", "?!")]),
+            &map(&[("waiting", "Ready")]),
+        )
+        .unwrap();
+        assert_eq!(set.icon(SessionStatus::Busy), ">");
+        assert_eq!(set.icon(SessionStatus::NeedsInput), "?!");
+        assert_eq!(set.icon(SessionStatus::Idle), "-");
+        assert_eq!(set.label(SessionStatus::Waiting), "Ready");
+        assert_eq!(set.label(SessionStatus::Busy), "Busy");
+    }
+
+    #[test]
+    fn unknown_preset_or_status_is_rejected() {
+        assert_eq!(
+            StatusIcons::from_config(Some("emoji"), &HashMap::new(), &HashMap::new()),
+            Err(StatusIconsError::UnknownPreset("emoji".to_string()))
+        );
+        assert_eq!(
+            StatusIcons::from_config(None, &map(&[("sleeping", "z")]), &HashMap::new()),
+            Err(StatusIconsError::UnknownStatus("sleeping".to_string()))
+        );
+    }
+}
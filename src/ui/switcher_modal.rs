@@ -0,0 +1,93 @@
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout},
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use super::theme::Theme;
+use super::{centered_rect, render_text_field};
+
+pub struct SwitcherModalState<'a> {
+    pub query: &'a str,
+    pub query_cursor: usize,
+    /// Labels of the matching entries, best match first.
+    pub items: &'a [&'a str],
+    pub selected_index: usize,
+}
+
+pub fn render_switcher_modal(frame: &mut Frame, theme: &Theme, state: &SwitcherModalState<'_>) {
+    let area = centered_rect(60, 60, frame.area());
+
+    frame.render_widget(Clear, area);
+
+    let block = Block::default()
+        .title(" Switch To ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.accent));
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Filter field
+            Constraint::Min(1),    // Matches
+            Constraint::Length(1), // Footer
+        ])
+        .split(inner);
+
+    render_text_field(
+        frame,
+        theme,
+        chunks[0],
+        "Filter",
+        state.query,
+        state.query_cursor,
+        true,
+    );
+
+    if state.items.is_empty() {
+        let empty = Paragraph::new(Line::from(Span::styled(
+            "No matches",
+            Style::default().fg(theme.text_muted),
+        )))
+        .alignment(Alignment::Center);
+        frame.render_widget(empty, chunks[1]);
+    } else {
+        // Scroll so the selection stays on screen
+        let visible = chunks[1].height.max(1) as usize;
+        let offset = state.selected_index.saturating_sub(visible - 1);
+        let lines: Vec<Line<'_>> = state
+            .items
+            .iter()
+            .enumerate()
+            .skip(offset)
+            .take(visible)
+            .map(|(i, label)| {
+                let text = format!(" {label} ");
+                if i == state.selected_index {
+                    Line::from(Span::styled(text, theme.selected_item()))
+                } else {
+                    Line::from(Span::styled(
+                        text,
+                        Style::default().fg(theme.text_secondary),
+                    ))
+                }
+            })
+            .collect();
+        frame.render_widget(Paragraph::new(lines), chunks[1]);
+    }
+
+    let help = Line::from(vec![
+        Span::styled("↑/↓", theme.keybind()),
+        Span::raw(" select  "),
+        Span::styled("Enter", theme.keybind()),
+        Span::raw(" jump  "),
+        Span::styled("Esc", theme.keybind()),
+        Span::raw(" close"),
+    ]);
+    frame.render_widget(Paragraph::new(help), chunks[2]);
+}
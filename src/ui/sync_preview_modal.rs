@@ -0,0 +1,91 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use super::centered_fixed_height_rect;
+use super::theme::Theme;
+
+/// View-only row of the sync preview modal: one worktree.
+pub struct SyncPreviewEntry {
+    pub session_name: String,
+    pub branch: String,
+    pub base_branch: String,
+    /// Commits ahead of and behind `origin/<base_branch>`, or why they
+    /// could not be counted.
+    pub counts: Result<(u32, u32), String>,
+}
+
+pub struct SyncPreviewState<'a> {
+    pub entries: &'a [SyncPreviewEntry],
+}
+
+/// Pre-sync summary (`Ctrl+S`): what each worktree would pull in, confirmed
+/// before anything is rebased or merged.
+pub fn render_sync_preview_modal(frame: &mut Frame, theme: &Theme, state: &SyncPreviewState<'_>) {
+    let list_height = state.entries.len().max(1) as u16;
+    // 2 (borders) + list + 1 (footer) + 1 (padding)
+    let total_height = (list_height + 4).min(20);
+    let area = centered_fixed_height_rect(70, total_height, frame.area());
+
+    frame.render_widget(Clear, area);
+
+    let block = Block::default()
+        .title(" Sync Worktrees ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.accent));
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(1), Constraint::Length(1)])
+        .split(inner);
+
+    let lines: Vec<Line<'_>> = state
+        .entries
+        .iter()
+        .map(|entry| {
+            let mut spans = vec![
+                Span::styled(
+                    format!(" {} ", entry.session_name),
+                    Style::default()
+                        .fg(theme.text_primary)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(
+                    format!("{} ", entry.branch),
+                    Style::default().fg(theme.branch_name),
+                ),
+            ];
+            spans.push(match &entry.counts {
+                Ok((ahead, 0)) => Span::styled(
+                    format!("up to date with {} (↑{ahead})", entry.base_branch),
+                    Style::default().fg(theme.text_muted),
+                ),
+                Ok((ahead, behind)) => Span::styled(
+                    format!("↓{behind} incoming from {} (↑{ahead})", entry.base_branch),
+                    Style::default().fg(theme.accent),
+                ),
+                Err(msg) => {
+                    Span::styled(format!("error: {msg}"), Style::default().fg(theme.danger))
+                }
+            });
+            Line::from(spans)
+        })
+        .collect();
+
+    frame.render_widget(Paragraph::new(lines), chunks[0]);
+
+    let help = Line::from(vec![
+        Span::styled("y/Enter", theme.keybind()),
+        Span::styled(" sync  ", theme.keybind_desc()),
+        Span::styled("n/Esc", theme.keybind()),
+        Span::styled(" cancel", theme.keybind_desc()),
+    ]);
+    frame.render_widget(Paragraph::new(help), chunks[1]);
+}
@@ -1,18 +1,31 @@
 use ratatui::{
     layout::{Margin, Position, Rect},
     style::{Color, Modifier, Style},
-    widgets::{Block, Borders, Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState},
     Frame,
 };
 use tui_term::widget::{Cursor, PseudoTerminal};
 
+use super::search::{self, SearchMatch};
+use super::selection::{self, CellPos};
+use super::status_icons::StatusIcons;
 use super::theme::Theme;
 use super::FocusLevel;
 use super::{admin_block, focus_block};
 use crate::session::SessionInfo;
 
+/// Footer shown while the view is `offset` lines above the live bottom, so
+/// output arriving meanwhile isn't missed; `None` at the bottom.
+pub fn scroll_indicator(offset: usize) -> Option<String> {
+    (offset > 0).then(|| format!(" SCROLLED \u{2191}{offset} \u{b7} End to return "))
+}
+
+#[allow(clippy::too_many_arguments)]
 pub fn render_terminal(
     frame: &mut Frame,
+    theme: &Theme,
+    icons: &StatusIcons,
     area: Rect,
     parser: &mut vt100::Parser,
     info: &SessionInfo,
@@ -30,35 +43,39 @@ pub fn render_terminal(
         max
     };
 
-    let title = {
-        let base = if is_shell {
-            format!(" {} (shell) ", info.name)
-        } else if let Some(wt) = info.worktrees.first() {
-            format!(
-                " {} ({}) [{}] [{}] ",
-                info.name, info.role, wt.branch, info.status
-            )
-        } else {
-            format!(" {} ({}) [{}] ", info.name, info.role, info.status)
-        };
-        if scroll_offset > 0 {
-            // Insert scroll indicator before the trailing space
-            let trimmed = base.trim_end();
-            format!("{trimmed} [{scroll_offset}\u{2191}] ")
-        } else {
-            base
-        }
+    let status = icons.label(info.status);
+    let title = if is_shell {
+        format!(" {} (shell) ", info.name)
+    } else if let Some(wt) = info.worktrees.first() {
+        format!(
+            " {} ({}) [{}] [{}] ",
+            info.name, info.role, wt.branch, status
+        )
+    } else {
+        format!(" {} ({}) [{}] ", info.name, info.role, status)
     };
 
-    let block = if is_admin {
-        admin_block(&title, level)
+    let mut block = if is_admin {
+        admin_block(&title, level, theme)
     } else {
-        focus_block(&title, level)
+        focus_block(&title, level, theme)
     };
+    if let Some(indicator) = scroll_indicator(scroll_offset) {
+        block = block.title_bottom(
+            Line::from(Span::styled(
+                indicator,
+                Style::default()
+                    .fg(theme.inverted_fg)
+                    .bg(theme.status_needs_input)
+                    .add_modifier(Modifier::BOLD),
+            ))
+            .right_aligned(),
+        );
+    }
 
     let mut pseudo_term = PseudoTerminal::new(parser.screen())
         .block(block)
-        .style(Style::default().fg(Theme::TEXT_PRIMARY).bg(Color::Reset));
+        .style(Style::default().fg(theme.text_primary).bg(Color::Reset));
 
     // Hide cursor when scrolled up
     if scroll_offset > 0 {
@@ -69,7 +86,7 @@ pub fn render_terminal(
 
     frame.render_widget(pseudo_term, area);
 
-    highlight_urls(frame, area, parser.screen());
+    highlight_urls(frame, area, parser.screen(), theme);
 
     // Render scrollbar when there's scrollback content
     if total_scrollback > 0 {
@@ -80,8 +97,8 @@ pub fn render_terminal(
         });
 
         let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight)
-            .thumb_style(Style::default().fg(Theme::ACCENT))
-            .track_style(Style::default().fg(Theme::TEXT_MUTED));
+            .thumb_style(Style::default().fg(theme.accent))
+            .track_style(Style::default().fg(theme.text_muted));
 
         // Invert: offset 0 (bottom) → position at max, offset max (top) → position at 0
         let position = total_scrollback.saturating_sub(scroll_offset);
@@ -95,7 +112,7 @@ pub fn render_terminal(
 }
 
 /// Post-process the frame buffer to underline and colorize detected URLs.
-fn highlight_urls(frame: &mut Frame, area: Rect, screen: &vt100::Screen) {
+fn highlight_urls(frame: &mut Frame, area: Rect, screen: &vt100::Screen, theme: &Theme) {
     let screen_rows = super::links::extract_screen_rows(screen);
     let links = super::links::detect_urls(&screen_rows);
     if links.is_empty() {
@@ -104,7 +121,7 @@ fn highlight_urls(frame: &mut Frame, area: Rect, screen: &vt100::Screen) {
 
     let inner = Block::default().borders(Borders::ALL).inner(area);
     let link_style = Style::default()
-        .fg(Color::Blue)
+        .fg(theme.link)
         .add_modifier(Modifier::UNDERLINED);
     let buf = frame.buffer_mut();
 
@@ -124,14 +141,216 @@ fn highlight_urls(frame: &mut Frame, area: Rect, screen: &vt100::Screen) {
     }
 }
 
-pub fn render_empty_terminal(frame: &mut Frame, area: Rect) {
+pub struct SearchBarState<'a> {
+    pub query: &'a str,
+    pub cursor: usize,
+    pub editing: bool,
+    /// 1-based index of the current match and total match count.
+    pub position: Option<(usize, usize)>,
+}
+
+/// Post-process the frame buffer to highlight scrollback search matches in
+/// the visible rows. `current` is drawn in the accent colour.
+pub fn highlight_search_matches(
+    frame: &mut Frame,
+    theme: &Theme,
+    area: Rect,
+    parser: &mut vt100::Parser,
+    query: &str,
+    current: Option<SearchMatch>,
+) {
+    let needle = search::fold_query(query);
+    if needle.is_empty() {
+        return;
+    }
+
+    let total = search::scrollback_len(parser);
+    let top = total - parser.screen().scrollback();
+    let screen = parser.screen();
+    let (rows, _) = screen.size();
+
+    let inner = Block::default().borders(Borders::ALL).inner(area);
+    let match_style = Style::default()
+        .fg(theme.inverted_fg)
+        .bg(theme.status_waiting);
+    let current_style = Style::default().fg(theme.inverted_fg).bg(theme.accent);
+    let buf = frame.buffer_mut();
+
+    for row in 0..rows.min(inner.height) {
+        for (start_col, end_col) in search::match_row(screen, row, &needle) {
+            let is_current =
+                current.is_some_and(|m| m.line == top + row as usize && m.start_col == start_col);
+            let style = if is_current {
+                current_style
+            } else {
+                match_style
+            };
+            for col in start_col..end_col.min(inner.width) {
+                let pos = Position::new(inner.x + col, inner.y + row);
+                if let Some(cell) = buf.cell_mut(pos) {
+                    cell.set_style(style);
+                }
+            }
+        }
+    }
+}
+
+/// The bottom row inside the terminal pane border, used for prompt bars.
+fn bottom_bar_area(area: Rect) -> Option<Rect> {
+    let inner = Block::default().borders(Borders::ALL).inner(area);
+    if inner.height == 0 {
+        return None;
+    }
+    Some(Rect::new(
+        inner.x,
+        inner.y + inner.height - 1,
+        inner.width,
+        1,
+    ))
+}
+
+/// Render the search prompt over the bottom row of the terminal pane.
+pub fn render_search_bar(frame: &mut Frame, theme: &Theme, area: Rect, state: &SearchBarState<'_>) {
+    let Some(bar_area) = bottom_bar_area(area) else {
+        return;
+    };
+
+    let mut spans = vec![Span::styled("/", theme.keybind())];
+    if state.editing {
+        let chars: Vec<char> = state.query.chars().collect();
+        let cursor = state.cursor.min(chars.len());
+        let before: String = chars[..cursor].iter().collect();
+        let at = chars.get(cursor).map_or(" ".to_string(), |c| c.to_string());
+        let after: String = chars.get(cursor + 1..).unwrap_or(&[]).iter().collect();
+        spans.push(Span::raw(before));
+        spans.push(Span::styled(at, theme.cursor()));
+        spans.push(Span::raw(after));
+        spans.push(Span::styled("  Enter", theme.keybind()));
+        spans.push(Span::styled(" search  ", theme.keybind_desc()));
+        spans.push(Span::styled("Esc", theme.keybind()));
+        spans.push(Span::styled(" cancel", theme.keybind_desc()));
+    } else {
+        spans.push(Span::raw(state.query.to_string()));
+        match state.position {
+            Some((index, count)) => {
+                spans.push(Span::styled(
+                    format!("  [{index}/{count}]  "),
+                    theme.keybind_desc(),
+                ));
+                spans.push(Span::styled("n", theme.keybind()));
+                spans.push(Span::styled(" older  ", theme.keybind_desc()));
+                spans.push(Span::styled("N", theme.keybind()));
+                spans.push(Span::styled(" newer  ", theme.keybind_desc()));
+            }
+            None => spans.push(Span::styled(
+                "  [no matches]  ",
+                Style::default().fg(theme.status_error),
+            )),
+        }
+        spans.push(Span::styled("Esc", theme.keybind()));
+        spans.push(Span::styled(" close", theme.keybind_desc()));
+    }
+
+    frame.render_widget(Clear, bar_area);
+    frame.render_widget(
+        Paragraph::new(Line::from(spans)).style(Style::default().bg(Color::Reset)),
+        bar_area,
+    );
+}
+
+/// Post-process the frame buffer to draw the copy-mode selection (from
+/// `anchor` to `cursor`) in reverse video.
+pub fn highlight_selection(
+    frame: &mut Frame,
+    area: Rect,
+    parser: &mut vt100::Parser,
+    anchor: CellPos,
+    cursor: CellPos,
+) {
+    let total = search::scrollback_len(parser);
+    let top = total - parser.screen().scrollback();
+    let (rows, cols) = parser.screen().size();
+
+    let inner = Block::default().borders(Borders::ALL).inner(area);
+    let style = Style::default().add_modifier(Modifier::REVERSED);
+    let buf = frame.buffer_mut();
+
+    for row in 0..rows.min(inner.height) {
+        for col in 0..cols.min(inner.width) {
+            let pos = CellPos {
+                line: top + row as usize,
+                col,
+            };
+            if !selection::contains(anchor, cursor, pos) {
+                continue;
+            }
+            if let Some(cell) = buf.cell_mut(Position::new(inner.x + col, inner.y + row)) {
+                cell.set_style(style);
+            }
+        }
+    }
+}
+
+/// Render the copy-mode hint bar over the bottom row of the terminal pane.
+pub fn render_selection_bar(frame: &mut Frame, theme: &Theme, area: Rect) {
+    let Some(bar_area) = bottom_bar_area(area) else {
+        return;
+    };
+
+    let spans = vec![
+        Span::styled(" VISUAL ", theme.focused_title()),
+        Span::raw("  "),
+        Span::styled("h/j/k/l", theme.keybind()),
+        Span::styled(" move  ", theme.keybind_desc()),
+        Span::styled("0/$", theme.keybind()),
+        Span::styled(" line start/end  ", theme.keybind_desc()),
+        Span::styled("y", theme.keybind()),
+        Span::styled(" yank  ", theme.keybind_desc()),
+        Span::styled("Esc", theme.keybind()),
+        Span::styled(" cancel", theme.keybind_desc()),
+    ];
+
+    frame.render_widget(Clear, bar_area);
+    frame.render_widget(
+        Paragraph::new(Line::from(spans)).style(Style::default().bg(Color::Reset)),
+        bar_area,
+    );
+}
+
+/// Inline hint over the frozen output of a session whose pane died.
+pub fn render_dead_session_bar(frame: &mut Frame, theme: &Theme, area: Rect) {
+    let Some(bar_area) = bottom_bar_area(area) else {
+        return;
+    };
+
+    let spans = vec![
+        Span::styled(
+            " session died ",
+            Style::default()
+                .fg(theme.inverted_fg)
+                .bg(theme.status_error)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::raw("  "),
+        Span::styled("Ctrl+R", theme.keybind()),
+        Span::styled(" restart", theme.keybind_desc()),
+    ];
+
+    frame.render_widget(Clear, bar_area);
+    frame.render_widget(
+        Paragraph::new(Line::from(spans)).style(Style::default().bg(Color::Reset)),
+        bar_area,
+    );
+}
+
+pub fn render_empty_terminal(frame: &mut Frame, theme: &Theme, area: Rect) {
     use ratatui::layout::{Alignment, Constraint, Direction, Layout};
     use ratatui::text::{Line, Span};
 
     let block = Block::default()
         .title(" No Session ")
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Theme::TEXT_MUTED));
+        .border_style(Style::default().fg(theme.text_muted));
 
     let inner = block.inner(area);
     frame.render_widget(block, area);
@@ -161,7 +380,7 @@ pub fn render_empty_terminal(frame: &mut Frame, area: Rect) {
 
         let hint_block = Block::default()
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(Theme::BORDER_UNFOCUSED));
+            .border_style(Style::default().fg(theme.border_unfocused));
 
         let hint_inner = hint_block.inner(center);
         frame.render_widget(hint_block, center);
@@ -169,18 +388,32 @@ pub fn render_empty_terminal(frame: &mut Frame, area: Rect) {
         let lines = vec![
             Line::from(Span::styled(
                 "No active sessions",
-                Style::default().fg(Theme::TEXT_SECONDARY),
+                Style::default().fg(theme.text_secondary),
             )),
             Line::from(""),
             Line::from(vec![
-                Span::styled("  Ctrl+N", Theme::keybind()),
-                Span::styled("  New session", Style::default().fg(Theme::TEXT_MUTED)),
+                Span::styled("  Ctrl+N", theme.keybind()),
+                Span::styled("  New session", Style::default().fg(theme.text_muted)),
             ]),
             Line::from(vec![
-                Span::styled("  F1    ", Theme::keybind()),
-                Span::styled("  Help", Style::default().fg(Theme::TEXT_MUTED)),
+                Span::styled("  F1    ", theme.keybind()),
+                Span::styled("  Help", Style::default().fg(theme.text_muted)),
             ]),
         ];
         frame.render_widget(Paragraph::new(lines).alignment(Alignment::Left), hint_inner);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scroll_indicator_reflects_offset() {
+        assert_eq!(
+            scroll_indicator(12).as_deref(),
+            Some(" SCROLLED \u{2191}12 \u{b7} End to return ")
+        );
+        assert_eq!(scroll_indicator(0), None);
+    }
+}
@@ -1,124 +1,368 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
 use ratatui::style::{Color, Modifier, Style};
 
-/// Centralized color and style constants for the Thurbox UI.
+/// Named color slots for the Thurbox UI.
 ///
-/// All widget files reference these constants instead of hard-coding colors,
-/// enabling consistent theming and single-line visual changes.
-pub struct Theme;
-
-impl Theme {
+/// All widget code reads colors from the active theme instead of hard-coding
+/// them, so a `[theme]` table in `config.toml` can restyle the whole UI:
+///
+/// ```toml
+/// [theme]
+/// name = "monochrome"    # built-in base theme (default: "default")
+/// status_busy = "light-green"
+/// accent = "#5fafff"
+/// ```
+///
+/// Slot names are the field names below; values are anything ratatui's
+/// `Color` parses (`red`, `light-blue`, `#rrggbb`, an ANSI index).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Theme {
     // ── Accent ──────────────────────────────────────────────────────────────
-
     /// Primary accent color used for focused borders, selected items, branding.
-    pub const ACCENT: Color = Color::Cyan;
+    pub accent: Color,
 
     // ── Status colors ───────────────────────────────────────────────────────
-
-    pub const STATUS_BUSY: Color = Color::Green;
-    pub const STATUS_WAITING: Color = Color::Yellow;
-    pub const STATUS_IDLE: Color = Color::DarkGray;
-    pub const STATUS_ERROR: Color = Color::Red;
+    pub status_busy: Color,
+    pub status_needs_input: Color,
+    pub status_waiting: Color,
+    pub status_idle: Color,
+    pub status_error: Color,
 
     // ── Text hierarchy ──────────────────────────────────────────────────────
-
-    pub const TEXT_PRIMARY: Color = Color::White;
-    pub const TEXT_SECONDARY: Color = Color::Gray;
-    pub const TEXT_MUTED: Color = Color::DarkGray;
+    pub text_primary: Color,
+    pub text_secondary: Color,
+    pub text_muted: Color,
 
     // ── Borders ─────────────────────────────────────────────────────────────
-
-    pub const BORDER_FOCUSED: Color = Color::Cyan;
-    pub const BORDER_UNFOCUSED: Color = Color::Gray;
+    pub border_focused: Color,
+    pub border_unfocused: Color,
 
     // ── Domain-specific colors ──────────────────────────────────────────────
+    pub role_name: Color,
+    pub admin_badge: Color,
+    pub branch_name: Color,
 
-    pub const ROLE_NAME: Color = Color::Magenta;
-    pub const ADMIN_BADGE: Color = Color::Yellow;
-    pub const BRANCH_NAME: Color = Color::Green;
+    // ── Keybind hints / tool permissions ────────────────────────────────────
+    pub keybind_hint: Color,
+    pub tool_allowed: Color,
+    pub tool_disallowed: Color,
 
-    // ── Keybind hints / tool permissions ─────────────────────────────────────
+    // ── Admin ───────────────────────────────────────────────────────────────
+    pub admin_border: Color,
 
-    pub const KEYBIND_HINT: Color = Color::Yellow;
-    pub const TOOL_ALLOWED: Color = Color::Green;
-    pub const TOOL_DISALLOWED: Color = Color::Red;
+    // ── Danger / destructive ────────────────────────────────────────────────
+    pub danger: Color,
 
-    // ── Admin ─────────────────────────────────────────────────────────────
+    // ── Links ───────────────────────────────────────────────────────────────
+    /// URLs detected in terminal output.
+    pub link: Color,
 
-    pub const ADMIN_BORDER: Color = Color::Yellow;
+    // ── Background colors ───────────────────────────────────────────────────
+    pub inverted_fg: Color,
+    /// Background of the highlighted row in the project and session lists.
+    pub highlight_bg: Color,
+}
 
-    // ── Danger / destructive ────────────────────────────────────────────────
+impl Default for Theme {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
 
-    pub const DANGER: Color = Color::Red;
+/// Error produced while building a [`Theme`] from the `[theme]` table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ThemeError {
+    UnknownTheme(String),
+    UnknownSlot(String),
+    InvalidColor { slot: String, value: String },
+}
 
-    // ── Background colors ───────────────────────────────────────────────────
+impl fmt::Display for ThemeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownTheme(name) => write!(
+                f,
+                "Unknown theme '{name}' (built-in: {})",
+                Theme::BUILTIN_NAMES.join(", ")
+            ),
+            Self::UnknownSlot(slot) => write!(f, "Unknown theme color '{slot}'"),
+            Self::InvalidColor { slot, value } => {
+                write!(f, "Invalid color '{value}' for theme.{slot}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ThemeError {}
+
+impl Theme {
+    /// Today's palette: cyan accent, green/yellow/red status.
+    pub const DEFAULT: Theme = Theme {
+        accent: Color::Cyan,
+        status_busy: Color::Green,
+        status_needs_input: Color::LightMagenta,
+        status_waiting: Color::Yellow,
+        status_idle: Color::DarkGray,
+        status_error: Color::Red,
+        text_primary: Color::White,
+        text_secondary: Color::Gray,
+        text_muted: Color::DarkGray,
+        border_focused: Color::Cyan,
+        border_unfocused: Color::Gray,
+        role_name: Color::Magenta,
+        admin_badge: Color::Yellow,
+        branch_name: Color::Green,
+        keybind_hint: Color::Yellow,
+        tool_allowed: Color::Green,
+        tool_disallowed: Color::Red,
+        admin_border: Color::Yellow,
+        danger: Color::Red,
+        link: Color::Blue,
+        inverted_fg: Color::Black,
+        highlight_bg: Color::DarkGray,
+    };
+
+    /// Grayscale only, for terminals or users that avoid color. Status is
+    /// still told apart by the indicator glyphs.
+    pub const MONOCHROME: Theme = Theme {
+        accent: Color::White,
+        status_busy: Color::White,
+        status_needs_input: Color::White,
+        status_waiting: Color::Gray,
+        status_idle: Color::DarkGray,
+        status_error: Color::White,
+        text_primary: Color::White,
+        text_secondary: Color::Gray,
+        text_muted: Color::DarkGray,
+        border_focused: Color::White,
+        border_unfocused: Color::DarkGray,
+        role_name: Color::Gray,
+        admin_badge: Color::White,
+        branch_name: Color::Gray,
+        keybind_hint: Color::White,
+        tool_allowed: Color::White,
+        tool_disallowed: Color::DarkGray,
+        admin_border: Color::Gray,
+        danger: Color::White,
+        link: Color::White,
+        inverted_fg: Color::Black,
+        highlight_bg: Color::DarkGray,
+    };
 
-    pub const INVERTED_FG: Color = Color::Black;
+    /// Names accepted by [`Theme::builtin`].
+    pub const BUILTIN_NAMES: [&'static str; 2] = ["default", "monochrome"];
+
+    /// Look up a built-in theme by name.
+    pub fn builtin(name: &str) -> Option<Theme> {
+        match name {
+            "default" => Some(Self::DEFAULT),
+            "monochrome" => Some(Self::MONOCHROME),
+            _ => None,
+        }
+    }
+
+    /// Build a theme from the built-in `name` (default when `None`) with
+    /// per-slot color overrides applied on top.
+    pub fn from_config(
+        name: Option<&str>,
+        colors: &HashMap<String, String>,
+    ) -> Result<Theme, ThemeError> {
+        let mut theme = match name {
+            Some(name) => {
+                Self::builtin(name).ok_or_else(|| ThemeError::UnknownTheme(name.to_string()))?
+            }
+            None => Self::DEFAULT,
+        };
+
+        for (slot, value) in colors {
+            let color = Color::from_str(value).map_err(|_| ThemeError::InvalidColor {
+                slot: slot.clone(),
+                value: value.clone(),
+            })?;
+            let target = theme
+                .slot_mut(slot)
+                .ok_or_else(|| ThemeError::UnknownSlot(slot.clone()))?;
+            *target = color;
+        }
+
+        Ok(theme)
+    }
+
+    fn slot_mut(&mut self, slot: &str) -> Option<&mut Color> {
+        Some(match slot {
+            "accent" => &mut self.accent,
+            "status_busy" => &mut self.status_busy,
+            "status_needs_input" => &mut self.status_needs_input,
+            "status_waiting" => &mut self.status_waiting,
+            "status_idle" => &mut self.status_idle,
+            "status_error" => &mut self.status_error,
+            "text_primary" => &mut self.text_primary,
+            "text_secondary" => &mut self.text_secondary,
+            "text_muted" => &mut self.text_muted,
+            "border_focused" => &mut self.border_focused,
+            "border_unfocused" => &mut self.border_unfocused,
+            "role_name" => &mut self.role_name,
+            "admin_badge" => &mut self.admin_badge,
+            "branch_name" => &mut self.branch_name,
+            "keybind_hint" => &mut self.keybind_hint,
+            "tool_allowed" => &mut self.tool_allowed,
+            "tool_disallowed" => &mut self.tool_disallowed,
+            "admin_border" => &mut self.admin_border,
+            "danger" => &mut self.danger,
+            "link" => &mut self.link,
+            "inverted_fg" => &mut self.inverted_fg,
+            "highlight_bg" => &mut self.highlight_bg,
+            _ => return None,
+        })
+    }
 
     // ── Composite styles ────────────────────────────────────────────────────
 
-    /// Style for a focused panel/modal title: bold black on accent background.
-    pub fn focused_title() -> Style {
+    /// Style for a focused panel/modal title: bold inverted text on accent.
+    pub fn focused_title(&self) -> Style {
         Style::default()
-            .fg(Self::INVERTED_FG)
-            .bg(Self::ACCENT)
+            .fg(self.inverted_fg)
+            .bg(self.accent)
             .add_modifier(Modifier::BOLD)
     }
 
     /// Style for an unfocused panel title: dimmed secondary text.
-    pub fn unfocused_title() -> Style {
-        Style::default().fg(Self::BORDER_UNFOCUSED)
+    pub fn unfocused_title(&self) -> Style {
+        Style::default().fg(self.border_unfocused)
     }
 
     /// Style for section headers (e.g. info panel sections, help overlay).
-    pub fn section_header() -> Style {
+    pub fn section_header(&self) -> Style {
         Style::default()
-            .fg(Self::ACCENT)
+            .fg(self.accent)
             .add_modifier(Modifier::BOLD)
     }
 
     /// Style for labels in info panels and status displays.
-    pub fn label() -> Style {
-        Style::default().fg(Self::TEXT_MUTED)
+    pub fn label(&self) -> Style {
+        Style::default().fg(self.text_muted)
     }
 
     /// Style for keybind hint keys in modal footers.
-    pub fn keybind() -> Style {
-        Style::default().fg(Self::KEYBIND_HINT)
+    pub fn keybind(&self) -> Style {
+        Style::default().fg(self.keybind_hint)
     }
 
     /// Style for keybind descriptions in modal footers.
-    pub fn keybind_desc() -> Style {
-        Style::default().fg(Self::TEXT_MUTED)
+    pub fn keybind_desc(&self) -> Style {
+        Style::default().fg(self.text_muted)
     }
 
     /// Style for selected/active list items.
-    pub fn selected_item() -> Style {
+    pub fn selected_item(&self) -> Style {
         Style::default()
-            .fg(Self::ACCENT)
+            .fg(self.accent)
             .add_modifier(Modifier::BOLD)
     }
 
     /// Style for normal (unselected) list items.
-    pub fn normal_item() -> Style {
-        Style::default().fg(Self::TEXT_PRIMARY)
+    pub fn normal_item(&self) -> Style {
+        Style::default().fg(self.text_primary)
     }
 
-    /// Style for admin section title: yellow bold.
-    pub fn admin_title() -> Style {
+    /// Style for admin section title: admin border color, bold.
+    pub fn admin_title(&self) -> Style {
         Style::default()
-            .fg(Self::ADMIN_BORDER)
+            .fg(self.admin_border)
             .add_modifier(Modifier::BOLD)
     }
 
     /// Style for project metadata lines (repo info, role count).
-    pub fn project_meta() -> Style {
-        Style::default().fg(Self::TEXT_MUTED)
+    pub fn project_meta(&self) -> Style {
+        Style::default().fg(self.text_muted)
     }
 
-    /// Style for the block cursor in text fields.
-    pub fn cursor() -> Style {
+    /// Style for the highlighted row of the project and session lists.
+    pub fn list_highlight(&self) -> Style {
         Style::default()
-            .fg(Self::INVERTED_FG)
-            .bg(Self::TEXT_PRIMARY)
+            .bg(self.highlight_bg)
+            .add_modifier(Modifier::BOLD)
+    }
+
+    /// Style for the block cursor in text fields.
+    pub fn cursor(&self) -> Style {
+        Style::default().fg(self.inverted_fg).bg(self.text_primary)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn colors(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn empty_config_is_default_palette() {
+        let theme = Theme::from_config(None, &HashMap::new()).unwrap();
+        assert_eq!(theme, Theme::default());
+        assert_eq!(theme.accent, Color::Cyan);
+        assert_eq!(theme.status_busy, Color::Green);
+    }
+
+    #[test]
+    fn builtin_names_resolve() {
+        for name in Theme::BUILTIN_NAMES {
+            assert!(Theme::builtin(name).is_some(), "{name}");
+        }
+        assert_eq!(
+            Theme::from_config(Some("monochrome"), &HashMap::new()).unwrap(),
+            Theme::MONOCHROME
+        );
+    }
+
+    #[test]
+    fn overrides_apply_on_top_of_base() {
+        let theme = Theme::from_config(
+            Some("monochrome"),
+            &colors(&[("status_busy", "light-green"), ("accent", "#5fafff")]),
+        )
+        .unwrap();
+        assert_eq!(theme.status_busy, Color::LightGreen);
+        assert_eq!(theme.accent, Color::Rgb(0x5f, 0xaf, 0xff));
+        assert_eq!(theme.status_waiting, Theme::MONOCHROME.status_waiting);
+    }
+
+    #[test]
+    fn unknown_theme_is_rejected() {
+        let err = Theme::from_config(Some("solarized"), &HashMap::new()).unwrap_err();
+        assert_eq!(err, ThemeError::UnknownTheme("solarized".to_string()));
+        assert!(err.to_string().contains("default, monochrome"));
+    }
+
+    #[test]
+    fn unknown_slot_is_rejected() {
+        let err = Theme::from_config(None, &colors(&[("status_bsy", "red")])).unwrap_err();
+        assert_eq!(err, ThemeError::UnknownSlot("status_bsy".to_string()));
+    }
+
+    #[test]
+    fn invalid_color_is_rejected() {
+        let err = Theme::from_config(None, &colors(&[("accent", "not-a-color")])).unwrap_err();
+        assert!(matches!(err, ThemeError::InvalidColor { .. }));
+    }
+
+    #[test]
+    fn composite_styles_follow_slots() {
+        let theme = Theme {
+            keybind_hint: Color::Blue,
+            ..Theme::DEFAULT
+        };
+        assert_eq!(theme.keybind().fg, Some(Color::Blue));
+
+        let theme = Theme::from_config(None, &colors(&[("highlight_bg", "blue")])).unwrap();
+        assert_eq!(theme.list_highlight().bg, Some(Color::Blue));
     }
 }
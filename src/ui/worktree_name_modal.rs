@@ -13,9 +13,11 @@ pub struct WorktreeNameState<'a> {
     pub name: &'a str,
     pub cursor: usize,
     pub base_branch: &'a str,
+    /// Validation error for the typed name, if any.
+    pub error: Option<&'a str>,
 }
 
-pub fn render_worktree_name_modal(frame: &mut Frame, state: &WorktreeNameState<'_>) {
+pub fn render_worktree_name_modal(frame: &mut Frame, theme: &Theme, state: &WorktreeNameState<'_>) {
     let area = centered_fixed_height_rect(50, 8, frame.area());
 
     frame.render_widget(Clear, area);
@@ -23,7 +25,7 @@ pub fn render_worktree_name_modal(frame: &mut Frame, state: &WorktreeNameState<'
     let block = Block::default()
         .title(format!(" New Branch (from {}) ", state.base_branch))
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Theme::ACCENT));
+        .border_style(Style::default().fg(theme.accent));
 
     let inner = block.inner(area);
     frame.render_widget(block, area);
@@ -38,6 +40,7 @@ pub fn render_worktree_name_modal(frame: &mut Frame, state: &WorktreeNameState<'
 
     super::render_text_field(
         frame,
+        theme,
         chunks[0],
         "Branch Name",
         state.name,
@@ -45,11 +48,18 @@ pub fn render_worktree_name_modal(frame: &mut Frame, state: &WorktreeNameState<'
         true,
     );
 
-    let footer = Line::from(vec![
-        Span::styled("Enter", Theme::keybind()),
-        Span::styled(" confirm  ", Theme::keybind_desc()),
-        Span::styled("Esc", Theme::keybind()),
-        Span::styled(" cancel", Theme::keybind_desc()),
-    ]);
+    let mut footer = Vec::new();
+    if let Some(error) = state.error {
+        footer.push(Line::from(Span::styled(
+            error,
+            Style::default().fg(theme.danger),
+        )));
+    }
+    footer.push(Line::from(vec![
+        Span::styled("Enter", theme.keybind()),
+        Span::styled(" confirm  ", theme.keybind_desc()),
+        Span::styled("Esc", theme.keybind()),
+        Span::styled(" cancel", theme.keybind_desc()),
+    ]));
     frame.render_widget(Paragraph::new(footer), chunks[1]);
 }
@@ -4,7 +4,7 @@
 /// database file and verify that session changes are properly shared.
 use std::path::PathBuf;
 
-use thurbox::project::{ProjectConfig, ProjectId};
+use thurbox::project::{ProjectConfig, ProjectId, SyncStrategy};
 use thurbox::session::{RoleConfig, RolePermissions, SessionId};
 use thurbox::storage::Database;
 use thurbox::sync::{self, SharedSession, SharedState, SharedWorktree};
@@ -23,6 +23,10 @@ fn make_session(id: SessionId, name: &str, project_id: ProjectId) -> SharedSessi
         additional_dirs: Vec::new(),
         worktrees: Vec::new(),
         shell_backend_id: None,
+        is_pinned: false,
+        label: None,
+        label_color: None,
+        created_at: None,
         tombstone: false,
         tombstone_at: None,
     }
@@ -34,6 +38,13 @@ fn test_project_id(name: &str) -> ProjectId {
         repos: vec![],
         roles: vec![],
         mcp_servers: vec![],
+        sync_base_branch: None,
+        remote: None,
+        sync_strategy: SyncStrategy::default(),
+        activity_timeout_ms: None,
+        worktree_root: None,
+        default_role: None,
+        session_init_command: None,
         id: None,
     };
     config.deterministic_id()
@@ -337,6 +348,7 @@ fn db_worktree_persisted_with_session() {
         repo_path: PathBuf::from("/repo"),
         worktree_path: PathBuf::from("/repo/.git/wt/feat"),
         branch: "feat".to_string(),
+        base_branch: "main".to_string(),
     }];
     db.upsert_session(&session).unwrap();
 
@@ -359,11 +371,13 @@ fn db_multi_worktree_persisted_and_loaded_via_sessions() {
             repo_path: PathBuf::from("/repo1"),
             worktree_path: PathBuf::from("/repo1/.git/wt/feat"),
             branch: "feat".to_string(),
+            base_branch: "main".to_string(),
         },
         SharedWorktree {
             repo_path: PathBuf::from("/repo2"),
             worktree_path: PathBuf::from("/repo2/.git/wt/feat"),
             branch: "feat".to_string(),
+            base_branch: "main".to_string(),
         },
     ];
     db.upsert_session(&session).unwrap();
@@ -398,11 +412,13 @@ fn db_multi_worktree_propagates_across_instances() {
             repo_path: PathBuf::from("/repo1"),
             worktree_path: PathBuf::from("/repo1/.git/wt/feat"),
             branch: "feat".to_string(),
+            base_branch: "main".to_string(),
         },
         SharedWorktree {
             repo_path: PathBuf::from("/repo2"),
             worktree_path: PathBuf::from("/repo2/.git/wt/feat"),
             branch: "feat".to_string(),
+            base_branch: "main".to_string(),
         },
     ];
     db_a.upsert_session(&session).unwrap();
@@ -437,6 +453,10 @@ fn db_session_metadata_preserved_across_instances() {
         additional_dirs: Vec::new(),
         worktrees: Vec::new(),
         shell_backend_id: None,
+        is_pinned: false,
+        label: None,
+        label_color: None,
+        created_at: None,
         tombstone: false,
         tombstone_at: None,
     };